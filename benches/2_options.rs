@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate bencher;
+use bencher::{black_box, Bencher};
+use clapi::{Argument, Command, CommandOption, Context, Parser};
+
+const OPTION_COUNT: usize = 200;
+
+fn command_with_options() -> Command {
+    let mut command = Command::new("App")
+        .option(CommandOption::new("global").global(true));
+
+    for i in 0..OPTION_COUNT {
+        command = command.option(
+            CommandOption::new(format!("option{}", i))
+                .alias(format!("o{}", i))
+                .arg(Argument::new()),
+        );
+    }
+
+    command.subcommand(Command::new("test"))
+}
+
+// Baseline: linear scan over the options by name and alias
+fn find_last_option_linear(b: &mut Bencher) {
+    let command = command_with_options();
+    let name = format!("o{}", OPTION_COUNT - 1);
+
+    b.iter(|| {
+        black_box(command.get_options().get(&name).unwrap());
+    })
+}
+
+fn parse_last_option(b: &mut Bencher) {
+    let context = Context::new(command_with_options());
+    let args = vec![format!("-o{}", OPTION_COUNT - 1), "value".to_owned()];
+
+    b.iter(|| {
+        let mut parser = Parser::new(&context);
+        black_box(parser.parse(args.clone()).unwrap());
+    })
+}
+
+fn parse_all_options(b: &mut Bencher) {
+    let context = Context::new(command_with_options());
+    let args = (0..OPTION_COUNT)
+        .flat_map(|i| vec![format!("--option{}", i), i.to_string()])
+        .collect::<Vec<String>>();
+
+    b.iter(|| {
+        let mut parser = Parser::new(&context);
+        black_box(parser.parse(args.clone()).unwrap());
+    })
+}
+
+fn parse_inherited_global_option(b: &mut Bencher) {
+    let context = Context::new(command_with_options());
+    let args = vec!["test", "--global"];
+
+    b.iter(|| {
+        let mut parser = Parser::new(&context);
+        black_box(parser.parse(args.clone()).unwrap());
+    })
+}
+
+benchmark_group!(
+    benches,
+    find_last_option_linear,
+    parse_last_option,
+    parse_all_options,
+    parse_inherited_global_option
+);
+
+benchmark_main!(benches);
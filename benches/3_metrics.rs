@@ -0,0 +1,47 @@
+#[macro_use]
+extern crate bencher;
+use bencher::{black_box, Bencher};
+use clapi::{Argument, Command, CommandOption, Context, Parser};
+
+const OPTION_COUNT: usize = 50;
+
+fn command_with_options() -> Command {
+    let mut command = Command::new("App");
+
+    for i in 0..OPTION_COUNT {
+        command = command.option(CommandOption::new(format!("option{}", i)).arg(Argument::new()));
+    }
+
+    command
+}
+
+fn args() -> Vec<String> {
+    (0..OPTION_COUNT)
+        .flat_map(|i| vec![format!("--option{}", i), i.to_string()])
+        .collect()
+}
+
+// Baseline: the clock is never read
+fn parse_metrics_disabled(b: &mut Bencher) {
+    let context = Context::new(command_with_options());
+    let args = args();
+
+    b.iter(|| {
+        let mut parser = Parser::new(&context);
+        black_box(parser.parse(args.clone()).unwrap());
+    })
+}
+
+fn parse_metrics_enabled(b: &mut Bencher) {
+    let context = Context::builder(command_with_options()).metrics(true).build();
+    let args = args();
+
+    b.iter(|| {
+        let mut parser = Parser::new(&context);
+        black_box(parser.parse(args.clone()).unwrap());
+    })
+}
+
+benchmark_group!(benches, parse_metrics_disabled, parse_metrics_enabled);
+
+benchmark_main!(benches);
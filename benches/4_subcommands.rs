@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate bencher;
+use bencher::{black_box, Bencher};
+use clapi::{Argument, Command, CommandOption, Context};
+
+const SUBCOMMAND_COUNT: usize = 300;
+
+fn command_with_subcommands() -> Command {
+    let mut command = Command::new("App")
+        .description("A sample app")
+        .option(CommandOption::new("verbose").alias("v").description("Shows more output"))
+        .arg(Argument::zero_or_more("values"));
+
+    for i in 0..SUBCOMMAND_COUNT {
+        command = command.subcommand(
+            Command::new(format!("command{}", i))
+                .alias(format!("c{}", i))
+                .description(format!("Runs the command {}", i))
+                .option(CommandOption::new("force")),
+        );
+    }
+
+    command
+}
+
+// Baseline: the cached usage is discarded before each render
+fn usage_uncached(b: &mut Bencher) {
+    let context = Context::new(command_with_subcommands());
+    let mut command = command_with_subcommands();
+
+    b.iter(|| {
+        command.get_subcommands_mut();
+        let mut buf = String::new();
+        clapi::help::command_usage(&mut buf, &context, &command, false);
+        black_box(buf)
+    })
+}
+
+fn usage_cached(b: &mut Bencher) {
+    let context = Context::new(command_with_subcommands());
+
+    b.iter(|| {
+        let mut buf = String::new();
+        clapi::help::command_usage(&mut buf, &context, context.root(), false);
+        black_box(buf)
+    })
+}
+
+fn help_with_subcommands(b: &mut Bencher) {
+    let context = Context::new(command_with_subcommands());
+
+    b.iter(|| {
+        let mut buf = String::new();
+        clapi::help::command_help(&mut buf, &context, context.root(), false);
+        black_box(buf)
+    })
+}
+
+benchmark_group!(benches, usage_uncached, usage_cached, help_with_subcommands);
+benchmark_main!(benches);
@@ -35,9 +35,12 @@ macro_rules! crate_version {
 ///
 /// You use the `@subcommand`, `@option` and `@arg` tags to create subcommand, option and args
 /// respectively. A list of the tags and its properties:
-/// - `@subcommand` : description, usage, help, handler, hidden, @subcommand, @option and @arg.
+/// - `@subcommand` : description, summary, usage, help, handler, hidden, @subcommand, @option and @arg.
 /// - `@option` : description, alias, required, multiple, requires_assign and @arg.
-/// - `@arg` : description, values, default, count, validator and type,
+/// - `@arg` : description, values, default, count, validator, type and allow_empty,
+///
+/// The `values` and `default` properties take a list of literals or expressions of any type
+/// that implements `Display`, for example: `(default => 1, 2, MAX_VALUE)`.
 ///
 /// # Usage
 /// To create the app start with:
@@ -128,6 +131,14 @@ macro_rules! app {
         }
     };
 
+    // Command `summary`:
+    // clapi::app! { MyApp => (summary => ... ) }
+    (@command ($builder:expr) (summary => $summary:expr) $($tt:tt)*) => {
+        $crate::app!{
+            @command ($builder.summary($summary)) $($tt)*
+        }
+    };
+
     // Command `usage`:
     // clapi::app! { MyApp => (usage => ... ) }
     (@command ($builder:expr) (usage => $usage:expr) $($tt:tt)*) => {
@@ -182,17 +193,24 @@ macro_rules! app {
         }
     };
 
+    // The handlers with typed options and arguments capture the name of the command,
+    // which is used in the conversion errors of the variables.
+
     // Command handler with typed arguments with a block.
     // clapi::app! { MyApp => (handler (...argument : type) => { ... } ) }
     (@command ($builder:expr) (handler (...$($arg_name:ident: $arg_type:ty),+) => $block:block) $($tt:tt)*) => {
         $crate::app!{
-            @command ($builder.handler(|options, arguments|{
-                $(
-                    let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, $arg_name: $arg_type);
-                )+
-                $block
-                Ok(())
-            })) $($tt)*
+            @command ({
+                let command = $builder;
+                let command_name = command.get_name().to_owned();
+                command.handler(move |options, arguments|{
+                    $(
+                        let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, command_name, $arg_name: $arg_type);
+                    )+
+                    $block
+                    Ok(())
+                })
+            }) $($tt)*
         }
     };
 
@@ -200,13 +218,17 @@ macro_rules! app {
     // clapi::app! { MyApp => (handler (...argument : type) => ... ) }
     (@command ($builder:expr) (handler (...$($arg_name:ident: $arg_type:ty),+) => $expr:expr) $($tt:tt)*) => {
         $crate::app!{
-            @command ($builder.handler(|options, arguments|{
-                $(
-                    let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, $arg_name: $arg_type);
-                )+
-                $expr;
-                Ok(())
-            })) $($tt)*
+            @command ({
+                let command = $builder;
+                let command_name = command.get_name().to_owned();
+                command.handler(move |options, arguments|{
+                    $(
+                        let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, command_name, $arg_name: $arg_type);
+                    )+
+                    $expr;
+                    Ok(())
+                })
+            }) $($tt)*
         }
     };
 
@@ -214,22 +236,26 @@ macro_rules! app {
     // clapi::app! { MyApp => (handler (option : type, ...argument : type) => { ... } ) }
     (@command ($builder:expr) (handler ($($name:ident : $ty:ty),+ $(,...$($arg_name:ident: $arg_type:ty),+)?) => $block:block) $($tt:tt)*) => {
         $crate::app!{
-            @command ($builder.handler(|options, arguments|{
-                #[cfg(debug_assertions)]
-                #[allow(unused_variables)]
-                fn assert_non_duplicate_arguments($($name: $ty),+ $(,$($arg_name: $arg_type),+)?){}
+            @command ({
+                let command = $builder;
+                let command_name = command.get_name().to_owned();
+                command.handler(move |options, arguments|{
+                    #[cfg(debug_assertions)]
+                    #[allow(unused_variables)]
+                    fn assert_non_duplicate_arguments($($name: $ty),+ $(,$($arg_name: $arg_type),+)?){}
 
-                $(
-                    let $name : $ty = $crate::declare_option_var!(options, $name: $ty);
-                )+
-                $(
                     $(
-                        let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, $arg_name: $arg_type);
+                        let $name : $ty = $crate::declare_option_var!(options, command_name, $name: $ty);
                     )+
-                )?
-                $block
-                Ok(())
-            })) $($tt)*
+                    $(
+                        $(
+                            let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, command_name, $arg_name: $arg_type);
+                        )+
+                    )?
+                    $block
+                    Ok(())
+                })
+            }) $($tt)*
         }
     };
 
@@ -237,22 +263,26 @@ macro_rules! app {
     // clapi::app! { MyApp => (handler (option : type, ...argument : type) => ... ) }
     (@command ($builder:expr) (handler ($($name:ident : $ty:ty),+ $(,...$($arg_name:ident: $arg_type:ty),+)?) => $expr:expr) $($tt:tt)*) => {
         $crate::app!{
-            @command ($builder.handler(|options, arguments|{
-                #[cfg(debug_assertions)]
-                #[allow(unused_variables)]
-                fn assert_non_duplicate_arguments($($name: $ty),+ $(,$($arg_name: $arg_type),+)?){}
+            @command ({
+                let command = $builder;
+                let command_name = command.get_name().to_owned();
+                command.handler(move |options, arguments|{
+                    #[cfg(debug_assertions)]
+                    #[allow(unused_variables)]
+                    fn assert_non_duplicate_arguments($($name: $ty),+ $(,$($arg_name: $arg_type),+)?){}
 
-                $(
-                    let $name : $ty = $crate::declare_option_var!(options, $name: $ty);
-                )+
-                $(
                     $(
-                        let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, $arg_name: $arg_type);
+                        let $name : $ty = $crate::declare_option_var!(options, command_name, $name: $ty);
                     )+
-                )?
-                $expr;
-                Ok(())
-            })) $($tt)*
+                    $(
+                        $(
+                            let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, command_name, $arg_name: $arg_type);
+                        )+
+                    )?
+                    $expr;
+                    Ok(())
+                })
+            }) $($tt)*
         }
     };
 
@@ -479,20 +509,22 @@ macro_rules! app {
         }
     };
 
-    // Argument valid values
-    // clapi::app! { (@arg => (values => ... ) }
+    // Argument valid values, the values can be of different types
+    // clapi::app! { (@arg => (values => "auto", 1, true) }
     (@arg ($arg_builder:expr) (values => $($valid_values:expr),+) $($tt:tt)*) => {
         $crate::app!{
-            @arg ($arg_builder.valid_values(&[$($valid_values),+])) $($tt)*
+            @arg ($arg_builder.valid_values([$(($valid_values).to_string()),+])) $($tt)*
         }
     };
 
-    // Argument default values
-    // clapi::app! { (@arg => (default => ... ) }
+    // Argument default values, these are set after all the other properties
+    // so are checked against the `count`, `values` and `validator` of the argument
+    // clapi::app! { (@arg => (default => 1, 2, 3) }
     (@arg ($arg_builder:expr) (default => $($default_values:expr),+) $($tt:tt)*) => {
         $crate::app!{
-            @arg ($arg_builder.defaults(&[$($default_values),+])) $($tt)*
+            @arg ($arg_builder) $($tt)*
         }
+        .defaults([$(($default_values).to_string()),+])
     };
 
     // Argument validator
@@ -519,6 +551,14 @@ macro_rules! app {
         }
     };
 
+    // Argument allow empty values
+    // clapi::app! { (@arg => (allow_empty => false) }
+    (@arg ($arg_builder:expr) (allow_empty => $allow:expr) $($tt:tt)*) => {
+        $crate::app!{
+            @arg ($arg_builder.allow_empty_values($allow)) $($tt)*
+        }
+    };
+
     //////////////////////////////////////////////////////////////////////
     // Some special cases to only create `Command` and not `CommandLine`//
     //////////////////////////////////////////////////////////////////////
@@ -590,8 +630,7 @@ macro_rules! crate_app {
                         .description($crate::package_description!())
                         .subcommand(Command::new("version")
                             .handler(|_, _| {
-                                println!("{}", $crate::package_version!());
-                                Ok(())
+                                $crate::utils::write_line(&mut std::io::stdout(), $crate::package_version!())
                             })
                         )
                 ) $($rest)*
@@ -612,8 +651,7 @@ macro_rules! run_crate_app {
                         .description($crate::package_description!())
                         .subcommand(Command::new("version")
                             .handler(|_, _| {
-                                println!("{}", $crate::package_version!());
-                                Ok(())
+                                $crate::utils::write_line(&mut std::io::stdout(), $crate::package_version!())
                             })
                         )
                 ) $($rest)*
@@ -624,3 +662,65 @@ macro_rules! run_crate_app {
         .run()
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    const MAX_COUNT: usize = 10;
+
+    #[test]
+    fn app_arg_default_values_test() {
+        let app = crate::app! { MyApp =>
+            (@arg numbers =>
+                (count => 1..)
+                (default => 1, 2, MAX_COUNT * 2)
+            )
+            (@option mode =>
+                (@arg mode =>
+                    (default => true)
+                )
+            )
+            (@subcommand run =>
+                (@arg "values" =>
+                    (default => "a", 1, false)
+                    (count => 3)
+                )
+            )
+        };
+
+        let root = app.root();
+        assert_eq!(root.get_arg().unwrap().get_default_values(), &["1", "2", "20"]);
+
+        let mode = root.get_options().get("mode").unwrap().get_arg().unwrap();
+        assert_eq!(mode.get_default_values(), &["true"]);
+
+        let run = root.find_subcommand("run").unwrap();
+        assert_eq!(run.get_arg().unwrap().get_default_values(), &["a", "1", "false"]);
+    }
+
+    #[test]
+    fn app_arg_valid_values_test() {
+        let mut app = crate::app! { MyApp =>
+            (@arg value =>
+                (default => 1)
+                (values => "auto", 1, MAX_COUNT, true)
+            )
+        };
+
+        let arg = app.root().get_arg().unwrap();
+        assert_eq!(arg.get_valid_values(), &["auto", "1", "10", "true"]);
+        assert_eq!(arg.get_default_values(), &["1"]);
+
+        assert!(app.parse_from(vec!["true"]).is_ok());
+        assert!(app.parse_from(vec!["never"]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "argument `value` expects 1 value but 3 default values were declared")]
+    fn app_arg_invalid_default_count_test() {
+        crate::app! { MyApp =>
+            (@arg value =>
+                (default => 1, 2, 3)
+            )
+        };
+    }
+}
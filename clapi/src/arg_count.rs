@@ -7,6 +7,17 @@ use std::ops::{
 
 /**
 Represents the number of values an argument takes.
+
+An `ArgCount` can be created from an integer or any of the std range types:
+
+- `n` takes exactly `n` values.
+- `..` takes any number of values.
+- `a..` takes `a` or more values.
+- `a..=b` and `..=b` take up to `b` values.
+- `a..b` and `..b` take up to `b - 1` values, the exclusive upper bound is decremented.
+- `(Bound<usize>, Bound<usize>)` follows the same rules, an excluded start is incremented.
+
+Conversions panic if the range is empty or contains negative values.
 */
 #[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq)]
 pub struct ArgCount {
@@ -97,18 +108,71 @@ impl ArgCount {
         unsafe { Self::new_unchecked(Some(count), Some(count)) }
     }
 
-    /// Constructs a new `ArgCount` for more than the specified number of values.
+    /// Constructs a new `ArgCount` for the specified number of values or more.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::ArgCount;
+    ///
+    /// let count = ArgCount::at_least(2);
+    /// assert!(!count.takes(1));
+    /// assert!(count.takes(2));
+    /// assert!(count.takes(usize::MAX));
+    /// ```
     #[inline]
-    pub fn more_than(min: usize) -> Self {
+    pub const fn at_least(min: usize) -> Self {
         unsafe { Self::new_unchecked(Some(min), None) }
     }
 
-    /// Constructs a new `ArgCount` for less than the specified number of values.
+    /// Constructs a new `ArgCount` for the specified number of values or less.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::ArgCount;
+    ///
+    /// let count = ArgCount::at_most(2);
+    /// assert!(count.takes(0));
+    /// assert!(count.takes(2));
+    /// assert!(!count.takes(3));
+    /// ```
     #[inline]
-    pub fn less_than(max: usize) -> Self {
+    pub const fn at_most(max: usize) -> Self {
         unsafe { Self::new_unchecked(None, Some(max)) }
     }
 
+    /// Constructs a new `ArgCount` for `min` to `max` values, both inclusive.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::ArgCount;
+    ///
+    /// let count = ArgCount::between(1, 3);
+    /// assert!(count.takes(1));
+    /// assert!(count.takes(3));
+    /// assert!(!count.takes(4));
+    /// ```
+    ///
+    /// # Panics
+    /// If min > max
+    #[inline]
+    pub fn between(min: usize, max: usize) -> Self {
+        Self::new_bounded(min, max)
+    }
+
+    /// Constructs a new `ArgCount` for the specified number of values or more,
+    /// this is the same as `ArgCount::at_least`.
+    #[inline]
+    pub fn more_than(min: usize) -> Self {
+        Self::at_least(min)
+    }
+
+    /// Constructs a new `ArgCount` for the specified number of values or less,
+    /// this is the same as `ArgCount::at_most`.
+    #[inline]
+    pub fn less_than(max: usize) -> Self {
+        Self::at_most(max)
+    }
+
     /// Returns the min number of values if bounded or `usize::MIN` if unbounded.
     #[inline]
     pub const fn min_or_default(&self) -> usize {
@@ -209,6 +273,28 @@ impl From<RangeFull> for ArgCount {
     }
 }
 
+impl From<(Bound<usize>, Bound<usize>)> for ArgCount {
+    fn from((start, end): (Bound<usize>, Bound<usize>)) -> Self {
+        let min = match start {
+            Bound::Included(n) => Some(n),
+            Bound::Excluded(n) => Some(n.checked_add(1).expect("empty range")),
+            Bound::Unbounded => None,
+        };
+
+        let max = match end {
+            Bound::Included(n) => Some(n),
+            Bound::Excluded(n) => Some(n.checked_sub(1).expect("empty range")),
+            Bound::Unbounded => None,
+        };
+
+        if let (Some(min), Some(max)) = (min, max) {
+            assert!(min <= max, "empty range");
+        }
+
+        ArgCount::new(min, max)
+    }
+}
+
 impl RangeBounds<usize> for ArgCount {
     fn start_bound(&self) -> Bound<&usize> {
         match self.min {
@@ -244,6 +330,7 @@ macro_rules! impl_value_count_from_unsigned_int {
 
             impl From<Range<$target>> for ArgCount {
                 fn from(value: Range<$target>) -> Self {
+                    assert!(value.start < value.end, "empty range: `{:?}`", value);
                     let start = value.start;
                     let end = value.end.sub(1);
                     ArgCount::new_bounded(start as usize, end as usize)
@@ -253,21 +340,22 @@ macro_rules! impl_value_count_from_unsigned_int {
             impl From<RangeFrom<$target>> for ArgCount {
                 fn from(value: RangeFrom<$target>) -> Self {
                     let start = value.start;
-                    ArgCount::more_than(start as usize)
+                    ArgCount::at_least(start as usize)
                 }
             }
 
             impl From<RangeTo<$target>> for ArgCount {
                 fn from(value: RangeTo<$target>) -> Self {
+                    assert!(value.end > 0, "empty range: `{:?}`", value);
                     let end = value.end.sub(1);
-                    ArgCount::less_than(end as usize)
+                    ArgCount::at_most(end as usize)
                 }
             }
 
             impl From<RangeToInclusive<$target>> for ArgCount {
                 fn from(value: RangeToInclusive<$target>) -> Self {
                     let end = value.end;
-                    ArgCount::less_than(end as usize)
+                    ArgCount::at_most(end as usize)
                 }
             }
         )*
@@ -297,6 +385,7 @@ macro_rules! impl_value_count_from_signed_int {
 
             impl From<Range<$target>> for ArgCount {
                 fn from(value: Range<$target>) -> Self {
+                    assert!(value.start < value.end, "empty range: `{:?}`", value);
                     let start = value.start;
                     let end = value.end.sub(1);
 
@@ -310,15 +399,15 @@ macro_rules! impl_value_count_from_signed_int {
                 fn from(value: RangeFrom<$target>) -> Self {
                     let start = value.start;
                     assert!(start >= 0, "start cannot be negative");
-                    ArgCount::more_than(start as usize)
+                    ArgCount::at_least(start as usize)
                 }
             }
 
             impl From<RangeTo<$target>> for ArgCount {
                 fn from(value: RangeTo<$target>) -> Self {
+                    assert!(value.end > 0, "empty range: `{:?}`", value);
                     let end = value.end.sub(1);
-                    assert!(end >= 0, "end cannot be negative");
-                    ArgCount::less_than(end as usize)
+                    ArgCount::at_most(end as usize)
                 }
             }
 
@@ -326,7 +415,7 @@ macro_rules! impl_value_count_from_signed_int {
                 fn from(value: RangeToInclusive<$target>) -> Self {
                     let end = value.end;
                     assert!(end >= 0, "end cannot be negative");
-                    ArgCount::less_than(end as usize)
+                    ArgCount::at_most(end as usize)
                 }
             }
         )*
@@ -401,6 +490,77 @@ mod tests {
         let _: ArgCount = (0..-2).into();
     }
 
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn into_value_count_panic_test4() {
+        let _: ArgCount = (..0_u32).into();
+    }
+
+    #[test]
+    fn into_value_count_bounds_test() {
+        let count = ArgCount::from((Bound::Excluded(1), Bound::Excluded(4)));
+        assert_eq!(count, ArgCount::between(2, 3));
+
+        let count = ArgCount::from((Bound::Included(1), Bound::Unbounded));
+        assert_eq!(count, ArgCount::at_least(1));
+
+        let count = ArgCount::from((Bound::Unbounded, Bound::Included(4)));
+        assert_eq!(count, ArgCount::at_most(4));
+    }
+
+    // Checks `ArgCount::from(range).takes(n) == range.contains(&n)` for small ranges.
+    #[test]
+    fn into_value_count_contains_test() {
+        const LIMIT: usize = 8;
+
+        fn assert_same<R>(range: R)
+        where
+            R: RangeBounds<usize> + Into<ArgCount> + Clone + std::fmt::Debug,
+        {
+            let arg_count: ArgCount = range.clone().into();
+            for n in 0..=(LIMIT + 1) {
+                assert_eq!(arg_count.takes(n), range.contains(&n), "range: `{:?}`, n: {}", range, n);
+            }
+            assert_eq!(arg_count.takes(usize::MAX), range.contains(&usize::MAX), "range: `{:?}`", range);
+        }
+
+        assert_same(..);
+
+        for a in 0..=LIMIT {
+            assert_same(a..);
+            assert_same(..=a);
+            assert_same((Bound::Excluded(a), Bound::Unbounded));
+
+            if a > 0 {
+                assert_same(..a);
+            }
+
+            for b in a..=LIMIT {
+                assert_same(a..=b);
+                assert_same((Bound::Included(a), Bound::Included(b)));
+
+                if a < b {
+                    assert_same(a..b);
+                    assert_same((Bound::Excluded(a), Bound::Included(b)));
+                    assert_same((Bound::Included(a), Bound::Excluded(b)));
+                }
+
+                if a + 1 < b {
+                    assert_same((Bound::Excluded(a), Bound::Excluded(b)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn named_constructors_test() {
+        assert_eq!(ArgCount::exactly(3), ArgCount::from(3));
+        assert_eq!(ArgCount::at_least(2), ArgCount::from(2..));
+        assert_eq!(ArgCount::at_most(2), ArgCount::from(..=2));
+        assert_eq!(ArgCount::between(1, 3), ArgCount::from(1..4));
+        assert_eq!(ArgCount::between(2, 2), ArgCount::exactly(2));
+    }
+
     #[test]
     fn none_test() {
         let arg_count = ArgCount::zero();
@@ -1,15 +1,19 @@
 #![allow(clippy::len_zero)]
 use crate::error::Result;
 use crate::{ArgCount, Error, ErrorKind};
+use crate::utils::{assert_valid_name, cmp_display_order};
 use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::ops::Index;
+use std::panic::AssertUnwindSafe;
 use std::rc::Rc;
 use std::slice::SliceIndex;
 use std::str::FromStr;
 
-use crate::validator::Validator;
+use crate::validator::{CapturePattern, Validator};
 
 #[cfg(feature = "typing")]
 use crate::typing::Type;
@@ -18,6 +22,64 @@ use crate::typing::Type;
 /// Name used for unnamed `Argument`s.
 pub const ARGUMENT_DEFAULT_NAME: &str = "arg";
 
+/// What an `Argument` does when it receives more values than its max values count.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OverflowPolicy {
+    /// Returns an `InvalidArgumentCount` error, this is the default.
+    Error,
+    /// Takes the first values and ignores the rest, the `CommandLine` prints a warning.
+    TruncateWarn,
+    /// Takes the first values and ignores the rest.
+    TruncateSilent,
+}
+
+impl Default for OverflowPolicy {
+    #[inline]
+    fn default() -> Self {
+        OverflowPolicy::Error
+    }
+}
+
+/// The kind of value an `Argument` expects, used as placeholder in the `help`
+/// and by shell completions.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ValueHint {
+    /// A path to a file, displayed as `<FILE>`.
+    FilePath,
+    /// A path to a directory, displayed as `<DIR>`.
+    DirPath,
+    /// A host name, displayed as `<HOST>`.
+    Hostname,
+    /// An URL, displayed as `<URL>`.
+    Url,
+    /// The name of an executable, displayed as `<COMMAND>`.
+    CommandName,
+    /// Any other kind of value, displayed using the given placeholder.
+    Other(String),
+}
+
+impl ValueHint {
+    /// Returns the placeholder used in the `help` for this hint.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::ValueHint;
+    ///
+    /// assert_eq!(ValueHint::FilePath.placeholder(), "FILE");
+    /// assert_eq!(ValueHint::Other("PORT".to_owned()).placeholder(), "PORT");
+    /// ```
+    pub fn placeholder(&self) -> &str {
+        match self {
+            ValueHint::FilePath => "FILE",
+            ValueHint::DirPath => "DIR",
+            ValueHint::Hostname => "HOST",
+            ValueHint::Url => "URL",
+            ValueHint::CommandName => "COMMAND",
+            ValueHint::Other(placeholder) => placeholder.as_str(),
+        }
+    }
+}
+
 /// Represents the arguments of an `option` or `command`.
 #[derive(Clone)]
 pub struct Argument {
@@ -25,10 +87,25 @@ pub struct Argument {
     description: Option<String>,
     values_count: Option<ArgCount>,
     validator: Option<Rc<dyn Validator>>,
+    #[cfg(feature = "typing")]
+    valid_type: Option<Type>,
     validation_error: Option<String>,
+    capture: Option<CapturePattern>,
     default_values: Vec<String>,
     valid_values: Vec<String>,
+    overflow_policy: OverflowPolicy,
+    value_delimiter: Option<char>,
+    allow_empty_values: bool,
+    allow_hyphen_values: bool,
+    allow_invalid_utf8: bool,
+    raw: bool,
+    trim_values: bool,
+    lowercase_values: bool,
+    display_order: Option<usize>,
+    value_hint: Option<ValueHint>,
     values: Option<Vec<String>>,
+    os_values: Option<Vec<OsString>>,
+    overflow_values: Vec<String>,
 }
 
 impl Argument {
@@ -39,17 +116,32 @@ impl Argument {
             description: None,
             values_count: None,
             validator: None,
+            #[cfg(feature = "typing")]
+            valid_type: None,
             validation_error: None,
+            capture: None,
             default_values: vec![],
             valid_values: vec![],
+            overflow_policy: OverflowPolicy::Error,
+            value_delimiter: None,
+            allow_empty_values: true,
+            allow_hyphen_values: false,
+            allow_invalid_utf8: false,
+            raw: false,
+            trim_values: false,
+            lowercase_values: false,
+            display_order: None,
+            value_hint: None,
             values: None,
+            os_values: None,
+            overflow_values: vec![],
         }
     }
 
     /// Constructs a new `Argument` with the given name that takes 1 value.
     ///
     /// # Panics:
-    /// Panics if the argument `name` is empty or contains whitespaces.
+    /// Panics if the argument `name` is empty, contains whitespaces or starts with `-`.
     ///
     /// # Example
     /// ```
@@ -60,17 +152,32 @@ impl Argument {
     /// ```
     pub fn with_name<S: Into<String>>(name: S) -> Self {
         let name = name.into();
-        assert!(!name.is_empty(), "argument `name` cannot be empty");
+        assert_valid_name("argument `name`", &name);
 
         Argument {
             name: Some(name),
             description: None,
             values_count: None,
             validator: None,
+            #[cfg(feature = "typing")]
+            valid_type: None,
             validation_error: None,
+            capture: None,
             default_values: vec![],
             valid_values: vec![],
+            overflow_policy: OverflowPolicy::Error,
+            value_delimiter: None,
+            allow_empty_values: true,
+            allow_hyphen_values: false,
+            allow_invalid_utf8: false,
+            raw: false,
+            trim_values: false,
+            lowercase_values: false,
+            display_order: None,
+            value_hint: None,
             values: None,
+            os_values: None,
+            overflow_values: vec![],
         }
     }
 
@@ -121,11 +228,36 @@ impl Argument {
         self.validator.as_ref().map(|s| s.as_ref())
     }
 
+    /// Returns the `Type` expected by the validator of this argument,
+    /// or `None` if there is no validator or the validator accepts any type.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Argument;
+    /// use clapi::typing::Type;
+    /// use clapi::validator::validate_type;
+    ///
+    /// let arg = Argument::new().validator(validate_type::<i64>());
+    /// assert_eq!(arg.expected_type(), Some(&Type::of::<i64>()));
+    /// assert_eq!(Argument::new().expected_type(), None);
+    /// ```
+    #[cfg(feature = "typing")]
+    pub fn expected_type(&self) -> Option<&Type> {
+        self.valid_type.as_ref()
+    }
+
     /// Returns the validation error message.
     pub fn get_validation_error(&self) -> Option<&str> {
         self.validation_error.as_deref()
     }
 
+    /// Returns the pattern of the value of this argument, if any.
+    ///
+    /// See `Argument::capture`.
+    pub fn get_capture(&self) -> Option<&CapturePattern> {
+        self.capture.as_ref()
+    }
+
     /// Returns the default values of this argument or a 0-length slice if none.
     pub fn get_default_values(&self) -> &[String] {
         self.default_values.as_slice()
@@ -136,6 +268,62 @@ impl Argument {
         self.valid_values.as_slice()
     }
 
+    /// Returns what this argument does when receives more values than its max values count.
+    pub fn get_overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    /// Returns the delimiter used to split the values of this argument in an option, if any.
+    pub fn get_value_delimiter(&self) -> Option<char> {
+        self.value_delimiter
+    }
+
+    /// Returns `true` if this argument accepts empty values like `""`, this is the default.
+    pub fn is_empty_values_allowed(&self) -> bool {
+        self.allow_empty_values
+    }
+
+    /// Returns `true` if this argument takes values that look like an option, like `--foo`.
+    pub fn is_hyphen_values_allowed(&self) -> bool {
+        self.allow_hyphen_values
+    }
+
+    /// Returns `true` if this argument takes values that are not valid UTF-8.
+    pub fn is_invalid_utf8_allowed(&self) -> bool {
+        self.allow_invalid_utf8
+    }
+
+    /// Returns `true` if this argument takes the values after `--` as they are, without validate them.
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+
+    /// Returns `true` if the whitespaces around the values are removed before validate them.
+    pub fn is_trimmed(&self) -> bool {
+        self.trim_values
+    }
+
+    /// Returns `true` if the values are converted to lowercase before validate them.
+    pub fn is_lowercased(&self) -> bool {
+        self.lowercase_values
+    }
+
+    /// Returns the position of this argument in the `usage`, or `None` if not set.
+    pub fn get_display_order(&self) -> Option<usize> {
+        self.display_order
+    }
+
+    /// Returns the kind of value this argument expects, or `None` if not set.
+    pub fn get_value_hint(&self) -> Option<&ValueHint> {
+        self.value_hint.as_ref()
+    }
+
+    /// Returns the values truncated by the `OverflowPolicy` in the last `set_values`
+    /// or a 0-length slice if none.
+    pub fn get_overflow_values(&self) -> &[String] {
+        self.overflow_values.as_slice()
+    }
+
     /// Returns the values of this argument or a 0-length slice if none.
     pub fn get_values(&self) -> &[String] {
         // Returns the `default_values` if `values` was not set in `set_values`
@@ -145,6 +333,71 @@ impl Argument {
         }
     }
 
+    /// Returns the values of this argument as `OsString`s, keeping the values that are not valid UTF-8.
+    ///
+    /// The values are only different from `get_values` if the argument allows invalid UTF-8
+    /// and was parsed from `OsString`s, in that case `get_values` contains the lossy values.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument};
+    /// use std::ffi::OsString;
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .arg(Argument::with_name("path").allow_invalid_utf8(true))
+    ///     .try_parse_from_os(vec![OsString::from("/tmp/file.txt")])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.arg().unwrap().get_os_values(), vec![OsString::from("/tmp/file.txt")]);
+    /// ```
+    pub fn get_os_values(&self) -> Vec<OsString> {
+        match &self.os_values {
+            Some(os_values) => os_values.clone(),
+            None => self.get_values().iter().map(OsString::from).collect(),
+        }
+    }
+
+    /// Takes the values of this argument leaving it without values, if the values were not set
+    /// takes the default values.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Argument;
+    ///
+    /// let mut arg = Argument::one_or_more("values");
+    /// arg.set_values(vec!["hello", "world"]).unwrap();
+    ///
+    /// assert_eq!(arg.take_values(), vec!["hello".to_string(), "world".to_string()]);
+    /// assert!(arg.get_values().is_empty());
+    /// ```
+    pub fn take_values(&mut self) -> Vec<String> {
+        self.os_values = None;
+        match self.values.take() {
+            Some(values) => values,
+            None => std::mem::take(&mut self.default_values),
+        }
+    }
+
+    /// Removes the values set in this argument, after this the argument uses its default values.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Argument;
+    ///
+    /// let mut arg = Argument::with_name("color").default("auto");
+    /// arg.set_values(vec!["never"]).unwrap();
+    /// assert!(arg.is_set());
+    ///
+    /// arg.reset_values();
+    /// assert!(!arg.is_set());
+    /// assert_eq!(arg.get_values(), &["auto"]);
+    /// ```
+    pub fn reset_values(&mut self) {
+        self.values = None;
+        self.os_values = None;
+        self.overflow_values.clear();
+    }
+
     /// Returns `true` if this argument contains the specified value, `false` otherwise.
     ///
     /// # Example
@@ -183,17 +436,39 @@ impl Argument {
     /// assert!(!arg.is_valid("Hello"));    // Invalid `i64` value
     /// ```
     pub fn is_valid<S: AsRef<str>>(&self, value: S) -> bool {
-        if let Some(validator) = &self.validator {
-            if validator.validate(value.as_ref()).is_err() {
-                return false;
-            }
+        let value = self.preprocess_value(value.as_ref().to_owned());
+        self.validate_value(&value).is_ok()
+    }
+
+    /// Checks if the given values can be set to this `Argument`, returning the same error
+    /// than `set_values` if the values count, the validator or the valid values don't match.
+    ///
+    /// The values are expected to be already split by the delimiter.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Argument;
+    ///
+    /// let arg = Argument::one_or_more("color")
+    ///     .valid_values(vec!["red", "green", "blue"]);
+    ///
+    /// assert!(arg.validate_values(&["red", "blue"]).is_ok());
+    /// assert!(arg.validate_values(&["red", "yellow"]).is_err());
+    /// assert!(arg.validate_values::<&str>(&[]).is_err());
+    /// ```
+    pub fn validate_values<S: AsRef<str>>(&self, values: &[S]) -> Result<()> {
+        let values = &values[..values.len() - self.overflow_count(values.len())];
+        self.validate_values_count(values.len())?;
+
+        if self.raw {
+            return Ok(());
         }
 
-        if self.valid_values.is_empty() {
-            true
-        } else {
-            self.valid_values.iter().any(|s| s == value.as_ref())
+        for value in values {
+            self.validate_value(&self.preprocess_value(value.as_ref().to_owned()))?;
         }
+
+        Ok(())
     }
 
     /// Returns `true` if this `Argument` contains values, or false if don't contains values
@@ -282,12 +557,247 @@ impl Argument {
         }
     }
 
+    /// Sets what this argument does when receives more values than its max values count.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument, OverflowPolicy};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .arg(Argument::with_name("numbers")
+    ///         .values_count(1..=2)
+    ///         .overflow_policy(OverflowPolicy::TruncateSilent))
+    ///     .parse_from(vec!["10", "20", "30"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.args().get("numbers").unwrap().get_values(), &["10", "20"]);
+    /// assert_eq!(result.overflow_values("numbers"), Some(&["30".to_owned()][..]));
+    /// ```
+    pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+
+    /// Sets a delimiter to split the values of this argument when used in an option,
+    /// so `--tags=a,b,c` or `--tags a,b,c` takes 3 values.
+    ///
+    /// Empty values like `a,,b` are rejected, and the delimiter can be used in a value
+    /// by quoting it like `"a,b",c`.
+    ///
+    /// # Panics
+    /// If the delimiter is a whitespace, a letter, a number or `"`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("tags")
+    ///         .arg(Argument::one_or_more("tags").value_delimiter(',')))
+    ///     .parse_from(vec!["--tags", "red,green,blue"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.options().get_arg("tags").unwrap().get_values(), &["red", "green", "blue"]);
+    /// ```
+    pub fn value_delimiter(mut self, delimiter: char) -> Self {
+        assert!(
+            !delimiter.is_whitespace() && !delimiter.is_alphanumeric() && delimiter != '"',
+            "invalid value delimiter: `{}`",
+            delimiter
+        );
+        self.value_delimiter = Some(delimiter);
+        self
+    }
+
+    /// Specify if this argument accepts empty values like `--prefix ""` or `--prefix=`,
+    /// by default empty values are allowed.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("prefix")
+    ///         .arg(Argument::with_name("prefix").allow_empty_values(false)));
+    ///
+    /// assert!(command.clone().parse_from(vec!["--prefix", "app_"]).is_ok());
+    /// assert!(command.clone().parse_from(vec!["--prefix", ""]).is_err());
+    /// assert!(command.parse_from(vec!["--prefix="]).is_err());
+    /// ```
+    pub fn allow_empty_values(mut self, allow: bool) -> Self {
+        self.allow_empty_values = allow;
+        self
+    }
+
+    /// Specify if this argument takes values that look like an option, like `--pattern --foo`,
+    /// when used in an option. By default is `false`.
+    ///
+    /// The values still stop at the known options of the command, and `--` can be used
+    /// to pass any value as a command argument.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("pattern")
+    ///         .arg(Argument::with_name("pattern").allow_hyphen_values(true)))
+    ///     .parse_from(vec!["--pattern", "--foo"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.options().get_arg("pattern").unwrap().get_values(), &["--foo"]);
+    /// ```
+    pub fn allow_hyphen_values(mut self, allow: bool) -> Self {
+        self.allow_hyphen_values = allow;
+        self
+    }
+
+    /// Specify if this argument takes values that are not valid UTF-8, by default is `false`.
+    ///
+    /// When parsing `OsString`s an invalid UTF-8 value is an `ErrorKind::InvalidUtf8` error,
+    /// unless the argument allows it, in that case the validator receives the lossy value
+    /// and the raw value is kept in `get_os_values`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument, ErrorKind};
+    /// # #[cfg(unix)] {
+    /// use std::ffi::OsString;
+    /// use std::os::unix::ffi::OsStringExt;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .arg(Argument::with_name("path").allow_invalid_utf8(true));
+    ///
+    /// let path = OsString::from_vec(vec![b'f', 0xFF, b'o']);
+    /// let result = command.clone().try_parse_from_os(vec![path.clone()]).unwrap();
+    /// assert_eq!(result.arg().unwrap().get_os_values(), vec![path.clone()]);
+    ///
+    /// let command = Command::new("MyApp").arg(Argument::with_name("path"));
+    /// let error = command.try_parse_from_os(vec![path.clone()]).unwrap_err();
+    /// assert_eq!(error.kind(), &ErrorKind::InvalidUtf8(path));
+    /// # }
+    /// ```
+    pub fn allow_invalid_utf8(mut self, allow: bool) -> Self {
+        self.allow_invalid_utf8 = allow;
+        self
+    }
+
+    /// Specify if this argument takes the values after `--` as they are, by default is `false`.
+    ///
+    /// A raw argument must be the last argument of a command. When the command is parsed,
+    /// the tokens after `--` are not interpreted, even if look like options, and are passed
+    /// to this argument without trim, split or validate them; only the values count is checked.
+    ///
+    /// The values before `--` are passed to the other arguments of the command,
+    /// and not to the last option as happens when the command don't have a raw argument.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("run")
+    ///     .option(CommandOption::new("jobs").arg(Argument::with_name("jobs")))
+    ///     .arg(Argument::zero_or_more("command").raw(true))
+    ///     .parse_from(vec!["--jobs", "2", "--", "cargo", "build", "--release"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.options().get_arg("jobs").unwrap().get_values(), &["2"]);
+    /// assert_eq!(result.arg().unwrap().get_values(), &["cargo", "build", "--release"]);
+    /// ```
+    pub fn raw(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Specify if the whitespaces around the values of this argument are removed,
+    /// by default is `false`.
+    ///
+    /// The values are trimmed in `set_values` before check if are empty, and before
+    /// the validator and the valid values, so the `Validator::normalize` receives the trimmed value.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("name")
+    ///         .arg(Argument::with_name("name").trim(true)))
+    ///     .parse_from(vec!["--name", "  Alice "])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.options().get_arg("name").unwrap().get_values(), &["Alice"]);
+    /// ```
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim_values = trim;
+        self
+    }
+
+    /// Specify if the values of this argument are converted to lowercase, by default is `false`.
+    ///
+    /// Like `trim`, the values are converted in `set_values` before the validator
+    /// and the valid values, and before `Validator::normalize`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("color")
+    ///         .arg(Argument::with_name("color")
+    ///             .valid_values(vec!["red", "green", "blue"])
+    ///             .to_lowercase(true)))
+    ///     .parse_from(vec!["--color", "Red"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["red"]);
+    /// ```
+    pub fn to_lowercase(mut self, to_lowercase: bool) -> Self {
+        self.lowercase_values = to_lowercase;
+        self
+    }
+
     /// Sets the description of this argument.
     pub fn description<S: Into<String>>(mut self, description: S) -> Self {
         self.description = Some(description.into());
         self
     }
 
+    /// Sets the position of this argument when the arguments are listed in the `help`.
+    ///
+    /// This only changes how the arguments are listed, the values are always
+    /// assigned in the order the arguments were added, which is the order used in the `usage`.
+    pub fn display_order(mut self, order: usize) -> Self {
+        self.display_order = Some(order);
+        self
+    }
+
+    /// Sets the kind of value this argument expects, the `help` displays
+    /// the placeholder of the hint instead of the argument name.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument, ValueHint, Context};
+    /// use clapi::help::write_command_usage;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("verbose"))
+    ///     .option(CommandOption::new("config"))
+    ///     .arg(Argument::with_name("input").value_hint(ValueHint::FilePath));
+    ///
+    /// let context = Context::new(command);
+    /// let mut usage = String::new();
+    /// write_command_usage(&mut usage, &context, context.root(), false).unwrap();
+    /// assert!(usage.contains("MyApp [OPTIONS] [FILE]"));
+    /// ```
+    pub fn value_hint(mut self, hint: ValueHint) -> Self {
+        if let ValueHint::Other(placeholder) = &hint {
+            assert!(!placeholder.trim().is_empty(), "argument `value_hint` cannot be empty");
+        }
+
+        self.value_hint = Some(hint);
+        self
+    }
+
     /// Sets the value `Validator` of this argument.
     ///
     /// # Panics
@@ -339,6 +849,10 @@ impl Argument {
             self.values.is_none(),
             "validator cannot be set if there is values"
         );
+        #[cfg(feature = "typing")]
+        {
+            self.valid_type = validator.valid_type();
+        }
         self.validator = Some(Rc::new(validator));
         self
     }
@@ -366,6 +880,49 @@ impl Argument {
         self
     }
 
+    /// Sets the pattern of the value of this argument, the value is split in named parts
+    /// like `{owner}/{repo}` which are retrieved with `Argument::captures`.
+    ///
+    /// The pattern is the validator of the argument.
+    ///
+    /// # Panics
+    /// - If the pattern is invalid, see `CapturePattern::new`.
+    /// - If there is already a validator, default values, valid values or values.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("remote")
+    ///         .arg(Argument::with_name("remote").capture("{owner}/{repo}")));
+    ///
+    /// let result = command.clone().parse_from(vec!["--remote", "rust-lang/rust"]).unwrap();
+    /// let captures = result.captures_of("remote").unwrap();
+    /// assert_eq!(captures["owner"], "rust-lang");
+    /// assert_eq!(captures["repo"], "rust");
+    ///
+    /// let error = command.parse_from(vec!["--remote", "rust"]).unwrap_err();
+    /// assert!(error.to_string().contains("expected `{owner}/{repo}` but was `rust`"));
+    /// ```
+    pub fn capture(mut self, pattern: &str) -> Self {
+        let pattern = CapturePattern::new(pattern);
+        self = self.validator(pattern.clone());
+        self.capture = Some(pattern);
+        self
+    }
+
+    /// Returns the parts of the value of this argument by name, or `None` if the argument
+    /// have no capture pattern or have not exactly 1 value.
+    ///
+    /// See `Argument::capture`.
+    pub fn captures(&self) -> Option<HashMap<String, String>> {
+        match self.get_values() {
+            [value] => self.capture.as_ref()?.captures(value),
+            _ => None,
+        }
+    }
+
     /// Sets the valid values of this argument.
     ///
     /// # Panics
@@ -415,6 +972,10 @@ impl Argument {
 
     /// Sets the default value of this argument.
     ///
+    /// When used with other arguments, each argument without default values takes its exact
+    /// number of values first, and this argument takes the values left or keeps its default
+    /// value if there is no values left.
+    ///
     /// # Panics
     /// - If argument already contains values.
     /// - If already contains default values.
@@ -475,17 +1036,23 @@ impl Argument {
         assert!(self.get_values().is_empty(), "already contains values");
         assert!(
             self.get_values_count().takes(values.len()),
-            "invalid value count expected {} but was {}",
+            "argument `{}` expects {} but {} default values were declared",
+            self.get_name(),
             self.get_values_count(),
             values.len()
         );
 
-        // Validate all the values
-        if let Some(validator) = &self.validator {
-            for value in &values {
-                validator.validate(value).unwrap();
-            }
-        }
+        // Validate and normalize all the values
+        let values = match &self.validator {
+            Some(validator) => values
+                .into_iter()
+                .map(|value| {
+                    validator.validate(&value).unwrap();
+                    validator.normalize(&value).unwrap_or(value)
+                })
+                .collect::<Vec<String>>(),
+            None => values,
+        };
 
         if !self.valid_values.is_empty() {
             for value in &values {
@@ -505,6 +1072,9 @@ impl Argument {
 
     /// Sets the values of this argument.
     ///
+    /// All the values are checked before being set, if any is invalid the argument
+    /// keeps its previous values or its default values.
+    ///
     /// # Example
     /// ```
     /// use clapi::Argument;
@@ -516,55 +1086,76 @@ impl Argument {
     ///
     /// assert!(arg.set_values(vec![2]).is_ok());
     /// assert!(arg.set_values(vec!["hello"]).is_err());
+    /// assert_eq!(arg.get_values(), &["2"]);
     /// ```
     pub fn set_values<S, I>(&mut self, values: I) -> Result<()>
     where
         S: ToString,
         I: IntoIterator<Item = S>,
     {
-        let values = values
+        let values = values.into_iter().map(|s| (s.to_string(), None)).collect();
+        self.set_values_with_os(values)
+    }
+
+    // Sets the values paired with the raw value of the values that were not valid UTF-8,
+    // each raw value follows its value through the same steps of `set_values`
+    pub(crate) fn set_values_with_os(&mut self, values: Vec<(String, Option<OsString>)>) -> Result<()> {
+        // The values of a raw argument are taken as they are
+        if self.raw {
+            let mut values = values;
+            let overflow_values = values.split_off(values.len() - self.overflow_count(values.len()));
+            self.validate_values_count(values.len())?;
+            self.set_paired_values(values);
+            self.overflow_values = overflow_values.into_iter().map(|(value, _)| value).collect();
+            return Ok(());
+        }
+
+        let mut values = values
             .into_iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+            .map(|(value, os_value)| (self.preprocess_value(value), os_value))
+            .collect::<Vec<(String, Option<OsString>)>>();
 
-        if !self.get_values_count().takes(values.len()) {
-            return Err(Error::new(
-                ErrorKind::InvalidArgumentCount,
-                invalid_arg_count_message(
-                    self.get_name(),
-                    values.len(),
-                    self.get_values_count()))
-            );
-        }
+        let overflow_values = values.split_off(values.len() - self.overflow_count(values.len()));
+        self.validate_values_count(values.len())?;
 
-        if let Some(validator) = &self.validator {
-            for value in &values {
-                // Checks if the value is valid
-                if let Err(error) = validator.validate(value) {
-                    return match self.validation_error.clone() {
-                        Some(msg) => Err(self.invalid_argument(msg)),
-                        None => Err(self.invalid_argument(error)),
-                    };
-                }
-            }
+        if !self.allow_empty_values && values.iter().any(|(s, _)| s.is_empty()) {
+            return Err(self.invalid_argument(format!(
+                "value for '{}' cannot be empty",
+                self.get_name()
+            )));
         }
 
-        if !self.valid_values.is_empty() {
-            for value in &values {
-                if !self.valid_values.iter().any(|s| s == value) {
-                    return Err(self.invalid_argument(format!(
-                        "expected {} but was {}",
-                        self.valid_values.join(", "),
-                        value
-                    )));
-                }
-            }
-        }
+        // Stores the values normalized by the validator, if any
+        let values = values
+            .into_iter()
+            .map(|(value, os_value)| Ok((self.validate_value(&value)?.unwrap_or(value), os_value)))
+            .collect::<Result<Vec<(String, Option<OsString>)>>>()?;
 
-        self.values = Some(values);
+        self.set_paired_values(values);
+        self.overflow_values = overflow_values.into_iter().map(|(value, _)| value).collect();
         Ok(())
     }
 
+    // Sets the values and its raw values, the raw values are only kept if the argument allows invalid UTF-8
+    fn set_paired_values(&mut self, values: Vec<(String, Option<OsString>)>) {
+        let keep_os_values = self.is_invalid_utf8_allowed() && values.iter().any(|(_, os)| os.is_some());
+        let (values, os_values): (Vec<String>, Vec<Option<OsString>>) = values.into_iter().unzip();
+
+        self.os_values = if keep_os_values {
+            let os_values = values
+                .iter()
+                .zip(os_values)
+                .map(|(value, os_value)| os_value.unwrap_or_else(|| OsString::from(value)))
+                .collect();
+
+            Some(os_values)
+        } else {
+            None
+        };
+
+        self.values = Some(values);
+    }
+
     /// Converts the value of this argument to a concrete type.
     ///
     /// # Returns
@@ -690,6 +1281,95 @@ impl Argument {
         Ok(())
     }
 
+    // Calls the validator returning a `ValidatorPanic` error if panics,
+    // so the panic don't unwind through the parser
+    fn catch_validator_panic<T, F: FnOnce() -> T>(&self, f: F) -> Result<T> {
+        std::panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_owned());
+
+            Error::new(ErrorKind::ValidatorPanic(self.get_name().to_owned()), message)
+        })
+    }
+
+    // Returns the number of values over the max that are truncated by the `OverflowPolicy`
+    fn overflow_count(&self, count: usize) -> usize {
+        match (self.overflow_policy, self.get_values_count().max()) {
+            (OverflowPolicy::Error, _) | (_, None) => 0,
+            (_, Some(max)) => count.saturating_sub(max),
+        }
+    }
+
+    fn validate_values_count(&self, count: usize) -> Result<()> {
+        if !self.get_values_count().takes(count) {
+            return Err(Error::new(
+                ErrorKind::InvalidArgumentCount,
+                invalid_arg_count_message(self.get_name(), count, self.get_values_count()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Adds values that were already checked, like the values of other occurrence of an option
+    pub(crate) fn extend_values(&mut self, values: &[String]) {
+        self.values.get_or_insert_with(Vec::new).extend_from_slice(values);
+    }
+
+    // Sets the raw values of the values that were already set, one for each value
+    pub(crate) fn set_os_values(&mut self, os_values: Vec<OsString>) {
+        debug_assert_eq!(os_values.len(), self.get_values().len());
+        self.os_values = Some(os_values);
+    }
+
+    // Applies `trim` and `to_lowercase` to the value, this happens before any validation
+    fn preprocess_value(&self, value: String) -> String {
+        let value = if self.trim_values {
+            value.trim().to_owned()
+        } else {
+            value
+        };
+
+        if self.lowercase_values {
+            value.to_lowercase()
+        } else {
+            value
+        }
+    }
+
+    // Checks the value against the validator and the valid values,
+    // returns the value normalized by the validator, if any
+    pub(crate) fn validate_value(&self, value: &str) -> Result<Option<String>> {
+        let mut normalized = None;
+
+        if let Some(validator) = &self.validator {
+            if let Err(error) = self.catch_validator_panic(|| validator.validate(value))? {
+                return match self.validation_error.clone() {
+                    Some(msg) => Err(self.invalid_argument(msg)),
+                    None => Err(self.invalid_argument(error)),
+                };
+            }
+
+            normalized = self.catch_validator_panic(|| validator.normalize(value))?;
+        }
+
+        let value = normalized.as_deref().unwrap_or(value);
+        if !self.valid_values.is_empty() && !self.valid_values.iter().any(|s| s == value) {
+            return Err(self
+                .invalid_argument(format!(
+                    "expected {} but was {}",
+                    self.valid_values.join(", "),
+                    value
+                ))
+                .with_invalid_value(value, &self.valid_values));
+        }
+
+        Ok(normalized)
+    }
+
     // Returns an `InvalidArgument` error with the given message
     #[inline(always)]
     fn invalid_argument(&self, msg: String) -> Error {
@@ -757,7 +1437,19 @@ impl Debug for Argument {
             )
             .field("default_values", &self.get_default_values())
             .field("valid_values", &self.get_valid_values())
+            .field("overflow_policy", &self.get_overflow_policy())
+            .field("value_delimiter", &self.get_value_delimiter())
+            .field("allow_empty_values", &self.is_empty_values_allowed())
+            .field("allow_hyphen_values", &self.is_hyphen_values_allowed())
+            .field("allow_invalid_utf8", &self.is_invalid_utf8_allowed())
+            .field("raw", &self.is_raw())
+            .field("trim", &self.is_trimmed())
+            .field("to_lowercase", &self.is_lowercased())
+            .field("display_order", &self.get_display_order())
+            .field("value_hint", &self.get_value_hint())
+            .field("capture", &self.get_capture().map(|c| c.as_str()))
             .field("values", &self.values)
+            .field("overflow_values", &self.get_overflow_values())
             .finish()
     }
 }
@@ -829,6 +1521,30 @@ impl ArgumentList {
         }
     }
 
+    /// Constructs an `ArgumentList` from the given arguments,
+    /// or returns `Err` with the first duplicated argument.
+    ///
+    /// # Panics:
+    /// Panics if there is multiple arguments with default values.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Argument, ArgumentList};
+    ///
+    /// let args = ArgumentList::try_from_iter(vec![Argument::with_name("from"), Argument::with_name("to")]);
+    /// assert_eq!(args.unwrap().len(), 2);
+    ///
+    /// let duplicated = ArgumentList::try_from_iter(vec![Argument::with_name("from"), Argument::with_name("from")]);
+    /// assert_eq!(duplicated.unwrap_err().get_name(), "from");
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = Argument>>(iter: I) -> std::result::Result<Self, Box<Argument>> {
+        let mut args = ArgumentList::new();
+        for arg in iter {
+            args.add(arg).map_err(Box::new)?;
+        }
+        Ok(args)
+    }
+
     /// Returns the number of arguments.
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -866,6 +1582,16 @@ impl ArgumentList {
             .find(|a| a.get_name() == arg_name.as_ref())
     }
 
+    // Returns a mutable reference to the `Argument` with the given name or `None` if no found.
+    pub(crate) fn get_mut<S: AsRef<str>>(&mut self, arg_name: S) -> Option<&mut Argument> {
+        self.iter_mut().find(|a| a.get_name() == arg_name.as_ref())
+    }
+
+    // Returns an iterator over the mutable arguments.
+    pub(crate) fn iter_mut(&mut self) -> std::slice::IterMut<'_, Argument> {
+        self.inner.iter_mut()
+    }
+
     /// Returns an iterator over the `&str` values of this `ArgumentList`.
     pub fn get_raw_args(&self) -> RawArgs<'_> {
         RawArgs {
@@ -982,9 +1708,48 @@ impl ArgumentList {
         }
     }
 
+    /// Returns an `Iterator` over the arguments in the order they are listed in the `help`,
+    /// see [`Argument::display_order`].
+    pub fn iter_sorted(&self) -> std::vec::IntoIter<&Argument> {
+        let mut args = self.inner.iter().collect::<Vec<&Argument>>();
+        args.sort_by(|a, b| {
+            let a_name = a.name.as_deref().unwrap_or_default();
+            let b_name = b.name.as_deref().unwrap_or_default();
+            cmp_display_order((a.display_order, a_name), (b.display_order, b_name))
+        });
+        args.into_iter()
+    }
+
     fn assert_args(&self) {
-        if self.len() == 1 {
-            return;
+        if let Some(message) = self.check_args() {
+            panic!("{}", message);
+        }
+    }
+
+    // Returns `true` if the argument can be added without make the list invalid
+    pub(crate) fn can_add(&self, arg: &Argument) -> bool {
+        if self.inner.contains(arg) {
+            return false;
+        }
+
+        let mut args = self.clone();
+        args.inner.push(arg.clone());
+        args.check_args().is_none()
+    }
+
+    // Returns the reason why the list is invalid, if any
+    fn check_args(&self) -> Option<String> {
+        if self.len() <= 1 {
+            return None;
+        }
+
+        // Check if there is an argument after a raw argument.
+        //
+        // The raw argument takes all the values after `--` so the next arguments never receive values.
+        if let Some(index) = self.inner.iter().position(|a| a.is_raw()) {
+            if let Some(arg) = self.inner.get(index + 1) {
+                return Some(format!("a raw argument must be the last argument: `{}` is declared after `{}`", arg.get_name(), self.inner[index].get_name()));
+            }
         }
 
         // Check if there more than 1 argument with default values.
@@ -999,7 +1764,7 @@ impl ArgumentList {
                 .nth(1)
                 .unwrap();
 
-            panic!("multiple arguments with default values is not allowed: `{}` contains default values", arg.get_name());
+            return Some(format!("multiple arguments with default values is not allowed: `{}` contains default values", arg.get_name()));
         }
 
         // Check if there is an argument with variable arguments when there is default values
@@ -1016,7 +1781,7 @@ impl ArgumentList {
                 .filter(|arg| !arg.has_default_values())
                 .find(|arg| !arg.get_values_count().is_exact())
             {
-                panic!("arguments with variable values is no allowed if there is default values: `{}` contains variable values", arg.get_name())
+                return Some(format!("arguments with variable values is no allowed if there is default values: `{}` contains variable values", arg.get_name()));
             }
         }
 
@@ -1040,8 +1805,10 @@ impl ArgumentList {
                 .nth(1)
                 .unwrap();
 
-            panic!("multiple arguments with variable arguments is not allowed: `{}` contains variable values", arg.get_name());
+            return Some(format!("multiple arguments with variable arguments is not allowed: `{}` contains variable values", arg.get_name()));
         }
+
+        None
     }
 }
 
@@ -1104,6 +1871,30 @@ impl IntoIterator for ArgumentList {
     }
 }
 
+impl FromIterator<Argument> for ArgumentList {
+    /// Constructs an `ArgumentList` from the given arguments.
+    ///
+    /// # Panics
+    /// If there is duplicated arguments.
+    fn from_iter<I: IntoIterator<Item = Argument>>(iter: I) -> Self {
+        ArgumentList::try_from_iter(iter)
+            .unwrap_or_else(|arg| panic!("duplicated argument: `{}`", arg.get_name()))
+    }
+}
+
+impl Extend<Argument> for ArgumentList {
+    /// Adds the given arguments to this list.
+    ///
+    /// # Panics
+    /// If there is duplicated arguments.
+    fn extend<I: IntoIterator<Item = Argument>>(&mut self, iter: I) {
+        for arg in iter {
+            self.add(arg)
+                .unwrap_or_else(|arg| panic!("duplicated argument: `{}`", arg.get_name()));
+        }
+    }
+}
+
 impl Index<usize> for ArgumentList {
     type Output = Argument;
 
@@ -1181,7 +1972,7 @@ impl<'a> Iterator for RawArgs<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::validator::validate_type;
+    use crate::validator::{validate_type, Validator};
 
     #[test]
     fn arg_test() {
@@ -1208,10 +1999,22 @@ mod tests {
     }
 
     #[test]
+    #[should_panic(expected = "argument `name` cannot contains whitespaces: `my arg`")]
     fn arg_name_with_whitespaces_test() {
         Argument::with_name("my arg");
     }
 
+    #[test]
+    #[should_panic(expected = "argument `name` cannot start with `-`: `-arg`")]
+    fn arg_name_with_prefix_test() {
+        Argument::with_name("-arg");
+    }
+
+    #[test]
+    fn arg_unicode_name_test() {
+        assert_eq!(Argument::with_name("número").get_name(), "número");
+    }
+
     #[test]
     fn arg_min_max_values_test() {
         let arg = Argument::with_name("number").min_values(5).max_values(10);
@@ -1232,6 +2035,155 @@ mod tests {
         assert!(number.is_set());
     }
 
+    #[test]
+    fn set_values_failed_keeps_values_test() {
+        let mut numbers = Argument::one_or_more("numbers")
+            .validator(validate_type::<i64>())
+            .default(0);
+
+        assert!(numbers.set_values(["1", "x"]).is_err());
+        assert!(!numbers.is_set());
+        assert_eq!(numbers.get_values(), &["0"]);
+
+        numbers.set_values(["1", "2"]).unwrap();
+        assert!(numbers.set_values(["3", "x", "4"]).is_err());
+        assert_eq!(numbers.get_values(), &["1", "2"]);
+
+        let mut numbers = Argument::with_name("numbers")
+            .values_count(1..=2)
+            .overflow_policy(OverflowPolicy::TruncateSilent);
+
+        numbers.set_values(["1", "2", "3"]).unwrap();
+        assert_eq!(numbers.get_overflow_values(), &["3"]);
+        assert!(numbers.set_values(Vec::<String>::new()).is_err());
+        assert_eq!(numbers.get_values(), &["1", "2"]);
+        assert_eq!(numbers.get_overflow_values(), &["3"]);
+
+        numbers.reset_values();
+        assert!(numbers.get_values().is_empty());
+        assert!(numbers.get_overflow_values().is_empty());
+    }
+
+    #[test]
+    fn set_values_normalized_test() {
+        let mut flag = Argument::with_name("flag").validator(validate_type::<bool>());
+        flag.set_values(["TRUE"]).unwrap();
+        assert_eq!(flag.get_values(), &["true"]);
+        assert_eq!(flag.convert::<bool>().ok(), Some(true));
+        assert!(flag.is_valid("False"));
+        assert!(flag.set_values(["yes"]).is_err());
+
+        // The values are normalized before check the valid values
+        struct Lowercase;
+        impl Validator for Lowercase {
+            fn validate(&self, _: &str) -> std::result::Result<(), String> {
+                Ok(())
+            }
+
+            fn normalize(&self, value: &str) -> Option<String> {
+                Some(value.to_lowercase())
+            }
+        }
+
+        let mut color = Argument::with_name("color")
+            .validator(Lowercase)
+            .valid_values(["red", "blue"])
+            .default("RED");
+
+        assert_eq!(color.get_default_values(), &["red"]);
+        assert!(color.validate_values(&["Blue"]).is_ok());
+        color.set_values(["Blue"]).unwrap();
+        assert_eq!(color.get_values(), &["blue"]);
+        assert!(color.set_values(["Green"]).is_err());
+    }
+
+    #[test]
+    fn set_values_trim_and_lowercase_test() {
+        let mut color = Argument::one_or_more("color")
+            .valid_values(["red", "blue"])
+            .trim(true)
+            .to_lowercase(true);
+
+        color.set_values([" Red", "BLUE  "]).unwrap();
+        assert_eq!(color.get_values(), &["red", "blue"]);
+        assert!(color.is_valid(" Blue "));
+        assert!(color.validate_values(&["\tRED\n"]).is_ok());
+        assert!(color.set_values(["green"]).is_err());
+
+        // Without `trim` the whitespaces are kept
+        let mut name = Argument::with_name("name").to_lowercase(true);
+        name.set_values([" Alice "]).unwrap();
+        assert_eq!(name.get_values(), &[" alice "]);
+
+        // The value is normalized after trimmed
+        let mut flag = Argument::with_name("flag")
+            .validator(validate_type::<bool>())
+            .trim(true);
+        flag.set_values([" TRUE "]).unwrap();
+        assert_eq!(flag.get_values(), &["true"]);
+    }
+
+    #[test]
+    fn set_values_trim_empty_test() {
+        // A value of only whitespaces becomes empty after trimmed
+        let mut prefix = Argument::with_name("prefix").trim(true);
+        prefix.set_values(["   "]).unwrap();
+        assert_eq!(prefix.get_values(), &[""]);
+
+        let mut prefix = Argument::with_name("prefix")
+            .trim(true)
+            .allow_empty_values(false);
+        let error = prefix.set_values(["   "]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("prefix".to_owned()));
+        assert!(prefix.set_values([" app_ "]).is_ok());
+        assert_eq!(prefix.get_values(), &["app_"]);
+
+        // Without `trim` the whitespaces are not empty
+        let mut prefix = Argument::with_name("prefix").allow_empty_values(false);
+        assert!(prefix.set_values(["   "]).is_ok());
+    }
+
+    #[test]
+    fn validate_values_parity_test() {
+        fn assert_parity(arg: &Argument, values: &[&str]) {
+            let expected = arg.clone().set_values(values).map_err(|e| e.to_string());
+            assert_eq!(arg.validate_values(values).map_err(|e| e.to_string()), expected);
+        }
+
+        // Defaults present
+        let number = Argument::one_or_more("number")
+            .validator(validate_type::<i64>())
+            .default(0);
+
+        assert_parity(&number, &[]);
+        assert_parity(&number, &["1", "2"]);
+        assert_parity(&number, &["1", "two"]);
+        assert!(number.validate_values::<&str>(&[]).is_err());
+
+        // Values split by the delimiter
+        let colors = Argument::with_name("colors")
+            .values_count(1..=2)
+            .valid_values(["red", "green", "blue"]);
+
+        assert_parity(&colors, &["red", "blue"]);
+        assert_parity(&colors, &["red", "green", "blue"]);
+        assert!(colors.validate_values(&["red", "green", "blue"]).is_err());
+
+        // Valid values are case sensitive
+        assert_parity(&colors, &["RED"]);
+        assert!(!colors.is_valid("RED"));
+        assert!(colors.validate_values(&["RED"]).is_err());
+
+        // Custom validation error
+        let port = Argument::with_name("port")
+            .validator(validate_type::<u16>())
+            .validation_error("invalid port");
+
+        assert_parity(&port, &["80000"]);
+        assert!(!port.is_valid("80000"));
+        assert!(port.is_valid("8080"));
+    }
+
     #[test]
     fn arg_convert_test() {
         let mut number = Argument::with_name("number").validator(validate_type::<i64>());
@@ -1264,6 +2216,22 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "typing")]
+    #[test]
+    fn arg_expected_type_test() {
+        use crate::typing::Type;
+
+        let typed = Argument::new().validator(validate_type::<u32>());
+        assert_eq!(typed.expected_type(), Some(&Type::of::<u32>()));
+        assert_eq!(typed.expected_type().unwrap().to_string(), "u32");
+        assert_eq!(typed.clone().expected_type(), Some(&Type::of::<u32>()));
+
+        let untyped = Argument::new().validator(|_: &str| Ok(()));
+        assert_eq!(untyped.expected_type(), None);
+
+        assert_eq!(Argument::new().expected_type(), None);
+    }
+
     #[test]
     fn argument_list_test() {
         let mut arg_list = ArgumentList::new();
@@ -1363,6 +2331,34 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    #[should_panic(
+        expected = "a raw argument must be the last argument: `file` is declared after `command`"
+    )]
+    fn argument_list_with_arg_after_raw_test() {
+        let mut args = ArgumentList::new();
+        assert!(args
+            .add(Argument::zero_or_more("command").raw(true))
+            .is_ok());
+        assert!(args.add(Argument::with_name("file")).is_ok());
+    }
+
+    #[test]
+    fn set_values_raw_test() {
+        let mut arg = Argument::with_name("command")
+            .values_count(1..=2)
+            .validator(validate_type::<i64>())
+            .trim(true)
+            .allow_empty_values(false)
+            .raw(true);
+
+        assert!(arg.set_values(["cargo", " build ", ""]).is_err());
+        assert!(arg.set_values(Vec::<String>::new()).is_err());
+        assert!(arg.set_values(["cargo", " build "]).is_ok());
+        assert_eq!(arg.get_values(), &["cargo", " build "]);
+        assert!(arg.validate_values(&["", "x"]).is_ok());
+    }
+
     #[test]
     fn get_raw_args_test() {
         let mut args = ArgumentList::new();
@@ -1418,4 +2414,135 @@ mod tests {
         let values = args.get_raw_args_as_type::<i32>();
         assert!(values.is_err());
     }
+
+    #[test]
+    fn argument_list_from_iter_test() {
+        let args = vec!["a", "b", "c"]
+            .into_iter()
+            .map(Argument::with_name)
+            .collect::<ArgumentList>();
+
+        assert_eq!(args.len(), 3);
+        assert!(args.contains("a"));
+        assert!(args.contains("c"));
+
+        let error = ArgumentList::try_from_iter(vec![
+            Argument::with_name("a"),
+            Argument::with_name("b"),
+            Argument::with_name("a"),
+        ]);
+        assert_eq!(error.unwrap_err().get_name(), "a");
+    }
+
+    #[test]
+    fn take_values_test() {
+        let mut arg = Argument::zero_or_more("values").defaults(["1", "2"]);
+        assert_eq!(arg.take_values(), vec!["1".to_owned(), "2".to_owned()]);
+        assert!(arg.get_values().is_empty());
+
+        let mut arg = Argument::zero_or_more("values").default("1");
+        arg.set_values(["3"]).unwrap();
+        assert_eq!(arg.take_values(), vec!["3".to_owned()]);
+        assert!(!arg.is_set());
+        assert_eq!(arg.get_values(), &["1".to_owned()]);
+    }
+
+    #[test]
+    fn validator_panic_test() {
+        let mut arg = Argument::with_name("number").validator(|value: &str| -> std::result::Result<(), String> {
+            if value == "0" {
+                panic!("zero is not supported");
+            }
+
+            Ok(())
+        });
+
+        let error = arg.set_values(["0"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::ValidatorPanic("number".to_owned()));
+        assert!(error.is_validation_error());
+        assert!(error.to_string().contains("zero is not supported"));
+        assert!(!arg.is_set());
+        assert!(!arg.is_valid("0"));
+
+        arg.set_values(["1"]).unwrap();
+        assert_eq!(arg.get_values(), &["1"]);
+    }
+
+    #[test]
+    fn overflow_policy_test() {
+        let mut numbers = Argument::with_name("numbers").values_count(1..=2);
+        assert_eq!(numbers.get_overflow_policy(), OverflowPolicy::Error);
+        assert!(numbers.set_values(["1", "2", "3"]).is_err());
+
+        let mut numbers = numbers
+            .validator(validate_type::<i64>())
+            .overflow_policy(OverflowPolicy::TruncateSilent);
+
+        // The truncated values are not validated
+        numbers.set_values(["1", "2", "three"]).unwrap();
+        assert_eq!(numbers.get_values(), &["1", "2"]);
+        assert_eq!(numbers.get_overflow_values(), &["three"]);
+        assert!(numbers.validate_values(&["1", "2", "three"]).is_ok());
+
+        numbers.set_values(["1"]).unwrap();
+        assert!(numbers.get_overflow_values().is_empty());
+        assert!(numbers.set_values(Vec::<String>::new()).is_err());
+        assert!(numbers.set_values(["one", "2", "3"]).is_err());
+
+        // No max values count
+        let mut values = Argument::one_or_more("values").overflow_policy(OverflowPolicy::TruncateWarn);
+        values.set_values(["1", "2", "3"]).unwrap();
+        assert_eq!(values.get_values().len(), 3);
+        assert!(values.get_overflow_values().is_empty());
+    }
+
+    #[test]
+    fn set_values_with_os_test() {
+        let mut arg = Argument::with_name("paths")
+            .values_count(1..=2)
+            .overflow_policy(OverflowPolicy::TruncateSilent)
+            .trim(true)
+            .allow_invalid_utf8(true);
+
+        // Each raw value stays with its value after trim the values and truncate the overflow
+        arg.set_values_with_os(vec![
+            (" a ".to_owned(), None),
+            (" b\u{FFFD} ".to_owned(), Some(OsString::from("raw b"))),
+            ("c\u{FFFD}".to_owned(), Some(OsString::from("raw c"))),
+        ])
+        .unwrap();
+
+        assert_eq!(arg.get_values(), &["a", "b\u{FFFD}"]);
+        assert_eq!(arg.get_os_values(), vec![OsString::from("a"), OsString::from("raw b")]);
+        assert_eq!(arg.get_overflow_values(), &["c\u{FFFD}"]);
+
+        // The raw values are only kept if the argument allows invalid UTF-8
+        let mut arg = Argument::with_name("path");
+        arg.set_values_with_os(vec![("a\u{FFFD}".to_owned(), Some(OsString::from("raw a")))]).unwrap();
+        assert_eq!(arg.get_os_values(), vec![OsString::from("a\u{FFFD}")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicated argument: `a`")]
+    fn argument_list_from_iter_duplicated_test() {
+        let _: ArgumentList = vec![Argument::with_name("a"), Argument::with_name("a")]
+            .into_iter()
+            .collect();
+    }
+
+    #[test]
+    fn argument_list_extend_test() {
+        let mut args = ArgumentList::new();
+        args.extend(vec![Argument::with_name("a"), Argument::with_name("b")]);
+        args.extend(vec![Argument::with_name("c")]);
+        assert_eq!(args.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicated argument: `b`")]
+    fn argument_list_extend_duplicated_test() {
+        let mut args = ArgumentList::new();
+        args.extend(vec![Argument::with_name("a"), Argument::with_name("b")]);
+        args.extend(vec![Argument::with_name("b")]);
+    }
 }
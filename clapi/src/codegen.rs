@@ -0,0 +1,439 @@
+use crate::{ArgCount, Argument, Command, CommandOption, ValueHint};
+
+// Number of spaces used for each level of indentation in the generated code.
+const INDENT_SIZE: usize = 4;
+
+impl Command {
+    /// Returns the Rust code that reconstructs this `Command` using the builder API.
+    ///
+    /// The generated code expects `Command`, `CommandOption`, `Argument`, `ArgCount`,
+    /// `ValueHint` and `validator::validate_type` to be in scope. Handlers cannot be represented
+    /// and are omitted, validators are only represented when the `typing` feature
+    /// is enabled and the validator provides its `valid_type`, otherwise a `TODO`
+    /// comment is emitted in its place.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .description("My application")
+    ///     .option(CommandOption::new("enable").alias("e"));
+    ///
+    /// assert_eq!(command.to_builder_code(),
+    /// r#"Command::new("MyApp")
+    ///     .description("My application")
+    ///     .option(CommandOption::new("enable")
+    ///         .alias("e"))"#);
+    /// ```
+    pub fn to_builder_code(&self) -> String {
+        command_to_code(self, 0)
+    }
+}
+
+impl CommandOption {
+    /// Returns the Rust code that reconstructs this `CommandOption` using the builder API.
+    ///
+    /// See [`Command::to_builder_code`] for the limitations of the generated code.
+    pub fn to_builder_code(&self) -> String {
+        option_to_code(self, 0)
+    }
+}
+
+impl Argument {
+    /// Returns the Rust code that reconstructs this `Argument` using the builder API.
+    ///
+    /// See [`Command::to_builder_code`] for the limitations of the generated code.
+    pub fn to_builder_code(&self) -> String {
+        argument_to_code(self, 0)
+    }
+}
+
+fn command_to_code(command: &Command, indent: usize) -> String {
+    let mut code = format!("Command::new({:?})", command.get_name());
+
+    for alias in command.get_aliases() {
+        write_call(&mut code, indent, "alias", &format!("{:?}", alias));
+    }
+
+    for alias in command.get_hidden_aliases() {
+        write_call(&mut code, indent, "hidden_alias", &format!("{:?}", alias));
+    }
+
+    if let Some(description) = command.get_description() {
+        write_call(
+            &mut code,
+            indent,
+            "description",
+            &format!("{:?}", description),
+        );
+    }
+
+    // The summary taken from the description is not written
+    if let Some(summary) = command.get_summary().filter(|_| command.has_summary()) {
+        write_call(&mut code, indent, "summary", &format!("{:?}", summary));
+    }
+
+    if let Some(usage) = command.get_usage() {
+        write_call(&mut code, indent, "usage", &format!("{:?}", usage));
+    }
+
+    if let Some(help) = command.get_help() {
+        write_call(&mut code, indent, "help", &format!("{:?}", help));
+    }
+
+    if let Some(before_help) = command.get_before_help() {
+        write_call(&mut code, indent, "before_help", &format!("{:?}", before_help));
+    }
+
+    if let Some(after_help) = command.get_after_help() {
+        write_call(&mut code, indent, "after_help", &format!("{:?}", after_help));
+    }
+
+    if let Some(version) = command.get_version() {
+        write_call(&mut code, indent, "version", &format!("{:?}", version));
+    }
+
+    if command.is_hidden() {
+        write_call(&mut code, indent, "hidden", "true");
+    }
+
+    if command.is_empty_invocation_allowed() {
+        write_call(&mut code, indent, "allow_empty_invocation", "true");
+    }
+
+    if command.is_eoo_treated_as_value() {
+        write_call(&mut code, indent, "treat_eoo_as_value", "true");
+    }
+
+    for (command_line, description) in command.get_examples() {
+        write_call(
+            &mut code,
+            indent,
+            "example",
+            &format!("{:?}, {:?}", command_line, description),
+        );
+    }
+
+    for option in command.get_options() {
+        write_call(
+            &mut code,
+            indent,
+            "option",
+            &option_to_code(option, indent + 1),
+        );
+    }
+
+    for arg in command.get_args() {
+        write_call(&mut code, indent, "arg", &argument_to_code(arg, indent + 1));
+    }
+
+    for subcommand in command.get_subcommands() {
+        write_call(
+            &mut code,
+            indent,
+            "subcommand",
+            &command_to_code(subcommand, indent + 1),
+        );
+    }
+
+    code
+}
+
+fn option_to_code(option: &CommandOption, indent: usize) -> String {
+    let mut code = format!("CommandOption::new({:?})", option.get_name());
+
+    for alias in option.get_aliases() {
+        write_call(&mut code, indent, "alias", &format!("{:?}", alias));
+    }
+
+    for alias in option.get_hidden_aliases() {
+        write_call(&mut code, indent, "hidden_alias", &format!("{:?}", alias));
+    }
+
+    if let Some(description) = option.get_description() {
+        write_call(
+            &mut code,
+            indent,
+            "description",
+            &format!("{:?}", description),
+        );
+    }
+
+    if option.is_required() {
+        write_call(&mut code, indent, "required", "true");
+    }
+
+    if option.is_hidden() {
+        write_call(&mut code, indent, "hidden", "true");
+    }
+
+    // A counted option allows multiple occurrences
+    if option.is_counted() {
+        write_call(&mut code, indent, "counted", "true");
+    } else if option.allow_multiple() {
+        write_call(&mut code, indent, "multiple", "true");
+    }
+
+    if let Some(min) = option.get_min_occurrences() {
+        write_call(&mut code, indent, "min_occurrences", &min.to_string());
+    }
+
+    if let Some(max) = option.get_max_occurrences() {
+        write_call(&mut code, indent, "max_occurrences", &max.to_string());
+    }
+
+    if option.is_global() {
+        write_call(&mut code, indent, "global", "true");
+    }
+
+    if option.is_assign_required() {
+        write_call(&mut code, indent, "requires_assign", "true");
+    }
+
+    if let Some(order) = option.get_display_order() {
+        write_call(&mut code, indent, "display_order", &order.to_string());
+    }
+
+    if let Some(env) = option.get_env() {
+        write_call(&mut code, indent, "env", &format!("{:?}", env));
+    }
+
+    if !option.is_env_enabled() {
+        write_call(&mut code, indent, "use_env", "false");
+    }
+
+    for arg in option.get_args() {
+        write_call(&mut code, indent, "arg", &argument_to_code(arg, indent + 1));
+    }
+
+    code
+}
+
+fn argument_to_code(arg: &Argument, indent: usize) -> String {
+    let mut code = format!("Argument::with_name({:?})", arg.get_name());
+
+    if let Some(description) = arg.get_description() {
+        write_call(
+            &mut code,
+            indent,
+            "description",
+            &format!("{:?}", description),
+        );
+    }
+
+    let count = arg.get_values_count();
+    if count != ArgCount::one() {
+        write_call(&mut code, indent, "values_count", &arg_count_to_code(count));
+    }
+
+    if arg.get_validator().is_some() {
+        match validator_type_name(arg) {
+            Some(type_name) => {
+                write_call(
+                    &mut code,
+                    indent,
+                    "validator",
+                    &format!("validate_type::<{}>()", type_name),
+                );
+            }
+            None => {
+                code.push('\n');
+                code.push_str(&padding(indent + 1));
+                code.push_str("/* TODO: validator */");
+            }
+        }
+    }
+
+    if let Some(error) = arg.get_validation_error() {
+        write_call(
+            &mut code,
+            indent,
+            "validation_error",
+            &format!("{:?}", error),
+        );
+    }
+
+    if !arg.get_valid_values().is_empty() {
+        write_call(
+            &mut code,
+            indent,
+            "valid_values",
+            &slice_to_code(arg.get_valid_values()),
+        );
+    }
+
+    if !arg.get_default_values().is_empty() {
+        write_call(
+            &mut code,
+            indent,
+            "defaults",
+            &slice_to_code(arg.get_default_values()),
+        );
+    }
+
+    if let Some(order) = arg.get_display_order() {
+        write_call(&mut code, indent, "display_order", &order.to_string());
+    }
+
+    if let Some(hint) = arg.get_value_hint() {
+        write_call(&mut code, indent, "value_hint", &value_hint_to_code(hint));
+    }
+
+    code
+}
+
+fn value_hint_to_code(hint: &ValueHint) -> String {
+    match hint {
+        ValueHint::Other(placeholder) => format!("ValueHint::Other({:?}.to_owned())", placeholder),
+        _ => format!("ValueHint::{:?}", hint),
+    }
+}
+
+#[cfg(feature = "typing")]
+fn validator_type_name(arg: &Argument) -> Option<String> {
+    arg.get_validator()
+        .and_then(|validator| validator.valid_type())
+        // `std::any::type_name` may return paths to `alloc` which is not in scope by default
+        .map(|ty| ty.name().replace("alloc::", "std::"))
+}
+
+#[cfg(not(feature = "typing"))]
+fn validator_type_name(_: &Argument) -> Option<String> {
+    None
+}
+
+fn arg_count_to_code(count: ArgCount) -> String {
+    format!(
+        "ArgCount::new({}, {})",
+        option_to_string(count.min()),
+        option_to_string(count.max())
+    )
+}
+
+fn option_to_string(value: Option<usize>) -> String {
+    match value {
+        Some(n) => format!("Some({})", n),
+        None => String::from("None"),
+    }
+}
+
+fn slice_to_code(values: &[String]) -> String {
+    let values = values
+        .iter()
+        .map(|s| format!("{:?}", s))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("[{}]", values)
+}
+
+fn write_call(code: &mut String, indent: usize, method: &str, value: &str) {
+    code.push('\n');
+    code.push_str(&padding(indent + 1));
+    code.push('.');
+    code.push_str(method);
+    code.push('(');
+    code.push_str(value);
+    code.push(')');
+}
+
+fn padding(indent: usize) -> String {
+    " ".repeat(indent * INDENT_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "typing")]
+    use crate::validator::validate_type;
+    use crate::*;
+
+    #[cfg(feature = "typing")]
+    fn sample_command() -> Command {
+        Command::new("MyApp")
+            .description("A sample \"app\"")
+            .summary("A sample")
+            .version("1.0")
+            .example("MyApp --times 2 hello", "Prints \"hello\" twice")
+            .option(
+                CommandOption::new("times")
+                    .alias("t")
+                    .alias("n")
+                    .description("Number of times")
+                    .required(true)
+                    .arg(
+                        Argument::with_name("count")
+                            .validator(validate_type::<u64>())
+                            .validation_error("expected a number")
+                            .default(1),
+                    ),
+            )
+            .option(
+                CommandOption::new("color")
+                    .hidden_alias("colour")
+                    .global(true)
+                    .requires_assign(true)
+                    .display_order(0)
+                    .arg(Argument::with_name("color").valid_values(["red", "green", "blue"])),
+            )
+            .arg(Argument::zero_or_more("values").validator(validate_type::<String>()).display_order(0))
+            .subcommand(
+                Command::new("test")
+                    .alias("t")
+                    .hidden_alias("tests")
+                    .usage("test [OPTIONS]")
+                    .help("Runs the tests")
+                    .hidden(true)
+                    .option(CommandOption::new("verbose").multiple(true).hidden(true))
+                    .option(CommandOption::new("quiet").alias("q").counted(true))
+                    .arg(Argument::with_name("files").values_count(1..=3).value_hint(ValueHint::FilePath)),
+            )
+    }
+
+    #[cfg(feature = "typing")]
+    #[test]
+    fn to_builder_code_golden_test() {
+        let code = sample_command().to_builder_code();
+        assert_eq!(
+            code,
+            include_str!("../tests/golden/builder_code.rs").trim_end()
+        );
+    }
+
+    #[cfg(feature = "typing")]
+    #[test]
+    fn to_builder_code_round_trip_test() {
+        let expected = sample_command();
+        let generated: Command = include!("../tests/golden/builder_code.rs");
+
+        assert_eq!(format!("{:?}", expected), format!("{:?}", generated));
+        assert_eq!(expected.to_builder_code(), generated.to_builder_code());
+    }
+
+    #[test]
+    fn to_builder_code_validator_without_type_test() {
+        let arg = Argument::with_name("number")
+            .validator(|s: &str| s.parse::<i64>().map(|_| ()).map_err(|e| e.to_string()));
+
+        assert_eq!(
+            arg.to_builder_code(),
+            "Argument::with_name(\"number\")\n    /* TODO: validator */"
+        );
+    }
+
+    #[test]
+    fn argument_to_builder_code_test() {
+        let arg = Argument::with_name("number")
+            .description("A number")
+            .values_count(2..)
+            .defaults([1, 2]);
+
+        assert_eq!(
+            arg.to_builder_code(),
+            "Argument::with_name(\"number\")\n    \
+            .description(\"A number\")\n    \
+            .values_count(ArgCount::new(Some(2), None))\n    \
+            .defaults([\"1\", \"2\"])"
+        );
+    }
+}
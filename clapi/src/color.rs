@@ -0,0 +1,399 @@
+use crate::help::{write_command_help, write_command_usage, HelpSource};
+use crate::{Command, Context};
+use std::ffi::OsString;
+use std::io::IsTerminal;
+use std::rc::Rc;
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+// Indentation of the entries of the help sections, see `help::INDENT`
+const INDENT: &str = "   ";
+
+/// When to use colors in the help messages.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ColorChoice {
+    /// Uses colors if `stdout` is a terminal and the `NO_COLOR` environment variable is not set.
+    Auto,
+    /// Always uses colors.
+    Always,
+    /// Never uses colors.
+    Never,
+}
+
+impl Default for ColorChoice {
+    #[inline]
+    fn default() -> Self {
+        ColorChoice::Auto
+    }
+}
+
+/// A help provider that writes the default help messages with ANSI colors,
+/// the section headers are bold, the option and subcommand names are green
+/// and the descriptions are dim.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandLine, CommandOption};
+/// use clapi::color::{ColorChoice, ColoredHelp};
+///
+/// let help = ColoredHelp::new().color(ColorChoice::Always);
+/// let context = clapi::Context::new(Command::new("MyApp")
+///     .option(CommandOption::new("verbose").description("Shows more output")));
+///
+/// let mut buf = String::new();
+/// help.write_help(&mut buf, &context, context.root(), false);
+/// assert!(buf.contains("\x1b[1mOPTIONS:\x1b[0m"));
+///
+/// let command_line = CommandLine::new(Command::new("MyApp"))
+///     .use_default_help()
+///     .use_help(ColoredHelp::default());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ColoredHelp {
+    color: ColorChoice,
+}
+
+impl ColoredHelp {
+    /// Constructs a new `ColoredHelp` that uses `ColorChoice::Auto`.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets when to use colors.
+    pub fn color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Returns when to use colors.
+    pub fn get_color(&self) -> ColorChoice {
+        self.color
+    }
+
+    /// Returns `true` if the messages will be written with colors.
+    pub fn is_enabled(&self) -> bool {
+        match self.color {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                is_color_supported(std::env::var_os("NO_COLOR"), std::io::stdout().is_terminal())
+            }
+        }
+    }
+
+    /// Writes the help message of the given command.
+    pub fn write_help(
+        &self,
+        buf: &mut String,
+        context: &Context,
+        command: &Command,
+        after_help_message: bool,
+    ) {
+        let mut help = String::new();
+        write_command_help(&mut help, context, command, after_help_message)
+            .expect("failed to write the help message");
+
+        self.write_colored(buf, context, &help);
+    }
+
+    /// Writes the usage message of the given command.
+    pub fn write_usage(
+        &self,
+        buf: &mut String,
+        context: &Context,
+        command: &Command,
+        after_help_message: bool,
+    ) {
+        let mut usage = String::new();
+        write_command_usage(&mut usage, context, command, after_help_message)
+            .expect("failed to write the usage message");
+
+        self.write_colored(buf, context, &usage);
+    }
+
+    fn write_colored(&self, buf: &mut String, context: &Context, message: &str) {
+        if self.is_enabled() {
+            buf.push_str(&colorize(context, message));
+        } else {
+            buf.push_str(message);
+        }
+    }
+}
+
+impl From<ColoredHelp> for HelpSource {
+    fn from(colored: ColoredHelp) -> Self {
+        let colored = Rc::new(colored);
+        let usage = colored.clone();
+
+        HelpSource {
+            help: Rc::new(move |buf, context, command, after_help_message| {
+                colored.write_help(buf, context, command, after_help_message)
+            }),
+            usage: Rc::new(move |buf, context, command, after_help_message| {
+                usage.write_usage(buf, context, command, after_help_message)
+            }),
+        }
+    }
+}
+
+// Colors are disabled by a non-empty `NO_COLOR`, see https://no-color.org
+fn is_color_supported(no_color: Option<OsString>, is_terminal: bool) -> bool {
+    is_terminal && no_color.is_none_or(|s| s.is_empty())
+}
+
+// The section of the help message being colored
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Section {
+    Options,
+    Subcommands,
+    Other,
+}
+
+// Adds colors to a help message written by `write_command_help` or `write_command_usage`
+fn colorize(context: &Context, message: &str) -> String {
+    let mut result = String::with_capacity(message.len() * 2);
+    let mut section = Section::Other;
+
+    for line in message.split_inclusive('\n') {
+        let content = line.trim_end_matches('\n');
+        let newline = &line[content.len()..];
+
+        if is_header(content) {
+            section = match content {
+                "SUBCOMMANDS:" => Section::Subcommands,
+                // Also the options grouped by its prefix, like `DB OPTIONS:` or `SERVER.TLS OPTIONS:`
+                _ if content.ends_with("OPTIONS:") => Section::Options,
+                _ => Section::Other,
+            };
+
+            paint(&mut result, BOLD, content);
+        } else if content.is_empty() || section == Section::Other {
+            result.push_str(content);
+        } else if !content.starts_with(INDENT) {
+            // Any text after the sections, like the after help message
+            section = Section::Other;
+            result.push_str(content);
+        } else {
+            let entry = content.trim_start();
+            let indent = &content[..content.len() - entry.len()];
+            result.push_str(indent);
+
+            // The descriptions of the entries written as columns have more indentation
+            if indent.len() > INDENT.len() {
+                paint(&mut result, DIM, entry);
+            } else {
+                write_entry(&mut result, context, section, entry);
+            }
+        }
+
+        result.push_str(newline);
+    }
+
+    result
+}
+
+// Writes an option or subcommand entry, like: `-c, --color <COLOR>     Color to use`
+fn write_entry(result: &mut String, context: &Context, section: Section, entry: &str) {
+    let is_option_name = |s: &str| {
+        context
+            .name_prefixes()
+            .chain(context.alias_prefixes())
+            .any(|prefix| s.starts_with(prefix.as_str()))
+    };
+
+    let mut rest = entry;
+    let mut expect_name = true;
+
+    while !rest.is_empty() {
+        let token_len = rest.find(' ').unwrap_or(rest.len());
+        let token = &rest[..token_len];
+
+        let (is_name, is_arg) = match section {
+            Section::Options => (is_option_name(token), token.starts_with(['<', '['])),
+            // Subcommand names are separated by commas, like: `remote, rm`
+            _ => (expect_name, false),
+        };
+
+        if !is_name && !is_arg {
+            paint(result, DIM, rest);
+            return;
+        }
+
+        if is_name {
+            let name = token.trim_end_matches(',');
+            paint(result, GREEN, name);
+            result.push_str(&token[name.len()..]);
+        } else {
+            result.push_str(token);
+        }
+
+        expect_name = token.ends_with(',');
+
+        let spaces = rest[token_len..].len() - rest[token_len..].trim_start().len();
+        result.push_str(&rest[token_len..token_len + spaces]);
+        rest = &rest[token_len + spaces..];
+    }
+}
+
+// Section headers are in uppercase, like: `USAGE:`, `GLOBAL OPTIONS:` or `SERVER.TLS OPTIONS:`,
+// the prefix of grouped options can have any character of an option name
+fn is_header(line: &str) -> bool {
+    match line.strip_suffix(':') {
+        Some(name) => {
+            !name.is_empty()
+                && name.chars().all(|c| {
+                    c.is_ascii_uppercase() || c.is_ascii_digit() || matches!(c, ' ' | '.' | '-' | '_')
+                })
+        }
+        None => false,
+    }
+}
+
+fn paint(result: &mut String, color: &str, text: &str) {
+    result.push_str(color);
+    result.push_str(text);
+    result.push_str(RESET);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::help::command_help;
+    use crate::help::HelpStyle;
+    use crate::{Argument, CommandOption};
+
+    // The help is written with a fixed width, so the tests don't depend on the terminal or `COLUMNS`
+    fn new_context(command: Command) -> Context {
+        Context::builder(command).help_style(HelpStyle::new().max_width(100)).build()
+    }
+
+    fn test_context() -> Context {
+        new_context(
+            Command::new("MyApp")
+                .description("My application")
+                .option(CommandOption::new("verbose").alias("v").description("Shows more output"))
+                .option(CommandOption::new("color").arg(Argument::with_name("color")))
+                .subcommand(Command::new("remote").alias("rm").description("Manages the remotes"))
+                .subcommand(Command::new("log")),
+        )
+    }
+
+    fn strip_colors(s: &str) -> String {
+        [BOLD, DIM, GREEN, RESET]
+            .iter()
+            .fold(s.to_owned(), |s, color| s.replace(color, ""))
+    }
+
+    #[test]
+    fn colored_help_test() {
+        let context = test_context();
+        let mut buf = String::new();
+        ColoredHelp::new()
+            .color(ColorChoice::Always)
+            .write_help(&mut buf, &context, context.root(), false);
+
+        assert!(buf.contains("\x1b[1mUSAGE:\x1b[0m\n"), "{}", buf);
+        assert!(buf.contains("\x1b[1mOPTIONS:\x1b[0m\n"));
+        assert!(buf.contains("\x1b[1mSUBCOMMANDS:\x1b[0m\n"));
+        assert!(buf.contains(
+            "   \x1b[32m-v\x1b[0m, \x1b[32m--verbose\x1b[0m       \x1b[2mShows more output\x1b[0m\n"
+        ));
+        assert!(buf.contains("   \x1b[32m--color\x1b[0m <COLOR>\n"));
+        assert!(buf.contains(
+            "   \x1b[32mremote\x1b[0m, \x1b[32mrm\x1b[0m      \x1b[2mManages the remotes\x1b[0m\n"
+        ));
+        assert!(buf.contains("   \x1b[32mlog\x1b[0m\n"));
+
+        // Only adds colors
+        let mut plain = String::new();
+        command_help(&mut plain, &context, context.root(), false);
+        assert_eq!(strip_colors(&buf), plain);
+    }
+
+    #[test]
+    fn colored_help_disabled_test() {
+        let context = test_context();
+        let mut buf = String::new();
+        ColoredHelp::new()
+            .color(ColorChoice::Never)
+            .write_help(&mut buf, &context, context.root(), true);
+
+        let mut plain = String::new();
+        command_help(&mut plain, &context, context.root(), true);
+        assert!(!buf.contains('\x1b'));
+        assert_eq!(buf, plain);
+    }
+
+    #[test]
+    fn colored_usage_test() {
+        let context = test_context();
+        let mut buf = String::new();
+        ColoredHelp::new()
+            .color(ColorChoice::Always)
+            .write_usage(&mut buf, &context, context.root(), false);
+
+        assert!(buf.starts_with("\n\x1b[1mUSAGE:\x1b[0m\n   MyApp"), "{}", buf);
+    }
+
+    #[test]
+    fn colored_help_columns_test() {
+        let context = new_context(Command::new("MyApp").option(
+            CommandOption::new("a-very-long-option-name-to-use-columns")
+                .description("A long option")
+                .arg(Argument::with_name("value")),
+        ));
+
+        let mut buf = String::new();
+        ColoredHelp::new()
+            .color(ColorChoice::Always)
+            .write_help(&mut buf, &context, context.root(), false);
+
+        assert!(buf.contains("   \x1b[32m--a-very-long-option-name-to-use-columns\x1b[0m <VALUE>\n"), "{}", buf);
+        assert!(buf.contains("      \x1b[2mA long option\x1b[0m"));
+    }
+
+    #[test]
+    fn colored_help_grouped_options_test() {
+        let context = new_context(
+            Command::new("MyApp")
+                .option(CommandOption::new("verbose"))
+                .option(CommandOption::new("db.host").arg(Argument::with_name("host")))
+                .option(CommandOption::new("server.tls.cert").arg(Argument::with_name("cert"))),
+        );
+
+        let mut buf = String::new();
+        ColoredHelp::new()
+            .color(ColorChoice::Always)
+            .write_help(&mut buf, &context, context.root(), false);
+
+        assert!(buf.contains("\x1b[1mDB OPTIONS:\x1b[0m\n   \x1b[32m--db.host\x1b[0m <HOST>\n"), "{}", buf);
+        assert!(buf.contains("\x1b[1mSERVER.TLS OPTIONS:\x1b[0m\n   \x1b[32m--server.tls.cert\x1b[0m <CERT>\n"));
+
+        let mut plain = String::new();
+        command_help(&mut plain, &context, context.root(), false);
+        assert_eq!(strip_colors(&buf), plain);
+    }
+
+    #[test]
+    fn is_header_test() {
+        assert!(is_header("USAGE:"));
+        assert!(is_header("GLOBAL OPTIONS:"));
+        assert!(is_header("SERVER.TLS OPTIONS:"));
+        assert!(is_header("MY-DB_2 OPTIONS:"));
+        assert!(!is_header("Usage:"));
+        assert!(!is_header(":"));
+        assert!(!is_header("OPTIONS"));
+    }
+
+    #[test]
+    fn is_color_supported_test() {
+        assert!(is_color_supported(None, true));
+        assert!(is_color_supported(Some(OsString::new()), true));
+        assert!(!is_color_supported(Some(OsString::from("1")), true));
+        assert!(!is_color_supported(None, false));
+    }
+}
@@ -1,12 +1,16 @@
 #![allow(clippy::type_complexity, clippy::len_zero)]
 use crate::args::{Argument, ArgumentList};
 use crate::error::Result;
-use crate::option::{CommandOption, OptionList};
-use crate::utils::debug_option;
+use crate::help::HelpSource;
+use crate::option::{Aliases, CommandOption, NameCase, OptionList};
+use crate::settings::{CommandSettings, Setting};
+use crate::suggestion::SuggestionSource;
+use crate::utils::{assert_valid_name, debug_option};
 use crate::{CommandLine, ParseResult};
 use std::borrow::Borrow;
-use std::cell::{RefCell, RefMut};
-use std::fmt::{Debug, Formatter};
+use std::cell::{OnceCell, RefCell, RefMut};
+use std::ffi::OsString;
+use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
@@ -16,22 +20,34 @@ use std::rc::Rc;
 #[derive(Clone)]
 pub struct Command {
     name: String,
+    aliases: Vec<String>,
+    hidden_aliases: Vec<String>,
     description: Option<String>,
-    usage: Option<String>,
-    help: Option<String>,
+    summary: Option<String>,
+    usage: Option<LazyString>,
+    help: Option<LazyString>,
+    before_help: Option<String>,
+    after_help: Option<String>,
     version: Option<String>,
+    examples: Vec<(String, String)>,
     subcommands: Vec<Command>,
     options: OptionList,
     args: ArgumentList,
     is_hidden: bool,
+    settings: CommandSettings,
     handler: Option<Rc<RefCell<dyn FnMut(&OptionList, &ArgumentList) -> Result<()>>>>,
+    help_provider: Option<HelpSource>,
+    suggestion_provider: Option<SuggestionSource>,
+    // The usage generated by the `help`, cleared when the options, args or subcommands
+    // or the `Context` change
+    usage_cache: UsageCache,
 }
 
 impl Command {
     /// Constructs a new `Command`.
     ///
     /// # Panics
-    /// Panics if the command `name` is blank or empty.
+    /// Panics if the command `name` is blank or empty, or starts with `-`.
     ///
     /// # Example
     /// ```
@@ -99,17 +115,31 @@ impl Command {
         let name = name.into();
         assert!(!name.is_empty(), "command `name` cannot be empty");
 
+        if name.starts_with('-') {
+            panic!("command `name` cannot start with `-`: `{}`", name);
+        }
+
         Command {
             name,
+            aliases: Vec::new(),
+            hidden_aliases: Vec::new(),
             description: None,
+            summary: None,
             usage: None,
             help: None,
+            before_help: None,
+            after_help: None,
             version: None,
+            examples: Vec::new(),
             subcommands: Default::default(),
             handler: None,
+            help_provider: None,
+            suggestion_provider: None,
             args: ArgumentList::new(),
             options,
-            is_hidden: false
+            is_hidden: false,
+            settings: CommandSettings::new(),
+            usage_cache: UsageCache::default(),
         }
     }
 
@@ -118,19 +148,77 @@ impl Command {
         self.name.as_str()
     }
 
+    /// Returns an `Iterator` over the aliases of the command, without the hidden aliases.
+    pub fn get_aliases(&self) -> Aliases<'_> {
+        Aliases::new(&self.aliases)
+    }
+
+    /// Returns an `Iterator` over the hidden aliases of the command.
+    pub fn get_hidden_aliases(&self) -> Aliases<'_> {
+        Aliases::new(&self.hidden_aliases)
+    }
+
+    /// Returns `true` if the command contains the specified alias or hidden alias.
+    pub fn has_alias<S: AsRef<str>>(&self, alias: S) -> bool {
+        self.get_all_aliases().any(|s| s == alias.as_ref())
+    }
+
+    // Returns an `Iterator` over the aliases and hidden aliases of the command.
+    pub(crate) fn get_all_aliases(&self) -> impl Iterator<Item = &String> {
+        self.aliases.iter().chain(self.hidden_aliases.iter())
+    }
+
     /// Returns a short description of the command, or `None` if is not set.
     pub fn get_description(&self) -> Option<&str> {
         self.description.as_deref()
     }
 
+    /// Returns the one line description used when this command is listed by its parent,
+    /// or the first sentence of the `description` if is not set.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let command = Command::new("build").description("Builds the project. Uses the release profile by default.");
+    /// assert_eq!(command.get_summary(), Some("Builds the project."));
+    ///
+    /// let command = command.summary("Builds it");
+    /// assert_eq!(command.get_summary(), Some("Builds it"));
+    /// ```
+    pub fn get_summary(&self) -> Option<&str> {
+        self.summary
+            .as_deref()
+            .or_else(|| self.description.as_deref().map(first_sentence))
+    }
+
+    /// Returns `true` if the summary was set using `summary`.
+    pub(crate) fn has_summary(&self) -> bool {
+        self.summary.is_some()
+    }
+
     /// Returns information about the usage of this command.
+    ///
+    /// If the usage was set using `usage_with` the function is called on first use.
     pub fn get_usage(&self) -> Option<&str> {
-        self.usage.as_deref()
+        self.usage.as_ref().map(|s| s.get())
     }
 
     /// Returns the `help` information of the command.
+    ///
+    /// If the help was set using `help_with` the function is called on first use.
     pub fn get_help(&self) -> Option<&str> {
-        self.help.as_deref()
+        self.help.as_ref().map(|s| s.get())
+    }
+
+    /// Returns the text written in the help message before the usage, or `None` if is not set.
+    pub fn get_before_help(&self) -> Option<&str> {
+        self.before_help.as_deref()
+    }
+
+    /// Returns the text written at the end of the help message, or `None` if is not set.
+    pub fn get_after_help(&self) -> Option<&str> {
+        self.after_help.as_deref()
     }
 
     /// Returns the `version` of this command.
@@ -138,6 +226,11 @@ impl Command {
         self.version.as_deref()
     }
 
+    /// Returns the examples of this command as pairs of `(command-line, description)`.
+    pub fn get_examples(&self) -> &[(String, String)] {
+        &self.examples
+    }
+
     /// Returns an iterator over the subcommands of this command.
     pub fn get_subcommands(&self) -> Iter<'_> {
         Iter {
@@ -147,6 +240,8 @@ impl Command {
 
     /// Returns an `ExactSizeIterator` over the children of this command.
     pub fn get_subcommands_mut(&mut self) -> IterMut<'_> {
+        // The subcommands may change the usage of this command
+        self.usage_cache.clear();
         IterMut {
             iter: self.subcommands.iter_mut()
         }
@@ -181,6 +276,27 @@ impl Command {
         self.is_hidden
     }
 
+    /// Returns `true` if this command can be executed without a subcommand
+    /// when it only groups subcommands.
+    pub fn is_empty_invocation_allowed(&self) -> bool {
+        self.has_setting(Setting::AllowEmptyInvocation)
+    }
+
+    /// Returns `true` if the end of options (`--`) is passed as a normal argument to this command.
+    pub fn is_eoo_treated_as_value(&self) -> bool {
+        self.has_setting(Setting::TreatEooAsValue)
+    }
+
+    /// Returns `true` if the given setting is set in this command.
+    pub fn has_setting(&self, setting: Setting) -> bool {
+        self.settings.contains(setting)
+    }
+
+    /// Returns the settings of this command.
+    pub fn get_settings(&self) -> &CommandSettings {
+        &self.settings
+    }
+
     /// Returns the handler of this command, or `None` if not set.
     pub fn get_handler(
         &self,
@@ -188,9 +304,93 @@ impl Command {
         self.handler.as_ref().map(|x| x.borrow_mut())
     }
 
-    /// Returns the child with the given name, or `None` if not child if found.
-    pub fn find_subcommand<S: AsRef<str>>(&self, name: S) -> Option<&Command> {
-        self.subcommands.iter().find(|c| c.get_name() == name.as_ref())
+    /// Returns the help provider of this command, or `None` if not set.
+    ///
+    /// See `Command::help_provider`.
+    pub fn get_help_provider(&self) -> Option<&HelpSource> {
+        self.help_provider.as_ref()
+    }
+
+    /// Returns the suggestion provider of this command, or `None` if not set.
+    ///
+    /// See `Command::suggestion_provider`.
+    pub fn get_suggestion_provider(&self) -> Option<&SuggestionSource> {
+        self.suggestion_provider.as_ref()
+    }
+
+    /// Returns the child with the given name or alias, or `None` if not child if found.
+    pub fn find_subcommand<S: AsRef<str>>(&self, name_or_alias: S) -> Option<&Command> {
+        let name_or_alias = name_or_alias.as_ref();
+        self.subcommands
+            .iter()
+            .find(|c| c.get_name() == name_or_alias || c.has_alias(name_or_alias))
+    }
+
+    /// Returns a mutable reference to the subcommand in the given path, or `None` if not found.
+    ///
+    /// The path is the names or aliases of the subcommands separated by `.`, like: `data.set`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let mut command = Command::new("MyApp")
+    ///     .subcommand(Command::new("data").subcommand(Command::new("set")));
+    ///
+    /// let set = command.find_subcommand_mut("data.set").unwrap();
+    /// set.set_handler(|_, _| Ok(()));
+    ///
+    /// assert!(command.find_subcommand("data").unwrap().find_subcommand("set").unwrap().get_handler().is_some());
+    /// assert!(command.find_subcommand_mut("data.get").is_none());
+    /// ```
+    pub fn find_subcommand_mut<S: AsRef<str>>(&mut self, path: S) -> Option<&mut Command> {
+        let mut current = self;
+
+        for name in path.as_ref().split('.') {
+            // The subcommands may change the usage of the parent
+            current.usage_cache.clear();
+            current = current
+                .subcommands
+                .iter_mut()
+                .find(|c| c.get_name() == name || c.has_alias(name))?;
+        }
+
+        Some(current)
+    }
+
+    /// Adds an alias to this command, so can be invoked using other name.
+    ///
+    /// # Panics
+    /// Panics if the `alias` is empty, contains whitespaces or starts with `-`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .subcommand(Command::new("install").alias("i"))
+    ///     .parse_from(vec!["i"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.command_name(), "install");
+    /// ```
+    pub fn alias<S: Into<String>>(mut self, alias: S) -> Self {
+        let alias = alias.into();
+        assert_valid_name("command `alias`", &alias);
+        self.aliases.push(alias);
+        self
+    }
+
+    /// Adds an alias to this command that is not shown in the `help`,
+    /// useful to keep accepting an old name of the command.
+    ///
+    /// # Panics
+    /// Panics if the `alias` is empty, contains whitespaces or starts with `-`.
+    pub fn hidden_alias<S: Into<String>>(mut self, alias: S) -> Self {
+        let alias = alias.into();
+        assert_valid_name("command `alias`", &alias);
+        self.hidden_aliases.push(alias);
+        self
     }
 
     /// Sets a short description of this command.
@@ -207,6 +407,24 @@ impl Command {
         self
     }
 
+    /// Sets a one line description of this command, used in the subcommands listed in the help
+    /// of the parent and in the shell completions; the `description` is shown in the help of this command.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let command = Command::new("build")
+    ///     .description("Builds the project using the settings of the current workspace")
+    ///     .summary("Builds the project");
+    ///
+    /// assert_eq!(command.get_summary(), Some("Builds the project"));
+    /// ```
+    pub fn summary<S: Into<String>>(mut self, summary: S) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
     /// Sets information about the usage of this command.
     ///
     /// # Example
@@ -218,7 +436,23 @@ impl Command {
     /// assert_eq!(command.get_usage(), Some("app [VALUES]\napp [OPTIONS] [VALUES]"));
     /// ```
     pub fn usage<S: Into<String>>(mut self, usage: S) -> Self {
-        self.usage = Some(usage.into());
+        self.usage = Some(LazyString::new(usage.into()));
+        self
+    }
+
+    /// Sets a function that provides information about the usage of this command,
+    /// the function is only called the first time the usage is requested.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    /// let command = Command::new("app")
+    ///     .usage_with(|| format!("{} [VALUES]", "app"));
+    ///
+    /// assert_eq!(command.get_usage(), Some("app [VALUES]"));
+    /// ```
+    pub fn usage_with<F: Fn() -> String + 'static>(mut self, f: F) -> Self {
+        self.usage = Some(LazyString::with(f));
         self
     }
 
@@ -243,10 +477,68 @@ impl Command {
     /// assert!(command.get_help().is_some());
     /// ```
     pub fn help<S: Into<String>>(mut self, help: S) -> Self {
-        self.help = Some(help.into());
+        self.help = Some(LazyString::new(help.into()));
+        self
+    }
+
+    /// Sets a function that provides help information about this command,
+    /// the function is only called the first time the help is requested.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    /// let command = Command::new("MyApp")
+    ///     .help_with(|| "MyApp - An app for sum numbers".to_string());
+    ///
+    /// assert_eq!(command.get_help(), Some("MyApp - An app for sum numbers"));
+    /// ```
+    pub fn help_with<F: Fn() -> String + 'static>(mut self, f: F) -> Self {
+        self.help = Some(LazyString::with(f));
+        self
+    }
+
+    /// Sets a text written in the help message before the usage,
+    /// this is not inherited by the subcommands.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let command = Command::new("MyApp").before_help("NOTE: This app is experimental");
+    /// assert_eq!(command.get_before_help(), Some("NOTE: This app is experimental"));
+    /// ```
+    pub fn before_help<S: Into<String>>(mut self, before_help: S) -> Self {
+        self.before_help = Some(before_help.into());
+        self
+    }
+
+    /// Sets a text written at the end of the help message, after the options and subcommands,
+    /// this is not inherited by the subcommands.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let command = Command::new("MyApp").after_help("LICENSE:\n   MIT");
+    /// assert_eq!(command.get_after_help(), Some("LICENSE:\n   MIT"));
+    /// ```
+    pub fn after_help<S: Into<String>>(mut self, after_help: S) -> Self {
+        self.after_help = Some(after_help.into());
         self
     }
 
+    /// Returns `true` if the usage is provided by a function, see `usage_with`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn is_usage_fn(&self) -> bool {
+        self.usage.as_ref().is_some_and(LazyString::is_fn)
+    }
+
+    /// Returns `true` if the help is provided by a function, see `help_with`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn is_help_fn(&self) -> bool {
+        self.help.as_ref().is_some_and(LazyString::is_fn)
+    }
+
     /// Sets the version of this command.
     ///
     /// # Example
@@ -261,6 +553,27 @@ impl Command {
         self
     }
 
+    /// Adds an example of how to use this command, which is displayed in the help message.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .example("MyApp sum 1 2 3 --precision=2", "Sum with two decimals");
+    ///
+    /// assert_eq!(command.get_examples()[0].0, "MyApp sum 1 2 3 --precision=2");
+    /// assert_eq!(command.get_examples()[0].1, "Sum with two decimals");
+    /// ```
+    pub fn example<S1, S2>(mut self, command_line: S1, description: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        self.examples.push((command_line.into(), description.into()));
+        self
+    }
+
     /// Adds an `CommandOption` to this command.
     ///
     /// # Panics:
@@ -285,6 +598,37 @@ impl Command {
         self
     }
 
+    /// Adds an `CommandOption` to this command, or returns this command and the rejected option
+    /// if conflicts with an existing option.
+    ///
+    /// An option conflicts if its name or any of its aliases is equal to the name or an alias
+    /// of an option of this command, the comparison is case sensitive,
+    /// see `ContextBuilder::ignore_option_case`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .try_option(CommandOption::new("verbose").alias("v"))
+    ///     .unwrap();
+    ///
+    /// let (command, rejected) = command.try_option(CommandOption::new("version").alias("v")).unwrap_err();
+    /// assert_eq!(rejected.get_name(), "version");
+    /// assert!(!command.get_options().contains("version"));
+    /// assert!(command.try_option(CommandOption::new("V")).is_ok());
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn try_option(mut self, option: CommandOption) -> std::result::Result<Self, (Self, CommandOption)> {
+        match self.options.add(option) {
+            Ok(()) => {
+                self.usage_cache.clear();
+                Ok(self)
+            }
+            Err(option) => Err((self, option)),
+        }
+    }
+
     /// Replaces the options of this command with the specified.
     ///
     /// # Example
@@ -304,6 +648,7 @@ impl Command {
     /// ```
     pub fn options(mut self, options: OptionList) -> Self {
         self.options = options;
+        self.usage_cache.clear();
         self
     }
 
@@ -327,6 +672,7 @@ impl Command {
                 duplicated.get_name()
             );
         }
+        self.usage_cache.clear();
         self
     }
 
@@ -344,6 +690,7 @@ impl Command {
     /// ```
     pub fn args(mut self, args: ArgumentList) -> Self {
         self.args = args;
+        self.usage_cache.clear();
         self
     }
 
@@ -364,6 +711,77 @@ impl Command {
         self
     }
 
+    /// Specify if this command can be executed without a subcommand when it have no handler,
+    /// takes no arguments and have subcommands.
+    ///
+    /// By default the `CommandLine` fails and shows the help of these commands.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let command = Command::new("config")
+    ///     .subcommand(Command::new("get"))
+    ///     .allow_empty_invocation(true);
+    ///
+    /// assert!(command.is_empty_invocation_allowed());
+    /// ```
+    pub fn allow_empty_invocation(self, allow: bool) -> Self {
+        self.toggle_setting(Setting::AllowEmptyInvocation, allow)
+    }
+
+    /// Specify if the end of options (`--`) is passed as a normal argument when this command is executed.
+    ///
+    /// When enabled, the values of an option only stop at a known option.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument};
+    ///
+    /// let command = Command::new("exec")
+    ///     .arg(Argument::zero_or_more("args"))
+    ///     .treat_eoo_as_value(true);
+    ///
+    /// let result = command.parse_from(vec!["--", "-v"]).unwrap();
+    /// assert_eq!(result.arg().unwrap().get_values(), &["--", "-v"]);
+    /// ```
+    pub fn treat_eoo_as_value(self, treat_as_value: bool) -> Self {
+        self.toggle_setting(Setting::TreatEooAsValue, treat_as_value)
+    }
+
+    /// Sets the given setting in this command.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Setting};
+    ///
+    /// let command = Command::new("config")
+    ///     .setting(Setting::AllowEmptyInvocation)
+    ///     .setting(Setting::TreatEooAsValue);
+    ///
+    /// assert!(command.has_setting(Setting::AllowEmptyInvocation));
+    /// assert!(command.has_setting(Setting::TreatEooAsValue));
+    /// ```
+    pub fn setting(mut self, setting: Setting) -> Self {
+        self.settings.insert(setting);
+        self
+    }
+
+    /// Unsets the given setting in this command.
+    pub fn unset_setting(mut self, setting: Setting) -> Self {
+        self.settings.remove(setting);
+        self
+    }
+
+    #[inline]
+    fn toggle_setting(self, setting: Setting, enable: bool) -> Self {
+        if enable {
+            self.setting(setting)
+        } else {
+            self.unset_setting(setting)
+        }
+    }
+
     /// Sets the handler of this command.
     ///
     /// # Example
@@ -387,6 +805,78 @@ impl Command {
         self
     }
 
+    /// Sets the help provider used for this command and its subcommands.
+    ///
+    /// The help messages use the provider of the command or its nearest parent that have one,
+    /// otherwise the one of the `CommandLine`, see `CommandLine::use_help`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine};
+    /// use clapi::help::HelpSource;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .subcommand(Command::new("plugin")
+    ///         .help_provider(HelpSource::new())
+    ///         .subcommand(Command::new("list")));
+    ///
+    /// let plugin = command.find_subcommand("plugin").unwrap();
+    /// assert!(plugin.get_help_provider().is_some());
+    /// ```
+    pub fn help_provider<H: Into<HelpSource>>(mut self, help: H) -> Self {
+        self.help_provider = Some(help.into());
+        self
+    }
+
+    /// Sets the suggestion provider used for this command and its subcommands.
+    ///
+    /// The suggestions use the provider of the executing command or its nearest parent
+    /// that have one, otherwise the one of the `CommandLine`, see `CommandLine::use_suggestions`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine};
+    /// use clapi::suggestion::SuggestionSource;
+    ///
+    /// fn plugins() -> Vec<String> {
+    ///     vec!["deploy".to_owned(), "lint".to_owned()]
+    /// }
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp")
+    ///     .subcommand(Command::new("plugin")
+    ///         .suggestion_provider(SuggestionSource::new().commands(plugins))
+    ///         .subcommand(Command::new("list"))));
+    ///
+    /// let error = command_line.parse_from(vec!["plugin", "deplyo"]).unwrap_err();
+    /// assert!(error.to_string().contains("Did you mean `deploy`?"));
+    /// ```
+    pub fn suggestion_provider(mut self, suggestions: SuggestionSource) -> Self {
+        self.suggestion_provider = Some(suggestions);
+        self
+    }
+
+    /// Replaces the handler of this command, used to set the handler of a command
+    /// that was not created with the builder, like one loaded using `serde`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use clapi::Command;
+    ///
+    /// let mut command = Command::new("test");
+    /// command.set_handler(|_options, _args| {
+    ///     println!("This is a test");
+    ///     Ok(())
+    /// });
+    ///
+    /// assert!(command.get_handler().is_some());
+    /// ```
+    pub fn set_handler<F>(&mut self, f: F)
+    where
+        F: FnMut(&OptionList, &ArgumentList) -> Result<()> + 'static,
+    {
+        self.handler = Some(Rc::new(RefCell::new(f)));
+    }
+
     /// Adds a new child `Command`.
     ///
     /// # Example
@@ -403,8 +893,166 @@ impl Command {
         self
     }
 
+    /// Adds a new child `Command`, or returns this command and the rejected subcommand
+    /// if conflicts with an existing subcommand.
+    ///
+    /// A subcommand conflicts if its name or any of its aliases is equal to the name or an alias
+    /// of a subcommand of this command, the comparison is case sensitive.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Command;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .try_subcommand(Command::new("test"))
+    ///     .unwrap();
+    ///
+    /// let (command, rejected) = command.try_subcommand(Command::new("test")).unwrap_err();
+    /// assert_eq!(rejected.get_name(), "test");
+    /// assert_eq!(command.get_subcommands().count(), 1);
+    /// assert!(command.try_subcommand(Command::new("Test")).is_ok());
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn try_subcommand(mut self, command: Command) -> std::result::Result<Self, (Self, Command)> {
+        if self.is_subcommand_duplicate(&command) {
+            return Err((self, command));
+        }
+
+        self.subcommands.push(command);
+        self.usage_cache.clear();
+        Ok(self)
+    }
+
+    /// Merges the options, arguments and subcommands of `other` into this command,
+    /// or returns the first conflict found.
+    ///
+    /// The name of `other` is ignored, its definitions are merged into this command:
+    /// - The subcommands with the same name are merged recursively.
+    /// - The options and arguments with the same name are kept if its definitions are equals,
+    ///   otherwise is a conflict. The global options keep being global.
+    /// - The description, usage, help, version and handler of this command are kept,
+    ///   the ones of `other` are only used if this command don't have it.
+    /// - The aliases, examples and settings are combined.
+    ///
+    /// A conflict is an option or argument with the same name but different arguments
+    /// or values count, a name or alias used by other option or subcommand, or an argument
+    /// that cannot be added to the arguments of the command.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument, MergeConflictKind};
+    ///
+    /// let host = Command::new("MyApp")
+    ///     .option(CommandOption::new("verbose").global(true))
+    ///     .subcommand(Command::new("build").description("Builds the project"));
+    ///
+    /// let plugin = Command::new("MyApp")
+    ///     .subcommand(Command::new("build")
+    ///         .description("Builds the plugin")
+    ///         .option(CommandOption::new("release")))
+    ///     .subcommand(Command::new("deploy"));
+    ///
+    /// let command = host.clone().merge(plugin).unwrap();
+    /// let build = command.find_subcommand("build").unwrap();
+    /// assert_eq!(build.get_description(), Some("Builds the project"));
+    /// assert!(build.get_options().contains("release"));
+    /// assert!(command.find_subcommand("deploy").is_some());
+    ///
+    /// let plugin = Command::new("MyApp")
+    ///     .subcommand(Command::new("build")
+    ///         .option(CommandOption::new("target").arg(Argument::one_or_more("target"))));
+    ///
+    /// let command = host.merge(plugin).unwrap();
+    /// let conflict = command.merge(Command::new("MyApp")
+    ///         .subcommand(Command::new("build")
+    ///             .option(CommandOption::new("target").arg(Argument::with_name("target")))))
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(conflict.get_path(), &["MyApp", "build"]);
+    /// assert_eq!(conflict.kind(), &MergeConflictKind::Option("target".to_owned()));
+    /// ```
+    pub fn merge(self, other: Command) -> std::result::Result<Command, MergeConflict> {
+        let path = vec![self.name.clone()];
+        self.merge_at(other, &path)
+    }
+
+    // Merges `other` into this command, `path` is the names of the commands from the root to this command
+    fn merge_at(mut self, other: Command, path: &[String]) -> std::result::Result<Command, MergeConflict> {
+        let conflict = |kind: MergeConflictKind| MergeConflict {
+            path: path.to_vec(),
+            kind,
+        };
+
+        self.description = self.description.or(other.description);
+        self.summary = self.summary.or(other.summary);
+        self.usage = self.usage.or(other.usage);
+        self.help = self.help.or(other.help);
+        self.before_help = self.before_help.or(other.before_help);
+        self.after_help = self.after_help.or(other.after_help);
+        self.version = self.version.or(other.version);
+        self.handler = self.handler.or(other.handler);
+        self.settings.extend(other.settings);
+
+        for alias in other.aliases {
+            if !self.aliases.contains(&alias) {
+                self.aliases.push(alias);
+            }
+        }
+
+        for alias in other.hidden_aliases {
+            if !self.hidden_aliases.contains(&alias) {
+                self.hidden_aliases.push(alias);
+            }
+        }
+
+        for example in other.examples {
+            if !self.examples.contains(&example) {
+                self.examples.push(example);
+            }
+        }
+
+        for option in other.options {
+            match self.options.get_by_name(option.get_name()) {
+                Some(current) if is_same_option(current, &option) => {}
+                Some(_) => return Err(conflict(MergeConflictKind::Option(option.get_name().to_owned()))),
+                None => {
+                    if let Err(option) = self.options.add(option) {
+                        return Err(conflict(MergeConflictKind::Option(option.get_name().to_owned())));
+                    }
+                }
+            }
+        }
+
+        for arg in other.args {
+            match self.args.get(arg.get_name()) {
+                Some(current) if current.get_values_count() == arg.get_values_count() => {}
+                Some(_) => return Err(conflict(MergeConflictKind::Arg(arg.get_name().to_owned()))),
+                None if self.args.can_add(&arg) => self.args.add(arg).unwrap(),
+                None => return Err(conflict(MergeConflictKind::Arg(arg.get_name().to_owned()))),
+            }
+        }
+
+        for subcommand in other.subcommands {
+            if let Some(index) = self.subcommands.iter().position(|c| c.name == subcommand.name) {
+                let mut subcommand_path = path.to_vec();
+                subcommand_path.push(subcommand.name.clone());
+
+                let current = self.subcommands.remove(index);
+                let merged = current.merge_at(subcommand, &subcommand_path)?;
+                self.subcommands.insert(index, merged);
+            } else if self.is_subcommand_duplicate(&subcommand) {
+                return Err(conflict(MergeConflictKind::Subcommand(subcommand.name)));
+            } else {
+                self.subcommands.push(subcommand);
+            }
+        }
+
+        self.usage_cache.clear();
+        Ok(self)
+    }
+
     pub(crate) fn add_command(&mut self, command: Command) {
-        if self.subcommands.contains(&command) {
+        if self.find_subcommand(command.get_name()).is_some() {
             panic!(
                 "command `{}` already contains a subcommand named: `{}`",
                 self.name,
@@ -412,7 +1060,32 @@ impl Command {
             );
         }
 
-        self.subcommands.push(command)
+        if let Some(alias) = command.get_all_aliases().find(|alias| self.find_subcommand(alias).is_some()) {
+            panic!(
+                "command `{}` already contains a subcommand with alias: `{}`",
+                self.name, alias
+            );
+        }
+
+        self.subcommands.push(command);
+        self.usage_cache.clear();
+    }
+
+    // Returns `true` if the name or any alias of the command is used by a subcommand
+    fn is_subcommand_duplicate(&self, command: &Command) -> bool {
+        std::iter::once(command.get_name())
+            .chain(command.get_all_aliases().map(|s| s.as_str()))
+            .any(|name| self.find_subcommand(name).is_some())
+    }
+
+    // Applies the inherited settings of the parent to this command and its subcommands.
+    pub(crate) fn inherit_settings(&mut self, inherited: CommandSettings) {
+        self.settings.extend(inherited);
+        let inherited = self.settings.inherited();
+
+        for subcommand in self.subcommands.iter_mut() {
+            subcommand.inherit_settings(inherited);
+        }
     }
 
     pub(crate) fn add_option(&mut self, option: CommandOption) {
@@ -424,7 +1097,7 @@ impl Command {
                     duplicated.get_name()
                 );
             } else {
-                for alias in duplicated.get_aliases() {
+                for alias in duplicated.get_all_aliases() {
                     if self.options.contains(alias) {
                         panic!(
                             "command `{}` already contains an option with alias: `{}`",
@@ -436,13 +1109,69 @@ impl Command {
                 unreachable!()
             }
         }
+
+        self.usage_cache.clear();
+    }
+
+    // Panics if an option of this command or its subcommands conflicts with other option
+    // of the same command using the given `NameCase`.
+    pub(crate) fn validate(&self, case: NameCase) {
+        if let Some(duplicated) = self.options.find_duplicate(case) {
+            panic!(
+                "command `{}` already contains an option named or aliased like: `{}`",
+                self.name,
+                duplicated.get_name()
+            );
+        }
+
+        // A name cannot be both an option and the prefix of a dotted option, like `db` and `db.host`
+        for option in self.options.iter() {
+            let prefix = format!("{}.", case.fold(option.get_name()));
+            if let Some(nested) = self.options.iter().find(|o| case.fold(o.get_name()).starts_with(&prefix)) {
+                panic!(
+                    "command `{}` option `{}` conflicts with the nested option `{}`",
+                    self.name,
+                    option.get_name(),
+                    nested.get_name()
+                );
+            }
+        }
+
+        for subcommand in &self.subcommands {
+            subcommand.validate(case);
+        }
+    }
+
+    // Sets how the option names and aliases of this command and its subcommands are compared,
+    // must be called after `validate` with the same `NameCase`.
+    pub(crate) fn set_option_case(&mut self, case: NameCase) {
+        self.options.set_case(case);
+
+        for subcommand in &mut self.subcommands {
+            subcommand.set_option_case(case);
+        }
+    }
+
+    // Returns the usage generated by the `help`, the usage is computed once until this command changes.
+    pub(crate) fn get_or_init_usage<F: FnOnce() -> String>(&self, f: F) -> &str {
+        self.usage_cache.0.get_or_init(f)
+    }
+
+    // Clears the usage of this command and its subcommands, must be called when the `Context` changes
+    // because the usage depends on its prefixes and `HelpStyle`.
+    pub(crate) fn clear_usage_cache(&mut self) {
+        self.usage_cache.clear();
+
+        for subcommand in &mut self.subcommands {
+            subcommand.clear_usage_cache();
+        }
     }
 
     //////////////////////////////////////////////////
     //             Utility Parse Methods            //
     //////////////////////////////////////////////////
 
-    /// Parse the arguments from `std::env::args` using this command and returns the `ParseResult`.
+    /// Parse the arguments from `std::env::args_os` using this command and returns the `ParseResult`.
     ///
     /// # Example:
     /// ```no_run
@@ -491,6 +1220,34 @@ impl Command {
             .use_default_suggestions()
             .parse_from(args)
     }
+
+    /// Parse the `OsString` arguments using this command and returns the `ParseResult`.
+    ///
+    /// The arguments that are not valid UTF-8 returns an `ErrorKind::InvalidUtf8` error,
+    /// unless are values of an `Argument` that allows invalid UTF-8, see `Argument::allow_invalid_utf8`.
+    ///
+    /// # Example:
+    /// ```
+    /// use clapi::{Command, Argument};
+    /// use std::ffi::OsString;
+    ///
+    /// let result = Command::root()
+    ///     .arg(Argument::one_or_more("files"))
+    ///     .try_parse_from_os(vec![OsString::from("a.txt"), OsString::from("b.txt")])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.arg().unwrap().get_values(), &["a.txt", "b.txt"]);
+    /// ```
+    #[inline]
+    pub fn try_parse_from_os<I, S>(self, args: I) -> Result<ParseResult>
+        where
+            I: IntoIterator<Item = S>,
+            S: Into<OsString> {
+        CommandLine::new(self)
+            .use_default_help()
+            .use_default_suggestions()
+            .parse_from_os(args)
+    }
 }
 
 impl Eq for Command {}
@@ -513,9 +1270,15 @@ impl Debug for Command {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Command")
             .field("name", &self.get_name())
+            .field("aliases", &self.aliases)
+            .field("hidden_aliases", &self.hidden_aliases)
             .field("description", &self.get_description())
-            .field("about", &self.get_usage())
-            .field("help", &self.get_help())
+            .field("summary", &self.summary)
+            .field("about", &self.usage)
+            .field("help", &self.help)
+            .field("before_help", &self.before_help)
+            .field("after_help", &self.after_help)
+            .field("examples", &self.get_examples())
             .field("options", &self.get_options())
             .field("arguments", &self.get_args())
             .field(
@@ -525,12 +1288,143 @@ impl Debug for Command {
                     "FnMut(&OptionList, &ArgumentList) -> Result<()>",
                 ),
             )
+            .field("help_provider", &debug_option(&self.help_provider, "HelpSource"))
+            .field("suggestion_provider", &self.suggestion_provider)
             .field("is_hidden", &self.is_hidden())
+            .field("settings", &self.get_settings())
             .field("children", &self.get_subcommands())
             .finish()
     }
 }
 
+/// A conflict found while merging two commands with `Command::merge`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MergeConflict {
+    path: Vec<String>,
+    kind: MergeConflictKind,
+}
+
+/// The definition that conflicts in a `MergeConflict`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MergeConflictKind {
+    /// An option with the same name but other definition, or which name or alias is used by other option.
+    Option(String),
+    /// An argument with the same name but other values count, or that cannot be added to the arguments
+    /// of the command, like a second argument that takes a variable number of values.
+    Arg(String),
+    /// A subcommand which name or alias is used by other subcommand.
+    Subcommand(String),
+}
+
+impl MergeConflict {
+    /// Returns the names of the commands from the root to the command where the conflict was found.
+    pub fn get_path(&self) -> &[String] {
+        self.path.as_slice()
+    }
+
+    /// Returns the definition that conflicts.
+    pub fn kind(&self) -> &MergeConflictKind {
+        &self.kind
+    }
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.join(" ");
+        match &self.kind {
+            MergeConflictKind::Option(name) => write!(f, "conflicting option '{}' in '{}'", name, path),
+            MergeConflictKind::Arg(name) => write!(f, "conflicting argument '{}' in '{}'", name, path),
+            MergeConflictKind::Subcommand(name) => write!(f, "conflicting subcommand '{}' in '{}'", name, path),
+        }
+    }
+}
+
+impl std::error::Error for MergeConflict {}
+
+// Returns `true` if both options take the same arguments and are both global or not
+fn is_same_option(option: &CommandOption, other: &CommandOption) -> bool {
+    option.is_global() == other.is_global()
+        && option.get_args().len() == other.get_args().len()
+        && option
+            .get_args()
+            .iter()
+            .zip(other.get_args().iter())
+            .all(|(a, b)| a.get_name() == b.get_name() && a.get_values_count() == b.get_values_count())
+}
+
+// Returns the text up to the first period followed by a whitespace, or all the text if none
+fn first_sentence(text: &str) -> &str {
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if c == '.' && chars.peek().is_some_and(|(_, next)| next.is_whitespace()) {
+            return &text[..=index];
+        }
+    }
+
+    text
+}
+
+// The usage generated for a command, clones start empty because the cloned command
+// may be used with other `Context`.
+#[derive(Default)]
+struct UsageCache(OnceCell<String>);
+
+impl UsageCache {
+    fn clear(&mut self) {
+        self.0.take();
+    }
+}
+
+impl Clone for UsageCache {
+    fn clone(&self) -> Self {
+        UsageCache::default()
+    }
+}
+
+// A string that may be provided by a function, which is called on first use.
+// Clones share the evaluated value.
+#[derive(Clone)]
+struct LazyString {
+    value: Rc<OnceCell<String>>,
+    f: Option<Rc<dyn Fn() -> String>>,
+}
+
+impl LazyString {
+    fn new(value: String) -> Self {
+        LazyString {
+            value: Rc::new(OnceCell::from(value)),
+            f: None,
+        }
+    }
+
+    fn with<F: Fn() -> String + 'static>(f: F) -> Self {
+        LazyString {
+            value: Rc::new(OnceCell::new()),
+            f: Some(Rc::new(f)),
+        }
+    }
+
+    fn get(&self) -> &str {
+        self.value.get_or_init(|| (self.f.as_ref().unwrap())())
+    }
+
+    #[cfg(feature = "serde")]
+    fn is_fn(&self) -> bool {
+        self.f.is_some()
+    }
+}
+
+impl Debug for LazyString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Debug don't evaluate the function
+        match self.value.get() {
+            Some(value) => write!(f, "{:?}", value),
+            None => write!(f, "Fn() -> String"),
+        }
+    }
+}
+
 /// An iterator over the subcommands of a `Command`.
 #[derive(Debug, Clone)]
 pub struct Iter<'a> {
@@ -588,6 +1482,52 @@ mod tests {
     use super::*;
     use std::ops::DerefMut;
 
+    #[test]
+    fn find_subcommand_mut_test() {
+        let mut command = Command::new("MyApp")
+            .subcommand(Command::new("data").alias("d").subcommand(Command::new("set").alias("s")))
+            .subcommand(Command::new("version"));
+
+        assert_eq!(command.find_subcommand_mut("version").unwrap().get_name(), "version");
+        assert_eq!(command.find_subcommand_mut("data.set").unwrap().get_name(), "set");
+        assert_eq!(command.find_subcommand_mut("d.s").unwrap().get_name(), "set");
+        assert!(command.find_subcommand_mut("data.get").is_none());
+        assert!(command.find_subcommand_mut("set").is_none());
+        assert!(command.find_subcommand_mut("").is_none());
+        assert!(command.find_subcommand_mut("data.").is_none());
+    }
+
+    #[test]
+    fn set_handler_test() {
+        let called = Rc::new(RefCell::new(None));
+        let mut command = Command::new("MyApp")
+            .subcommand(Command::new("data").subcommand(Command::new("set").arg(Argument::with_name("value"))));
+
+        let values = called.clone();
+        command.find_subcommand_mut("data.set").unwrap().set_handler(move |_, args| {
+            *values.borrow_mut() = Some(args.get_raw_args().into_vec());
+            Ok(())
+        });
+
+        crate::CommandLine::new(command).run_from(vec!["data", "set", "10"]).unwrap();
+        assert_eq!(called.take(), Some(vec!["10".to_owned()]));
+    }
+
+    #[test]
+    fn summary_test() {
+        assert_eq!(Command::new("build").get_summary(), None);
+
+        let command = Command::new("build").description("Builds version 1.0 of the project");
+        assert_eq!(command.get_summary(), Some("Builds version 1.0 of the project"));
+
+        let command = Command::new("build").description("Builds the project.\nUses the release profile.");
+        assert_eq!(command.get_summary(), Some("Builds the project."));
+
+        let command = command.summary("Builds");
+        assert_eq!(command.get_summary(), Some("Builds"));
+        assert_eq!(command.get_description(), Some("Builds the project.\nUses the release profile."));
+    }
+
     #[test]
     fn command_test1() {
         let cmd = Command::new("time")
@@ -599,6 +1539,38 @@ mod tests {
         assert_eq!(cmd.get_usage(), Some("Sets the time or show it"));
     }
 
+    #[test]
+    fn command_usage_and_help_with_test() {
+        use std::cell::Cell;
+
+        let calls = Rc::new(Cell::new(0));
+        let cmd = Command::new("time")
+            .usage_with({
+                let calls = calls.clone();
+                move || {
+                    calls.set(calls.get() + 1);
+                    "Sets the time or show it".to_string()
+                }
+            })
+            .help_with(|| "time [OPTIONS]".to_string());
+
+        let cloned = cmd.clone();
+        assert_eq!(calls.get(), 0);
+        assert!(format!("{:?}", cmd).contains("Fn() -> String"));
+
+        assert_eq!(cmd.get_usage(), Some("Sets the time or show it"));
+        assert_eq!(cmd.get_usage(), Some("Sets the time or show it"));
+        assert_eq!(cloned.get_usage(), Some("Sets the time or show it"));
+        assert_eq!(cmd.get_help(), Some("time [OPTIONS]"));
+        assert_eq!(calls.get(), 1);
+
+        #[cfg(feature = "serde")]
+        {
+            assert!(cmd.is_usage_fn());
+            assert!(cmd.is_help_fn());
+        }
+    }
+
     #[test]
     #[should_panic(expected="command `name` cannot be empty")]
     fn command_empty_name_test() {
@@ -610,6 +1582,12 @@ mod tests {
         Command::new("My App");
     }
 
+    #[test]
+    #[should_panic(expected = "command `name` cannot start with `-`: `-app`")]
+    fn command_name_with_prefix_test() {
+        Command::new("-app");
+    }
+
     #[test]
     fn children_test() {
         let cmd = Command::new("data")
@@ -639,6 +1617,84 @@ mod tests {
             .subcommand(Command::new("get"));
     }
 
+    #[test]
+    fn try_subcommand_test() {
+        let cmd = Command::new("data")
+            .try_subcommand(Command::new("set").description("Sets a value"))
+            .unwrap();
+
+        let (cmd, rejected) = cmd.try_subcommand(Command::new("set")).unwrap_err();
+        assert_eq!(rejected.get_name(), "set");
+        assert_eq!(cmd.get_subcommands().count(), 1);
+        assert_eq!(cmd.find_subcommand("set").unwrap().get_description(), Some("Sets a value"));
+    }
+
+    #[test]
+    fn subcommand_alias_test() {
+        let cmd = Command::new("pkg")
+            .subcommand(Command::new("install").alias("i").hidden_alias("add"));
+
+        assert_eq!(cmd.find_subcommand("i").unwrap().get_name(), "install");
+        assert_eq!(cmd.find_subcommand("add").unwrap().get_name(), "install");
+
+        let sub = cmd.find_subcommand("install").unwrap();
+        assert_eq!(sub.get_aliases().cloned().collect::<Vec<String>>(), vec!["i".to_owned()]);
+        assert_eq!(sub.get_hidden_aliases().cloned().collect::<Vec<String>>(), vec!["add".to_owned()]);
+        assert!(sub.has_alias("i"));
+        assert!(sub.has_alias("add"));
+        assert!(!sub.has_alias("install"));
+
+        let (cmd, rejected) = cmd.try_subcommand(Command::new("i")).unwrap_err();
+        assert_eq!(rejected.get_name(), "i");
+
+        let (_, rejected) = cmd.try_subcommand(Command::new("remove").alias("add")).unwrap_err();
+        assert_eq!(rejected.get_name(), "remove");
+    }
+
+    #[test]
+    #[should_panic(expected="`pkg` already contains a subcommand with alias: `i`")]
+    fn duplicated_command_alias_test() {
+        Command::new("pkg")
+            .subcommand(Command::new("install").alias("i"))
+            .subcommand(Command::new("init").alias("i"));
+    }
+
+    #[test]
+    fn try_option_test() {
+        let cmd = Command::new("time")
+            .try_option(CommandOption::new("version").alias("v"))
+            .unwrap();
+
+        let (cmd, rejected) = cmd.try_option(CommandOption::new("verbose").alias("v")).unwrap_err();
+        assert_eq!(rejected.get_name(), "verbose");
+        assert_eq!(cmd.get_options().len(), 1);
+
+        let (cmd, rejected) = cmd.try_option(CommandOption::new("v")).unwrap_err();
+        assert_eq!(rejected.get_name(), "v");
+
+        let cmd = cmd.try_option(CommandOption::new("V")).unwrap();
+        assert_eq!(cmd.get_options().len(), 2);
+    }
+
+    #[test]
+    fn setting_test() {
+        let cmd = Command::new("exec")
+            .setting(Setting::TreatEooAsValue)
+            .allow_empty_invocation(true);
+
+        assert!(cmd.has_setting(Setting::TreatEooAsValue));
+        assert!(cmd.is_eoo_treated_as_value());
+        assert!(cmd.has_setting(Setting::AllowEmptyInvocation));
+
+        let cmd = cmd
+            .unset_setting(Setting::AllowEmptyInvocation)
+            .treat_eoo_as_value(false);
+
+        assert!(!cmd.is_empty_invocation_allowed());
+        assert!(!cmd.has_setting(Setting::TreatEooAsValue));
+        assert!(cmd.get_settings().is_empty());
+    }
+
     #[test]
     fn option_test() {
         let cmd = Command::new("time")
@@ -708,4 +1764,87 @@ mod tests {
 
         assert_eq!(unsafe { VALUE }, 2);
     }
+
+    #[test]
+    fn merge_test() {
+        let host = Command::new("MyApp")
+            .description("Host app")
+            .option(CommandOption::new("verbose").global(true))
+            .subcommand(Command::new("build")
+                .description("Builds the project")
+                .option(CommandOption::new("target").arg(Argument::with_name("target")))
+                .subcommand(Command::new("docs")));
+
+        let plugin = Command::new("plugin")
+            .description("Plugin app")
+            .version("2.0")
+            .option(CommandOption::new("verbose").global(true))
+            .option(CommandOption::new("color").global(true).arg(Argument::with_name("color")))
+            .subcommand(Command::new("build")
+                .description("Builds the plugin")
+                .alias("b")
+                .option(CommandOption::new("target").arg(Argument::with_name("target")))
+                .subcommand(Command::new("docs").option(CommandOption::new("open")))
+                .handler(|_, _| Ok(())))
+            .subcommand(Command::new("deploy").arg(Argument::one_or_more("servers")));
+
+        let command = host.merge(plugin).unwrap();
+        assert_eq!(command.get_name(), "MyApp");
+        assert_eq!(command.get_description(), Some("Host app"));
+        assert_eq!(command.get_version(), Some("2.0"));
+        assert!(command.get_options().get("verbose").unwrap().is_global());
+        assert!(command.get_options().get("color").unwrap().is_global());
+
+        let build = command.find_subcommand("b").unwrap();
+        assert_eq!(build.get_description(), Some("Builds the project"));
+        assert!(build.get_handler().is_some());
+        assert_eq!(build.get_options().len(), 1);
+        assert!(build.find_subcommand("docs").unwrap().get_options().contains("open"));
+
+        let deploy = command.find_subcommand("deploy").unwrap();
+        assert_eq!(deploy.get_arg().unwrap().get_name(), "servers");
+        assert_eq!(command.get_subcommands().count(), 2);
+    }
+
+    #[test]
+    fn merge_conflict_test() {
+        let host = Command::new("MyApp")
+            .option(CommandOption::new("color").alias("c").arg(Argument::with_name("color")))
+            .subcommand(Command::new("build")
+                .alias("b")
+                .arg(Argument::zero_or_more("files")));
+
+        let merge = |other: Command| host.clone().merge(other).unwrap_err();
+
+        // Different arity
+        let conflict = merge(Command::new("MyApp").option(CommandOption::new("color")));
+        assert_eq!(conflict.get_path(), &["MyApp"]);
+        assert_eq!(conflict.kind(), &MergeConflictKind::Option("color".to_owned()));
+        assert_eq!(conflict.to_string(), "conflicting option 'color' in 'MyApp'");
+
+        // Global and not global
+        let conflict = merge(Command::new("MyApp")
+            .option(CommandOption::new("color").global(true).arg(Argument::with_name("color"))));
+        assert_eq!(conflict.kind(), &MergeConflictKind::Option("color".to_owned()));
+
+        // Alias used by other option
+        let conflict = merge(Command::new("MyApp").option(CommandOption::new("count").alias("c")));
+        assert_eq!(conflict.kind(), &MergeConflictKind::Option("count".to_owned()));
+
+        // Alias used by other subcommand
+        let conflict = merge(Command::new("MyApp").subcommand(Command::new("bench").alias("b")));
+        assert_eq!(conflict.kind(), &MergeConflictKind::Subcommand("bench".to_owned()));
+
+        // Different values count
+        let conflict = merge(Command::new("MyApp")
+            .subcommand(Command::new("build").arg(Argument::one_or_more("files"))));
+        assert_eq!(conflict.get_path(), &["MyApp", "build"]);
+        assert_eq!(conflict.kind(), &MergeConflictKind::Arg("files".to_owned()));
+        assert_eq!(conflict.to_string(), "conflicting argument 'files' in 'MyApp build'");
+
+        // Second argument with variable values
+        let conflict = merge(Command::new("MyApp")
+            .subcommand(Command::new("build").arg(Argument::zero_or_more("flags"))));
+        assert_eq!(conflict.kind(), &MergeConflictKind::Arg("flags".to_owned()));
+    }
 }
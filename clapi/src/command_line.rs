@@ -1,18 +1,28 @@
 #![allow(clippy::len_zero)]
 use crate::command::Command;
+use crate::completions::Shell;
 use crate::context::Context;
+use crate::doctor::ReportFormat;
 use crate::error::{Error, ErrorKind, Result};
-use crate::help::HelpSource;
+use crate::help::{HelpSource, HelpStyle};
+use crate::inject::Provided;
 use crate::parser::Parser;
+use crate::settings::Setting;
 use crate::suggestion::SuggestionSource;
-use crate::{Argument, CommandOption, OptionList, ParseResult};
-use std::borrow::Borrow;
-use std::fmt::Debug;
+use crate::utils::write_line;
+use crate::{CommandOption, OverflowPolicy, ParseResult};
+use std::borrow::{Borrow, Cow};
+use std::ffi::OsString;
+use std::fmt::{Debug, Formatter};
+use std::io::Write;
 
 /// Represents a command-line app.
-#[derive(Debug)]
 pub struct CommandLine {
     context: Context,
+    stdout: Box<dyn Write>,
+    stderr: Box<dyn Write>,
+    provided: Provided,
+    continue_on_error: bool,
 }
 
 impl CommandLine {
@@ -29,7 +39,53 @@ impl CommandLine {
             context.set_version_option(crate::default_version_option());
         }
 
-        CommandLine { context }
+        CommandLine {
+            context,
+            stdout: Box::new(std::io::stdout()),
+            stderr: Box::new(std::io::stderr()),
+            provided: Provided::default(),
+            continue_on_error: false,
+        }
+    }
+
+    /// Sets the writer used for the help and version messages, by default `stdout`.
+    ///
+    /// Only affects the messages written by this command-line, handlers still write wherever they want.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine};
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp").version("1.0"))
+    ///     .with_stdout(std::io::sink());
+    ///
+    /// // The version is written to the sink
+    /// assert!(command_line.run_from(vec!["--version"]).is_ok());
+    /// ```
+    pub fn with_stdout<W: Write + 'static>(mut self, stdout: W) -> Self {
+        self.stdout = Box::new(stdout);
+        self
+    }
+
+    /// Sets the writer used for the error messages, by default `stderr`.
+    pub fn with_stderr<W: Write + 'static>(mut self, stderr: W) -> Self {
+        self.stderr = Box::new(stderr);
+        self
+    }
+
+    /// Sets whether `run_all` continues running the next commands after a command fails,
+    /// the errors are written to the `stderr` and the first one is returned at the end.
+    pub fn continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Provides a value to the command handlers, which is obtained using `clapi::inject::<T>()`.
+    ///
+    /// If a value of the same type was already provided is replaced.
+    pub fn provide<T: Clone + 'static>(mut self, value: T) -> Self {
+        self.provided.insert(value);
+        self
     }
 
     /// Returns the `Context` used by this command-line.
@@ -48,6 +104,8 @@ impl CommandLine {
     }
 
     /// Sets the default `Help`.
+    ///
+    /// A `help` subcommand, or a `--help` or `-h` option declared in the commands is used instead of the default.
     pub fn use_default_help(mut self) -> Self {
         self.context
             .set_help_option(crate::context::default_help_option());
@@ -62,14 +120,21 @@ impl CommandLine {
     }
 
     /// Sets the `SuggestionSource` of this command-line context.
+    ///
+    /// The suggestions use the provider of the executing command or its nearest parent
+    /// that have one, see `Command::suggestion_provider`, and then this one.
     pub fn use_suggestions(mut self, suggestions: SuggestionSource) -> Self {
         self.context.set_suggestions(suggestions);
         self
     }
 
-    /// Sets the `HelpSource` of this command-line context.
-    pub fn use_help(mut self, help: HelpSource) -> Self {
-        self.context.set_help(help);
+    /// Sets the `HelpSource` of this command-line context, like a `ColoredHelp`
+    /// when the `color` feature is enable.
+    ///
+    /// The help messages use the provider of the command or its nearest parent
+    /// that have one, see `Command::help_provider`, and then this one.
+    pub fn use_help<H: Into<HelpSource>>(mut self, help: H) -> Self {
+        self.context.set_help(help.into());
         self
     }
 
@@ -97,11 +162,148 @@ impl CommandLine {
         self
     }
 
+    /// Sets the prefix of the environment variables derived from the option names,
+    /// each option takes its value from `<PREFIX>_<OPTION_NAME>` when is not passed.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine, CommandOption, Argument};
+    ///
+    /// std::env::set_var("MYAPP_DRY_RUN", "true");
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp")
+    ///     .option(CommandOption::new("dry-run").arg(Argument::new().values_count(0..=1))))
+    ///     .env_prefix("MYAPP");
+    ///
+    /// let result = command_line.parse_from(Vec::<String>::new()).unwrap();
+    /// assert!(result.options().get_arg("dry-run").unwrap().contains("true"));
+    /// ```
+    pub fn env_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.context.set_env_prefix(prefix);
+        self
+    }
+
+    /// Sets the option name prefix, by default is `--`.
+    ///
+    /// The tokenizer, the help messages and the suggestions use the prefixes of the `Context`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine, CommandOption, Argument};
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp")
+    ///     .option(CommandOption::new("out").alias("o").arg(Argument::with_name("file"))))
+    ///     .name_prefix("/")
+    ///     .alias_prefix("/")
+    ///     .assign_operator(':');
+    ///
+    /// let result = command_line.parse_from(vec!["/out:file.txt"]).unwrap();
+    /// assert!(result.options().get_arg("out").unwrap().contains("file.txt"));
+    /// assert!(command_line.parse_from(vec!["--out", "file.txt"]).is_err());
+    /// ```
+    pub fn name_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.context.set_name_prefix(prefix);
+        self
+    }
+
+    /// Sets the option alias prefix, by default is `-`.
+    pub fn alias_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.context.set_alias_prefix(prefix);
+        self
+    }
+
+    /// Sets the assign operator of the options, by default is `=`.
+    pub fn assign_operator(mut self, value: char) -> Self {
+        self.context.set_assign_operator(value);
+        self
+    }
+
+    /// Sets the token used to separate several commands in one invocation,
+    /// the commands are run using `run_all`.
+    pub fn command_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.context.set_command_separator(separator);
+        self
+    }
+
+    /// Sets whether bundled aliases like `-abc` are expanded to `-a -b -c`.
+    pub fn allow_option_bundling(mut self, allow: bool) -> Self {
+        self.context.set_allow_option_bundling(allow);
+        self
+    }
+
+    /// Sets whether the parser reports all the errors found instead of only the first one.
+    pub fn collect_errors(mut self, collect: bool) -> Self {
+        self.context.set_collect_errors(collect);
+        self
+    }
+
+    /// Sets whether the parser records the `ParseMetrics` of each parse, see `ContextBuilder::metrics`.
+    pub fn metrics(mut self, enabled: bool) -> Self {
+        self.context.set_metrics(enabled);
+        self
+    }
+
+    /// Sets the `HelpStyle` used for the help messages.
+    pub fn help_style(mut self, style: HelpStyle) -> Self {
+        self.context.set_help_style(style);
+        self
+    }
+
+    /// Adds a hidden `__doctor` subcommand that writes a report of the configuration of this command-line
+    /// to the `stdout`: the version, the enabled features, the prefixes and assign operators,
+    /// the environment variables consulted and a summary of each command.
+    ///
+    /// Use `__doctor --format json` to write the report as JSON, see `clapi::doctor::write_report`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine};
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp").version("1.0"))
+    ///     .use_doctor()
+    ///     .with_stdout(std::io::sink());
+    ///
+    /// assert!(command_line.run_from(vec!["__doctor", "--format", "json"]).is_ok());
+    /// ```
+    pub fn use_doctor(mut self) -> Self {
+        self.context.set_doctor_command(crate::doctor::doctor_command());
+        self
+    }
+
+    /// Adds an option available for every command, the handlers receive it with the command options.
+    ///
+    /// See `ContextBuilder::global_option`.
+    pub fn global_option(mut self, option: CommandOption) -> Self {
+        self.context.add_global_option(option);
+        self
+    }
+
+    /// Writes the completion script for the given `Shell` to the `stdout` of this command-line,
+    /// using the prefixes of the `Context`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine};
+    /// use clapi::completions::Shell;
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp"))
+    ///     .with_stdout(std::io::sink());
+    ///
+    /// assert!(command_line.generate_completions(Shell::Zsh).is_ok());
+    /// ```
+    pub fn generate_completions(&mut self, shell: Shell) -> Result<()> {
+        crate::completions::write_completions(&self.context, shell, &mut self.stdout)
+            .and_then(|_| self.stdout.flush())
+            .map_err(|e| Error::new(ErrorKind::Other, e))
+    }
+
     /// Parse the program arguments get the `ParseResult`
     /// after handling any help, version or suggestion messages.
+    ///
+    /// The arguments are read with `std::env::args_os`, see `CommandLine::parse_from_os`.
     #[inline]
     pub fn parse_args(&mut self) -> Result<ParseResult> {
-        self.parse_from(std::env::args().skip(1))
+        self.parse_from_os(std::env::args_os().skip(1))
     }
 
     /// Parse given arguments get the `ParseResult`
@@ -113,17 +315,49 @@ impl CommandLine {
     {
         let mut parser = Parser::new(&self.context);
         let result = parser.parse(args);
+        self.handle_parse_result(&parser, result)
+    }
+
+    /// Parse given `OsString` arguments get the `ParseResult`
+    /// after handling any help, version or suggestion messages.
+    ///
+    /// The arguments that are not valid UTF-8 returns an `ErrorKind::InvalidUtf8` error,
+    /// unless are values of an `Argument` that allows invalid UTF-8.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine, Argument};
+    /// use std::ffi::OsString;
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp")
+    ///     .arg(Argument::with_name("path").allow_invalid_utf8(true)));
+    ///
+    /// let result = command_line.parse_from_os(vec![OsString::from("/tmp")]).unwrap();
+    /// assert_eq!(result.arg().unwrap().get_os_values(), vec![OsString::from("/tmp")]);
+    /// ```
+    pub fn parse_from_os<S, I>(&mut self, args: I) -> Result<ParseResult>
+    where
+        S: Into<OsString>,
+        I: IntoIterator<Item = S>,
+    {
+        let mut parser = Parser::new(&self.context);
+        let result = parser.parse_os(args);
+        self.handle_parse_result(&parser, result)
+    }
+
+    // Returns the `ParseResult` or the error, help or version message to display
+    fn handle_parse_result(&self, parser: &Parser<'_>, result: Result<ParseResult>) -> Result<ParseResult> {
         let parse_result = match result {
             Ok(r) => r,
-            Err(error) => return Err(self.handle_error(&parser, error).unwrap_err()),
+            Err(error) => return Err(self.handle_error(parser, error).unwrap_err()),
         };
 
         // Checks if the command requires to display help
-        if self.requires_help(&parse_result) {
+        if parse_result.is_help_requested() {
             Err(self.handle_help(&parse_result).unwrap_err())
         }
         // Checks if the command requires to display the version
-        else if self.requires_version(&parse_result) {
+        else if parse_result.is_version_requested() {
             Err(self.show_version(&parse_result).unwrap_err())
         } else {
             Ok(parse_result)
@@ -132,11 +366,11 @@ impl CommandLine {
 
     /// Parse the program arguments and runs the app.
     ///
-    /// This is equivalent to `CommandLine::parse_from(std::env::args().skip(1))`.
+    /// This is equivalent to `CommandLine::run_from_os(std::env::args_os().skip(1))`.
     #[inline]
     pub fn run(&mut self) -> Result<()> {
         // We skip the first element that may be the path of the executable
-        self.run_from(std::env::args().skip(1))
+        self.run_from_os(std::env::args_os().skip(1))
     }
 
     /// Parses the given arguments and runs the app.
@@ -145,24 +379,26 @@ impl CommandLine {
         S: Borrow<str>,
         I: IntoIterator<Item = S>,
     {
-        fn print_help_or_version(error: Error) -> Result<()> {
-            match error.kind() {
-                ErrorKind::DisplayHelp(s) | ErrorKind::DisplayVersion(s) => {
-                    println!("{}", s);
-                    Ok(())
-                }
-                _ => unreachable!(),
-            }
-        }
+        let result = self.parse_from(args);
+        self.run_parse_result(result)
+    }
+
+    /// Parses the given `OsString` arguments and runs the app.
+    pub fn run_from_os<S, I>(&mut self, args: I) -> Result<()>
+    where
+        S: Into<OsString>,
+        I: IntoIterator<Item = S>,
+    {
+        let result = self.parse_from_os(args);
+        self.run_parse_result(result)
+    }
 
-        // Parse the arguments and get the result
-        let parse_result = match self.parse_from(args) {
+    // Runs the handler of the executing command, or displays the help, version or error message
+    fn run_parse_result(&mut self, result: Result<ParseResult>) -> Result<()> {
+        let parse_result = match result {
             Err(err) => {
-                return if matches!(
-                    err.kind(),
-                    ErrorKind::DisplayHelp(_) | ErrorKind::DisplayVersion(_)
-                ) {
-                    print_help_or_version(err)
+                return if err.is_display_message() {
+                    self.print_error(&err)
                 } else {
                     Err(err)
                 }
@@ -170,15 +406,71 @@ impl CommandLine {
             Ok(x) => x,
         };
 
+        // Warns about the values ignored by a lenient parser before run the command
+        if !parse_result.ignored().is_empty() {
+            let ignored = parse_result
+                .ignored()
+                .iter()
+                .map(|s| format!("'{}'", s))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            write_line(&mut self.stderr, format_args!("Warning: ignored unknown arguments: {}", ignored))?;
+        }
+
+        // Warns about the values truncated by the `OverflowPolicy` of the arguments
+        let truncated_args = parse_result
+            .options()
+            .iter()
+            .chain(parse_result.global_options().iter())
+            .flat_map(|option| option.get_args().iter())
+            .chain(parse_result.args().iter())
+            .filter(|arg| arg.get_overflow_policy() == OverflowPolicy::TruncateWarn)
+            .filter(|arg| !arg.get_overflow_values().is_empty());
+
+        for arg in truncated_args {
+            let truncated = arg
+                .get_overflow_values()
+                .iter()
+                .map(|s| format!("'{}'", s))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            write_line(&mut self.stderr, format_args!("Warning: ignored extra values for `{}`: {}", arg.get_name(), truncated))?;
+        }
+
+        // The doctor command writes the report of the context instead of running a handler
+        if self.is_doctor_command(parse_result.executing_command()) {
+            let format = parse_result
+                .options()
+                .get_arg("format")
+                .and_then(|arg| arg.get_values().first())
+                .and_then(|s| s.parse::<ReportFormat>().ok())
+                .unwrap_or(ReportFormat::Text);
+
+            return crate::doctor::write_report_with_metrics(&self.context, parse_result.metrics(), format, &mut self.stdout)
+                .map_err(|e| Error::new(ErrorKind::Other, e));
+        }
+
         // We borrow the value from the Option to avoid create a temporary
         let handler = parse_result.executing_command().get_handler();
 
         if let Some(mut handler) = handler {
-            let options = parse_result.options();
+            // The handlers receive the global options of the context with the command options
+            let options = if parse_result.global_options().is_empty() {
+                Cow::Borrowed(parse_result.options())
+            } else {
+                let mut options = parse_result.options().clone();
+                for option in parse_result.global_options() {
+                    options.add_or_replace(option.clone());
+                }
+                Cow::Owned(options)
+            };
+
             let args = parse_result.args();
 
             // Calls the handler and pass the arguments
-            match (*handler)(options, args) {
+            match self.provided.scope(|| (*handler)(&options, args)) {
                 Ok(_) => Ok(()),
                 Err(error) => {
                     // Special case, the caller can returns `ErrorKind::FallthroughHelp`
@@ -191,78 +483,280 @@ impl CommandLine {
                 }
             }
         } else {
+            let command = parse_result.executing_command();
+
+            // Commands that only group subcommands requires one of them
+            if self.is_namespace_command(command) {
+                let help = self.get_help_message(Some(parse_result.path()), MessageKind::Help)?;
+
+                return Err(Error::from(ErrorKind::MissingSubcommand(command.get_name().to_string()))
+                    .with_message(format!("\n\n{}", help.trim_end())));
+            }
+
             // Shows a help message if there is no handler
-            print_help_or_version(self.display_help(None).unwrap_err())
+            let error = self.display_help(None).unwrap_err();
+            self.print_error(&error)
         }
     }
 
-    fn handle_error(&self, parser: &Parser<'_>, error: Error) -> Result<()> {
-        // `Err` was decided initially due using an invalid `command` or `argument` is an error
-        match error.kind() {
-            ErrorKind::InvalidArgumentCount | ErrorKind::InvalidArgument(_)
-                if self.context.help_option().is_some()
-                    || self.context.help_command().is_some() =>
-            {
-                Err(error.with_message(self.get_help_message(None, MessageKind::Usage)?))
-            }
-            ErrorKind::UnexpectedOption(_) if self.suggestions().is_some() => {
-                self.display_option_suggestions(parser, error)
-            }
-            ErrorKind::UnexpectedCommand(_) if self.suggestions().is_some() => {
-                self.display_command_suggestions(parser, error)
-            }
-            _ => Err(error),
-        }
+    /// Parse the program arguments, runs the app and returns the exit code instead of exiting.
+    ///
+    /// This is equivalent to `CommandLine::try_run_from_os(std::env::args_os().skip(1))`.
+    #[inline]
+    pub fn try_run(&mut self) -> Result<i32> {
+        self.try_run_from_os(std::env::args_os().skip(1))
+    }
+
+    /// Parses the given arguments, runs the app and returns the exit code instead of exiting,
+    /// any error is written to the `stderr` of this command-line.
+    ///
+    /// Fails only if the error cannot be written, see `Error::exit_code` for the codes.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine, CommandOption};
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp")
+    ///         .option(CommandOption::new("verbose"))
+    ///         .handler(|_, _| Ok(())))
+    ///     .with_stderr(std::io::sink());
+    ///
+    /// assert_eq!(command_line.try_run_from(vec!["--verbose"]).unwrap(), 0);
+    /// assert_eq!(command_line.try_run_from(vec!["--color"]).unwrap(), 2);
+    /// ```
+    pub fn try_run_from<S, I>(&mut self, args: I) -> Result<i32>
+    where
+        S: Borrow<str>,
+        I: IntoIterator<Item = S>,
+    {
+        let result = self.run_from(args);
+        self.exit_code_of(result)
     }
 
-    fn requires_version(&self, result: &ParseResult) -> bool {
-        if let Some(version_option) = self.context.version_option() {
-            if result.options().contains(version_option.get_name()) {
-                return true;
+    /// Parses the given `OsString` arguments, runs the app and returns the exit code instead of exiting,
+    /// any error is written to the `stderr` of this command-line.
+    pub fn try_run_from_os<S, I>(&mut self, args: I) -> Result<i32>
+    where
+        S: Into<OsString>,
+        I: IntoIterator<Item = S>,
+    {
+        let result = self.run_from_os(args);
+        self.exit_code_of(result)
+    }
+
+    // Returns the exit code of the result, writing the error to the `stderr`
+    fn exit_code_of(&mut self, result: Result<()>) -> Result<i32> {
+        match result {
+            Ok(()) => Ok(0),
+            Err(error) => {
+                self.print_error(&error)?;
+                Ok(error.exit_code())
             }
         }
+    }
+
+    /// Parse the program arguments and runs each of the commands separated by
+    /// the `Context` command separator.
+    ///
+    /// This is equivalent to `CommandLine::run_all_from_os(std::env::args_os().skip(1))`.
+    #[inline]
+    pub fn run_all(&mut self) -> Result<()> {
+        self.run_all_from_os(std::env::args_os().skip(1))
+    }
+
+    /// Runs each of the commands in the given arguments separated by the `Context`
+    /// command separator, in order.
+    ///
+    /// Stops at the first error unless `continue_on_error` is set.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine, Context};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let executed = Rc::new(RefCell::new(Vec::new()));
+    /// let (fetch, rebase) = (executed.clone(), executed.clone());
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .subcommand(Command::new("fetch").handler(move |_, _| {
+    ///         fetch.borrow_mut().push("fetch");
+    ///         Ok(())
+    ///     }))
+    ///     .subcommand(Command::new("rebase").handler(move |_, _| {
+    ///         rebase.borrow_mut().push("rebase");
+    ///         Ok(())
+    ///     }));
+    ///
+    /// let context = Context::builder(command).command_separator(";").build();
+    /// let mut command_line = CommandLine::with_context(context);
+    ///
+    /// command_line.run_all_from(vec!["fetch", ";", "rebase"]).unwrap();
+    /// assert_eq!(*executed.borrow(), vec!["fetch", "rebase"]);
+    /// ```
+    pub fn run_all_from<S, I>(&mut self, args: I) -> Result<()>
+    where
+        S: Borrow<str>,
+        I: IntoIterator<Item = S>,
+    {
+        self.run_all_from_os(args.into_iter().map(|arg| arg.borrow().to_owned()))
+    }
 
-        if let Some(version_command) = self.context.help_command() {
-            if result.command_name() == version_command.get_name() {
-                return true;
+    /// Runs each of the commands in the given `OsString` arguments separated by the `Context`
+    /// command separator, in order.
+    ///
+    /// See `CommandLine::run_all_from`.
+    pub fn run_all_from_os<S, I>(&mut self, args: I) -> Result<()>
+    where
+        S: Into<OsString>,
+        I: IntoIterator<Item = S>,
+    {
+        let args = args.into_iter().map(Into::<OsString>::into);
+        let mut first_error = None;
+
+        for command_args in crate::tokenizer::split_commands(&self.context, args) {
+            if let Err(error) = self.run_from_os(command_args) {
+                if !self.continue_on_error {
+                    return Err(error);
+                }
+
+                self.print_error(&error)?;
+                first_error.get_or_insert(error);
             }
         }
 
-        false
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 
-    fn show_version(&self, result: &ParseResult) -> Result<()> {
-        match result.executing_command().get_version() {
-            Some(version) => {
-                let name = result.command_name();
-                Err(Error::from(ErrorKind::DisplayVersion(format!(
-                    "{} {}",
-                    name, version
-                ))))
-            }
-            None => unreachable!(),
+    /// Writes the given error to the `stderr` of this command-line,
+    /// help and version messages are written to the `stdout`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandLine, Error, ErrorKind};
+    ///
+    /// let mut command_line = CommandLine::new(Command::new("MyApp"))
+    ///     .with_stderr(std::io::sink());
+    ///
+    /// let error = Error::from(ErrorKind::InvalidExpression);
+    /// assert!(command_line.print_error(&error).is_ok());
+    /// ```
+    pub fn print_error(&mut self, error: &Error) -> Result<()> {
+        if error.is_display_message() {
+            error.print(&mut self.stdout)
+        } else {
+            error.print(&mut self.stderr)
         }
     }
 
-    fn requires_help(&self, result: &ParseResult) -> bool {
-        let context = &self.context;
+    fn handle_error(&self, parser: &Parser<'_>, error: Error) -> Result<()> {
+        // `Err` was decided initially due using an invalid `command` or `argument` is an error
+        if error.iter().len() == 1 {
+            return Err(self.with_suggestions(parser, error, true));
+        }
+
+        // Each error has its suggestions and the usage is written once after all of them
+        let mut show_usage = false;
+        let error = error.map_errors(|error| {
+            show_usage |= self.shows_usage(&error);
+            self.with_suggestions(parser, error, false)
+        });
 
-        if context.help_option().is_none() && context.help_command().is_none() {
-            return false;
+        if show_usage {
+            Err(error.with_message(self.help_message_for(parser.path(), MessageKind::Usage)))
+        } else {
+            Err(error)
         }
+    }
 
-        if let Some(help_option) = self.context.help_option() {
-            let options = result.options();
-            if options.contains(help_option.get_name()) {
-                return true;
+    // Returns the error with the suggestions for it, and the usage if `usage` is `true` and the error shows it
+    fn with_suggestions(&self, parser: &Parser<'_>, error: Error, usage: bool) -> Error {
+        let usage = usage && self.shows_usage(&error);
+
+        match (error.kind(), self.find_suggestions(parser.path())) {
+            (ErrorKind::InvalidArgument(_), Some(source)) if error.invalid_value().is_some() => {
+                self.display_value_suggestions(source, parser, error, usage)
             }
+            (ErrorKind::UnexpectedOption(_), Some(source)) => {
+                self.display_option_suggestions(source, parser, error)
+            }
+            (ErrorKind::UnexpectedCommand(_), Some(source)) => {
+                self.display_command_suggestions(source, parser, error)
+            }
+            _ if usage => error.with_message(self.help_message_for(parser.path(), MessageKind::Usage)),
+            _ => error,
         }
+    }
 
-        if let Some(help_command) = self.context.help_command() {
-            return help_command.get_name() == result.command_name();
-        }
+    // The invalid arguments are shown with the usage of the command
+    fn shows_usage(&self, error: &Error) -> bool {
+        self.has_help()
+            && matches!(error.kind(), ErrorKind::InvalidArgumentCount | ErrorKind::InvalidArgument(_))
+    }
 
-        false
+    // Returns the help provider of the nearest command of the path that have one,
+    // otherwise the help provider of the context
+    fn find_help<'b>(&'b self, path: &[&'b Command]) -> &'b HelpSource {
+        path.iter()
+            .rev()
+            .find_map(|c| c.get_help_provider())
+            .unwrap_or_else(|| self.context.help())
+    }
+
+    // Returns the suggestion provider of the nearest command of the path that have one,
+    // otherwise the suggestion provider of the context
+    fn find_suggestions<'b>(&'b self, path: &[&'b Command]) -> Option<&'b SuggestionSource> {
+        path.iter()
+            .rev()
+            .find_map(|c| c.get_suggestion_provider())
+            .or_else(|| self.suggestions())
+    }
+
+    // Returns `true` if the command have no handler, takes no arguments and only groups subcommands,
+    // the builtin `help`, `version` and doctor subcommands are not taken into account.
+    fn is_namespace_command(&self, command: &Command) -> bool {
+        let is_builtin = |c: &Command| {
+            crate::context::is_help_command(&self.context, c.get_name())
+                || self.context.version_command().is_some_and(|v| v.get_name() == c.get_name())
+                || self.is_doctor_command(c)
+        };
+
+        command.get_handler().is_none()
+            && !command.take_args()
+            && !command.has_setting(Setting::AllowEmptyInvocation)
+            && command.get_subcommands().any(|c| !is_builtin(c))
+    }
+
+    fn is_doctor_command(&self, command: &Command) -> bool {
+        self.context
+            .doctor_command()
+            .is_some_and(|c| c.get_name() == command.get_name())
+    }
+
+    fn show_version(&self, result: &ParseResult) -> Result<()> {
+        // The version command displays the version of the root command
+        let is_version_command = self
+            .context
+            .version_command()
+            .is_some_and(|c| c.get_name() == result.command_name());
+
+        let name = if is_version_command {
+            self.context.root().get_name()
+        } else {
+            result.command_name()
+        };
+
+        match result.executing_command().get_version() {
+            Some(version) => Err(Error::from(ErrorKind::DisplayVersion(format!(
+                "{} {}",
+                name, version
+            )))),
+            // There is no version to display
+            None => self.display_help(None),
+        }
     }
 
     fn handle_help(&self, parse_result: &ParseResult) -> Result<()> {
@@ -270,21 +764,31 @@ impl CommandLine {
         // * --help [subcommand]
         // * [subcommand] --help
         if let Some(help_option) = self.context.help_option() {
-            if parse_result.options().contains(help_option.get_name()) {
-                let arg = parse_result
-                    .options()
-                    .get(help_option.get_name())
-                    .unwrap()
-                    .get_arg();
-
-                return self.display_help(arg);
+            if let Some(option) = parse_result.options().get(help_option.get_name()) {
+                let args = option.get_args();
+                let values = args
+                    .get(help_option.get_arg().map_or("", |arg| arg.get_name()))
+                    .map(|arg| arg.get_values())
+                    .filter(|values| !values.is_empty())
+                    // Without a subcommand shows the help of the executing command
+                    .unwrap_or_else(|| parse_result.path());
+
+                // handler for: --help=json
+                return match args.get(crate::context::HELP_FORMAT_ARG) {
+                    Some(format) if format.contains("json") => self.display_help_json(values),
+                    Some(format) => Err(Error::new(
+                        ErrorKind::InvalidArgument(help_option.get_name().to_owned()),
+                        format!("unknown help format: `{}`, expected `json`", format.get_values().join(" "))
+                    )),
+                    None => self.display_help(Some(values)),
+                };
             }
         }
 
         // handler for: help [subcommand]
         if let Some(help_command) = self.context.help_command() {
             if parse_result.executing_command().get_name() == help_command.get_name() {
-                return self.display_help(parse_result.arg());
+                return self.display_help(parse_result.arg().map(|arg| arg.get_values()));
             }
         }
 
@@ -293,8 +797,7 @@ impl CommandLine {
         unreachable!()
     }
 
-    fn display_help(&self, args: Option<&Argument>) -> Result<()> {
-        let values = args.map(|s| s.get_values());
+    fn display_help(&self, values: Option<&[String]>) -> Result<()> {
         let mut message = self.get_help_message(values, MessageKind::Help)?;
 
         // Remove the last newline (if any) to have a cleaner message
@@ -309,47 +812,63 @@ impl CommandLine {
         Err(Error::from(ErrorKind::DisplayHelp(message)))
     }
 
+    fn display_help_json(&self, values: &[String]) -> Result<()> {
+        let path = self.find_help_path(Some(values))?;
+        let command = path.last().copied().unwrap();
+        let json = crate::help::to_json(&self.context, command);
+        Err(Error::from(ErrorKind::DisplayHelp(json)))
+    }
+
     fn get_help_message(&self, values: Option<&[String]>, kind: MessageKind) -> Result<String> {
-        fn find_command<'a>(root: &'a Command, children: &[String]) -> Result<&'a Command> {
-            let mut current = root;
+        let path = self.find_help_path(values)?;
+        Ok(self.help_message_for(&path, kind))
+    }
 
-            for child_name in children {
-                if let Some(cmd) = current.find_subcommand(child_name) {
-                    current = cmd;
-                } else {
+    // Returns the commands from the root to the subcommand with the given names
+    fn find_help_path(&self, values: Option<&[String]>) -> Result<Vec<&Command>> {
+        let context = &self.context;
+        let mut path = vec![context.root()];
+
+        for child_name in values.unwrap_or_default() {
+            match path.last().unwrap().find_subcommand(child_name) {
+                Some(cmd) => path.push(cmd),
+                None => {
                     return Err(Error::from(ErrorKind::UnexpectedCommand(
                         child_name.to_string(),
-                    )));
+                    )))
                 }
             }
-
-            Ok(current)
         }
 
+        Ok(path)
+    }
+
+    // Returns the help message of the last command of the path using its nearest help provider
+    fn help_message_for(&self, path: &[&Command], kind: MessageKind) -> String {
         let context = &self.context;
-        let command = match values {
-            None => context.root(),
-            Some(values) => find_command(&context.root(), values)?,
-        };
+        let command = path.last().copied().unwrap_or_else(|| context.root());
+        let help = self.find_help(path);
 
         let mut buf = String::new();
         match kind {
-            MessageKind::Help => context.help().get_help(&mut buf, &context, command, true),
-            MessageKind::Usage => context.help().get_usage(&mut buf, &context, command, true),
+            MessageKind::Help => help.get_help(&mut buf, context, command, true),
+            MessageKind::Usage => help.get_usage(&mut buf, context, command, true),
         }
 
-        Ok(buf)
+        buf
     }
 
-    fn display_option_suggestions(&self, parser: &Parser<'_>, error: Error) -> Result<()> {
+    fn display_option_suggestions(
+        &self,
+        suggestion_source: &SuggestionSource,
+        parser: &Parser<'_>,
+        error: Error,
+    ) -> Error {
         let unprefixed_option = match error.kind() {
             ErrorKind::UnexpectedOption(s) => self.context.trim_prefix(s),
             _ => unreachable!(),
         };
 
-        // SAFETY: We ensure `suggestions` is some before calling this method
-        // check `CommandLine::handle_error`
-        let suggestion_source = self.suggestions().unwrap();
         let command_options = parser
             .command()
             .unwrap()
@@ -362,12 +881,12 @@ impl CommandLine {
         let mut suggestions =
             suggestion_source.suggestions_for(unprefixed_option, &command_options);
 
-        // Prefix all the suggested options
-        let context = self.context();
-        let options = parser.command().unwrap().get_options();
+        // Prefix all the suggested options, the suggestions are always option names
+        // so we use the primary name prefix of the context
+        let name_prefix = self.context.name_prefixes().next().unwrap();
 
         for s in &mut suggestions {
-            prefix_option(context, options, &mut s.value);
+            s.value.insert_str(0, name_prefix);
         }
 
         // Suggestion message
@@ -379,22 +898,30 @@ impl CommandLine {
         self.display_suggestions(error, msg)
     }
 
-    fn display_command_suggestions(&self, parser: &Parser<'_>, error: Error) -> Result<()> {
+    fn display_command_suggestions(
+        &self,
+        suggestion_source: &SuggestionSource,
+        parser: &Parser<'_>,
+        error: Error,
+    ) -> Error {
         let command_name = match error.kind() {
             ErrorKind::UnexpectedCommand(s) => s,
             _ => unreachable!(),
         };
 
-        // SAFETY: We ensure `suggestions` is some before calling this method
-        // check `CommandLine::handle_error`
-        let suggestion_source = self.suggestions().unwrap();
-        let subcommands = parser
+        let mut subcommands = parser
             .command()
             .unwrap()
             .get_subcommands()
-            .map(|c| c.get_name().to_string())
+            .flat_map(|c| std::iter::once(c.get_name()).chain(c.get_aliases().map(|s| s.as_str())))
+            .map(|s| s.to_string())
             .collect::<Vec<String>>();
 
+        // Commands provided at runtime, like plugins
+        if let Some(commands) = suggestion_source.commands {
+            subcommands.extend(commands());
+        }
+
         // Suggested subcommands
         let suggestions = suggestion_source.suggestions_for(command_name, &subcommands);
 
@@ -406,14 +933,59 @@ impl CommandLine {
         self.display_suggestions(error, msg)
     }
 
-    fn display_suggestions(&self, error: Error, message: Option<String>) -> Result<()> {
+    fn display_value_suggestions(
+        &self,
+        suggestion_source: &SuggestionSource,
+        parser: &Parser<'_>,
+        error: Error,
+        usage: bool,
+    ) -> Error {
+        let (value, valid_values) = error
+            .invalid_value()
+            .expect("`CommandLine::handle_error` checks the error have an invalid value");
+
+        // Suggested valid values
+        let suggestions = suggestion_source.suggestions_for(value, valid_values);
+        let mut msg = suggestion_source
+            .message_for(suggestions)
+            .map(|s| format!("\n\n{}\n", s));
+
+        // Keeps the usage message shown for invalid arguments
+        if usage {
+            let usage = self.help_message_for(parser.path(), MessageKind::Usage);
+            msg = Some(match msg {
+                Some(msg) => format!("{}{}", msg, usage),
+                None => usage,
+            });
+        }
+
+        self.display_suggestions(error, msg)
+    }
+
+    fn has_help(&self) -> bool {
+        self.context.help_option().is_some() || self.context.help_command().is_some()
+    }
+
+    fn display_suggestions(&self, error: Error, message: Option<String>) -> Error {
         match message {
-            Some(msg) => Err(error.with_message(msg)),
-            None => Err(error),
+            Some(msg) => error.with_message(msg),
+            None => error,
         }
     }
 }
 
+impl Debug for CommandLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandLine")
+            .field("context", &self.context)
+            .field("stdout", &"Write")
+            .field("stderr", &"Write")
+            .field("provided", &self.provided.len())
+            .field("continue_on_error", &self.continue_on_error)
+            .finish()
+    }
+}
+
 /// Type of the help message.
 enum MessageKind {
     /// A help message.
@@ -422,19 +994,6 @@ enum MessageKind {
     Usage,
 }
 
-// Adds a prefix to the option name
-fn prefix_option(context: &Context, options: &OptionList, name: &mut String) {
-    if options.get_by_alias(&name).is_some() {
-        let prefix = context.alias_prefixes().next().unwrap();
-        name.insert_str(0, prefix);
-    }
-
-    if options.get_by_name(&name).is_some() {
-        let prefix = context.name_prefixes().next().unwrap();
-        name.insert_str(0, prefix);
-    }
-}
-
 // Checks if the option or any of its children have `version`
 pub(crate) fn contains_version_recursive(command: &Command) -> bool {
     for c in command {
@@ -565,7 +1124,6 @@ impl ArgSplitter {
             result.push(temp);
         }
 
-        dbg!(&result);
         result
     }
 }
@@ -573,6 +1131,7 @@ impl ArgSplitter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Argument;
 
     #[test]
     fn into_arg_iterator_test1() {
@@ -598,4 +1157,896 @@ mod tests {
         assert_eq!("--times:3", args[1]);
         assert_eq!("hello world", args[2]);
     }
+
+    // A writer that shares its buffer so can be read after given to the `CommandLine`
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.as_ref().borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn with_stdout_test() {
+        let stdout = SharedBuffer::default();
+        let stderr = SharedBuffer::default();
+        let mut command_line = CommandLine::new(Command::new("MyApp").version("1.0"))
+            .use_default_help()
+            .with_stdout(stdout.clone())
+            .with_stderr(stderr.clone());
+
+        command_line.run_from(vec!["--version"]).unwrap();
+        assert_eq!(stdout.contents(), "MyApp 1.0\n");
+
+        command_line.run_from(vec!["--help"]).unwrap();
+        assert!(stdout.contents().contains("USAGE:"));
+        assert!(stderr.contents().is_empty());
+    }
+
+    #[test]
+    fn help_json_test() {
+        let command = Command::new("MyApp")
+            .description("An app")
+            .option(CommandOption::new("verbose").alias("v"))
+            .subcommand(Command::new("server")
+                .description("Starts the server")
+                .option(CommandOption::new("port").arg(Argument::new().default(8080))));
+
+        let help = |args: Vec<&str>| {
+            let stdout = SharedBuffer::default();
+            CommandLine::new(command.clone())
+                .use_default_help()
+                .with_stdout(stdout.clone())
+                .run_from(args)
+                .map(|_| stdout.contents())
+        };
+
+        let json = help(vec!["--help=json"]).unwrap();
+        assert!(json.starts_with("{\n  \"name\": \"MyApp\",\n  \"description\": \"An app\",\n"), "{}", json);
+        assert!(json.contains("\"name\": \"server\""));
+
+        let json = help(vec!["server", "--help=json"]).unwrap();
+        assert!(json.starts_with("{\n  \"name\": \"server\",\n  \"description\": \"Starts the server\",\n"), "{}", json);
+        assert!(json.contains("\"default\": [\n"));
+        assert_eq!(json, help(vec!["--help=json", "server"]).unwrap());
+
+        // Without a format the help is still written as text
+        assert!(help(vec!["--help", "server"]).unwrap().starts_with("server\n"));
+
+        let error = help(vec!["--help=yaml"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("help".to_owned()));
+        assert!(error.to_string().contains("unknown help format: `yaml`"), "{}", error);
+    }
+
+    #[test]
+    fn nested_subcommand_version_test() {
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .subcommand(
+                Command::new("server")
+                    .version("2.0")
+                    .subcommand(Command::new("start").handler(|_, _| Ok(()))),
+            )
+            .subcommand(Command::new("client").handler(|_, _| Ok(())));
+
+        let version = |args: Vec<&str>| {
+            let stdout = SharedBuffer::default();
+            CommandLine::new(command.clone())
+                .use_default_help()
+                .with_stdout(stdout.clone())
+                .run_from(args)
+                .unwrap();
+            stdout.contents()
+        };
+
+        assert_eq!(version(vec!["--version"]), "MyApp 1.0\n");
+        assert_eq!(version(vec!["server", "--version"]), "server 2.0\n");
+        assert_eq!(version(vec!["server", "start", "-v"]), "start 2.0\n");
+        assert_eq!(version(vec!["client", "--version"]), "client 1.0\n");
+        assert!(version(vec!["help", "server"]).starts_with("server 2.0\n"));
+        assert!(version(vec!["help", "server", "start"]).starts_with("start 2.0\n"));
+    }
+
+    #[test]
+    fn builtin_help_not_shadowed_test() {
+        let stdout = SharedBuffer::default();
+        let out = stdout.clone();
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .option(CommandOption::new("verbose").alias("v"))
+            .subcommand(Command::new("help").arg(Argument::zero_or_one("topic")).handler(move |_, args| {
+                writeln!(out.clone(), "topic: {}", args.get("topic").unwrap().get_values().join(" ")).unwrap();
+                Ok(())
+            }))
+            .subcommand(Command::new("connect").option(CommandOption::new("host").alias("h").arg(Argument::new())).handler(|opts, _| {
+                assert_eq!(opts.get_arg("host").unwrap().get_values(), &["localhost"]);
+                Ok(())
+            }));
+
+        let mut command_line = CommandLine::new(command)
+            .use_default_help()
+            .with_stdout(stdout.clone());
+
+        // The subcommand and options of the user are used
+        command_line.run_from(vec!["help", "options"]).unwrap();
+        command_line.run_from(vec!["connect", "-h", "localhost"]).unwrap();
+        command_line.run_from(vec!["help"]).unwrap();
+        assert_eq!(stdout.contents(), "topic: options\ntopic: \n");
+
+        // The builtin names not in use still work
+        command_line.run_from(vec!["--version"]).unwrap();
+        assert!(stdout.contents().ends_with("MyApp 1.0\n"));
+        command_line.run_from(vec!["connect", "--help"]).unwrap();
+        assert!(stdout.contents().contains("USAGE:"));
+    }
+
+    #[test]
+    fn user_help_option_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("help").arg(Argument::with_name("topic")))
+            .handler(|opts, _| {
+                assert_eq!(opts.get_arg("help").unwrap().get_values(), &["options"]);
+                Ok(())
+            });
+
+        let mut command_line = CommandLine::new(command)
+            .use_default_help()
+            .with_stdout(std::io::sink());
+
+        assert!(command_line.context().help_option().is_none());
+        command_line.run_from(vec!["--help", "options"]).unwrap();
+    }
+
+    #[test]
+    fn version_command_test() {
+        let stdout = SharedBuffer::default();
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .subcommand(Command::new("test").handler(|_, _| Ok(())));
+
+        let mut command_line = CommandLine::new(command)
+            .use_default_help()
+            .use_version_command(crate::default_version_command())
+            .with_stdout(stdout.clone());
+
+        command_line.run_from(vec!["version"]).unwrap();
+        assert_eq!(stdout.contents(), "MyApp 1.0\n");
+    }
+
+    #[test]
+    fn generate_completions_test() {
+        let stdout = SharedBuffer::default();
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v"))
+            .subcommand(Command::new("test"));
+
+        let mut command_line = CommandLine::new(command)
+            .use_default_help()
+            .with_stdout(stdout.clone());
+
+        command_line.generate_completions(Shell::Bash).unwrap();
+        let script = stdout.contents();
+        assert!(script.contains("'MyApp,test') cmd='MyApp__test' ;;"));
+        assert!(script.contains("COMPREPLY=($(compgen -W 'test help --verbose -v --help -h' -- \"${cur}\"))"));
+    }
+
+    #[test]
+    fn with_stderr_test() {
+        let stdout = SharedBuffer::default();
+        let stderr = SharedBuffer::default();
+        let mut command_line = CommandLine::new(Command::new("MyApp"))
+            .with_stdout(stdout.clone())
+            .with_stderr(stderr.clone());
+
+        let error = command_line.run_from(vec!["--color"]).unwrap_err();
+        command_line.print_error(&error).unwrap();
+
+        assert!(stdout.contents().is_empty());
+        assert_eq!(stderr.contents(), "Error: unexpected option: '--color'\n");
+    }
+
+    // A writer that always fails with the given error kind, like a closed pipe
+    struct FailingWriter(std::io::ErrorKind);
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::from(self.0))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Err(std::io::Error::from(self.0))
+        }
+    }
+
+    #[test]
+    fn broken_pipe_test() {
+        let mut command_line = CommandLine::new(Command::new("MyApp").version("1.0"))
+            .use_default_help()
+            .with_stdout(FailingWriter(std::io::ErrorKind::BrokenPipe))
+            .with_stderr(FailingWriter(std::io::ErrorKind::BrokenPipe));
+
+        assert!(command_line.run_from(vec!["--help"]).is_ok());
+        assert!(command_line.run_from(vec!["--version"]).is_ok());
+
+        let error = command_line.run_from(vec!["--color"]).unwrap_err();
+        assert!(command_line.print_error(&error).is_ok());
+    }
+
+    #[test]
+    fn write_error_test() {
+        let mut command_line = CommandLine::new(Command::new("MyApp"))
+            .use_default_help()
+            .with_stdout(FailingWriter(std::io::ErrorKind::PermissionDenied));
+
+        let error = command_line.run_from(vec!["--help"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::Other);
+    }
+
+    #[test]
+    fn run_all_test() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let executed = Rc::new(RefCell::new(Vec::new()));
+        let (fetch, rebase) = (executed.clone(), executed.clone());
+
+        let command = Command::new("MyApp")
+            .subcommand(
+                Command::new("fetch")
+                    .option(CommandOption::new("remote").arg(Argument::new()))
+                    .handler(move |options, _| {
+                        fetch.borrow_mut().push(format!("fetch {}", options.convert::<String>("remote")?));
+                        Ok(())
+                    }),
+            )
+            .subcommand(
+                Command::new("rebase")
+                    .arg(Argument::zero_or_more("args"))
+                    .handler(move |_, args| {
+                        rebase.borrow_mut().push(format!("rebase {}", args.get_raw_args().into_vec().join(" ")));
+                        Ok(())
+                    }),
+            );
+
+        let stderr = SharedBuffer::default();
+        let mut command_line = CommandLine::new(command)
+            .command_separator(";")
+            .with_stderr(stderr.clone());
+
+        command_line
+            .run_all_from(vec!["fetch", "--remote", "origin", ";", "rebase", "a;b", ";", "rebase", "--", ";", "c"])
+            .unwrap();
+        assert_eq!(*RefCell::borrow(&executed), vec!["fetch origin", "rebase a;b", "rebase ; c"]);
+
+        // Stops at the first error
+        executed.borrow_mut().clear();
+        assert!(command_line.run_all_from(vec!["fetch", ";", "rebase", ";"]).is_err());
+        assert!(RefCell::borrow(&executed).is_empty());
+
+        // Runs all the commands and returns the first error
+        let mut command_line = command_line.continue_on_error(true);
+        let error = command_line.run_all_from(vec!["fetch", ";", "rebase", ";", "pull"]).unwrap_err();
+        assert!(error.to_string().contains("cannot find option named 'remote'"));
+        assert_eq!(*RefCell::borrow(&executed), vec!["rebase "]);
+        assert!(stderr.contents().starts_with("Error: "));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_all_from_os_test() {
+        use std::cell::RefCell;
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+        use std::rc::Rc;
+
+        let executed = Rc::new(RefCell::new(Vec::new()));
+        let open = executed.clone();
+
+        let command = Command::new("MyApp")
+            .subcommand(
+                Command::new("open")
+                    .arg(Argument::with_name("path").allow_invalid_utf8(true))
+                    .handler(move |_, args| {
+                        open.borrow_mut().push(args.get("path").unwrap().get_os_values());
+                        Ok(())
+                    }),
+            )
+            .subcommand(Command::new("echo").arg(Argument::with_name("value")).handler(|_, _| Ok(())));
+
+        let mut command_line = CommandLine::new(command).command_separator(";");
+        let invalid = OsString::from_vec(vec![b'f', 0xFF]);
+
+        command_line
+            .run_all_from_os(vec!["open".into(), invalid.clone(), ";".into(), "open".into(), "b".into()])
+            .unwrap();
+        assert_eq!(*RefCell::borrow(&executed), vec![vec![invalid.clone()], vec![OsString::from("b")]]);
+
+        // The invalid UTF-8 values are errors unless an argument allows it
+        let error = command_line
+            .run_all_from_os(vec!["open".into(), "a".into(), ";".into(), "echo".into(), invalid.clone()])
+            .unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidUtf8(invalid));
+    }
+
+    #[test]
+    fn lenient_warning_test() {
+        let stderr = SharedBuffer::default();
+        let context = Context::builder(
+            Command::new("MyApp")
+                .option(CommandOption::new("verbose"))
+                .handler(|_, _| Ok(())),
+        )
+        .lenient(true)
+        .build();
+
+        let mut command_line = CommandLine::with_context(context)
+            .with_stdout(std::io::sink())
+            .with_stderr(stderr.clone());
+
+        command_line.run_from(vec!["--verbose"]).unwrap();
+        assert!(stderr.contents().is_empty());
+
+        command_line.run_from(vec!["--old", "--verbose", "-x"]).unwrap();
+        assert_eq!(
+            stderr.contents(),
+            "Warning: ignored unknown arguments: '--old', '-x'\n"
+        );
+    }
+
+    #[test]
+    fn try_run_exit_code_test() {
+        let stdout = SharedBuffer::default();
+        let stderr = SharedBuffer::default();
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .arg(Argument::zero_or_one("count").validator(crate::validator::validate_type::<u32>()))
+            .option(CommandOption::new("fail"))
+            .handler(|opts, _| {
+                if opts.contains("fail") {
+                    Err(Error::new(ErrorKind::Other, "failed"))
+                } else {
+                    Ok(())
+                }
+            });
+
+        let mut command_line = CommandLine::new(command)
+            .use_default_help()
+            .with_stdout(stdout.clone())
+            .with_stderr(stderr.clone());
+
+        assert_eq!(command_line.try_run_from(vec!["1"]).unwrap(), 0);
+        assert!(stdout.contents().is_empty());
+        assert!(stderr.contents().is_empty());
+
+        // Help and version messages are not errors
+        assert_eq!(command_line.try_run_from(vec!["--help"]).unwrap(), 0);
+        assert_eq!(command_line.try_run_from(vec!["--version"]).unwrap(), 0);
+        assert!(stdout.contents().contains("MyApp 1.0"));
+        assert!(stderr.contents().is_empty());
+
+        assert_eq!(command_line.try_run_from(vec!["--fail"]).unwrap(), 1);
+        assert_eq!(command_line.try_run_from(vec!["x"]).unwrap(), 1);
+        assert_eq!(command_line.try_run_from(vec!["--color"]).unwrap(), 2);
+        assert_eq!(command_line.try_run_from(vec!["1", "2"]).unwrap(), 2);
+        assert!(stderr.contents().starts_with("Error: unexpected error: failed\n"));
+        assert!(stderr.contents().contains("Error: unexpected option: '--color'"));
+    }
+
+    #[test]
+    fn overflow_warning_test() {
+        let stderr = SharedBuffer::default();
+        let command = Command::new("MyApp")
+            .arg(
+                Argument::with_name("files")
+                    .values_count(1..=2)
+                    .overflow_policy(OverflowPolicy::TruncateWarn),
+            )
+            .handler(|_, args| {
+                assert_eq!(args.get("files").unwrap().get_values(), &["a.txt", "b.txt"]);
+                Ok(())
+            });
+
+        let mut command_line = CommandLine::new(command)
+            .with_stdout(std::io::sink())
+            .with_stderr(stderr.clone());
+
+        command_line.run_from(vec!["a.txt", "b.txt"]).unwrap();
+        assert!(stderr.contents().is_empty());
+
+        command_line.run_from(vec!["a.txt", "b.txt", "c.txt", "d.txt"]).unwrap();
+        assert_eq!(
+            stderr.contents(),
+            "Warning: ignored extra values for `files`: 'c.txt', 'd.txt'\n"
+        );
+    }
+
+    #[test]
+    fn subcommand_version_test() {
+        let stdout = SharedBuffer::default();
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .subcommand(Command::new("sub").version("2.0.1"))
+            .subcommand(Command::new("other"));
+
+        let mut command_line = CommandLine::new(command)
+            .with_stdout(stdout.clone())
+            .with_stderr(std::io::sink());
+
+        command_line.run_from(vec!["--version"]).unwrap();
+        command_line.run_from(vec!["sub", "--version"]).unwrap();
+        command_line.run_from(vec!["other", "--version"]).unwrap();
+        assert_eq!(stdout.contents(), "MyApp 1.0\nsub 2.0.1\nother 1.0\n");
+    }
+
+    fn config_command() -> Command {
+        Command::new("config")
+            .description("Manages the configuration")
+            .subcommand(Command::new("get").handler(|_, _| Ok(())))
+            .subcommand(Command::new("set").handler(|_, _| Ok(())))
+    }
+
+    #[test]
+    fn namespace_command_test() {
+        let stdout = SharedBuffer::default();
+        let mut command_line = CommandLine::new(Command::new("MyApp").subcommand(config_command()))
+            .use_default_help()
+            .with_stdout(stdout.clone());
+
+        let error = command_line.run_from(vec!["config"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingSubcommand("config".to_owned()));
+        assert!(error.is_usage_error());
+
+        let message = error.to_string();
+        assert!(message.starts_with("'config' requires a subcommand"));
+        assert!(message.contains("Manages the configuration"));
+        assert!(stdout.contents().is_empty());
+
+        assert!(command_line.run_from(vec!["config", "get"]).is_ok());
+    }
+
+    #[test]
+    fn namespace_command_allow_empty_invocation_test() {
+        let stdout = SharedBuffer::default();
+        let command = Command::new("MyApp")
+            .subcommand(config_command().allow_empty_invocation(true));
+
+        let mut command_line = CommandLine::new(command)
+            .use_default_help()
+            .with_stdout(stdout.clone());
+
+        assert!(command_line.run_from(vec!["config"]).is_ok());
+        assert!(stdout.contents().contains("USAGE:"));
+    }
+
+    #[test]
+    fn namespace_command_with_handler_test() {
+        let executed = std::rc::Rc::new(std::cell::Cell::new(false));
+        let executed_clone = executed.clone();
+        let command = Command::new("MyApp").subcommand(config_command().handler(move |_, _| {
+            executed_clone.set(true);
+            Ok(())
+        }));
+
+        let mut command_line = CommandLine::new(command).use_default_help();
+        assert!(command_line.run_from(vec!["config"]).is_ok());
+        assert!(executed.get());
+    }
+
+    #[test]
+    fn root_with_builtin_subcommands_is_not_namespace_test() {
+        let stdout = SharedBuffer::default();
+        let mut command_line = CommandLine::new(Command::new("MyApp").version("1.0"))
+            .use_default_help()
+            .with_stdout(stdout.clone());
+
+        assert!(command_line.run_from(Vec::<String>::new()).is_ok());
+        assert!(stdout.contents().starts_with("MyApp"));
+    }
+
+    fn prefixed_command_line(name_prefix: &str, alias_prefix: &str) -> (CommandLine, SharedBuffer) {
+        let stdout = SharedBuffer::default();
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v"))
+            .option(CommandOption::new("color").arg(Argument::with_name("color")));
+
+        let context = Context::builder(command)
+            .name_prefix(name_prefix)
+            .alias_prefix(alias_prefix)
+            .suggestions(SuggestionSource::new())
+            .help_option(crate::context::default_help_option())
+            .build();
+
+        let command_line = CommandLine::with_context(context)
+            .with_stdout(stdout.clone())
+            .with_stderr(std::io::sink());
+
+        (command_line, stdout)
+    }
+
+    #[test]
+    fn single_dash_prefix_test() {
+        let (mut command_line, stdout) = prefixed_command_line("-", "-");
+
+        let error = command_line.run_from(vec!["-verbos"]).unwrap_err();
+        assert!(error.to_string().contains("Did you mean `-verbose`?"));
+        assert!(!error.to_string().contains("--"));
+
+        command_line.run_from(vec!["-help"]).unwrap();
+        let help = stdout.contents();
+        assert!(help.contains("-v, -verbose"));
+        assert!(help.contains("-color <COLOR>"));
+        assert!(help.contains("Use 'MyApp <subcommand> -help'"));
+        assert!(!help.contains("--"));
+    }
+
+    fn build_tree() -> Command {
+        Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v"))
+            .option(CommandOption::new("out").alias("o").arg(Argument::with_name("file")))
+            .subcommand(Command::new("build")
+                .option(CommandOption::new("release").alias("r"))
+                .arg(Argument::zero_or_more("targets")))
+    }
+
+    // Runs the same command tree with the given prefixes and assign operator
+    fn check_prefix_style(name_prefix: &str, alias_prefix: &str, assign: char) {
+        let stdout = SharedBuffer::default();
+        let mut command_line = CommandLine::new(build_tree())
+            .use_default_help()
+            .use_default_suggestions()
+            .name_prefix(name_prefix)
+            .alias_prefix(alias_prefix)
+            .assign_operator(assign)
+            .with_stdout(stdout.clone())
+            .with_stderr(std::io::sink());
+
+        let result = command_line
+            .parse_from(vec![format!("{}out{}file.txt", name_prefix, assign), format!("{}v", alias_prefix)])
+            .unwrap();
+        assert!(result.options().get_arg("out").unwrap().contains("file.txt"));
+        assert!(result.options().contains("verbose"));
+
+        let result = command_line
+            .parse_from(vec!["build".to_owned(), format!("{}r", alias_prefix), "app".to_owned()])
+            .unwrap();
+        assert!(result.options().contains("release"));
+        assert_eq!(result.arg().unwrap().get_values(), &["app"]);
+
+        let error = command_line.parse_from(vec![format!("{}verbos", name_prefix)]).unwrap_err();
+        assert!(error.to_string().contains(&format!("Did you mean `{}verbose`?", name_prefix)));
+
+        command_line.run_from(vec![format!("{}help", name_prefix)]).unwrap();
+        let help = stdout.contents();
+        assert!(help.contains(&format!("{}v, {}verbose", alias_prefix, name_prefix)));
+        assert!(help.contains(&format!("{}o, {}out <FILE>", alias_prefix, name_prefix)));
+        assert!(!help.contains(if name_prefix == "--" { "/" } else { "-" }));
+    }
+
+    #[test]
+    fn prefix_styles_test() {
+        check_prefix_style("--", "-", '=');
+        check_prefix_style("/", "/", ':');
+
+        // The default prefixes are replaced
+        let mut command_line = CommandLine::new(build_tree())
+            .name_prefix("/")
+            .alias_prefix("/")
+            .assign_operator(':');
+
+        assert!(command_line.parse_from(vec!["--verbose"]).is_err());
+        assert!(command_line.parse_from(vec!["-v"]).is_err());
+        assert!(command_line.parse_from(vec!["/out=file.txt"]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "option `host:port` cannot contains the assign operator `:`")]
+    fn assign_operator_conflicts_with_option_test() {
+        CommandLine::new(Command::new("MyApp").option(CommandOption::new("host:port")))
+            .assign_operator(':');
+    }
+
+    #[test]
+    fn slash_prefix_test() {
+        let (mut command_line, stdout) = prefixed_command_line("/", "/");
+
+        let error = command_line.run_from(vec!["/verbos"]).unwrap_err();
+        assert!(error.to_string().contains("Did you mean `/verbose`?"));
+
+        command_line.run_from(vec!["/help"]).unwrap();
+        let help = stdout.contents();
+        assert!(help.contains("/v, /verbose"));
+        assert!(help.contains("/color <COLOR>"));
+        assert!(help.contains("Use 'MyApp <subcommand> /help'"));
+        assert!(!help.contains("-"));
+    }
+
+    #[test]
+    fn global_option_handler_test() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let profile = Rc::new(RefCell::new(String::new()));
+        let captured = profile.clone();
+
+        let command = Command::new("MyApp")
+            .subcommand(Command::new("build").handler(move |opts, _| {
+                captured.replace(opts.get_arg("profile").unwrap().get_values()[0].clone());
+                Ok(())
+            }));
+
+        let mut command_line = CommandLine::new(command)
+            .global_option(CommandOption::new("profile").arg(Argument::new().default("dev")));
+
+        command_line.run_from(vec!["--profile", "release", "build"]).unwrap();
+        assert_eq!(profile.take(), "release");
+
+        command_line.run_from(vec!["build"]).unwrap();
+        assert_eq!(profile.take(), "dev");
+    }
+
+    #[test]
+    fn use_doctor_test() {
+        let stdout = SharedBuffer::default();
+        let mut command_line = CommandLine::new(
+            Command::new("MyApp")
+                .version("1.0")
+                .subcommand(Command::new("build").handler(|_, _| Ok(()))),
+        )
+        .use_default_help()
+        .use_doctor()
+        .with_stdout(stdout.clone());
+
+        command_line.run_from(vec!["__doctor"]).unwrap();
+        let report = stdout.0.take();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.starts_with("name: MyApp\nversion: 1.0\n"), "{}", report);
+        assert!(report.contains("command: MyApp __doctor (options: 1, args: 0, subcommands: 0) [hidden]\n"));
+
+        command_line.run_from(vec!["__doctor", "--format", "json"]).unwrap();
+        assert!(stdout.contents().starts_with("{\n  \"name\": \"MyApp\","));
+
+        assert!(command_line.run_from(vec!["__doctor", "--format", "yaml"]).is_err());
+
+        // The doctor command is hidden
+        stdout.0.take();
+        command_line.run_from(vec!["--help"]).unwrap();
+        assert!(!stdout.contents().contains("__doctor"));
+    }
+
+    #[test]
+    fn use_doctor_metrics_test() {
+        let stdout = SharedBuffer::default();
+        let mut command_line = CommandLine::new(Command::new("MyApp"))
+            .use_doctor()
+            .metrics(true)
+            .with_stdout(stdout.clone());
+
+        command_line.run_from(vec!["__doctor", "--format", "text"]).unwrap();
+        let report = stdout.contents();
+        assert!(report.contains("metrics: true\n"), "{}", report);
+        assert!(report.contains("parse metrics: (tokens: 3, option occurrences: 1, recovered errors: 0, "), "{}", report);
+    }
+
+    #[test]
+    fn use_doctor_shadowed_test() {
+        let command_line = CommandLine::new(
+            Command::new("MyApp").subcommand(Command::new("__doctor").handler(|_, _| Ok(()))),
+        )
+        .use_doctor();
+
+        assert!(command_line.context().doctor_command().is_none());
+        assert!(!command_line.root().find_subcommand("__doctor").unwrap().is_hidden());
+    }
+
+    fn suggestions_command_line(suggestions: SuggestionSource) -> CommandLine {
+        CommandLine::new(
+            Command::new("MyApp")
+                .subcommand(Command::new("add").handler(|_, _| Ok(())))
+                .subcommand(
+                    Command::new("status")
+                        .option(CommandOption::new("color").arg(
+                            Argument::new().valid_values(["red", "green", "blue"]),
+                        ))
+                        .handler(|_, _| Ok(())),
+                ),
+        )
+        .use_suggestions(suggestions)
+    }
+
+    #[test]
+    fn suggestions_test() {
+        let mut command_line = suggestions_command_line(SuggestionSource::new());
+
+        let error = command_line.parse_from(vec!["staus"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::UnexpectedCommand(_)));
+        assert!(error.to_string().contains("Did you mean `status`?"));
+
+        let error = command_line.parse_from(vec!["status", "--colr", "red"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::UnexpectedOption(_)));
+        assert!(error.to_string().contains("Did you mean `--color`?"));
+
+        let error = command_line.parse_from(vec!["status", "--color", "gren"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidArgument(_)));
+        assert!(error.to_string().contains("expected red, green, blue but was gren"));
+        assert!(error.to_string().contains("Did you mean `green`?"));
+    }
+
+    #[test]
+    fn suggestions_max_distance_test() {
+        let mut command_line = suggestions_command_line(SuggestionSource::new().max_distance(1));
+
+        let error = command_line.parse_from(vec!["status", "--color", "blu"]).unwrap_err();
+        assert!(error.to_string().contains("Did you mean `blue`?"));
+
+        let error = command_line.parse_from(vec!["status", "--color", "yellow"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidArgument(_)));
+        assert!(!error.to_string().contains("Did you mean"));
+
+        let error = command_line.parse_from(vec!["stat"]).unwrap_err();
+        assert!(!error.to_string().contains("Did you mean"));
+    }
+
+    #[test]
+    fn value_suggestions_with_usage_test() {
+        let mut command_line = suggestions_command_line(SuggestionSource::new()).use_default_help();
+
+        let error = command_line.parse_from(vec!["status", "--color", "gren"]).unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("Did you mean `green`?"), "{}", message);
+        assert!(message.contains("USAGE:"), "{}", message);
+    }
+
+    #[test]
+    fn collect_errors_suggestions_test() {
+        let mut command_line = CommandLine::new(
+            Command::new("MyApp")
+                .option(CommandOption::new("count").arg(Argument::new().validator(crate::validator::validate_type::<u32>())))
+                .option(CommandOption::new("color").arg(Argument::new().valid_values(["red", "green", "blue"])))
+                .option(CommandOption::new("verbose")),
+        )
+        .use_default_help()
+        .use_default_suggestions()
+        .collect_errors(true);
+
+        let error = command_line
+            .parse_from(vec!["--count", "abc", "--color", "rde", "--verbse"])
+            .unwrap_err();
+
+        // The usage is written once after the errors and each error keeps its suggestions
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "found 3 errors:\n",
+                "1. invalid value for argument 'count': in option '--count': `abc`\n",
+                "2. invalid value for argument 'color': in option '--color': expected red, green, blue but was rde\n",
+                "\n",
+                "\n",
+                "      Did you mean `red`?\n",
+                "\n",
+                "3. unexpected option: '--verbse': \n",
+                "\n",
+                "      Did you mean `--verbose`?\n",
+                "\n",
+                "\n",
+                "USAGE:\n",
+                "   MyApp [OPTIONS]\n",
+                "   MyApp [SUBCOMMAND] [ARGS]\n",
+                "\n",
+                "Use 'MyApp help <subcommand>' for more information about a command.\n",
+            )
+        );
+        assert_eq!(error.to_string().matches("USAGE:").count(), 1);
+    }
+
+    fn providers_command_line() -> CommandLine {
+        fn help_of(name: &'static str) -> HelpSource {
+            HelpSource::custom(
+                move |buf, _, command, _| buf.push_str(&format!("{} help of {}", name, command.get_name())),
+                move |buf, _, command, _| buf.push_str(&format!("{} usage of {}", name, command.get_name())),
+            )
+        }
+
+        fn plugins() -> Vec<String> {
+            vec!["deploy".to_owned()]
+        }
+
+        let handler = |_: &crate::OptionList, _: &crate::ArgumentList| Ok(());
+
+        CommandLine::new(
+            Command::new("MyApp")
+                .subcommand(
+                    Command::new("tools")
+                        .help_provider(help_of("tools"))
+                        .suggestion_provider(SuggestionSource::new().max_distance(1))
+                        .subcommand(
+                            Command::new("plugin")
+                                .help_provider(help_of("plugin"))
+                                .suggestion_provider(SuggestionSource::new().commands(plugins))
+                                .subcommand(Command::new("install")
+                                    .option(CommandOption::new("force"))
+                                    .arg(Argument::zero_or_one("package").valid_values(vec!["core", "extra"]))
+                                    .handler(handler)),
+                        )
+                        .subcommand(Command::new("list")
+                            .option(CommandOption::new("verbose"))
+                            .handler(handler)),
+                )
+                .subcommand(Command::new("other").handler(handler)),
+        )
+        .use_default_help()
+        .use_help(help_of("global"))
+        .use_default_suggestions()
+    }
+
+    #[test]
+    fn help_provider_resolution_test() {
+        let mut command_line = providers_command_line();
+        let mut help = |args: &[&str]| match command_line.parse_from(args.to_vec()).unwrap_err().kind() {
+            ErrorKind::DisplayHelp(msg) => msg.clone(),
+            kind => panic!("unexpected error: {:?}", kind),
+        };
+
+        assert_eq!(help(&["--help"]), "global help of MyApp");
+        assert_eq!(help(&["other", "--help"]), "global help of other");
+        assert_eq!(help(&["tools", "--help"]), "tools help of tools");
+        assert_eq!(help(&["tools", "list", "--help"]), "tools help of list");
+        assert_eq!(help(&["tools", "plugin", "--help"]), "plugin help of plugin");
+        assert_eq!(help(&["tools", "plugin", "install", "--help"]), "plugin help of install");
+        assert_eq!(help(&["help", "tools", "plugin", "install"]), "plugin help of install");
+
+        // Commands that only group subcommands show their help
+        let error = command_line.run_from(vec!["tools", "plugin"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::MissingSubcommand(_)));
+        assert!(error.to_string().contains("plugin help of plugin"), "{}", error);
+    }
+
+    #[test]
+    fn usage_provider_resolution_test() {
+        let mut command_line = providers_command_line();
+
+        // The usage shown for invalid arguments is the usage of the failing command
+        let error = command_line.parse_from(vec!["tools", "plugin", "install", "cor"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidArgument(_)));
+        assert!(error.to_string().contains("Did you mean `core`?"), "{}", error);
+        assert!(error.to_string().ends_with("plugin usage of install"), "{}", error);
+
+        let error = command_line.parse_from(vec!["tools", "plugin", "install", "core", "extra"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidArgumentCount));
+        assert!(error.to_string().ends_with("plugin usage of install"), "{}", error);
+
+        let error = command_line.parse_from(vec!["other", "1"]).unwrap_err();
+        assert!(error.to_string().ends_with("global usage of other"), "{}", error);
+    }
+
+    #[test]
+    fn suggestion_provider_resolution_test() {
+        let mut command_line = providers_command_line();
+        let mut suggestion = |args: &[&str]| command_line.parse_from(args.to_vec()).unwrap_err().to_string();
+
+        // The default suggestions of the command-line
+        assert!(suggestion(&["othre"]).contains("Did you mean `other`?"));
+
+        // `tools` only suggests values within 1 edit, also for `list`
+        assert!(suggestion(&["tools", "plugn"]).contains("Did you mean `plugin`?"));
+        assert!(!suggestion(&["tools", "plgn"]).contains("Did you mean"));
+        assert!(suggestion(&["tools", "list", "--verbos"]).contains("Did you mean `--verbose`?"));
+        assert!(!suggestion(&["tools", "list", "--verb"]).contains("Did you mean"));
+
+        // `plugin` also suggests the commands provided at runtime
+        assert!(suggestion(&["tools", "plugin", "deplyo"]).contains("Did you mean `deploy`?"));
+        assert!(suggestion(&["tools", "plugin", "instal"]).contains("Did you mean `install`?"));
+        assert!(suggestion(&["tools", "plugin", "install", "--frc"]).contains("Did you mean `--force`?"));
+    }
 }
@@ -0,0 +1,394 @@
+use crate::{Argument, Command, CommandOption, ParseResult, Result};
+use std::borrow::Borrow;
+
+/// A command, like `clap::App`.
+///
+/// # Example
+/// ```
+/// use clapi::compat::clap::{App, Arg};
+///
+/// let result = App::new("myapp")
+///     .about("Does awesome things")
+///     .arg(Arg::with_name("config")
+///         .short("c")
+///         .long("config")
+///         .takes_value(true))
+///     .arg(Arg::with_name("INPUT")
+///         .required(true))
+///     .get_matches_from(vec!["myapp", "-c", "app.toml", "file.txt"]);
+///
+/// assert_eq!(result.value_of_option("config"), Some("app.toml"));
+/// assert_eq!(result.value_of("INPUT"), Some("file.txt"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct App {
+    command: Command,
+}
+
+impl App {
+    /// Constructs a new `App` with the given name.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        App {
+            command: Command::new(name),
+        }
+    }
+
+    /// Sets the version of this app.
+    pub fn version<S: Into<String>>(mut self, version: S) -> Self {
+        self.command = self.command.version(version);
+        self
+    }
+
+    /// Sets the description of this app.
+    pub fn about<S: Into<String>>(mut self, about: S) -> Self {
+        self.command = self.command.description(about);
+        self
+    }
+
+    /// Adds an option if the `Arg` have a `long` or `short` name, otherwise adds an argument.
+    pub fn arg(mut self, arg: Arg) -> Self {
+        self.command = match arg.into_kind() {
+            ArgKind::Option(option) => self.command.option(option),
+            ArgKind::Argument(argument) => self.command.arg(argument),
+        };
+        self
+    }
+
+    /// Adds all the given `Arg`s.
+    pub fn args<I: IntoIterator<Item = Arg>>(self, args: I) -> Self {
+        args.into_iter().fold(self, |app, arg| app.arg(arg))
+    }
+
+    /// Adds a subcommand to this app.
+    pub fn subcommand(mut self, app: App) -> Self {
+        self.command = self.command.subcommand(app.command);
+        self
+    }
+
+    /// Returns the `Command` of this app.
+    pub fn into_command(self) -> Command {
+        self.command
+    }
+
+    /// Parses the program arguments, prints the error and exit the process if fails.
+    pub fn get_matches(self) -> ParseResult {
+        self.command.parse_args().unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parses the given arguments, prints the error and exit the process if fails.
+    ///
+    /// Like `clap` the first argument is the name of the binary and is ignored.
+    pub fn get_matches_from<S, I>(self, args: I) -> ParseResult
+    where
+        S: Borrow<str>,
+        I: IntoIterator<Item = S>,
+    {
+        self.try_get_matches_from(args).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parses the given arguments and returns the `ParseResult` or the error.
+    ///
+    /// Like `clap` the first argument is the name of the binary and is ignored.
+    pub fn try_get_matches_from<S, I>(self, args: I) -> Result<ParseResult>
+    where
+        S: Borrow<str>,
+        I: IntoIterator<Item = S>,
+    {
+        self.command.parse_from(args.into_iter().skip(1))
+    }
+}
+
+impl From<App> for Command {
+    fn from(app: App) -> Self {
+        app.into_command()
+    }
+}
+
+/// An option or argument, like `clap::Arg`.
+#[derive(Debug, Clone)]
+pub struct Arg {
+    name: String,
+    long: Option<String>,
+    short: Option<String>,
+    help: Option<String>,
+    takes_value: bool,
+    required: bool,
+    multiple: bool,
+    default_value: Option<String>,
+    possible_values: Vec<String>,
+}
+
+impl Arg {
+    /// Constructs a new `Arg` with the given name.
+    pub fn with_name<S: Into<String>>(name: S) -> Self {
+        Arg {
+            name: name.into(),
+            long: None,
+            short: None,
+            help: None,
+            takes_value: false,
+            required: false,
+            multiple: false,
+            default_value: None,
+            possible_values: vec![],
+        }
+    }
+
+    /// Sets the long name of this option, like `--config`.
+    pub fn long<S: Into<String>>(mut self, long: S) -> Self {
+        self.long = Some(long.into());
+        self
+    }
+
+    /// Sets the short name of this option, like `-c`.
+    pub fn short<S: Into<String>>(mut self, short: S) -> Self {
+        self.short = Some(short.into());
+        self
+    }
+
+    /// Sets the description of this option or argument.
+    pub fn help<S: Into<String>>(mut self, help: S) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Specify if this option takes a value, otherwise is a flag.
+    pub fn takes_value(mut self, takes_value: bool) -> Self {
+        self.takes_value = takes_value;
+        self
+    }
+
+    /// Specify if this option or argument is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Specify if this option or argument takes multiple values,
+    /// an option also can be declared multiple times.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Sets the default value, this option takes a value.
+    pub fn default_value<S: Into<String>>(mut self, value: S) -> Self {
+        self.default_value = Some(value.into());
+        self.takes_value = true;
+        self
+    }
+
+    /// Sets the valid values, this option takes a value.
+    pub fn possible_values<S: ToString>(mut self, values: &[S]) -> Self {
+        self.possible_values = values.iter().map(|s| s.to_string()).collect();
+        self.takes_value = true;
+        self
+    }
+
+    // Converts to a `CommandOption` if have a `long` or `short` name, otherwise to an `Argument`
+    fn into_kind(self) -> ArgKind {
+        let mut argument = Argument::with_name(self.name.as_str());
+
+        if let Some(help) = &self.help {
+            argument = argument.description(help);
+        }
+
+        if !self.possible_values.is_empty() {
+            argument = argument.valid_values(&self.possible_values);
+        }
+
+        if let Some(default_value) = &self.default_value {
+            argument = argument.default(default_value);
+        }
+
+        if self.long.is_none() && self.short.is_none() {
+            return ArgKind::Argument(match (self.required, self.multiple) {
+                (false, false) => argument.values_count(0..=1),
+                (true, false) => argument,
+                (false, true) => argument.values_count(0..),
+                (true, true) => argument.values_count(1..),
+            });
+        }
+
+        let mut option = CommandOption::new(self.long.unwrap_or(self.name))
+            .required(self.required)
+            .multiple(self.multiple);
+
+        if let Some(short) = self.short {
+            option = option.alias(short);
+        }
+
+        if let Some(help) = self.help {
+            option = option.description(help);
+        }
+
+        if self.takes_value {
+            if self.multiple {
+                argument = argument.values_count(1..);
+            }
+
+            option = option.arg(argument);
+        }
+
+        ArgKind::Option(option)
+    }
+}
+
+enum ArgKind {
+    Option(CommandOption),
+    Argument(Argument),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The executing command, the options and the args with its values
+    type Summary = (String, Vec<(String, Vec<String>)>, Vec<(String, Vec<String>)>);
+
+    fn summary(result: &ParseResult) -> Summary {
+        let options = result
+            .options()
+            .iter()
+            .map(|opt| {
+                let values = opt.get_args().iter().flat_map(|arg| arg.get_values().to_vec());
+                (opt.get_name().to_owned(), values.collect())
+            })
+            .collect();
+
+        let args = result
+            .args()
+            .iter()
+            .map(|arg| (arg.get_name().to_owned(), arg.get_values().to_vec()))
+            .collect();
+
+        (result.command_name().to_owned(), options, args)
+    }
+
+    fn assert_same_result(app: &App, command: &Command, args: &[&str]) {
+        let expected = command.clone().parse_from(args[1..].iter().copied()).map(|r| summary(&r));
+        let actual = app.clone().try_get_matches_from(args.iter().copied()).map(|r| summary(&r));
+
+        match (actual, expected) {
+            (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "{:?}", args),
+            (Err(actual), Err(expected)) => assert_eq!(actual.kind(), expected.kind(), "{:?}", args),
+            (actual, expected) => panic!("{:?}: {:?} != {:?}", args, actual, expected),
+        }
+    }
+
+    #[test]
+    fn app_with_options_test() {
+        let app = App::new("myapp")
+            .version("1.0")
+            .about("Does awesome things")
+            .arg(
+                Arg::with_name("config")
+                    .short("c")
+                    .long("config")
+                    .help("Sets a custom config file")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("INPUT")
+                    .help("Sets the input file to use")
+                    .required(true),
+            )
+            .arg(
+                Arg::with_name("debug")
+                    .short("d")
+                    .multiple(true)
+                    .help("Turn debugging information on"),
+            );
+
+        let command = Command::new("myapp")
+            .version("1.0")
+            .description("Does awesome things")
+            .option(
+                CommandOption::new("config")
+                    .alias("c")
+                    .description("Sets a custom config file")
+                    .arg(Argument::with_name("config").description("Sets a custom config file")),
+            )
+            .arg(Argument::with_name("INPUT").description("Sets the input file to use"))
+            .option(
+                CommandOption::new("debug")
+                    .alias("d")
+                    .multiple(true)
+                    .description("Turn debugging information on"),
+            );
+
+        assert_same_result(&app, &command, &["myapp", "-c", "app.toml", "file.txt"]);
+        assert_same_result(&app, &command, &["myapp", "--config=app.toml", "-d", "-d", "file.txt"]);
+        assert_same_result(&app, &command, &["myapp", "-d"]);
+        assert_same_result(&app, &command, &["myapp", "--config"]);
+
+        let result = app.get_matches_from(vec!["myapp", "-c", "app.toml", "file.txt"]);
+        assert_eq!(result.value_of_option("config"), Some("app.toml"));
+        assert_eq!(result.value_of("INPUT"), Some("file.txt"));
+        assert!(!result.options().contains("debug"));
+    }
+
+    #[test]
+    fn app_with_subcommands_test() {
+        let app = App::new("git")
+            .subcommand(
+                App::new("clone")
+                    .about("Clones a repository")
+                    .arg(Arg::with_name("remote").required(true))
+                    .arg(Arg::with_name("depth").long("depth").default_value("1")),
+            )
+            .subcommand(App::new("push").arg(Arg::with_name("force").short("f").long("force")));
+
+        let command = Command::new("git")
+            .subcommand(
+                Command::new("clone")
+                    .description("Clones a repository")
+                    .arg(Argument::with_name("remote"))
+                    .option(CommandOption::new("depth").arg(Argument::with_name("depth").default("1"))),
+            )
+            .subcommand(Command::new("push").option(CommandOption::new("force").alias("f")));
+
+        assert_same_result(&app, &command, &["git", "clone", "--depth", "3", "https://a.git"]);
+        assert_same_result(&app, &command, &["git", "clone", "https://a.git"]);
+        assert_same_result(&app, &command, &["git", "clone"]);
+        assert_same_result(&app, &command, &["git", "push", "-f"]);
+        assert_same_result(&app, &command, &["git", "pull"]);
+    }
+
+    #[test]
+    fn app_with_multiple_values_test() {
+        let app = App::new("fmt").args(vec![
+            Arg::with_name("mode")
+                .long("mode")
+                .possible_values(&["fast", "slow"])
+                .default_value("fast"),
+            Arg::with_name("exclude")
+                .short("e")
+                .long("exclude")
+                .takes_value(true)
+                .multiple(true),
+            Arg::with_name("files").multiple(true),
+        ]);
+
+        let command = Command::new("fmt")
+            .option(
+                CommandOption::new("mode").arg(
+                    Argument::with_name("mode")
+                        .valid_values(["fast", "slow"])
+                        .default("fast"),
+                ),
+            )
+            .option(
+                CommandOption::new("exclude")
+                    .alias("e")
+                    .multiple(true)
+                    .arg(Argument::one_or_more("exclude")),
+            )
+            .arg(Argument::zero_or_more("files"));
+
+        assert_same_result(&app, &command, &["fmt", "--exclude", "a", "b", "--mode", "slow", "--", "x.rs", "y.rs"]);
+        assert_same_result(&app, &command, &["fmt", "-e", "a", "-e", "b", "x.rs"]);
+        assert_same_result(&app, &command, &["fmt"]);
+        assert_same_result(&app, &command, &["fmt", "--mode", "medium"]);
+    }
+}
@@ -0,0 +1,6 @@
+/// A subset of the `clap` 2 builders to ease the migration from `clap`,
+/// `App` and `Arg` delegates to `Command`, `CommandOption` and `Argument`.
+///
+/// The `ParseResult` is returned in place of the `ArgMatches`, and the options are named
+/// after its `long` name, or the `Arg` name if there is no `long`.
+pub mod clap;
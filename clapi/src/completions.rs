@@ -0,0 +1,723 @@
+use crate::{Command, CommandOption, Context};
+use std::fmt::{Display, Formatter};
+use std::io::{Result, Write};
+use std::str::FromStr;
+
+/// A shell supported for the completion scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    /// The `bash` shell.
+    Bash,
+    /// The `zsh` shell.
+    Zsh,
+    /// The `fish` shell.
+    Fish,
+    /// The `PowerShell` shell.
+    PowerShell,
+}
+
+impl Shell {
+    /// All the supported shells.
+    pub const ALL: [Shell; 4] = [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell];
+
+    /// Returns the name of this shell.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+            Shell::PowerShell => "powershell",
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Shell::ALL
+            .iter()
+            .find(|shell| shell.as_str().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| format!("invalid shell: `{}`", s))
+    }
+}
+
+/// Writes the completion script of the given `Command` for the given `Shell`.
+///
+/// The script completes the subcommands, the option names and aliases and the valid values
+/// of the arguments, hidden commands and options are not included.
+/// Options that requires an assign operator are completed as `--option=`.
+///
+/// This uses the default prefixes `--` and `-`, use `CommandLine::generate_completions`
+/// to use the prefixes of the `Context`.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandOption};
+/// use clapi::completions::{generate_completions, Shell};
+///
+/// let command = Command::new("MyApp")
+///     .option(CommandOption::new("verbose").alias("v"))
+///     .subcommand(Command::new("test"));
+///
+/// let mut buf = Vec::new();
+/// generate_completions(&command, Shell::Bash, &mut buf).unwrap();
+///
+/// let script = String::from_utf8(buf).unwrap();
+/// assert!(script.contains("complete -o default -F _MyApp MyApp"));
+/// ```
+pub fn generate_completions(command: &Command, shell: Shell, buf: &mut dyn Write) -> Result<()> {
+    write_completions(&Context::new(command.clone()), shell, buf)
+}
+
+/// Writes the completion script of the root command of the given `Context` for the given `Shell`,
+/// using the prefixes and assign operator of the context.
+///
+/// See [`generate_completions`].
+pub fn write_completions(context: &Context, shell: Shell, buf: &mut dyn Write) -> Result<()> {
+    let script = Script::new(context);
+
+    match shell {
+        Shell::Bash => write_bash(&script, buf),
+        Shell::Zsh => write_zsh(&script, buf),
+        Shell::Fish => write_fish(&script, buf),
+        Shell::PowerShell => write_powershell(&script, buf),
+    }
+}
+
+// The data used to write the completion scripts
+struct Script {
+    name: String,
+    name_prefix: String,
+    alias_prefix: String,
+    assign: char,
+    nodes: Vec<Node>,
+}
+
+// A command of the completion script
+struct Node {
+    // Unique identifier of the command, derived from its path: `MyApp__remote__add`
+    id: String,
+    // Identifier of the parent and the name used for enter this command
+    parent: Option<(String, String)>,
+    subcommands: Vec<(String, Option<String>)>,
+    options: Vec<OptionEntry>,
+    values: Vec<String>,
+}
+
+struct OptionEntry {
+    name: String,
+    aliases: Vec<String>,
+    description: Option<String>,
+    takes_args: bool,
+    requires_assign: bool,
+    values: Vec<String>,
+}
+
+impl Script {
+    fn new(context: &Context) -> Self {
+        let root = context.root();
+        let mut nodes = Vec::new();
+
+        // The help and version options are hidden and only added to the commands
+        // when the root have subcommands, so we add them to all the commands
+        let builtin = context
+            .help_option()
+            .into_iter()
+            .chain(context.version_option())
+            .cloned()
+            .collect::<Vec<CommandOption>>();
+
+        collect_nodes(root, sanitize(root.get_name()), None, &builtin, &[], &mut nodes);
+
+        Script {
+            name: root.get_name().to_owned(),
+            // The `Context` always have prefixes and assign operators
+            name_prefix: context.name_prefixes().next().unwrap().to_owned(),
+            alias_prefix: context.alias_prefixes().next().unwrap().to_owned(),
+            assign: *context.assign_operators().next().unwrap(),
+            nodes,
+        }
+    }
+
+    fn root_id(&self) -> &str {
+        self.nodes[0].id.as_str()
+    }
+
+    // Returns the prefixed name and aliases of the option
+    fn option_names(&self, option: &OptionEntry) -> Vec<String> {
+        std::iter::once(format!("{}{}", self.name_prefix, option.name))
+            .chain(option.aliases.iter().map(|s| format!("{}{}", self.alias_prefix, s)))
+            .collect()
+    }
+
+    // Returns the words to complete for the command, subcommands, option names and argument values
+    fn words(&self, node: &Node) -> Vec<String> {
+        let mut words = node.subcommands.iter().map(|(name, _)| name.clone()).collect::<Vec<String>>();
+
+        for option in &node.options {
+            for name in self.option_names(option) {
+                if option.requires_assign {
+                    words.push(format!("{}{}", name, self.assign));
+                } else {
+                    words.push(name);
+                }
+            }
+        }
+
+        words.extend(node.values.iter().cloned());
+        words
+    }
+}
+
+fn collect_nodes(
+    command: &Command,
+    id: String,
+    parent: Option<(String, String)>,
+    builtin: &[CommandOption],
+    inherited: &[CommandOption],
+    nodes: &mut Vec<Node>,
+) {
+    let is_builtin = |o: &CommandOption| builtin.iter().any(|b| b.get_name() == o.get_name());
+    let mut options = command
+        .get_options()
+        .iter()
+        .filter(|o| !o.is_hidden() || is_builtin(o))
+        .cloned()
+        .collect::<Vec<CommandOption>>();
+
+    for option in inherited.iter().chain(builtin) {
+        if !options.iter().any(|o| o.get_name() == option.get_name()) {
+            options.push(option.clone());
+        }
+    }
+
+    let subcommands = command
+        .get_subcommands()
+        .filter(|c| !c.is_hidden())
+        .collect::<Vec<&Command>>();
+
+    nodes.push(Node {
+        id: id.clone(),
+        parent,
+        subcommands: subcommands
+            .iter()
+            .map(|c| (c.get_name().to_owned(), c.get_summary().map(|s| s.to_owned())))
+            .collect(),
+        options: options
+            .iter()
+            .map(|o| OptionEntry {
+                name: o.get_name().to_owned(),
+                aliases: o.get_aliases().cloned().collect(),
+                description: o.get_description().map(|s| s.to_owned()),
+                // Options like `--help` may take no values
+                takes_args: o.get_args().iter().any(|a| a.get_values_count().min_or_default() > 0),
+                requires_assign: o.is_assign_required() && o.take_args(),
+                values: o.get_args().iter().flat_map(|a| a.get_valid_values()).cloned().collect(),
+            })
+            .collect(),
+        values: command.get_args().iter().flat_map(|a| a.get_valid_values()).cloned().collect(),
+    });
+
+    // Global options are also completed in the subcommands
+    let inherited = options
+        .into_iter()
+        .filter(|o| o.is_global())
+        .collect::<Vec<CommandOption>>();
+
+    for subcommand in subcommands {
+        let child_id = format!("{}__{}", id, sanitize(subcommand.get_name()));
+        let parent = Some((id.clone(), subcommand.get_name().to_owned()));
+        collect_nodes(subcommand, child_id, parent, builtin, &inherited, nodes);
+    }
+}
+
+// Replaces the characters that are not valid in a shell function name
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+// Quotes the value with single quotes, valid for bash, zsh and fish
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn write_bash(script: &Script, w: &mut dyn Write) -> Result<()> {
+    let root_id = script.root_id();
+
+    writeln!(w, "# Completes the values of the `option{}value` words", script.assign)?;
+    writeln!(w, "__{}_assign_values() {{", root_id)?;
+    writeln!(w, "    local value=\"${{2#*{}}}\"", script.assign)?;
+    writeln!(w, "    if [[ \"${{COMP_WORDBREAKS}}\" == *{}* ]]; then", quote(&script.assign.to_string()))?;
+    writeln!(w, "        COMPREPLY=($(compgen -W \"$3\" -- \"${{value}}\"))")?;
+    writeln!(w, "    else")?;
+    writeln!(w, "        COMPREPLY=($(compgen -P \"$1\" -W \"$3\" -- \"${{value}}\"))")?;
+    writeln!(w, "    fi")?;
+    writeln!(w, "}}")?;
+    writeln!(w)?;
+    writeln!(w, "_{}() {{", root_id)?;
+    writeln!(w, "    local cur prev line word cmd i")?;
+    writeln!(w, "    cur=\"${{COMP_WORDS[COMP_CWORD]}}\"")?;
+    writeln!(w, "    prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"")?;
+    writeln!(w, "    line=\"${{COMP_LINE:0:COMP_POINT}}\"")?;
+    writeln!(w, "    word=\"${{line##*[[:space:]]}}\"")?;
+    writeln!(w, "    cmd={}", quote(root_id))?;
+    writeln!(w)?;
+    writeln!(w, "    for ((i = 1; i < COMP_CWORD; i++)); do")?;
+    writeln!(w, "        case \"${{cmd}},${{COMP_WORDS[i]}}\" in")?;
+    for node in &script.nodes[1..] {
+        let (parent, name) = node.parent.as_ref().unwrap();
+        writeln!(w, "            {}) cmd={} ;;", quote(&format!("{},{}", parent, name)), quote(&node.id))?;
+    }
+    writeln!(w, "        esac")?;
+    writeln!(w, "    done")?;
+    writeln!(w)?;
+    writeln!(w, "    case \"${{cmd}}\" in")?;
+
+    for node in &script.nodes {
+        writeln!(w, "        {})", quote(&node.id))?;
+
+        let (assign_options, value_options) = node
+            .options
+            .iter()
+            .filter(|o| o.takes_args)
+            .partition::<Vec<&OptionEntry>, _>(|o| o.requires_assign);
+
+        if !value_options.is_empty() {
+            writeln!(w, "            case \"${{prev}}\" in")?;
+            for option in value_options {
+                let names = script.option_names(option).iter().map(|s| quote(s)).collect::<Vec<String>>();
+                writeln!(w, "                {})", names.join("|"))?;
+                if option.values.is_empty() {
+                    writeln!(w, "                    COMPREPLY=()")?;
+                } else {
+                    let values = quote(&option.values.join(" "));
+                    writeln!(w, "                    COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))", values)?;
+                }
+                writeln!(w, "                    return 0")?;
+                writeln!(w, "                    ;;")?;
+            }
+            writeln!(w, "            esac")?;
+        }
+
+        if !assign_options.is_empty() {
+            writeln!(w, "            case \"${{word}}\" in")?;
+            for option in assign_options {
+                for name in script.option_names(option) {
+                    let prefix = quote(&format!("{}{}", name, script.assign));
+                    writeln!(w, "                {}*)", prefix)?;
+                    if option.values.is_empty() {
+                        writeln!(w, "                    COMPREPLY=()")?;
+                    } else {
+                        let values = quote(&option.values.join(" "));
+                        writeln!(w, "                    __{}_assign_values {} \"${{word}}\" {}", root_id, prefix, values)?;
+                    }
+                    writeln!(w, "                    return 0")?;
+                    writeln!(w, "                    ;;")?;
+                }
+            }
+            writeln!(w, "            esac")?;
+        }
+
+        let words = quote(&script.words(node).join(" "));
+        writeln!(w, "            COMPREPLY=($(compgen -W {} -- \"${{cur}}\"))", words)?;
+        writeln!(w, "            ;;")?;
+    }
+
+    writeln!(w, "    esac")?;
+    writeln!(w)?;
+    writeln!(w, "    if [[ \"${{COMPREPLY[0]}}\" == *{} ]]; then", quote(&script.assign.to_string()))?;
+    writeln!(w, "        compopt -o nospace")?;
+    writeln!(w, "    fi")?;
+    writeln!(w, "}}")?;
+    writeln!(w)?;
+    writeln!(w, "complete -o default -F _{} {}", root_id, script.name)
+}
+
+fn write_zsh(script: &Script, w: &mut dyn Write) -> Result<()> {
+    let root_id = script.root_id();
+
+    writeln!(w, "#compdef {}", script.name)?;
+    writeln!(w)?;
+    writeln!(w, "_{}() {{", root_id)?;
+    writeln!(w, "    local cur prev cmd i")?;
+    writeln!(w, "    cmd={}", quote(root_id))?;
+    writeln!(w)?;
+    writeln!(w, "    for ((i = 2; i < CURRENT; i++)); do")?;
+    writeln!(w, "        case \"${{cmd}},${{words[i]}}\" in")?;
+    for node in &script.nodes[1..] {
+        let (parent, name) = node.parent.as_ref().unwrap();
+        writeln!(w, "            {}) cmd={} ;;", quote(&format!("{},{}", parent, name)), quote(&node.id))?;
+    }
+    writeln!(w, "        esac")?;
+    writeln!(w, "    done")?;
+    writeln!(w)?;
+    writeln!(w, "    cur=\"${{words[CURRENT]}}\"")?;
+    writeln!(w, "    prev=\"${{words[CURRENT-1]}}\"")?;
+    writeln!(w)?;
+    writeln!(w, "    case \"${{cmd}}\" in")?;
+
+    for node in &script.nodes {
+        writeln!(w, "        {})", quote(&node.id))?;
+
+        let (assign_options, value_options) = node
+            .options
+            .iter()
+            .filter(|o| o.takes_args)
+            .partition::<Vec<&OptionEntry>, _>(|o| o.requires_assign);
+
+        if !value_options.is_empty() {
+            writeln!(w, "            case \"${{prev}}\" in")?;
+            for option in value_options {
+                let names = script.option_names(option).iter().map(|s| quote(s)).collect::<Vec<String>>();
+                writeln!(w, "                {})", names.join("|"))?;
+                write_zsh_values(w, &option.values)?;
+                writeln!(w, "                    return")?;
+                writeln!(w, "                    ;;")?;
+            }
+            writeln!(w, "            esac")?;
+        }
+
+        if !assign_options.is_empty() {
+            writeln!(w, "            case \"${{cur}}\" in")?;
+            for option in assign_options {
+                for name in script.option_names(option) {
+                    writeln!(w, "                {}*)", quote(&format!("{}{}", name, script.assign)))?;
+                    writeln!(w, "                    compset -P {}", quote(&format!("*{}", script.assign)))?;
+                    write_zsh_values(w, &option.values)?;
+                    writeln!(w, "                    return")?;
+                    writeln!(w, "                    ;;")?;
+                }
+            }
+            writeln!(w, "            esac")?;
+        }
+
+        let mut words = script.words(node);
+        let assign_words = words
+            .iter()
+            .filter(|s| s.ends_with(script.assign))
+            .cloned()
+            .collect::<Vec<String>>();
+
+        words.retain(|s| !s.ends_with(script.assign));
+
+        if !words.is_empty() {
+            let words = words.iter().map(|s| quote(s)).collect::<Vec<String>>();
+            writeln!(w, "            compadd -- {}", words.join(" "))?;
+        }
+
+        if !assign_words.is_empty() {
+            // No space after `--option=`
+            let words = assign_words.iter().map(|s| quote(s)).collect::<Vec<String>>();
+            writeln!(w, "            compadd -S '' -- {}", words.join(" "))?;
+        }
+
+        writeln!(w, "            ;;")?;
+    }
+
+    writeln!(w, "    esac")?;
+    writeln!(w, "}}")?;
+    writeln!(w)?;
+    writeln!(w, "if [ \"$funcstack[1]\" = \"_{}\" ]; then", root_id)?;
+    writeln!(w, "    _{} \"$@\"", root_id)?;
+    writeln!(w, "else")?;
+    writeln!(w, "    compdef _{} {}", root_id, script.name)?;
+    writeln!(w, "fi")
+}
+
+fn write_zsh_values(w: &mut dyn Write, values: &[String]) -> Result<()> {
+    if values.is_empty() {
+        writeln!(w, "                    _files")
+    } else {
+        let values = values.iter().map(|s| quote(s)).collect::<Vec<String>>();
+        writeln!(w, "                    compadd -- {}", values.join(" "))
+    }
+}
+
+fn write_fish(script: &Script, w: &mut dyn Write) -> Result<()> {
+    let root_id = script.root_id();
+    let name = quote(&script.name);
+
+    writeln!(w, "function __fish_{}_command", root_id)?;
+    writeln!(w, "    set -l cmd {}", quote(root_id))?;
+    writeln!(w, "    set -l words (commandline -opc)")?;
+    writeln!(w, "    set -e words[1]")?;
+    writeln!(w, "    for word in $words")?;
+    writeln!(w, "        switch \"$cmd,$word\"")?;
+    for node in &script.nodes[1..] {
+        let (parent, name) = node.parent.as_ref().unwrap();
+        writeln!(w, "            case {}", quote(&format!("{},{}", parent, name)))?;
+        writeln!(w, "                set cmd {}", quote(&node.id))?;
+    }
+    writeln!(w, "        end")?;
+    writeln!(w, "    end")?;
+    writeln!(w, "    echo $cmd")?;
+    writeln!(w, "end")?;
+    writeln!(w)?;
+    writeln!(w, "complete -c {} -f", name)?;
+
+    for node in &script.nodes {
+        let condition = quote(&format!("test (__fish_{}_command) = {}", root_id, node.id));
+
+        for (subcommand, description) in &node.subcommands {
+            write!(w, "complete -c {} -n {} -a {}", name, condition, quote(subcommand))?;
+            if let Some(description) = description {
+                write!(w, " -d {}", quote(description))?;
+            }
+            writeln!(w)?;
+        }
+
+        for option in &node.options {
+            write!(w, "complete -c {} -n {}", name, condition)?;
+            write!(w, " {}", fish_option_flag(&script.name_prefix, &option.name))?;
+            for alias in &option.aliases {
+                write!(w, " {}", fish_option_flag(&script.alias_prefix, alias))?;
+            }
+
+            if option.takes_args {
+                if option.values.is_empty() {
+                    write!(w, " -r -F")?;
+                } else {
+                    write!(w, " -r -a {}", quote(&option.values.join(" ")))?;
+                }
+            }
+
+            if let Some(description) = &option.description {
+                write!(w, " -d {}", quote(description))?;
+            }
+            writeln!(w)?;
+        }
+
+        if !node.values.is_empty() {
+            writeln!(w, "complete -c {} -n {} -a {}", name, condition, quote(&node.values.join(" ")))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the `complete` flag for the option, fish only understand the `-` and `--` prefixes
+fn fish_option_flag(prefix: &str, name: &str) -> String {
+    match prefix {
+        "--" => format!("-l {}", quote(name)),
+        "-" if name.chars().count() == 1 => format!("-s {}", quote(name)),
+        "-" => format!("-o {}", quote(name)),
+        _ => format!("-a {}", quote(&format!("{}{}", prefix, name))),
+    }
+}
+
+fn write_powershell(script: &Script, w: &mut dyn Write) -> Result<()> {
+    // PowerShell escapes single quotes doubling them
+    fn quote(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    fn array(values: &[String]) -> String {
+        format!("@({})", values.iter().map(|s| quote(s)).collect::<Vec<String>>().join(", "))
+    }
+
+    writeln!(w, "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{", quote(&script.name))?;
+    writeln!(w, "    param($wordToComplete, $commandAst, $cursorPosition)")?;
+    writeln!(w)?;
+    writeln!(w, "    $cmd = {}", quote(script.root_id()))?;
+    writeln!(w, "    $prev = ''")?;
+    writeln!(w, "    foreach ($element in $commandAst.CommandElements | Select-Object -Skip 1) {{")?;
+    writeln!(w, "        if ($element.Extent.EndOffset -ge $cursorPosition) {{ break }}")?;
+    writeln!(w, "        $word = $element.ToString()")?;
+    writeln!(w, "        $cmd = switch (\"$cmd,$word\") {{")?;
+    for node in &script.nodes[1..] {
+        let (parent, name) = node.parent.as_ref().unwrap();
+        writeln!(w, "            {} {{ {}; break }}", quote(&format!("{},{}", parent, name)), quote(&node.id))?;
+    }
+    writeln!(w, "            default {{ $cmd }}")?;
+    writeln!(w, "        }}")?;
+    writeln!(w, "        $prev = $word")?;
+    writeln!(w, "    }}")?;
+    writeln!(w)?;
+    writeln!(w, "    $completions = switch (\"$cmd,$prev\") {{")?;
+    for node in &script.nodes {
+        for option in node.options.iter().filter(|o| o.takes_args && !o.requires_assign) {
+            for name in script.option_names(option) {
+                writeln!(w, "        {} {{ {}; break }}", quote(&format!("{},{}", node.id, name)), array(&option.values))?;
+            }
+        }
+    }
+    writeln!(w, "        default {{")?;
+    writeln!(w, "            switch ($cmd) {{")?;
+    for node in &script.nodes {
+        let mut words = script.words(node);
+
+        // Also complete the values of `--option=value`
+        for option in node.options.iter().filter(|o| o.requires_assign) {
+            for name in script.option_names(option) {
+                for value in &option.values {
+                    words.push(format!("{}{}{}", name, script.assign, value));
+                }
+            }
+        }
+
+        writeln!(w, "                {} {{ {}; break }}", quote(&node.id), array(&words))?;
+    }
+    writeln!(w, "            }}")?;
+    writeln!(w, "        }}")?;
+    writeln!(w, "    }}")?;
+    writeln!(w)?;
+    writeln!(w, "    $completions | Where-Object {{ $_ -like \"$wordToComplete*\" }} | ForEach-Object {{")?;
+    writeln!(w, "        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)")?;
+    writeln!(w, "    }}")?;
+    writeln!(w, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Argument;
+
+    fn command() -> Command {
+        Command::new("git")
+            .option(CommandOption::new("verbose").alias("v").global(true))
+            .option(
+                CommandOption::new("color")
+                    .requires_assign(true)
+                    .arg(Argument::with_name("when").valid_values(["always", "never", "auto"])),
+            )
+            .subcommand(
+                Command::new("remote")
+                    .description("Manage remotes")
+                    .subcommand(
+                        Command::new("add")
+                            .option(CommandOption::new("track").alias("t").arg(Argument::with_name("branch")))
+                            .arg(Argument::with_name("name")),
+                    )
+                    .subcommand(Command::new("remove").arg(Argument::with_name("name"))),
+            )
+            .subcommand(
+                Command::new("log")
+                    .description("Shows the logs. Uses a pager if the output is a terminal.")
+                    .option(
+                        CommandOption::new("format")
+                            .arg(Argument::with_name("format").valid_values(["short", "full"])),
+                    )
+                    .option(CommandOption::new("internal").hidden(true)),
+            )
+            .subcommand(Command::new("debug").hidden(true))
+    }
+
+    fn completions(shell: Shell) -> String {
+        let mut buf = Vec::new();
+        generate_completions(&command(), shell, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn shell_from_str_test() {
+        for shell in Shell::ALL.iter() {
+            assert_eq!(shell.to_string().parse::<Shell>(), Ok(*shell));
+        }
+
+        assert_eq!("PowerShell".parse::<Shell>(), Ok(Shell::PowerShell));
+        assert!("cmd".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn bash_completions_test() {
+        let script = completions(Shell::Bash);
+
+        assert!(script.contains("'git,remote') cmd='git__remote' ;;"));
+        assert!(script.contains("'git__remote,add') cmd='git__remote__add' ;;"));
+        assert!(script.contains("'git__remote,remove') cmd='git__remote__remove' ;;"));
+        assert!(script.contains("COMPREPLY=($(compgen -W 'remote log --verbose -v --color=' -- \"${cur}\"))"));
+        assert!(script.contains("COMPREPLY=($(compgen -W 'add remove --verbose -v' -- \"${cur}\"))"));
+        assert!(script.contains("COMPREPLY=($(compgen -W 'short full' -- \"${cur}\"))"));
+        assert!(script.contains("'--track'|'-t')"));
+        assert!(script.contains("'--color='*)"));
+        assert!(script.contains("__git_assign_values '--color=' \"${word}\" 'always never auto'"));
+        assert!(script.contains("complete -o default -F _git git"));
+
+        assert!(!script.contains("debug"));
+        assert!(!script.contains("--internal"));
+    }
+
+    #[test]
+    fn zsh_completions_test() {
+        let script = completions(Shell::Zsh);
+
+        assert!(script.starts_with("#compdef git\n"));
+        assert!(script.contains("'git__remote,add') cmd='git__remote__add' ;;"));
+        assert!(script.contains("compadd -- 'remote' 'log' '--verbose' '-v'"));
+        assert!(script.contains("compadd -S '' -- '--color='"));
+        assert!(script.contains("compadd -- 'add' 'remove' '--verbose' '-v'"));
+        assert!(script.contains("compadd -- 'always' 'never' 'auto'"));
+        assert!(script.contains("'--track'|'-t')"));
+        assert!(script.contains("compdef _git git"));
+
+        assert!(!script.contains("debug"));
+        assert!(!script.contains("--internal"));
+    }
+
+    #[test]
+    fn fish_completions_test() {
+        let script = completions(Shell::Fish);
+
+        assert!(script.contains("case 'git__remote,add'\n                set cmd 'git__remote__add'"));
+        assert!(script.contains(
+            "complete -c 'git' -n 'test (__fish_git_command) = git' -a 'remote' -d 'Manage remotes'"
+        ));
+        assert!(script.contains(
+            "complete -c 'git' -n 'test (__fish_git_command) = git' -a 'log' -d 'Shows the logs.'"
+        ));
+        assert!(script.contains("-n 'test (__fish_git_command) = git__remote' -a 'add'"));
+        assert!(script.contains("-n 'test (__fish_git_command) = git__remote__add' -l 'verbose' -s 'v'"));
+        assert!(script.contains("-l 'color' -r -a 'always never auto'"));
+        assert!(script.contains("-l 'track' -s 't' -r -F"));
+
+        assert!(!script.contains("debug"));
+        assert!(!script.contains("'internal'"));
+    }
+
+    #[test]
+    fn powershell_completions_test() {
+        let script = completions(Shell::PowerShell);
+
+        assert!(script.contains("Register-ArgumentCompleter -Native -CommandName 'git'"));
+        assert!(script.contains("'git__remote,add' { 'git__remote__add'; break }"));
+        assert!(script.contains("'git__log,--format' { @('short', 'full'); break }"));
+        assert!(script.contains(
+            "'git' { @('remote', 'log', '--verbose', '-v', '--color=', '--color=always', '--color=never', '--color=auto'); break }"
+        ));
+
+        assert!(!script.contains("debug"));
+        assert!(!script.contains("--internal"));
+    }
+
+    #[test]
+    fn completions_context_prefixes_test() {
+        let context = Context::builder(command())
+            .name_prefix("/")
+            .alias_prefix("/")
+            .assign_operator(':')
+            .build();
+
+        let mut buf = Vec::new();
+        write_completions(&context, Shell::Bash, &mut buf).unwrap();
+        let script = String::from_utf8(buf).unwrap();
+
+        assert!(script.contains("COMPREPLY=($(compgen -W 'remote log /verbose /v /color:' -- \"${cur}\"))"));
+        assert!(script.contains("'/color:'*)"));
+
+        let mut buf = Vec::new();
+        write_completions(&context, Shell::Fish, &mut buf).unwrap();
+        let script = String::from_utf8(buf).unwrap();
+
+        assert!(script.contains("-a '/verbose' -a '/v'"));
+    }
+}
@@ -1,10 +1,12 @@
 use crate::command::Command;
-use crate::option::CommandOption;
+use crate::option::{CommandOption, NameCase, OptionList};
+use crate::settings::CommandSettings;
 use crate::suggestion::SuggestionSource;
 use std::fmt::{Debug, Formatter};
 use crate::utils::debug_option;
 use crate::Argument;
-use crate::help::HelpSource;
+use crate::help::{HelpSource, HelpStyle};
+use std::collections::HashMap;
 
 /// Provides configuration info for parsing a command.
 ///
@@ -42,10 +44,22 @@ pub struct Context {
     alias_prefixes: Vec<String>,
     assign_operators: Vec<char>,
     delimiter: char,
+    command_separator: Option<String>,
     help_option: Option<CommandOption>,
     help_command: Option<Command>,
     version_option: Option<CommandOption>,
     version_command: Option<Command>,
+    doctor_command: Option<Command>,
+    lenient: bool,
+    allow_option_bundling: bool,
+    collect_errors: bool,
+    metrics: bool,
+    ignore_option_case: bool,
+    env_prefix: Option<String>,
+    help_style: HelpStyle,
+    global_options: OptionList,
+    option_lookup: OptionLookup,
+    global_option_lookup: HashMap<String, usize>,
 }
 
 impl Context {
@@ -90,6 +104,11 @@ impl Context {
         self.delimiter
     }
 
+    /// Returns the token used to separate several commands in one invocation, if any.
+    pub fn command_separator(&self) -> Option<&str> {
+        self.command_separator.as_deref()
+    }
+
     /// Returns the `SuggestionProvider` or `None` if not set.
     pub fn suggestions(&self) -> Option<&SuggestionSource> {
         self.suggestions.as_ref()
@@ -120,6 +139,150 @@ impl Context {
         self.version_command.as_ref()
     }
 
+    /// Gets the doctor `Command` of this context, see `CommandLine::use_doctor`.
+    pub fn doctor_command(&self) -> Option<&Command> {
+        self.doctor_command.as_ref()
+    }
+
+    /// Returns `true` if unknown options and subcommands are ignored instead of failing the parse.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Returns `true` if bundled aliases like `-abc` are expanded to `-a -b -c`.
+    pub fn is_option_bundling_allowed(&self) -> bool {
+        self.allow_option_bundling
+    }
+
+    /// Returns `true` if the parser reports all the errors found instead of only the first one.
+    pub fn is_collecting_errors(&self) -> bool {
+        self.collect_errors
+    }
+
+    /// Returns `true` if the parser records the `ParseMetrics` of each parse.
+    pub fn is_metrics_enabled(&self) -> bool {
+        self.metrics
+    }
+
+    /// Returns `true` if the names and aliases of the options are compared ignoring the case.
+    pub fn is_option_case_ignored(&self) -> bool {
+        self.ignore_option_case
+    }
+
+    // Returns how the names and aliases of the options are compared.
+    pub(crate) fn option_case(&self) -> NameCase {
+        if self.ignore_option_case {
+            NameCase::Insensitive
+        } else {
+            NameCase::Sensitive
+        }
+    }
+
+    /// Returns the prefix of the environment variables derived from the option names, if any.
+    pub fn env_prefix(&self) -> Option<&str> {
+        self.env_prefix.as_deref()
+    }
+
+    /// Returns the `HelpStyle` used for the help messages.
+    pub fn help_style(&self) -> &HelpStyle {
+        &self.help_style
+    }
+
+    /// Returns the options available for every command of this context.
+    pub fn global_options(&self) -> &OptionList {
+        &self.global_options
+    }
+
+    /// Returns the version of the given command of this context,
+    /// or the version of its closest parent if the command have no version.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Context};
+    ///
+    /// let context = Context::new(Command::new("MyApp")
+    ///     .version("1.0")
+    ///     .subcommand(Command::new("server").version("2.0"))
+    ///     .subcommand(Command::new("client")));
+    ///
+    /// let server = context.root().find_subcommand("server").unwrap();
+    /// let client = context.root().find_subcommand("client").unwrap();
+    /// assert_eq!(context.command_version(server), Some("2.0"));
+    /// assert_eq!(context.command_version(client), Some("1.0"));
+    /// ```
+    pub fn command_version<'a>(&'a self, command: &'a Command) -> Option<&'a str> {
+        // Finds the parents of the command, the commands are compared by address
+        fn find_parents<'c>(current: &'c Command, command: &Command, parents: &mut Vec<&'c Command>) -> bool {
+            if std::ptr::eq(current, command) {
+                return true;
+            }
+
+            parents.push(current);
+            if current.get_subcommands().any(|child| find_parents(child, command, parents)) {
+                return true;
+            }
+
+            parents.pop();
+            false
+        }
+
+        if let Some(version) = command.get_version() {
+            return Some(version);
+        }
+
+        let mut parents = Vec::new();
+        if find_parents(&self.root, command, &mut parents) {
+            parents.iter().rev().find_map(|c| c.get_version())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the environment variable used as the value of the given option when is not passed.
+    ///
+    /// This is the `env` of the option, or `<PREFIX>_<OPTION_NAME>` if the context have an `env_prefix`,
+    /// where any character that is not alphanumeric is replaced with `_` and the name is uppercase.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Context};
+    ///
+    /// let context = Context::builder(Command::new("MyApp"))
+    ///     .env_prefix("MYAPP")
+    ///     .build();
+    ///
+    /// let dry_run = CommandOption::new("dry-run");
+    /// assert_eq!(context.env_var_name(&dry_run), Some("MYAPP_DRY_RUN".to_owned()));
+    ///
+    /// let color = CommandOption::new("color").env("COLOR");
+    /// assert_eq!(context.env_var_name(&color), Some("COLOR".to_owned()));
+    ///
+    /// let verbose = CommandOption::new("verbose").use_env(false);
+    /// assert_eq!(context.env_var_name(&verbose), None);
+    /// ```
+    pub fn env_var_name(&self, option: &CommandOption) -> Option<String> {
+        if !option.is_env_enabled()
+            || is_help_option(self, option.get_name())
+            || self.version_option.as_ref().is_some_and(|o| o.get_name() == option.get_name())
+        {
+            return None;
+        }
+
+        if let Some(env) = option.get_env() {
+            return Some(env.to_owned());
+        }
+
+        self.env_prefix.as_ref().map(|prefix| {
+            let name = option
+                .get_name()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+                .collect::<String>();
+
+            format!("{}_{}", prefix, name)
+        })
+    }
+
     /// Sets the `SuggestionSource` of this context.
     pub fn set_suggestions(&mut self, suggestions: SuggestionSource) {
         self.suggestions = Some(suggestions);
@@ -130,32 +293,165 @@ impl Context {
         self.help = help;
     }
 
+    /// Sets whether unknown options and subcommands are ignored instead of failing the parse.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    /// Sets whether bundled aliases like `-abc` are expanded to `-a -b -c`.
+    pub fn set_allow_option_bundling(&mut self, allow: bool) {
+        self.allow_option_bundling = allow;
+    }
+
+    /// Sets whether the parser reports all the errors found instead of only the first one.
+    pub fn set_collect_errors(&mut self, collect: bool) {
+        self.collect_errors = collect;
+    }
+
+    /// Sets whether the parser records the `ParseMetrics` of each parse.
+    pub fn set_metrics(&mut self, enabled: bool) {
+        self.metrics = enabled;
+    }
+
+    /// Sets whether the names and aliases of the options are compared ignoring the case.
+    ///
+    /// # Panics
+    /// If a command or the global options contains options which names or aliases only differ in case.
+    pub fn set_ignore_option_case(&mut self, ignore: bool) {
+        let case = if ignore { NameCase::Insensitive } else { NameCase::Sensitive };
+        self.validate_options(case);
+        self.ignore_option_case = ignore;
+        self.rebuild_option_lookup();
+    }
+
+    /// Sets the option name prefix of this context, replacing the current prefixes.
+    ///
+    /// # Panics
+    /// If the prefix contains whitespaces, numbers or letters, or if an option starts with it.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Context};
+    ///
+    /// let mut context = Context::new(Command::new("MyApp").option(CommandOption::new("verbose")));
+    /// context.set_name_prefix("/");
+    ///
+    /// assert!(context.is_name_prefix("/"));
+    /// assert!(!context.is_name_prefix("--"));
+    /// ```
+    pub fn set_name_prefix<S: Into<String>>(&mut self, prefix: S) {
+        let prefix = prefix.into();
+        assert_valid_symbol("prefixes", prefix.as_str());
+        self.name_prefixes = vec![prefix];
+        assert_valid_names(self, &self.root, true);
+        self.root.clear_usage_cache();
+    }
+
+    /// Sets the option alias prefix of this context, replacing the current prefixes.
+    ///
+    /// # Panics
+    /// If the prefix contains whitespaces, numbers or letters, or if an option starts with it.
+    pub fn set_alias_prefix<S: Into<String>>(&mut self, prefix: S) {
+        let prefix = prefix.into();
+        assert_valid_symbol("prefixes", prefix.as_str());
+        self.alias_prefixes = vec![prefix];
+        assert_valid_names(self, &self.root, true);
+        self.root.clear_usage_cache();
+    }
+
+    /// Sets the assign operator of this context, replacing the current operators.
+    ///
+    /// # Panics
+    /// If the operator is a whitespace, number or letter, or if an option contains it.
+    pub fn set_assign_operator(&mut self, value: char) {
+        assert_valid_symbol("assign chars", value.encode_utf8(&mut [0; 4]));
+        self.assign_operators = vec![value];
+        assert_valid_names(self, &self.root, true);
+        self.root.clear_usage_cache();
+    }
+
+    /// Sets the token used to separate several commands in one invocation.
+    ///
+    /// # Panics
+    /// If the separator is empty, contains whitespaces, numbers or letters.
+    pub fn set_command_separator<S: Into<String>>(&mut self, separator: S) {
+        let separator = separator.into();
+        assert_valid_command_separator(&separator);
+        self.command_separator = Some(separator);
+    }
+
+    /// Sets the prefix of the environment variables derived from the option names.
+    pub fn set_env_prefix<S: Into<String>>(&mut self, prefix: S) {
+        self.env_prefix = Some(prefix.into());
+    }
+
+    /// Sets the `HelpStyle` used for the help messages.
+    pub fn set_help_style(&mut self, style: HelpStyle) {
+        self.help_style = style;
+        self.root.clear_usage_cache();
+    }
+
     /// Sets the help `CommandOption` of this context.
+    ///
+    /// See `ContextBuilder::help_option` for the names already in use.
     pub fn set_help_option(&mut self, option: CommandOption) {
         assert!(self.help_option.is_none(), "`Context` already contains a help option");
         self.help_option = Some(option);
         add_command_builtin_help_option(self);
+        self.rebuild_option_lookup();
     }
 
     /// Sets the help `Command` of this context.
+    ///
+    /// See `ContextBuilder::help_command` for the names already in use.
     pub fn set_help_command(&mut self, command: Command) {
         assert!(self.help_command.is_none(), "`Context` already contains a help command");
         self.help_command = Some(command);
         add_command_builtin_help_command(self);
+        self.rebuild_option_lookup();
     }
 
     /// Sets the version `CommandOption` of this context.
+    ///
+    /// See `ContextBuilder::version_option` for the names already in use.
     pub fn set_version_option(&mut self, option: CommandOption) {
         assert!(self.version_option.is_none(), "`Context` already contains a version option");
         self.version_option = Some(option);
         add_command_builtin_version_option(self);
+        self.rebuild_option_lookup();
     }
 
     /// Sets the version `Command` of this context.
+    ///
+    /// See `ContextBuilder::version_command` for the names already in use.
     pub fn set_version_command(&mut self, command: Command) {
         assert!(self.version_command.is_none(), "`Context` already contains a version command");
         self.version_command = Some(command);
         add_command_builtin_version_command(self);
+        self.rebuild_option_lookup();
+    }
+
+    /// Sets the doctor `Command` of this context, which writes a report of this context.
+    ///
+    /// See `CommandLine::use_doctor`.
+    pub fn set_doctor_command(&mut self, command: Command) {
+        assert!(self.doctor_command.is_none(), "`Context` already contains a doctor command");
+        self.doctor_command = Some(command);
+        add_command_builtin_doctor_command(self);
+        self.rebuild_option_lookup();
+    }
+
+    /// Adds an option available for every command of this context.
+    ///
+    /// See `ContextBuilder::global_option`.
+    ///
+    /// # Panics
+    /// If there is a global option with the same name or alias, or if the name or an alias
+    /// starts with a prefix or contains an assign operator.
+    pub fn add_global_option(&mut self, option: CommandOption) {
+        assert_valid_option_names(self, &option);
+        add_global_option(&mut self.global_options, option);
+        self.rebuild_option_lookup();
     }
 
     /// Returns the `CommandOption` with the given name or alias or `None` if not found.
@@ -173,6 +469,49 @@ impl Context {
         None
     }
 
+    // Returns the precomputed option lookup of the root command.
+    pub(crate) fn option_lookup(&self) -> &OptionLookup {
+        &self.option_lookup
+    }
+
+    // Returns the global option of this context with the given name or alias,
+    // or `None` if an option of the command with the given lookup uses its name.
+    pub(crate) fn find_global_option(&self, option_lookup: &OptionLookup, name_or_alias: &str) -> Option<&CommandOption> {
+        self.global_option_lookup.get(self.option_case().fold(name_or_alias).as_ref())
+            .and_then(|index| self.global_options.iter().nth(*index))
+            .filter(|option| option_lookup.get(option.get_name()).is_none())
+    }
+
+    // Panics if the options of a command or the global options conflicts using the given `NameCase`.
+    fn validate_options(&self, case: NameCase) {
+        // The options are added case sensitive so may conflict under the case of the context
+        self.root.validate(case);
+        if let Some(duplicated) = self.global_options.find_duplicate(case) {
+            panic!("`Context` already contains a global option named or aliased like: `{}`", duplicated.get_name());
+        }
+    }
+
+    // Must be called each time the command tree or the global options are mutated.
+    fn rebuild_option_lookup(&mut self) {
+        let case = self.option_case();
+        self.root.clear_usage_cache();
+
+        // The option lists follow the case of the context after being validated
+        self.validate_options(case);
+        self.root.set_option_case(case);
+        self.global_options.set_case(case);
+
+        self.option_lookup = OptionLookup::new(&self.root, case);
+        self.global_option_lookup.clear();
+
+        for (index, option) in self.global_options.iter().enumerate() {
+            let names = std::iter::once(option.get_name()).chain(option.get_all_aliases().map(|s| s.as_str()));
+            for name in names {
+                self.global_option_lookup.insert(case.fold(name).into_owned(), index);
+            }
+        }
+    }
+
     /// Returns the `Command` with the given name or `None` if not found.
     pub fn get_command(&self, name: &str) -> Option<&Command> {
         self.root().get_subcommands().find(|c| c.get_name() == name)
@@ -209,10 +548,19 @@ impl Debug for Context {
             .field("alias_prefixes", &self.alias_prefixes)
             .field("assign_operators", &self.assign_operators)
             .field("delimiter", &self.delimiter)
+            .field("command_separator", &self.command_separator)
             .field("help_option", &self.help_option)
             .field("help_command", &self.help_command)
             .field("version_option", &self.version_option)
             .field("version_command", &self.version_command)
+            .field("doctor_command", &self.doctor_command)
+            .field("allow_option_bundling", &self.allow_option_bundling)
+            .field("collect_errors", &self.collect_errors)
+            .field("metrics", &self.metrics)
+            .field("ignore_option_case", &self.ignore_option_case)
+            .field("env_prefix", &self.env_prefix)
+            .field("help_style", &self.help_style)
+            .field("global_options", &self.global_options)
             .finish()
     }
 }
@@ -247,10 +595,19 @@ pub struct ContextBuilder {
     alias_prefixes: Vec<String>,
     assign_operators: Vec<char>,
     delimiter: Option<char>,
+    command_separator: Option<String>,
     help_option: Option<CommandOption>,
     help_command: Option<Command>,
     version_option: Option<CommandOption>,
     version_command: Option<Command>,
+    lenient: bool,
+    allow_option_bundling: bool,
+    collect_errors: bool,
+    metrics: bool,
+    ignore_option_case: bool,
+    env_prefix: Option<String>,
+    help_style: HelpStyle,
+    global_options: OptionList,
 }
 
 impl ContextBuilder {
@@ -264,10 +621,19 @@ impl ContextBuilder {
             alias_prefixes: Default::default(),
             assign_operators: Default::default(),
             delimiter: None,
+            command_separator: None,
             help_option: None,
             help_command: None,
             version_option: None,
             version_command: None,
+            lenient: false,
+            allow_option_bundling: false,
+            collect_errors: false,
+            metrics: false,
+            ignore_option_case: false,
+            env_prefix: None,
+            help_style: HelpStyle::default(),
+            global_options: OptionList::new(),
         }
     }
 
@@ -303,6 +669,21 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets the token used to separate several commands in one invocation,
+    /// like: `MyApp fetch --remote origin ; rebase --onto main`.
+    ///
+    /// Only the arguments equals to the separator split the commands, and any separator
+    /// after `--` is a value. Use `Parser::parse_all` or `CommandLine::run_all` to parse the commands.
+    ///
+    /// # Panics
+    /// If the separator is empty, contains whitespaces, numbers or letters.
+    pub fn command_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        let separator = separator.into();
+        assert_valid_command_separator(&separator);
+        self.command_separator = Some(separator);
+        self
+    }
+
     /// Sets the `SuggestionSource` for this context.
     pub fn suggestions(mut self, suggestions: SuggestionSource) -> Self {
         self.suggestions = Some(suggestions);
@@ -316,6 +697,9 @@ impl ContextBuilder {
     }
 
     /// Sets the help `CommandOption` for this context.
+    ///
+    /// The options of the commands take precedence: the help option is not added if an option
+    /// uses its name, and the aliases used by other options are removed, like `-h` for `--host`.
     pub fn help_option(mut self, option: CommandOption) -> Self {
         assert_is_help_option(&option);
         self.help_option = Some(option);
@@ -323,6 +707,8 @@ impl ContextBuilder {
     }
 
     /// Sets the help `Command` for this context.
+    ///
+    /// The help command is not added if the root command have a subcommand with the same name.
     pub fn help_command(mut self, command: Command) -> Self {
         assert_is_help_command(&command);
         self.help_command = Some(command);
@@ -330,6 +716,9 @@ impl ContextBuilder {
     }
 
     /// Sets the version `CommandOption` for this context.
+    ///
+    /// The options of the commands take precedence: the version option is not added if an option
+    /// uses its name, and the aliases used by other options are removed, like `-v` for `--verbose`.
     pub fn version_option(mut self, option: CommandOption) -> Self {
         assert_is_version_option(&option);
         self.version_option = Some(option);
@@ -337,12 +726,143 @@ impl ContextBuilder {
     }
 
     /// Sets the version `Command` for this context.
+    ///
+    /// The version command is not added if the root command have a subcommand with the same name.
     pub fn version_command(mut self, command: Command) -> Self {
         assert_is_version_command(&command);
         self.version_command = Some(command);
         self
     }
 
+    /// Sets whether unknown options and subcommands are ignored instead of failing the parse,
+    /// the ignored values can be retrieved using `ParseResult::ignored`.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets whether bundled aliases like `-abc` are expanded to `-a -b -c`.
+    ///
+    /// If one of the options takes arguments, the rest of the bundle is its value like `-ofile.txt`,
+    /// or the next values if is the last option of the bundle like `-vo file.txt`.
+    pub fn allow_option_bundling(mut self, allow: bool) -> Self {
+        self.allow_option_bundling = allow;
+        self
+    }
+
+    /// Sets whether the parser reports all the errors found instead of only the first one.
+    ///
+    /// The parser continues after an invalid value, an unknown option or an invalid number
+    /// of values and returns an `Error` with all of them, see `Error::iter`.
+    /// An unknown subcommand still stops the parse.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Argument, Command, CommandOption, Context, Parser};
+    /// use clapi::validator::validate_type;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("count").arg(Argument::new().validator(validate_type::<u32>())));
+    ///
+    /// let context = Context::builder(command).collect_errors(true).build();
+    /// let error = Parser::new(&context)
+    ///     .parse(vec!["--count", "abc", "--color", "extra"])
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.iter().count(), 3);
+    /// ```
+    pub fn collect_errors(mut self, collect: bool) -> Self {
+        self.collect_errors = collect;
+        self
+    }
+
+    /// Sets whether the parser records the `ParseMetrics` of each parse, like the number of tokens
+    /// and the time spent tokenizing and parsing, by default is `false`.
+    ///
+    /// When disabled the parser don't read the clock.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Context, Parser};
+    ///
+    /// let command = Command::new("MyApp").option(CommandOption::new("verbose").alias("v").counted(true));
+    /// let context = Context::builder(command).metrics(true).build();
+    ///
+    /// let result = Parser::new(&context).parse(vec!["-v", "--verbose"]).unwrap();
+    /// let metrics = result.metrics().unwrap();
+    /// assert_eq!(metrics.token_count(), 2);
+    /// assert_eq!(metrics.option_occurrences(), 2);
+    /// ```
+    pub fn metrics(mut self, enabled: bool) -> Self {
+        self.metrics = enabled;
+        self
+    }
+
+    /// Sets whether the names and aliases of the options are compared ignoring the case,
+    /// by default is `false`.
+    ///
+    /// The options keep the case they were declared with, but options of the same command
+    /// which names or aliases only differ in case are rejected.
+    ///
+    /// # Panics
+    /// When building the `Context` if a command or the global options contains options
+    /// which names or aliases only differ in case.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Context, Parser};
+    ///
+    /// let command = Command::new("MyApp").option(CommandOption::new("Verbose").alias("V"));
+    /// let context = Context::builder(command).ignore_option_case(true).build();
+    ///
+    /// let result = Parser::new(&context).parse(vec!["--verbose"]).unwrap();
+    /// assert!(result.options().contains("Verbose"));
+    /// ```
+    pub fn ignore_option_case(mut self, ignore: bool) -> Self {
+        self.ignore_option_case = ignore;
+        self
+    }
+
+    /// Sets the prefix of the environment variables derived from the option names,
+    /// each option takes its value from `<PREFIX>_<OPTION_NAME>` when is not passed.
+    pub fn env_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the `HelpStyle` used for the help messages.
+    pub fn help_style(mut self, style: HelpStyle) -> Self {
+        self.help_style = style;
+        self
+    }
+
+    /// Adds an option available for every command of this context, like `--config <FILE>`.
+    ///
+    /// Global options are parsed wherever they appear in the command-line, are shown in their own
+    /// section of the help and are retrieved using `ParseResult::global_options`.
+    /// The options of the commands take precedence over the global options with the same name.
+    ///
+    /// # Panics
+    /// If there is a global option with the same name or alias.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Argument, Command, CommandOption, Context, Parser};
+    ///
+    /// let command = Command::new("MyApp").subcommand(Command::new("build"));
+    /// let context = Context::builder(command)
+    ///     .global_option(CommandOption::new("profile").arg(Argument::new().default("dev")))
+    ///     .build();
+    ///
+    /// let result = Parser::new(&context).parse(vec!["build", "--profile", "release"]).unwrap();
+    /// assert!(result.global_options().get_arg("profile").unwrap().contains("release"));
+    /// assert!(!result.options().contains("profile"));
+    /// ```
+    pub fn global_option(mut self, option: CommandOption) -> Self {
+        add_global_option(&mut self.global_options, option);
+        self
+    }
+
     /// Constructs a `Context` using this builder data.
     pub fn build(mut self) -> Context {
         let mut context = Context {
@@ -382,6 +902,9 @@ impl ContextBuilder {
             // Argument values delimiter
             delimiter: self.delimiter.unwrap_or(','),
 
+            // Separator of several commands
+            command_separator: self.command_separator,
+
             // Help option
             help_option: self.help_option,
 
@@ -392,13 +915,49 @@ impl ContextBuilder {
             version_option: self.version_option,
 
             // Version command
-            version_command: self.version_command
+            version_command: self.version_command,
+
+            // Doctor command, only set with `CommandLine::use_doctor`
+            doctor_command: None,
+
+            // Ignore unknown options and subcommands
+            lenient: self.lenient,
+
+            // Expand bundled aliases like `-abc`
+            allow_option_bundling: self.allow_option_bundling,
+
+            // Report all the errors of the parse
+            collect_errors: self.collect_errors,
+
+            // Record the metrics of each parse
+            metrics: self.metrics,
+
+            // Compare the option names ignoring the case
+            ignore_option_case: self.ignore_option_case,
+
+            // Prefix of the environment variables of the options
+            env_prefix: self.env_prefix,
+
+            // Style of the help messages
+            help_style: self.help_style,
+
+            // Options available for every command
+            global_options: self.global_options,
+
+            // Built after the builtin options and commands are added
+            option_lookup: OptionLookup::default(),
+            global_option_lookup: HashMap::new(),
         };
 
+        assert_valid_names(&context, &context.root, true);
+
+        context.root.inherit_settings(CommandSettings::new());
+
         add_command_builtin_help_option(&mut context);
         add_command_builtin_help_command(&mut context);
         add_command_builtin_version_option(&mut context);
         add_command_builtin_version_command(&mut context);
+        context.rebuild_option_lookup();
         context
     }
 }
@@ -428,6 +987,9 @@ pub fn default_help_option() -> CommandOption {
         .arg(Argument::zero_or_more("command"))
 }
 
+// Name of the argument added to the help option with the format of `--help=json`
+pub(crate) const HELP_FORMAT_ARG: &str = "__format";
+
 #[inline]
 #[doc(hidden)]
 pub fn default_help_command() -> Command {
@@ -449,6 +1011,53 @@ fn assert_valid_symbol(source: &str, value: &str) {
     }
 }
 
+#[inline]
+fn assert_valid_command_separator(value: &str) {
+    assert!(!value.is_empty(), "command separators cannot be empty");
+    assert_valid_symbol("command separators", value);
+}
+
+// Checks the names of the commands and options don't conflict with the prefixes and assign operators.
+fn assert_valid_names(context: &Context, command: &Command, is_root: bool) {
+    // The root command is never typed, so its name don't need to be checked
+    if !is_root && command.get_name().chars().any(char::is_whitespace) {
+        panic!("subcommand `{}` cannot contains whitespaces", command.get_name());
+    }
+
+    if is_root {
+        for option in context.global_options() {
+            assert_valid_option_names(context, option);
+        }
+    }
+
+    for option in command.get_options() {
+        assert_valid_option_names(context, option);
+    }
+
+    for subcommand in command.get_subcommands() {
+        assert_valid_names(context, subcommand, false);
+    }
+}
+
+fn assert_valid_option_names(context: &Context, option: &CommandOption) {
+    let names = std::iter::once(option.get_name()).chain(option.get_all_aliases().map(|s| s.as_str()));
+    for name in names {
+        if let Some(prefix) = context.name_prefixes().chain(context.alias_prefixes()).find(|p| name.starts_with(p.as_str())) {
+            panic!("option `{}` cannot start with the prefix `{}`", name, prefix);
+        }
+
+        if let Some(c) = context.assign_operators().find(|c| name.contains(**c)) {
+            panic!("option `{}` cannot contains the assign operator `{}`", name, c);
+        }
+    }
+}
+
+fn add_global_option(options: &mut OptionList, option: CommandOption) {
+    if let Err(duplicated) = options.add(option) {
+        panic!("`Context` already contains a global option named or aliased like: `{}`", duplicated.get_name());
+    }
+}
+
 #[inline]
 fn assert_is_help_option(option: &CommandOption) {
     let arg = option.get_arg().expect("help option must take only 1 argument");
@@ -472,13 +1081,19 @@ fn assert_is_version_option(option: &CommandOption) {
 
 #[inline]
 fn assert_is_version_command(command: &Command) {
-    if command.get_arg().is_some() {
+    if !command.get_args().is_empty() {
         panic!("version command must take no arguments");
     }
 }
 
 #[inline]
 fn add_command_builtin_help_option(context: &mut Context) {
+    let case = context.option_case();
+    context.help_option = context
+        .help_option
+        .take()
+        .and_then(|option| builtin_option_not_shadowed(&context.root, option, case));
+
     if context.root.get_subcommands().count() > 0 {
         if let Some(help_option) = context.help_option.as_ref().cloned() {
             let command = &mut context.root;
@@ -489,6 +1104,11 @@ fn add_command_builtin_help_option(context: &mut Context) {
 
 #[inline]
 fn add_command_builtin_help_command(context: &mut Context) {
+    context.help_command = context
+        .help_command
+        .take()
+        .filter(|command| context.root.find_subcommand(command.get_name()).is_none());
+
     if let Some(help_command) = context.help_command.as_ref().cloned() {
         context.root.add_command(help_command);
     }
@@ -496,6 +1116,12 @@ fn add_command_builtin_help_command(context: &mut Context) {
 
 #[inline]
 fn add_command_builtin_version_option(context: &mut Context) {
+    let case = context.option_case();
+    context.version_option = context
+        .version_option
+        .take()
+        .and_then(|option| builtin_option_not_shadowed(&context.root, option, case));
+
     if context.root.get_subcommands().count() > 0 {
         if let Some(version_option) = context.version_option.as_ref().cloned() {
             let command = &mut context.root;
@@ -506,11 +1132,43 @@ fn add_command_builtin_version_option(context: &mut Context) {
 
 #[inline]
 fn add_command_builtin_version_command(context: &mut Context) {
+    context.version_command = context
+        .version_command
+        .take()
+        .filter(|command| context.root.find_subcommand(command.get_name()).is_none());
+
     if let Some(version_command) = context.version_command.as_ref().cloned() {
         context.root.add_command(version_command);
     }
 }
 
+#[inline]
+fn add_command_builtin_doctor_command(context: &mut Context) {
+    context.doctor_command = context
+        .doctor_command
+        .take()
+        .filter(|command| context.root.find_subcommand(command.get_name()).is_none());
+
+    if let Some(doctor_command) = context.doctor_command.as_ref().cloned() {
+        context.root.add_command(doctor_command);
+    }
+}
+
+// The options declared by the user take precedence over the builtin option: returns the builtin option
+// without the aliases used by any option of the commands, or `None` if its name is used.
+fn builtin_option_not_shadowed(root: &Command, mut option: CommandOption, case: NameCase) -> Option<CommandOption> {
+    fn is_used(command: &Command, name: &str, case: NameCase) -> bool {
+        command.get_options().contains_with(name, case) || command.get_subcommands().any(|c| is_used(c, name, case))
+    }
+
+    if is_used(root, option.get_name(), case) {
+        return None;
+    }
+
+    option.retain_aliases(|alias| !is_used(root, alias, case));
+    Some(option)
+}
+
 fn add_option_recursive(command: &mut Command, option: CommandOption) {
     for subcommand in command.get_subcommands_mut() {
         add_option_recursive(subcommand, option.clone());
@@ -531,16 +1189,200 @@ pub(crate) fn is_help_command(context: &Context, name: &str) -> bool {
 // Checks if the given string is a help option.
 pub(crate) fn is_help_option(context: &Context, name: &str) -> bool {
     if let Some(help_option) = context.help_option.as_ref() {
-        help_option.get_name() == name || help_option.has_alias(name)
+        context.option_case().matches(help_option, name)
     } else {
         false
     }
 }
 
+/// Location of a `CommandOption` in the command tree.
+///
+/// `depth` is the position of the declaring command in the path from the root,
+/// and `index` the position of the option in that command's options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OptionId {
+    depth: usize,
+    index: usize,
+}
+
+impl OptionId {
+    /// Returns the `CommandOption` using the commands from the root to the executing command.
+    pub fn resolve<'a>(&self, path: &[&'a Command]) -> Option<&'a CommandOption> {
+        path.get(self.depth)
+            .and_then(|command| command.get_options().iter().nth(self.index))
+    }
+}
+
+/// A precomputed map of option names and aliases for each command,
+/// including the global options inherited from its parents.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OptionLookup {
+    options: HashMap<String, OptionId>,
+    children: HashMap<String, OptionLookup>,
+    case: NameCase,
+}
+
+impl OptionLookup {
+    /// Constructs the `OptionLookup` of the given root command, the names are compared using the given `NameCase`.
+    pub fn new(root: &Command, case: NameCase) -> Self {
+        OptionLookup::with_globals(root, 0, &[], case)
+    }
+
+    fn with_globals(command: &Command, depth: usize, globals: &[(OptionId, &CommandOption)], case: NameCase) -> Self {
+        let mut options = HashMap::new();
+
+        // The command options take priority over the inherited ones
+        for (index, option) in command.get_options().iter().enumerate() {
+            insert_option_names(&mut options, option, OptionId { depth, index }, case);
+        }
+
+        for (id, option) in globals {
+            if !command.get_options().contains_with(option.get_name(), case) {
+                insert_option_names(&mut options, option, *id, case);
+            }
+        }
+
+        // The global options of this command replace the inherited ones with the same name
+        let mut child_globals = globals.iter()
+            .filter(|(_, opt)| {
+                !command.get_options().iter().any(|o| o.is_global() && case.names_eq(o.get_name(), opt.get_name()))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for (index, option) in command.get_options().iter().enumerate() {
+            if option.is_global() {
+                child_globals.push((OptionId { depth, index }, option));
+            }
+        }
+
+        let children = command.get_subcommands()
+            .map(|c| (c.get_name().to_owned(), OptionLookup::with_globals(c, depth + 1, &child_globals, case)))
+            .collect();
+
+        OptionLookup { options, children, case }
+    }
+
+    /// Returns the `OptionId` of the option with the given name or alias.
+    pub fn get(&self, name_or_alias: &str) -> Option<OptionId> {
+        self.options.get(self.case.fold(name_or_alias).as_ref()).copied()
+    }
+
+    /// Returns the `OptionLookup` of the subcommand with the given name.
+    pub fn get_child(&self, name: &str) -> Option<&OptionLookup> {
+        self.children.get(name)
+    }
+}
+
+fn insert_option_names(options: &mut HashMap<String, OptionId>, option: &CommandOption, id: OptionId, case: NameCase) {
+    let names = std::iter::once(option.get_name()).chain(option.get_all_aliases().map(|s| s.as_str()));
+    for name in names {
+        options.entry(case.fold(name).into_owned()).or_insert(id);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn builtin_commands_not_shadowed_test() {
+        let command = Command::new("MyApp")
+            .subcommand(Command::new("help").description("User help"))
+            .subcommand(Command::new("version").description("User version"));
+
+        let context = Context::builder(command)
+            .help_command(default_help_command())
+            .version_command(default_version_command())
+            .build();
+
+        assert!(context.help_command().is_none());
+        assert!(context.version_command().is_none());
+        assert_eq!(context.root().get_subcommands().count(), 2);
+        assert_eq!(context.root().find_subcommand("help").unwrap().get_description(), Some("User help"));
+        assert_eq!(context.root().find_subcommand("version").unwrap().get_description(), Some("User version"));
+
+        // Without a subcommand with the same name the builtin commands are added
+        let mut context = Context::new(Command::new("MyApp").subcommand(Command::new("run")));
+        context.set_help_command(default_help_command());
+        context.set_version_command(default_version_command());
+        assert!(context.help_command().is_some());
+        assert!(context.version_command().is_some());
+        assert_eq!(context.root().get_subcommands().count(), 3);
+    }
+
+    #[test]
+    fn builtin_options_not_shadowed_test() {
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .option(CommandOption::new("verbose").alias("v"))
+            .subcommand(Command::new("connect").option(CommandOption::new("host").alias("h").arg(Argument::new())));
+
+        let context = Context::builder(command)
+            .help_option(default_help_option())
+            .version_option(default_version_option())
+            .build();
+
+        // Only the aliases are removed
+        let help_option = context.help_option().unwrap();
+        let version_option = context.version_option().unwrap();
+        assert!(!help_option.has_alias("h"));
+        assert!(!version_option.has_alias("v"));
+
+        let root = context.root();
+        let connect = root.find_subcommand("connect").unwrap();
+        assert_eq!(root.get_options().get("v").unwrap().get_name(), "verbose");
+        assert_eq!(connect.get_options().get("h").unwrap().get_name(), "host");
+        assert_eq!(connect.get_options().get("help").unwrap().get_name(), "help");
+        assert_eq!(connect.get_options().get("version").unwrap().get_name(), "version");
+
+        // An option with the same name replaces the builtin option in all the commands
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("version").arg(Argument::new()))
+            .subcommand(Command::new("topics").option(CommandOption::new("help").arg(Argument::new())));
+
+        let mut context = Context::new(command);
+        context.set_help_option(default_help_option());
+        context.set_version_option(default_version_option());
+        assert!(context.help_option().is_none());
+        assert!(context.version_option().is_none());
+        assert!(context.root().get_options().get("help").is_none());
+        assert!(context.root().get_options().get("version").unwrap().take_args());
+    }
+
+    #[test]
+    fn command_version_test() {
+        let context = Context::new(
+            Command::new("MyApp")
+                .version("1.0")
+                .subcommand(
+                    Command::new("server")
+                        .version("2.0")
+                        .subcommand(Command::new("start"))
+                        .subcommand(Command::new("stop").version("2.1")),
+                )
+                .subcommand(Command::new("client").subcommand(Command::new("connect"))),
+        );
+
+        let root = context.root();
+        let server = root.find_subcommand("server").unwrap();
+        let client = root.find_subcommand("client").unwrap();
+
+        assert_eq!(context.command_version(root), Some("1.0"));
+        assert_eq!(context.command_version(server), Some("2.0"));
+        assert_eq!(context.command_version(server.find_subcommand("start").unwrap()), Some("2.0"));
+        assert_eq!(context.command_version(server.find_subcommand("stop").unwrap()), Some("2.1"));
+        assert_eq!(context.command_version(client), Some("1.0"));
+        assert_eq!(context.command_version(client.find_subcommand("connect").unwrap()), Some("1.0"));
+
+        // Commands that are not part of the context only use its own version
+        assert_eq!(context.command_version(&Command::new("start")), None);
+        assert_eq!(context.command_version(&Command::new("start").version("3.0")), Some("3.0"));
+
+        let context = Context::new(Command::new("MyApp").subcommand(Command::new("server")));
+        assert_eq!(context.command_version(context.root().find_subcommand("server").unwrap()), None);
+    }
+
     #[test]
     fn context_test(){
         let context = Context::new(Command::root());
@@ -588,4 +1430,219 @@ mod tests {
     fn invalid_delimiter_test() {
         Context::builder(Command::root()).delimiter('\t');
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[should_panic(expected="option `/x` cannot start with the prefix `/`")]
+    fn option_name_with_custom_prefix_test() {
+        Context::builder(Command::new("MyApp").option(CommandOption::new("/x")))
+            .name_prefix("/")
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected="option `a:b` cannot contains the assign operator `:`")]
+    fn option_alias_with_custom_assign_operator_test() {
+        Context::builder(Command::new("MyApp").option(CommandOption::new("test").alias("a:b")))
+            .assign_operator(':')
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected="subcommand `my test` cannot contains whitespaces")]
+    fn subcommand_name_with_whitespaces_test() {
+        Context::new(Command::new("My App").subcommand(Command::new("my test")));
+    }
+
+    #[test]
+    #[should_panic(expected = "command `MyApp` option `db` conflicts with the nested option `db.host`")]
+    fn nested_option_conflict_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("db.host"))
+            .option(CommandOption::new("db"));
+
+        Context::new(command);
+    }
+
+    #[test]
+    fn env_var_name_test() {
+        let context = Context::builder(Command::new("MyApp"))
+            .env_prefix("MYAPP")
+            .help_option(default_help_option())
+            .build();
+
+        let env = |option: CommandOption| context.env_var_name(&option);
+        assert_eq!(env(CommandOption::new("dry-run")), Some("MYAPP_DRY_RUN".to_owned()));
+        assert_eq!(env(CommandOption::new("max_line-count")), Some("MYAPP_MAX_LINE_COUNT".to_owned()));
+        assert_eq!(env(CommandOption::new("x.y")), Some("MYAPP_X_Y".to_owned()));
+        assert_eq!(env(CommandOption::new("color").env("TERM_COLOR")), Some("TERM_COLOR".to_owned()));
+        assert_eq!(env(CommandOption::new("dry-run").use_env(false)), None);
+        assert_eq!(env(default_help_option()), None);
+
+        let context = Context::new(Command::new("MyApp"));
+        assert_eq!(context.env_var_name(&CommandOption::new("dry-run")), None);
+        assert_eq!(
+            context.env_var_name(&CommandOption::new("dry-run").env("DRY_RUN")),
+            Some("DRY_RUN".to_owned())
+        );
+    }
+
+    #[test]
+    fn option_lookup_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("color").alias("c").global(true))
+            .option(CommandOption::new("local"))
+            .subcommand(Command::new("test")
+                .option(CommandOption::new("count").alias("c"))
+                .subcommand(Command::new("unit")));
+
+        let context = Context::new(command);
+        let root = context.root();
+        let test = root.find_subcommand("test").unwrap();
+        let unit = test.find_subcommand("unit").unwrap();
+
+        let lookup = context.option_lookup();
+        assert_eq!(lookup.get("c").and_then(|id| id.resolve(&[root])).unwrap().get_name(), "color");
+        assert_eq!(lookup.get("local").and_then(|id| id.resolve(&[root])).unwrap().get_name(), "local");
+
+        // The command options override the aliases of inherited global options
+        let test_lookup = lookup.get_child("test").unwrap();
+        assert_eq!(test_lookup.get("c").and_then(|id| id.resolve(&[root, test])).unwrap().get_name(), "count");
+        assert_eq!(test_lookup.get("color").and_then(|id| id.resolve(&[root, test])).unwrap().get_name(), "color");
+        assert!(test_lookup.get("local").is_none());
+
+        let unit_lookup = test_lookup.get_child("unit").unwrap();
+        assert_eq!(unit_lookup.get("color").and_then(|id| id.resolve(&[root, test, unit])).unwrap().get_name(), "color");
+        assert!(unit_lookup.get("count").is_none());
+    }
+
+    #[test]
+    fn option_lookup_rebuild_test() {
+        let mut context = Context::builder(Command::new("MyApp").subcommand(Command::new("test")))
+            .build();
+
+        let lookup = context.option_lookup().get_child("test").unwrap();
+        assert!(lookup.get("v").is_none());
+
+        context.set_version_option(default_version_option());
+        let lookup = context.option_lookup().get_child("test").unwrap();
+        assert!(lookup.get("v").is_some());
+    }
+
+    #[test]
+    fn global_options_test() {
+        let mut context = Context::builder(Command::new("MyApp").option(CommandOption::new("verbose")))
+            .global_option(CommandOption::new("config").alias("c").arg(Argument::new()))
+            .build();
+
+        context.add_global_option(CommandOption::new("profile").arg(Argument::new()));
+
+        assert_eq!(context.global_options().len(), 2);
+        let lookup = context.option_lookup();
+        assert_eq!(context.find_global_option(lookup, "c").unwrap().get_name(), "config");
+        assert_eq!(context.find_global_option(lookup, "profile").unwrap().get_name(), "profile");
+        assert!(context.find_global_option(lookup, "verbose").is_none());
+        assert_eq!(context.root().get_options().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "`Context` already contains a global option named or aliased like: `color`")]
+    fn duplicated_global_option_test() {
+        Context::builder(Command::new("MyApp"))
+            .global_option(CommandOption::new("colour").alias("c"))
+            .global_option(CommandOption::new("color").alias("c"));
+    }
+
+    #[test]
+    #[should_panic(expected = "option `host:port` cannot contains the assign operator `:`")]
+    fn global_option_with_assign_operator_test() {
+        let mut context = Context::builder(Command::new("MyApp")).assign_operator(':').build();
+        context.add_global_option(CommandOption::new("host:port"));
+    }
+
+    #[test]
+    fn ignore_option_case_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("Verbose").alias("V"))
+            .subcommand(Command::new("build").option(CommandOption::new("release")));
+
+        let context = Context::builder(command)
+            .ignore_option_case(true)
+            .global_option(CommandOption::new("Config").arg(Argument::new()))
+            .build();
+
+        let lookup = context.option_lookup();
+        assert!(context.is_option_case_ignored());
+        assert!(lookup.get("verbose").is_some());
+        assert!(lookup.get("v").is_some());
+        assert!(lookup.get_child("build").unwrap().get("RELEASE").is_some());
+        assert_eq!(context.find_global_option(lookup, "CONFIG").unwrap().get_name(), "Config");
+
+        // The names keep its case but the lookups of the options follow the context
+        assert!(context.root().get_options().contains("Verbose"));
+        assert_eq!(context.root().get_options().get("verbose").unwrap().get_name(), "Verbose");
+        assert!(context.global_options().contains("config"));
+    }
+
+    #[test]
+    fn ignore_option_case_option_list_test() {
+        let mut context = Context::builder(Command::new("MyApp").option(CommandOption::new("Format")))
+            .ignore_option_case(true)
+            .build();
+
+        let mut options = context.root().get_options().clone();
+        assert!(options.add(CommandOption::new("format")).is_err());
+        assert!(options.add(CommandOption::new("FORMAT")).is_err());
+
+        context.set_ignore_option_case(false);
+        assert!(!context.root().get_options().contains("format"));
+        assert!(context.root().get_options().contains("Format"));
+    }
+
+    #[test]
+    fn set_ignore_option_case_validates_before_assign_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("Verbose"))
+            .option(CommandOption::new("verbose"));
+
+        let mut context = Context::new(command);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            context.set_ignore_option_case(true);
+        }));
+
+        assert!(result.is_err());
+        assert!(!context.is_option_case_ignored());
+        assert!(!context.root().get_options().contains("VERBOSE"));
+    }
+
+    #[test]
+    #[should_panic(expected = "command `MyApp` already contains an option named or aliased like: `verbose`")]
+    fn ignore_option_case_duplicated_option_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("Verbose"))
+            .option(CommandOption::new("verbose"));
+
+        Context::builder(command).ignore_option_case(true).build();
+    }
+
+    #[test]
+    #[should_panic(expected = "command `build` already contains an option named or aliased like: `Mode`")]
+    fn ignore_option_case_duplicated_alias_test() {
+        let command = Command::new("MyApp")
+            .subcommand(Command::new("build")
+                .option(CommandOption::new("release").alias("m"))
+                .option(CommandOption::new("Mode").alias("M")));
+
+        let mut context = Context::new(command);
+        context.set_ignore_option_case(true);
+    }
+
+    #[test]
+    #[should_panic(expected = "`Context` already contains a global option named or aliased like: `COLOR`")]
+    fn ignore_option_case_duplicated_global_option_test() {
+        Context::builder(Command::new("MyApp"))
+            .global_option(CommandOption::new("color"))
+            .global_option(CommandOption::new("COLOR"))
+            .ignore_option_case(true)
+            .build();
+    }
+}
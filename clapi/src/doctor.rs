@@ -0,0 +1,550 @@
+use crate::parse_result::ParseMetrics;
+use crate::{Argument, Command, CommandOption, Context};
+use std::fmt::{Display, Formatter};
+use std::io::{Result, Write};
+use std::str::FromStr;
+
+/// Name of the hidden subcommand added by `CommandLine::use_doctor`.
+pub const DOCTOR_COMMAND_NAME: &str = "__doctor";
+
+// Features of the crate and whether are enabled
+const FEATURES: [(&str, bool); 10] = [
+    ("macros", cfg!(feature = "macros")),
+    ("typing", cfg!(feature = "typing")),
+    ("testing", cfg!(feature = "testing")),
+    ("compat", cfg!(feature = "compat")),
+    ("serde", cfg!(feature = "serde")),
+    ("json", cfg!(feature = "json")),
+    ("toml", cfg!(feature = "toml")),
+    ("yaml", cfg!(feature = "yaml")),
+    ("color", cfg!(feature = "color")),
+    ("man", cfg!(feature = "man")),
+];
+
+/// The format of a doctor report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportFormat {
+    /// One `key: value` per line.
+    Text,
+    /// A JSON object.
+    Json,
+}
+
+impl ReportFormat {
+    /// All the report formats.
+    pub const ALL: [ReportFormat; 2] = [ReportFormat::Text, ReportFormat::Json];
+
+    /// Returns the name of this format.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "text",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        ReportFormat::ALL
+            .iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| format!("invalid report format: `{}`", s))
+    }
+}
+
+/// Returns the hidden `__doctor` command used by `CommandLine::use_doctor`.
+pub fn doctor_command() -> Command {
+    Command::new(DOCTOR_COMMAND_NAME)
+        .description("Shows the configuration of the command-line app")
+        .hidden(true)
+        .option(
+            CommandOption::new("format")
+                .description("Format of the report")
+                .use_env(false)
+                .arg(
+                    Argument::with_name("format")
+                        .valid_values(ReportFormat::ALL.iter().map(|f| f.as_str()))
+                        .default("text"),
+                ),
+        )
+}
+
+/// Writes a report of the configuration of the given `Context`: the version, the enabled features,
+/// the prefixes and assign operators, the environment variables consulted, the global options
+/// and a summary of each command.
+///
+/// The environment variables are reported as `set` or `unset`, their values are never written.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandOption, Context};
+/// use clapi::doctor::{write_report, ReportFormat};
+///
+/// let context = Context::builder(Command::new("MyApp").option(CommandOption::new("verbose")))
+///     .env_prefix("MYAPP")
+///     .build();
+///
+/// let mut buf = Vec::new();
+/// write_report(&context, ReportFormat::Text, &mut buf).unwrap();
+///
+/// let report = String::from_utf8(buf).unwrap();
+/// assert!(report.contains("name: MyApp\n"));
+/// assert!(report.contains("env prefix: MYAPP\n"));
+/// ```
+pub fn write_report(context: &Context, format: ReportFormat, buf: &mut dyn Write) -> Result<()> {
+    write_report_with_metrics(context, None, format, buf)
+}
+
+// Writes the report including the metrics of the parse that invoked the doctor command, if any
+pub(crate) fn write_report_with_metrics(
+    context: &Context,
+    metrics: Option<&ParseMetrics>,
+    format: ReportFormat,
+    buf: &mut dyn Write,
+) -> Result<()> {
+    let mut report = Report::new(context);
+    report.parse_metrics = metrics.copied();
+
+    match format {
+        ReportFormat::Text => report.write_text(buf),
+        ReportFormat::Json => report.write_json(buf),
+    }
+}
+
+// The configuration gathered from a `Context`
+struct Report {
+    name: String,
+    version: Option<String>,
+    clapi_version: String,
+    features: Vec<String>,
+    name_prefixes: Vec<String>,
+    alias_prefixes: Vec<String>,
+    assign_operators: Vec<String>,
+    delimiter: String,
+    command_separator: Option<String>,
+    option_bundling: bool,
+    lenient: bool,
+    collect_errors: bool,
+    metrics: bool,
+    ignore_option_case: bool,
+    help_option: Option<String>,
+    help_command: Option<String>,
+    version_option: Option<String>,
+    version_command: Option<String>,
+    env_prefix: Option<String>,
+    env: Vec<(String, bool)>,
+    global_options: Vec<String>,
+    commands: Vec<CommandSummary>,
+    parse_metrics: Option<ParseMetrics>,
+}
+
+struct CommandSummary {
+    path: String,
+    options: usize,
+    args: usize,
+    subcommands: usize,
+    hidden: bool,
+}
+
+impl Report {
+    fn new(context: &Context) -> Self {
+        let root = context.root();
+        let name_prefix = context.name_prefixes().next().map_or("", |s| s.as_str());
+        let prefixed = |option: &CommandOption| format!("{}{}", name_prefix, option.get_name());
+
+        let mut env = Vec::new();
+        let mut commands = Vec::new();
+        collect_env(context, context.global_options().iter(), &mut env);
+        collect_commands(context, root, root.get_name().to_owned(), &mut env, &mut commands);
+
+        Report {
+            name: root.get_name().to_owned(),
+            version: root.get_version().map(str::to_owned),
+            clapi_version: env!("CARGO_PKG_VERSION").to_owned(),
+            features: FEATURES
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| (*name).to_owned())
+                .collect(),
+            name_prefixes: context.name_prefixes().cloned().collect(),
+            alias_prefixes: context.alias_prefixes().cloned().collect(),
+            assign_operators: context.assign_operators().map(|c| c.to_string()).collect(),
+            delimiter: context.delimiter().to_string(),
+            command_separator: context.command_separator().map(str::to_owned),
+            option_bundling: context.is_option_bundling_allowed(),
+            lenient: context.is_lenient(),
+            collect_errors: context.is_collecting_errors(),
+            metrics: context.is_metrics_enabled(),
+            ignore_option_case: context.is_option_case_ignored(),
+            help_option: context.help_option().map(prefixed),
+            help_command: context.help_command().map(|c| c.get_name().to_owned()),
+            version_option: context.version_option().map(prefixed),
+            version_command: context.version_command().map(|c| c.get_name().to_owned()),
+            env_prefix: context.env_prefix().map(str::to_owned),
+            env,
+            global_options: context.global_options().iter().map(prefixed).collect(),
+            commands,
+            parse_metrics: None,
+        }
+    }
+
+    fn write_text(&self, w: &mut dyn Write) -> Result<()> {
+        fn list(values: &[String]) -> String {
+            if values.is_empty() {
+                String::from("none")
+            } else {
+                values.join(", ")
+            }
+        }
+
+        fn value(value: &Option<String>) -> &str {
+            value.as_deref().unwrap_or("none")
+        }
+
+        writeln!(w, "name: {}", self.name)?;
+        writeln!(w, "version: {}", value(&self.version))?;
+        writeln!(w, "clapi version: {}", self.clapi_version)?;
+        writeln!(w, "features: {}", list(&self.features))?;
+        writeln!(w, "name prefixes: {}", list(&self.name_prefixes))?;
+        writeln!(w, "alias prefixes: {}", list(&self.alias_prefixes))?;
+        writeln!(w, "assign operators: {}", list(&self.assign_operators))?;
+        writeln!(w, "delimiter: {}", self.delimiter)?;
+        writeln!(w, "command separator: {}", value(&self.command_separator))?;
+        writeln!(w, "option bundling: {}", self.option_bundling)?;
+        writeln!(w, "lenient: {}", self.lenient)?;
+        writeln!(w, "collect errors: {}", self.collect_errors)?;
+        writeln!(w, "metrics: {}", self.metrics)?;
+        writeln!(w, "ignore option case: {}", self.ignore_option_case)?;
+        writeln!(w, "help option: {}", value(&self.help_option))?;
+        writeln!(w, "help command: {}", value(&self.help_command))?;
+        writeln!(w, "version option: {}", value(&self.version_option))?;
+        writeln!(w, "version command: {}", value(&self.version_command))?;
+        writeln!(w, "env prefix: {}", value(&self.env_prefix))?;
+
+        if self.env.is_empty() {
+            writeln!(w, "env: none")?;
+        }
+
+        for (name, is_set) in &self.env {
+            writeln!(w, "env: {} ({})", name, if *is_set { "set" } else { "unset" })?;
+        }
+
+        writeln!(w, "global options: {}", list(&self.global_options))?;
+
+        for command in &self.commands {
+            write!(
+                w,
+                "command: {} (options: {}, args: {}, subcommands: {})",
+                command.path, command.options, command.args, command.subcommands
+            )?;
+
+            if command.hidden {
+                write!(w, " [hidden]")?;
+            }
+
+            writeln!(w)?;
+        }
+
+        if let Some(metrics) = &self.parse_metrics {
+            writeln!(
+                w,
+                "parse metrics: (tokens: {}, option occurrences: {}, recovered errors: {}, tokenize time: {:?}, parse time: {:?})",
+                metrics.token_count(),
+                metrics.option_occurrences(),
+                metrics.recovered_errors(),
+                metrics.tokenize_time(),
+                metrics.parse_time()
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn write_json(&self, w: &mut dyn Write) -> Result<()> {
+        fn string(value: &str) -> Json {
+            Json::String(value.to_owned())
+        }
+
+        fn optional(value: &Option<String>) -> Json {
+            value.as_deref().map_or(Json::Null, string)
+        }
+
+        fn array(values: &[String]) -> Json {
+            Json::Array(values.iter().map(|s| string(s)).collect())
+        }
+
+        let env = self
+            .env
+            .iter()
+            .map(|(name, is_set)| {
+                Json::Object(vec![("name", string(name)), ("set", Json::Bool(*is_set))])
+            })
+            .collect();
+
+        let commands = self
+            .commands
+            .iter()
+            .map(|command| {
+                Json::Object(vec![
+                    ("path", string(&command.path)),
+                    ("options", Json::Number(command.options)),
+                    ("args", Json::Number(command.args)),
+                    ("subcommands", Json::Number(command.subcommands)),
+                    ("hidden", Json::Bool(command.hidden)),
+                ])
+            })
+            .collect();
+
+        let mut report = vec![
+            ("name", string(&self.name)),
+            ("version", optional(&self.version)),
+            ("clapi_version", string(&self.clapi_version)),
+            ("features", array(&self.features)),
+            ("name_prefixes", array(&self.name_prefixes)),
+            ("alias_prefixes", array(&self.alias_prefixes)),
+            ("assign_operators", array(&self.assign_operators)),
+            ("delimiter", string(&self.delimiter)),
+            ("command_separator", optional(&self.command_separator)),
+            ("option_bundling", Json::Bool(self.option_bundling)),
+            ("lenient", Json::Bool(self.lenient)),
+            ("collect_errors", Json::Bool(self.collect_errors)),
+            ("metrics", Json::Bool(self.metrics)),
+            ("ignore_option_case", Json::Bool(self.ignore_option_case)),
+            ("help_option", optional(&self.help_option)),
+            ("help_command", optional(&self.help_command)),
+            ("version_option", optional(&self.version_option)),
+            ("version_command", optional(&self.version_command)),
+            ("env_prefix", optional(&self.env_prefix)),
+            ("env", Json::Array(env)),
+            ("global_options", array(&self.global_options)),
+            ("commands", Json::Array(commands)),
+        ];
+
+        if let Some(metrics) = &self.parse_metrics {
+            report.push((
+                "parse_metrics",
+                Json::Object(vec![
+                    ("tokens", Json::Number(metrics.token_count())),
+                    ("option_occurrences", Json::Number(metrics.option_occurrences())),
+                    ("recovered_errors", Json::Number(metrics.recovered_errors())),
+                    ("tokenize_time_us", Json::Number(metrics.tokenize_time().as_micros() as usize)),
+                    ("parse_time_us", Json::Number(metrics.parse_time().as_micros() as usize)),
+                ]),
+            ));
+        }
+
+        Json::Object(report).write(w, 0)?;
+        writeln!(w)
+    }
+}
+
+// Collects the environment variables of the options, in order and without duplicates
+fn collect_env<'a, I>(context: &Context, options: I, env: &mut Vec<(String, bool)>)
+where
+    I: Iterator<Item = &'a CommandOption>,
+{
+    for name in options.filter_map(|option| context.env_var_name(option)) {
+        if env.iter().all(|(s, _)| *s != name) {
+            let is_set = std::env::var_os(&name).is_some();
+            env.push((name, is_set));
+        }
+    }
+}
+
+fn collect_commands(
+    context: &Context,
+    command: &Command,
+    path: String,
+    env: &mut Vec<(String, bool)>,
+    commands: &mut Vec<CommandSummary>,
+) {
+    collect_env(context, command.get_options().iter(), env);
+
+    commands.push(CommandSummary {
+        path: path.clone(),
+        options: command.get_options().len(),
+        args: command.get_args().len(),
+        subcommands: command.get_subcommands().count(),
+        hidden: command.is_hidden(),
+    });
+
+    for subcommand in command.get_subcommands() {
+        let path = format!("{} {}", path, subcommand.get_name());
+        collect_commands(context, subcommand, path, env, commands);
+    }
+}
+
+// A minimal JSON value, written with 2 spaces of indentation
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(usize),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    pub(crate) fn write(&self, w: &mut dyn Write, indent: usize) -> Result<()> {
+        let padding = "  ".repeat(indent + 1);
+
+        match self {
+            Json::Null => write!(w, "null"),
+            Json::Bool(value) => write!(w, "{}", value),
+            Json::Number(value) => write!(w, "{}", value),
+            Json::String(value) => write_json_string(w, value),
+            Json::Array(values) if values.is_empty() => write!(w, "[]"),
+            Json::Array(values) => {
+                writeln!(w, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    write!(w, "{}", padding)?;
+                    value.write(w, indent + 1)?;
+                    writeln!(w, "{}", if index + 1 < values.len() { "," } else { "" })?;
+                }
+                write!(w, "{}]", "  ".repeat(indent))
+            }
+            Json::Object(entries) => {
+                writeln!(w, "{{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    write!(w, "{}", padding)?;
+                    write_json_string(w, key)?;
+                    write!(w, ": ")?;
+                    value.write(w, indent + 1)?;
+                    writeln!(w, "{}", if index + 1 < entries.len() { "," } else { "" })?;
+                }
+                write!(w, "{}}}", "  ".repeat(indent))
+            }
+        }
+    }
+}
+
+fn write_json_string(w: &mut dyn Write, value: &str) -> Result<()> {
+    write!(w, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(w, "\\\"")?,
+            '\\' => write!(w, "\\\\")?,
+            '\n' => write!(w, "\\n")?,
+            '\r' => write!(w, "\\r")?,
+            '\t' => write!(w, "\\t")?,
+            c if c.is_control() => write!(w, "\\u{:04x}", c as u32)?,
+            c => write!(w, "{}", c)?,
+        }
+    }
+    write!(w, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{default_help_command, default_help_option};
+    use std::time::Duration;
+
+    fn report() -> Report {
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .option(CommandOption::new("verbose").alias("v"))
+            .option(CommandOption::new("color").env("DOCTOR_TEST_COLOR").arg(Argument::new()))
+            .subcommand(
+                Command::new("build")
+                    .option(CommandOption::new("verbose"))
+                    .option(CommandOption::new("release").use_env(false))
+                    .arg(Argument::zero_or_more("targets")),
+            )
+            .subcommand(Command::new("secret").hidden(true));
+
+        let context = Context::builder(command)
+            .env_prefix("DOCTOR_TEST")
+            .alias_prefix("/")
+            .assign_operator(':')
+            .command_separator(";")
+            .help_option(default_help_option())
+            .help_command(default_help_command())
+            .global_option(CommandOption::new("config").arg(Argument::new()))
+            .build();
+
+        std::env::set_var("DOCTOR_TEST_VERBOSE", "true");
+
+        // The features and the version of the crate are replaced to keep the snapshots stable
+        let mut report = Report::new(&context);
+        report.features = vec![String::from("macros"), String::from("typing")];
+        report.clapi_version = String::from("0.0.0");
+        report
+    }
+
+    #[test]
+    fn text_report_snapshot_test() {
+        let mut buf = Vec::new();
+        report().write_text(&mut buf).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            include_str!("../tests/golden/doctor.txt")
+        );
+    }
+
+    #[test]
+    fn json_report_snapshot_test() {
+        let mut buf = Vec::new();
+        report().write_json(&mut buf).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            include_str!("../tests/golden/doctor.json")
+        );
+    }
+
+    #[test]
+    fn parse_metrics_report_test() {
+        let mut report = report();
+        report.parse_metrics = Some(ParseMetrics {
+            token_count: 3,
+            option_occurrences: 1,
+            recovered_errors: 0,
+            tokenize_time: Duration::from_micros(12),
+            parse_time: Duration::from_micros(40),
+        });
+
+        let mut buf = Vec::new();
+        report.write_text(&mut buf).unwrap();
+        assert!(String::from_utf8(buf).unwrap().ends_with(
+            "parse metrics: (tokens: 3, option occurrences: 1, recovered errors: 0, tokenize time: 12µs, parse time: 40µs)\n"
+        ));
+
+        let mut buf = Vec::new();
+        report.write_json(&mut buf).unwrap();
+        assert!(String::from_utf8(buf).unwrap().ends_with(concat!(
+            "  \"parse_metrics\": {\n",
+            "    \"tokens\": 3,\n",
+            "    \"option_occurrences\": 1,\n",
+            "    \"recovered_errors\": 0,\n",
+            "    \"tokenize_time_us\": 12,\n",
+            "    \"parse_time_us\": 40\n",
+            "  }\n",
+            "}\n"
+        )));
+    }
+
+    #[test]
+    fn json_string_escape_test() {
+        let mut buf = Vec::new();
+        write_json_string(&mut buf, "a \"b\"\\\n\t\u{1}").unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), r#""a \"b\"\\\n\t\u0001""#);
+    }
+
+    #[test]
+    fn report_format_test() {
+        assert_eq!("json".parse::<ReportFormat>(), Ok(ReportFormat::Json));
+        assert_eq!("TEXT".parse::<ReportFormat>(), Ok(ReportFormat::Text));
+        assert!("yaml".parse::<ReportFormat>().is_err());
+    }
+}
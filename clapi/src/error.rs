@@ -1,5 +1,8 @@
-use crate::error::Inner::{Custom, Simple};
+use crate::error::Inner::{Custom, Multiple, Simple};
+use std::ffi::OsString;
 use std::fmt::{Debug, Display, Formatter};
+use crate::utils::write_line;
+use std::io::Write;
 
 /// A convenient `Result` type.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -9,11 +12,22 @@ type AnyError = Box<dyn std::error::Error + Sync + Send>;
 /// An error in a command-line operation.
 pub struct Error {
     inner: Inner,
+    token_index: Option<usize>,
+    invalid_value: Option<Box<InvalidValue>>,
+}
+
+// The value rejected by an argument with valid values, used for the suggestions
+#[derive(Clone)]
+struct InvalidValue {
+    value: String,
+    valid_values: Vec<String>,
 }
 
 enum Inner {
     Simple(ErrorKind),
     Custom(CustomError),
+    // The errors and the message written after all of them
+    Multiple(Vec<Error>, Option<String>),
 }
 
 impl Error {
@@ -35,18 +49,109 @@ impl Error {
                     None
                 )
             ),
+            token_index: None,
+            invalid_value: None,
         }
     }
 
-    /// Returns the `ErrorKind` of this error.
+    // Constructs an `Error` with all the given errors, or the error itself if there is only 1
+    pub(crate) fn from_errors(mut errors: Vec<Error>) -> Self {
+        assert!(!errors.is_empty(), "no errors to report");
+
+        if errors.len() == 1 {
+            errors.pop().unwrap()
+        } else {
+            Error {
+                inner: Multiple(errors, None),
+                token_index: None,
+                invalid_value: None,
+            }
+        }
+    }
+
+    /// Returns the `ErrorKind` of this error, or the kind of the first error if contains several.
     pub fn kind(&self) -> &ErrorKind {
         match &self.inner {
             Inner::Simple(kind) => kind,
             Inner::Custom(custom) => &custom.kind,
+            Inner::Multiple(errors, _) => errors[0].kind(),
+        }
+    }
+
+    /// Returns an iterator over the errors reported by the parser when `Context::collect_errors`
+    /// is enabled, otherwise the iterator only contains this error.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, CommandLine};
+    ///
+    /// let error = CommandLine::new(Command::new("MyApp").option(CommandOption::new("debug")))
+    ///     .collect_errors(true)
+    ///     .parse_from(vec!["--verbose", "--color"])
+    ///     .unwrap_err();
+    ///
+    /// let errors = error.iter().map(|e| e.to_string()).collect::<Vec<String>>();
+    /// assert_eq!(errors, vec!["unexpected option: '--verbose'", "unexpected option: '--color'"]);
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, Error> {
+        match &self.inner {
+            Inner::Multiple(errors, _) => errors.iter(),
+            _ => std::slice::from_ref(self).iter(),
+        }
+    }
+
+    /// Returns the index of the token where the parser found this error, if any.
+    ///
+    /// The index is from the tokens returned by `Tokenizer::tokenize`, an option with a value
+    /// like `--color=red` are 3 tokens: `--color`, `=` and `red`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption};
+    ///
+    /// let error = Command::new("MyApp")
+    ///     .option(CommandOption::new("debug"))
+    ///     .parse_from(vec!["--debug", "--verbose"])
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.token_index(), Some(1));
+    /// ```
+    pub fn token_index(&self) -> Option<usize> {
+        self.token_index
+    }
+
+    // Sets the index of the token where the error was found, if is not already set
+    pub(crate) fn at_token(mut self, index: usize) -> Self {
+        if self.token_index.is_none() && !matches!(self.inner, Multiple(..)) {
+            self.token_index = Some(index);
+        }
+
+        self
+    }
+
+    // Sets the value rejected by an argument and the values it expected
+    pub(crate) fn with_invalid_value(mut self, value: &str, valid_values: &[String]) -> Self {
+        self.invalid_value = Some(Box::new(InvalidValue {
+            value: value.to_owned(),
+            valid_values: valid_values.to_vec(),
+        }));
+
+        self
+    }
+
+    // Returns the value rejected by an argument and the values it expected, if any
+    pub(crate) fn invalid_value(&self) -> Option<(&str, &[String])> {
+        match &self.inner {
+            Multiple(errors, _) => errors[0].invalid_value(),
+            _ => self
+                .invalid_value
+                .as_deref()
+                .map(|v| (v.value.as_str(), v.valid_values.as_slice())),
         }
     }
 
-    /// Returns this error with the given message.
+    /// Returns this error with the given message, if contains several errors
+    /// the message is written once after all of them.
     ///
     /// # Example
     /// ```
@@ -57,39 +162,213 @@ impl Error {
     /// assert_eq!(new_error.to_string(), "invalid value for argument 'xyz': expected a number".to_string())
     /// ```
     pub fn with_message<S: Into<AnyError>>(&self, msg: S) -> Self {
-        match &self.inner {
+        let inner = match &self.inner {
             Simple(kind) => {
-                Error::new(kind.clone(), msg.into())
+                Custom(CustomError::new(kind.clone(), msg.into(), None))
             }
             Custom(custom) => {
-                Error {
-                    inner: Custom(CustomError::new(
-                        custom.kind.clone(),
-                        custom.error.to_string().into(),
-                        Some(msg.into().to_string())
-                    ))
-                }
+                Custom(CustomError::new(
+                    custom.kind.clone(),
+                    custom.error.to_string().into(),
+                    Some(msg.into().to_string())
+                ))
             }
+            Multiple(errors, _) => {
+                // The message is written once after all the errors
+                Multiple(errors.iter().map(Error::copy).collect(), Some(msg.into().to_string()))
+            }
+        };
+
+        Error {
+            inner,
+            token_index: self.token_index,
+            invalid_value: self.invalid_value.clone(),
         }
     }
 
-    /// Prints this error in the `stderr` and exit this process with status 0.
-    pub fn exit(self) -> ! {
-        if matches!(self.kind(), ErrorKind::DisplayHelp(_) | ErrorKind::DisplayVersion(_)) {
-            println!("{}", self);
-        } else {
+    // Returns a copy of this error, the source of a custom error is kept as its message
+    fn copy(&self) -> Self {
+        let inner = match &self.inner {
+            Simple(kind) => Simple(kind.clone()),
+            Custom(custom) => Custom(CustomError::new(
+                custom.kind.clone(),
+                custom.error.to_string().into(),
+                custom.info.clone(),
+            )),
+            Multiple(errors, info) => Multiple(errors.iter().map(Error::copy).collect(), info.clone()),
+        };
+
+        Error {
+            inner,
+            token_index: self.token_index,
+            invalid_value: self.invalid_value.clone(),
+        }
+    }
+
+    // Returns this error with each of the errors it contains replaced by the given function,
+    // or replaces this error if only contains itself
+    pub(crate) fn map_errors<F: FnMut(Error) -> Error>(self, mut f: F) -> Self {
+        match self.inner {
+            Multiple(errors, info) => Error {
+                inner: Multiple(errors.into_iter().map(f).collect(), info),
+                token_index: self.token_index,
+                invalid_value: self.invalid_value,
+            },
+            _ => f(self),
+        }
+    }
+
+    /// Returns this error with the given context before its message.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Error, ErrorKind};
+    ///
+    /// let error = Error::new(ErrorKind::Other, "file not found");
+    /// let new_error = error.context("cannot read `config.json`");
+    /// assert_eq!(new_error.to_string(), "unexpected error: cannot read `config.json`: file not found".to_string())
+    /// ```
+    pub fn context<S: Display>(self, context: S) -> Self {
+        let inner = match self.inner {
+            Simple(kind) => Custom(CustomError::new(kind, context.to_string().into(), None)),
+            Custom(custom) => Custom(CustomError::new(
+                custom.kind,
+                format!("{}: {}", context, custom.error).into(),
+                custom.info,
+            )),
+            Multiple(errors, info) => {
+                let context = context.to_string();
+                Multiple(errors.into_iter().map(|e| e.context(context.as_str())).collect(), info)
+            }
+        };
+
+        Error {
+            inner,
+            token_index: self.token_index,
+            invalid_value: self.invalid_value,
+        }
+    }
+
+    /// Returns `true` if the command-line was used incorrectly, like an unknown option or
+    /// subcommand, a missing required option, an invalid number of arguments or an invalid expression.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Error, ErrorKind};
+    ///
+    /// let error = Error::from(ErrorKind::UnexpectedOption("--color".to_string()));
+    /// assert!(error.is_usage_error());
+    /// assert!(!error.is_validation_error());
+    /// ```
+    pub fn is_usage_error(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::InvalidArgumentCount
+                | ErrorKind::InvalidExpression
+                | ErrorKind::UnexpectedOption(_)
+                | ErrorKind::UnexpectedCommand(_)
+                | ErrorKind::MissingOption(_)
+                | ErrorKind::MissingSubcommand(_)
+                | ErrorKind::ConflictingOptions(_, _)
+                | ErrorKind::MissingRequiredBy(_, _)
+        )
+    }
+
+    /// Returns `true` if a value passed to an argument is invalid or its validator panicked.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Error, ErrorKind};
+    ///
+    /// let error = Error::from(ErrorKind::InvalidArgument("count".to_string()));
+    /// assert!(error.is_validation_error());
+    /// ```
+    pub fn is_validation_error(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::InvalidArgument(_) | ErrorKind::ValidatorPanic(_) | ErrorKind::InvalidUtf8(_)
+        )
+    }
+
+    /// Returns `true` if the error is not related to the command-line input,
+    /// like the errors returned by the command handlers.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Error, ErrorKind};
+    ///
+    /// let error = Error::new(ErrorKind::Other, "file not found");
+    /// assert!(error.is_handler_error());
+    /// assert!(!error.is_usage_error());
+    /// ```
+    pub fn is_handler_error(&self) -> bool {
+        matches!(self.kind(), ErrorKind::Other)
+    }
+
+    // Returns `true` if this is a help or version message rather than an actual error.
+    pub(crate) fn is_display_message(&self) -> bool {
+        matches!(self.kind(), ErrorKind::DisplayHelp(_) | ErrorKind::DisplayVersion(_))
+    }
+
+    /// Returns the exit code of the process for this error.
+    ///
+    /// - `0` for the help and version messages.
+    /// - `1` for the validation errors and the errors returned by the command handlers.
+    /// - `2` for the usage errors.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Error, ErrorKind};
+    ///
+    /// assert_eq!(Error::from(ErrorKind::UnexpectedOption("--color".to_string())).exit_code(), 2);
+    /// assert_eq!(Error::from(ErrorKind::InvalidArgument("count".to_string())).exit_code(), 1);
+    /// assert_eq!(Error::from(ErrorKind::DisplayHelp("Usage: MyApp".to_string())).exit_code(), 0);
+    /// ```
+    pub fn exit_code(&self) -> i32 {
+        match self.kind() {
+            ErrorKind::DisplayHelp(_) | ErrorKind::DisplayVersion(_) | ErrorKind::FallthroughHelp => 0,
+            _ if self.is_usage_error() => 2,
+            _ => 1,
+        }
+    }
+
+    /// Writes this error to the given writer, prefixed with `Error: ` if is not a help or version message.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Error, ErrorKind};
+    ///
+    /// let mut buffer = Vec::new();
+    /// let error = Error::from(ErrorKind::MissingOption("token".to_string()));
+    /// error.print(&mut buffer).unwrap();
+    /// assert_eq!(String::from_utf8(buffer).unwrap(), "Error: 'token' is required\n");
+    /// ```
+    pub fn print(&self, writer: &mut dyn Write) -> Result<()> {
+        match self.kind() {
+            ErrorKind::DisplayHelp(s) | ErrorKind::DisplayVersion(s) => write_line(writer, s),
             // FIXME: Error already contains a newline
-            eprintln!("Error: {}", self);
+            _ => write_line(writer, format_args!("Error: {}", self)),
         }
+    }
+
+    /// Prints this error and exit this process with its `exit_code`,
+    /// help and version messages are printed in the `stdout` and the errors in the `stderr`.
+    pub fn exit(self) -> ! {
+        // A closed `stdout` or `stderr` is ignored, there is nothing else to report
+        let _ = if self.is_display_message() {
+            self.print(&mut std::io::stdout())
+        } else {
+            self.print(&mut std::io::stderr())
+        };
 
-        std::process::exit(0)
+        std::process::exit(self.exit_code())
     }
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self.inner {
-            Simple(_) => None,
+            Simple(_) | Multiple(..) => None,
             Custom(ref custom) => Some(custom.error.as_ref()),
         }
     }
@@ -99,7 +378,20 @@ impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match &self.inner {
             Inner::Simple(kind) => Display::fmt(kind, f),
-            Inner::Custom(custom) =>  Display::fmt(custom, f)
+            Inner::Custom(custom) =>  Display::fmt(custom, f),
+            Inner::Multiple(errors, info) => {
+                // found 2 errors:
+                // 1. unexpected option: '--color'
+                // 2. invalid value for argument 'count'
+                write!(f, "found {} errors:", errors.len())?;
+                for (index, error) in errors.iter().enumerate() {
+                    write!(f, "\n{}. {}", index + 1, error)?;
+                }
+                if let Some(info) = info {
+                    write!(f, "\n{}", info)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -114,27 +406,46 @@ impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Self {
         Error {
             inner: Simple(kind),
+            token_index: None,
+            invalid_value: None,
         }
     }
 }
 
 /// Types of errors.
+///
+/// New variants may be added in the future, prefer `Error::is_usage_error`,
+/// `Error::is_validation_error` and `Error::is_handler_error` over matching all the variants.
 #[derive(Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum ErrorKind {
-    /// The value passed to the argument is invalid.
+    /// The value passed to the argument is invalid. *Validation error*.
     InvalidArgument(String),
-    /// Invalid number of arguments being passed.
+    /// The `Validator` of the argument panicked while checking a value. *Validation error*.
+    ValidatorPanic(String),
+    /// A value is not valid UTF-8 and the argument don't allow it. *Validation error*.
+    InvalidUtf8(OsString),
+    /// Invalid number of arguments being passed. *Usage error*.
     InvalidArgumentCount,
-    /// The expression is invalid.
+    /// The expression is invalid. *Usage error*.
     InvalidExpression,
-    /// The option wasn't expected in the current context
+    /// The option wasn't expected in the current context. *Usage error*.
     UnexpectedOption(String),
-    /// The command wasn't expected in the current context
+    /// The command wasn't expected in the current context. *Usage error*.
     UnexpectedCommand(String),
-    /// The option is required.
+    /// The option is required. *Usage error*.
     MissingOption(String),
-    /// An error no listed.
+    /// The command only groups subcommands and none was used. *Usage error*.
+    MissingSubcommand(String),
+    /// Two options that cannot be used together were passed, as they were written. *Usage error*.
+    ConflictingOptions(String, String),
+    /// An option was passed without other option it requires, the first is the option as was written.
+    /// *Usage error*.
+    MissingRequiredBy(String, String),
+    /// An error no listed. *Handler error*.
     Other,
+    /// The definition of a command cannot be deserialized, like an invalid JSON, TOML or YAML file.
+    Deserialize(String),
 
     /// *Not an actual error used for convenience*.
     ///
@@ -155,12 +466,18 @@ impl Display for ErrorKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             ErrorKind::InvalidArgument(s) => write!(f, "invalid value for argument '{}'", s),
+            ErrorKind::ValidatorPanic(s) => write!(f, "validator panicked for argument '{}'", s),
+            ErrorKind::InvalidUtf8(s) => write!(f, "invalid UTF-8 value: '{}'", s.to_string_lossy()),
             ErrorKind::InvalidArgumentCount => write!(f, "invalid argument count"),
             ErrorKind::InvalidExpression => write!(f, "invalid expression"),
             ErrorKind::UnexpectedOption(s) => write!(f, "unexpected option: '{}'", s),
             ErrorKind::UnexpectedCommand(s) => write!(f, "unexpected command: '{}'", s),
             ErrorKind::MissingOption(s) => write!(f, "'{}' is required", s),
+            ErrorKind::MissingSubcommand(s) => write!(f, "'{}' requires a subcommand", s),
+            ErrorKind::ConflictingOptions(a, b) => write!(f, "'{}' cannot be used with '{}'", a, b),
+            ErrorKind::MissingRequiredBy(a, b) => write!(f, "'{}' requires '{}'", a, b),
             ErrorKind::Other => write!(f, "unexpected error"),
+            ErrorKind::Deserialize(s) => write!(f, "invalid command definition: {}", s),
             ErrorKind::DisplayHelp(s) => write!(f, "{}", s),
             ErrorKind::DisplayVersion(s) => write!(f, "{}", s),
             ErrorKind::FallthroughHelp => panic!("`ErrorKind::FallthroughHelp` should not be used as an error")
@@ -198,4 +515,96 @@ impl Display for CustomError {
             write!(f, "{}: {}", self.kind, self.error)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_code_test() {
+        let code = |kind: ErrorKind| Error::from(kind).exit_code();
+        let s = || "value".to_string();
+
+        // Help and version messages
+        assert_eq!(code(ErrorKind::DisplayHelp(s())), 0);
+        assert_eq!(code(ErrorKind::DisplayVersion(s())), 0);
+
+        // Validation and handler errors
+        assert_eq!(code(ErrorKind::InvalidArgument(s())), 1);
+        assert_eq!(code(ErrorKind::ValidatorPanic(s())), 1);
+        assert_eq!(code(ErrorKind::InvalidUtf8(s().into())), 1);
+        assert_eq!(code(ErrorKind::Other), 1);
+        assert_eq!(Error::new(ErrorKind::Other, "file not found").exit_code(), 1);
+        assert_eq!(code(ErrorKind::Deserialize(s())), 1);
+
+        // Usage errors
+        assert_eq!(code(ErrorKind::InvalidArgumentCount), 2);
+        assert_eq!(code(ErrorKind::InvalidExpression), 2);
+        assert_eq!(code(ErrorKind::UnexpectedOption(s())), 2);
+        assert_eq!(code(ErrorKind::UnexpectedCommand(s())), 2);
+        assert_eq!(code(ErrorKind::MissingOption(s())), 2);
+        assert_eq!(code(ErrorKind::MissingSubcommand(s())), 2);
+        assert_eq!(code(ErrorKind::ConflictingOptions(s(), s())), 2);
+        assert_eq!(code(ErrorKind::MissingRequiredBy(s(), s())), 2);
+    }
+
+    #[test]
+    fn print_test() {
+        let print = |error: Error| {
+            let mut buffer = Vec::new();
+            error.print(&mut buffer).unwrap();
+            String::from_utf8(buffer).unwrap()
+        };
+
+        assert_eq!(print(Error::from(ErrorKind::DisplayHelp("Usage: MyApp".to_string()))), "Usage: MyApp\n");
+        assert_eq!(print(Error::from(ErrorKind::DisplayVersion("MyApp 1.0".to_string()))), "MyApp 1.0\n");
+        assert_eq!(print(Error::from(ErrorKind::InvalidExpression)), "Error: invalid expression\n");
+        assert_eq!(
+            print(Error::new(ErrorKind::Other, "file not found")),
+            "Error: unexpected error: file not found\n"
+        );
+    }
+
+    #[test]
+    fn multiple_errors_test() {
+        let error = Error::from_errors(vec![
+            Error::from(ErrorKind::UnexpectedOption("--color".to_string())).at_token(0),
+            Error::new(ErrorKind::InvalidArgument("count".to_string()), "expected a number").at_token(2),
+        ]);
+
+        // The kind and the exit code are the ones of the first error
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedOption("--color".to_string()));
+        assert_eq!(error.exit_code(), 2);
+        assert_eq!(error.token_index(), None);
+        assert_eq!(error.iter().map(|e| e.token_index()).collect::<Vec<_>>(), vec![Some(0), Some(2)]);
+        assert_eq!(
+            error.to_string(),
+            "found 2 errors:\n\
+            1. unexpected option: '--color'\n\
+            2. invalid value for argument 'count': expected a number"
+        );
+
+        // The context is added to each error
+        let error = error.context("in `config.json`");
+        assert_eq!(error.iter().map(|e| e.token_index()).collect::<Vec<_>>(), vec![Some(0), Some(2)]);
+        assert!(error.to_string().contains("2. invalid value for argument 'count': in `config.json`: expected a number"));
+
+        // The message is written once after all the errors
+        let error = error.with_message("USAGE:\n   MyApp [OPTIONS]");
+        assert_eq!(error.iter().map(|e| e.token_index()).collect::<Vec<_>>(), vec![Some(0), Some(2)]);
+        assert_eq!(
+            error.to_string(),
+            "found 2 errors:\n\
+            1. unexpected option: '--color': in `config.json`\n\
+            2. invalid value for argument 'count': in `config.json`: expected a number\n\
+            USAGE:\n   MyApp [OPTIONS]"
+        );
+
+        // A single error is not wrapped
+        let error = Error::from_errors(vec![Error::from(ErrorKind::InvalidExpression).at_token(1)]);
+        assert_eq!(error.iter().count(), 1);
+        assert_eq!(error.token_index(), Some(1));
+        assert_eq!(error.to_string(), "invalid expression");
+    }
+}
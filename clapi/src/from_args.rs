@@ -0,0 +1,157 @@
+use crate::error::Result;
+use crate::{Command, ParseResult};
+use std::borrow::Borrow;
+
+/// A type that can be constructed from the command-line arguments.
+///
+/// This is implemented by `#[derive(FromCommand)]`.
+pub trait FromArgs: Sized {
+    /// Returns the `Command` used to parse this type.
+    fn command() -> Command;
+
+    /// Constructs this type from the result of parse the arguments using its `command`.
+    fn from_parse_result(result: &ParseResult) -> Result<Self>;
+}
+
+/// Parses `T` from `std::env::args_os`.
+///
+/// Prints the help, version or error message and exits the process
+/// if the arguments cannot be parsed into `T`.
+///
+/// # Example
+/// ```no_run
+/// use clapi::{Argument, Command, FromArgs, ParseResult};
+///
+/// struct Echo {
+///     values: Vec<String>,
+/// }
+///
+/// impl FromArgs for Echo {
+///     fn command() -> Command {
+///         Command::root().arg(Argument::zero_or_more("values"))
+///     }
+///
+///     fn from_parse_result(result: &ParseResult) -> clapi::Result<Self> {
+///         let values = result.args().get("values").unwrap().convert_all::<String>()?;
+///         Ok(Echo { values })
+///     }
+/// }
+///
+/// let echo = clapi::parse::<Echo>();
+/// println!("{}", echo.values.join(" "));
+/// ```
+pub fn parse<T: FromArgs>() -> T {
+    try_parse().unwrap_or_else(|error| error.exit())
+}
+
+/// Parses `T` from `std::env::args_os`, returning the help, version or error message as an `Err`.
+pub fn try_parse<T: FromArgs>() -> Result<T> {
+    let result = T::command().try_parse_from_os(std::env::args_os().skip(1))?;
+    T::from_parse_result(&result)
+}
+
+/// Parses `T` from the given arguments.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandOption, FromArgs, ParseResult};
+///
+/// struct Verbose(bool);
+///
+/// impl FromArgs for Verbose {
+///     fn command() -> Command {
+///         Command::root().option(CommandOption::new("verbose"))
+///     }
+///
+///     fn from_parse_result(result: &ParseResult) -> clapi::Result<Self> {
+///         Ok(Verbose(result.options().contains("verbose")))
+///     }
+/// }
+///
+/// assert!(clapi::parse_from::<Verbose, _, _>(vec!["--verbose"]).unwrap().0);
+/// assert!(!clapi::parse_from::<Verbose, _, _>(Vec::<String>::new()).unwrap().0);
+/// ```
+pub fn parse_from<T, I, S>(args: I) -> Result<T>
+where
+    T: FromArgs,
+    I: IntoIterator<Item = S>,
+    S: Borrow<str>,
+{
+    let result = T::command().parse_from(args)?;
+    T::from_parse_result(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::validate_type;
+    use crate::{Argument, CommandOption, ErrorKind};
+
+    #[derive(Debug, PartialEq)]
+    struct Sum {
+        times: u32,
+        values: Vec<i64>,
+    }
+
+    impl FromArgs for Sum {
+        fn command() -> Command {
+            Command::new("sum")
+                .version("1.0")
+                .option(
+                    CommandOption::new("times").alias("t").arg(
+                        Argument::with_name("times")
+                            .validator(validate_type::<u32>())
+                            .default(1),
+                    ),
+                )
+                .arg(Argument::one_or_more("values").validator(validate_type::<i64>()))
+        }
+
+        fn from_parse_result(result: &ParseResult) -> Result<Self> {
+            Ok(Sum {
+                times: result.options().convert("times")?,
+                values: result.args().convert_all("values")?,
+            })
+        }
+    }
+
+    #[test]
+    fn parse_from_test() {
+        let sum = parse_from::<Sum, _, _>(vec!["-t", "2", "1", "2"]).unwrap();
+        assert_eq!(
+            sum,
+            Sum {
+                times: 2,
+                values: vec![1, 2]
+            }
+        );
+
+        let sum = parse_from::<Sum, _, _>(vec!["5"]).unwrap();
+        assert_eq!(
+            sum,
+            Sum {
+                times: 1,
+                values: vec![5]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_from_error_test() {
+        let error = parse_from::<Sum, _, _>(vec!["-t", "x", "1"]).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::InvalidArgument("times".to_owned())
+        );
+
+        let error = parse_from::<Sum, _, _>(vec!["--help"]).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::DisplayHelp(_)));
+        assert_eq!(error.exit_code(), 0);
+
+        let error = parse_from::<Sum, _, _>(vec!["--version"]).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::DisplayVersion("sum 1.0".to_owned())
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use crate::utils::write_line;
+use crate::{Argument, Command, Error, ErrorKind, Result};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// Type of the value of a setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingType {
+    /// Any value.
+    String,
+    /// `true` or `false`.
+    Bool,
+    /// A 64 bits signed integer.
+    Integer,
+    /// A 64 bits floating point number.
+    Float,
+}
+
+impl SettingType {
+    /// Returns `true` if the given value is valid for this type.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::generators::SettingType;
+    ///
+    /// assert!(SettingType::Integer.is_valid("25"));
+    /// assert!(!SettingType::Bool.is_valid("yes"));
+    /// ```
+    pub fn is_valid(&self, value: &str) -> bool {
+        match self {
+            SettingType::String => true,
+            SettingType::Bool => value.parse::<bool>().is_ok(),
+            SettingType::Integer => value.parse::<i64>().is_ok(),
+            SettingType::Float => value.parse::<f64>().is_ok(),
+        }
+    }
+}
+
+impl Display for SettingType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingType::String => write!(f, "string"),
+            SettingType::Bool => write!(f, "bool"),
+            SettingType::Integer => write!(f, "integer"),
+            SettingType::Float => write!(f, "float"),
+        }
+    }
+}
+
+/// Declaration of a setting used by [`settings_commands`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingDef {
+    name: String,
+    ty: SettingType,
+    description: Option<String>,
+}
+
+impl SettingDef {
+    /// Constructs a new `SettingDef` with the given name and type.
+    ///
+    /// # Panics
+    /// If the name is empty, contains whitespaces or starts with `-`.
+    pub fn new<S: Into<String>>(name: S, ty: SettingType) -> Self {
+        let name = name.into();
+        crate::utils::assert_valid_name("setting names", &name);
+
+        SettingDef {
+            name,
+            ty,
+            description: None,
+        }
+    }
+
+    /// Returns the name of this setting.
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns the type of this setting.
+    pub fn get_type(&self) -> SettingType {
+        self.ty
+    }
+
+    /// Returns the description of this setting or `None` if not set.
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Sets the description of this setting.
+    pub fn description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// Storage for the values of the settings used by [`settings_commands`].
+pub trait SettingsBackend {
+    /// Returns the value of the setting with the given key or `None` if is not set.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Sets the value of the setting with the given key,
+    /// the value is already validated against the type of the setting.
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+}
+
+/// Constructs a root `Command` with the `get <key>`, `set <key> <value>` and `list` subcommands
+/// for the given settings, storing the values in the given `SettingsBackend`.
+///
+/// The `key` only accepts the names of the settings and the `value` of `set` is checked against
+/// the `SettingType` of the key before calling `SettingsBackend::set`, if invalid returns
+/// an `ErrorKind::InvalidArgument("value")` error.
+///
+/// # Panics
+/// If there is duplicated setting names.
+///
+/// # Example
+/// ```
+/// use clapi::generators::{settings_commands, SettingDef, SettingType, SettingsBackend};
+/// use std::collections::HashMap;
+///
+/// #[derive(Default)]
+/// struct Settings(HashMap<String, String>);
+///
+/// impl SettingsBackend for Settings {
+///     fn get(&self, key: &str) -> Option<String> {
+///         self.0.get(key).cloned()
+///     }
+///
+///     fn set(&mut self, key: &str, value: &str) -> clapi::Result<()> {
+///         self.0.insert(key.to_owned(), value.to_owned());
+///         Ok(())
+///     }
+/// }
+///
+/// let command = settings_commands(vec![
+///     SettingDef::new("editor", SettingType::String),
+///     SettingDef::new("tab-size", SettingType::Integer),
+/// ], Settings::default());
+///
+/// assert!(command.find_subcommand("get").is_some());
+/// assert!(command.find_subcommand("set").is_some());
+/// assert!(command.find_subcommand("list").is_some());
+/// ```
+pub fn settings_commands<B>(defs: Vec<SettingDef>, backend: B) -> Command
+where
+    B: SettingsBackend + 'static,
+{
+    for (index, def) in defs.iter().enumerate() {
+        assert!(
+            !defs[..index].iter().any(|d| d.name == def.name),
+            "duplicated setting: `{}`",
+            def.name
+        );
+    }
+
+    let defs = Rc::new(defs);
+    let backend = Rc::new(RefCell::new(backend));
+    let names = defs.iter().map(|d| d.name.clone()).collect::<Vec<String>>();
+
+    let get = {
+        let backend = backend.clone();
+        Command::new("get")
+            .description("Prints the value of a setting")
+            .arg(key_arg(&names))
+            .handler(move |_, args| {
+                let key = args.get("key").unwrap().convert::<String>()?;
+                match backend.borrow().get(&key) {
+                    Some(value) => write_line(&mut std::io::stdout(), value),
+                    None => Ok(()),
+                }
+            })
+    };
+
+    let set = {
+        let backend = backend.clone();
+        let defs = defs.clone();
+        Command::new("set")
+            .description("Sets the value of a setting")
+            .arg(key_arg(&names))
+            .arg(Argument::with_name("value").description("the new value of the setting"))
+            .handler(move |_, args| {
+                let key = args.get("key").unwrap().convert::<String>()?;
+                let value = args.get("value").unwrap().convert::<String>()?;
+                // The type of `value` depends on `key`, so we check it here
+                let def = defs.iter().find(|d| d.name == key).unwrap();
+
+                if !def.ty.is_valid(&value) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidArgument("value".to_owned()),
+                        format!("expected {} for `{}` but was `{}`", def.ty, key, value),
+                    ));
+                }
+
+                backend.borrow_mut().set(&key, &value)
+            })
+    };
+
+    let list = Command::new("list")
+        .description("Prints the value of all the settings")
+        .handler(move |_, _| {
+            let mut stdout = std::io::stdout();
+            for def in defs.iter() {
+                if let Some(value) = backend.borrow().get(&def.name) {
+                    write_line(&mut stdout, format!("{} = {}", def.name, value))?;
+                }
+            }
+            Ok(())
+        });
+
+    Command::root().subcommand(get).subcommand(set).subcommand(list)
+}
+
+// The `key` argument of the `get` and `set` subcommands
+fn key_arg(names: &[String]) -> Argument {
+    Argument::with_name("key")
+        .description("the name of the setting")
+        .valid_values(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CommandLine;
+    use std::collections::HashMap;
+
+    #[derive(Clone, Default)]
+    struct InMemory(Rc<RefCell<HashMap<String, String>>>);
+
+    impl SettingsBackend for InMemory {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key).cloned()
+        }
+
+        fn set(&mut self, key: &str, value: &str) -> Result<()> {
+            self.0.borrow_mut().insert(key.to_owned(), value.to_owned());
+            Ok(())
+        }
+    }
+
+    fn defs() -> Vec<SettingDef> {
+        vec![
+            SettingDef::new("editor", SettingType::String).description("the text editor"),
+            SettingDef::new("tab-size", SettingType::Integer),
+            SettingDef::new("autosave", SettingType::Bool),
+            SettingDef::new("zoom", SettingType::Float),
+        ]
+    }
+
+    #[test]
+    fn setting_type_test() {
+        assert!(SettingType::String.is_valid("anything"));
+        assert!(SettingType::Bool.is_valid("true"));
+        assert!(!SettingType::Bool.is_valid("1"));
+        assert!(SettingType::Integer.is_valid("-4"));
+        assert!(!SettingType::Integer.is_valid("4.5"));
+        assert!(SettingType::Float.is_valid("4.5"));
+        assert!(!SettingType::Float.is_valid("four"));
+    }
+
+    #[test]
+    fn settings_commands_test() {
+        let backend = InMemory::default();
+        let mut cli = CommandLine::new(settings_commands(defs(), backend.clone()))
+            .with_stderr(std::io::sink());
+
+        cli.run_from(vec!["set", "tab-size", "4"]).unwrap();
+        cli.run_from(vec!["set", "autosave", "true"]).unwrap();
+        cli.run_from(vec!["set", "zoom", "1.5"]).unwrap();
+        cli.run_from(vec!["set", "editor", "vim"]).unwrap();
+        cli.run_from(vec!["get", "editor"]).unwrap();
+        cli.run_from(vec!["list"]).unwrap();
+
+        let values = backend.0.borrow();
+        assert_eq!(values.get("tab-size").unwrap(), "4");
+        assert_eq!(values.get("autosave").unwrap(), "true");
+        assert_eq!(values.get("zoom").unwrap(), "1.5");
+        assert_eq!(values.get("editor").unwrap(), "vim");
+    }
+
+    #[test]
+    fn settings_commands_error_test() {
+        let backend = InMemory::default();
+        let mut cli = CommandLine::new(settings_commands(defs(), backend.clone()))
+            .with_stderr(std::io::sink());
+
+        let error = cli.run_from(vec!["set", "tab-size", "four"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("value".to_owned()));
+
+        let error = cli.run_from(vec!["set", "autosave", "yes"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("value".to_owned()));
+
+        assert!(cli.run_from(vec!["set", "theme", "dark"]).is_err());
+        assert!(cli.run_from(vec!["get", "theme"]).is_err());
+        assert!(cli.run_from(vec!["set", "editor"]).is_err());
+        assert!(backend.0.borrow().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn settings_commands_duplicated_test() {
+        settings_commands(
+            vec![
+                SettingDef::new("editor", SettingType::String),
+                SettingDef::new("editor", SettingType::Integer),
+            ],
+            InMemory::default(),
+        );
+    }
+}
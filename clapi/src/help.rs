@@ -1,18 +1,27 @@
 use self::utils::*;
-use crate::{Command, Context, OptionList};
-use std::fmt::Write;
+use crate::doctor::Json;
+use crate::{Argument, Command, CommandOption, Context, OptionList};
+use std::cmp;
+use std::fmt::{self, Write};
+use std::io;
 use std::rc::Rc;
 
 // Indentation used to write the help messages
 const INDENT: &str = "   ";
 
+// Max width of the help messages if is not set and the terminal width is unknown
+const DEFAULT_MAX_WIDTH: usize = 100;
+
 // TODO: Rename to CommandHelp
 
+// Writes a help or usage message of the command into the buffer
+pub(crate) type HelpFn = Rc<dyn Fn(&mut String, &Context, &Command, bool)>;
+
 /// Configuration for provider help messages.
 #[derive(Clone)]
 pub struct HelpSource {
-    help: Rc<dyn Fn(&mut String, &Context, &Command, bool)>,
-    usage: Rc<dyn Fn(&mut String, &Context, &Command, bool)>,
+    pub(crate) help: HelpFn,
+    pub(crate) usage: HelpFn,
 }
 
 impl HelpSource {
@@ -22,6 +31,35 @@ impl HelpSource {
         Default::default()
     }
 
+    /// Constructs a `HelpSource` that writes the help and usage messages with the given functions,
+    /// the functions receive the same arguments than `HelpSource::get_help`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Context};
+    /// use clapi::help::{command_usage, HelpSource};
+    ///
+    /// let help = HelpSource::custom(
+    ///     |buf, _, command, _| buf.push_str(&format!("Help of {}", command.get_name())),
+    ///     command_usage,
+    /// );
+    ///
+    /// let context = Context::new(Command::new("MyApp"));
+    /// let mut buf = String::new();
+    /// help.get_help(&mut buf, &context, context.root(), false);
+    /// assert_eq!(buf, "Help of MyApp");
+    /// ```
+    pub fn custom<H, U>(help: H, usage: U) -> Self
+    where
+        H: Fn(&mut String, &Context, &Command, bool) + 'static,
+        U: Fn(&mut String, &Context, &Command, bool) + 'static,
+    {
+        HelpSource {
+            help: Rc::new(help),
+            usage: Rc::new(usage),
+        }
+    }
+
     /// Gets a help message for the given command.
     ///
     /// # Arguments by position:
@@ -66,6 +104,190 @@ impl Default for HelpSource {
     }
 }
 
+/// Configuration of the hints written after the description of the options.
+///
+/// The hints are always written in the same order:
+/// `(can be repeated)`, `[type: ...]`, `[possible values: ...]`, `[default: ...]` and `[env: ...]`.
+///
+/// # Example
+/// ```
+/// use clapi::{Argument, Command, CommandOption, Context};
+/// use clapi::help::{command_help, HelpStyle};
+///
+/// let context = Context::builder(Command::new("MyApp")
+///     .option(CommandOption::new("color")
+///         .description("Color to use")
+///         .arg(Argument::new().valid_values(vec!["red", "blue"]).default("red"))))
+///     .help_style(HelpStyle::new().show_possible_values(true).max_width(100))
+///     .build();
+///
+/// let mut help = String::new();
+/// command_help(&mut help, &context, context.root(), false);
+/// assert!(help.contains("Color to use [possible values: red, blue] [default: red]"));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HelpStyle {
+    types: bool,
+    possible_values: bool,
+    default_values: bool,
+    env: bool,
+    repeated: bool,
+    max_width: Option<usize>,
+}
+
+impl HelpStyle {
+    /// Constructs a default `HelpStyle`.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Specify if the type expected by the validator of the option arguments is shown
+    /// as a `[type: ...]` hint, by default `true`. Require `typing` feature enable.
+    ///
+    /// The integers are shown as `integer`, the floats as `number`, `bool` as `boolean`,
+    /// `String` as `string` and `PathBuf` as `path`, other types are shown with its name.
+    pub fn show_types(mut self, show: bool) -> Self {
+        self.types = show;
+        self
+    }
+
+    /// Specify if the valid values of the option arguments are shown as a `[possible values: ...]` hint,
+    /// otherwise are shown after the option name as `<VALUE1|VALUE2>`, by default `false`.
+    pub fn show_possible_values(mut self, show: bool) -> Self {
+        self.possible_values = show;
+        self
+    }
+
+    /// Specify if the default values of the options are shown as a `[default: ...]` hint,
+    /// by default `true`.
+    pub fn show_default_values(mut self, show: bool) -> Self {
+        self.default_values = show;
+        self
+    }
+
+    /// Specify if the environment variable of the options is shown as an `[env: ...]` hint,
+    /// by default `true`.
+    pub fn show_env(mut self, show: bool) -> Self {
+        self.env = show;
+        self
+    }
+
+    /// Specify if the counted options are shown with a `(can be repeated)` hint, by default `true`.
+    pub fn show_repeated(mut self, show: bool) -> Self {
+        self.repeated = show;
+        self
+    }
+
+    /// Sets the max width of the help messages, the descriptions longer than this
+    /// are wrapped and aligned under its column.
+    ///
+    /// By default is the width given by the `COLUMNS` environment variable if set,
+    /// otherwise the width of the terminal of the `stdout` with the `color` feature, or 100.
+    ///
+    /// # Panics
+    /// If the width is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Context};
+    /// use clapi::help::{command_help, HelpStyle};
+    ///
+    /// let context = Context::builder(Command::new("MyApp")
+    ///     .option(CommandOption::new("color")
+    ///         .description("The color used to write the output of the commands")))
+    ///     .help_style(HelpStyle::new().max_width(40))
+    ///     .build();
+    ///
+    /// let mut help = String::new();
+    /// command_help(&mut help, &context, context.root(), false);
+    /// assert!(help.contains(concat!(
+    ///     "   --color      The color used to write\n",
+    ///     "                the output of the\n",
+    ///     "                commands\n"
+    /// )));
+    /// ```
+    pub fn max_width(mut self, width: usize) -> Self {
+        assert!(width > 0, "help `max_width` cannot be 0");
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Hides all the hints of the options.
+    pub fn hide_all_hints(self) -> Self {
+        self.show_types(false)
+            .show_possible_values(false)
+            .show_default_values(false)
+            .show_env(false)
+            .show_repeated(false)
+    }
+
+    /// Returns `true` if the type of the option arguments are shown as a hint.
+    pub fn is_types_shown(&self) -> bool {
+        self.types
+    }
+
+    /// Returns `true` if the valid values of the options are shown as a hint.
+    pub fn is_possible_values_shown(&self) -> bool {
+        self.possible_values
+    }
+
+    /// Returns `true` if the default values of the options are shown as a hint.
+    pub fn is_default_values_shown(&self) -> bool {
+        self.default_values
+    }
+
+    /// Returns `true` if the environment variable of the options is shown as a hint.
+    pub fn is_env_shown(&self) -> bool {
+        self.env
+    }
+
+    /// Returns `true` if the counted options are shown with a hint.
+    pub fn is_repeated_shown(&self) -> bool {
+        self.repeated
+    }
+
+    /// Returns the max width of the help messages, see `HelpStyle::max_width`.
+    pub fn get_max_width(&self) -> usize {
+        self.max_width.unwrap_or_else(|| {
+            default_max_width(std::env::var("COLUMNS").ok().as_deref(), terminal_width)
+        })
+    }
+}
+
+#[cfg(feature = "color")]
+fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(width, _)| width.0 as usize)
+}
+
+#[cfg(not(feature = "color"))]
+fn terminal_width() -> Option<usize> {
+    None
+}
+
+// Returns the `COLUMNS` width if valid, or the width of the terminal, or `DEFAULT_MAX_WIDTH`
+fn default_max_width<F: FnOnce() -> Option<usize>>(columns: Option<&str>, terminal_width: F) -> usize {
+    columns
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .filter(|width| *width > 0)
+        .or_else(|| terminal_width().filter(|width| *width > 0))
+        .unwrap_or(DEFAULT_MAX_WIDTH)
+}
+
+impl Default for HelpStyle {
+    #[inline]
+    fn default() -> Self {
+        HelpStyle {
+            types: true,
+            possible_values: false,
+            default_values: true,
+            env: true,
+            repeated: true,
+            max_width: None,
+        }
+    }
+}
+
 // TODO: Add help and usage for global options
 
 // Provides a help message for the command
@@ -76,170 +298,691 @@ pub fn command_help(
     command: &Command,
     after_help_message: bool,
 ) {
+    write_command_help(buf, context, command, after_help_message)
+        .expect("failed to write the help message")
+}
+
+// Provides a usage message for the command
+#[doc(hidden)]
+pub fn command_usage(
+    buf: &mut String,
+    context: &Context,
+    command: &Command,
+    after_help_message: bool,
+) {
+    write_command_usage(buf, context, command, after_help_message)
+        .expect("failed to write the usage message")
+}
+
+/// Writes the help message of the given command.
+///
+/// Any `std::fmt::Write` can be used as output, to write into a `std::io::Write`
+/// wrap it in an [`IoWrite`].
+///
+/// # Example
+/// ```
+/// use clapi::{Command, Context};
+/// use clapi::help::{write_command_help, IoWrite};
+///
+/// let context = Context::new(Command::new("MyApp").description("My application"));
+/// let mut stdout = IoWrite::new(std::io::stdout());
+/// write_command_help(&mut stdout, &context, context.root(), true).unwrap();
+/// ```
+pub fn write_command_help<W: Write>(
+    w: &mut W,
+    context: &Context,
+    command: &Command,
+    after_help_message: bool,
+) -> fmt::Result {
     // If the command have a `help` message use that instead
     if let Some(msg) = command.get_help() {
-        buf.push_str(msg);
-        return;
+        return w.write_str(msg);
+    }
+
+    // Command name and version, subcommands without a version use the version of its parent
+    match context.command_version(command) {
+        Some(version) => writeln!(w, "{} {}", command.get_name(), version)?,
+        None => writeln!(w, "{}", command.get_name())?,
     }
 
-    // Command name
-    writeln!(buf, "{}", command.get_name()).unwrap();
+    let max_width = context.help_style().get_max_width();
 
     // Command description
     if let Some(description) = command.get_description() {
-        write_indent(buf);
-        writeln!(buf, "{}", description).unwrap();
+        write_indent(w)?;
+        writeln!(w, "{}", wrap_text(description, INDENT.len(), max_width))?;
     }
 
     // Number of no-hidden options and subcommands
     let option_count = count_options(command.get_options());
-    let subcommand_count = count_subcommands(&command);
+    let subcommand_count = count_subcommands(command);
+
+    // Text before the usage
+    if let Some(before_help) = command.get_before_help() {
+        writeln!(w)?;
+        writeln!(w, "{}", before_help)?;
+    }
 
     // Command usage
     // Write into the buffer the command usage
-    command_usage(buf, context, command, false);
+    write_command_usage(w, context, command, false)?;
 
-    // Command Options
+    // Command Options, the dotted options like `--db.host` are grouped by its prefix
     if option_count > 0 {
-        writeln!(buf).unwrap();
-        writeln!(buf, "OPTIONS:").unwrap();
+        let (options, groups) = group_dotted_options(command.get_options());
 
-        let width = calculate_required_options_width(context, command, true);
-        for option in command.get_options().iter().filter(|o| !o.is_hidden()) {
-            write_indent(buf);
-            if width > MAX_WIDTH {
-                writeln!(
-                    buf,
-                    "{}",
-                    option_to_string(context, option, Align::Column, true)
-                )
-                .unwrap();
-            } else {
-                writeln!(
-                    buf,
-                    "{}",
-                    option_to_string(context, option, Align::Row(width), true)
-                )
-                .unwrap();
-            }
+        if count_options(&options) > 0 {
+            writeln!(w)?;
+            writeln!(w, "OPTIONS:")?;
+            write_options(w, context, &options)?;
         }
 
-        // Remove the last newline of the column
-        if width > MAX_WIDTH {
-            buf.pop();
+        for (prefix, options) in groups.iter().filter(|(_, options)| count_options(options) > 0) {
+            writeln!(w)?;
+            writeln!(w, "{} OPTIONS:", prefix.to_uppercase())?;
+            write_options(w, context, options)?;
         }
     }
 
+    // Context global options, except the ones replaced by the command options
+    let global_options = context
+        .global_options()
+        .iter()
+        .filter(|o| !command.get_options().contains(o.get_name()))
+        .cloned()
+        .collect::<OptionList>();
+
+    if count_options(&global_options) > 0 {
+        writeln!(w)?;
+        writeln!(w, "GLOBAL OPTIONS:")?;
+        write_options(w, context, &global_options)?;
+    }
+
     // Command Subcommands
     if subcommand_count > 0 {
-        writeln!(buf).unwrap();
-        writeln!(buf, "SUBCOMMANDS:").unwrap();
+        writeln!(w)?;
+        writeln!(w, "SUBCOMMANDS:")?;
 
-        let width = calculate_required_subcommands_width(command);
+        // Takes what is needed from each subcommand in a single pass
+        let entries = command
+            .get_subcommands()
+            .filter(|c| !c.is_hidden())
+            .map(SubcommandEntry::new)
+            .collect::<Vec<SubcommandEntry<'_>>>();
+
+        let width = calculate_entries_width(&entries);
+        let mut subcommands = entries.iter().peekable();
 
-        for command in command.get_subcommands().filter(|c| !c.is_hidden()) {
-            write_indent(buf);
+        while let Some(entry) = subcommands.next() {
+            write_indent(w)?;
             if width > MAX_WIDTH {
-                writeln!(buf, "{}", command_to_string(command, Align::Column)).unwrap();
+                let s = entry.format_wrapped(Align::Column, max_width);
+
+                // The column already ends with a newline, except the last one
+                if subcommands.peek().is_some() {
+                    writeln!(w, "{}", s)?;
+                } else {
+                    write!(w, "{}", s)?;
+                }
             } else {
-                writeln!(buf, "{}", command_to_string(command, Align::Row(width))).unwrap();
+                writeln!(w, "{}", entry.format_wrapped(Align::Row(width), max_width))?;
             }
         }
+    }
 
-        // Remove the last newline of the column
-        if width > MAX_WIDTH {
-            buf.pop();
+    // Command examples
+    if !command.get_examples().is_empty() {
+        writeln!(w)?;
+        writeln!(w, "EXAMPLES:")?;
+
+        for (command_line, description) in command.get_examples() {
+            write_indent(w)?;
+            writeln!(w, "{}", command_line)?;
+            write_indent(w)?;
+            write_indent(w)?;
+            writeln!(w, "{}", wrap_text(description, INDENT.len() * 2, max_width))?;
         }
     }
 
+    // Text at the end of the help
+    if let Some(after_help) = command.get_after_help() {
+        writeln!(w)?;
+        writeln!(w, "{}", after_help)?;
+    }
+
     if after_help_message {
         if let Some(msg) = get_after_help_message(context) {
-            writeln!(buf).unwrap();
-            writeln!(buf, "{}", msg).unwrap();
+            writeln!(w)?;
+            writeln!(w, "{}", msg)?;
         }
     }
+
+    Ok(())
 }
 
-// Provides a usage message for the command
-#[doc(hidden)]
-pub fn command_usage(
-    buf: &mut String,
+// Writes the no-hidden options in rows, or in columns if don't fit in the max width
+fn write_options<W: Write>(w: &mut W, context: &Context, options: &OptionList) -> fmt::Result {
+    let width = calculate_options_width(context, options, true);
+    let mut options = options
+        .iter_sorted()
+        .filter(|o| !o.is_hidden())
+        .peekable();
+
+    while let Some(option) = options.next() {
+        write_indent(w)?;
+        if width > MAX_WIDTH {
+            let s = option_to_string(context, option, Align::Column, true);
+
+            // The column already ends with a newline, except the last one
+            if options.peek().is_some() {
+                writeln!(w, "{}", s)?;
+            } else {
+                write!(w, "{}", s)?;
+            }
+        } else {
+            writeln!(
+                w,
+                "{}",
+                option_to_string(context, option, Align::Row(width), true)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the usage message of the given command.
+///
+/// Any `std::fmt::Write` can be used as output, to write into a `std::io::Write`
+/// wrap it in an [`IoWrite`].
+pub fn write_command_usage<W: Write>(
+    w: &mut W,
     context: &Context,
     command: &Command,
     after_help_message: bool,
-) {
+) -> fmt::Result {
     // Writes the usage from the `Command` if any
     if let Some(usage) = command.get_usage() {
-        writeln!(buf).unwrap();
-        writeln!(buf, "USAGE:").unwrap();
-        buf.write_str(usage).unwrap();
-        return;
+        writeln!(w)?;
+        writeln!(w, "USAGE:")?;
+        return w.write_str(usage);
+    }
+
+    // The usage only depends on the command and the prefixes of the context, so is computed once
+    let usage = command.get_or_init_usage(|| synthesize_usage(context, command));
+    if !usage.is_empty() {
+        writeln!(w)?;
+        writeln!(w, "USAGE:")?;
+        w.write_str(usage)?;
     }
 
+    if after_help_message {
+        // After help message
+        if let Some(msg) = get_after_help_message(context) {
+            writeln!(w)?;
+            writeln!(w, "{}", msg)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Generates the usage lines of the command like: `MyApp [OPTIONS] [ARGS]...`
+pub(crate) fn synthesize_usage(context: &Context, command: &Command) -> String {
+    let mut usage = String::new();
+
     // Number of no-hidden options and subcommands
     let option_count = count_options(command.get_options());
-    let subcommand_count = count_subcommands(&command);
+    let subcommand_count = count_subcommands(command);
 
-    if command.take_args() || subcommand_count > 0 || option_count > 0 {
-        writeln!(buf).unwrap();
-        writeln!(buf, "USAGE:").unwrap();
+    // command [OPTIONS] [ARGS]...
+    if command.take_args() || option_count > 0 {
+        usage.push_str(INDENT);
+        usage.push_str(command.get_name());
 
-        // command [OPTIONS] [ARGS]...
-        if command.take_args() || option_count > 0 {
-            write_indent(buf);
-            write!(buf, "{}", command.get_name()).unwrap();
+        if option_count > 1 {
+            if option_count == 1 {
+                usage.push_str(" [OPTION]");
+            } else {
+                usage.push_str(" [OPTIONS]");
+            }
+        }
 
-            if option_count > 1 {
-                if option_count == 1 {
-                    write!(buf, " [OPTION]").unwrap();
-                } else {
-                    write!(buf, " [OPTIONS]").unwrap();
-                }
+        // The required options are written with its arguments, like: `--replace <FROM> <TO>`
+        let name_prefix = context.name_prefixes().next().unwrap();
+        for option in command.get_options().iter_sorted() {
+            if option.is_hidden() || !option.is_required() {
+                continue;
             }
 
-            for arg in command.get_args() {
-                let arg_name = arg.get_name().to_uppercase();
-                if arg.get_values_count().max_or_default() > 1 {
-                    write!(buf, " [{}]...", arg_name).unwrap();
-                } else {
-                    write!(buf, " [{}] ", arg_name).unwrap();
-                }
+            usage.push_str(&format!(" {}{}", name_prefix, option.get_name()));
+
+            let display_args = DisplayArgs {
+                valid_values: !context.help_style().is_possible_values_shown(),
+                ..DisplayArgs::default()
+            };
+
+            if let Some(args) = args_to_string(option.get_args(), display_args) {
+                usage.push_str(&format!(" {}", args));
             }
+        }
 
-            writeln!(buf).unwrap();
+        // The arguments take its values in declaration order, so `display_order` is not used here
+        for arg in command.get_args() {
+            let arg_name = arg_placeholder(arg).to_uppercase();
+            if arg.get_values_count().max_or_default() > 1 {
+                usage.push_str(&format!(" [{}]...", arg_name));
+            } else {
+                usage.push_str(&format!(" [{}] ", arg_name));
+            }
         }
 
-        // command [SUBCOMMAND] [OPTIONS] [ARGS]...
-        if subcommand_count > 0 {
-            write_indent(buf);
-            write!(buf, "{} [SUBCOMMAND]", command.get_name()).unwrap();
+        usage.push('\n');
+    }
 
-            if command
-                .get_subcommands()
-                .any(|c| count_options(c.get_options()) > 0)
-            {
-                write!(buf, " [OPTIONS]").unwrap();
-            }
+    // command [SUBCOMMAND] [OPTIONS] [ARGS]...
+    if subcommand_count > 0 {
+        usage.push_str(INDENT);
+        usage.push_str(command.get_name());
+        usage.push_str(" [SUBCOMMAND]");
+
+        if command
+            .get_subcommands()
+            .any(|c| count_options(c.get_options()) > 0)
+        {
+            usage.push_str(" [OPTIONS]");
+        }
+
+        if command
+            .get_subcommands()
+            .filter(|c| !c.is_hidden())
+            .any(|c| c.take_args())
+        {
+            usage.push_str(" [ARGS]");
+        }
+
+        usage.push('\n');
+    }
 
-            if command
-                .get_subcommands()
-                .filter(|c| !c.is_hidden())
-                .any(|c| c.take_args())
-            {
-                write!(buf, " [ARGS]").unwrap();
+    usage
+}
+
+// The usage lines of the command, the subcommands are prefixed with the names of its parents
+pub(crate) fn usage_lines(context: &Context, command: &Command, parents: &[&str]) -> Vec<String> {
+    let usage = match command.get_usage() {
+        Some(usage) => usage,
+        None => command.get_or_init_usage(|| synthesize_usage(context, command)),
+    };
+
+    let mut lines = usage
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            if parents.is_empty() || !line.starts_with(command.get_name()) {
+                line.to_owned()
+            } else {
+                format!("{} {}", parents.join(" "), line)
             }
+        })
+        .collect::<Vec<String>>();
+
+    // Commands without options, args or subcommands have no usage
+    if lines.is_empty() {
+        lines.push(
+            parents
+                .iter()
+                .copied()
+                .chain(std::iter::once(command.get_name()))
+                .collect::<Vec<&str>>()
+                .join(" "),
+        );
+    }
+
+    lines
+}
+
+/// Returns the documentation of the given `Command` and its subcommands in Markdown.
+///
+/// Each command is a section with its description, a usage code block, a table
+/// of its options and arguments and a table of its examples, the subcommands are nested sections.
+/// Hidden options and subcommands are not included.
+///
+/// The options are written using the prefixes of the `Context`, and its descriptions
+/// are followed by the hints of the `HelpStyle`, except the default and possible values
+/// which have its own columns.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandOption, Context};
+/// use clapi::help::to_markdown;
+///
+/// let context = Context::new(Command::new("MyApp")
+///     .description("An app")
+///     .option(CommandOption::new("verbose").alias("v").description("Shows more output")));
+///
+/// let markdown = to_markdown(&context, context.root());
+/// assert!(markdown.starts_with("## MyApp\n\nAn app\n"));
+/// assert!(markdown.contains("| `--verbose` | `-v` | Shows more output |  |  |\n"));
+/// ```
+pub fn to_markdown(context: &Context, command: &Command) -> String {
+    let mut buf = String::new();
+    write_markdown(&mut buf, context, command, &mut Vec::new())
+        .expect("failed to write the markdown");
+    buf
+}
+
+// Writes the section of the command and its subcommands, `parents` are the names of the commands from the root
+fn write_markdown<'a>(
+    w: &mut String,
+    context: &Context,
+    command: &'a Command,
+    parents: &mut Vec<&'a str>,
+) -> fmt::Result {
+    // The root is `##` and each level of subcommands adds a `#`, up to `######`
+    let heading = "#".repeat(cmp::min(parents.len() + 2, 6));
+    let path = parents
+        .iter()
+        .copied()
+        .chain(std::iter::once(command.get_name()))
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    writeln!(w, "{} {}", heading, path)?;
+
+    if let Some(description) = command.get_description() {
+        writeln!(w)?;
+        writeln!(w, "{}", description)?;
+    }
+
+    writeln!(w)?;
+    writeln!(w, "```text")?;
+    for line in usage_lines(context, command, parents) {
+        writeln!(w, "{}", line)?;
+    }
+    writeln!(w, "```")?;
+
+    // OPTIONS
+    let options = command
+        .get_options()
+        .iter_sorted()
+        .filter(|o| !o.is_hidden())
+        .collect::<Vec<&CommandOption>>();
+
+    if !options.is_empty() {
+        let name_prefix = context.name_prefixes().next().unwrap();
+        let alias_prefix = context.alias_prefixes().next().unwrap();
+
+        // The default and possible values are in its own columns
+        let style = context
+            .help_style()
+            .clone()
+            .show_default_values(false)
+            .show_possible_values(false);
+
+        writeln!(w)?;
+        writeln!(w, "**Options:**")?;
+        writeln!(w)?;
+        writeln!(w, "| Option | Alias | Description | Default | Values |")?;
+        writeln!(w, "|--------|-------|-------------|---------|--------|")?;
+
+        for option in options {
+            let aliases = option
+                .get_aliases()
+                .map(|alias| format!("`{}{}`", alias_prefix, alias))
+                .collect::<Vec<String>>()
+                .join(", ");
 
-            writeln!(buf).unwrap();
+            // Option description followed by its hints
+            let description = option
+                .get_description()
+                .into_iter()
+                .map(str::to_owned)
+                .chain(option_hints_with_style(context, &style, option))
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            writeln!(
+                w,
+                "| `{}{}` | {} | {} | {} | {} |",
+                name_prefix,
+                option.get_name(),
+                aliases,
+                markdown_cell(&description),
+                markdown_values(option.get_args().iter().flat_map(|a| a.get_default_values())),
+                markdown_values(option.get_args().iter().flat_map(|a| a.get_valid_values())),
+            )?;
         }
     }
 
-    if after_help_message {
-        // After help message
-        if let Some(msg) = get_after_help_message(context) {
-            writeln!(buf).unwrap();
-            writeln!(buf, "{}", msg).unwrap();
+    // ARGS
+    if command.take_args() {
+        writeln!(w)?;
+        writeln!(w, "**Arguments:**")?;
+        writeln!(w)?;
+        writeln!(w, "| Argument | Description | Default | Values |")?;
+        writeln!(w, "|----------|-------------|---------|--------|")?;
+
+        for arg in command.get_args().iter_sorted() {
+            // Argument description followed by its type
+            let description = arg
+                .get_description()
+                .map(str::to_owned)
+                .into_iter()
+                .chain(value_type(arg)
+                    .filter(|_| context.help_style().is_types_shown())
+                    .map(|value_type| format!("[type: {}]", value_type)))
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            writeln!(
+                w,
+                "| `<{}>` | {} | {} | {} |",
+                arg.get_name(),
+                markdown_cell(&description),
+                markdown_values(arg.get_default_values()),
+                markdown_values(arg.get_valid_values()),
+            )?;
         }
     }
+
+    // EXAMPLES
+    if !command.get_examples().is_empty() {
+        writeln!(w)?;
+        writeln!(w, "**Examples:**")?;
+        writeln!(w)?;
+        writeln!(w, "| Example | Description |")?;
+        writeln!(w, "|---------|-------------|")?;
+
+        for (command_line, description) in command.get_examples() {
+            writeln!(
+                w,
+                "| `{}` | {} |",
+                markdown_cell(command_line),
+                markdown_cell(description)
+            )?;
+        }
+    }
+
+    parents.push(command.get_name());
+    for subcommand in command.get_subcommands().filter(|c| !c.is_hidden()) {
+        writeln!(w)?;
+        write_markdown(w, context, subcommand, parents)?;
+    }
+    parents.pop();
+
+    Ok(())
+}
+
+// Escapes the pipes and the line breaks of a table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+// Formats the values as a list of code spans, like: `red`, `green`
+fn markdown_values<'a, I: IntoIterator<Item = &'a String>>(values: I) -> String {
+    values
+        .into_iter()
+        .map(|value| format!("`{}`", markdown_cell(value)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Returns the help of the given `Command` and its subcommands as a JSON object,
+/// this is the output of `--help=json`.
+///
+/// Each command have its `name`, `description`, `usage`, `options`, `args`, `examples`
+/// and `subcommands`, the arguments include the name of its `type` like in the `[type: ...]` hint
+/// or `null` if unknown. Hidden options and subcommands are not included.
+///
+/// # Example
+/// ```
+/// use clapi::{Argument, Command, CommandOption, Context};
+/// use clapi::help::to_json;
+///
+/// let context = Context::new(Command::new("MyApp")
+///     .description("An app")
+///     .option(CommandOption::new("times").alias("t").arg(Argument::new())));
+///
+/// let json = to_json(&context, context.root());
+/// assert!(json.starts_with("{\n  \"name\": \"MyApp\",\n  \"description\": \"An app\",\n"));
+/// assert!(json.contains("\"names\": [\n        \"-t\",\n        \"--times\"\n      ]"));
+/// ```
+pub fn to_json(context: &Context, command: &Command) -> String {
+    let mut buf = Vec::new();
+    command_json(context, command, &mut Vec::new())
+        .write(&mut buf, 0)
+        .expect("failed to write the json");
+    String::from_utf8(buf).expect("invalid utf-8 json")
+}
+
+// The JSON of the command and its subcommands, `parents` are the names of the commands from the root
+fn command_json<'a>(context: &Context, command: &'a Command, parents: &mut Vec<&'a str>) -> Json {
+    fn string(value: &str) -> Json {
+        Json::String(value.to_owned())
+    }
+
+    fn strings<'a, I: IntoIterator<Item = &'a String>>(values: I) -> Json {
+        Json::Array(values.into_iter().map(|s| string(s)).collect())
+    }
+
+    fn arg_json(arg: &Argument) -> Json {
+        let count = arg.get_values_count();
+
+        Json::Object(vec![
+            ("name", string(arg.get_name())),
+            ("description", arg.get_description().map_or(Json::Null, string)),
+            ("type", value_type(arg).map_or(Json::Null, string)),
+            ("min", count.min().map_or(Json::Null, Json::Number)),
+            ("max", count.max().map_or(Json::Null, Json::Number)),
+            ("default", strings(arg.get_default_values())),
+            ("values", strings(arg.get_valid_values())),
+        ])
+    }
+
+    let name_prefix = context.name_prefixes().next().unwrap();
+    let alias_prefix = context.alias_prefixes().next().unwrap();
+
+    let options = command
+        .get_options()
+        .iter_sorted()
+        .filter(|o| !o.is_hidden())
+        .map(|option| {
+            let names = option
+                .get_aliases()
+                .map(|alias| format!("{}{}", alias_prefix, alias))
+                .chain(std::iter::once(format!("{}{}", name_prefix, option.get_name())))
+                .map(Json::String)
+                .collect();
+
+            Json::Object(vec![
+                ("names", Json::Array(names)),
+                ("description", option.get_description().map_or(Json::Null, string)),
+                ("required", Json::Bool(option.is_required())),
+                ("env", context.env_var_name(option).map_or(Json::Null, Json::String)),
+                ("args", Json::Array(option.get_args().iter().map(arg_json).collect())),
+            ])
+        })
+        .collect();
+
+    let args = command
+        .get_args()
+        .iter()
+        .map(arg_json)
+        .collect();
+
+    let examples = command
+        .get_examples()
+        .iter()
+        .map(|(command_line, description)| {
+            Json::Object(vec![("command", string(command_line)), ("description", string(description))])
+        })
+        .collect();
+
+    let usage = usage_lines(context, command, parents)
+        .into_iter()
+        .map(Json::String)
+        .collect();
+
+    parents.push(command.get_name());
+    let subcommands = command
+        .get_subcommands()
+        .filter(|c| !c.is_hidden())
+        .map(|subcommand| command_json(context, subcommand, parents))
+        .collect();
+    parents.pop();
+
+    Json::Object(vec![
+        ("name", string(command.get_name())),
+        ("description", command.get_description().map_or(Json::Null, string)),
+        ("usage", Json::Array(usage)),
+        ("options", Json::Array(options)),
+        ("args", Json::Array(args)),
+        ("examples", Json::Array(examples)),
+        ("subcommands", Json::Array(subcommands)),
+    ])
+}
+
+/// An adapter to use a `std::io::Write` as a `std::fmt::Write`.
+///
+/// Because `std::fmt::Error` don't carry any information,
+/// the last `std::io::Error` can be retrieved using [`IoWrite::take_error`].
+#[derive(Debug)]
+pub struct IoWrite<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoWrite<W> {
+    /// Constructs a new `IoWrite` from the given `std::io::Write`.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        IoWrite { inner, error: None }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Takes the last `std::io::Error` that occurred, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::Write> Write for IoWrite<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
 }
 
 // Use '' for see more information about a command
@@ -270,8 +1013,35 @@ pub(crate) fn get_after_help_message(context: &Context) -> Option<String> {
 
 // Add indentation to the buffer
 #[inline]
-fn write_indent(buf: &mut String) {
-    buf.push_str(INDENT)
+fn write_indent<W: Write>(w: &mut W) -> fmt::Result {
+    w.write_str(INDENT)
+}
+
+// Splits the options without a dotted name from the dotted options grouped by its prefix,
+// like `db` for `db.host`, the groups are in order of appearance
+fn group_dotted_options(options: &OptionList) -> (OptionList, Vec<(&str, OptionList)>) {
+    let mut undotted = OptionList::new();
+    let mut groups: Vec<(&str, OptionList)> = Vec::new();
+
+    for option in options.iter() {
+        let group = match option.get_name().rfind('.') {
+            Some(index) => {
+                let prefix = &option.get_name()[..index];
+                match groups.iter().position(|(p, _)| *p == prefix) {
+                    Some(pos) => &mut groups[pos].1,
+                    None => {
+                        groups.push((prefix, OptionList::new()));
+                        &mut groups.last_mut().unwrap().1
+                    }
+                }
+            }
+            None => &mut undotted,
+        };
+
+        group.add_or_replace(option.clone());
+    }
+
+    (undotted, groups)
 }
 
 // Number of no-hidden options
@@ -287,7 +1057,8 @@ fn count_subcommands(parent: &Command) -> usize {
 // Utilities for formatting command, options and args
 #[doc(hidden)]
 pub mod utils {
-    use crate::{ArgumentList, Command, CommandOption, Context};
+    use crate::help::HelpStyle;
+    use crate::{Argument, ArgumentList, Command, CommandOption, Context, OptionList};
     use std::cmp;
 
     // Min width of the name
@@ -337,6 +1108,7 @@ pub mod utils {
         pub grouping: (char, char),  // ('<', '>')
         pub only_name: bool,         // false
         pub delimiter: char,         // |
+        pub valid_values: bool,      // true
     }
 
     impl DisplayArgs {
@@ -354,6 +1126,7 @@ pub mod utils {
                 grouping: ('<', '>'),
                 only_name: false,
                 delimiter: '|',
+                valid_values: true,
             }
         }
     }
@@ -386,7 +1159,12 @@ pub mod utils {
 
         // Option args
         let mut args = if include_args && option.get_args().len() > 0 {
-            args_to_string(option.get_args(), DisplayArgs::default())
+            let display_args = DisplayArgs {
+                valid_values: !context.help_style().is_possible_values_shown(),
+                ..DisplayArgs::default()
+            };
+
+            args_to_string(option.get_args(), display_args)
         } else {
             None
         };
@@ -396,9 +1174,23 @@ pub mod utils {
             args.insert(0, ' ');
         }
 
+        // Option description followed by its hints
+        let description = match (option.get_description(), option_hints(context, option)) {
+            (Some(description), Some(hints)) => Some(format!("{} {}", description, hints)),
+            (None, Some(hints)) => Some(hints),
+            (description, None) => description.map(|s| s.to_owned()),
+        };
+
+        // The descriptions are wrapped and aligned under its column
+        let max_width = context.help_style().get_max_width();
+        let description = description.map(|s| match align {
+            Align::Row(width) => wrap_text(&s, super::INDENT.len() + width, max_width),
+            Align::Column => wrap_text(&s, COLUMN_DESCRIPTION_PADDING, max_width),
+        });
+
         match align {
             Align::Row(width) => {
-                if let Some(description) = option.get_description() {
+                if let Some(description) = &description {
                     format!(
                         "{:width$}{}",
                         // format_args! is not working with the width
@@ -412,7 +1204,7 @@ pub mod utils {
             }
             Align::Column => {
                 // The next column
-                if let Some(description) = option.get_description() {
+                if let Some(description) = &description {
                     format!(
                         // We add a left-padding of 6 spaces
                         "{}{:padding$}{}\n",
@@ -429,37 +1221,231 @@ pub mod utils {
         }
     }
 
-    // version              Shows the version
-    pub fn command_to_string(command: &Command, align: Align) -> String {
-        match align {
-            Align::Row(width) => {
-                if let Some(description) = command.get_description() {
-                    format!(
-                        "{:width$} {}",
-                        command.get_name(),
-                        description,
-                        width = width
-                    )
-                } else {
-                    command.get_name().to_owned()
+    // [type: integer] [possible values: 1, 2] [default: 1] [env: MYAPP_LEVEL]
+    //
+    // All the hints of the options are written here to keep them in the same order.
+    pub fn option_hints(context: &Context, option: &CommandOption) -> Option<String> {
+        option_hints_with_style(context, context.help_style(), option)
+    }
+
+    // The hints of the option using the given style instead of the style of the context
+    pub fn option_hints_with_style(
+        context: &Context,
+        style: &HelpStyle,
+        option: &CommandOption,
+    ) -> Option<String> {
+        let args = option.get_args();
+        let mut hints = Vec::new();
+
+        if style.is_repeated_shown() && option.is_counted() {
+            hints.push("(can be repeated)".to_owned());
+        }
+
+        if style.is_types_shown() {
+            for arg in args.iter() {
+                if let Some(value_type) = value_type(arg) {
+                    if args.len() > 1 {
+                        hints.push(format!("[type for {}: {}]", arg.get_name(), value_type));
+                    } else {
+                        hints.push(format!("[type: {}]", value_type));
+                    }
                 }
             }
-            Align::Column => {
-                if let Some(description) = command.get_description() {
-                    format!(
-                        "{}\n{:padding$}{}\n",
-                        command.get_name(),
-                        "",
-                        description,
-                        padding = COLUMN_DESCRIPTION_PADDING
-                    )
+        }
+
+        if style.is_possible_values_shown() {
+            for arg in args.iter().filter(|arg| !arg.get_valid_values().is_empty()) {
+                let values = arg.get_valid_values().join(", ");
+                if args.len() > 1 {
+                    hints.push(format!("[possible values for {}: {}]", arg.get_name(), values));
                 } else {
-                    format!("{}\n", command.get_name())
+                    hints.push(format!("[possible values: {}]", values));
+                }
+            }
+        }
+
+        if style.is_default_values_shown() {
+            for arg in args.iter().filter(|arg| arg.has_default_values()) {
+                hints.push(format!("[default: {}]", arg.get_default_values().join(", ")));
+            }
+        }
+
+        if style.is_env_shown() {
+            if let Some(env) = context.env_var_name(option) {
+                hints.push(format!("[env: {}]", env));
+            }
+        }
+
+        if hints.is_empty() {
+            None
+        } else {
+            Some(hints.join(" "))
+        }
+    }
+
+    // Returns the name of the type expected by the validator of the argument, if any
+    #[cfg(feature = "typing")]
+    pub fn value_type(arg: &Argument) -> Option<&'static str> {
+        use std::any::TypeId;
+
+        let r#type = arg.expected_type()?;
+        let is_any = |types: &[TypeId]| types.contains(&r#type.id());
+
+        let name = if is_any(&[
+            TypeId::of::<i8>(), TypeId::of::<i16>(), TypeId::of::<i32>(), TypeId::of::<i64>(),
+            TypeId::of::<i128>(), TypeId::of::<isize>(), TypeId::of::<u8>(), TypeId::of::<u16>(),
+            TypeId::of::<u32>(), TypeId::of::<u64>(), TypeId::of::<u128>(), TypeId::of::<usize>(),
+        ]) {
+            "integer"
+        } else if is_any(&[TypeId::of::<f32>(), TypeId::of::<f64>()]) {
+            "number"
+        } else if is_any(&[TypeId::of::<bool>()]) {
+            "boolean"
+        } else if is_any(&[TypeId::of::<String>()]) {
+            "string"
+        } else if is_any(&[TypeId::of::<std::path::PathBuf>()]) {
+            "path"
+        } else {
+            // Types like `std::net::IpAddr` are shown without its path
+            let name = r#type.name();
+            match name.contains('<') {
+                true => name,
+                false => name.rsplit("::").next().unwrap_or(name),
+            }
+        };
+
+        Some(name)
+    }
+
+    // Returns the name of the type expected by the validator of the argument, if any
+    #[cfg(not(feature = "typing"))]
+    pub fn value_type(_: &Argument) -> Option<&'static str> {
+        None
+    }
+
+    // The values of a subcommand displayed in the help, taken once per subcommand
+    pub struct SubcommandEntry<'a> {
+        names: String,
+        description: Option<&'a str>,
+    }
+
+    impl<'a> SubcommandEntry<'a> {
+        pub fn new(command: &'a Command) -> Self {
+            SubcommandEntry {
+                names: command_names(command),
+                description: command.get_summary(),
+            }
+        }
+
+        // version              Shows the version
+        // install, i           Installs a package
+        pub fn format(&self, align: Align) -> String {
+            self.format_wrapped(align, usize::MAX)
+        }
+
+        // Formats the entry wrapping the description to the max width
+        pub fn format_wrapped(&self, align: Align, max_width: usize) -> String {
+            let names = &self.names;
+            let description = self.description.map(|s| match align {
+                // The row starts after the indent and the names are followed by a space
+                Align::Row(width) => wrap_text(s, super::INDENT.len() + width + 1, max_width),
+                Align::Column => wrap_text(s, COLUMN_DESCRIPTION_PADDING, max_width),
+            });
+
+            match align {
+                Align::Row(width) => {
+                    if let Some(description) = description {
+                        format!(
+                            "{:width$} {}",
+                            names,
+                            description,
+                            width = width
+                        )
+                    } else {
+                        names.clone()
+                    }
+                }
+                Align::Column => {
+                    if let Some(description) = description {
+                        format!(
+                            "{}\n{:padding$}{}\n",
+                            names,
+                            "",
+                            description,
+                            padding = COLUMN_DESCRIPTION_PADDING
+                        )
+                    } else {
+                        format!("{}\n", names)
+                    }
                 }
             }
         }
     }
 
+    // Wraps the text in lines of at most `max_width` chars, the text is written from the `indent`
+    // column and the next lines are indented to that column, including the lines of the text.
+    pub fn wrap_text(text: &str, indent: usize, max_width: usize) -> String {
+        // Narrow columns still have room for a few words
+        const MIN_WIDTH: usize = 20;
+
+        let width = cmp::max(max_width.saturating_sub(indent), MIN_WIDTH);
+        let mut result = String::with_capacity(text.len());
+
+        for (index, line) in text.lines().enumerate() {
+            if index > 0 {
+                result.push('\n');
+                result.push_str(&" ".repeat(indent));
+            }
+
+            // Lines that fit are kept as they are
+            if line.chars().count() <= width {
+                result.push_str(line);
+                continue;
+            }
+
+            let mut line_len = 0;
+            for word in line.split_whitespace() {
+                let word_len = word.chars().count();
+
+                if line_len > 0 && line_len + 1 + word_len > width {
+                    result.push('\n');
+                    result.push_str(&" ".repeat(indent));
+                    line_len = 0;
+                }
+
+                if line_len > 0 {
+                    result.push(' ');
+                    line_len += 1;
+                }
+
+                result.push_str(word);
+                line_len += word_len;
+            }
+        }
+
+        result
+    }
+
+    pub fn command_to_string(command: &Command, align: Align) -> String {
+        SubcommandEntry::new(command).format(align)
+    }
+
+    // The name of the command followed by its visible aliases, like: `install, i`
+    fn command_names(command: &Command) -> String {
+        std::iter::once(command.get_name())
+            .chain(command.get_aliases().map(|s| s.as_str()))
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+
+    // The placeholder of the value hint or the name of the argument
+    pub fn arg_placeholder(arg: &Argument) -> &str {
+        match arg.get_value_hint() {
+            Some(hint) => hint.placeholder(),
+            None => arg.get_name(),
+        }
+    }
+
     // <ARG1> <ARG2>
     pub fn args_to_string(args: &ArgumentList, display_args: DisplayArgs) -> Option<String> {
         if args.is_empty() {
@@ -470,12 +1456,13 @@ pub mod utils {
                 grouping,
                 only_name,
                 delimiter,
+                valid_values,
             } = display_args;
 
             match args.len() {
                 1 => {
                     let arg = &args[0];
-                    if arg.get_valid_values().len() > 0 || only_name {
+                    if (valid_values && !arg.get_valid_values().is_empty()) || only_name {
                         // --option <VALUE1|VALUE2|VALUE2>
                         let buf = &mut [0; 4];
                         let str_delimiter = delimiter.encode_utf8(buf);
@@ -490,7 +1477,7 @@ pub mod utils {
                         // --option <ARG>
                         Some(format!(
                             "{1}{0}{2}",
-                            letter_case.format(arg.get_name()),
+                            letter_case.format(arg_placeholder(arg)),
                             grouping.0,
                             grouping.1
                         ))
@@ -503,7 +1490,7 @@ pub mod utils {
                         .map(|s| {
                             format!(
                                 "{1}{0}{2}",
-                                letter_case.format(s.get_name()),
+                                letter_case.format(arg_placeholder(s)),
                                 grouping.0,
                                 grouping.1
                             )
@@ -523,7 +1510,16 @@ pub mod utils {
         command: &Command,
         include_args: bool,
     ) -> usize {
-        fn args_required_len(option: &CommandOption) -> usize {
+        calculate_options_width(context, command.get_options(), include_args)
+    }
+
+    // Calculates the min width required for display the given options
+    pub fn calculate_options_width(
+        context: &Context,
+        options: &OptionList,
+        include_args: bool,
+    ) -> usize {
+        fn args_required_len(option: &CommandOption, valid_values: bool) -> usize {
             if option.get_args().len() == 0 {
                 0
             } else {
@@ -532,7 +1528,7 @@ pub mod utils {
                 match option.get_args().len() {
                     1 => {
                         if let Some(arg) = option.get_arg() {
-                            if arg.get_valid_values().len() > 0 {
+                            if valid_values && !arg.get_valid_values().is_empty() {
                                 let valid_values_len = arg
                                     .get_valid_values()
                                     .iter()
@@ -545,7 +1541,7 @@ pub mod utils {
                                 valid_values_len + delimiters + GROUPING
                             } else {
                                 // padding + <NAME>
-                                arg.get_name().len() + GROUPING
+                                arg_placeholder(arg).len() + GROUPING
                             }
                         } else {
                             unreachable!()
@@ -555,7 +1551,7 @@ pub mod utils {
                         let args_len = option
                             .get_args()
                             .iter()
-                            .map(|s| s.get_name().len())
+                            .map(|s| arg_placeholder(s).len())
                             .sum::<usize>();
 
                         // padding + <ARG1> + padding + <ARG2> ...
@@ -567,11 +1563,11 @@ pub mod utils {
 
         let name_prefix = context.name_prefixes().next().unwrap();
         let alias_prefix = context.alias_prefixes().next().unwrap();
+        let valid_values = !context.help_style().is_possible_values_shown();
 
         // Here we calculate the max width needed for write the options
         // for that we select the `max` len of: name + aliases + delimiter
-        let total_width = command
-            .get_options()
+        let total_width = options
             .iter()
             .filter(|opt| !opt.is_hidden())
             .fold(0, |width, opt| {
@@ -587,7 +1583,7 @@ pub mod utils {
 
                 // Total length required for the args
                 let args_len = if include_args {
-                    args_required_len(opt)
+                    args_required_len(opt, valid_values)
                 } else {
                     0
                 };
@@ -604,13 +1600,832 @@ pub mod utils {
 
     // Calculates the min width required for display the command subcommands
     pub fn calculate_required_subcommands_width(command: &Command) -> usize {
-        let total_width = command
+        let entries = command
             .get_subcommands()
             .filter(|c| !c.is_hidden())
-            .fold(0, |width, subcommand| {
-                cmp::max(width, subcommand.get_name().len() + MIN_SPACING)
-            });
+            .map(SubcommandEntry::new)
+            .collect::<Vec<SubcommandEntry<'_>>>();
+
+        calculate_entries_width(&entries)
+    }
+
+    // Calculates the min width required for display the given subcommands
+    pub fn calculate_entries_width(entries: &[SubcommandEntry<'_>]) -> usize {
+        let total_width = entries
+            .iter()
+            .fold(0, |width, entry| cmp::max(width, entry.names.len() + MIN_SPACING));
 
         cmp::max(MIN_WIDTH, total_width)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Argument, CommandOption, ValueHint};
+
+    // The help is written with a fixed width, so the tests don't depend on the terminal or `COLUMNS`
+    fn help_style() -> HelpStyle {
+        HelpStyle::new().max_width(100)
+    }
+
+    fn new_context(command: Command) -> Context {
+        Context::builder(command).help_style(help_style()).build()
+    }
+
+    fn test_context() -> Context {
+        new_context(
+            Command::new("MyApp")
+                .description("My application")
+                .option(CommandOption::new("enable").description("Enables something"))
+                .subcommand(Command::new("test").description("Runs the tests")),
+        )
+    }
+
+    #[test]
+    fn default_help_snapshot_test() {
+        use crate::testing::CommandExt;
+        use crate::Argument;
+
+        Command::new("MyApp")
+            .description("My application")
+            .version("1.0")
+            .option(CommandOption::new("verbose").alias("v").description("Shows more output").global(true))
+            .option(
+                CommandOption::new("color")
+                    .description("Color of the output")
+                    .arg(Argument::with_name("color").valid_values(vec!["auto", "always", "never"]).default("auto")),
+            )
+            .subcommand(
+                Command::new("remote")
+                    .description("Manages the remotes. A remote is a repository tracked by this one.")
+                    .subcommand(
+                        Command::new("add")
+                            .description("Adds a remote")
+                            .option(CommandOption::new("fetch").alias("f").description("Fetches the remote"))
+                            .arg(Argument::with_name("name"))
+                            .arg(Argument::with_name("url")),
+                    )
+                    .subcommand(Command::new("remove").description("Removes a remote").arg(Argument::with_name("name"))),
+            )
+            .subcommand(
+                Command::new("log")
+                    .description("Shows the commit logs starting at the current commit")
+                    .summary("Shows the commit logs")
+                    .option(CommandOption::new("max-count").alias("n").arg(Argument::with_name("number")))
+                    .arg(Argument::zero_or_more("paths")),
+            )
+            .assert_help_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/help"));
+    }
+
+    #[test]
+    fn write_command_help_hidden_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("debug-internal").hidden(true))
+            .option(CommandOption::new("verbose"))
+            .subcommand(Command::new("secret").hidden(true))
+            .subcommand(Command::new("visible"));
+
+        let context = new_context(command.clone());
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+        assert!(help.contains("--verbose"));
+        assert!(help.contains("visible"));
+        assert!(!help.contains("debug-internal"));
+        assert!(!help.contains("secret"));
+
+        // Hidden options and subcommands still can be used
+        let result = command.clone().parse_from(vec!["--debug-internal"]).unwrap();
+        assert!(result.options().contains("debug-internal"));
+
+        let result = command.parse_from(vec!["secret"]).unwrap();
+        assert_eq!(result.command_name(), "secret");
+    }
+
+    #[test]
+    fn write_command_help_aliases_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("color").alias("c").hidden_alias("colour"))
+            .subcommand(Command::new("install").alias("i").hidden_alias("add"));
+
+        let context = new_context(command.clone());
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+        assert!(help.contains("install, i"));
+        assert!(help.contains("-c, --color"));
+        assert!(!help.contains("add"));
+        assert!(!help.contains("colour"));
+
+        // Hidden aliases still can be used
+        let result = command.clone().parse_from(vec!["--colour"]).unwrap();
+        assert!(result.options().contains("color"));
+
+        let result = command.parse_from(vec!["add"]).unwrap();
+        assert_eq!(result.command_name(), "install");
+    }
+
+    #[test]
+    fn write_command_help_value_hint_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("config").arg(Argument::new().value_hint(ValueHint::FilePath)))
+            .option(CommandOption::new("connect")
+                .arg(Argument::with_name("host").value_hint(ValueHint::Hostname))
+                .arg(Argument::with_name("port").value_hint(ValueHint::Other("port".to_owned()))))
+            .arg(Argument::with_name("source").value_hint(ValueHint::DirPath))
+            .arg(Argument::zero_or_more("urls").value_hint(ValueHint::Url));
+
+        let context = new_context(command);
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+
+        assert!(help.contains("MyApp [OPTIONS] [DIR]"));
+        assert!(help.contains("[URL]..."));
+        assert!(help.contains("--config <FILE>"));
+        assert!(help.contains("--connect <HOST> <PORT>"));
+        assert!(!help.contains("SOURCE"));
+    }
+
+    #[test]
+    fn write_command_usage_cache_test() {
+        fn usage(command: &Command) -> String {
+            let context = new_context(command.clone());
+            let mut usage = String::new();
+            write_command_usage(&mut usage, &context, command, false).unwrap();
+            usage
+        }
+
+        let command = Command::new("MyApp").subcommand(Command::new("test"));
+        assert_eq!(usage(&command), "\nUSAGE:\n   MyApp [SUBCOMMAND]\n");
+        assert_eq!(usage(&command), "\nUSAGE:\n   MyApp [SUBCOMMAND]\n");
+
+        // The cached usage is discarded when the command changes
+        let mut command = command.arg(Argument::with_name("value"));
+        assert_eq!(usage(&command), "\nUSAGE:\n   MyApp [VALUE] \n   MyApp [SUBCOMMAND]\n");
+
+        let test = command.get_subcommands_mut().next().unwrap();
+        *test = test.clone().arg(Argument::with_name("file"));
+        assert_eq!(usage(&command), "\nUSAGE:\n   MyApp [VALUE] \n   MyApp [SUBCOMMAND] [ARGS]\n");
+    }
+
+    #[test]
+    fn write_command_usage_cache_context_test() {
+        fn usage(context: &Context) -> String {
+            let mut usage = String::new();
+            write_command_usage(&mut usage, context, context.root(), false).unwrap();
+            usage
+        }
+
+        fn paint_usage(context: &Context) -> String {
+            let mut usage = String::new();
+            let paint = context.root().find_subcommand("paint").unwrap();
+            write_command_usage(&mut usage, context, paint, false).unwrap();
+            usage
+        }
+
+        let command = Command::new("MyApp")
+            .option(
+                CommandOption::new("color")
+                    .required(true)
+                    .arg(Argument::with_name("color").valid_values(["red", "blue"])),
+            )
+            .subcommand(Command::new("paint").option(CommandOption::new("brush").required(true)));
+
+        let mut context = new_context(command);
+        assert_eq!(usage(&context), "\nUSAGE:\n   MyApp --color <RED|BLUE>\n   MyApp [SUBCOMMAND] [OPTIONS]\n");
+        assert_eq!(paint_usage(&context), "\nUSAGE:\n   paint --brush\n");
+
+        // The cached usage is discarded when the context changes
+        context.set_name_prefix("/");
+        context.set_help_style(HelpStyle::new().show_possible_values(true));
+        assert_eq!(usage(&context), "\nUSAGE:\n   MyApp /color <COLOR>\n   MyApp [SUBCOMMAND] [OPTIONS]\n");
+        assert_eq!(paint_usage(&context), "\nUSAGE:\n   paint /brush\n");
+
+        // A cloned command don't keep the usage of other context
+        let context = Context::builder(context.root().clone()).name_prefix("+").build();
+        assert_eq!(usage(&context), "\nUSAGE:\n   MyApp +color <RED|BLUE>\n   MyApp [SUBCOMMAND] [OPTIONS]\n");
+    }
+
+    #[test]
+    fn write_command_help_required_option_test() {
+        let context = new_context(
+            Command::new("MyApp").option(
+                CommandOption::new("replace")
+                    .required(true)
+                    .description("Values to replace")
+                    .arg(Argument::with_name("from"))
+                    .arg(Argument::with_name("to")),
+            ),
+        );
+
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+        assert_eq!(
+            help,
+            concat!(
+                "MyApp\n",
+                "\n",
+                "USAGE:\n",
+                "   MyApp --replace <FROM> <TO>\n",
+                "\n",
+                "OPTIONS:\n",
+                "   --replace <FROM> <TO>     Values to replace\n",
+            )
+        );
+    }
+
+    #[test]
+    fn write_command_help_to_io_test() {
+        let context = test_context();
+        let mut expected = String::new();
+        command_help(&mut expected, &context, context.root(), true);
+
+        let mut w = IoWrite::new(Vec::new());
+        write_command_help(&mut w, &context, context.root(), true).unwrap();
+        assert_eq!(String::from_utf8(w.into_inner()).unwrap(), expected);
+    }
+
+    #[test]
+    fn write_command_help_error_test() {
+        struct FailingWriter;
+        impl io::Write for FailingWriter {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let context = test_context();
+        let mut w = IoWrite::new(FailingWriter);
+        assert!(write_command_help(&mut w, &context, context.root(), true).is_err());
+        assert_eq!(w.take_error().unwrap().kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn write_command_help_version_test() {
+        let context = new_context(
+            Command::new("MyApp")
+                .version("1.0")
+                .subcommand(Command::new("server").version("2.0").subcommand(Command::new("start")))
+                .subcommand(Command::new("client")),
+        );
+
+        let help = |names: &[&str]| {
+            let command = names
+                .iter()
+                .fold(context.root(), |command, name| command.find_subcommand(name).unwrap());
+
+            let mut buf = String::new();
+            command_help(&mut buf, &context, command, false);
+            buf.lines().next().unwrap().to_owned()
+        };
+
+        assert_eq!(help(&[]), "MyApp 1.0");
+        assert_eq!(help(&["server"]), "server 2.0");
+        assert_eq!(help(&["server", "start"]), "start 2.0");
+        assert_eq!(help(&["client"]), "client 1.0");
+
+        let context = new_context(Command::new("MyApp").subcommand(Command::new("client")));
+        let mut buf = String::new();
+        command_help(&mut buf, &context, context.root().find_subcommand("client").unwrap(), false);
+        assert!(buf.starts_with("client\n"));
+    }
+
+    #[test]
+    fn write_command_help_examples_test() {
+        let context = new_context(
+            Command::new("MyApp")
+                .example("MyApp sum 1 2 3 --precision=2", "Sum with two decimals")
+                .subcommand(
+                    Command::new("sum").example("MyApp sum 1 2", "Sum two numbers"),
+                ),
+        );
+
+        let mut root_help = String::new();
+        write_command_help(&mut root_help, &context, context.root(), false).unwrap();
+        assert!(root_help.ends_with(
+            "EXAMPLES:\n   MyApp sum 1 2 3 --precision=2\n      Sum with two decimals\n"
+        ));
+        assert!(!root_help.contains("Sum two numbers"));
+
+        let mut sum_help = String::new();
+        let sum = context.root().find_subcommand("sum").unwrap();
+        write_command_help(&mut sum_help, &context, sum, false).unwrap();
+        assert!(sum_help.contains("EXAMPLES:\n   MyApp sum 1 2\n      Sum two numbers\n"));
+        assert!(!sum_help.contains("Sum with two decimals"));
+    }
+
+    #[test]
+    fn write_command_help_before_and_after_help_test() {
+        let context = new_context(
+            Command::new("MyApp")
+                .description("My application")
+                .before_help("NOTE: This app is experimental")
+                .after_help("LICENSE:\n   MIT")
+                .option(CommandOption::new("enable").description("Enables something"))
+                .subcommand(Command::new("test").description("Runs the tests"))
+                .example("MyApp test", "Runs the tests"),
+        );
+
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), true).unwrap();
+
+        let before_help = help.find("NOTE: This app is experimental").unwrap();
+        let after_help = help.find("LICENSE:\n   MIT\n").unwrap();
+        assert!(help.find("My application").unwrap() < before_help);
+        assert!(before_help < help.find("USAGE:").unwrap());
+        assert!(help.find("SUBCOMMANDS:").unwrap() < after_help);
+        assert!(help.find("EXAMPLES:").unwrap() < after_help);
+        assert!(help.contains("   My application\n\nNOTE: This app is experimental\n\nUSAGE:\n"), "{}", help);
+
+        // The subcommands don't inherit the sections
+        let test = context.root().find_subcommand("test").unwrap();
+        let mut help = String::new();
+        write_command_help(&mut help, &context, test, false).unwrap();
+        assert!(!help.contains("NOTE:"));
+        assert!(!help.contains("LICENSE:"));
+    }
+
+    #[test]
+    fn write_command_help_dotted_options_test() {
+        let context = new_context(
+            Command::new("MyApp")
+                .option(CommandOption::new("verbose").description("Shows more output"))
+                .option(CommandOption::new("db.host").description("Database host"))
+                .option(CommandOption::new("cache.size").description("Cache size"))
+                .option(CommandOption::new("db.port").description("Database port"))
+                .option(CommandOption::new("log.secret").hidden(true)),
+        );
+
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+
+        let verbose = help.find("--verbose").unwrap();
+        let db = help.find("DB OPTIONS:\n   --db.host").unwrap();
+        let cache = help.find("CACHE OPTIONS:\n   --cache.size").unwrap();
+        assert!(help.find("OPTIONS:").unwrap() < verbose);
+        assert!(verbose < db && db < cache, "{}", help);
+        assert!(help[db..cache].contains("--db.port"));
+        assert!(!help.contains("LOG OPTIONS:"));
+    }
+
+    #[test]
+    fn write_command_help_env_test() {
+        let context = Context::builder(
+            Command::new("MyApp")
+                .option(CommandOption::new("dry-run").description("Runs without changes"))
+                .option(CommandOption::new("color").env("COLOR"))
+                .option(CommandOption::new("verbose").use_env(false)),
+        )
+        .env_prefix("MYAPP")
+        .help_option(crate::context::default_help_option())
+        .help_style(help_style())
+        .build();
+
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+        assert!(help.contains("Runs without changes [env: MYAPP_DRY_RUN]"));
+        assert!(help.contains("[env: COLOR]"));
+        assert!(!help.contains("MYAPP_VERBOSE"));
+        assert!(!help.contains("MYAPP_HELP"));
+    }
+
+    fn hints_context(style: HelpStyle) -> Context {
+        Context::builder(
+            Command::new("MyApp").option(
+                CommandOption::new("color")
+                    .alias("c")
+                    .description("Color to use")
+                    .arg(
+                        crate::Argument::with_name("color")
+                            .valid_values(vec!["red", "green", "blue"])
+                            .default("red"),
+                    ),
+            ),
+        )
+        .env_prefix("MYAPP")
+        .help_style(style)
+        .build()
+    }
+
+    #[test]
+    fn write_command_help_hints_test() {
+        let context = hints_context(help_style().show_possible_values(true).max_width(120));
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+        assert_eq!(
+            help,
+            "MyApp\n\
+            \nUSAGE:\n   MyApp\n\
+            \nOPTIONS:\n   \
+            -c, --color <COLOR>     Color to use [possible values: red, green, blue] [default: red] [env: MYAPP_COLOR]\n"
+        );
+    }
+
+    #[test]
+    fn write_command_help_default_hints_test() {
+        let context = hints_context(help_style());
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+        assert!(help.contains("--color <RED|GREEN|BLUE>"));
+        assert!(help.contains("Color to use [default: red] [env: MYAPP_COLOR]\n"));
+    }
+
+    #[test]
+    fn write_command_help_hide_all_hints_test() {
+        let context = hints_context(help_style().show_possible_values(true).hide_all_hints());
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+        assert!(help.contains("--color <RED|GREEN|BLUE>"));
+        assert!(help.ends_with("Color to use\n"));
+    }
+
+    #[test]
+    fn write_command_help_repeated_hint_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v").description("Use verbose output").counted(true));
+
+        let help_with = |style: HelpStyle| {
+            let context = Context::builder(command.clone()).help_style(style).build();
+            let mut help = String::new();
+            write_command_help(&mut help, &context, context.root(), false).unwrap();
+            help
+        };
+
+        assert!(help_with(help_style()).contains("-v, --verbose     Use verbose output (can be repeated)\n"));
+        assert!(help_with(help_style().show_repeated(false)).ends_with("Use verbose output\n"));
+        assert!(help_with(help_style().hide_all_hints()).ends_with("Use verbose output\n"));
+    }
+
+    #[test]
+    fn write_command_help_display_order_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("zeta"))
+            .option(CommandOption::new("beta").display_order(1))
+            .option(CommandOption::new("alpha").display_order(1))
+            .option(CommandOption::new("gamma"))
+            .option(CommandOption::new("delta").display_order(0))
+            .arg(Argument::with_name("source"))
+            .arg(Argument::with_name("target").display_order(0));
+
+        let context = new_context(command);
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+
+        let options = help
+            .lines()
+            .skip_while(|line| *line != "OPTIONS:")
+            .skip(1)
+            .map(str::trim)
+            .collect::<Vec<&str>>();
+
+        assert_eq!(options, vec!["--delta", "--alpha", "--beta", "--zeta", "--gamma"]);
+        assert!(help.find("[SOURCE]").unwrap() < help.find("[TARGET]").unwrap(), "{}", help);
+
+        // The arguments are listed in display order
+        let markdown = to_markdown(&context, context.root());
+        assert!(markdown.find("`<target>`").unwrap() < markdown.find("`<source>`").unwrap());
+
+        // The values are assigned in the order the arguments were added, as shown in the usage
+        let result = context.root().clone().parse_from(vec!["a", "b"]).unwrap();
+        assert_eq!(result.args().get("source").unwrap().get_values(), &["a"]);
+        assert_eq!(result.args().get("target").unwrap().get_values(), &["b"]);
+    }
+
+    #[test]
+    fn write_command_help_global_options_test() {
+        let context = Context::builder(
+            Command::new("MyApp")
+                .option(CommandOption::new("verbose").description("Shows more output"))
+                .subcommand(
+                    Command::new("build")
+                        .option(CommandOption::new("profile").description("Build profile")),
+                ),
+        )
+        .global_option(
+            CommandOption::new("config")
+                .alias("c")
+                .description("Configuration file")
+                .arg(Argument::with_name("file")),
+        )
+        .global_option(CommandOption::new("profile").description("Profile to use"))
+        .global_option(CommandOption::new("trace").hidden(true))
+        .help_style(help_style())
+        .build();
+
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+
+        let options = help
+            .lines()
+            .skip_while(|line| *line != "OPTIONS:")
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<&str>>();
+
+        let global_options = help
+            .lines()
+            .skip_while(|line| *line != "GLOBAL OPTIONS:")
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(options.len(), 2);
+        assert!(options[1].contains("--verbose"));
+        assert_eq!(global_options.len(), 3);
+        assert!(global_options[1].contains("-c, --config <FILE>") && global_options[1].contains("Configuration file"));
+        assert!(global_options[2].contains("--profile") && global_options[2].contains("Profile to use"));
+        assert!(!help.contains("--trace"));
+
+        // The command options replace the global options with the same name
+        let build = context.root().find_subcommand("build").unwrap();
+        let mut help = String::new();
+        write_command_help(&mut help, &context, build, false).unwrap();
+        assert!(help.contains("Build profile"));
+        assert!(!help.contains("Profile to use"));
+        assert!(help.contains("GLOBAL OPTIONS:"));
+        assert!(help.contains("--config <FILE>"));
+    }
+
+    #[test]
+    fn wrap_text_test() {
+        use utils::wrap_text;
+
+        assert_eq!(wrap_text("Short text", 4, 80), "Short text");
+        assert_eq!(
+            wrap_text("Shows the commit logs starting at the current commit", 10, 40),
+            "Shows the commit logs starting\n          at the current commit"
+        );
+        assert_eq!(wrap_text("First line\nSecond line", 2, 80), "First line\n  Second line");
+
+        // At least 20 chars are used for the text
+        assert_eq!(
+            wrap_text("one two three four five six", 70, 80),
+            "one two three four\n                                                                      five six"
+        );
+    }
+
+    #[test]
+    fn write_command_help_max_width_test() {
+        let command = Command::new("MyApp")
+            .description("An application used to show how the help messages are wrapped")
+            .option(
+                CommandOption::new("color")
+                    .alias("c")
+                    .description("The color used to write the output of the commands"),
+            )
+            .subcommand(Command::new("remote").description("Manages the remotes tracked by this repository"))
+            .subcommand(Command::new("log").description("Shows the logs\nstarting at the current commit"));
+
+        let context = Context::builder(command).help_style(HelpStyle::new().max_width(40)).build();
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+
+        assert!(help.contains("   An application used to show how the\n   help messages are wrapped\n"), "{}", help);
+        assert!(help.contains(concat!(
+            "   -c, --color     The color used to\n",
+            "                   write the output of\n",
+            "                   the commands\n"
+        )), "{}", help);
+        assert!(help.contains(concat!(
+            "   remote        Manages the remotes\n",
+            "                 tracked by this\n",
+            "                 repository\n",
+            "   log           Shows the logs\n",
+            "                 starting at the current\n",
+            "                 commit\n"
+        )), "{}", help);
+
+        // Descriptions written in columns
+        let command = Command::new("MyApp").option(
+            CommandOption::new("a-very-long-option-name-to-use-columns")
+                .description("An option that is written in columns because its name is very long"),
+        );
+
+        let context = Context::builder(command).help_style(HelpStyle::new().max_width(40)).build();
+        let mut help = String::new();
+        write_command_help(&mut help, &context, context.root(), false).unwrap();
+
+        assert!(help.contains(concat!(
+            "   --a-very-long-option-name-to-use-columns\n",
+            "      An option that is written in\n",
+            "      columns because its name is very\n",
+            "      long\n"
+        )), "{}", help);
+    }
+
+    #[test]
+    #[should_panic]
+    fn help_style_zero_max_width_test() {
+        HelpStyle::new().max_width(0);
+    }
+
+    #[test]
+    fn default_max_width_test() {
+        // `COLUMNS` overrides the width of the terminal
+        assert_eq!(default_max_width(Some("60"), || Some(120)), 60);
+        assert_eq!(default_max_width(None, || Some(120)), 120);
+        assert_eq!(default_max_width(Some("0"), || Some(120)), 120);
+        assert_eq!(default_max_width(Some("wide"), || None), DEFAULT_MAX_WIDTH);
+        assert_eq!(default_max_width(None, || Some(0)), DEFAULT_MAX_WIDTH);
+        assert_eq!(default_max_width(None, || None), DEFAULT_MAX_WIDTH);
+    }
+
+    #[test]
+    fn to_markdown_snapshot_test() {
+        let command = Command::new("math")
+            .description("Performs math operations")
+            .option(CommandOption::new("verbose").alias("v").description("Shows the steps"))
+            .option(CommandOption::new("debug").hidden(true))
+            .option(
+                CommandOption::new("color")
+                    .description("Colors the output")
+                    .env("MATH_COLOR")
+                    .arg(Argument::with_name("when").valid_values(["always", "never"])),
+            )
+            .subcommand(
+                Command::new("sum")
+                    .description("Sums the values")
+                    .example("math sum 1 2 3", "Sums 1, 2 and 3")
+                    .option(
+                        CommandOption::new("precision")
+                            .alias("p")
+                            .description("Number of decimal digits")
+                            .arg(Argument::with_name("digits").default(2)),
+                    )
+                    .arg(Argument::one_or_more("values").description("Values to sum")),
+            )
+            .subcommand(
+                Command::new("round")
+                    .description("Rounds a value")
+                    .option(
+                        CommandOption::new("mode")
+                            .description("Rounding mode | direction")
+                            .arg(Argument::with_name("mode").valid_values(["up", "down", "nearest"])),
+                    )
+                    .arg(Argument::with_name("value"))
+                    .subcommand(Command::new("half").description("Rounds to the nearest half")),
+            )
+            .subcommand(Command::new("secret").hidden(true));
+
+        let context = new_context(command);
+        assert_eq!(
+            to_markdown(&context, context.root()),
+            include_str!("../tests/golden/markdown/math.md")
+        );
+    }
+
+    #[test]
+    fn to_markdown_prefixes_test() {
+        let context = Context::builder(
+            Command::new("MyApp")
+                .option(CommandOption::new("verbose").alias("v").description("Shows more output")),
+        )
+        .name_prefix("/")
+        .alias_prefix("/")
+        .build();
+
+        let markdown = to_markdown(&context, context.root());
+        assert!(markdown.contains("| `/verbose` | `/v` | Shows more output |  |  |\n"), "{}", markdown);
+    }
+
+    // An argument with a typed validator, an untyped validator and no validator
+    #[cfg(feature = "typing")]
+    fn typed_command() -> Command {
+        use crate::validator::validate_type;
+
+        Command::new("MyApp")
+            .option(CommandOption::new("times").description("Times to run")
+                .arg(Argument::new().validator(validate_type::<u32>())))
+            .option(CommandOption::new("ratio").description("Ratio to use")
+                .arg(Argument::new().validator(|_: &str| Ok(()))))
+            .option(CommandOption::new("name").description("Name to use")
+                .arg(Argument::new()))
+            .arg(Argument::with_name("path").description("File to read")
+                .validator(validate_type::<std::path::PathBuf>()))
+    }
+
+    #[cfg(feature = "typing")]
+    #[test]
+    fn write_command_help_types_test() {
+        let context = new_context(typed_command());
+
+        let mut help = String::new();
+        command_help(&mut help, &context, context.root(), false);
+        assert!(help.contains("Times to run [type: integer]\n"), "{}", help);
+        assert!(help.contains("Ratio to use\n"), "{}", help);
+        assert!(help.contains("Name to use\n"), "{}", help);
+
+        let context = Context::builder(typed_command())
+            .help_style(help_style().show_types(false))
+            .build();
+
+        let mut help = String::new();
+        command_help(&mut help, &context, context.root(), false);
+        assert!(help.contains("Times to run\n"), "{}", help);
+    }
+
+    #[cfg(feature = "typing")]
+    #[test]
+    fn to_markdown_types_test() {
+        let context = new_context(typed_command());
+
+        let markdown = to_markdown(&context, context.root());
+        assert!(markdown.contains("| `--times` |  | Times to run [type: integer] |  |  |\n"), "{}", markdown);
+        assert!(markdown.contains("| `--ratio` |  | Ratio to use |  |  |\n"), "{}", markdown);
+        assert!(markdown.contains("| `--name` |  | Name to use |  |  |\n"), "{}", markdown);
+        assert!(markdown.contains("| `<path>` | File to read [type: path] |  |  |\n"), "{}", markdown);
+    }
+
+    #[cfg(feature = "typing")]
+    #[test]
+    fn to_json_types_test() {
+        let context = new_context(typed_command());
+
+        let json = to_json(&context, context.root());
+        let types = json
+            .lines()
+            .filter(|line| line.trim_start().starts_with("\"type\""))
+            .map(str::trim)
+            .collect::<Vec<&str>>();
+
+        // The options: times, ratio and name, followed by the args
+        assert_eq!(types, vec![
+            "\"type\": \"integer\",",
+            "\"type\": null,",
+            "\"type\": null,",
+            "\"type\": \"path\",",
+        ]);
+    }
+
+    #[test]
+    fn to_json_test() {
+        let command = Command::new("MyApp")
+            .description("An app")
+            .option(CommandOption::new("color").alias("c").required(true).env("MYAPP_COLOR")
+                .arg(Argument::new().valid_values(vec!["red", "blue"]).default("red")))
+            .option(CommandOption::new("debug").hidden(true))
+            .example("MyApp --color blue", "Uses blue")
+            .subcommand(Command::new("version").description("Shows the version"))
+            .subcommand(Command::new("secret").hidden(true));
+
+        let context = new_context(command);
+        assert_eq!(to_json(&context, context.root()), concat!(
+            "{\n",
+            "  \"name\": \"MyApp\",\n",
+            "  \"description\": \"An app\",\n",
+            "  \"usage\": [\n",
+            "    \"MyApp --color <RED|BLUE>\",\n",
+            "    \"MyApp [SUBCOMMAND]\"\n",
+            "  ],\n",
+            "  \"options\": [\n",
+            "    {\n",
+            "      \"names\": [\n",
+            "        \"-c\",\n",
+            "        \"--color\"\n",
+            "      ],\n",
+            "      \"description\": null,\n",
+            "      \"required\": true,\n",
+            "      \"env\": \"MYAPP_COLOR\",\n",
+            "      \"args\": [\n",
+            "        {\n",
+            "          \"name\": \"color\",\n",
+            "          \"description\": null,\n",
+            "          \"type\": null,\n",
+            "          \"min\": 1,\n",
+            "          \"max\": 1,\n",
+            "          \"default\": [\n",
+            "            \"red\"\n",
+            "          ],\n",
+            "          \"values\": [\n",
+            "            \"red\",\n",
+            "            \"blue\"\n",
+            "          ]\n",
+            "        }\n",
+            "      ]\n",
+            "    }\n",
+            "  ],\n",
+            "  \"args\": [],\n",
+            "  \"examples\": [\n",
+            "    {\n",
+            "      \"command\": \"MyApp --color blue\",\n",
+            "      \"description\": \"Uses blue\"\n",
+            "    }\n",
+            "  ],\n",
+            "  \"subcommands\": [\n",
+            "    {\n",
+            "      \"name\": \"version\",\n",
+            "      \"description\": \"Shows the version\",\n",
+            "      \"usage\": [\n",
+            "        \"MyApp version\"\n",
+            "      ],\n",
+            "      \"options\": [],\n",
+            "      \"args\": [],\n",
+            "      \"examples\": [],\n",
+            "      \"subcommands\": []\n",
+            "    }\n",
+            "  ]\n",
+            "}",
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::error::{Error, ErrorKind, Result};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+thread_local! {
+    // The values provided by the `CommandLine` that is running a handler
+    static PROVIDED: RefCell<Option<Provided>> = const { RefCell::new(None) };
+
+    // The values provided with `clapi::provide`
+    static GLOBAL: RefCell<Provided> = RefCell::new(Provided::default());
+}
+
+/// Provides a value to the command handlers of any `CommandLine` in the current thread,
+/// which is obtained using `clapi::inject::<T>()`.
+///
+/// This is intended for the apps declared with the macros where the `CommandLine` is not accessible,
+/// the values provided with `CommandLine::provide` takes priority.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandLine};
+///
+/// clapi::provide(String::from("localhost"));
+///
+/// let mut command_line = CommandLine::new(Command::new("MyApp")
+///     .handler(|_, _| {
+///         assert_eq!(clapi::inject::<String>()?, "localhost");
+///         Ok(())
+///     }));
+///
+/// assert!(command_line.run_from(Vec::<String>::new()).is_ok());
+/// ```
+pub fn provide<T: Clone + 'static>(value: T) {
+    GLOBAL.with(|global| global.borrow_mut().insert(value));
+}
+
+/// Returns a clone of the value of type `T` provided with `CommandLine::provide` or `clapi::provide`.
+///
+/// The values of a `CommandLine` are only available while is running a command handler,
+/// this is used by the function parameters marked with `skip` in the macros.
+///
+/// # Errors
+/// If no value of type `T` was provided.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandLine};
+///
+/// #[derive(Clone)]
+/// struct Database(String);
+///
+/// let mut command_line = CommandLine::new(Command::new("MyApp")
+///     .handler(|_, _| {
+///         let db = clapi::inject::<Database>()?;
+///         assert_eq!(db.0, "localhost");
+///         Ok(())
+///     }))
+///     .provide(Database("localhost".to_owned()));
+///
+/// assert!(command_line.run_from(Vec::<String>::new()).is_ok());
+/// assert!(clapi::inject::<Database>().is_err());
+/// ```
+pub fn inject<T: Clone + 'static>() -> Result<T> {
+    PROVIDED
+        .with(|provided| provided.borrow().as_ref().and_then(|provided| provided.get::<T>()))
+        .or_else(|| GLOBAL.with(|global| global.borrow().get::<T>()))
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::Other,
+                format!("no value provided for type `{}`", std::any::type_name::<T>()),
+            )
+        })
+}
+
+// A map of values by type.
+#[derive(Default, Clone)]
+pub(crate) struct Provided {
+    values: HashMap<TypeId, Rc<dyn Any>>,
+}
+
+impl Provided {
+    pub fn insert<T: Clone + 'static>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Rc::new(value));
+    }
+
+    pub fn get<T: Clone + 'static>(&self) -> Option<T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    // Makes the values available to `inject` while running `f`.
+    pub fn scope<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        // Restores the previous values even if `f` panics
+        struct Restore(Option<Provided>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                PROVIDED.with(|provided| *provided.borrow_mut() = previous);
+            }
+        }
+
+        let previous = PROVIDED.with(|provided| provided.replace(Some(self.clone())));
+        let _restore = Restore(previous);
+        f()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inject_test() {
+        let mut provided = Provided::default();
+        provided.insert(10_i32);
+        provided.insert(String::from("hello"));
+
+        assert!(inject::<i32>().is_err());
+
+        provided.scope(|| {
+            assert_eq!(inject::<i32>().unwrap(), 10);
+            assert_eq!(inject::<String>().unwrap(), "hello");
+            assert!(inject::<u64>().is_err());
+
+            // Inner scopes restore the outer values
+            Provided::default().scope(|| assert!(inject::<i32>().is_err()));
+            assert_eq!(inject::<i32>().unwrap(), 10);
+        });
+
+        assert!(inject::<i32>().is_err());
+    }
+
+    #[test]
+    fn inject_global_test() {
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        struct Port(u16);
+
+        provide(Port(80));
+        assert_eq!(inject::<Port>().unwrap(), Port(80));
+
+        let mut provided = Provided::default();
+        provided.insert(Port(8080));
+        provided.scope(|| assert_eq!(inject::<Port>().unwrap(), Port(8080)));
+    }
+}
@@ -159,17 +159,30 @@ mod serde;
 
 mod arg_count;
 mod args;
+mod codegen;
 mod command;
 mod command_line;
 mod context;
 mod error;
+mod from_args;
+mod inject;
 mod option;
 mod parse_result;
 mod parser;
+mod settings;
 
 /// Utilities for provide suggestions.
 pub mod suggestion;
 
+/// Generators of commonly used commands.
+pub mod generators;
+
+/// Generates shell completion scripts for the commands.
+pub mod completions;
+
+/// Reports the configuration of a command-line app, see `CommandLine::use_doctor`.
+pub mod doctor;
+
 /// Utilities for provide commands help information.
 pub mod help;
 
@@ -186,6 +199,22 @@ pub mod validator;
 #[cfg(feature = "typing")]
 pub mod typing;
 
+/// Utilities for test the commands. Require `testing` feature enable.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+/// Compatibility layers with the API of other command-line crates. Require `compat` feature enable.
+#[cfg(feature = "compat")]
+pub mod compat;
+
+/// Help messages with ANSI colors. Require `color` feature enable.
+#[cfg(feature = "color")]
+pub mod color;
+
+/// Generates man pages of the commands. Require `man` feature enable.
+#[cfg(feature = "man")]
+pub mod man;
+
 // Re-exports
 pub use self::arg_count::*;
 pub use self::args::*;
@@ -193,9 +222,21 @@ pub use self::command::*;
 pub use self::command_line::*;
 pub use self::context::*;
 pub use self::error::*;
+pub use self::from_args::*;
+pub use self::inject::*;
 pub use self::option::*;
 pub use self::parse_result::*;
 pub use self::parser::*;
+pub use self::settings::*;
+
+#[cfg(feature = "json")]
+pub use self::serde::from_json_str;
+
+#[cfg(feature = "toml")]
+pub use self::serde::from_toml_str;
+
+#[cfg(feature = "yaml")]
+pub use self::serde::from_yaml_str;
 
 /// Clapi macros
 #[macro_use]
@@ -224,7 +265,7 @@ pub mod private {
     // currently in rust `Vec<$type:ty>` and `$type:ty` could be considered the same.
     //
     // In the `app_macros::app!` with declare the variables as:
-    // `let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, $arg_name: $arg_type);`
+    // `let $arg_name : $arg_type = $crate::declare_argument_var!(arguments, command_name, $arg_name: $arg_type);`
     //
     // We give a name to the variable outside the `proc_macro` to allow the IDE to provide type
     // information of the actual variable.
@@ -233,16 +274,16 @@ pub mod private {
     #[doc(hidden)]
     #[macro_export]
     macro_rules! declare_option_var {
-        ($options:ident, $name:ident: $ty:ty) => {
-            $crate::private::clapi_internal::__declare_option_var!($options, $name: $ty)
+        ($options:ident, $command_name:ident, $name:ident: $ty:ty) => {
+            $crate::private::clapi_internal::__declare_option_var!($options, $command_name, $name: $ty)
         };
     }
 
     #[doc(hidden)]
     #[macro_export]
     macro_rules! declare_argument_var {
-        ($arguments:ident, $name:ident: $ty:ty) => {
-            $crate::private::clapi_internal::__declare_argument_var!($arguments, $name: $ty)
+        ($arguments:ident, $command_name:ident, $name:ident: $ty:ty) => {
+            $crate::private::clapi_internal::__declare_argument_var!($arguments, $command_name, $name: $ty)
         };
     }
 }
@@ -0,0 +1,353 @@
+use crate::help::usage_lines;
+use crate::help::utils::{args_to_string, option_hints, DisplayArgs};
+use crate::{Command, CommandOption, Context};
+use std::io::{Result, Write};
+
+/// Writes the man page of the given `Command` in `roff` format.
+///
+/// The page contains the `NAME`, `SYNOPSIS`, `OPTIONS`, `SUBCOMMANDS` and `EXAMPLES` sections,
+/// hidden options and subcommands are not included.
+///
+/// The options are written using the prefixes of the `Context`,
+/// and its descriptions are followed by the hints of the `HelpStyle`.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, CommandOption, Context};
+/// use clapi::man::render_man;
+///
+/// let context = Context::new(Command::new("MyApp")
+///     .description("An app")
+///     .option(CommandOption::new("verbose").alias("v").description("Shows more output")));
+///
+/// let mut buf = Vec::new();
+/// render_man(&context, context.root(), 1, &mut buf).unwrap();
+///
+/// let page = String::from_utf8(buf).unwrap();
+/// assert!(page.starts_with(".TH MYAPP 1\n.SH NAME\nMyApp \\- An app\n"));
+/// assert!(page.contains("\\fB\\-v\\fR, \\fB\\-\\-verbose\\fR\nShows more output\n"));
+/// ```
+pub fn render_man(context: &Context, command: &Command, section: u8, buf: &mut dyn Write) -> Result<()> {
+    write_man(context, command, &[], section, buf)
+}
+
+/// Renders the man pages of the given `Command` and all its subcommands,
+/// returns pairs of `(file name, content)`.
+///
+/// The pages of the subcommands are named after its path, like: `MyApp-remote-add.1`,
+/// hidden subcommands are not included.
+///
+/// # Example
+/// ```
+/// use clapi::{Command, Context};
+/// use clapi::man::render_man_all;
+///
+/// let context = Context::new(Command::new("MyApp")
+///     .subcommand(Command::new("remote").subcommand(Command::new("add")))
+///     .subcommand(Command::new("secret").hidden(true)));
+///
+/// let names = render_man_all(&context, context.root(), 1)
+///     .into_iter()
+///     .map(|(name, _)| name)
+///     .collect::<Vec<String>>();
+///
+/// assert_eq!(names, vec!["MyApp.1", "MyApp-remote.1", "MyApp-remote-add.1"]);
+/// ```
+pub fn render_man_all(context: &Context, command: &Command, section: u8) -> Vec<(String, String)> {
+    let mut pages = Vec::new();
+    collect_pages(context, command, &mut Vec::new(), section, &mut pages);
+    pages
+}
+
+fn collect_pages<'a>(
+    context: &Context,
+    command: &'a Command,
+    parents: &mut Vec<&'a str>,
+    section: u8,
+    pages: &mut Vec<(String, String)>,
+) {
+    let mut buf = Vec::new();
+    write_man(context, command, parents, section, &mut buf).expect("failed to write the man page");
+
+    let content = String::from_utf8(buf).expect("man page is not valid UTF-8");
+    pages.push((format!("{}.{}", page_name(command, parents), section), content));
+
+    parents.push(command.get_name());
+    for subcommand in command.get_subcommands().filter(|c| !c.is_hidden()) {
+        collect_pages(context, subcommand, parents, section, pages);
+    }
+    parents.pop();
+}
+
+// Writes the man page of the command, `parents` are the names of the commands from the root
+fn write_man(
+    context: &Context,
+    command: &Command,
+    parents: &[&str],
+    section: u8,
+    w: &mut dyn Write,
+) -> Result<()> {
+    let name = page_name(command, parents);
+
+    match command.get_version() {
+        Some(version) => writeln!(
+            w,
+            ".TH {} {} \"\" \"{} {}\"",
+            escape(&name.to_uppercase()),
+            section,
+            escape(&name),
+            escape(version)
+        )?,
+        None => writeln!(w, ".TH {} {}", escape(&name.to_uppercase()), section)?,
+    }
+
+    // NAME
+    writeln!(w, ".SH NAME")?;
+    match command.get_description() {
+        Some(description) => writeln!(w, "{} \\- {}", escape(&name), escape(description))?,
+        None => writeln!(w, "{}", escape(&name))?,
+    }
+
+    // SYNOPSIS
+    writeln!(w, ".SH SYNOPSIS")?;
+    writeln!(w, ".nf")?;
+    for line in usage_lines(context, command, parents) {
+        writeln!(w, "{}", escape(&line))?;
+    }
+    writeln!(w, ".fi")?;
+
+    // OPTIONS
+    let options = command
+        .get_options()
+        .iter_sorted()
+        .filter(|o| !o.is_hidden())
+        .collect::<Vec<&CommandOption>>();
+
+    if !options.is_empty() {
+        writeln!(w, ".SH OPTIONS")?;
+        for option in options {
+            writeln!(w, ".TP")?;
+            writeln!(w, "{}", option_signature(context, option))?;
+
+            // Option description followed by its hints
+            let description = option
+                .get_description()
+                .map(str::to_owned)
+                .into_iter()
+                .chain(option_hints(context, option))
+                .collect::<Vec<String>>();
+
+            if !description.is_empty() {
+                writeln!(w, "{}", escape(&description.join(" ")))?;
+            }
+        }
+    }
+
+    // SUBCOMMANDS
+    let subcommands = command
+        .get_subcommands()
+        .filter(|c| !c.is_hidden())
+        .collect::<Vec<&Command>>();
+
+    if !subcommands.is_empty() {
+        writeln!(w, ".SH SUBCOMMANDS")?;
+        for subcommand in subcommands {
+            let names = std::iter::once(subcommand.get_name())
+                .chain(subcommand.get_aliases().map(|s| s.as_str()))
+                .map(|s| format!("\\fB{}\\fR", escape(s)))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            writeln!(w, ".TP")?;
+            writeln!(w, "{}", names)?;
+            if let Some(description) = subcommand.get_description() {
+                writeln!(w, "{}", escape(description))?;
+            }
+        }
+    }
+
+    // EXAMPLES
+    if !command.get_examples().is_empty() {
+        writeln!(w, ".SH EXAMPLES")?;
+        for (command_line, description) in command.get_examples() {
+            writeln!(w, ".TP")?;
+            writeln!(w, "\\fB{}\\fR", escape(command_line))?;
+            writeln!(w, "{}", escape(description))?;
+        }
+    }
+
+    Ok(())
+}
+
+// The name of the page, the names of the commands joined with `-`, like: `MyApp-remote-add`
+fn page_name(command: &Command, parents: &[&str]) -> String {
+    parents
+        .iter()
+        .copied()
+        .chain(std::iter::once(command.get_name()))
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+// \fB-v\fR, \fB--verbose\fR \fI<ARG>\fR
+fn option_signature(context: &Context, option: &CommandOption) -> String {
+    let name_prefix = context.name_prefixes().next().unwrap();
+    let alias_prefix = context.alias_prefixes().next().unwrap();
+
+    let mut signature = option
+        .get_aliases()
+        .map(|alias| format!("\\fB{}\\fR", escape(&format!("{}{}", alias_prefix, alias))))
+        .chain(std::iter::once(format!(
+            "\\fB{}\\fR",
+            escape(&format!("{}{}", name_prefix, option.get_name()))
+        )))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    if let Some(args) = args_to_string(option.get_args(), DisplayArgs::default()) {
+        signature.push_str(&format!(" \\fI{}\\fR", escape(&args)));
+    }
+
+    signature
+}
+
+// Escapes the backslashes and dashes, and the lines starting with a control character
+fn escape(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line = line.replace('\\', "\\e").replace('-', "\\-");
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{}", line)
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Argument;
+
+    // A command-line calculator used for the snapshot tests
+    fn math_command() -> Context {
+        Context::new(math_root())
+    }
+
+    fn math_root() -> Command {
+        Command::new("math")
+            .version("1.0")
+            .description("Performs math operations")
+            .option(CommandOption::new("verbose").alias("v").description("Shows the steps"))
+            .option(CommandOption::new("debug").hidden(true))
+            .option(
+                CommandOption::new("color")
+                    .description("Colors the output")
+                    .env("MATH_COLOR")
+                    .arg(Argument::with_name("when").default("always")),
+            )
+            .subcommand(
+                Command::new("sum")
+                    .alias("add")
+                    .description("Sums the values")
+                    .example("math sum 1 2 3", "Sums 1, 2 and 3")
+                    .option(
+                        CommandOption::new("precision")
+                            .alias("p")
+                            .description("Number of decimal digits")
+                            .arg(Argument::with_name("digits")),
+                    )
+                    .arg(Argument::one_or_more("values")),
+            )
+            .subcommand(
+                Command::new("round")
+                    .description("Rounds a value")
+                    .option(
+                        CommandOption::new("mode")
+                            .description("Rounding mode")
+                            .arg(Argument::with_name("mode").valid_values(["up", "down", "nearest"])),
+                    )
+                    .arg(Argument::with_name("value")),
+            )
+            .subcommand(Command::new("secret").hidden(true))
+    }
+
+    #[test]
+    fn render_man_all_snapshot_test() {
+        let context = math_command();
+        let pages = render_man_all(&context, context.root(), 1);
+        let names = pages.iter().map(|(name, _)| name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(names, vec!["math.1", "math-sum.1", "math-round.1"]);
+
+        for (name, content) in pages {
+            let path = format!("{}/tests/golden/man/{}", env!("CARGO_MANIFEST_DIR"), name);
+            let expected = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(content, expected, "man page `{}` don't match", name);
+        }
+    }
+
+    #[test]
+    fn render_man_test() {
+        let context = math_command();
+        let mut buf = Vec::new();
+        render_man(&context, context.root(), 8, &mut buf).unwrap();
+
+        let page = String::from_utf8(buf).unwrap();
+        assert!(page.starts_with(".TH MATH 8 \"\" \"math 1.0\"\n"));
+        assert!(!page.contains("debug"));
+        assert!(!page.contains("secret"));
+    }
+
+    #[test]
+    fn render_man_usage_test() {
+        let context = Context::new(Command::new("MyApp").usage("MyApp [FILE]\n.hidden line"));
+
+        let mut buf = Vec::new();
+        render_man(&context, context.root(), 1, &mut buf).unwrap();
+
+        let page = String::from_utf8(buf).unwrap();
+        assert!(page.contains(".SH SYNOPSIS\n.nf\nMyApp [FILE]\n\\&.hidden line\n.fi\n"), "{}", page);
+    }
+
+    #[test]
+    fn render_man_prefixes_test() {
+        let context = Context::builder(math_root())
+            .name_prefix("/")
+            .alias_prefix("/")
+            .build();
+
+        let mut buf = Vec::new();
+        render_man(&context, context.root(), 1, &mut buf).unwrap();
+
+        let page = String::from_utf8(buf).unwrap();
+        assert!(page.contains("\\fB/v\\fR, \\fB/verbose\\fR\nShows the steps\n"), "{}", page);
+    }
+
+    #[cfg(feature = "typing")]
+    #[test]
+    fn render_man_types_test() {
+        use crate::validator::validate_type;
+
+        let context = Context::new(Command::new("MyApp")
+            .option(CommandOption::new("times").description("Times to run")
+                .arg(Argument::new().validator(validate_type::<u32>())))
+            .option(CommandOption::new("name").description("Name to use")
+                .arg(Argument::new().validator(|_: &str| Ok(())))));
+
+        let mut buf = Vec::new();
+        render_man(&context, context.root(), 1, &mut buf).unwrap();
+
+        let page = String::from_utf8(buf).unwrap();
+        assert!(page.contains("\\fB\\-\\-times\\fR \\fI<TIMES>\\fR\nTimes to run [type: integer]\n"), "{}", page);
+        assert!(page.contains("\\fB\\-\\-name\\fR \\fI<NAME>\\fR\nName to use\n"), "{}", page);
+    }
+
+    #[test]
+    fn escape_test() {
+        assert_eq!(escape("a-b"), "a\\-b");
+        assert_eq!(escape("C:\\path"), "C:\\epath");
+        assert_eq!(escape("first\n.second\n'third"), "first\n\\&.second\n\\&'third");
+    }
+}
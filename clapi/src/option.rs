@@ -1,9 +1,11 @@
-#![allow(clippy::len_zero)]
+#![allow(clippy::len_zero, clippy::result_large_err)]
 use crate::args::{Argument, ArgumentList};
+use std::borrow::Cow;
 use std::fmt::{Debug, Display};
 use std::hash::{Hash, Hasher};
 use std::ops::Index;
 use std::str::FromStr;
+use crate::utils::{assert_valid_name, cmp_display_order};
 use crate::{Error, ErrorKind, Result};
 
 /// Represents a command-line option.
@@ -11,20 +13,32 @@ use crate::{Error, ErrorKind, Result};
 pub struct CommandOption {
     name: String,
     aliases: Vec<String>,
+    hidden_aliases: Vec<String>,
     description: Option<String>,
     args: ArgumentList,
     is_required: bool,
     is_hidden: bool,
     is_global: bool,
     allow_multiple: bool,
+    counted: bool,
+    min_occurrences: Option<usize>,
+    max_occurrences: Option<usize>,
     requires_assign: bool,
+    env: Option<String>,
+    use_env: bool,
+    conflicts: Vec<String>,
+    requires: Vec<String>,
+    default_value_ifs: Vec<(String, Option<String>, String)>,
+    display_order: Option<usize>,
+    spelling: Option<String>,
+    occurrences: usize,
 }
 
 impl CommandOption {
     /// Constructs a new `CommandOption`.
     ///
     /// # Panics:
-    /// Panics if the `name` is empty.
+    /// Panics if the `name` is empty, contains whitespaces or the `=` operator, or starts with `-`.
     ///
     /// # Example
     /// ```
@@ -39,18 +53,30 @@ impl CommandOption {
     /// ```
     pub fn new<S: Into<String>>(name: S) -> Self {
         let name = name.into();
-        assert!(!name.is_empty(), "option `name` cannot be empty");
+        assert_valid_option_name("option `name`", &name);
 
         CommandOption {
             name,
             aliases: Vec::new(),
+            hidden_aliases: Vec::new(),
             description: None,
             args: ArgumentList::new(),
             is_required: false,
             is_hidden: false,
             is_global: false,
             allow_multiple: false,
+            counted: false,
+            min_occurrences: None,
+            max_occurrences: None,
             requires_assign: false,
+            env: None,
+            use_env: true,
+            conflicts: Vec::new(),
+            requires: Vec::new(),
+            default_value_ifs: Vec::new(),
+            display_order: None,
+            spelling: None,
+            occurrences: 0,
         }
     }
 
@@ -59,11 +85,14 @@ impl CommandOption {
         self.name.as_str()
     }
 
-    /// Returns an `Iterator` over the aliases of this option.
+    /// Returns an `Iterator` over the aliases of this option, without the hidden aliases.
     pub fn get_aliases(&self) -> Aliases<'_> {
-        Aliases {
-            iter: self.aliases.iter(),
-        }
+        Aliases::new(&self.aliases)
+    }
+
+    /// Returns an `Iterator` over the hidden aliases of this option.
+    pub fn get_hidden_aliases(&self) -> Aliases<'_> {
+        Aliases::new(&self.hidden_aliases)
     }
 
     /// Returns a short description of this option or `None` if not set.
@@ -88,7 +117,22 @@ impl CommandOption {
 
     /// Returns `true` if this option is allowed to appear multiple times.
     pub fn allow_multiple(&self) -> bool {
-        self.allow_multiple
+        self.allow_multiple || self.counted
+    }
+
+    /// Returns `true` if this option takes no arguments and counts the times it was passed.
+    pub fn is_counted(&self) -> bool {
+        self.counted
+    }
+
+    /// Returns the minimum number of times this option must be passed, if any.
+    pub fn get_min_occurrences(&self) -> Option<usize> {
+        self.min_occurrences
+    }
+
+    /// Returns the maximum number of times this option can be passed, if any.
+    pub fn get_max_occurrences(&self) -> Option<usize> {
+        self.max_occurrences
     }
 
     /// Returns `true` if the option requires an assign operator.
@@ -96,12 +140,29 @@ impl CommandOption {
         self.requires_assign
     }
 
-    /// Returns the `Argument` this option takes or `None` if have more than 1 argument.
+    /// Returns the environment variable set for this option, or `None` if not set.
+    ///
+    /// Use `Context::env_var_name` to get the environment variable derived from the context `env_prefix`.
+    pub fn get_env(&self) -> Option<&str> {
+        self.env.as_deref()
+    }
+
+    /// Returns `true` if this option can take its value from an environment variable, by default `true`.
+    pub fn is_env_enabled(&self) -> bool {
+        self.use_env
+    }
+
+    /// Returns the names or aliases of the options that cannot be used with this option.
+    pub fn get_conflicts(&self) -> &[String] {
+        self.conflicts.as_slice()
+    }
+
+    /// Returns the `Argument` this option takes or `None` if takes no arguments or more than 1 argument.
     pub fn get_arg(&self) -> Option<&Argument> {
-        if self.args.len() > 1 {
-            None
-        } else {
+        if self.args.len() == 1 {
             Some(&self.args[0])
+        } else {
+            None
         }
     }
 
@@ -110,20 +171,77 @@ impl CommandOption {
         &self.args
     }
 
+    /// Returns the names or aliases of the options required by this option.
+    pub fn get_requires(&self) -> &[String] {
+        self.requires.as_slice()
+    }
+
+    /// Returns the conditional default values of this option
+    /// as tuples of `(option, value, default)`, see [`CommandOption::default_value_if`].
+    pub fn get_default_value_ifs(&self) -> &[(String, Option<String>, String)] {
+        self.default_value_ifs.as_slice()
+    }
+
+    // Returns the option as written in the command-line, like: `-t`, or `None` if wasn't passed.
+    /// Returns the position of this option in the `help`, or `None` if not set.
+    pub fn get_display_order(&self) -> Option<usize> {
+        self.display_order
+    }
+
+    pub(crate) fn spelling(&self) -> Option<&str> {
+        self.spelling.as_deref()
+    }
+
+    // Sets the option as written in the command-line.
+    pub(crate) fn set_spelling(&mut self, spelling: String) {
+        self.spelling = Some(spelling);
+    }
+
+    // Returns the number of times this option was passed.
+    pub(crate) fn occurrences(&self) -> usize {
+        self.occurrences
+    }
+
+    // Sets the number of times this option was passed.
+    pub(crate) fn set_occurrences(&mut self, occurrences: usize) {
+        self.occurrences = occurrences;
+    }
+
+    // Returns `true` if this option have a minimum or maximum number of occurrences.
+    pub(crate) fn has_occurrence_limits(&self) -> bool {
+        self.min_occurrences.is_some() || self.max_occurrences.is_some()
+    }
+
+    // Removes the aliases and hidden aliases that don't satisfy the predicate.
+    pub(crate) fn retain_aliases<F: FnMut(&str) -> bool>(&mut self, mut f: F) {
+        self.aliases.retain(|alias| f(alias));
+        self.hidden_aliases.retain(|alias| f(alias));
+    }
+
+    // Returns an `Iterator` over the aliases and hidden aliases of this option.
+    pub(crate) fn get_all_aliases(&self) -> impl Iterator<Item = &String> {
+        self.aliases.iter().chain(self.hidden_aliases.iter())
+    }
+
+    // Returns a mutable reference to the `Arguments` of this option.
+    pub(crate) fn get_args_mut(&mut self) -> &mut ArgumentList {
+        &mut self.args
+    }
+
     /// Returns `true` if this option take arguments.
     pub fn take_args(&self) -> bool {
         self.args.len() > 0
     }
 
-    /// Returns `true` if option contains the specified alias.
+    /// Returns `true` if option contains the specified alias or hidden alias.
     pub fn has_alias<S: AsRef<str>>(&self, alias: S) -> bool {
-        self.aliases.iter().any(|s| s == alias.as_ref())
+        self.get_all_aliases().any(|s| s == alias.as_ref())
     }
 
     /// Adds a new alias to this option.
     ///
     /// # Panics:
-    /// Panics if the `alias` is empty.
+    /// Panics if the `alias` is empty, contains whitespaces or the `=` operator, or starts with `-`.
     ///
     /// # Example
     /// ```
@@ -138,11 +256,35 @@ impl CommandOption {
     /// ```
     pub fn alias<S: Into<String>>(mut self, alias: S) -> Self {
         let alias = alias.into();
-        assert!(!alias.is_empty(), "option `alias` cannot be empty");
+        assert_valid_option_name("option `alias`", &alias);
         self.aliases.push(alias);
         self
     }
 
+    /// Adds a new alias to this option that is not shown in the `help`,
+    /// useful to keep accepting an old name of the option.
+    ///
+    /// # Panics:
+    /// Panics if the `alias` is empty, contains whitespaces or the `=` operator, or starts with `-`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("color").hidden_alias("colour"))
+    ///     .parse_from(vec!["--colour"])
+    ///     .unwrap();
+    ///
+    /// assert!(result.options().contains("color"));
+    /// ```
+    pub fn hidden_alias<S: Into<String>>(mut self, alias: S) -> Self {
+        let alias = alias.into();
+        assert_valid_option_name("option `alias`", &alias);
+        self.hidden_aliases.push(alias);
+        self
+    }
+
     /// Sets a short description of this option.
     ///
     /// # Example
@@ -213,6 +355,28 @@ impl CommandOption {
         self
     }
 
+    /// Sets the position of this option in the `help`.
+    ///
+    /// The options are shown by its `display_order` and then by its name,
+    /// options without a `display_order` are shown after in the order they were added.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("verbose"))
+    ///     .option(CommandOption::new("quiet").display_order(1))
+    ///     .option(CommandOption::new("color").display_order(0));
+    ///
+    /// let names = command.get_options().iter_sorted().map(|o| o.get_name()).collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["color", "quiet", "verbose"]);
+    /// ```
+    pub fn display_order(mut self, order: usize) -> Self {
+        self.display_order = Some(order);
+        self
+    }
+
     /// Specify if this option can appear multiple times.
     ///
     /// # Example
@@ -235,7 +399,128 @@ impl CommandOption {
         self
     }
 
+    /// Specify if this option can be repeated to count the times it was passed, like `-v -v -v`.
+    ///
+    /// The count is retrieved using `ParseResult::occurrences_of` or `OptionList::get_count`.
+    ///
+    /// # Panics
+    /// If the option takes arguments.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("verbose").alias("v").counted(true))
+    ///     .parse_from(vec!["-v", "--verbose", "-v"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.occurrences_of("verbose"), 3);
+    /// ```
+    pub fn counted(mut self, counted: bool) -> Self {
+        assert!(
+            !counted || self.args.is_empty(),
+            "`{}` cannot be counted because takes arguments",
+            self.name
+        );
+
+        self.counted = counted;
+        self
+    }
+
+    /// Sets the minimum number of times this option must be passed, when is passed,
+    /// a value greater than 1 allows this option to appear multiple times.
+    ///
+    /// The values count of the arguments are checked on each occurrence instead of on all the values.
+    ///
+    /// # Panics
+    /// If the value is greater than the maximum number of occurrences.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("point")
+    ///         .min_occurrences(2)
+    ///         .arg(Argument::with_name("xy").values_count(2)));
+    ///
+    /// let result = command.clone().parse_from(vec!["--point", "1", "2", "--point", "3", "4"]).unwrap();
+    /// assert_eq!(result.options().get_arg("point").unwrap().get_values(), &["1", "2", "3", "4"]);
+    ///
+    /// let error = command.parse_from(vec!["--point", "1", "2"]).unwrap_err();
+    /// assert!(error.to_string().contains("'--point' must be given at least 2 times"));
+    /// ```
+    pub fn min_occurrences(mut self, min: usize) -> Self {
+        if let Some(max) = self.max_occurrences {
+            assert!(min <= max, "`min_occurrences` is greater than `max_occurrences`: {} > {}", min, max);
+        }
+
+        self.min_occurrences = Some(min);
+        self.allow_multiple |= min > 1;
+        self
+    }
+
+    /// Sets the maximum number of times this option can be passed,
+    /// a value greater than 1 allows this option to appear multiple times.
+    ///
+    /// The values count of the arguments are checked on each occurrence instead of on all the values.
+    ///
+    /// # Panics
+    /// If the value is 0 or is lower than the minimum number of occurrences.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("header")
+    ///         .max_occurrences(3)
+    ///         .arg(Argument::with_name("header").values_count(2)));
+    ///
+    /// let result = command.clone()
+    ///     .parse_from(vec!["--header", "Accept", "*/*", "--header", "Host", "localhost"])
+    ///     .unwrap();
+    /// assert_eq!(result.options().get_arg("header").unwrap().get_values().len(), 4);
+    ///
+    /// let error = command
+    ///     .parse_from(vec!["--header", "A", "1", "--header", "B", "2", "--header", "C", "3", "--header", "D", "4"])
+    ///     .unwrap_err();
+    /// assert!(error.to_string().contains("'--header' may be given at most 3 times"));
+    /// ```
+    pub fn max_occurrences(mut self, max: usize) -> Self {
+        assert!(max > 0, "`max_occurrences` cannot be 0");
+        if let Some(min) = self.min_occurrences {
+            assert!(min <= max, "`min_occurrences` is greater than `max_occurrences`: {} > {}", min, max);
+        }
+
+        self.max_occurrences = Some(max);
+        self.allow_multiple |= max > 1;
+        self
+    }
+
     /// Specify if this is a global option.
+    ///
+    /// A global option is inherited by all the subcommands and can be declared before, between
+    /// or after the subcommands, its values, default values and `requires_assign` are the same in any position.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("color")
+    ///         .global(true)
+    ///         .arg(Argument::new()))
+    ///     .subcommand(Command::new("echo"));
+    ///
+    /// let result = command.clone().parse_from(vec!["--color", "red", "echo"]).unwrap();
+    /// assert_eq!(result.command_name(), "echo");
+    /// assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["red"]);
+    ///
+    /// let result = command.parse_from(vec!["echo", "--color", "red"]).unwrap();
+    /// assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["red"]);
+    /// ```
     pub fn global(mut self, is_global: bool) -> Self {
         self.is_global = is_global;
         self
@@ -276,8 +561,141 @@ impl CommandOption {
         self
     }
 
+    /// Sets the environment variable used as the value of this option when is not passed.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// std::env::set_var("MYAPP_OPTION_COLOR", "red");
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("color")
+    ///         .env("MYAPP_OPTION_COLOR")
+    ///         .arg(Argument::new()))
+    ///     .parse_from(Vec::<String>::new())
+    ///     .unwrap();
+    ///
+    /// assert!(result.options().get_arg("color").unwrap().contains("red"));
+    /// ```
+    pub fn env<S: Into<String>>(mut self, name: S) -> Self {
+        let name = name.into();
+        assert!(!name.trim().is_empty(), "option `env` cannot be empty");
+        self.env = Some(name);
+        self
+    }
+
+    /// Specify if this option can take its value from an environment variable,
+    /// used to exclude an option from the context `env_prefix`.
+    pub fn use_env(mut self, use_env: bool) -> Self {
+        self.use_env = use_env;
+        self
+    }
+
+    /// Adds the name or alias of an option that cannot be used with this option,
+    /// the parse fails with `ErrorKind::ConflictingOptions` if both are passed.
+    ///
+    /// Options set by default values or environment variables are not considered passed.
+    ///
+    /// # Panics:
+    /// Panics if the `name` is empty, contains whitespaces or the `=` operator, or starts with `-`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, ErrorKind};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("json").conflicts_with("yaml"))
+    ///     .option(CommandOption::new("yaml"));
+    ///
+    /// assert!(command.clone().parse_from(vec!["--json"]).is_ok());
+    ///
+    /// let error = command.parse_from(vec!["--json", "--yaml"]).unwrap_err();
+    /// assert_eq!(error.kind(), &ErrorKind::ConflictingOptions("--json".to_owned(), "--yaml".to_owned()));
+    /// ```
+    pub fn conflicts_with<S: Into<String>>(mut self, name: S) -> Self {
+        let name = name.into();
+        assert_valid_option_name("option `conflicts_with`", &name);
+        self.conflicts.push(name);
+        self
+    }
+
+    /// Adds the name or alias of an option required by this option,
+    /// the parse fails with `ErrorKind::MissingRequiredBy` if this option is passed without it.
+    ///
+    /// Options set by default values satisfy the requirement.
+    ///
+    /// # Panics:
+    /// Panics if the `name` is empty, contains whitespaces or the `=` operator, or starts with `-`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument, ErrorKind};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("output").requires("format").arg(Argument::new()))
+    ///     .option(CommandOption::new("format").arg(Argument::new()));
+    ///
+    /// assert!(command.clone().parse_from(vec!["--output", "a.txt", "--format", "csv"]).is_ok());
+    ///
+    /// let error = command.parse_from(vec!["--output", "a.txt"]).unwrap_err();
+    /// assert_eq!(error.kind(), &ErrorKind::MissingRequiredBy("--output".to_owned(), "--format".to_owned()));
+    /// ```
+    pub fn requires<S: Into<String>>(mut self, name: S) -> Self {
+        let name = name.into();
+        assert_valid_option_name("option `requires`", &name);
+        self.requires.push(name);
+        self
+    }
+
+    /// Adds a default value for this option used when the option named `option` is present,
+    /// or if `value` is `Some` when that option contains the given value.
+    ///
+    /// The default value is assigned to the first argument of this option, options
+    /// without arguments are just set when the condition holds.
+    ///
+    /// # Evaluation order
+    /// - Values passed in the command-line and environment variables take precedence over any default.
+    /// - The rules are evaluated after the passed options, in the order the options were declared,
+    ///   so a condition only sees the default values of the options declared before.
+    /// - If several rules of this option holds the last one is used.
+    /// - If no rule holds, the default values of the arguments (if any) are used.
+    ///
+    /// # Panics:
+    /// Panics if the `option` is empty, contains whitespaces or the `=` operator, or starts with `-`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("output").arg(Argument::new()))
+    ///     .option(CommandOption::new("format")
+    ///         .default_value_if("output", Some("report.json"), "json")
+    ///         .arg(Argument::new().default("yaml")));
+    ///
+    /// let result = command.clone().parse_from(vec!["--output", "report.json"]).unwrap();
+    /// assert!(result.options().get_arg("format").unwrap().contains("json"));
+    ///
+    /// let result = command.parse_from(vec!["--output", "report.txt"]).unwrap();
+    /// assert!(result.options().get_arg("format").unwrap().contains("yaml"));
+    /// ```
+    pub fn default_value_if<S, D>(mut self, option: S, value: Option<&str>, default: D) -> Self
+    where
+        S: Into<String>,
+        D: ToString,
+    {
+        let option = option.into();
+        assert_valid_option_name("option `default_value_if`", &option);
+        self.default_value_ifs.push((option, value.map(str::to_owned), default.to_string()));
+        self
+    }
+
     /// Adds a new `Argument` to this option.
     ///
+    /// # Panics
+    /// If the argument is raw, only the arguments of a command can be raw.
+    ///
     /// # Example
     /// ```
     /// use clapi::{Command, CommandOption, Argument};
@@ -293,6 +711,15 @@ impl CommandOption {
     /// assert!(result.options().get_args("copy").unwrap().get("to").unwrap().contains("/src/utils/"));
     /// ```
     pub fn arg(mut self, mut arg: Argument) -> Self {
+        assert!(
+            !arg.is_raw(),
+            "`{}` cannot take a raw argument: `{}`",
+            self.name,
+            arg.get_name()
+        );
+
+        assert!(!self.counted, "`{}` is counted and cannot take arguments", self.name);
+
         arg.set_name_and_description_if_none(self.get_name(), self.get_description());
 
         if let Err(duplicated) = self.args.add(arg) {
@@ -320,17 +747,34 @@ impl CommandOption {
     /// assert!(option.get_args().contains("to"));
     /// ```
     pub fn args(mut self, args: ArgumentList) -> Self {
+        assert!(!self.counted || args.is_empty(), "`{}` is counted and cannot take arguments", self.name);
         self.args = args;
         self
     }
 }
 
+// The default assign operator, custom operators are checked by the `Context`
+#[inline]
+fn assert_valid_option_name(source: &str, name: &str) {
+    assert_valid_name(source, name);
+
+    if name.contains('=') {
+        panic!("{} cannot contains the assign operator `=`: `{}`", source, name);
+    }
+
+    // Dotted names like `db.host` are nested under its prefix
+    if name.contains('.') && name.split('.').any(str::is_empty) {
+        panic!("{} cannot contains empty segments: `{}`", source, name);
+    }
+}
+
 impl Eq for CommandOption {}
 
 impl PartialEq for CommandOption {
     fn eq(&self, other: &Self) -> bool {
         // This implementation is enough for the purposes of the library
-        // but don't reflect the true equality of this struct
+        // but don't reflect the true equality of this struct,
+        // the comparison is case sensitive regardless of the `NameCase` of the `Context`
         self.name == other.name
     }
 }
@@ -341,12 +785,51 @@ impl Hash for CommandOption {
     }
 }
 
-/// An iterator over the aliases of `CommandOption`.
+// How the names and aliases of the options are compared, the case of the names is always preserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum NameCase {
+    // The names must be equal
+    #[default]
+    Sensitive,
+    // The names are compared ignoring the case, see `ContextBuilder::ignore_option_case`
+    Insensitive,
+}
+
+impl NameCase {
+    // Returns the form of the name used to compare and hash it
+    pub(crate) fn fold<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self {
+            NameCase::Sensitive => Cow::Borrowed(name),
+            NameCase::Insensitive => Cow::Owned(name.to_lowercase()),
+        }
+    }
+
+    // Returns `true` if both names refer to the same option
+    pub(crate) fn names_eq(&self, a: &str, b: &str) -> bool {
+        a == b || (*self == NameCase::Insensitive && self.fold(a) == self.fold(b))
+    }
+
+    // Returns `true` if the option has the given name or alias
+    pub(crate) fn matches(&self, option: &CommandOption, name_or_alias: &str) -> bool {
+        self.names_eq(&option.name, name_or_alias)
+            || option.get_all_aliases().any(|alias| self.names_eq(alias, name_or_alias))
+    }
+}
+
+/// An iterator over the aliases of a `CommandOption` or `Command`.
 #[derive(Debug, Clone)]
 pub struct Aliases<'a> {
     iter: std::slice::Iter<'a, String>
 }
 
+impl<'a> Aliases<'a> {
+    pub(crate) fn new(aliases: &'a [String]) -> Self {
+        Aliases {
+            iter: aliases.iter(),
+        }
+    }
+}
+
 impl<'a> Iterator for Aliases<'a> {
     type Item = &'a String;
 
@@ -362,9 +845,13 @@ impl<'a> ExactSizeIterator for Aliases<'a> {
 }
 
 /// Represents a collection of `CommandOption`s.
+///
+/// The names and aliases are compared case sensitive unless the list belongs to a `Context`
+/// that ignores the case, see `ContextBuilder::ignore_option_case`.
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct OptionList {
     inner: Vec<CommandOption>,
+    case: NameCase,
 }
 
 impl OptionList {
@@ -372,7 +859,35 @@ impl OptionList {
     pub fn new() -> Self {
         OptionList {
             inner: vec![],
+            case: NameCase::Sensitive,
+        }
+    }
+
+    // Sets how the names and aliases are compared, the options must not conflict using the given `NameCase`.
+    pub(crate) fn set_case(&mut self, case: NameCase) {
+        debug_assert!(self.find_duplicate(case).is_none());
+        self.case = case;
+    }
+
+    /// Constructs an `OptionList` from the given options,
+    /// or returns `Err` with the first duplicated option.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{CommandOption, OptionList};
+    ///
+    /// let options = OptionList::try_from_iter(vec![CommandOption::new("a"), CommandOption::new("b")]);
+    /// assert_eq!(options.unwrap().len(), 2);
+    ///
+    /// let duplicated = OptionList::try_from_iter(vec![CommandOption::new("a"), CommandOption::new("a")]);
+    /// assert_eq!(duplicated.unwrap_err().get_name(), "a");
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = CommandOption>>(iter: I) -> std::result::Result<Self, CommandOption> {
+        let mut options = OptionList::new();
+        for option in iter {
+            options.add(option)?;
         }
+        Ok(options)
     }
 
     /// Adds the specified `CommandOption`.
@@ -380,7 +895,12 @@ impl OptionList {
     /// # Returns
     /// `false` if there is an option with the same alias than the provided one.
     pub fn add(&mut self, option: CommandOption) -> std::result::Result<(), CommandOption> {
-        if self.is_option_duplicate(&option) {
+        self.add_with(option, self.case)
+    }
+
+    // Adds the specified `CommandOption` if don't conflicts with other option using the given `NameCase`.
+    pub(crate) fn add_with(&mut self, option: CommandOption, case: NameCase) -> std::result::Result<(), CommandOption> {
+        if self.is_option_duplicate(&option, case) {
             return Err(option);
         }
 
@@ -390,20 +910,41 @@ impl OptionList {
 
     /// Adds the specified `CommandOption` or replace it it already exists,
     pub fn add_or_replace(&mut self, option: CommandOption) {
-        if self.inner.contains(&option) {
-            let pos = self.inner.iter().position(|o| o.get_name() == option.get_name()).unwrap();
-            self.inner[pos] = option;
-        } else {
-            self.add(option).unwrap();
+        let case = self.case;
+        match self.inner.iter().position(|o| case.names_eq(&o.name, &option.name)) {
+            Some(pos) => self.inner[pos] = option,
+            None => self.add(option).unwrap(),
         }
     }
 
     /// Returns the `CommandOption` with the given name or alias or `None`
     /// if not found.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Context};
+    ///
+    /// let command = Command::new("MyApp").option(CommandOption::new("Format"));
+    /// assert!(command.get_options().get("format").is_none());
+    ///
+    /// let context = Context::builder(command).ignore_option_case(true).build();
+    /// assert_eq!(context.root().get_options().get("format").unwrap().get_name(), "Format");
+    /// ```
     pub fn get<S: AsRef<str>>(&self, name_or_alias: S) -> Option<&CommandOption> {
-        self.inner.iter().find(|o| {
-            o.name == name_or_alias.as_ref() || o.get_aliases().any(|s| s == name_or_alias.as_ref())
-        })
+        self.get_with(name_or_alias, self.case)
+    }
+
+    // Returns the `CommandOption` with the given name or alias using the given `NameCase` or `None`
+    // if not found.
+    pub(crate) fn get_with<S: AsRef<str>>(&self, name_or_alias: S, case: NameCase) -> Option<&CommandOption> {
+        self.inner.iter().find(|o| case.matches(o, name_or_alias.as_ref()))
+    }
+
+    // Returns a mutable reference to the `CommandOption` with the given name or alias or `None`
+    // if not found.
+    pub(crate) fn get_mut<S: AsRef<str>>(&mut self, name_or_alias: S) -> Option<&mut CommandOption> {
+        let case = self.case;
+        self.inner.iter_mut().find(|o| case.matches(o, name_or_alias.as_ref()))
     }
 
     /// Returns the `CommandOption` with the given name or `None` if not found.
@@ -418,29 +959,37 @@ impl OptionList {
         self.inner.iter().find(|opt| opt.has_alias(alias.as_ref()))
     }
 
-    /// Converts the argument value of the given option to the type `T` or results `Err` if:
+    /// Converts the argument value of the given option name or alias to the type `T`
+    /// or results `Err` if:
     /// * The option is not found.
     /// * The option takes no arguments.
     /// * The option takes more than 1 argument.
+    /// * The argument have not exactly 1 value.
     /// * The argument value parse fail.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("times").alias("t").arg(Argument::new()))
+    ///     .option(CommandOption::new("verbose"))
+    ///     .parse_from(vec!["-t", "3", "--verbose"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.options().convert::<u32>("times").unwrap(), 3);
+    /// assert_eq!(result.options().convert::<u32>("t").unwrap(), 3);
+    /// assert!(result.options().convert::<u32>("verbose").is_err());
+    /// ```
     pub fn convert<T>(&self, option: &str) -> Result<T>
     where
         T: FromStr + 'static,
         <T as FromStr>::Err: Display {
-        match self.get(option) {
-            Some(opt) => {
-                opt.get_arg()
-                    .unwrap_or_else(|| panic!("`{}` takes no arguments", option))
-                    .convert()
-            },
-            None => Err(Error::new(
-                ErrorKind::Other,
-                format!("cannot find option named '{}'", option))
-            )
-        }
+        self.single_arg(option)?.convert()
     }
 
-    /// Converts all the argument values of the given option to the type `T` or results `Err` if:
+    /// Converts all the argument values of the given option name or alias to the type `T`
+    /// or results `Err` if:
     /// * The option is not found.
     /// * The option takes no arguments.
     /// * The option takes more than 1 argument.
@@ -449,34 +998,75 @@ impl OptionList {
         where
             T: FromStr + 'static,
             <T as FromStr>::Err: Display {
-        match self.get(option) {
-            Some(opt) => {
-                opt.get_arg()
-                    .unwrap_or_else(|| panic!("`{}` takes no arguments", option))
-                    .convert_all()
-            },
-            None => Err(Error::new(
-                ErrorKind::Other,
-                format!("cannot find option named '{}'", option))
-            )
-        }
+        self.single_arg(option)?.convert_all()
     }
 
     /// Returns the `Argument` of the option with the given name or alias or
-    /// `None` if the option cannot be found or have more than 1 argument.
+    /// `None` if the option cannot be found, takes no arguments or takes more than 1 argument,
+    /// use `get_args` for those options.
     pub fn get_arg<S: AsRef<str>>(&self, option: S) -> Option<&Argument> {
-        self.get(option.as_ref()).map(|o| o.get_arg()).flatten()
+        self.get(option.as_ref()).and_then(|o| o.get_arg())
     }
 
     /// Returns the `ArgumentList` of the option with the given name or alias, or `None`
-    /// if the option canno tbe found.
+    /// if the option cannot be found. The `ArgumentList` is empty if the option takes no arguments.
     pub fn get_args<S: AsRef<str>>(&self, option: S) -> Option<&ArgumentList> {
         self.get(option.as_ref()).map(|o| o.get_args())
     }
 
+    // Returns the single `Argument` of the given option or an error
+    fn single_arg(&self, option: &str) -> Result<&Argument> {
+        let opt = self.get(option).ok_or_else(|| {
+            Error::new(ErrorKind::Other, format!("cannot find option named '{}'", option))
+        })?;
+
+        match opt.get_args().len() {
+            0 => Err(Error::new(ErrorKind::Other, format!("`{}` takes no arguments", option))),
+            1 => Ok(opt.get_arg().unwrap()),
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                format!("`{}` takes more than 1 argument", option),
+            )),
+        }
+    }
+
     /// Returns `true` if there is an option with the given name or alias.
     pub fn contains<S: AsRef<str>>(&self, option: S) -> bool {
-        self.get(option).is_some()
+        self.contains_with(option, self.case)
+    }
+
+    // Returns `true` if there is an option with the given name or alias using the given `NameCase`.
+    pub(crate) fn contains_with<S: AsRef<str>>(&self, option: S, case: NameCase) -> bool {
+        self.get_with(option, case).is_some()
+    }
+
+    // Returns the first option that conflicts with a previous one using the given `NameCase`, if any.
+    pub(crate) fn find_duplicate(&self, case: NameCase) -> Option<&CommandOption> {
+        self.inner.iter().enumerate().find_map(|(index, option)| {
+            let is_duplicate = self.inner[..index].iter().any(|other| conflicts(other, option, case));
+            if is_duplicate { Some(option) } else { None }
+        })
+    }
+
+    /// Returns the number of times the option with the given name or alias was passed,
+    /// or 0 if not found or only have default values.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("verbose").alias("v").counted(true))
+    ///     .option(CommandOption::new("quiet"))
+    ///     .parse_from(vec!["-v", "-v", "--quiet"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.options().get_count("v"), 2);
+    /// assert_eq!(result.options().get_count("quiet"), 1);
+    /// assert_eq!(result.options().get_count("color"), 0);
+    /// ```
+    pub fn get_count<S: AsRef<str>>(&self, option: S) -> usize {
+        self.get(option).map_or(0, |o| o.occurrences())
     }
 
     /// Returns the number of options in this collection.
@@ -501,12 +1091,27 @@ impl OptionList {
         }
     }
 
-    fn is_option_duplicate(&self, option: &CommandOption) -> bool {
+    /// Returns an `ExactSizeIterator` over the `CommandOption` of this collection in the order
+    /// they are shown in the `help`, see [`CommandOption::display_order`].
+    pub fn iter_sorted(&self) -> std::vec::IntoIter<&CommandOption> {
+        let mut options = self.inner.iter().collect::<Vec<&CommandOption>>();
+        options.sort_by(|a, b| {
+            cmp_display_order((a.display_order, &a.name), (b.display_order, &b.name))
+        });
+        options.into_iter()
+    }
+
+    fn is_option_duplicate(&self, option: &CommandOption, case: NameCase) -> bool {
         // Check if there if any option that match the new option `alias` or `name`
-        self.contains(&option.name) || option.get_aliases().any(|alias| self.contains(alias))
+        self.inner.iter().any(|other| conflicts(other, option, case))
     }
 }
 
+// Returns `true` if the name or any alias of `option` is the name or alias of `other`
+fn conflicts(other: &CommandOption, option: &CommandOption, case: NameCase) -> bool {
+    case.matches(other, &option.name) || option.get_all_aliases().any(|alias| case.matches(other, alias))
+}
+
 /// An iterator over the `CommandOption`s of an option list.
 #[derive(Debug, Clone)]
 pub struct Iter<'a> {
@@ -568,6 +1173,30 @@ impl IntoIterator for OptionList {
     }
 }
 
+impl FromIterator<CommandOption> for OptionList {
+    /// Constructs an `OptionList` from the given options.
+    ///
+    /// # Panics
+    /// If there is duplicated options.
+    fn from_iter<I: IntoIterator<Item = CommandOption>>(iter: I) -> Self {
+        OptionList::try_from_iter(iter)
+            .unwrap_or_else(|option| panic!("duplicated option: `{}`", option.get_name()))
+    }
+}
+
+impl Extend<CommandOption> for OptionList {
+    /// Adds the given options to this list.
+    ///
+    /// # Panics
+    /// If there is duplicated options.
+    fn extend<I: IntoIterator<Item = CommandOption>>(&mut self, iter: I) {
+        for option in iter {
+            self.add(option)
+                .unwrap_or_else(|option| panic!("duplicated option: `{}`", option.get_name()));
+        }
+    }
+}
+
 impl Index<&str> for OptionList {
     type Output = CommandOption;
 
@@ -583,6 +1212,12 @@ impl Index<&str> for OptionList {
 mod tests {
     use super::*;
 
+    #[test]
+    #[should_panic(expected = "`run` cannot take a raw argument: `command`")]
+    fn option_raw_arg_test() {
+        CommandOption::new("run").arg(Argument::zero_or_more("command").raw(true));
+    }
+
     #[test]
     #[should_panic(expected = "option `name` cannot be empty")]
     fn option_empty_name_test() {
@@ -596,15 +1231,48 @@ mod tests {
     }
 
     #[test]
+    #[should_panic(expected = "option `name` cannot contains whitespaces: `my option`")]
     fn option_name_with_whitespaces_test() {
         CommandOption::new("my option");
     }
 
     #[test]
+    #[should_panic(expected = "option `alias` cannot contains whitespaces: `m o`")]
     fn option_alias_with_whitespaces_test() {
         CommandOption::new("test").alias("m o");
     }
 
+    #[test]
+    #[should_panic(expected = "option `name` cannot start with `-`: `--weird`")]
+    fn option_name_with_prefix_test() {
+        CommandOption::new("--weird");
+    }
+
+    #[test]
+    #[should_panic(expected = "option `alias` cannot start with `-`: `-w`")]
+    fn option_alias_with_prefix_test() {
+        CommandOption::new("weird").alias("-w");
+    }
+
+    #[test]
+    #[should_panic(expected = "option `name` cannot contains empty segments: `db..host`")]
+    fn option_name_empty_segment_test() {
+        CommandOption::new("db..host");
+    }
+
+    #[test]
+    #[should_panic(expected = "option `name` cannot contains the assign operator `=`: `a=b`")]
+    fn option_name_with_assign_operator_test() {
+        CommandOption::new("a=b");
+    }
+
+    #[test]
+    fn option_unicode_name_test() {
+        let opt = CommandOption::new("größe").alias("ñ");
+        assert_eq!(opt.get_name(), "größe");
+        assert!(opt.has_alias("ñ"));
+    }
+
     #[test]
     fn alias_test() {
         let opt = CommandOption::new("name").alias("n").alias("nm");
@@ -619,6 +1287,20 @@ mod tests {
         assert!(!opt.get_aliases().any(|s| s == "name"));
     }
 
+    #[test]
+    fn hidden_alias_test() {
+        let opt = CommandOption::new("color").alias("c").hidden_alias("colour");
+
+        assert!(opt.has_alias("c"));
+        assert!(opt.has_alias("colour"));
+        assert_eq!(opt.get_aliases().collect::<Vec<_>>(), vec!["c"]);
+        assert_eq!(opt.get_hidden_aliases().collect::<Vec<_>>(), vec!["colour"]);
+
+        let options = OptionList::from_iter(vec![opt]);
+        assert_eq!(options.get("colour").unwrap().get_name(), "color");
+        assert!(options.contains("colour"));
+    }
+
     #[test]
     fn description_test() {
         let opt = CommandOption::new("date").description("Sets the date");
@@ -653,6 +1335,53 @@ mod tests {
         assert!(opt2.allow_multiple());
     }
 
+    #[test]
+    fn counted_test() {
+        let opt = CommandOption::new("verbose").counted(true);
+        assert!(opt.is_counted());
+        assert!(opt.allow_multiple());
+
+        let opt = opt.counted(false);
+        assert!(!opt.is_counted());
+        assert!(!opt.allow_multiple());
+    }
+
+    #[test]
+    #[should_panic(expected = "`verbose` is counted and cannot take arguments")]
+    fn counted_with_arg_test() {
+        CommandOption::new("verbose").counted(true).arg(Argument::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "`level` cannot be counted because takes arguments")]
+    fn counted_after_arg_test() {
+        CommandOption::new("level").arg(Argument::new()).counted(true);
+    }
+
+    #[test]
+    fn occurrences_test() {
+        let opt1 = CommandOption::new("header").min_occurrences(1).max_occurrences(3);
+        assert_eq!(opt1.get_min_occurrences(), Some(1));
+        assert_eq!(opt1.get_max_occurrences(), Some(3));
+        assert!(opt1.allow_multiple());
+
+        let opt2 = CommandOption::new("header").max_occurrences(1);
+        assert_eq!(opt2.get_min_occurrences(), None);
+        assert!(!opt2.allow_multiple());
+    }
+
+    #[test]
+    #[should_panic(expected = "`min_occurrences` is greater than `max_occurrences`: 3 > 2")]
+    fn invalid_occurrences_test() {
+        CommandOption::new("header").max_occurrences(2).min_occurrences(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "`max_occurrences` cannot be 0")]
+    fn zero_max_occurrences_test() {
+        CommandOption::new("header").max_occurrences(0);
+    }
+
     #[test]
     fn require_assign_test() {
         let opt1 = CommandOption::new("values");
@@ -757,6 +1486,42 @@ mod tests {
         assert!(options.get_arg("verbose").is_some());
     }
 
+    #[test]
+    fn options_convert_test() {
+        let mut size = Argument::with_name("size");
+        size.set_values(["10"]).unwrap();
+
+        let mut values = Argument::one_or_more("values");
+        values.set_values(["1", "2"]).unwrap();
+
+        let mut from = Argument::with_name("from");
+        from.set_values(["1"]).unwrap();
+
+        let options = OptionList::try_from_iter(vec![
+            CommandOption::new("size").alias("s").arg(size),
+            CommandOption::new("values").arg(values),
+            CommandOption::new("verbose"),
+            CommandOption::new("range").arg(from).arg(Argument::with_name("to")),
+        ])
+        .unwrap();
+
+        assert_eq!(options.convert::<u32>("size").unwrap(), 10);
+        assert_eq!(options.convert::<u32>("s").unwrap(), 10);
+        assert_eq!(options.convert_all::<u32>("s").unwrap(), vec![10]);
+        assert_eq!(options.convert_all::<u32>("values").unwrap(), vec![1, 2]);
+        assert!(options.convert::<u32>("values").is_err());
+
+        // Not found, no arguments or more than 1 argument
+        assert!(options.convert::<u32>("other").is_err());
+        assert!(options.convert::<u32>("verbose").is_err());
+        assert!(options.convert_all::<u32>("verbose").is_err());
+        assert!(options.convert::<u32>("range").is_err());
+        assert!(options.get_arg("verbose").is_none());
+        assert!(options.get_arg("range").is_none());
+        assert_eq!(options.get_args("verbose").unwrap().len(), 0);
+        assert_eq!(options.get_args("range").unwrap().len(), 2);
+    }
+
     #[test]
     fn options_add_duplicated_test() {
         let mut options = OptionList::new();
@@ -772,6 +1537,37 @@ mod tests {
         assert!(options.add(CommandOption::new("value").alias("v")).is_err());
     }
 
+    #[test]
+    fn options_name_case_test() {
+        let mut options = OptionList::new();
+        options.add(CommandOption::new("Verbose").alias("V")).unwrap();
+
+        // `add`, `get` and `contains` are case sensitive
+        assert!(options.get("verbose").is_none());
+        assert!(options.contains("V"));
+        assert!(!options.contains("v"));
+
+        let case = NameCase::Insensitive;
+        assert_eq!(options.get_with("VERBOSE", case).unwrap().get_name(), "Verbose");
+        assert!(options.contains_with("v", case));
+        assert!(options.add_with(CommandOption::new("verbose"), case).is_err());
+        assert!(options.add_with(CommandOption::new("values").alias("v"), case).is_err());
+        assert!(options.find_duplicate(case).is_none());
+
+        options.add(CommandOption::new("verbose")).unwrap();
+        assert!(options.find_duplicate(NameCase::Sensitive).is_none());
+        assert_eq!(options.find_duplicate(case).unwrap().get_name(), "verbose");
+    }
+
+    #[test]
+    fn name_case_test() {
+        assert!(NameCase::Sensitive.names_eq("color", "color"));
+        assert!(!NameCase::Sensitive.names_eq("color", "Color"));
+        assert!(NameCase::Insensitive.names_eq("color", "COLOR"));
+        assert!(NameCase::Insensitive.names_eq("Número", "NÚMERO"));
+        assert_eq!(NameCase::Insensitive.fold("ÑANDÚ"), "ñandú");
+    }
+
     #[test]
     fn option_list_indexer_test() {
         let mut options = OptionList::new();
@@ -781,4 +1577,46 @@ mod tests {
         assert_eq!(options["number"].get_name(), "number");
         assert_eq!(options["enable"].get_name(), "enable");
     }
+
+    #[test]
+    fn option_list_from_iter_test() {
+        let options = vec!["a", "b", "c"]
+            .into_iter()
+            .map(CommandOption::new)
+            .collect::<OptionList>();
+
+        assert_eq!(options.len(), 3);
+        assert!(options.contains("a"));
+        assert!(options.contains("c"));
+
+        let error = OptionList::try_from_iter(vec![
+            CommandOption::new("a").alias("x"),
+            CommandOption::new("b").alias("x"),
+        ]);
+        assert_eq!(error.unwrap_err().get_name(), "b");
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicated option: `a`")]
+    fn option_list_from_iter_duplicated_test() {
+        let _: OptionList = vec![CommandOption::new("a"), CommandOption::new("a")]
+            .into_iter()
+            .collect();
+    }
+
+    #[test]
+    fn option_list_extend_test() {
+        let mut options = OptionList::new();
+        options.extend(vec![CommandOption::new("a"), CommandOption::new("b")]);
+        options.extend(vec![CommandOption::new("c")]);
+        assert_eq!(options.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicated option: `b`")]
+    fn option_list_extend_duplicated_test() {
+        let mut options = OptionList::new();
+        options.extend(vec![CommandOption::new("a"), CommandOption::new("b")]);
+        options.extend(vec![CommandOption::new("b")]);
+    }
 }
@@ -1,10 +1,13 @@
 use crate::args::ArgumentList;
 use crate::command::Command;
-use crate::option::OptionList;
+use crate::option::{CommandOption, OptionList};
+use crate::error::{Error, ErrorKind, Result};
 use crate::Argument;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::slice::Iter;
 use std::str::FromStr;
+use std::time::Duration;
 
 /// Represents the result of a parse operation
 /// and provides a set of methods to query over the values.
@@ -12,7 +15,13 @@ use std::str::FromStr;
 pub struct ParseResult {
     command: Command,
     options: OptionList,
+    global_options: OptionList,
     args: ArgumentList,
+    ignored: Vec<String>,
+    path: Vec<String>,
+    help_requested: bool,
+    version_requested: bool,
+    metrics: Option<ParseMetrics>,
 }
 
 impl ParseResult {
@@ -21,10 +30,59 @@ impl ParseResult {
         ParseResult {
             command,
             options,
+            global_options: OptionList::new(),
             args,
+            ignored: Vec::new(),
+            path: Vec::new(),
+            help_requested: false,
+            version_requested: false,
+            metrics: None,
         }
     }
 
+    // Sets the global options of the context that were passed or have default values.
+    pub(crate) fn with_global_options(mut self, global_options: OptionList) -> Self {
+        self.global_options = global_options;
+        self
+    }
+
+    // Sets the values ignored by a lenient parser.
+    pub(crate) fn with_ignored(mut self, ignored: Vec<String>) -> Self {
+        self.ignored = ignored;
+        self
+    }
+
+    // Sets the names of the subcommands from the root to the executing command.
+    pub(crate) fn with_path(mut self, path: Vec<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    // Returns the names of the subcommands from the root to the executing command.
+    pub(crate) fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    // Sets the metrics recorded by the parser.
+    pub(crate) fn with_metrics(mut self, metrics: ParseMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the metrics recorded by the parser, or `None` if are not enabled.
+    ///
+    /// See `ContextBuilder::metrics`.
+    pub fn metrics(&self) -> Option<&ParseMetrics> {
+        self.metrics.as_ref()
+    }
+
+    // Sets whether the help or version was requested, the parser stops before validate the input.
+    pub(crate) fn with_requests(mut self, help_requested: bool, version_requested: bool) -> Self {
+        self.help_requested = help_requested;
+        self.version_requested = version_requested;
+        self
+    }
+
     // Returns the executing command.
     #[doc(hidden)]
     pub fn executing_command(&self) -> &Command {
@@ -56,6 +114,25 @@ impl ParseResult {
         &self.options
     }
 
+    /// Returns the global options of the `Context` passed in any position of the command-line,
+    /// or that have default values, these are not included in `options`.
+    ///
+    /// See `ContextBuilder::global_option`.
+    pub fn global_options(&self) -> &OptionList {
+        &self.global_options
+    }
+
+    /// Returns the number of times the option with the given name or alias was passed,
+    /// including the global options of the `Context`, or 0 if was not passed.
+    ///
+    /// See `CommandOption::counted`.
+    pub fn occurrences_of(&self, name: &str) -> usize {
+        match self.options.get_count(name) {
+            0 => self.global_options.get_count(name),
+            count => count,
+        }
+    }
+
     /// Returns the `Argument` passed to the executing command or `None` is there is more than 1 argument.
     pub fn arg(&self) -> Option<&Argument> {
         if self.args.len() == 1 {
@@ -70,6 +147,485 @@ impl ParseResult {
         &self.args
     }
 
+    /// Takes the values of the argument with the given name without cloning them,
+    /// after this the argument have no values.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument};
+    ///
+    /// let mut result = Command::new("MyApp")
+    ///     .arg(Argument::one_or_more("files"))
+    ///     .parse_from(vec!["a.txt", "b.txt"])
+    ///     .unwrap();
+    ///
+    /// let files = result.take_values("files").unwrap();
+    /// assert_eq!(files, vec!["a.txt".to_string(), "b.txt".to_string()]);
+    /// ```
+    pub fn take_values(&mut self, arg_name: &str) -> Option<Vec<String>> {
+        self.args.get_mut(arg_name).map(|arg| arg.take_values())
+    }
+
+    /// Takes the values of the argument of the given option without cloning them,
+    /// returns `None` if the option is not found or takes more than 1 argument.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let mut result = Command::new("MyApp")
+    ///     .option(CommandOption::new("color").arg(Argument::new()))
+    ///     .parse_from(vec!["--color", "red"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.take_option_values("color"), Some(vec!["red".to_string()]));
+    /// ```
+    pub fn take_option_values(&mut self, option_name: &str) -> Option<Vec<String>> {
+        let args = self.options.get_mut(option_name)?.get_args_mut();
+        if args.len() == 1 {
+            args.iter_mut().next().map(|arg| arg.take_values())
+        } else {
+            None
+        }
+    }
+
+    /// Consumes this `ParseResult` and returns the executing command, its options and arguments.
+    pub fn into_parts(self) -> (Command, OptionList, ArgumentList) {
+        (self.command, self.options, self.args)
+    }
+
+    /// Returns the option with the given name or alias as was written in the command-line,
+    /// like: `-t`, or `None` if the option was not passed.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("times").alias("t").arg(Argument::new()))
+    ///     .option(CommandOption::new("color").arg(Argument::new().default("red")))
+    ///     .parse_from(vec!["-t", "2"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.spelling_of("times"), Some("-t"));
+    /// assert_eq!(result.spelling_of("color"), None);
+    /// ```
+    pub fn spelling_of(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|option| option.spelling())
+    }
+
+    /// Returns `true` if the help option was passed or the help command is executing.
+    ///
+    /// When the help is requested the parser stops before check the required options and the arguments,
+    /// so the result may be incomplete.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Argument, Command, Context, Parser};
+    ///
+    /// let command = Command::new("MyApp").arg(Argument::with_name("file"));
+    /// let context = Context::builder(command)
+    ///     .help_option(clapi::default_help_option())
+    ///     .build();
+    ///
+    /// let result = Parser::new(&context).parse(vec!["--help"]).unwrap();
+    /// assert!(result.is_help_requested());
+    /// assert!(!result.is_version_requested());
+    /// ```
+    pub fn is_help_requested(&self) -> bool {
+        self.help_requested
+    }
+
+    /// Returns `true` if the version option was passed or the version command is executing.
+    ///
+    /// When the version is requested the parser stops before check the required options and the arguments,
+    /// so the result may be incomplete.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Argument, Command, Context, Parser};
+    ///
+    /// let command = Command::new("MyApp").version("1.0").arg(Argument::with_name("file"));
+    /// let context = Context::builder(command)
+    ///     .version_option(clapi::default_version_option())
+    ///     .build();
+    ///
+    /// let result = Parser::new(&context).parse(vec!["--version"]).unwrap();
+    /// assert!(result.is_version_requested());
+    /// assert_eq!(result.command_version(), Some("1.0"));
+    /// ```
+    pub fn is_version_requested(&self) -> bool {
+        self.version_requested
+    }
+
+    /// Returns the unknown options and subcommands ignored when parsing with a lenient `Context`.
+    pub fn ignored(&self) -> &[String] {
+        &self.ignored
+    }
+
+    /// Returns the `Argument` of the option with the given name or alias, or the command
+    /// argument with the given name if there is no option.
+    ///
+    /// Returns `None` if not found or the option takes no arguments or more than 1 argument.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("times").alias("t").arg(Argument::new()))
+    ///     .arg(Argument::one_or_more("values"))
+    ///     .parse_from(vec!["-t", "2", "hello", "world"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.get_arg("t").unwrap().get_values(), &["2"]);
+    /// assert_eq!(result.get_arg("values").unwrap().get_values(), &["hello", "world"]);
+    /// ```
+    pub fn get_arg(&self, name: &str) -> Option<&Argument> {
+        if self.options.contains(name) {
+            self.options.get_arg(name)
+        } else {
+            self.args.get(name)
+        }
+    }
+
+    /// Returns the `ArgumentList` of the option with the given name or alias, or `None`
+    /// if not found.
+    pub fn get_args(&self, option: &str) -> Option<&ArgumentList> {
+        self.options.get_args(option)
+    }
+
+    /// Converts the value of the option with the given name or alias, or the command argument
+    /// with the given name if there is no option, to the type `T`.
+    ///
+    /// # Errors
+    /// - If the option or argument is not found.
+    /// - If the option takes no arguments or more than 1 argument.
+    /// - If the argument have not exactly 1 value or the value parse fail.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("times").arg(Argument::new()))
+    ///     .arg(Argument::one_or_more("numbers"))
+    ///     .parse_from(vec!["--times", "2", "1", "2", "3"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.convert::<u32>("times").unwrap(), 2);
+    /// assert_eq!(result.convert_all::<u32>("numbers").unwrap(), vec![1, 2, 3]);
+    /// assert!(result.convert::<u32>("numbers").is_err());
+    /// ```
+    pub fn convert<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr + 'static,
+        <T as FromStr>::Err: Display,
+    {
+        if self.options.contains(name) {
+            self.options.convert(name)
+        } else {
+            self.args.convert(name)
+        }
+    }
+
+    /// Converts all the values of the option with the given name or alias, or the command argument
+    /// with the given name if there is no option, to the type `T`.
+    ///
+    /// # Errors
+    /// - If the option or argument is not found.
+    /// - If the option takes no arguments or more than 1 argument.
+    /// - If any value parse fail.
+    pub fn convert_all<T>(&self, name: &str) -> Result<Vec<T>>
+    where
+        T: FromStr + 'static,
+        <T as FromStr>::Err: Display,
+    {
+        if self.options.contains(name) {
+            self.options.convert_all(name)
+        } else {
+            self.args.convert_all(name)
+        }
+    }
+
+    /// Returns the parts of the value of the option with the given name or alias, or the command
+    /// argument with the given name if there is no option.
+    ///
+    /// Returns `None` if not found, the argument have no capture pattern or have not exactly 1 value.
+    ///
+    /// See `Argument::capture`.
+    pub fn captures_of(&self, name: &str) -> Option<HashMap<String, String>> {
+        self.get_arg(name)?.captures()
+    }
+
+    /// Converts the part with the given name of the value of the option or argument
+    /// with the given name to the type `T`.
+    ///
+    /// # Errors
+    /// - If the option or argument is not found or have no captures, see `ParseResult::captures_of`.
+    /// - If the part is not found or the value parse fail.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .arg(Argument::with_name("address").capture("{host}:{port}"))
+    ///     .parse_from(vec!["localhost:8080"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.convert_capture::<u16>("address", "port").unwrap(), 8080);
+    /// assert!(result.convert_capture::<u16>("address", "host").is_err());
+    /// assert!(result.convert_capture::<u16>("address", "path").is_err());
+    /// ```
+    pub fn convert_capture<T>(&self, name: &str, part: &str) -> Result<T>
+    where
+        T: FromStr + 'static,
+        <T as FromStr>::Err: Display,
+    {
+        let captures = self.captures_of(name).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Other,
+                format!("cannot find captures of '{}'", name),
+            )
+        })?;
+
+        match captures.get(part) {
+            Some(value) => crate::args::try_parse_str(value),
+            None => Err(Error::new(
+                ErrorKind::Other,
+                format!("cannot find part named '{}' in '{}'", part, name),
+            )),
+        }
+    }
+
+    /// Converts the value of the argument of the option with the given name or alias to the type `T`.
+    ///
+    /// # Errors
+    /// - If the option is not present, or is not declared by the executing command.
+    /// - If the option takes no arguments or more than 1 argument.
+    /// - If the argument have not exactly 1 value or the value parse fail.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("times").arg(Argument::new()))
+    ///     .option(CommandOption::new("size").arg(Argument::new()))
+    ///     .parse_from(vec!["--times", "2"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.get_option_value::<u64>("times").unwrap(), 2);
+    /// assert!(result.get_option_value::<u64>("size").is_err());
+    /// ```
+    pub fn get_option_value<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr + 'static,
+        <T as FromStr>::Err: Display,
+    {
+        self.option_arg(name)?.convert()
+    }
+
+    /// Converts all the values of the argument of the option with the given name or alias
+    /// to the type `T`.
+    ///
+    /// # Errors
+    /// - If the option is not present, or is not declared by the executing command.
+    /// - If the option takes no arguments or more than 1 argument.
+    /// - If any value parse fail.
+    pub fn get_option_values<T>(&self, name: &str) -> Result<Vec<T>>
+    where
+        T: FromStr + 'static,
+        <T as FromStr>::Err: Display,
+    {
+        self.option_arg(name)?.convert_all()
+    }
+
+    /// Converts the value of the command argument with the given name to the type `T`.
+    ///
+    /// # Errors
+    /// - If the argument is not found.
+    /// - If the argument have not exactly 1 value or the value parse fail.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .arg(Argument::with_name("count"))
+    ///     .parse_from(vec!["10"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(result.get_arg_value::<u32>("count").unwrap(), 10);
+    /// ```
+    pub fn get_arg_value<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr + 'static,
+        <T as FromStr>::Err: Display,
+    {
+        self.args.convert(name)
+    }
+
+    /// Converts all the values of the command argument with the given name to the type `T`.
+    ///
+    /// # Errors
+    /// - If the argument is not found.
+    /// - If any value parse fail.
+    pub fn get_arg_values<T>(&self, name: &str) -> Result<Vec<T>>
+    where
+        T: FromStr + 'static,
+        <T as FromStr>::Err: Display,
+    {
+        self.args.convert_all(name)
+    }
+
+    /// Returns `true` if the option with the given name or alias was passed,
+    /// if the option takes a `bool` value returns that value, like: `--enable=false`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("verbose"))
+    ///     .option(CommandOption::new("color").arg(Argument::zero_or_one("enable")))
+    ///     .option(CommandOption::new("debug"));
+    ///
+    /// let result = command.parse_from(vec!["--verbose", "--color", "false"]).unwrap();
+    /// assert!(result.flag("verbose"));
+    /// assert!(!result.flag("color"));
+    /// assert!(!result.flag("debug"));
+    /// ```
+    pub fn flag(&self, name: &str) -> bool {
+        match self.options.get(name) {
+            Some(option) => match option.get_arg() {
+                Some(arg) if arg.get_values().len() == 1 => arg.convert::<bool>().unwrap_or(true),
+                _ => true,
+            },
+            None => false,
+        }
+    }
+
+    // Returns the single `Argument` of the given option or an error
+    fn option_arg(&self, name: &str) -> Result<&Argument> {
+        let option = match self.options.get(name) {
+            Some(option) => option,
+            None if self.command.get_options().contains(name) => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("option '{}' is not present", name),
+                ));
+            }
+            None => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("cannot find option named '{}'", name),
+                ));
+            }
+        };
+
+        match option.get_args().len() {
+            0 => Err(Error::new(
+                ErrorKind::Other,
+                format!("option '{}' takes no arguments", name),
+            )),
+            1 => Ok(option.get_arg().unwrap()),
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                format!("option '{}' takes more than 1 argument", name),
+            )),
+        }
+    }
+
+    /// Deserializes the arguments of the option with the given name or alias into `T`,
+    /// where each field of `T` is named as an argument of the option.
+    ///
+    /// If the option was not passed, its declared arguments with the default values are used.
+    /// Requires `serde` feature enable.
+    ///
+    /// If there is no option with the given name, the options nested under it are deserialized
+    /// where each field of `T` is the next segment of its names, like `host` for `db.host`,
+    /// see `ParseResult::nested`. The options without arguments are `true`, and the options
+    /// with several arguments are deserialized like its own struct.
+    ///
+    /// # Errors
+    /// - If the option is not declared by the executing command and no option is nested under it.
+    /// - If an argument is missing or its values cannot be deserialized to the field type.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Replace {
+    ///     from: String,
+    ///     to: String,
+    /// }
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("replace")
+    ///         .arg(Argument::with_name("from"))
+    ///         .arg(Argument::with_name("to")))
+    ///     .parse_from(vec!["--replace", "hello", "bye"])
+    ///     .unwrap();
+    ///
+    /// let replace = result.option_as::<Replace>("replace").unwrap();
+    /// assert_eq!(replace.from, "hello");
+    /// assert_eq!(replace.to, "bye");
+    /// ```
+    ///
+    /// Nested options:
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Db {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("db.host").arg(Argument::new()))
+    ///     .option(CommandOption::new("db.port").arg(Argument::new().default(5432)))
+    ///     .parse_from(vec!["--db.host", "localhost"])
+    ///     .unwrap();
+    ///
+    /// let db = result.option_as::<Db>("db").unwrap();
+    /// assert_eq!(db.host, "localhost");
+    /// assert_eq!(db.port, 5432);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn option_as<T: serde::de::DeserializeOwned>(&self, option: &str) -> Result<T> {
+        let args = self
+            .options
+            .get_args(option)
+            .or_else(|| self.command.get_options().get_args(option));
+
+        match args {
+            Some(args) => crate::serde::option_args::from_args(option, args),
+            None => {
+                let nested = self.nested_options(option);
+                if nested.is_empty() {
+                    return Err(Error::new(ErrorKind::Other, format!("cannot find option named '{}'", option)));
+                }
+
+                crate::serde::option_args::from_nested(option, nested)
+            }
+        }
+    }
+
+    /// Returns the values truncated from the argument with the given name by its `OverflowPolicy`,
+    /// or `None` if the argument is not found or no value was truncated.
+    pub fn overflow_values(&self, arg_name: &str) -> Option<&[String]> {
+        self.args
+            .get(arg_name)
+            .map(|arg| arg.get_overflow_values())
+            .filter(|values| !values.is_empty())
+    }
+
     /// Gets the value of the argument with the given name.
     pub fn value_of(&self, arg_name: &str) -> Option<&str> {
         self.args
@@ -83,6 +639,7 @@ impl ParseResult {
     pub fn values_of(&self, arg_name: &str) -> Option<Values<'_>> {
         if let Some(arg) = self.args.get(arg_name) {
             Some(Values {
+                name: arg.get_name(),
                 values: arg.get_values(),
             })
         } else {
@@ -106,6 +663,7 @@ impl ParseResult {
         if let Some(option) = self.options.get(option_name) {
             let arg = option.get_arg()?;
             Some(Values {
+                name: arg.get_name(),
                 values: arg.get_values(),
             })
         } else {
@@ -113,7 +671,69 @@ impl ParseResult {
         }
     }
 
+    /// Returns the values of the options nested under the given prefix, including the global options,
+    /// keyed by the rest of its name, like `host` and `port` for `db.host` and `db.port`.
+    ///
+    /// The options without arguments have no values, and the options with several arguments
+    /// have an entry for each argument, like `range.min` and `range.max` for `db.range`
+    /// with the arguments `min` and `max`.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Argument, Command, CommandOption};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .option(CommandOption::new("db.host").arg(Argument::new()))
+    ///     .option(CommandOption::new("db.port").arg(Argument::new().default(5432)))
+    ///     .option(CommandOption::new("verbose"))
+    ///     .parse_from(vec!["--db.host", "localhost"])
+    ///     .unwrap();
+    ///
+    /// let db = result.nested("db");
+    /// assert_eq!(db.len(), 2);
+    /// assert_eq!(db["host"].iter().collect::<Vec<_>>(), vec!["localhost"]);
+    /// assert_eq!(db["port"].iter().collect::<Vec<_>>(), vec!["5432"]);
+    /// ```
+    pub fn nested(&self, prefix: &str) -> HashMap<String, Values<'_>> {
+        let mut nested = HashMap::new();
+
+        for (leaf, option) in self.nested_options(prefix) {
+            let args = option.get_args();
+            match args.len() {
+                0 => {
+                    nested.insert(leaf.to_owned(), Values { name: option.get_name(), values: &[] });
+                }
+                1 => {
+                    let arg = option.get_arg().unwrap();
+                    nested.insert(leaf.to_owned(), Values { name: arg.get_name(), values: arg.get_values() });
+                }
+                _ => {
+                    for arg in args {
+                        let key = format!("{}.{}", leaf, arg.get_name());
+                        nested.insert(key, Values { name: arg.get_name(), values: arg.get_values() });
+                    }
+                }
+            }
+        }
+
+        nested
+    }
+
+    // Returns the options nested under the given prefix with the rest of its name, like `host` for `db.host`,
+    // the options of the command are after the global options so take priority over them
+    fn nested_options(&self, prefix: &str) -> Vec<(&str, &CommandOption)> {
+        self.global_options
+            .iter()
+            .chain(self.options.iter())
+            .filter_map(|option| {
+                let leaf = option.get_name().strip_prefix(prefix)?.strip_prefix('.')?;
+                Some((leaf, option))
+            })
+            .collect()
+    }
+
     /// Gets the value of the argument with the given name as a type `T`.
+    #[deprecated(since = "0.1.3", note = "use `ParseResult::convert` instead")]
     pub fn value_of_as<T>(&self, arg_name: &str) -> Option<T>
     where
         T: FromStr + 'static,
@@ -123,6 +743,7 @@ impl ParseResult {
     }
 
     /// Gets the values of the argument as a `Vec<T>`.
+    #[deprecated(since = "0.1.3", note = "use `ParseResult::convert_all` instead")]
     pub fn values_of_as<T>(&self, arg_name: &str) -> Option<Vec<T>>
     where
         T: FromStr + 'static,
@@ -132,6 +753,7 @@ impl ParseResult {
     }
 
     /// Gets the value of the argument of the given option as a type `T`.
+    #[deprecated(since = "0.1.3", note = "use `ParseResult::convert` instead")]
     pub fn value_of_option_as<T>(&self, option_name: &str) -> Option<T>
     where
         T: FromStr + 'static,
@@ -141,6 +763,7 @@ impl ParseResult {
     }
 
     /// Gets the values of the given option as a type `T`.
+    #[deprecated(since = "0.1.3", note = "use `ParseResult::convert_all` instead")]
     pub fn values_of_option_as<T>(&self, option_name: &str) -> Option<Vec<T>>
     where
         T: FromStr + 'static,
@@ -150,9 +773,52 @@ impl ParseResult {
     }
 }
 
+/// Counters and timings of a parse, recorded when `ContextBuilder::metrics` is enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseMetrics {
+    pub(crate) token_count: usize,
+    pub(crate) option_occurrences: usize,
+    pub(crate) recovered_errors: usize,
+    pub(crate) tokenize_time: Duration,
+    pub(crate) parse_time: Duration,
+}
+
+impl ParseMetrics {
+    /// Returns the number of tokens of the command-line.
+    pub fn token_count(&self) -> usize {
+        self.token_count
+    }
+
+    /// Returns the number of times the options were passed, including the global options.
+    pub fn option_occurrences(&self) -> usize {
+        self.option_occurrences
+    }
+
+    /// Returns the number of errors the parser continued after, see `ContextBuilder::collect_errors`.
+    pub fn recovered_errors(&self) -> usize {
+        self.recovered_errors
+    }
+
+    /// Returns the time spent converting the arguments into tokens.
+    pub fn tokenize_time(&self) -> Duration {
+        self.tokenize_time
+    }
+
+    /// Returns the time spent parsing the tokens.
+    pub fn parse_time(&self) -> Duration {
+        self.parse_time
+    }
+
+    /// Returns the time spent tokenizing and parsing.
+    pub fn total_time(&self) -> Duration {
+        self.tokenize_time + self.parse_time
+    }
+}
+
 /// An iterator over the values of an argument or option.
 #[derive(Debug, Clone)]
 pub struct Values<'a> {
+    name: &'a str,
     values: &'a [String],
 }
 
@@ -184,6 +850,68 @@ impl<'a> Values<'a> {
     pub fn inner(&self) -> &'a [String] {
         self.values
     }
+
+    /// Returns an iterator that parses each value to a type `T`.
+    ///
+    /// The errors are `ErrorKind::InvalidArgument` and include the index of the value that failed.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .arg(Argument::one_or_more("ports"))
+    ///     .parse_from(vec!["80", "8080", "http"])
+    ///     .unwrap();
+    ///
+    /// let mut ports = result.values_of("ports").unwrap().parsed::<u16>();
+    /// assert_eq!(ports.next().unwrap().ok(), Some(80));
+    /// assert_eq!(ports.next().unwrap().ok(), Some(8080));
+    /// assert!(ports.next().unwrap().is_err());
+    /// ```
+    pub fn parsed<T>(&self) -> impl Iterator<Item = Result<T>> + 'a
+    where
+        T: FromStr + 'a,
+        <T as FromStr>::Err: Display,
+    {
+        let name = self.name;
+        self.values.iter().enumerate().map(move |(index, value)| {
+            T::from_str(value).map_err(|error| {
+                Error::new(
+                    ErrorKind::InvalidArgument(name.to_owned()),
+                    format!(
+                        "failed to parse `{:?}` at index {} to `{}`: {}",
+                        value,
+                        index,
+                        std::any::type_name::<T>(),
+                        error
+                    ),
+                )
+            })
+        })
+    }
+
+    /// Returns an iterator over the values that can be parsed to a type `T`, skipping the others.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument};
+    ///
+    /// let result = Command::new("MyApp")
+    ///     .arg(Argument::one_or_more("ports"))
+    ///     .parse_from(vec!["80", "http", "8080"])
+    ///     .unwrap();
+    ///
+    /// let ports = result.values_of("ports").unwrap().parsed_ok::<u16>().collect::<Vec<_>>();
+    /// assert_eq!(ports, vec![80, 8080]);
+    /// ```
+    pub fn parsed_ok<T>(&self) -> impl Iterator<Item = T> + 'a
+    where
+        T: FromStr + 'a,
+        <T as FromStr>::Err: Display,
+    {
+        self.parsed().filter_map(|value| value.ok())
+    }
 }
 
 impl<'a> IntoIterator for Values<'a> {
@@ -489,6 +1217,128 @@ mod tests {
         assert!(result5.is_err());
     }
 
+    #[test]
+    fn parse_hyphen_values_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_more("files"))
+            .option(CommandOption::new("pattern").alias("p").arg(Argument::with_name("pattern").allow_hyphen_values(true)))
+            .option(CommandOption::new("exclude").arg(Argument::one_or_more("exclude").allow_hyphen_values(true)))
+            .option(CommandOption::new("color").arg(Argument::with_name("color")))
+            .option(CommandOption::new("verbose").alias("v"));
+
+        let result = parse_with("--pattern --foo a.txt", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("pattern").unwrap().get_values(), &["--foo"]);
+        assert_eq!(result.arg().unwrap().get_values(), &["a.txt"]);
+
+        let result = parse_with("-p -x --exclude -a --b -v -- --c", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("pattern").unwrap().get_values(), &["-x"]);
+        assert_eq!(result.options().get_arg("exclude").unwrap().get_values(), &["-a", "--b"]);
+        assert!(result.options().contains("verbose"));
+        assert_eq!(result.arg().unwrap().get_values(), &["--c"]);
+
+        // The known options takes precedence over the values
+        let error = parse_with("--pattern --verbose", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+        let error = parse_with("--pattern -v", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+        assert!(parse_with("--pattern --", command.clone()).is_err());
+
+        // Only the arguments that allow it takes hyphen values
+        let error = parse_with("--color --foo", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+    }
+
+    #[test]
+    fn parse_raw_arg_test() {
+        let command = Command::new("run")
+            .arg(Argument::with_name("target"))
+            .arg(Argument::zero_or_more("command").raw(true).validator(validate_type::<i64>()))
+            .option(CommandOption::new("jobs").alias("j").arg(Argument::with_name("jobs")))
+            .option(CommandOption::new("features").arg(Argument::one_or_more("features")))
+            .option(CommandOption::new("verbose").alias("v"));
+
+        let result = parse_with("--jobs 2 app -- cargo build --release", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("jobs").unwrap().get_values(), &["2"]);
+        assert_eq!(result.args().get("target").unwrap().get_values(), &["app"]);
+        assert_eq!(result.args().get("command").unwrap().get_values(), &["cargo", "build", "--release"]);
+
+        // The options only take the values before `--`, the values left are for the arguments
+        let result = parse_with("--features a b -j 1 app -- -v --features=c", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("features").unwrap().get_values(), &["a", "b"]);
+        assert!(!result.options().contains("verbose"));
+        assert_eq!(result.args().get("target").unwrap().get_values(), &["app"]);
+        assert_eq!(result.args().get("command").unwrap().get_values(), &["-v", "--features=c"]);
+
+        // The values after `--` are not validated nor split
+        let result = parse_with("-v -j=4 app -- \" x,y \" -- --jobs", command.clone()).unwrap();
+        assert!(result.options().contains("verbose"));
+        assert_eq!(result.options().get_arg("jobs").unwrap().get_values(), &["4"]);
+        assert_eq!(result.args().get("command").unwrap().get_values(), &[" x,y ", "--", "--jobs"]);
+
+        // Without `--` the values after the first argument are passed to the raw argument
+        let result = parse_with("app cargo test", command.clone()).unwrap();
+        assert_eq!(result.args().get("target").unwrap().get_values(), &["app"]);
+        assert_eq!(result.args().get("command").unwrap().get_values(), &["cargo", "test"]);
+
+        let result = parse_with("app --", command.clone()).unwrap();
+        assert!(result.args().get("command").unwrap().get_values().is_empty());
+
+        // Values before `--` are not passed to the last option
+        let error = parse_with("--jobs 2 -- cargo", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+
+        let command = Command::new("run")
+            .arg(Argument::one_or_more("command").raw(true))
+            .option(CommandOption::new("jobs").arg(Argument::with_name("jobs")));
+
+        let result = parse_with("-- --jobs 3", command.clone()).unwrap();
+        assert!(!result.options().contains("jobs"));
+        assert_eq!(result.arg().unwrap().get_values(), &["--jobs", "3"]);
+
+        let error = parse_with("--jobs 3 --", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+    }
+
+    #[test]
+    fn parse_negative_numbers_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_more("values").validator(validate_type::<f64>()))
+            .option(CommandOption::new("offset").alias("o").arg(Argument::with_name("offset")))
+            .option(CommandOption::new("range").arg(Argument::with_name("range").values_count(2)))
+            .option(CommandOption::new("verbose").alias("v"));
+
+        // As command args
+        let result = parse_with("-5 10 -2.5", command.clone()).unwrap();
+        assert_eq!(result.get_arg_values::<f64>("values").unwrap(), vec![-5.0, 10.0, -2.5]);
+
+        let result = parse_with("-v -.5 -1e3", command.clone()).unwrap();
+        assert!(result.options().contains("verbose"));
+        assert_eq!(result.get_arg_values::<f64>("values").unwrap(), vec![-0.5, -1000.0]);
+
+        // As option args
+        let result = parse_with("--offset -5 --range -2.5 -1 -2", command.clone()).unwrap();
+        assert_eq!(result.get_option_value::<i64>("offset").unwrap(), -5);
+        assert_eq!(result.get_option_values::<f64>("range").unwrap(), vec![-2.5, -1.0]);
+        assert_eq!(result.get_arg_values::<f64>("values").unwrap(), vec![-2.0]);
+
+        let result = parse_with("-o=-7 -- -8", command.clone()).unwrap();
+        assert_eq!(result.get_option_value::<i64>("offset").unwrap(), -7);
+        assert_eq!(result.get_arg_values::<f64>("values").unwrap(), vec![-8.0]);
+
+        // Not numbers are still options
+        assert!(matches!(
+            parse_with("-x 10", command.clone()).unwrap_err().kind(),
+            ErrorKind::UnexpectedOption(s) if s == "-x"
+        ));
+        assert!(parse_with("-inf", command.clone()).is_err());
+
+        // A numeric alias takes precedence over the negative number
+        let command = command.option(CommandOption::new("five").alias("5"));
+        let result = parse_with("-5 -3", command).unwrap();
+        assert!(result.options().contains("five"));
+        assert_eq!(result.get_arg_values::<f64>("values").unwrap(), vec![-3.0]);
+    }
+
     #[test]
     fn parse_result_variable_arg_count_test1() {
         let command = Command::new("MyApp")
@@ -721,6 +1571,63 @@ mod tests {
             .arg(Argument::with_name("max").default(10));
     }
 
+    #[test]
+    fn parse_result_arg_default_values_test3() {
+        fn values_of(result: &ParseResult) -> Vec<Vec<String>> {
+            result.args().iter().map(|a| a.get_values().to_vec()).collect()
+        }
+
+        // Each position of the argument with default values
+        for default_index in 0..3 {
+            let mut command = Command::new("MyApp");
+            for (i, name) in ["a", "b", "c"].iter().enumerate() {
+                let arg = Argument::with_name(*name);
+                command = command.arg(if i == default_index { arg.default(0) } else { arg });
+            }
+
+            // 1 value: not enough for the arguments without default values
+            let error = parse_with("1", command.clone()).unwrap_err();
+            assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+
+            // 2 values: the argument with default values keeps its default
+            let result = parse_with("1 2", command.clone()).unwrap();
+            let mut expected = vec![vec!["1".to_owned()], vec!["2".to_owned()]];
+            expected.insert(default_index, vec!["0".to_owned()]);
+            assert_eq!(values_of(&result), expected);
+
+            // 3 values: the values are passed in order
+            let result = parse_with("1 2 3", command.clone()).unwrap();
+            assert_eq!(values_of(&result), vec![vec!["1"], vec!["2"], vec!["3"]]);
+        }
+    }
+
+    #[test]
+    fn parse_result_arg_default_values_test4() {
+        let command = Command::new("MyApp")
+            .arg(Argument::with_name("a").values_count(1..=2).defaults([0, 0]))
+            .arg(Argument::with_name("b"))
+            .arg(Argument::with_name("c"));
+
+        let result = parse_with("1 2", command.clone()).unwrap();
+        assert_eq!(result.args().get("a").unwrap().get_values(), &["0", "0"]);
+        assert_eq!(result.args().get("b").unwrap().get_values(), &["1"]);
+        assert_eq!(result.args().get("c").unwrap().get_values(), &["2"]);
+
+        // The argument with default values takes only the values left
+        let result = parse_with("1 2 3", command.clone()).unwrap();
+        assert_eq!(result.args().get("a").unwrap().get_values(), &["1"]);
+        assert_eq!(result.args().get("b").unwrap().get_values(), &["2"]);
+        assert_eq!(result.args().get("c").unwrap().get_values(), &["3"]);
+
+        let result = parse_with("1 2 3 4", command.clone()).unwrap();
+        assert_eq!(result.args().get("a").unwrap().get_values(), &["1", "2"]);
+        assert_eq!(result.args().get("b").unwrap().get_values(), &["3"]);
+        assert_eq!(result.args().get("c").unwrap().get_values(), &["4"]);
+
+        let error = parse_with("1 2 3 4 5", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+    }
+
     #[test]
     fn parse_result_option_default_values_test1() {
         let command = Command::new("MyApp").option(
@@ -791,6 +1698,739 @@ mod tests {
         assert!(result2.options().get_arg("values").unwrap().contains("4"));
     }
 
+    #[test]
+    fn parse_option_occurrences_test() {
+        let command = Command::new("MyApp")
+            .option(
+                CommandOption::new("header")
+                    .alias("H")
+                    .max_occurrences(3)
+                    .arg(Argument::with_name("header").values_count(2)),
+            )
+            .option(CommandOption::new("verbose").alias("v").min_occurrences(2).max_occurrences(3))
+            .option(CommandOption::new("values").multiple(true).arg(Argument::with_name("values").values_count(2)));
+
+        // Each occurrence takes 2 values
+        let result = parse_with("--header a 1 -H b 2 --header c 3", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("header").unwrap().get_values(), &["a", "1", "b", "2", "c", "3"]);
+
+        let error = parse_with("--header a 1 -H b 2 --header c 3 -H d 4", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+        assert!(error.to_string().contains("'-H' may be given at most 3 times"));
+
+        let error = parse_with("--header a 1 2", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+
+        // Flags also count the occurrences, the minimum is only checked when the option is passed
+        assert!(parse_with("-v -v", command.clone()).unwrap().options().contains("verbose"));
+        assert!(parse_with("", command.clone()).is_ok());
+
+        let error = parse_with("--verbose", command.clone()).unwrap_err();
+        assert!(error.to_string().contains("'--verbose' must be given at least 2 times"));
+
+        let error = parse_with("-v -v -v -v", command.clone()).unwrap_err();
+        assert!(error.to_string().contains("'-v' may be given at most 3 times"));
+
+        // Without limits the values count is checked on all the values
+        assert!(parse_with("--values 1 2", command.clone()).is_ok());
+        assert!(parse_with("--values 1 2 --values 3 4", command.clone()).is_err());
+    }
+
+    #[test]
+    fn parse_lone_dash_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::with_name("file"))
+            .option(CommandOption::new("input").arg(Argument::with_name("input")));
+
+        let result = parse_with("-", command.clone()).unwrap();
+        assert_eq!(result.value_of("file"), Some("-"));
+
+        let result = parse_with("--input - out.txt", command.clone()).unwrap();
+        assert!(result.options().get_arg("input").unwrap().contains("-"));
+        assert_eq!(result.value_of("file"), Some("out.txt"));
+
+        // Not an unexpected option
+        let error = parse_with("-", Command::new("MyApp")).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+    }
+
+    #[test]
+    fn take_values_without_clone_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::one_or_more("files"))
+            .option(CommandOption::new("output").arg(Argument::new()))
+            .option(CommandOption::new("range").arg(Argument::with_name("min")).arg(Argument::with_name("max")));
+
+        let mut result = parse_with("--output out.txt --range 1 2 a.txt b.txt", command).unwrap();
+
+        // The taken `String`s must point to the same buffers of the parsed values
+        let files_ptr = result.args().get("files").unwrap().get_values()[0].as_ptr();
+        let output_ptr = result.options().get_arg("output").unwrap().get_values()[0].as_ptr();
+
+        let files = result.take_values("files").unwrap();
+        assert_eq!(files, vec!["a.txt".to_owned(), "b.txt".to_owned()]);
+        assert_eq!(files[0].as_ptr(), files_ptr);
+        assert!(result.args().get("files").unwrap().get_values().is_empty());
+
+        let output = result.take_option_values("output").unwrap();
+        assert_eq!(output, vec!["out.txt".to_owned()]);
+        assert_eq!(output[0].as_ptr(), output_ptr);
+
+        // Options with multiple arguments or not found
+        assert_eq!(result.take_option_values("range"), None);
+        assert_eq!(result.take_option_values("unknown"), None);
+        assert_eq!(result.take_values("unknown"), None);
+
+        let (command, options, args) = result.into_parts();
+        assert_eq!(command.get_name(), "MyApp");
+        assert!(options.contains("range"));
+        assert!(args.get("files").is_some());
+    }
+
+    #[test]
+    fn parse_error_category_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("mode").required(true).arg(Argument::new()))
+            .arg(Argument::with_name("count").validator(validate_type::<u64>()));
+
+        let error = parse_with("--unknown", command.clone()).unwrap_err();
+        assert!(error.is_usage_error());
+
+        let error = parse_with("10", command.clone()).unwrap_err();
+        assert!(error.is_usage_error());
+
+        let error = parse_with("--mode fast ten", command).unwrap_err();
+        assert!(error.is_validation_error());
+        assert!(!error.is_usage_error());
+        assert!(!error.is_handler_error());
+    }
+
+    #[test]
+    fn parse_subcommand_version_test() {
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .subcommand(Command::new("sub").version("2.0.1"))
+            .subcommand(Command::new("other").subcommand(Command::new("nested")));
+
+        let result = parse_with("sub", command.clone()).unwrap();
+        assert_eq!(result.command_version(), Some("2.0.1"));
+
+        let result = parse_with("other nested", command.clone()).unwrap();
+        assert_eq!(result.command_version(), Some("1.0"));
+
+        let result = parse_with("", command).unwrap();
+        assert_eq!(result.command_version(), Some("1.0"));
+    }
+
+    #[test]
+    fn parse_unversioned_subcommand_test() {
+        let command = Command::new("MyApp").subcommand(Command::new("sub"));
+        let result = parse_with("sub", command).unwrap();
+        assert_eq!(result.command_version(), None);
+    }
+
+    #[test]
+    fn parse_help_and_version_requested_test() {
+        let command = Command::new("MyApp")
+            .version("1.0")
+            .arg(Argument::with_name("file"))
+            .option(CommandOption::new("token").required(true).arg(Argument::new()))
+            .subcommand(Command::new("run").arg(Argument::with_name("script")));
+
+        let context = Context::builder(command)
+            .help_option(crate::default_help_option())
+            .help_command(crate::default_help_command())
+            .version_option(crate::default_version_option())
+            .version_command(crate::default_version_command())
+            .build();
+
+        let parse = |args: &[&str]| Parser::new(&context).parse(args.iter().copied());
+
+        // The required options and arguments are not checked
+        let result = parse(&["--help"]).unwrap();
+        assert!(result.is_help_requested());
+        assert!(!result.is_version_requested());
+
+        let result = parse(&["run", "--help"]).unwrap();
+        assert!(result.is_help_requested());
+        assert_eq!(result.command_name(), "run");
+
+        let result = parse(&["help", "run"]).unwrap();
+        assert!(result.is_help_requested());
+        assert_eq!(result.command_name(), "help");
+
+        let result = parse(&["--version"]).unwrap();
+        assert!(result.is_version_requested());
+        assert!(!result.is_help_requested());
+
+        let result = parse(&["version"]).unwrap();
+        assert!(result.is_version_requested());
+        assert_eq!(result.command_version(), Some("1.0"));
+
+        let result = parse(&["--token", "abc", "file.txt"]).unwrap();
+        assert!(!result.is_help_requested());
+        assert!(!result.is_version_requested());
+
+        assert!(parse(&["file.txt"]).is_err());
+    }
+
+    fn parse_bundled_with(value: &str, command: Command) -> crate::Result<ParseResult> {
+        let context = Context::builder(command).allow_option_bundling(true).build();
+        Parser::new(&context).parse(split_into_args(value))
+    }
+
+    #[test]
+    fn parse_option_bundling_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_more("files"))
+            .option(CommandOption::new("all").alias("a"))
+            .option(CommandOption::new("long").alias("l"))
+            .option(CommandOption::new("human").alias("H"))
+            .option(CommandOption::new("output").alias("o").arg(Argument::with_name("output")))
+            .option(CommandOption::new("include").alias("I").arg(Argument::one_or_more("include")));
+
+        let result = parse_bundled_with("-alH a.txt", command.clone()).unwrap();
+        assert!(result.options().contains("all"));
+        assert!(result.options().contains("long"));
+        assert!(result.options().contains("human"));
+        assert_eq!(result.arg().unwrap().get_values(), &["a.txt"]);
+
+        // The rest of the bundle is the value of the option
+        let result = parse_bundled_with("-aofile.txt a.txt", command.clone()).unwrap();
+        assert!(result.options().contains("all"));
+        assert_eq!(result.options().get_arg("output").unwrap().get_values(), &["file.txt"]);
+        assert_eq!(result.arg().unwrap().get_values(), &["a.txt"]);
+
+        let result = parse_bundled_with("-olH", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("output").unwrap().get_values(), &["lH"]);
+        assert!(!result.options().contains("long"));
+
+        // The last option of the bundle takes the next values
+        let result = parse_bundled_with("-lo file.txt", command.clone()).unwrap();
+        assert!(result.options().contains("long"));
+        assert_eq!(result.options().get_arg("output").unwrap().get_values(), &["file.txt"]);
+
+        let result = parse_bundled_with("-aI src lib -- a.txt", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("include").unwrap().get_values(), &["src", "lib"]);
+        assert_eq!(result.arg().unwrap().get_values(), &["a.txt"]);
+
+        // Long names and negative numbers are not bundles
+        let result = parse_bundled_with("--all -5", command.clone()).unwrap();
+        assert!(result.options().contains("all"));
+        assert_eq!(result.arg().unwrap().get_values(), &["-5"]);
+
+        // Unknown options in the bundle
+        let error = parse_bundled_with("-alx", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedOption("-x".to_owned()));
+        let error = parse_bundled_with("-xal", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedOption("-x".to_owned()));
+
+        // Bundling is disabled by default
+        let error = parse_with("-al", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedOption("-al".to_owned()));
+    }
+
+    #[test]
+    fn parse_option_bundling_with_known_alias_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("all").alias("a"))
+            .option(CommandOption::new("verbose").alias("v"))
+            .option(CommandOption::new("all-verbose").alias("av"));
+
+        // An alias with the same name of the bundle takes precedence
+        let result = parse_bundled_with("-av", command.clone()).unwrap();
+        assert!(result.options().contains("all-verbose"));
+        assert!(!result.options().contains("all"));
+
+        let result = parse_bundled_with("-va", command.clone()).unwrap();
+        assert!(result.options().contains("all"));
+        assert!(result.options().contains("verbose"));
+    }
+
+    #[test]
+    fn parse_option_bundling_requires_assign_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v"))
+            .option(
+                CommandOption::new("color")
+                    .alias("c")
+                    .requires_assign(true)
+                    .arg(Argument::with_name("color")),
+            );
+
+        let result = parse_bundled_with("-v -c=red", command.clone()).unwrap();
+        assert!(result.options().contains("verbose"));
+        assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["red"]);
+
+        // The value of a bundle is not assigned
+        assert!(parse_bundled_with("-vcred", command.clone()).is_err());
+        assert!(parse_bundled_with("-vc red", command.clone()).is_err());
+
+        // A bundle with an assign operator is an option
+        let error = parse_bundled_with("-vc=red", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedOption("-vc".to_owned()));
+    }
+
+    fn parse_lenient_with(value: &str, command: Command) -> crate::Result<ParseResult> {
+        let context = Context::builder(command).lenient(true).build();
+        Parser::new(&context).parse(split_into_args(value))
+    }
+
+    #[test]
+    fn parse_lenient_unknown_options_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::with_name("file"))
+            .option(CommandOption::new("verbose").alias("v"))
+            .option(CommandOption::new("color").arg(Argument::with_name("color")));
+
+        let result = parse_lenient_with(
+            "--verbose --unknown --color=red --legacy=1 -x file.txt",
+            command.clone(),
+        )
+        .unwrap();
+
+        assert!(result.options().contains("verbose"));
+        assert!(result.options().get_arg("color").unwrap().contains("red"));
+        assert_eq!(result.value_of("file"), Some("file.txt"));
+        assert_eq!(result.ignored(), &["--unknown", "--legacy", "-x"]);
+
+        let result = parse_lenient_with("-v file.txt", command).unwrap();
+        assert!(result.ignored().is_empty());
+    }
+
+    #[test]
+    fn parse_env_options_test() {
+        std::env::set_var("PARSE_ENV_TEST_DRY_RUN", "1");
+        std::env::set_var("PARSE_ENV_TEST_NUMBERS", "1,2,3");
+        std::env::set_var("PARSE_ENV_TEST_QUIET", "false");
+        std::env::set_var("PARSE_ENV_TEST_COLOR", "red");
+
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("dry-run"))
+            .option(CommandOption::new("quiet"))
+            .option(CommandOption::new("numbers").arg(Argument::one_or_more("numbers")))
+            .option(CommandOption::new("color").required(true).arg(Argument::new()));
+
+        let context = Context::builder(command).env_prefix("PARSE_ENV_TEST").build();
+        let result = Parser::new(&context).parse(split_into_args("--color blue")).unwrap();
+
+        assert!(result.options().contains("dry-run"));
+        assert!(!result.options().contains("quiet"));
+        assert_eq!(result.options().get_arg("numbers").unwrap().get_values(), &["1", "2", "3"]);
+        assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["blue"]);
+
+        // Required options can be set from the environment
+        let result = Parser::new(&context).parse(split_into_args("")).unwrap();
+        assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["red"]);
+
+        // The values are validated
+        std::env::set_var("PARSE_ENV_TEST_INVALID_NUMBER", "one");
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("invalid-number")
+                .arg(Argument::new().validator(validate_type::<i64>())));
+
+        let context = Context::builder(command).env_prefix("PARSE_ENV_TEST").build();
+        let error = Parser::new(&context).parse(split_into_args("")).unwrap_err();
+        assert!(error.is_validation_error());
+    }
+
+    #[test]
+    fn parse_default_value_if_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("output").arg(Argument::new()))
+            .option(CommandOption::new("verbose"))
+            .option(CommandOption::new("format")
+                .default_value_if("output", None, "text")
+                .default_value_if("output", Some("report.json"), "json")
+                .arg(Argument::new().default("yaml")))
+            .option(CommandOption::new("pretty").default_value_if("format", Some("json"), "true"));
+
+        // Without conditions the argument default value is used
+        let result = parse_with("", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("format").unwrap().get_values(), &["yaml"]);
+        assert!(!result.options().contains("pretty"));
+
+        // The last rule that holds is used
+        let result = parse_with("--output report.txt", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("format").unwrap().get_values(), &["text"]);
+
+        let result = parse_with("--output report.json", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("format").unwrap().get_values(), &["json"]);
+
+        // Conditions see the default values of the options declared before
+        assert!(result.options().contains("pretty"));
+
+        // Passed values take precedence
+        let result = parse_with("--output report.json --format toml", command).unwrap();
+        assert_eq!(result.options().get_arg("format").unwrap().get_values(), &["toml"]);
+        assert!(!result.options().contains("pretty"));
+    }
+
+    #[test]
+    fn parse_default_value_if_precedence_test() {
+        std::env::set_var("PARSE_DEFAULT_IF_TEST_FORMAT", "csv");
+
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("output").alias("o").arg(Argument::new()))
+            .option(CommandOption::new("format")
+                .env("PARSE_DEFAULT_IF_TEST_FORMAT")
+                .default_value_if("o", Some("report.json"), "json")
+                .arg(Argument::new()))
+            .option(CommandOption::new("level")
+                .default_value_if("output", None, "high")
+                .arg(Argument::new().default("low")));
+
+        // Environment variables take precedence over the conditional defaults
+        let result = parse_with("-o report.json", command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("format").unwrap().get_values(), &["csv"]);
+        assert_eq!(result.options().get_arg("level").unwrap().get_values(), &["high"]);
+
+        let result = parse_with("--level low -o report.json", command).unwrap();
+        assert_eq!(result.options().get_arg("level").unwrap().get_values(), &["low"]);
+
+        // The conditional defaults are validated
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("output").arg(Argument::new()))
+            .option(CommandOption::new("color")
+                .default_value_if("output", None, "purple")
+                .arg(Argument::new().valid_values(["red", "blue"])));
+
+        let error = parse_with("--output report.json", command).unwrap_err();
+        assert!(error.is_validation_error());
+    }
+
+    #[test]
+    fn values_parsed_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::one_or_more("ports"))
+            .option(CommandOption::new("ids").arg(Argument::one_or_more("ids")));
+
+        let result = parse_with("--ids 1 2 -- 80 x 8080", command).unwrap();
+
+        let ports = result.values_of("ports").unwrap();
+        let parsed = ports.parsed::<u16>().collect::<Vec<_>>();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].as_ref().ok(), Some(&80));
+        assert_eq!(parsed[2].as_ref().ok(), Some(&8080));
+
+        let error = parsed[1].as_ref().unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("ports".to_owned()));
+        assert!(error.to_string().contains("at index 1"));
+
+        assert_eq!(ports.parsed_ok::<u16>().collect::<Vec<_>>(), vec![80, 8080]);
+
+        let ids = result.values_of_option("ids").unwrap();
+        assert_eq!(ids.parsed_ok::<u32>().sum::<u32>(), 3);
+
+        // The raw iterator
+        let mut iter = ports.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back().map(|s| s.as_str()), Some("8080"));
+        assert_eq!(iter.len(), 2);
+    }
+
+    #[test]
+    fn parse_eoo_as_value_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_more("args"))
+            .option(CommandOption::new("env").arg(Argument::one_or_more("values")))
+            .treat_eoo_as_value(true);
+
+        let result = parse_with("-- a -- b", command.clone()).unwrap();
+        assert_eq!(result.arg().unwrap().get_values(), &["--", "a", "--", "b"]);
+
+        let result = parse_with("--env x -- y", command).unwrap();
+        assert_eq!(result.options().get_arg("env").unwrap().get_values(), &["x", "--", "y"]);
+        assert!(result.arg().unwrap().get_values().is_empty());
+    }
+
+    #[test]
+    fn parse_lenient_unknown_command_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose"))
+            .subcommand(Command::new("build"));
+
+        let result = parse_lenient_with("run --verbose", command.clone()).unwrap();
+        assert_eq!(result.command_name(), "MyApp");
+        assert!(result.options().contains("verbose"));
+        assert_eq!(result.ignored(), &["run"]);
+
+        let error = parse_with("run --verbose", command).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedCommand("run".to_owned()));
+    }
+
+    #[test]
+    fn parse_lenient_still_fails_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("output").required(true).arg(Argument::new()))
+            .option(
+                CommandOption::new("color")
+                    .arg(Argument::new().valid_values(vec!["red", "green", "blue"])),
+            );
+
+        let error = parse_lenient_with("--unknown --color red", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingOption("output".to_owned()));
+
+        let error = parse_lenient_with("--output out.txt --color pink", command.clone()).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidArgument(_)));
+
+        // Strict by default
+        let error = parse_with("--unknown --output out.txt", command).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedOption("--unknown".to_owned()));
+    }
+
+    #[test]
+    fn parse_validator_panic_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("size").arg(Argument::new().validator(|value: &str| {
+                match value.parse::<u32>() {
+                    Ok(0) => panic!("size cannot be zero"),
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(e.to_string()),
+                }
+            })))
+            .arg(Argument::zero_or_more("values"));
+
+        let context = Context::new(command);
+        let mut parser = Parser::new(&context);
+
+        let error = parser.parse(split_into_args("--size 0 a b")).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::ValidatorPanic("size".to_owned()));
+        assert!(error.to_string().contains("size cannot be zero"));
+
+        // The same parser still can be used
+        let result = parser.parse(split_into_args("--size 10 a b")).unwrap();
+        assert_eq!(result.options().convert::<u32>("size").unwrap(), 10);
+        assert_eq!(result.args().get("values").unwrap().get_values(), &["a", "b"]);
+    }
+
+    #[test]
+    fn parse_value_delimiter_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("tags").arg(Argument::one_or_more("tags").value_delimiter(',')))
+            .option(
+                CommandOption::new("sizes")
+                    .requires_assign(true)
+                    .arg(Argument::one_or_more("sizes").value_delimiter(';').validator(validate_type::<u32>())),
+            )
+            .arg(Argument::zero_or_more("values"));
+
+        let context = Context::new(command);
+        let mut parser = Parser::new(&context);
+
+        let result = parser.parse(vec!["--tags=a,b,c"]).unwrap();
+        assert_eq!(result.options().get_arg("tags").unwrap().get_values(), &["a", "b", "c"]);
+
+        let result = parser.parse(vec!["--tags", "a,b", "c", "--", "d"]).unwrap();
+        assert_eq!(result.options().get_arg("tags").unwrap().get_values(), &["a", "b", "c"]);
+        assert_eq!(result.args().get("values").unwrap().get_values(), &["d"]);
+
+        let result = parser.parse(vec!["--tags", "\"a,b\",c"]).unwrap();
+        assert_eq!(result.options().get_arg("tags").unwrap().get_values(), &["a,b", "c"]);
+
+        let result = parser.parse(vec!["--sizes=1;2;3"]).unwrap();
+        assert_eq!(result.options().convert_all::<u32>("sizes").unwrap(), vec![1, 2, 3]);
+
+        // Empty values are rejected
+        let error = parser.parse(vec!["--tags", "a,,b"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidExpression);
+        let error = parser.parse(vec!["--sizes=1;;2"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidExpression);
+
+        // The error points to the invalid value
+        let error = parser.parse(vec!["--sizes=1;x;3"]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("sizes".to_owned()));
+        assert!(error.to_string().contains("`x` in `1;x;3`"), "{}", error);
+
+        // `requires_assign` still requires the assign operator
+        assert!(parser.parse(vec!["--sizes", "1;2"]).is_err());
+    }
+
+    #[test]
+    fn parse_allow_empty_values_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("prefix").arg(Argument::with_name("prefix")))
+            .option(CommandOption::new("name").arg(Argument::with_name("name").allow_empty_values(false)))
+            .arg(Argument::zero_or_one("value").allow_empty_values(false));
+
+        let context = Context::new(command);
+        let mut parser = Parser::new(&context);
+
+        // Empty values are allowed by default
+        let result = parser.parse(vec!["--prefix", ""]).unwrap();
+        assert_eq!(result.options().get_arg("prefix").unwrap().get_values(), &[""]);
+
+        let result = parser.parse(vec!["--name", "Alan", "Turing"]).unwrap();
+        assert_eq!(result.options().get_arg("name").unwrap().get_values(), &["Alan"]);
+        assert_eq!(result.args().get("value").unwrap().get_values(), &["Turing"]);
+
+        let error = parser.parse(vec!["--name", ""]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("name".to_owned()));
+        assert!(error.to_string().contains("value for 'name' cannot be empty"), "{}", error);
+
+        let error = parser.parse(vec!["--name="]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("name".to_owned()));
+        assert!(error.to_string().contains("in option '--name'"), "{}", error);
+
+        let error = parser.parse(vec![""]).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("value".to_owned()));
+
+        let result = parser.parse(vec!["--prefix="]).unwrap();
+        assert_eq!(result.options().get_arg("prefix").unwrap().get_values(), &[""]);
+    }
+
+    #[test]
+    fn get_option_and_arg_value_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("times").alias("t").arg(Argument::new().default(1)))
+            .option(CommandOption::new("sizes").arg(Argument::one_or_more("sizes")))
+            .option(CommandOption::new("range").arg(Argument::with_name("min")).arg(Argument::with_name("max")))
+            .option(CommandOption::new("verbose"))
+            .option(CommandOption::new("scale").arg(Argument::new()))
+            .arg(Argument::zero_or_more("values"));
+
+        let result = parse_with("--sizes 1 2 3 --verbose --range 0 9 -- 4 5", command.clone()).unwrap();
+
+        // Options with default values
+        assert_eq!(result.get_option_value::<u64>("times").unwrap(), 1);
+        assert_eq!(result.get_option_value::<u64>("t").unwrap(), 1);
+
+        assert_eq!(result.get_option_values::<u8>("sizes").unwrap(), vec![1, 2, 3]);
+        assert_eq!(result.get_arg_values::<u8>("values").unwrap(), vec![4, 5]);
+        assert!(result.get_arg_value::<u8>("values").is_err());
+
+        let not_present = result.get_option_value::<u64>("scale").unwrap_err();
+        assert!(not_present.to_string().contains("option 'scale' is not present"));
+
+        let not_found = result.get_option_value::<u64>("color").unwrap_err();
+        assert!(not_found.to_string().contains("cannot find option named 'color'"));
+
+        let no_args = result.get_option_value::<u64>("verbose").unwrap_err();
+        assert!(no_args.to_string().contains("option 'verbose' takes no arguments"));
+
+        let many_args = result.get_option_value::<u64>("range").unwrap_err();
+        assert!(many_args.to_string().contains("option 'range' takes more than 1 argument"));
+
+        let parse_error = result.get_option_values::<bool>("sizes").unwrap_err();
+        assert!(parse_error.to_string().contains("failed to parse"));
+
+        let result = parse_with("--times 2 10", command).unwrap();
+        assert_eq!(result.get_option_value::<u64>("times").unwrap(), 2);
+        assert_eq!(result.get_arg_value::<u8>("values").unwrap(), 10);
+    }
+
+    #[cfg(feature = "typing")]
+    #[test]
+    fn get_option_value_typing_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("times").arg(Argument::new().validator(validate_type::<u64>())));
+
+        let result = parse_with("--times 2", command).unwrap();
+        assert_eq!(result.get_option_value::<u64>("times").unwrap(), 2);
+        assert!(result.get_option_value::<i32>("times").is_err());
+    }
+
+    #[test]
+    fn flag_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v"))
+            .option(CommandOption::new("color").arg(Argument::zero_or_one("enable").validator(validate_type::<bool>())))
+            .option(CommandOption::new("level").arg(Argument::new()));
+
+        let result = parse_with("-v --color=false --level 3", command.clone()).unwrap();
+        assert!(result.flag("verbose"));
+        assert!(result.flag("v"));
+        assert!(!result.flag("color"));
+        assert!(result.flag("level"));
+        assert!(!result.flag("unknown"));
+
+        let result = parse_with("--color", command.clone()).unwrap();
+        assert!(result.flag("color"));
+        assert!(!result.flag("verbose"));
+
+        let result = parse_with("--color true", command).unwrap();
+        assert!(result.flag("color"));
+    }
+
+    #[test]
+    fn parse_conflicting_options_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("json").conflicts_with("y").conflicts_with("format"))
+            .option(CommandOption::new("yaml").alias("y"))
+            .option(CommandOption::new("format").arg(Argument::new().default("text")));
+
+        assert!(parse_with("--json", command.clone()).is_ok());
+        assert!(parse_with("--yaml --format csv", command.clone()).is_ok());
+
+        let error = parse_with("-y --json", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::ConflictingOptions("--json".to_owned(), "-y".to_owned()));
+        assert!(error.is_usage_error());
+
+        let error = parse_with("--json --format csv", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::ConflictingOptions("--json".to_owned(), "--format".to_owned()));
+
+        // Default values are not passed options
+        let result = parse_with("--json", command).unwrap();
+        assert!(result.options().contains("format"));
+    }
+
+    #[test]
+    fn parse_options_requirements_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("output").alias("o").requires("f").arg(Argument::new()))
+            .option(CommandOption::new("format").alias("f").arg(Argument::new()))
+            .option(CommandOption::new("encoding").arg(Argument::new().default("utf8")))
+            .option(CommandOption::new("mode").required(true).requires("encoding").arg(Argument::new()))
+            .option(CommandOption::new("verbose").global(true).requires("level"))
+            .option(CommandOption::new("level").global(true).arg(Argument::new()))
+            .subcommand(Command::new("run"));
+
+        assert!(parse_with("--mode fast", command.clone()).is_ok());
+        assert!(parse_with("--mode fast -o a.txt --format csv", command.clone()).is_ok());
+
+        let error = parse_with("--mode fast -o a.txt", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingRequiredBy("-o".to_owned(), "--format".to_owned()));
+        assert!(error.is_usage_error());
+
+        // Required options are checked first
+        let error = parse_with("-o a.txt", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingOption("mode".to_owned()));
+
+        // Global options satisfy the requirements in the subcommands
+        assert!(parse_with("--level 2 run --verbose", command.clone()).is_ok());
+        assert!(parse_with("run --verbose --level 2", command.clone()).is_ok());
+
+        let error = parse_with("run --verbose", command).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingRequiredBy("--verbose".to_owned(), "--level".to_owned()));
+    }
+
+    #[test]
+    fn spelling_of_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("times").alias("t").arg(Argument::new().validator(validate_type::<u32>())))
+            .option(CommandOption::new("verbose").alias("v").global(true))
+            .option(CommandOption::new("tags").multiple(true).arg(Argument::one_or_more("tags")))
+            .subcommand(Command::new("run"));
+
+        let result = parse_with("--verbose --times=3 --tags a --tags b", command.clone()).unwrap();
+        assert_eq!(result.spelling_of("times"), Some("--times"));
+        assert_eq!(result.spelling_of("t"), Some("--times"));
+        assert_eq!(result.spelling_of("verbose"), Some("--verbose"));
+        assert_eq!(result.spelling_of("tags"), Some("--tags"));
+
+        let result = parse_with("-v run", command.clone()).unwrap();
+        assert_eq!(result.spelling_of("verbose"), Some("-v"));
+        assert_eq!(result.spelling_of("times"), None);
+
+        // The errors use the option as written
+        let error = parse_with("-t x", command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("times".to_owned()));
+        assert!(error.to_string().contains("in option '-t'"), "{}", error);
+
+        let error = parse_with("--times", command).unwrap_err();
+        assert!(error.to_string().contains("in option '--times'"), "{}", error);
+    }
+
     #[test]
     fn parse_global_option_test() {
         let command = Command::new("MyApp")
@@ -824,6 +2464,167 @@ mod tests {
         assert!(parse_with("echo --flag hello world", command.clone()).is_ok())
     }
 
+    #[test]
+    fn parse_nested_global_option_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v").global(true))
+            .subcommand(Command::new("remote")
+                .subcommand(Command::new("add").arg(Argument::with_name("name"))));
+
+        let result = parse_with("remote add -v origin", command.clone()).unwrap();
+        assert_eq!(result.command_name(), "add");
+        assert!(result.options().contains("verbose"));
+        assert!(result.args().get("name").unwrap().contains("origin"));
+    }
+
+    #[test]
+    fn parse_global_options_position_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v").global(true))
+            .option(CommandOption::new("color").global(true).arg(Argument::new()))
+            .option(
+                CommandOption::new("format")
+                    .global(true)
+                    .requires_assign(true)
+                    .arg(Argument::new()),
+            )
+            .option(
+                CommandOption::new("level")
+                    .global(true)
+                    .arg(Argument::new().default(1)),
+            )
+            .subcommand(
+                Command::new("child")
+                    .option(CommandOption::new("all"))
+                    .subcommand(Command::new("grandchild").arg(Argument::zero_or_more("values"))),
+            );
+
+        // The global options are placed before, between and after the subcommands
+        let cases = [
+            ("MyApp", "{}"),
+            ("child", "{} child"),
+            ("child", "child {}"),
+            ("grandchild", "{} child grandchild a b"),
+            ("grandchild", "child {} grandchild a b"),
+            ("grandchild", "child grandchild {} a b"),
+        ];
+
+        let options = [
+            "-v --color red --format=json --level 3",
+            "--level=3 --format=json --color=red --verbose",
+        ];
+
+        for (command_name, case) in cases.iter() {
+            for opts in options.iter() {
+                let args = case.replace("{}", opts);
+
+                let result = parse_with(&args, command.clone())
+                    .unwrap_or_else(|e| panic!("failed to parse `{}`: {}", args, e));
+
+                assert_eq!(result.command_name(), *command_name, "{}", args);
+                assert!(result.options().contains("verbose"), "{}", args);
+                assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["red"], "{}", args);
+                assert_eq!(result.options().get_arg("format").unwrap().get_values(), &["json"], "{}", args);
+                assert_eq!(result.options().get_arg("level").unwrap().get_values(), &["3"], "{}", args);
+            }
+
+            // Default values and assign requirements of the global options
+            let args = case.replace("{}", "");
+            let result = parse_with(&args, command.clone()).unwrap();
+            assert_eq!(result.options().get_arg("level").unwrap().get_values(), &["1"], "{}", args);
+            assert!(!result.options().contains("verbose"), "{}", args);
+
+            let args = case.replace("{}", "--format json");
+            let error = parse_with(&args, command.clone()).unwrap_err();
+            assert_eq!(error.kind(), &ErrorKind::Other, "{}", args);
+        }
+
+        // The options of a command are not global
+        assert!(parse_with("child --all grandchild", command.clone()).is_err());
+        assert!(parse_with("--all child", command.clone()).is_err());
+    }
+
+    #[test]
+    fn parse_required_global_option_position_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("token").required(true).global(true).arg(Argument::new()))
+            .subcommand(Command::new("child").subcommand(Command::new("grandchild")));
+
+        for args in &["", "child", "child grandchild"] {
+            let error = parse_with(args, command.clone()).unwrap_err();
+            assert_eq!(error.kind(), &ErrorKind::MissingOption("token".to_owned()), "{}", args);
+        }
+
+        for args in &[
+            "--token abc",
+            "--token abc child",
+            "child --token abc",
+            "--token=abc child grandchild",
+            "child --token abc grandchild",
+            "child grandchild --token abc",
+        ] {
+            let result = parse_with(args, command.clone()).unwrap();
+            assert_eq!(result.options().get_arg("token").unwrap().get_values(), &["abc"], "{}", args);
+        }
+    }
+
+    #[test]
+    fn nested_options_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("db.host").alias("H").arg(Argument::new()))
+            .option(CommandOption::new("db.port").arg(Argument::new().default(5432)))
+            .option(CommandOption::new("db.tls"))
+            .option(CommandOption::new("server.tls.cert").arg(Argument::new()))
+            .option(CommandOption::new("db.range").arg(Argument::with_name("min")).arg(Argument::with_name("max")))
+            .option(CommandOption::new("dbx").arg(Argument::new()));
+
+        let context = Context::builder(command)
+            .global_option(CommandOption::new("db.user").arg(Argument::new().default("admin")))
+            .build();
+
+        let result = Parser::new(&context)
+            .parse(split_into_args("--db.host=localhost --db.tls --db.range 1 9 --server.tls.cert a.pem --dbx 1"))
+            .unwrap();
+
+        let db = result.nested("db");
+        let mut keys = db.keys().map(String::as_str).collect::<Vec<&str>>();
+        keys.sort();
+        assert_eq!(keys, vec!["host", "port", "range.max", "range.min", "tls", "user"]);
+        assert_eq!(db["range.min"].iter().collect::<Vec<_>>(), vec!["1"]);
+        assert_eq!(db["range.max"].iter().collect::<Vec<_>>(), vec!["9"]);
+        assert_eq!(db["host"].iter().collect::<Vec<_>>(), vec!["localhost"]);
+        assert_eq!(db["port"].iter().collect::<Vec<_>>(), vec!["5432"]);
+        assert_eq!(db["user"].iter().collect::<Vec<_>>(), vec!["admin"]);
+        assert!(db["tls"].is_empty());
+
+        let server = result.nested("server");
+        assert_eq!(server["tls.cert"].iter().collect::<Vec<_>>(), vec!["a.pem"]);
+        assert_eq!(result.nested("server.tls")["cert"].iter().collect::<Vec<_>>(), vec!["a.pem"]);
+        assert!(result.nested("data").is_empty());
+
+        // Aliases don't need dots
+        let result = Parser::new(&context).parse(split_into_args("-H 127.0.0.1")).unwrap();
+        assert_eq!(result.nested("db")["host"].iter().collect::<Vec<_>>(), vec!["127.0.0.1"]);
+    }
+
+    #[test]
+    fn parse_option_of_other_command_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("local"))
+            .subcommand(Command::new("get").option(CommandOption::new("all")))
+            .subcommand(Command::new("set"));
+
+        assert!(parse_with("get --all", command.clone()).is_ok());
+        assert_eq!(
+            parse_with("set --all", command.clone()).unwrap_err().kind(),
+            &ErrorKind::UnexpectedOption("--all".to_owned())
+        );
+        assert_eq!(
+            parse_with("get --local", command.clone()).unwrap_err().kind(),
+            &ErrorKind::UnexpectedOption("--local".to_owned())
+        );
+    }
+
     #[test]
     fn value_of_test() {
         let command = Command::new("MyApp").arg(Argument::with_name("color"));
@@ -875,6 +2676,36 @@ mod tests {
     }
 
     #[test]
+    fn parse_result_get_arg_and_convert_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("size").alias("s").arg(Argument::new()))
+            .option(CommandOption::new("values").arg(Argument::one_or_more("values")))
+            .arg(Argument::one_or_more("values"));
+
+        let result = parse_with("-s 10 --values 1 2 -- 3 4", command).unwrap();
+
+        // Options take priority over the arguments with the same name
+        assert_eq!(result.get_arg("size").unwrap().get_values(), &["10"]);
+        assert_eq!(result.get_arg("values").unwrap().get_values(), &["1", "2"]);
+        assert_eq!(result.get_args("s").unwrap().len(), 1);
+        assert!(result.get_args("other").is_none());
+        assert_eq!(result.convert::<u32>("s").unwrap(), 10);
+        assert_eq!(result.convert_all::<u32>("values").unwrap(), vec![1, 2]);
+        assert_eq!(result.args().convert_all::<u32>("values").unwrap(), vec![3, 4]);
+        assert!(result.convert::<u32>("other").is_err());
+
+        let result = parse_with(
+            "3 4",
+            Command::new("MyApp").arg(Argument::one_or_more("values")),
+        )
+        .unwrap();
+
+        assert_eq!(result.convert_all::<u32>("values").unwrap(), vec![3, 4]);
+        assert!(result.get_arg("other").is_none());
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn value_of_as_test() {
         let command = Command::new("MyApp").arg(Argument::with_name("numbers"));
         let result = parse_with("65", command.clone()).unwrap();
@@ -883,6 +2714,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn values_of_as_test() {
         let command = Command::new("MyApp").arg(Argument::one_or_more("numbers"));
         let result = parse_with("2 4 6", command.clone()).unwrap();
@@ -899,6 +2731,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn value_of_option_as_test() {
         let command = Command::new("MyApp")
             .option(CommandOption::new("size").arg(Argument::with_name("size")));
@@ -908,6 +2741,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn values_of_option_as_test() {
         let command = Command::new("MyApp")
             .option(CommandOption::new("sizes").arg(Argument::one_or_more("sizes")));
@@ -923,4 +2757,325 @@ mod tests {
                 .collect::<Vec<i64>>()
         );
     }
+
+    fn parse_collecting_with(value: &str, command: Command) -> crate::Result<ParseResult> {
+        let context = Context::builder(command).collect_errors(true).build();
+        Parser::new(&context).parse(split_into_args(value))
+    }
+
+    #[test]
+    fn parse_collect_errors_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("mode").arg(Argument::with_name("mode").valid_values(["low", "high"])))
+            .option(CommandOption::new("count").arg(Argument::with_name("count").validator(validate_type::<u32>())))
+            .option(CommandOption::new("token").required(true).arg(Argument::new()))
+            .subcommand(Command::new("run"));
+
+        // All the errors are reported with the index of its token
+        let error = parse_collecting_with("--mode lo --count abc --token x extra", command.clone()).unwrap_err();
+        let errors = error.iter().collect::<Vec<&Error>>();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].kind(), &ErrorKind::InvalidArgument("mode".to_owned()));
+        assert_eq!(errors[0].token_index(), Some(0));
+        assert_eq!(errors[1].kind(), &ErrorKind::InvalidArgument("count".to_owned()));
+        assert_eq!(errors[1].token_index(), Some(2));
+        assert_eq!(errors[2].kind(), &ErrorKind::InvalidArgumentCount);
+        assert_eq!(errors[2].token_index(), Some(6));
+
+        let message = error.to_string();
+        assert!(message.starts_with("found 3 errors:\n1. invalid value for argument 'mode'"), "{}", message);
+        assert!(message.contains("\n2. invalid value for argument 'count'"));
+        assert!(message.contains("\n3. invalid argument count: `MyApp` takes no arguments"));
+
+        // Unknown options are skipped and the missing options are reported
+        let error = parse_collecting_with("--color=red --size 2", command.clone()).unwrap_err();
+        let kinds = error.iter().map(|e| e.kind().clone()).collect::<Vec<ErrorKind>>();
+        assert_eq!(kinds, vec![
+            ErrorKind::UnexpectedOption("--color".to_owned()),
+            ErrorKind::UnexpectedOption("--size".to_owned()),
+            ErrorKind::MissingOption("token".to_owned()),
+            ErrorKind::InvalidArgumentCount,
+        ]);
+
+        // An invalid option is not reported again as missing
+        let error = parse_collecting_with("--token", command.clone()).unwrap_err();
+        assert_eq!(error.iter().count(), 1);
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+
+        // An unknown subcommand stops the parse
+        let error = parse_collecting_with("build --count abc", command.clone()).unwrap_err();
+        assert_eq!(error.iter().count(), 1);
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedCommand("build".to_owned()));
+
+        // By default only the first error is reported
+        let error = parse_with("--mode lo --count abc --token x extra", command.clone()).unwrap_err();
+        assert_eq!(error.iter().count(), 1);
+        assert_eq!(error.kind(), &ErrorKind::InvalidArgument("mode".to_owned()));
+        assert_eq!(error.token_index(), Some(0));
+
+        assert!(parse_collecting_with("--mode low --count 2 --token x", command).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_os_invalid_utf8_test() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let invalid = |s: &str| {
+            let mut bytes = s.as_bytes().to_vec();
+            bytes.push(0xFF);
+            OsString::from_vec(bytes)
+        };
+
+        let parse_os = |args: Vec<OsString>, command: Command| {
+            let context = Context::new(command);
+            Parser::new(&context).parse_os(args)
+        };
+
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("input")
+                .multiple(true)
+                .arg(Argument::one_or_more("input").allow_invalid_utf8(true)))
+            .option(CommandOption::new("tags").arg(Argument::one_or_more("tags").value_delimiter(',')))
+            .option(CommandOption::new("name").arg(Argument::new()))
+            .arg(Argument::zero_or_more("files").allow_invalid_utf8(true));
+
+        // The raw values are kept by the arguments that allow invalid UTF-8
+        let args = vec!["--input".into(), invalid("a"), "--input".into(), "b".into(), "--".into(), "c".into(), invalid("d")];
+        let result = parse_os(args, command.clone()).unwrap();
+        let input = result.options().get_arg("input").unwrap();
+        assert_eq!(input.get_values(), &["a\u{FFFD}", "b"]);
+        assert_eq!(input.get_os_values(), vec![invalid("a"), OsString::from("b")]);
+        assert_eq!(result.arg().unwrap().get_values(), &["c", "d\u{FFFD}"]);
+        assert_eq!(result.arg().unwrap().get_os_values(), vec![OsString::from("c"), invalid("d")]);
+
+        // Valid UTF-8 is the same as `parse`
+        let result = parse_os(vec!["--name".into(), "x".into(), "y".into()], command.clone()).unwrap();
+        assert_eq!(result.options().get_arg("name").unwrap().get_values(), &["x"]);
+        assert_eq!(result.arg().unwrap().get_os_values(), vec![OsString::from("y")]);
+
+        // Arguments that don't allow invalid UTF-8
+        let error = parse_os(vec!["--name".into(), invalid("x")], command.clone()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidUtf8(invalid("x")));
+
+        // Invalid values split by a delimiter cannot be kept
+        let error = parse_os(vec!["--tags".into(), invalid("a,b")], command).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidUtf8(invalid("a,b")));
+    }
+
+    #[test]
+    fn parse_context_global_options_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v"))
+            .subcommand(Command::new("build")
+                .option(CommandOption::new("config").arg(Argument::with_name("file")))
+                .option(CommandOption::new("release").alias("p"))
+                .arg(Argument::zero_or_more("targets")))
+            .subcommand(Command::new("clean"));
+
+        let context = Context::builder(command)
+            .global_option(CommandOption::new("config").alias("c").arg(Argument::with_name("file")))
+            .global_option(CommandOption::new("profile").alias("p").arg(Argument::new().default("dev")))
+            .build();
+
+        let parse = |value: &str| Parser::new(&context).parse(split_into_args(value));
+
+        // Before and after the subcommands
+        let result = parse("--config app.toml clean --profile release").unwrap();
+        assert_eq!(result.executing_command().get_name(), "clean");
+        assert!(result.options().is_empty());
+        assert!(result.global_options().get_arg("config").unwrap().contains("app.toml"));
+        assert!(result.global_options().get_arg("profile").unwrap().contains("release"));
+        assert!(!result.executing_command().get_options().contains("config"));
+
+        // Default values
+        let result = parse("-v").unwrap();
+        assert!(result.options().contains("verbose"));
+        assert!(!result.global_options().contains("config"));
+        assert!(result.global_options().get_arg("profile").unwrap().contains("dev"));
+
+        // The options of the commands take priority over the global options
+        let result = parse("build --config build.toml -p app").unwrap();
+        assert!(result.options().get_arg("config").unwrap().contains("build.toml"));
+        assert!(result.options().contains("release"));
+        assert!(!result.global_options().contains("config"));
+        assert!(result.global_options().get_arg("profile").unwrap().contains("dev"));
+        assert!(!result.global_options().get_by_name("profile").unwrap().has_alias("p"));
+        assert_eq!(result.arg().unwrap().get_values(), &["app"]);
+
+        let result = parse("build --profile=release app").unwrap();
+        assert!(result.global_options().get_arg("profile").unwrap().contains("release"));
+
+        // The aliases of a replaced global option are not used
+        assert!(parse("build -c other.toml").is_err());
+
+        assert!(parse("clean --config").is_err());
+    }
+
+    #[test]
+    fn parse_counted_options_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v").counted(true).global(true))
+            .option(CommandOption::new("color"))
+            .subcommand(Command::new("build"));
+
+        let context = Context::builder(command)
+            .global_option(CommandOption::new("quiet").alias("q").counted(true))
+            .build();
+
+        let parse = |value: &str| Parser::new(&context).parse(split_into_args(value));
+
+        assert_eq!(parse("").unwrap().occurrences_of("verbose"), 0);
+        assert_eq!(parse("-v").unwrap().occurrences_of("verbose"), 1);
+        assert_eq!(parse("-v -v").unwrap().occurrences_of("v"), 2);
+        assert_eq!(parse("-v --verbose -v").unwrap().occurrences_of("verbose"), 3);
+        assert_eq!(parse("-v --color").unwrap().occurrences_of("color"), 1);
+        assert_eq!(parse("-v --color").unwrap().occurrences_of("other"), 0);
+
+        // Propagated to the subcommands
+        let result = parse("-v build -v --verbose").unwrap();
+        assert_eq!(result.executing_command().get_name(), "build");
+        assert_eq!(result.occurrences_of("verbose"), 3);
+        assert_eq!(result.options().get_count("v"), 3);
+
+        // Context global options
+        let result = parse("-q build -q").unwrap();
+        assert_eq!(result.occurrences_of("quiet"), 2);
+        assert_eq!(result.global_options().get_count("q"), 2);
+        assert_eq!(result.occurrences_of("verbose"), 0);
+
+        // Counted options don't take arguments
+        assert!(parse("-v=2").is_err());
+        assert!(parse("build -v 2").is_err());
+    }
+
+    #[test]
+    fn parse_captures_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("remote").alias("r")
+                .arg(Argument::with_name("remote").capture("{owner}/{repo}")))
+            .option(CommandOption::new("when")
+                .arg(Argument::with_name("date").capture("{year}-{month}-{day}")))
+            .arg(Argument::zero_or_one("address").capture("{host}:{port}"));
+
+        let context = Context::new(command);
+        let parse = |value: &str| Parser::new(&context).parse(split_into_args(value));
+
+        let result = parse("-r rust-lang/rust --when 2024-01-02 localhost:80").unwrap();
+        let remote = result.captures_of("remote").unwrap();
+        assert_eq!(remote["owner"], "rust-lang");
+        assert_eq!(remote["repo"], "rust");
+        assert_eq!(result.captures_of("r"), Some(remote));
+        assert_eq!(result.convert_capture::<u32>("when", "year").unwrap(), 2024);
+        assert_eq!(result.convert_capture::<u8>("when", "month").unwrap(), 1);
+        assert_eq!(result.convert_capture::<u16>("address", "port").unwrap(), 80);
+        assert!(result.convert_capture::<u32>("when", "hour").is_err());
+
+        let result = parse("").unwrap();
+        assert!(result.captures_of("remote").is_none());
+        assert!(result.captures_of("address").is_none());
+        assert!(result.convert_capture::<u16>("address", "port").is_err());
+
+        let error = parse("--when 2024/01/02").unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidArgument(_)));
+        assert!(error
+            .to_string()
+            .contains("expected `{year}-{month}-{day}` but was `2024/01/02`"));
+    }
+
+    #[test]
+    fn parse_reused_parser_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("number").alias("n").multiple(true)
+                .arg(Argument::one_or_more("number").validator(validate_type::<i64>())))
+            .option(CommandOption::new("color")
+                .arg(Argument::with_name("color").valid_values(["auto", "never"]).default("auto")))
+            .subcommand(Command::new("build")
+                .arg(Argument::zero_or_more("targets").valid_values(["a", "b"])));
+
+        let context = Context::new(command);
+        let mut parser = Parser::new(&context);
+
+        // The first occurrence is valid, the second fails
+        assert!(parser.parse(split_into_args("-n 1 2 -n x --color never")).is_err());
+
+        let result = parser.parse(split_into_args("-n 3")).unwrap();
+        assert_eq!(result.options().get_arg("number").unwrap().get_values(), &["3"]);
+        assert_eq!(result.options().get_arg("color").unwrap().get_values(), &["auto"]);
+        assert!(result.ignored().is_empty());
+
+        assert!(parser.parse(split_into_args("build a c")).is_err());
+
+        let result = parser.parse(split_into_args("build b")).unwrap();
+        assert_eq!(result.command_name(), "build");
+        assert_eq!(result.arg().unwrap().get_values(), &["b"]);
+        assert!(!result.options().contains("number"));
+
+        let result = parser.parse(Vec::<String>::new()).unwrap();
+        assert_eq!(result.command_name(), "MyApp");
+        assert!(result.args().is_empty());
+
+        // The context is never modified
+        let color = context.root().get_options().get_arg("color").unwrap();
+        assert!(!color.is_set());
+    }
+
+    #[test]
+    fn parse_metrics_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("verbose").alias("v").counted(true))
+            .option(CommandOption::new("count").arg(Argument::new().validator(validate_type::<u32>())))
+            .subcommand(Command::new("build"));
+
+        let context = Context::new(command.clone());
+        let result = Parser::new(&context).parse(split_into_args("-v")).unwrap();
+        assert!(result.metrics().is_none());
+
+        let context = Context::builder(command).collect_errors(true).metrics(true).build();
+        let mut parser = Parser::new(&context);
+
+        let result = parser.parse(split_into_args("-v -v --count 2")).unwrap();
+        let metrics = result.metrics().unwrap();
+        assert_eq!(metrics.token_count(), 4);
+        assert_eq!(metrics.option_occurrences(), 3);
+        assert_eq!(metrics.recovered_errors(), 0);
+        assert_eq!(metrics.total_time(), metrics.tokenize_time() + metrics.parse_time());
+        assert_eq!(parser.metrics(), Some(metrics));
+
+        // The metrics of a failed parse are only available in the parser
+        assert!(parser.parse(split_into_args("-v --count x --other")).is_err());
+        let metrics = parser.metrics().unwrap();
+        assert_eq!(metrics.option_occurrences(), 1);
+        assert_eq!(metrics.recovered_errors(), 2);
+    }
+
+    #[test]
+    fn parse_ignore_option_case_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("Format").alias("F").arg(Argument::new()))
+            .option(CommandOption::new("Pretty").requires("format"))
+            .option(CommandOption::new("quiet").conflicts_with("PRETTY"));
+
+        let context = Context::builder(command.clone()).ignore_option_case(true).build();
+        let mut parser = Parser::new(&context);
+
+        let result = parser.parse(split_into_args("--FORMAT json --pretty")).unwrap();
+        assert_eq!(result.options().get_arg("Format").unwrap().get_values(), &["json"]);
+        assert!(result.options().contains("Pretty"));
+
+        let result = parser.parse(split_into_args("-f json")).unwrap();
+        assert!(result.options().contains("Format"));
+        assert!(result.options().contains("format"));
+        assert_eq!(result.options().convert::<String>("FORMAT").unwrap(), "json");
+        assert_eq!(result.executing_command().get_options().get("pretty").unwrap().get_name(), "Pretty");
+
+        let error = parser.parse(split_into_args("--pretty")).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingRequiredBy("--pretty".to_owned(), "--Format".to_owned()));
+        assert!(parser.parse(split_into_args("--Quiet --Pretty -F json")).is_err());
+
+        // Case sensitive by default
+        let context = Context::new(command);
+        assert!(Parser::new(&context).parse(split_into_args("--format json")).is_err());
+    }
 }
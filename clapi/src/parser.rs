@@ -2,15 +2,17 @@
 use std::borrow::Borrow;
 use crate::args::ArgumentList;
 use crate::command::Command;
-use crate::context::Context;
+use crate::context::{Context, OptionLookup, HELP_FORMAT_ARG};
 use crate::error::{Error, ErrorKind, Result};
 use crate::option::{CommandOption, OptionList};
-use crate::parse_result::ParseResult;
-use crate::tokenizer::Tokenizer;
+use crate::parse_result::{ParseMetrics, ParseResult};
+use crate::tokenizer::{split_commands, Tokenizer};
 use crate::token::Token;
 use crate::Argument;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashSet;
+use std::ffi::OsString;
+use std::time::Instant;
 
 /// A command-line argument parser.
 ///
@@ -34,13 +36,42 @@ use std::collections::HashSet;
 /// assert_eq!(result.options().get_arg("number").unwrap().convert::<i64>().ok(), Some(25));
 /// assert_eq!(result.options().get_arg("enable").unwrap().convert::<bool>().ok(), Some(false));
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Parser<'a> {
     context: &'a Context,
     cursor: Option<Cursor>,
+    path: Vec<&'a Command>,
+    option_lookup: &'a OptionLookup,
     command: Option<Command>,
     options: Option<OptionList>,
     args: Option<ArgumentList>,
+    ignored: Vec<String>,
+    errors: Vec<Error>,
+    // Number of errors collected by the last parse
+    recovered_errors: usize,
+    metrics: Option<ParseMetrics>,
+    // The lossy and raw values of the arguments that were not valid UTF-8, until are taken by an argument
+    invalid_utf8: RefCell<Vec<(String, OsString)>>,
+}
+
+impl<'a> Clone for Parser<'a> {
+    fn clone(&self) -> Self {
+        Parser {
+            context: self.context,
+            cursor: self.cursor.clone(),
+            path: self.path.clone(),
+            option_lookup: self.option_lookup,
+            command: self.command.clone(),
+            options: self.options.clone(),
+            args: self.args.clone(),
+            ignored: self.ignored.clone(),
+            // The errors only exist while parsing
+            errors: Vec::new(),
+            recovered_errors: self.recovered_errors,
+            metrics: self.metrics,
+            invalid_utf8: self.invalid_utf8.clone(),
+        }
+    }
 }
 
 impl<'a> Parser<'a> {
@@ -49,9 +80,16 @@ impl<'a> Parser<'a> {
         Parser {
             context,
             cursor: None,
+            path: Vec::new(),
+            option_lookup: context.option_lookup(),
             command: None,
             options: Some(OptionList::new()),
             args: Some(ArgumentList::new()),
+            ignored: Vec::new(),
+            errors: Vec::new(),
+            recovered_errors: 0,
+            metrics: None,
+            invalid_utf8: RefCell::new(Vec::new()),
         }
     }
 
@@ -71,11 +109,68 @@ impl<'a> Parser<'a> {
     pub fn parse<S, I>(&mut self, args: I) -> Result<ParseResult>
         where S: Borrow<str>,
               I: IntoIterator<Item = S> {
-        // If cursor is already set, reset the `Parser` state
-        if self.cursor.is_some() {
-            self.command = None;
-            self.options = Some(OptionList::new());
-            self.args = Some(ArgumentList::new());
+        self.invalid_utf8.get_mut().clear();
+        self.parse_strings(args)
+    }
+
+    /// Parsers the given `OsString` arguments and returns the `Ok(ParseResult)` if the parsing succeed
+    /// otherwise `Err(Error)`.
+    ///
+    /// The arguments that are not valid UTF-8 returns an `ErrorKind::InvalidUtf8` error,
+    /// unless are values of an `Argument` that allows invalid UTF-8.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, Argument, Parser, Context};
+    /// use std::ffi::OsString;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .arg(Argument::with_name("path"));
+    ///
+    /// let context = Context::new(command);
+    /// let result = Parser::new(&context).parse_os(vec![OsString::from("/tmp")]).unwrap();
+    /// assert_eq!(&result.arg().unwrap()[0], "/tmp");
+    /// ```
+    pub fn parse_os<S, I>(&mut self, args: I) -> Result<ParseResult>
+        where S: Into<OsString>,
+              I: IntoIterator<Item = S> {
+        let mut invalid_utf8 = Vec::new();
+
+        // The invalid values are parsed as its lossy value, and replaced when set to an argument
+        let args = args
+            .into_iter()
+            .map(|arg| match arg.into().into_string() {
+                Ok(s) => s,
+                Err(os_string) => {
+                    let lossy = os_string.to_string_lossy().into_owned();
+                    invalid_utf8.push((lossy.clone(), os_string));
+                    lossy
+                }
+            })
+            .collect::<Vec<String>>();
+
+        *self.invalid_utf8.get_mut() = invalid_utf8;
+        let result = self.parse_strings(args)?;
+
+        // Any invalid value not taken by an argument is an error, like option names or values split by a delimiter
+        if !result.is_help_requested() && !result.is_version_requested() {
+            if let Some((_, os_string)) = self.invalid_utf8.get_mut().drain(..).next() {
+                return Err(Error::from(ErrorKind::InvalidUtf8(os_string)));
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn parse_strings<S, I>(&mut self, args: I) -> Result<ParseResult>
+        where S: Borrow<str>,
+              I: IntoIterator<Item = S> {
+        // Any state of a previous parse is discarded, even if that parse failed
+        self.reset();
+
+        // The clock is only read when the metrics are enabled
+        if self.context.is_metrics_enabled() {
+            return self.parse_strings_with_metrics(args);
         }
 
         // Parse the tokens using the current `Context`
@@ -88,11 +183,128 @@ impl<'a> Parser<'a> {
         self.parse_tokens()
     }
 
+    fn parse_strings_with_metrics<S, I>(&mut self, args: I) -> Result<ParseResult>
+        where S: Borrow<str>,
+              I: IntoIterator<Item = S> {
+        let start = Instant::now();
+        let tokens = Tokenizer.tokenize(self.context, args);
+        let tokenize_time = start.elapsed();
+
+        let tokens = match tokens {
+            Ok(tokens) => tokens,
+            Err(error) => {
+                self.metrics = Some(ParseMetrics { tokenize_time, ..Default::default() });
+                return Err(error);
+            }
+        };
+
+        let token_count = tokens.len();
+        self.cursor = Some(Cursor::new(tokens));
+
+        let start = Instant::now();
+        let result = self.parse_tokens();
+        let parse_time = start.elapsed();
+
+        // If the parse failed we count the options parsed until then
+        let option_occurrences = match &result {
+            Ok(result) => count_occurrences(result.options()) + count_occurrences(result.global_options()),
+            Err(_) => self.options.as_ref().map_or(0, count_occurrences),
+        };
+
+        let metrics = ParseMetrics {
+            token_count,
+            option_occurrences,
+            recovered_errors: self.recovered_errors,
+            tokenize_time,
+            parse_time,
+        };
+
+        self.metrics = Some(metrics);
+        result.map(|result| result.with_metrics(metrics))
+    }
+
+    /// Returns the metrics of the last parse, or `None` if are not enabled in the `Context`.
+    ///
+    /// Unlike `ParseResult::metrics` these are also available if the parse failed.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Argument, Command, CommandOption, Context, Parser};
+    /// use clapi::validator::validate_type;
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .option(CommandOption::new("count").arg(Argument::new().validator(validate_type::<u32>())));
+    ///
+    /// let context = Context::builder(command).collect_errors(true).metrics(true).build();
+    /// let mut parser = Parser::new(&context);
+    ///
+    /// assert!(parser.parse(vec!["--count", "abc", "--color"]).is_err());
+    /// assert_eq!(parser.metrics().unwrap().recovered_errors(), 2);
+    /// ```
+    pub fn metrics(&self) -> Option<&ParseMetrics> {
+        self.metrics.as_ref()
+    }
+
+    /// Parses several commands separated by the `Context` command separator,
+    /// returning the `ParseResult` of each command or the first error.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::{Command, CommandOption, Argument, Context, Parser};
+    ///
+    /// let command = Command::new("MyApp")
+    ///     .subcommand(Command::new("fetch")
+    ///         .option(CommandOption::new("remote").arg(Argument::new())))
+    ///     .subcommand(Command::new("rebase")
+    ///         .option(CommandOption::new("onto").arg(Argument::new())));
+    ///
+    /// let context = Context::builder(command).command_separator(";").build();
+    /// let results = Parser::new(&context)
+    ///     .parse_all(vec!["fetch", "--remote", "origin", ";", "rebase", "--onto", "main"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results[0].command_name(), "fetch");
+    /// assert_eq!(results[1].command_name(), "rebase");
+    /// ```
+    pub fn parse_all<S, I>(&mut self, args: I) -> Result<Vec<ParseResult>>
+        where S: Borrow<str>,
+              I: IntoIterator<Item = S> {
+        let mut results = Vec::new();
+
+        let args = args.into_iter().map(|arg| arg.borrow().to_owned());
+
+        for command_args in split_commands(self.context, args) {
+            results.push(self.parse(command_args)?);
+        }
+
+        Ok(results)
+    }
+
+    // Resets the state of the `Parser`, the arguments are always cloned from the `Context`
+    // so the values of a previous parse are dropped with the options and arguments
+    fn reset(&mut self) {
+        self.cursor = None;
+        self.path.clear();
+        self.command = None;
+        self.options = Some(OptionList::new());
+        self.args = Some(ArgumentList::new());
+        self.ignored.clear();
+        self.errors.clear();
+        self.recovered_errors = 0;
+        self.metrics = None;
+    }
+
     // Returns the executing `Command` if the parse failed, otherwise `None`
     pub(crate) fn command(&self) -> Option<&Command> {
         self.command.as_ref()
     }
 
+    // Returns the commands from the root to the last command parsed
+    pub(crate) fn path(&self) -> &[&'a Command] {
+        &self.path
+    }
+
     // Returns the options if the parse failed, otherwise `None`
     // pub(crate) fn options(&self) -> Option<&OptionList> {
     //     self.options.as_ref()
@@ -104,6 +316,23 @@ impl<'a> Parser<'a> {
     // }
 
     fn parse_tokens(&mut self) -> Result<ParseResult> {
+        let result = self.try_parse_tokens();
+
+        if self.errors.is_empty() {
+            return result;
+        }
+
+        // Reports the errors collected before the parse stopped
+        self.recovered_errors = self.errors.len();
+        let mut errors = std::mem::take(&mut self.errors);
+        if let Err(error) = result {
+            errors.push(error);
+        }
+
+        Err(Error::from_errors(errors))
+    }
+
+    fn try_parse_tokens(&mut self) -> Result<ParseResult> {
         // Parse executing command
         self.parse_executing_command()?;
 
@@ -111,15 +340,17 @@ impl<'a> Parser<'a> {
         self.parse_options()?;
 
         // Quick path: If the current parsing result contains `help` or `version` we should exit
-        if self.contains_help() || self.contains_version() {
-            let command = self.command.take().unwrap();
-            let options = self.options.take().unwrap();
-            let args = self.args.take().unwrap();
-            return Ok(ParseResult::new(command, options, args));
+        let (help_requested, version_requested) = (self.contains_help(), self.contains_version());
+        if help_requested || version_requested {
+            return self
+                .take_result()
+                .map(|result| result.with_requests(help_requested, version_requested));
         }
 
-        // Skip next `end of arguments` token (if any)
-        if let Some(index) = self.cursor.as_ref().unwrap().remaining().iter().position(|t| t.is_eoo()) {
+        // Skip next `end of arguments` token (if any), a raw argument takes the values after it
+        if self.takes_raw_arg() {
+            // The `end of arguments` is skipped when parsing the args
+        } else if let Some(index) = self.cursor.as_ref().unwrap().remaining().iter().position(|t| t.is_eoo()) {
             // If there is arguments before `--` (end of arguments)
             // values are being passed to the last option which not exist.
             //
@@ -130,50 +361,114 @@ impl<'a> Parser<'a> {
                 // https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/V1_chap12.html
 
                 // We get the last argument to provide a hint of the error
-                let value = self.cursor.as_ref()
-                    .unwrap()
-                    .next()
-                    .cloned()
-                    .unwrap()
-                    .into_string();
+                let cursor = self.cursor.as_ref().unwrap();
+                let token_index = cursor.position();
+                let value = cursor.next().cloned().unwrap().into_string();
 
                 return Err(Error::new(
                     ErrorKind::Other,
                     format!("there is no option that expect arguments but `{}` was found", value)
-                ));
+                ).at_token(token_index));
             } else {
                 self.cursor.as_ref().unwrap().next();
             }
         }
 
+        // Check the options passed that cannot be used together (if any)
+        self.check_conflicting_options()?;
+
+        // Check the options passed less times than required (if any)
+        self.check_options_occurrences()?;
+
+        // Sets the options not passed that have an environment variable (if any)
+        self.set_env_options()?;
+
         // Check and set required options (if any)
         self.check_required_options()?;
 
         // Check and set options with default values (if any)
-        self.set_default_options();
+        self.set_default_options()?;
+
+        // Check the options required by the passed options (if any)
+        self.check_options_requirements()?;
 
         // Parse the command arguments
         self.parse_args()?;
 
         // If there is arguments left and the current command takes no arguments is an error
-        if self.cursor.as_ref().unwrap().peek().is_some() {
+        let cursor = self.cursor.as_ref().unwrap();
+        if cursor.peek().is_some() {
             let command = self.command.as_ref().unwrap();
-            return Err(Error::new(
+            let error = Error::new(
                 ErrorKind::InvalidArgumentCount,
                 format!("`{}` takes no arguments", command.get_name()),
-            ));
+            );
+
+            recover(self.context, &mut self.errors, error.at_token(cursor.position()))?;
+        }
+
+        self.take_result()
+    }
+
+    // Takes the command, options and arguments, or returns the errors collected (if any)
+    fn take_result(&mut self) -> Result<ParseResult> {
+        if !self.errors.is_empty() {
+            self.recovered_errors = self.errors.len();
+            return Err(Error::from_errors(std::mem::take(&mut self.errors)));
         }
 
-        // Sets the command, options and arguments
-        let command = self.command.take().unwrap();
-        let options = self.options.take().unwrap();
+        let mut command = self.command.take().unwrap();
+        let mut options = self.options.take().unwrap();
         let args = self.args.take().unwrap();
-        Ok(ParseResult::new(command, options, args))
+        let ignored = std::mem::take(&mut self.ignored);
+
+        // Moves the global options of the context out of the executing command
+        let mut global_options = OptionList::new();
+        if !self.context.global_options().is_empty() {
+            let option_lookup = self.option_lookup;
+            let is_context_global = |option: &CommandOption| {
+                self.context.find_global_option(option_lookup, option.get_name()).is_some()
+            };
+
+            let (globals, passed): (Vec<CommandOption>, Vec<CommandOption>) = options
+                .into_iter()
+                .partition(|option| is_context_global(option));
+
+            let mut command_options = command.get_options().iter()
+                .filter(|option| !is_context_global(option))
+                .cloned()
+                .collect::<OptionList>();
+            command_options.set_case(self.context.option_case());
+
+            command = command.options(command_options);
+            options = passed.into_iter().collect();
+            global_options = globals.into_iter()
+                .map(|mut option| {
+                    // Like the global options added to the executing command
+                    option.retain_aliases(|alias| option_lookup.get(alias).is_none());
+                    option
+                })
+                .collect();
+        }
+
+        // The options of the result are looked up like the options of the context
+        options.set_case(self.context.option_case());
+        global_options.set_case(self.context.option_case());
+
+        // The names of the subcommands to the executing command
+        let path = self.path.iter().skip(1).map(|c| c.get_name().to_owned()).collect();
+
+        Ok(ParseResult::new(command, options, args)
+            .with_global_options(global_options)
+            .with_ignored(ignored)
+            .with_path(path))
     }
 
     fn parse_executing_command(&mut self) -> Result<()> {
         let cursor = self.cursor.as_ref().unwrap();
         let mut command = self.context.root();
+        let mut option_lookup = self.context.option_lookup();
+        self.path = vec![command];
 
         // Store the global options of the commands,
         // We use a `HashSet` so each children can override the parent options
@@ -194,11 +489,19 @@ impl<'a> Parser<'a> {
                         global_options.replace(opt);
                     }
 
+                    option_lookup = option_lookup
+                        .get_child(subcommand.get_name())
+                        .expect("the option lookup is built from the same command tree");
+                    self.path.push(subcommand);
                     subcommand
                 }
+                None if self.context.is_lenient() => {
+                    self.ignored.push(name.clone());
+                    command
+                }
                 None => {
                     self.command = Some(command.clone());
-                    return Err(Error::from(ErrorKind::UnexpectedCommand(name.clone())))
+                    return Err(Error::from(ErrorKind::UnexpectedCommand(name.clone())).at_token(cursor.position()))
                 }
             };
 
@@ -211,20 +514,36 @@ impl<'a> Parser<'a> {
             // Pass the global options to the child
             for opt in global_options {
                 // We don't override children command options
-                if !result_command.get_options().contains(opt.get_name()) {
+                if !result_command.get_options().contains_with(opt.get_name(), self.context.option_case()) {
                     result_command.add_option(opt.clone());
                 }
             }
         }
 
+        // Pass the global options of the context, the options of the commands take priority
+        for opt in self.context.global_options() {
+            if option_lookup.get(opt.get_name()).is_none() {
+                let mut opt = opt.clone();
+                opt.retain_aliases(|alias| option_lookup.get(alias).is_none());
+                result_command.add_option(opt);
+            }
+        }
+
+        // Subcommands without a version use the version of the closest parent (if any)
+        if result_command.get_version().is_none() {
+            if let Some(version) = self.path.iter().rev().find_map(|c| c.get_version()) {
+                result_command = result_command.version(version);
+            }
+        }
+
         // Sets the executing command
         self.command = Some(result_command);
+        self.option_lookup = option_lookup;
         Ok(())
     }
 
     fn parse_options(&mut self) -> Result<()> {
         let cursor = self.cursor.as_ref().unwrap();
-        let command = self.command.as_ref().unwrap();
 
         while let Some(Token::Opt(s)) = cursor.peek() {
             // Checks if is a `help` option like: `--help`
@@ -232,20 +551,36 @@ impl<'a> Parser<'a> {
                 return self.parse_help_option();
             }
 
-            if let Some(option) = find_prefixed_option(&self.context, command, s) {
+            // A help with a format like: `--help=json`
+            if crate::context::is_help_option(self.context, self.context.trim_prefix(s))
+                && matches!(cursor.tokens().get(cursor.position() + 1), Some(Token::AssignOp(_))) {
+                return self.parse_help_format();
+            }
+
+            // Index of the option token, used to point the errors of the option
+            let token_index = cursor.position();
+
+            if let Some(mut option) = find_prefixed_option(self.context, self.option_lookup, &self.path, s) {
+                // Stores the option as written, like: `-t`
+                option.set_spelling(s.clone());
+
                 // Consumes option token
                 cursor.next();
 
                 if option.is_assign_required() && option.take_args() {
                     if let Some(Token::Arg(arg)) = cursor.peek() {
                         let assign_op : char = *self.context.assign_operators().next().unwrap();
-                        return Err(
-                            Error::new(
-                                ErrorKind::Other,
-                                format!(
-                                    "assignment operator was expected: `{}{}{}`", s, assign_op, arg)
-                            )
+                        let error = Error::new(
+                            ErrorKind::Other,
+                            format!("assignment operator was expected: `{}{}{}`", s, assign_op, arg)
                         );
+
+                        recover(self.context, &mut self.errors, error.at_token(token_index))?;
+
+                        // Skips the value
+                        cursor.next();
+                        add_invalid_option(self.options.as_mut().unwrap(), option);
+                        continue;
                     }
                 }
 
@@ -254,106 +589,80 @@ impl<'a> Parser<'a> {
                     cursor.next();
                 }
 
-                if option.take_args() {
-                    let mut option_args = ArgumentList::new();
-                    let mut option_args_iter = option.get_args().iter().cloned().peekable();
-                    let require_default_values = self.require_default_values(option.get_args());
-                    let mut default_value_is_set = false;
-
-                    while let Some(mut arg) = option_args_iter.next() {
-                        // We take the first `Argument` that required a default values.
-                        // Only 1 because multiple arguments with default values is no allowed.
-                        if require_default_values && !default_value_is_set {
-                            if arg.has_default_values() {
-                                add_argument(&mut option_args, arg);
-
-                                // This is just a flag, `Argument`S with default values already have
-                                // the default value set
-                                default_value_is_set = true;
-                                continue;
-                            }
+                let option = if option.take_args() {
+                    match assign_option_values(cursor, &option, &self.assign_context()) {
+                        // Sets the option arguments
+                        Ok(option_args) => option.args(option_args),
+                        Err(error) => {
+                            recover(self.context, &mut self.errors, error.at_token(token_index))?;
+                            add_invalid_option(self.options.as_mut().unwrap(), option);
+                            continue;
                         }
+                    }
+                } else {
+                    option
+                };
 
-                        let mut values = Vec::new();
-                        let max_count = arg.get_values_count().max_or_default();
-                        let mut count = 0;
-
-                        while count < max_count {
-                            if let Some(Token::Arg(value)) = cursor.peek() {
-                                cursor.next();
-                                values.push(value.clone());
-                                count += 1;
-                            } else {
-                                break;
-                            }
-                        }
+                // Adds the option
+                if let Err(error) = add_option(self.options.as_mut().unwrap(), option) {
+                    recover(self.context, &mut self.errors, error.at_token(token_index))?;
+                }
+            } else {
+                if self.context.is_lenient() {
+                    self.ignored.push(s.clone());
+                } else {
+                    let error = Error::from(ErrorKind::UnexpectedOption(s.clone()));
+                    recover(self.context, &mut self.errors, error.at_token(token_index))?;
+                }
 
-                        // If there is no more option args, check if there is an `end of arguments`
-                        if option_args_iter.peek().is_none() {
-                            if cursor.peek().map_or(false, |t| !t.is_option()) {
-                                // Check Guide 10
-                                // https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/V1_chap12.html
-                                // If there is an `--` (end of arguments) we pass all the values
-                                // before it to the last option as arguments (if any)
-                                //
-                                // Example: --numbers 1 2 3 -- hello world
-                                // 1 2 3 are passed to the option `--numbers`
-                                if let Some(mut index) = cursor.remaining().iter().position(|t| t.is_eoo()) {
-                                    while index > 0 {
-                                        let s = cursor.next().unwrap().clone().into_string();
-                                        values.push(s);
-                                        index -= 1;
-                                    }
-                                }
-                            }
-                        }
+                cursor.next();
 
-                        // Sets the argument values
-                        arg.set_values(values)?;
-                        add_argument(&mut option_args, arg);
-                    }
+                // Ignores the assigned value, if any: `--unknown=value`
+                if let Some(Token::AssignOp(_)) = cursor.peek() {
+                    cursor.next();
 
-                    // Sets the option arguments
-                    add_option(self.options.as_mut().unwrap(), option.args(option_args))?;
-                } else {
-                    // Adds the option
-                    // SAFETY: `add_option` only fail with duplicated options that allow multiples,
-                    // and takes args
-                    add_option(self.options.as_mut().unwrap(), option).unwrap();
+                    if let Some(Token::Arg(_)) = cursor.peek() {
+                        cursor.next();
+                    }
                 }
-            } else {
-                return Err(Error::from(ErrorKind::UnexpectedOption(s.clone())));
             }
         }
 
         Ok(())
     }
 
+    // The state of the parser used to assign the values of the options
+    fn assign_context(&self) -> AssignContext<'_> {
+        AssignContext {
+            // If the command takes raw args the values before `--` are for the command args
+            eoo_look_ahead: !self.takes_raw_arg(),
+            invalid_utf8: &self.invalid_utf8,
+        }
+    }
+
     fn parse_args(&mut self) -> Result<()> {
         let cursor = self.cursor.as_ref().unwrap();
         let command = self.command.as_ref().unwrap();
         let mut args_iter = command.get_args().iter().cloned().peekable();
-        let require_default_values = self.require_default_values(command.get_args());
-        let mut default_value_is_set = false;
+        let default_values_count = default_values_count(cursor, command.get_args());
 
         while let Some(mut arg) = args_iter.next() {
             let mut values = Vec::new();
+            let mut max_count = arg.get_values_count().max_or_default();
+            let token_index = cursor.position();
 
-            // We take the first `Argument` that required a default values.
-            // Only 1 because multiple arguments with default values is no allowed.
-            if require_default_values && !default_value_is_set {
-                if arg.has_default_values() {
+            // Only 1 argument can have default values
+            if let Some(n) = default_values_count.filter(|_| arg.has_default_values()) {
+                if n == 0 {
+                    // `Argument`s with default values already have the default value set
                     add_argument(self.args.as_mut().unwrap(), arg);
-
-                    // This is just a flag, `Argument`S with default values already have
-                    // the default value set
-                    default_value_is_set = true;
                     continue;
                 }
+
+                max_count = max_count.min(n);
             }
 
             if args_iter.peek().is_some() {
-                let max_count = arg.get_values_count().max_or_default();
                 let mut count = 0;
 
                 while count < max_count {
@@ -367,7 +676,13 @@ impl<'a> Parser<'a> {
                 }
             } else {
                 // If there is no `Argument`s left, pass the rest of the tokens as values
+                let mut skip_eoo = arg.is_raw();
                 while let Some(t) = cursor.next().cloned() {
+                    if skip_eoo && t.is_eoo() {
+                        skip_eoo = false;
+                        continue;
+                    }
+
                     values.push(t.into_string());
                 }
             }
@@ -375,7 +690,9 @@ impl<'a> Parser<'a> {
             // Sets the argument values
             // We attempt to set the values even if empty to return `invalid argument count` error.
             if values.len() > 0 || (values.is_empty() && !arg.has_default_values()) {
-                arg.set_values(values)?;
+                if let Err(error) = set_arg_values(&mut arg, values, &self.invalid_utf8) {
+                    recover(self.context, &mut self.errors, error.at_token(token_index))?;
+                }
             }
 
             add_argument(self.args.as_mut().unwrap(), arg);
@@ -420,8 +737,7 @@ impl<'a> Parser<'a> {
         if let Some(Token::Opt(s)) = cursor.next() {
             debug_assert!(crate::context::is_help_option(&self.context, s));
 
-            let command = self.command.as_ref().unwrap();
-            let option = find_prefixed_option(&self.context, command, s).unwrap();
+            let option = find_prefixed_option(self.context, self.option_lookup, &self.path, s).unwrap();
             let mut args = ArgumentList::new();
             let mut arg = option.get_arg().unwrap().clone();
 
@@ -459,7 +775,49 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn check_required_options(&self) -> Result<()> {
+    // Parses a help option with an assigned format like: `--help=json`, the help is for
+    // the executing command followed by the subcommands after the option, like: `--help=json server`
+    fn parse_help_format(&mut self) -> Result<()> {
+        let cursor = self.cursor.as_ref().unwrap();
+        let option = match cursor.next() {
+            Some(Token::Opt(s)) => find_prefixed_option(self.context, self.option_lookup, &self.path, s).unwrap(),
+            _ => unreachable!(),
+        };
+
+        // Skips the assign operator
+        cursor.next();
+
+        let format = match cursor.next() {
+            Some(Token::Arg(format)) => format.clone(),
+            _ => return Err(Error::from(ErrorKind::InvalidExpression)),
+        };
+
+        let values = self.path.iter()
+            .skip(1)
+            .map(|c| c.get_name().to_owned())
+            .chain(cursor.remaining().iter().map(|s| s.to_string()))
+            .collect::<Vec<String>>();
+
+        let mut args = ArgumentList::new();
+        if let Some(arg) = option.get_arg() {
+            let mut arg = arg.clone();
+            arg.set_values(values)?;
+            args.add(arg).unwrap();
+        }
+
+        let mut format_arg = Argument::with_name(HELP_FORMAT_ARG);
+        format_arg.set_values(vec![format])?;
+        args.add(format_arg).unwrap();
+
+        // Ignore the rest of tokens
+        cursor.move_to_end();
+
+        // Adds the help `CommandOption` and sets its values
+        self.options.as_mut().unwrap().add(option.args(args)).unwrap();
+        Ok(())
+    }
+
+    fn check_required_options(&mut self) -> Result<()> {
         let options = self.options.as_ref().unwrap();
         let command = self.command.as_ref().unwrap();
         let required_options = command
@@ -468,66 +826,185 @@ impl<'a> Parser<'a> {
 
         for opt in required_options {
             if !options.contains(opt.get_name()) {
-                return Err(Error::from(ErrorKind::MissingOption(
-                    opt.get_name().to_owned(),
-                )));
+                let error = Error::from(ErrorKind::MissingOption(opt.get_name().to_owned()));
+                recover(self.context, &mut self.errors, error)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Must be called before set the default and environment options, only passed options conflict
+    fn check_conflicting_options(&mut self) -> Result<()> {
+        let options = self.options.as_ref().unwrap();
+        let case = self.context.option_case();
+
+        for option in options {
+            for name in option.get_conflicts() {
+                if let Some(other) = options.get_with(name, case) {
+                    // Only passed options conflict, so both have a spelling
+                    let error = Error::from(ErrorKind::ConflictingOptions(
+                        option.spelling().unwrap_or(option.get_name()).to_owned(),
+                        other.spelling().unwrap_or(other.get_name()).to_owned(),
+                    ));
+
+                    recover(self.context, &mut self.errors, error)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Must be called before set the default and environment options, only passed options are counted
+    fn check_options_occurrences(&mut self) -> Result<()> {
+        let options = self.options.as_ref().unwrap();
+
+        for option in options {
+            if let Some(min) = option.get_min_occurrences() {
+                if option.occurrences() < min {
+                    let error = Error::new(
+                        ErrorKind::InvalidArgumentCount,
+                        format!(
+                            "'{}' must be given at least {}",
+                            option.spelling().unwrap_or(option.get_name()),
+                            times(min)
+                        ),
+                    );
+
+                    recover(self.context, &mut self.errors, error)?;
+                }
             }
         }
 
         Ok(())
     }
 
-    fn set_default_options(&mut self) {
+    // Must be called after set the default options, which satisfy the requirements
+    fn check_options_requirements(&mut self) -> Result<()> {
+        let options = self.options.as_ref().unwrap();
         let command = self.command.as_ref().unwrap();
-        let default_options = command
-            .get_options().iter()
-            .filter(|o| o.get_args().iter().any(|a| a.has_default_values()));
 
-        // Sets the options that takes default arguments
-        for opt in default_options {
-            if !self.options.as_ref().unwrap().contains(opt.get_name()) {
-                // SAFETY: `add_option` only fail with duplicated options that allow multiples
-                add_option(self.options.as_mut().unwrap(), opt.clone()).unwrap();
+        // Only passed options have a spelling
+        for option in options.iter().filter(|o| o.spelling().is_some()) {
+            for name in option.get_requires() {
+                if !options.contains_with(name, self.context.option_case()) {
+                    // The name of the required option, like: `--format`
+                    let required = command.get_options().get_with(name, self.context.option_case()).map_or(name.as_str(), |o| o.get_name());
+                    let prefix = self.context.name_prefixes().next().unwrap();
+
+                    let error = Error::from(ErrorKind::MissingRequiredBy(
+                        option.spelling().unwrap().to_owned(),
+                        format!("{}{}", prefix, required),
+                    ));
+
+                    recover(self.context, &mut self.errors, error)?;
+                }
             }
         }
+
+        Ok(())
     }
 
-    /// Returns `true` if one of the arguments need to have a default value.
-    ///
-    /// This is true when there is no enough values for the arguments,
-    /// for example we have 2 arguments:
-    /// - min (default value = 0)
-    /// - max
-    ///
-    /// If `20` is passed, because `available values < require values` where available values is 1
-    /// and required values is 2, is required to assign a default value to `min` and pass the `20`
-    /// to the `max` argument.
-    fn require_default_values(&self, args: &ArgumentList) -> bool {
-        let cursor = self.cursor.as_ref().unwrap();
-        let contains_default_args = args.iter().any(|a| a.has_default_values());
+    fn set_env_options(&mut self) -> Result<()> {
+        let command = self.command.as_ref().unwrap();
+
+        for opt in command.get_options().iter() {
+            if self.options.as_ref().unwrap().contains(opt.get_name()) {
+                continue;
+            }
+
+            let value = match self.context.env_var_name(opt).and_then(|name| std::env::var(name).ok()) {
+                Some(value) => value,
+                None => continue,
+            };
 
-        if contains_default_args {
-            let available_values = cursor.remaining().iter().take_while(|t| t.is_arg()).count();
+            let mut option = opt.clone();
+            match option.get_args().len() {
+                // An option with no arguments is set if the value is not empty, `0` or `false`
+                0 => {
+                    if value.is_empty() || value == "0" || value.eq_ignore_ascii_case("false") {
+                        continue;
+                    }
+                }
+                // The value is split by the delimiter like: `MYAPP_NUMBERS=1,2,3`
+                1 => {
+                    let values = value
+                        .split(self.context.delimiter())
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<&str>>();
+
+                    let arg = option.get_args_mut().iter_mut().next().unwrap();
+                    if let Err(error) = arg.set_values(values) {
+                        recover(self.context, &mut self.errors, error)?;
+                        continue;
+                    }
+                }
+                // We cannot know which values belongs to each argument
+                _ => continue,
+            }
 
-            // If is a single argument default values are only needed if there is no values left
-            if args.len() == 1 {
-                return available_values == 0;
+            if let Err(error) = add_option(self.options.as_mut().unwrap(), option) {
+                recover(self.context, &mut self.errors, error)?;
             }
+        }
+
+        Ok(())
+    }
 
-            let mut required_values: usize = 0;
+    fn set_default_options(&mut self) -> Result<()> {
+        let command = self.command.as_ref().unwrap();
+
+        // Options are visited in declaration order, so conditions see the defaults of the previous options
+        for opt in command.get_options().iter() {
+            let options = self.options.as_mut().unwrap();
+            if options.contains(opt.get_name()) {
+                continue;
+            }
 
-            for arg in args {
-                // To avoid overflow
-                match required_values.checked_add(arg.get_values_count().max_or_default()) {
-                    None => return false,
-                    Some(n) => required_values = n,
+            // The last conditional default that holds takes precedence over the argument default values
+            let default_value = opt.get_default_value_ifs()
+                .iter()
+                .rev()
+                .find(|(name, value, _)| match options.get_with(name, self.context.option_case()) {
+                    Some(other) => match value {
+                        Some(value) => other.get_args().iter().any(|a| a.contains(value)),
+                        None => true,
+                    },
+                    None => false,
+                })
+                .map(|(_, _, default)| default);
+
+            let mut option = opt.clone();
+            match default_value {
+                Some(default) => {
+                    if let Some(arg) = option.get_args_mut().iter_mut().next() {
+                        if let Err(error) = arg.set_values(vec![default]) {
+                            recover(self.context, &mut self.errors, in_option(error, opt))?;
+                            continue;
+                        }
+                    }
                 }
+                None if !opt.get_args().iter().any(|a| a.has_default_values()) => continue,
+                None => {}
             }
 
-            return available_values < required_values;
+            add_option(options, option)
+                .expect("`add_option` only fail with duplicated options that allow multiples");
+
+            // The options with default values were not passed
+            options.get_mut(opt.get_name()).unwrap().set_occurrences(0);
         }
 
-        false
+        Ok(())
+    }
+
+    // Returns `true` if the last argument of the executing command is raw
+    fn takes_raw_arg(&self) -> bool {
+        self.command
+            .as_ref()
+            .and_then(|command| command.get_args().iter().last())
+            .is_some_and(Argument::is_raw)
     }
 
     // Returns `true` if the parser found a `help` flag
@@ -626,9 +1103,143 @@ impl Cursor {
     }
 }
 
+// The state of the parser used by `assign_option_values`
+struct AssignContext<'a> {
+    // Whether the last argument of the option takes all the values before an end of options `--`
+    eoo_look_ahead: bool,
+    // The lossy and raw values of the arguments that were not valid UTF-8
+    invalid_utf8: &'a RefCell<Vec<(String, OsString)>>,
+}
+
+// Takes the values of the arguments of the option from the tokens of the cursor,
+// the cursor must be after the option and its assign operator (if any)
+fn assign_option_values(cursor: &Cursor, option: &CommandOption, context: &AssignContext<'_>) -> Result<ArgumentList> {
+    let mut option_args = ArgumentList::new();
+    let mut option_args_iter = option.get_args().iter().cloned().peekable();
+    let default_values_count = default_values_count(cursor, option.get_args());
+
+    while let Some(mut arg) = option_args_iter.next() {
+        let mut values = Vec::new();
+        let mut max_count = arg.get_values_count().max_or_default();
+        let mut count = 0;
+
+        // Only 1 argument can have default values
+        if let Some(n) = default_values_count.filter(|_| arg.has_default_values()) {
+            if n == 0 {
+                // `Argument`s with default values already have the default value set
+                add_argument(&mut option_args, arg);
+                continue;
+            }
+
+            max_count = max_count.min(n);
+        }
+
+        while count < max_count {
+            if let Some(Token::Arg(value)) = cursor.peek() {
+                cursor.next();
+                let split_values = split_arg_values(&arg, value)
+                    .map_err(|e| in_option(e, option))?;
+                count += split_values.len();
+                values.extend(split_values);
+            } else {
+                break;
+            }
+        }
+
+        // If there is no more option args, check if there is an `end of arguments`
+        if option_args_iter.peek().is_none() && context.eoo_look_ahead {
+            if cursor.peek().is_some_and(|t| !t.is_option()) {
+                // Check Guide 10
+                // https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/V1_chap12.html
+                // If there is an `--` (end of arguments) we pass all the values
+                // before it to the last option as arguments (if any)
+                //
+                // Example: --numbers 1 2 3 -- hello world
+                // 1 2 3 are passed to the option `--numbers`
+                if let Some(mut index) = cursor.remaining().iter().position(|t| t.is_eoo()) {
+                    while index > 0 {
+                        let s = cursor.next().unwrap().clone().into_string();
+                        values.extend(
+                            split_arg_values(&arg, &s).map_err(|e| in_option(e, option))?
+                        );
+                        index -= 1;
+                    }
+                }
+            }
+        }
+
+        // Sets the argument values
+        set_arg_values(&mut arg, values, context.invalid_utf8).map_err(|e| in_option(e, option))?;
+        add_argument(&mut option_args, arg);
+    }
+
+    Ok(option_args)
+}
+
+// Returns the number of values passed to the `Argument` with default values, if any.
+//
+// Only 1 argument can have default values and the other arguments must take an exact
+// number of values (see `ArgumentList`), so the values are distributed in order:
+// each argument without default values takes its exact number of values and the argument
+// with default values takes the values left, if there is no values left it keeps its default values.
+//
+// For example we have 2 arguments:
+// - min (default value = 0)
+// - max
+//
+// If `20` is passed, `max` requires 1 value so there is no values left for `min`
+// which is assigned its default value. If `10 20` is passed `min` takes the `10`.
+//
+// Any other number of values results in an invalid argument count error.
+fn default_values_count(cursor: &Cursor, args: &ArgumentList) -> Option<usize> {
+    if !args.iter().any(|a| a.has_default_values()) {
+        return None;
+    }
+
+    let available_values = cursor.remaining().iter().take_while(|t| t.is_arg()).count();
+    let required_values = args.iter()
+        .filter(|a| !a.has_default_values())
+        .fold(0_usize, |n, a| n.saturating_add(a.get_values_count().max_or_default()));
+
+    Some(available_values.saturating_sub(required_values))
+}
+
+// Sets the values of the argument, the values that were not valid UTF-8 are paired
+// with its raw value if the argument allows it, otherwise is an error
+fn set_arg_values(arg: &mut Argument, values: Vec<String>, invalid_utf8: &RefCell<Vec<(String, OsString)>>) -> Result<()> {
+    if invalid_utf8.borrow().is_empty() {
+        return arg.set_values(values);
+    }
+
+    let values = values
+        .into_iter()
+        .map(|value| {
+            let mut invalid_utf8 = invalid_utf8.borrow_mut();
+            let os_value = invalid_utf8
+                .iter()
+                .position(|(lossy, _)| *lossy == value)
+                .map(|index| invalid_utf8.remove(index).1);
+
+            (value, os_value)
+        })
+        .collect::<Vec<(String, Option<OsString>)>>();
+
+    if !arg.is_invalid_utf8_allowed() {
+        if let Some(os_string) = values.iter().find_map(|(_, os_value)| os_value.as_ref()) {
+            return Err(Error::new(
+                ErrorKind::InvalidUtf8(os_string.clone()),
+                format!("'{}' only takes valid UTF-8 values", arg.get_name()),
+            ));
+        }
+    }
+
+    arg.set_values_with_os(values)
+}
+
 fn find_prefixed_option<'a>(
     context: &'a Context,
-    _command: &'a Command,
+    option_lookup: &'a OptionLookup,
+    path: &[&'a Command],
     prefixed_option: &'a str,
 ) -> Option<CommandOption> {
     let unprefixed_option = context.trim_prefix(prefixed_option);
@@ -647,14 +1258,35 @@ fn find_prefixed_option<'a>(
         }
     }
 
-    // Finds and return the option from the context
-    context.get_option(unprefixed_option).cloned()
+    // Finds and return the option of the executing command, its parents or the context
+    option_lookup.get(unprefixed_option)
+        .and_then(|id| id.resolve(path))
+        .or_else(|| context.find_global_option(option_lookup, unprefixed_option))
+        .cloned()
 }
 
-fn add_option(options: &mut OptionList, new_option: CommandOption) -> Result<()> {
+fn add_option(options: &mut OptionList, mut new_option: CommandOption) -> Result<()> {
+    let occurrences = options.get(new_option.get_name()).map_or(0, |o| o.occurrences()) + 1;
+
+    if let Some(max) = new_option.get_max_occurrences() {
+        if occurrences > max {
+            return Err(Error::new(
+                ErrorKind::InvalidArgumentCount,
+                format!(
+                    "'{}' may be given at most {}",
+                    new_option.spelling().unwrap_or(new_option.get_name()),
+                    times(max)
+                ),
+            ));
+        }
+    }
+
+    new_option.set_occurrences(occurrences);
+
     if new_option.allow_multiple() && options.contains(new_option.get_name()) {
-        // If don't takes args is no-op
+        // If don't takes args only the occurrences are updated
         if !new_option.take_args() {
+            options.get_mut(new_option.get_name()).unwrap().set_occurrences(occurrences);
             return Ok(());
         }
 
@@ -663,16 +1295,29 @@ fn add_option(options: &mut OptionList, new_option: CommandOption) -> Result<()>
 
         // We iterate over the arguments and add any new argument value from the `new_option`.
         for arg in option.get_args() {
-            let mut values = Vec::new();
-            values.extend_from_slice(arg.get_values());
             let new_option_args = new_option.get_args()
                 .get(arg.get_name())
                 .unwrap();
 
-            values.extend_from_slice(new_option_args.get_values());
-
             let mut new_arg = arg.clone();
-            new_arg.set_values(values)?;
+
+            // With occurrence limits the values count was checked on each occurrence
+            if new_option.has_occurrence_limits() {
+                new_arg.extend_values(new_option_args.get_values());
+            } else {
+                let mut values = Vec::new();
+                values.extend_from_slice(arg.get_values());
+                values.extend_from_slice(new_option_args.get_values());
+                new_arg.set_values(values).map_err(|e| in_option(e, &new_option))?;
+            }
+
+            // Keeps the raw values of each occurrence
+            if new_arg.is_invalid_utf8_allowed() {
+                let mut os_values = arg.get_os_values();
+                os_values.extend(new_option_args.get_os_values());
+                os_values.truncate(new_arg.get_values().len());
+                new_arg.set_os_values(os_values);
+            }
 
             // SAFETY: the new option contains no duplicated args
             args.add(new_arg).unwrap();
@@ -688,6 +1333,73 @@ fn add_option(options: &mut OptionList, new_option: CommandOption) -> Result<()>
     }
 }
 
+// Returns the number of times the options were passed
+fn count_occurrences(options: &OptionList) -> usize {
+    options.iter().map(|option| option.occurrences()).sum()
+}
+
+// Returns the error if the context don't collect the errors, otherwise stores it to report it later
+fn recover(context: &Context, errors: &mut Vec<Error>, error: Error) -> Result<()> {
+    if context.is_collecting_errors() {
+        errors.push(error);
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
+// Adds an option which values were invalid so is not reported again as missing
+fn add_invalid_option(options: &mut OptionList, option: CommandOption) {
+    if !options.contains(option.get_name()) {
+        options.add(option).unwrap();
+    }
+}
+
+// Returns the number of times like: `1 time` or `3 times`
+fn times(n: usize) -> String {
+    if n == 1 {
+        "1 time".to_owned()
+    } else {
+        format!("{} times", n)
+    }
+}
+
+// Splits the value using the delimiter of the argument (if any) like: `a,b,c`,
+// each value is checked so the error points to the invalid one
+fn split_arg_values(arg: &Argument, value: &str) -> Result<Vec<String>> {
+    let delimiter = match arg.get_value_delimiter() {
+        Some(delimiter) => delimiter,
+        None => return Ok(vec![value.to_owned()]),
+    };
+
+    let values = crate::tokenizer::split_option_args(value, delimiter);
+
+    // Error when: `a,,b`
+    if values.iter().any(|s| s.is_empty()) {
+        return Err(Error::new(
+            ErrorKind::InvalidExpression,
+            format!("empty value in `{}`", value),
+        ));
+    }
+
+    if values.len() > 1 {
+        for s in &values {
+            arg.validate_value(s)
+                .map_err(|e| e.context(format!("`{}` in `{}`", s, value)))?;
+        }
+    }
+
+    Ok(values)
+}
+
+// Adds the option as written to the errors of its arguments, like: `in option '-t'`
+fn in_option(error: Error, option: &CommandOption) -> Error {
+    match option.spelling() {
+        Some(spelling) => error.context(format!("in option '{}'", spelling)),
+        None => error,
+    }
+}
+
 fn add_argument(arguments: &mut ArgumentList, new_arg: Argument){
     arguments.add(new_arg).unwrap_or_else(|e| {
         panic!("duplicated argument: `{}`", e.get_name())
@@ -696,4 +1408,116 @@ fn add_argument(arguments: &mut ArgumentList, new_arg: Argument){
 
 fn get_global_options(command: &Command) -> impl Iterator<Item=&CommandOption> {
     command.get_options().iter().filter(|opt| opt.is_global())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<Token> {
+        values
+            .iter()
+            .map(|s| match *s {
+                "--" => Token::EOO,
+                s if s.starts_with('-') => Token::Opt(s.to_owned()),
+                s => Token::Arg(s.to_owned()),
+            })
+            .collect()
+    }
+
+    fn assign(option: &CommandOption, tokens: &[&str], eoo_look_ahead: bool) -> (Result<ArgumentList>, usize) {
+        let cursor = Cursor::new(args(tokens));
+        let invalid_utf8 = RefCell::new(Vec::new());
+        let context = AssignContext { eoo_look_ahead, invalid_utf8: &invalid_utf8 };
+        let result = assign_option_values(&cursor, option, &context);
+        (result, cursor.position())
+    }
+
+    fn values(args: &ArgumentList) -> Vec<Vec<&str>> {
+        args.iter()
+            .map(|arg| arg.get_values().iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn assign_option_values_max_count_test() {
+        let option = CommandOption::new("size").arg(Argument::with_name("size"));
+
+        let (result, position) = assign(&option, &["10", "20"], true);
+        assert_eq!(values(&result.unwrap()), vec![vec!["10"]]);
+        assert_eq!(position, 1);
+
+        let option = CommandOption::new("numbers").arg(Argument::one_or_more("numbers"));
+
+        let (result, position) = assign(&option, &["1", "2", "--other", "3"], true);
+        assert_eq!(values(&result.unwrap()), vec![vec!["1", "2"]]);
+        assert_eq!(position, 2);
+
+        let (result, position) = assign(&option, &["--other"], true);
+        assert_eq!(result.unwrap_err().kind(), &ErrorKind::InvalidArgumentCount);
+        assert_eq!(position, 0);
+    }
+
+    #[test]
+    fn assign_option_values_eoo_look_ahead_test() {
+        let option = CommandOption::new("size").arg(Argument::with_name("size"));
+
+        // The values before `--` are for the option
+        let (result, position) = assign(&option, &["10", "20", "--", "file"], true);
+        assert_eq!(result.unwrap_err().kind(), &ErrorKind::InvalidArgumentCount);
+        assert_eq!(position, 2);
+
+        // The values after the first are for the raw arguments of the command
+        let (result, position) = assign(&option, &["10", "20", "--", "file"], false);
+        assert_eq!(values(&result.unwrap()), vec![vec!["10"]]);
+        assert_eq!(position, 1);
+
+        let (result, position) = assign(&option, &["10", "20"], true);
+        assert_eq!(values(&result.unwrap()), vec![vec!["10"]]);
+        assert_eq!(position, 1);
+
+        let option = CommandOption::new("numbers").arg(Argument::one_or_more("numbers"));
+
+        let (result, position) = assign(&option, &["1", "2", "--", "3"], true);
+        assert_eq!(values(&result.unwrap()), vec![vec!["1", "2"]]);
+        assert_eq!(position, 2);
+    }
+
+    #[test]
+    fn assign_option_values_default_values_test() {
+        let option = CommandOption::new("range")
+            .arg(Argument::with_name("min").default(0))
+            .arg(Argument::with_name("max"));
+
+        let (result, position) = assign(&option, &["20"], true);
+        assert_eq!(values(&result.unwrap()), vec![vec!["0"], vec!["20"]]);
+        assert_eq!(position, 1);
+
+        let (result, position) = assign(&option, &["10", "20", "--verbose"], true);
+        assert_eq!(values(&result.unwrap()), vec![vec!["10"], vec!["20"]]);
+        assert_eq!(position, 2);
+    }
+
+    #[test]
+    fn assign_option_values_delimiter_test() {
+        let option = CommandOption::new("colors")
+            .arg(Argument::one_or_more("colors").value_delimiter(',').valid_values(["red", "blue"]));
+
+        let (result, _) = assign(&option, &["red,blue", "red"], true);
+        assert_eq!(values(&result.unwrap()), vec![vec!["red", "blue", "red"]]);
+
+        let (result, _) = assign(&option, &["red,,blue"], true);
+        assert_eq!(result.unwrap_err().kind(), &ErrorKind::InvalidExpression);
+    }
+
+    #[test]
+    fn assign_option_values_invalid_utf8_test() {
+        let option = CommandOption::new("path").arg(Argument::with_name("path"));
+        let cursor = Cursor::new(args(&["a\u{FFFD}b"]));
+        let invalid_utf8 = RefCell::new(vec![(String::from("a\u{FFFD}b"), OsString::from("raw"))]);
+        let context = AssignContext { eoo_look_ahead: true, invalid_utf8: &invalid_utf8 };
+
+        let error = assign_option_values(&cursor, &option, &context).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidUtf8(_)));
+    }
+}
@@ -5,10 +5,85 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::ser::{SerializeSeq, SerializeStruct};
 
-use crate::{Argument, ArgumentList, Command, CommandOption, OptionList};
+use crate::{Argument, ArgumentList, Command, CommandOption, CommandSettings, OptionList, Setting, ValueHint};
 use crate::serde::internal::StringOrList;
 use crate::serde::valid_type::ValidType;
 
+/// Constructs a `Command` from its JSON definition.
+///
+/// Requires `json` feature enable.
+///
+/// # Example
+/// ```
+/// let command = clapi::from_json_str(r#"{
+///     "name": "MyApp",
+///     "options": [{ "name": "color", "args": [{ "name": "color", "valid_values": ["red", "green"] }] }]
+/// }"#).unwrap();
+///
+/// assert!(command.get_options().contains("color"));
+/// ```
+#[cfg(feature = "json")]
+pub fn from_json_str(s: &str) -> crate::Result<Command> {
+    serde_json::from_str(s).map_err(deserialize_error)
+}
+
+/// Constructs a `Command` from its TOML definition.
+///
+/// Requires `toml` feature enable.
+///
+/// # Example
+/// ```
+/// let command = clapi::from_toml_str(r#"
+///     name = "MyApp"
+///
+///     [[options]]
+///     name = "color"
+///     args = [{ name = "color", valid_values = ["red", "green"] }]
+/// "#).unwrap();
+///
+/// assert!(command.get_options().contains("color"));
+/// ```
+#[cfg(feature = "toml")]
+pub fn from_toml_str(s: &str) -> crate::Result<Command> {
+    toml::from_str(s).map_err(deserialize_error)
+}
+
+/// Constructs a `Command` from its YAML definition.
+///
+/// Requires `yaml` feature enable.
+///
+/// # Example
+/// ```
+/// let command = clapi::from_yaml_str(r#"
+///     name: MyApp
+///     options:
+///       - name: color
+///         args:
+///           - name: color
+///             valid_values: [red, green]
+/// "#).unwrap();
+///
+/// assert!(command.get_options().contains("color"));
+/// ```
+#[cfg(feature = "yaml")]
+pub fn from_yaml_str(s: &str) -> crate::Result<Command> {
+    serde_yaml::from_str(s).map_err(deserialize_error)
+}
+
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+fn deserialize_error<E: fmt::Display>(error: E) -> crate::Error {
+    crate::Error::from(crate::ErrorKind::Deserialize(error.to_string()))
+}
+
+#[cfg(feature = "json")]
+impl Command {
+    /// Returns the definition of this command as pretty-printed JSON,
+    /// the handler and the closures of the help and usage are not included.
+    pub fn to_json_string_pretty(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| crate::Error::new(crate::ErrorKind::Other, e.to_string()))
+    }
+}
+
 //  Argument
 impl Serialize for Argument {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -30,7 +105,11 @@ impl Serialize for Argument {
             }
         }
 
-        let mut state = serializer.serialize_struct("Argument", 8)?;
+        // `allow_empty_values` is only serialized if `false` and `value_hint` if set
+        let len = 8
+            + !self.is_empty_values_allowed() as usize
+            + self.get_value_hint().is_some() as usize;
+        let mut state = serializer.serialize_struct("Argument", len)?;
         state.serialize_field("name", &self.get_name())?;
         state.serialize_field("description", &self.get_description())?;
         state.serialize_field("min_values", &self.get_values_count().min())?;
@@ -42,10 +121,62 @@ impl Serialize for Argument {
         state.serialize_field("error", &self.get_validation_error())?;
         state.serialize_field("valid_values", &self.get_valid_values())?;
         state.serialize_field("default_values", &self.get_default_values())?;
+
+        if self.is_empty_values_allowed() {
+            state.skip_field("allow_empty_values")?;
+        } else {
+            state.serialize_field("allow_empty_values", &false)?;
+        }
+
+        match self.get_value_hint() {
+            Some(hint) => state.serialize_field("value_hint", hint)?,
+            None => state.skip_field("value_hint")?,
+        }
+
         state.end()
     }
 }
 
+// ValueHint
+//
+// The hints are serialized as: `file_path`, `dir_path`, `hostname`, `url` and `command_name`,
+// any other value is deserialized as `ValueHint::Other`.
+impl Serialize for ValueHint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        let value = match self {
+            ValueHint::FilePath => "file_path",
+            ValueHint::DirPath => "dir_path",
+            ValueHint::Hostname => "hostname",
+            ValueHint::Url => "url",
+            ValueHint::CommandName => "command_name",
+            ValueHint::Other(placeholder) => placeholder.as_str(),
+        };
+
+        serializer.serialize_str(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for ValueHint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>, {
+        let value = String::deserialize(deserializer)?;
+        let hint = match value.as_str() {
+            "file_path" => ValueHint::FilePath,
+            "dir_path" => ValueHint::DirPath,
+            "hostname" => ValueHint::Hostname,
+            "url" => ValueHint::Url,
+            "command_name" => ValueHint::CommandName,
+            _ if value.trim().is_empty() => return Err(de::Error::custom("`value_hint` cannot be empty")),
+            _ => ValueHint::Other(value),
+        };
+
+        Ok(hint)
+    }
+}
+
 impl<'de> Deserialize<'de> for Argument {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -85,20 +216,20 @@ impl<'de> Deserialize<'de> for ArgumentList {
             where
                 A: SeqAccess<'de>,
             {
-                let mut args = ArgumentList::new();
+                let mut args = Vec::new();
                 while let Some(next_arg) = seq.next_element()? {
-                    args.add(next_arg).expect("duplicated argument");
+                    args.push(next_arg);
                 }
-                Ok(args)
+
+                ArgumentList::try_from_iter(args).map_err(|arg| {
+                    de::Error::custom(format!("duplicated argument `{}`", arg.get_name()))
+                })
             }
 
             fn visit_map<A>(self, map: A) -> Result<Self::Value, <A as MapAccess<'de>>::Error>
                 where A: MapAccess<'de>, {
-                let mut args = ArgumentList::new();
-                args.add(argument::ArgumentVisitor.visit_map(map)?)
-                    .expect("duplicated argument");
-
-                Ok(args)
+                let arg = argument::ArgumentVisitor.visit_map(map)?;
+                Ok(std::iter::once(arg).collect())
             }
         }
 
@@ -112,7 +243,17 @@ impl Serialize for CommandOption {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("CommandOption", 8)?;
+        // The hidden aliases, `counted`, `conflicts_with`, `requires`, `default_value_ifs`
+        // and the occurrences are only serialized if set
+        let len = 8
+            + (self.get_hidden_aliases().len() > 0) as usize
+            + self.is_counted() as usize
+            + !self.get_conflicts().is_empty() as usize
+            + !self.get_requires().is_empty() as usize
+            + !self.get_default_value_ifs().is_empty() as usize
+            + self.get_min_occurrences().is_some() as usize
+            + self.get_max_occurrences().is_some() as usize;
+        let mut state = serializer.serialize_struct("CommandOption", len)?;
         state.serialize_field("name", self.get_name())?;
 
         if self.get_aliases().count() == 1 {
@@ -124,12 +265,57 @@ impl Serialize for CommandOption {
             )?;
         }
 
+        if self.get_hidden_aliases().len() == 0 {
+            state.skip_field("hidden_aliases")?;
+        } else {
+            state.serialize_field(
+                "hidden_aliases",
+                &self.get_hidden_aliases().cloned().collect::<Vec<String>>(),
+            )?;
+        }
+
         state.serialize_field("description", &self.get_description())?;
         state.serialize_field("args", self.get_args())?;
         state.serialize_field("required", &self.is_required())?;
         state.serialize_field("hidden", &self.is_hidden())?;
         state.serialize_field("multiple", &self.allow_multiple())?;
+
+        if self.is_counted() {
+            state.serialize_field("counted", &true)?;
+        } else {
+            state.skip_field("counted")?;
+        }
+
+        match self.get_min_occurrences() {
+            Some(min) => state.serialize_field("min_occurrences", &min)?,
+            None => state.skip_field("min_occurrences")?,
+        }
+
+        match self.get_max_occurrences() {
+            Some(max) => state.serialize_field("max_occurrences", &max)?,
+            None => state.skip_field("max_occurrences")?,
+        }
+
         state.serialize_field("requires_assign", &self.is_assign_required())?;
+
+        if self.get_conflicts().is_empty() {
+            state.skip_field("conflicts_with")?;
+        } else {
+            state.serialize_field("conflicts_with", self.get_conflicts())?;
+        }
+
+        if self.get_requires().is_empty() {
+            state.skip_field("requires")?;
+        } else {
+            state.serialize_field("requires", self.get_requires())?;
+        }
+
+        if self.get_default_value_ifs().is_empty() {
+            state.skip_field("default_value_ifs")?;
+        } else {
+            state.serialize_field("default_value_ifs", self.get_default_value_ifs())?;
+        }
+
         state.end()
     }
 }
@@ -143,23 +329,37 @@ impl<'de> Deserialize<'de> for CommandOption {
             "name",
             "alias",
             "aliases",
+            "hidden_aliases",
             "description",
             "args",
             "required",
             "hidden",
             "multiple",
+            "counted",
+            "min_occurrences",
+            "max_occurrences",
             "requires_assign",
+            "conflicts_with",
+            "requires",
+            "default_value_ifs",
         ];
 
         enum Field {
             Name,
             Aliases,
+            HiddenAliases,
             Description,
             Args,
             Required,
             Hidden,
             Multiple,
+            Counted,
+            MinOccurrences,
+            MaxOccurrences,
             RequiresAssign,
+            ConflictsWith,
+            Requires,
+            DefaultValueIfs,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -184,12 +384,19 @@ impl<'de> Deserialize<'de> for CommandOption {
                         match v {
                             "name" => Ok(Field::Name),
                             "aliases" | "alias" => Ok(Field::Aliases),
+                            "hidden_aliases" => Ok(Field::HiddenAliases),
                             "description" => Ok(Field::Description),
                             "args" => Ok(Field::Args),
                             "required" => Ok(Field::Required),
                             "hidden" => Ok(Field::Hidden),
                             "multiple" => Ok(Field::Multiple),
+                            "counted" => Ok(Field::Counted),
+                            "min_occurrences" => Ok(Field::MinOccurrences),
+                            "max_occurrences" => Ok(Field::MaxOccurrences),
                             "requires_assign" => Ok(Field::RequiresAssign),
+                            "conflicts_with" => Ok(Field::ConflictsWith),
+                            "requires" => Ok(Field::Requires),
+                            "default_value_ifs" => Ok(Field::DefaultValueIfs),
                             _ => return Err(de::Error::unknown_field(v, FIELDS)),
                         }
                     }
@@ -201,12 +408,19 @@ impl<'de> Deserialize<'de> for CommandOption {
                         match v {
                             b"name" => Ok(Field::Name),
                             b"aliases" | b"alias" => Ok(Field::Aliases),
+                            b"hidden_aliases" => Ok(Field::HiddenAliases),
                             b"description" => Ok(Field::Description),
                             b"args" => Ok(Field::Args),
                             b"required" => Ok(Field::Required),
                             b"hidden" => Ok(Field::Hidden),
                             b"multiple" => Ok(Field::Multiple),
+                            b"counted" => Ok(Field::Counted),
+                            b"min_occurrences" => Ok(Field::MinOccurrences),
+                            b"max_occurrences" => Ok(Field::MaxOccurrences),
                             b"requires_assign" => Ok(Field::RequiresAssign),
+                            b"conflicts_with" => Ok(Field::ConflictsWith),
+                            b"requires" => Ok(Field::Requires),
+                            b"default_value_ifs" => Ok(Field::DefaultValueIfs),
                             _ => {
                                 let value = String::from_utf8_lossy(v);
                                 return Err(de::Error::unknown_field(&value, FIELDS));
@@ -233,12 +447,19 @@ impl<'de> Deserialize<'de> for CommandOption {
             {
                 let mut name: Option<String> = None;
                 let mut aliases: Option<Vec<String>> = None;
+                let mut hidden_aliases: Option<Vec<String>> = None;
                 let mut description: Option<Option<String>> = None;
                 let mut args: Option<ArgumentList> = None;
                 let mut required: Option<bool> = None;
                 let mut hidden : Option<bool> = None;
                 let mut multiple : Option<bool> = None;
+                let mut counted : Option<bool> = None;
+                let mut min_occurrences: Option<usize> = None;
+                let mut max_occurrences: Option<usize> = None;
                 let mut requires_assign: Option<bool> = None;
+                let mut conflicts_with: Option<Vec<String>> = None;
+                let mut requires: Option<Vec<String>> = None;
+                let mut default_value_ifs: Option<Vec<(String, Option<String>, String)>> = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -260,6 +481,13 @@ impl<'de> Deserialize<'de> for CommandOption {
                             };
                             //aliases = Some(map.next_value()?);
                         }
+                        Field::HiddenAliases => {
+                            if hidden_aliases.is_some() {
+                                return Err(de::Error::duplicate_field("hidden_aliases"));
+                            }
+
+                            hidden_aliases = Some(map.next_value()?);
+                        }
                         Field::Description => {
                             if description.is_some() {
                                 return Err(de::Error::duplicate_field("description"));
@@ -295,6 +523,27 @@ impl<'de> Deserialize<'de> for CommandOption {
 
                             multiple = Some(map.next_value()?);
                         }
+                        Field::Counted => {
+                            if counted.is_some() {
+                                return Err(de::Error::duplicate_field("counted"));
+                            }
+
+                            counted = Some(map.next_value()?);
+                        }
+                        Field::MinOccurrences => {
+                            if min_occurrences.is_some() {
+                                return Err(de::Error::duplicate_field("min_occurrences"));
+                            }
+
+                            min_occurrences = Some(map.next_value()?);
+                        }
+                        Field::MaxOccurrences => {
+                            if max_occurrences.is_some() {
+                                return Err(de::Error::duplicate_field("max_occurrences"));
+                            }
+
+                            max_occurrences = Some(map.next_value()?);
+                        }
                         Field::RequiresAssign => {
                             if requires_assign.is_some() {
                                 return Err(de::Error::duplicate_field("requires_assign"));
@@ -302,6 +551,27 @@ impl<'de> Deserialize<'de> for CommandOption {
 
                             requires_assign = Some(map.next_value()?);
                         }
+                        Field::ConflictsWith => {
+                            if conflicts_with.is_some() {
+                                return Err(de::Error::duplicate_field("conflicts_with"));
+                            }
+
+                            conflicts_with = Some(map.next_value()?);
+                        }
+                        Field::Requires => {
+                            if requires.is_some() {
+                                return Err(de::Error::duplicate_field("requires"));
+                            }
+
+                            requires = Some(map.next_value()?);
+                        }
+                        Field::DefaultValueIfs => {
+                            if default_value_ifs.is_some() {
+                                return Err(de::Error::duplicate_field("default_value_ifs"));
+                            }
+
+                            default_value_ifs = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -310,15 +580,24 @@ impl<'de> Deserialize<'de> for CommandOption {
 
                 if let Some(aliases) = aliases {
                     for alias in aliases {
-                        // Prints duplicated aliases warnings
                         if option.has_alias(&alias) {
-                            println!("duplicated alias `{}`", alias);
+                            return Err(de::Error::custom(format!("duplicated alias `{}`", alias)));
                         }
 
                         option = option.alias(alias);
                     }
                 }
 
+                if let Some(hidden_aliases) = hidden_aliases {
+                    for alias in hidden_aliases {
+                        if option.has_alias(&alias) {
+                            return Err(de::Error::custom(format!("duplicated alias `{}`", alias)));
+                        }
+
+                        option = option.hidden_alias(alias);
+                    }
+                }
+
                 if let Some(Some(description)) = description {
                     option = option.description(description);
                 }
@@ -339,10 +618,60 @@ impl<'de> Deserialize<'de> for CommandOption {
                     option = option.multiple(multiple);
                 }
 
+                if let Some(counted) = counted {
+                    if counted && option.take_args() {
+                        return Err(de::Error::custom(format!(
+                            "`{}` cannot be counted because takes arguments",
+                            option.get_name()
+                        )));
+                    }
+
+                    option = option.counted(counted);
+                }
+
+                if let (Some(min), Some(max)) = (min_occurrences, max_occurrences) {
+                    if min > max {
+                        return Err(de::Error::custom(format!(
+                            "`min_occurrences` is greater than `max_occurrences`: {} > {}",
+                            min, max
+                        )));
+                    }
+                }
+
+                if let Some(min) = min_occurrences {
+                    option = option.min_occurrences(min);
+                }
+
+                if let Some(max) = max_occurrences {
+                    if max == 0 {
+                        return Err(de::Error::custom("`max_occurrences` cannot be 0"));
+                    }
+
+                    option = option.max_occurrences(max);
+                }
+
                 if let Some(requires_assign) = requires_assign {
                     option = option.requires_assign(requires_assign);
                 }
 
+                if let Some(conflicts_with) = conflicts_with {
+                    for name in conflicts_with {
+                        option = option.conflicts_with(name);
+                    }
+                }
+
+                if let Some(requires) = requires {
+                    for name in requires {
+                        option = option.requires(name);
+                    }
+                }
+
+                if let Some(default_value_ifs) = default_value_ifs {
+                    for (name, value, default) in default_value_ifs {
+                        option = option.default_value_if(name, value.as_deref(), default);
+                    }
+                }
+
                 Ok(option)
             }
         }
@@ -382,11 +711,14 @@ impl<'de> Deserialize<'de> for OptionList {
             where
                 A: SeqAccess<'de>,
             {
-                let mut options = OptionList::new();
+                let mut options = Vec::new();
                 while let Some(next_option) = seq.next_element()? {
-                    options.add(next_option).unwrap();
+                    options.push(next_option);
                 }
-                Ok(options)
+
+                OptionList::try_from_iter(options).map_err(|option| {
+                    de::Error::custom(format!("duplicated option `{}`", option.get_name()))
+                })
             }
         }
 
@@ -394,21 +726,95 @@ impl<'de> Deserialize<'de> for OptionList {
     }
 }
 
+// CommandSettings
+impl Serialize for CommandSettings {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let settings = self.iter().collect::<Vec<Setting>>();
+        let mut seq = serializer.serialize_seq(Some(settings.len()))?;
+        for setting in settings {
+            seq.serialize_element(setting.as_str())?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CommandSettings {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        names
+            .iter()
+            .map(|name| name.parse::<Setting>().map_err(de::Error::custom))
+            .collect()
+    }
+}
+
 // Command
 impl Serialize for Command {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Command", 8)?;
+        // The aliases, summary, before_help and after_help are only serialized if set
+        let len = 9
+            + (self.get_aliases().len() > 0) as usize
+            + (self.get_hidden_aliases().len() > 0) as usize
+            + self.has_summary() as usize
+            + self.get_before_help().is_some() as usize
+            + self.get_after_help().is_some() as usize;
+        let mut state = serializer.serialize_struct("Command", len)?;
         state.serialize_field("name", self.get_name())?;
+
+        if self.get_aliases().len() == 0 {
+            state.skip_field("aliases")?;
+        } else {
+            state.serialize_field("aliases", &self.get_aliases().cloned().collect::<Vec<String>>())?;
+        }
+
+        if self.get_hidden_aliases().len() == 0 {
+            state.skip_field("hidden_aliases")?;
+        } else {
+            state.serialize_field(
+                "hidden_aliases",
+                &self.get_hidden_aliases().cloned().collect::<Vec<String>>(),
+            )?;
+        }
+
         state.serialize_field("description", &self.get_description())?;
-        state.serialize_field("usage", &self.get_usage())?;
-        state.serialize_field("help", &self.get_help())?;
+        if self.has_summary() {
+            state.serialize_field("summary", &self.get_summary())?;
+        } else {
+            state.skip_field("summary")?;
+        }
+        // Function-backed values are skipped
+        if self.is_usage_fn() {
+            state.skip_field("usage")?;
+        } else {
+            state.serialize_field("usage", &self.get_usage())?;
+        }
+        if self.is_help_fn() {
+            state.skip_field("help")?;
+        } else {
+            state.serialize_field("help", &self.get_help())?;
+        }
+        match self.get_before_help() {
+            Some(before_help) => state.serialize_field("before_help", before_help)?,
+            None => state.skip_field("before_help")?,
+        }
+        match self.get_after_help() {
+            Some(after_help) => state.serialize_field("after_help", after_help)?,
+            None => state.skip_field("after_help")?,
+        }
         state.serialize_field("subcommands", &self.get_subcommands().cloned().collect::<Vec<Command>>())?;
         state.serialize_field("options", &self.get_options())?;
         state.serialize_field("args", &self.get_args())?;
         state.serialize_field("hidden", &self.is_hidden())?;
+        state.serialize_field("settings", self.get_settings())?;
         state.end()
     }
 }
@@ -420,24 +826,36 @@ impl<'de> Deserialize<'de> for Command {
     {
         const FIELDS: &'static [&'static str] = &[
             "name",
+            "aliases",
+            "hidden_aliases",
             "description",
+            "summary",
             "usage",
             "help",
+            "before_help",
+            "after_help",
             "subcommands",
             "options",
             "args",
             "hidden",
+            "settings",
         ];
 
         enum Field {
             Name,
+            Aliases,
+            HiddenAliases,
             Description,
+            Summary,
             Usage,
             Help,
+            BeforeHelp,
+            AfterHelp,
             Subcommands,
             Options,
             Args,
             Hidden,
+            Settings,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -461,13 +879,19 @@ impl<'de> Deserialize<'de> for Command {
                     {
                         match v {
                             "name" => Ok(Field::Name),
+                            "aliases" => Ok(Field::Aliases),
+                            "hidden_aliases" => Ok(Field::HiddenAliases),
                             "description" => Ok(Field::Description),
+                            "summary" => Ok(Field::Summary),
                             "usage" => Ok(Field::Usage),
                             "help" => Ok(Field::Help),
+                            "before_help" => Ok(Field::BeforeHelp),
+                            "after_help" => Ok(Field::AfterHelp),
                             "subcommands" => Ok(Field::Subcommands),
                             "options" => Ok(Field::Options),
                             "args" => Ok(Field::Args),
                             "hidden" => Ok(Field::Hidden),
+                            "settings" => Ok(Field::Settings),
                             _ => return Err(de::Error::unknown_field(v, FIELDS)),
                         }
                     }
@@ -478,13 +902,19 @@ impl<'de> Deserialize<'de> for Command {
                     {
                         match v {
                             b"name" => Ok(Field::Name),
+                            b"aliases" => Ok(Field::Aliases),
+                            b"hidden_aliases" => Ok(Field::HiddenAliases),
                             b"description" => Ok(Field::Description),
+                            b"summary" => Ok(Field::Summary),
                             b"usage" => Ok(Field::Usage),
                             b"help" => Ok(Field::Help),
+                            b"before_help" => Ok(Field::BeforeHelp),
+                            b"after_help" => Ok(Field::AfterHelp),
                             b"subcommands" => Ok(Field::Subcommands),
                             b"options" => Ok(Field::Options),
                             b"args" => Ok(Field::Args),
                             b"hidden" => Ok(Field::Hidden),
+                            b"settings" => Ok(Field::Settings),
                             _ => {
                                 let value = String::from_utf8_lossy(v);
                                 return Err(de::Error::unknown_field(&value, FIELDS));
@@ -510,13 +940,19 @@ impl<'de> Deserialize<'de> for Command {
                 A: MapAccess<'de>,
             {
                 let mut name: Option<String> = None;
+                let mut aliases: Option<Vec<String>> = None;
+                let mut hidden_aliases: Option<Vec<String>> = None;
                 let mut description: Option<Option<String>> = None;
+                let mut summary: Option<Option<String>> = None;
                 let mut usage: Option<Option<String>> = None;
                 let mut help: Option<Option<String>> = None;
+                let mut before_help: Option<Option<String>> = None;
+                let mut after_help: Option<Option<String>> = None;
                 let mut subcommands: Option<Vec<Command>> = None;
                 let mut options: Option<OptionList> = None;
                 let mut args: Option<ArgumentList> = None;
                 let mut hidden : Option<bool> = None;
+                let mut settings: Option<CommandSettings> = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -527,6 +963,20 @@ impl<'de> Deserialize<'de> for Command {
 
                             name = Some(map.next_value()?);
                         }
+                        Field::Aliases => {
+                            if aliases.is_some() {
+                                return Err(de::Error::duplicate_field("aliases"));
+                            }
+
+                            aliases = Some(map.next_value()?);
+                        }
+                        Field::HiddenAliases => {
+                            if hidden_aliases.is_some() {
+                                return Err(de::Error::duplicate_field("hidden_aliases"));
+                            }
+
+                            hidden_aliases = Some(map.next_value()?);
+                        }
                         Field::Description => {
                             if description.is_some() {
                                 return Err(de::Error::duplicate_field("description"));
@@ -534,6 +984,13 @@ impl<'de> Deserialize<'de> for Command {
 
                             description = Some(map.next_value()?);
                         }
+                        Field::Summary => {
+                            if summary.is_some() {
+                                return Err(de::Error::duplicate_field("summary"));
+                            }
+
+                            summary = Some(map.next_value()?);
+                        }
                         Field::Usage => {
                             if usage.is_some() {
                                 return Err(de::Error::duplicate_field("usage"));
@@ -548,6 +1005,20 @@ impl<'de> Deserialize<'de> for Command {
 
                             help = Some(map.next_value()?);
                         }
+                        Field::BeforeHelp => {
+                            if before_help.is_some() {
+                                return Err(de::Error::duplicate_field("before_help"));
+                            }
+
+                            before_help = Some(map.next_value()?);
+                        }
+                        Field::AfterHelp => {
+                            if after_help.is_some() {
+                                return Err(de::Error::duplicate_field("after_help"));
+                            }
+
+                            after_help = Some(map.next_value()?);
+                        }
                         Field::Subcommands => {
                             if subcommands.is_some() {
                                 return Err(de::Error::duplicate_field("subcommands"));
@@ -576,16 +1047,43 @@ impl<'de> Deserialize<'de> for Command {
 
                             hidden = Some(map.next_value()?);
                         }
+                        Field::Settings => {
+                            if settings.is_some() {
+                                return Err(de::Error::duplicate_field("settings"));
+                            }
+
+                            settings = Some(map.next_value()?);
+                        }
                     }
                 }
 
                 let mut command =
                     Command::new(name.ok_or_else(|| de::Error::missing_field("name"))?);
 
+                for alias in aliases.unwrap_or_default() {
+                    if command.has_alias(&alias) {
+                        return Err(de::Error::custom(format!("duplicated alias `{}`", alias)));
+                    }
+
+                    command = command.alias(alias);
+                }
+
+                for alias in hidden_aliases.unwrap_or_default() {
+                    if command.has_alias(&alias) {
+                        return Err(de::Error::custom(format!("duplicated alias `{}`", alias)));
+                    }
+
+                    command = command.hidden_alias(alias);
+                }
+
                 if let Some(Some(description)) = description {
                     command = command.description(description);
                 }
 
+                if let Some(Some(summary)) = summary {
+                    command = command.summary(summary);
+                }
+
                 if let Some(Some(usage)) = usage {
                     command = command.usage(usage);
                 }
@@ -594,6 +1092,14 @@ impl<'de> Deserialize<'de> for Command {
                     command = command.help(help);
                 }
 
+                if let Some(Some(before_help)) = before_help {
+                    command = command.before_help(before_help);
+                }
+
+                if let Some(Some(after_help)) = after_help {
+                    command = command.after_help(after_help);
+                }
+
                 if let Some(subcommands) = subcommands {
                     for subcommand in subcommands {
                         command = command.subcommand(subcommand)
@@ -612,6 +1118,12 @@ impl<'de> Deserialize<'de> for Command {
                     command = command.hidden(hidden)
                 }
 
+                if let Some(settings) = settings {
+                    for setting in settings.iter() {
+                        command = command.setting(setting);
+                    }
+                }
+
                 Ok(command)
             }
         }
@@ -689,80 +1201,424 @@ mod internal {
     }
 }
 
-#[cfg(feature = "typing")]
-mod valid_type {
-    use std::any::TypeId;
-    use std::fmt::{Display, Formatter};
-    use std::net::{IpAddr, SocketAddr};
-    use serde::{Deserialize, Serialize};
-    use crate::Argument;
-    use crate::typing::Type;
-    use crate::validator::validate_type;
+// Deserializes the arguments of an option as a struct where each field is an argument name,
+// or the options nested under a prefix where each field is the next segment of its names
+pub(crate) mod option_args {
+    use std::fmt::{self, Display, Formatter};
+    use std::str::FromStr;
 
-    /// Declares the enum `ValidType` used for serialize the type of an argument.
-    macro_rules! declare_impl_valid_type {
-        ('primitives: $($ty:ty => $variant:ident),+
-            'other: $($ty2:ty => $variant2:ident $name:literal),* $(,)?) => {
+    use serde::de::value::{MapDeserializer, SeqDeserializer};
+    use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+    use serde::forward_to_deserialize_any;
 
-            #[derive(Debug, Clone, Serialize, Deserialize)]
-            #[serde(rename_all="lowercase")]
-            pub enum ValidType {
-                $(
-                    $variant
-                ),+,
-                $(
-                    #[serde(rename=$name)]
-                    $variant2
-                ),*
-            }
+    use crate::{ArgumentList, CommandOption, Error, ErrorKind};
 
-            impl ValidType {
-                pub fn from_type(ty: &Type) -> Option<ValidType>{
-                    let id = ty.id();
-                    $(
-                        if TypeId::of::<$ty>() == id {
-                            return Some(ValidType::$variant);
-                        }
-                    )+
+    /// Deserializes the values of the given `ArgumentList` into `T`.
+    pub fn from_args<T: DeserializeOwned>(option: &str, args: &ArgumentList) -> crate::Result<T> {
+        T::deserialize(ArgsDeserializer(args)).map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("cannot deserialize option `{}`: {}", option, e),
+            )
+        })
+    }
 
-                     $(
-                        if TypeId::of::<$ty2>() == id {
-                            return Some(ValidType::$variant2);
-                        }
-                     )*
+    /// Deserializes the options nested under the given prefix into `T`,
+    /// each option is paired with the rest of its name, like `tls.cert` for `server.tls.cert`.
+    pub fn from_nested<'a, T, I>(prefix: &str, options: I) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator<Item = (&'a str, &'a CommandOption)>,
+    {
+        let mut entries = Vec::new();
+        for (leaf, option) in options {
+            insert_node(&mut entries, leaf, Node::from_option(option));
+        }
 
-                    None
-                }
+        T::deserialize(Node::Nested(entries)).map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("cannot deserialize option `{}`: {}", prefix, e),
+            )
+        })
+    }
 
-                pub fn set_validator(&self, arg: Argument) -> Argument {
-                    match self {
-                        $(
-                            ValidType::$variant => arg.validator(validate_type::<$ty>())
-                        ),+,
-                        $(
-                            ValidType::$variant2 => arg.validator(validate_type::<$ty2>())
-                        ),*
-                    }
-                }
+    // Inserts the node in the entry of each segment of the path, a later node replaces the previous one
+    fn insert_node<'de>(entries: &mut Vec<(String, Node<'de>)>, path: &str, node: Node<'de>) {
+        let (key, rest) = match path.split_once('.') {
+            Some((key, rest)) => (key, Some(rest)),
+            None => (path, None),
+        };
 
-                pub fn as_str(&self) -> &'static str {
-                    match self {
-                        $(
-                            ValidType::$variant => stringify!($ty)
-                        ),+,
-                        $(
-                            ValidType::$variant2 => $name
-                        ),*
-                    }
-                }
+        let index = match entries.iter().position(|(k, _)| k == key) {
+            Some(index) => index,
+            None => {
+                entries.push((key.to_owned(), Node::Nested(Vec::new())));
+                entries.len() - 1
             }
+        };
 
-            impl Display for ValidType {
-                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-                    write!(f, "{}", self.as_str())
+        match rest {
+            None => entries[index].1 = node,
+            Some(rest) => {
+                if !matches!(entries[index].1, Node::Nested(_)) {
+                    entries[index].1 = Node::Nested(Vec::new());
+                }
+
+                if let Node::Nested(children) = &mut entries[index].1 {
+                    insert_node(children, rest, node);
                 }
             }
-        };
+        }
+    }
+
+    // The values of a nested option, or the options nested under a segment like `tls` in `server.tls.cert`
+    enum Node<'de> {
+        // An option without arguments, always `true`
+        Flag,
+        // The values of an option with 1 argument
+        Values(ValuesDeserializer<'de>),
+        // The arguments of an option with several arguments
+        Args(&'de ArgumentList),
+        // The options keyed by the next segment of its names
+        Nested(Vec<(String, Node<'de>)>),
+    }
+
+    impl<'de> Node<'de> {
+        fn from_option(option: &'de CommandOption) -> Self {
+            match option.get_args().len() {
+                0 => Node::Flag,
+                1 => Node::Values(ValuesDeserializer {
+                    name: option.get_name(),
+                    values: option.get_arg().unwrap().get_values(),
+                }),
+                _ => Node::Args(option.get_args()),
+            }
+        }
+    }
+
+    impl<'de> IntoDeserializer<'de, DeError> for Node<'de> {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            self
+        }
+    }
+
+    // The values are deserialized by its `ValuesDeserializer`, other nodes are always deserialized with `deserialize_any`
+    macro_rules! forward_to_node {
+        ($($method:ident($($arg:ident: $ty:ty),*)),+ $(,)?) => {
+            $(
+                fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+                    match self {
+                        Node::Values(values) => values.$method($($arg,)* visitor),
+                        node => node.deserialize_any(visitor),
+                    }
+                }
+            )+
+        };
+    }
+
+    impl<'de> de::Deserializer<'de> for Node<'de> {
+        type Error = DeError;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self {
+                Node::Flag => visitor.visit_bool(true),
+                Node::Values(values) => values.deserialize_any(visitor),
+                Node::Args(args) => ArgsDeserializer(args).deserialize_any(visitor),
+                Node::Nested(entries) => {
+                    let mut map = MapDeserializer::new(entries.into_iter());
+                    let value = visitor.visit_map(&mut map)?;
+                    map.end()?;
+                    Ok(value)
+                }
+            }
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self {
+                Node::Values(values) => values.deserialize_option(visitor),
+                node => visitor.visit_some(node),
+            }
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            match self {
+                Node::Values(values) => values.deserialize_newtype_struct(name, visitor),
+                node => visitor.visit_newtype_struct(node),
+            }
+        }
+
+        forward_to_node! {
+            deserialize_bool(), deserialize_i8(), deserialize_i16(), deserialize_i32(), deserialize_i64(),
+            deserialize_i128(), deserialize_u8(), deserialize_u16(), deserialize_u32(), deserialize_u64(),
+            deserialize_u128(), deserialize_f32(), deserialize_f64(), deserialize_char(), deserialize_str(),
+            deserialize_string(), deserialize_bytes(), deserialize_byte_buf(), deserialize_unit(),
+            deserialize_seq(), deserialize_map(), deserialize_identifier(), deserialize_ignored_any(),
+            deserialize_unit_struct(name: &'static str),
+            deserialize_tuple(len: usize),
+            deserialize_tuple_struct(name: &'static str, len: usize),
+            deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+            deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct DeError(String);
+
+    impl Display for DeError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::error::Error for DeError {}
+
+    impl de::Error for DeError {
+        fn custom<T: Display>(msg: T) -> Self {
+            DeError(msg.to_string())
+        }
+    }
+
+    struct ArgsDeserializer<'de>(&'de ArgumentList);
+
+    impl<'de> de::Deserializer<'de> for ArgsDeserializer<'de> {
+        type Error = DeError;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            let entries = self.0.iter().map(|arg| {
+                let values = ValuesDeserializer {
+                    name: arg.get_name(),
+                    values: arg.get_values(),
+                };
+
+                (arg.get_name(), values)
+            });
+
+            let mut map = MapDeserializer::new(entries);
+            let value = visitor.visit_map(&mut map)?;
+            map.end()?;
+            Ok(value)
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    // The values of an argument
+    struct ValuesDeserializer<'de> {
+        name: &'de str,
+        values: &'de [String],
+    }
+
+    macro_rules! deserialize_from_str {
+        ($($method:ident => $visit:ident),+ $(,)?) => {
+            $(
+                fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                    visitor.$visit(self.parse()?)
+                }
+            )+
+        };
+    }
+
+    impl<'de> ValuesDeserializer<'de> {
+        fn single(&self) -> Result<&'de str, DeError> {
+            match self.values {
+                [value] => Ok(value.as_str()),
+                _ => Err(DeError(format!(
+                    "expected 1 value for `{}` but was {}",
+                    self.name,
+                    self.values.len()
+                ))),
+            }
+        }
+
+        fn parse<T>(&self) -> Result<T, DeError>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            let value = self.single()?;
+            value.parse::<T>().map_err(|e| {
+                DeError(format!("invalid value for `{}`: `{}`, {}", self.name, value, e))
+            })
+        }
+    }
+
+    impl<'de> IntoDeserializer<'de, DeError> for ValuesDeserializer<'de> {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self::Deserializer {
+            self
+        }
+    }
+
+    impl<'de> de::Deserializer<'de> for ValuesDeserializer<'de> {
+        type Error = DeError;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self.values.len() {
+                0 => visitor.visit_unit(),
+                1 => visitor.visit_borrowed_str(self.values[0].as_str()),
+                _ => self.deserialize_seq(visitor),
+            }
+        }
+
+        deserialize_from_str! {
+            deserialize_bool => visit_bool,
+            deserialize_i8 => visit_i8,
+            deserialize_i16 => visit_i16,
+            deserialize_i32 => visit_i32,
+            deserialize_i64 => visit_i64,
+            deserialize_i128 => visit_i128,
+            deserialize_u8 => visit_u8,
+            deserialize_u16 => visit_u16,
+            deserialize_u32 => visit_u32,
+            deserialize_u64 => visit_u64,
+            deserialize_u128 => visit_u128,
+            deserialize_f32 => visit_f32,
+            deserialize_f64 => visit_f64,
+            deserialize_char => visit_char,
+        }
+
+        fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.visit_borrowed_str(self.single()?)
+        }
+
+        fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_str(visitor)
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            if self.values.is_empty() {
+                visitor.visit_none()
+            } else {
+                visitor.visit_some(self)
+            }
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            let name = self.name;
+            let values = self.values.iter().map(move |value| ValuesDeserializer {
+                name,
+                values: std::slice::from_ref(value),
+            });
+
+            let mut seq = SeqDeserializer::new(values);
+            let value = visitor.visit_seq(&mut seq)?;
+            seq.end()?;
+            Ok(value)
+        }
+
+        fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_seq(visitor)
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            _: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            visitor.visit_enum(self.single()?.into_deserializer())
+        }
+
+        forward_to_deserialize_any! {
+            bytes byte_buf unit unit_struct tuple_struct map struct identifier ignored_any
+        }
+    }
+}
+
+#[cfg(feature = "typing")]
+mod valid_type {
+    use std::any::TypeId;
+    use std::fmt::{Display, Formatter};
+    use std::net::{IpAddr, SocketAddr};
+    use serde::{Deserialize, Serialize};
+    use crate::Argument;
+    use crate::typing::Type;
+    use crate::validator::validate_type;
+
+    /// Declares the enum `ValidType` used for serialize the type of an argument.
+    macro_rules! declare_impl_valid_type {
+        ('primitives: $($ty:ty => $variant:ident),+
+            'other: $($ty2:ty => $variant2:ident $name:literal),* $(,)?) => {
+
+            #[derive(Debug, Clone, Serialize, Deserialize)]
+            #[serde(rename_all="lowercase")]
+            pub enum ValidType {
+                $(
+                    $variant
+                ),+,
+                $(
+                    #[serde(rename=$name)]
+                    $variant2
+                ),*
+            }
+
+            impl ValidType {
+                pub fn from_type(ty: &Type) -> Option<ValidType>{
+                    let id = ty.id();
+                    $(
+                        if TypeId::of::<$ty>() == id {
+                            return Some(ValidType::$variant);
+                        }
+                    )+
+
+                     $(
+                        if TypeId::of::<$ty2>() == id {
+                            return Some(ValidType::$variant2);
+                        }
+                     )*
+
+                    None
+                }
+
+                pub fn set_validator(&self, arg: Argument) -> Argument {
+                    match self {
+                        $(
+                            ValidType::$variant => arg.validator(validate_type::<$ty>())
+                        ),+,
+                        $(
+                            ValidType::$variant2 => arg.validator(validate_type::<$ty2>())
+                        ),*
+                    }
+                }
+
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $(
+                            ValidType::$variant => stringify!($ty)
+                        ),+,
+                        $(
+                            ValidType::$variant2 => $name
+                        ),*
+                    }
+                }
+            }
+
+            impl Display for ValidType {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{}", self.as_str())
+                }
+            }
+        };
     }
 
     declare_impl_valid_type!{
@@ -798,7 +1654,7 @@ mod argument {
     use serde::{de, Deserialize, Deserializer};
     use serde::de::{MapAccess, Visitor};
 
-    use crate::{ArgCount, Argument};
+    use crate::{ArgCount, Argument, ValueHint};
     use crate::serde::internal::AnyToString;
     #[cfg(feature = "typing")]
     use crate::serde::valid_type::ValidType;
@@ -811,6 +1667,8 @@ mod argument {
         "error",
         "valid_values",
         "default_values",
+        "allow_empty_values",
+        "value_hint",
 
         #[cfg(feature = "typing")]
         "type",
@@ -824,6 +1682,8 @@ mod argument {
         Error,
         ValidValues,
         DefaultValues,
+        AllowEmptyValues,
+        ValueHint,
 
         #[cfg(feature = "typing")]
         Type,
@@ -841,11 +1701,11 @@ mod argument {
                 fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
                     #[cfg(feature = "typing")]
                     {
-                        formatter.write_str("`name`, `description`, `min_values`, `max_values`, `type`, `valid_values` or `default_values`")
+                        formatter.write_str("`name`, `description`, `min_values`, `max_values`, `type`, `valid_values`, `default_values`, `allow_empty_values` or `value_hint`")
                     }
                     #[cfg(not(feature = "typing"))]
                     {
-                        formatter.write_str("`name`, `description`, `min_values`, `max_values`, `valid_values` or `default_values`")
+                        formatter.write_str("`name`, `description`, `min_values`, `max_values`, `valid_values`, `default_values`, `allow_empty_values` or `value_hint`")
                     }
                 }
 
@@ -861,6 +1721,8 @@ mod argument {
                         "error" => Ok(Field::Error),
                         "valid_values" => Ok(Field::ValidValues),
                         "default_values" => Ok(Field::DefaultValues),
+                        "allow_empty_values" => Ok(Field::AllowEmptyValues),
+                        "value_hint" => Ok(Field::ValueHint),
 
                         #[cfg(feature = "typing")]
                         "type" => Ok(Field::Type),
@@ -880,6 +1742,8 @@ mod argument {
                         b"error" => Ok(Field::Error),
                         b"valid_values" => Ok(Field::ValidValues),
                         b"default_values" => Ok(Field::DefaultValues),
+                        b"allow_empty_values" => Ok(Field::AllowEmptyValues),
+                        b"value_hint" => Ok(Field::ValueHint),
 
                         #[cfg(feature = "typing")]
                         b"type" => Ok(Field::Type),
@@ -914,6 +1778,8 @@ mod argument {
             let mut validation_error: Option<Option<String>> = None;
             let mut valid_values: Option<Vec<String>> = None;
             let mut default_values: Option<Vec<String>> = None;
+            let mut allow_empty_values: Option<bool> = None;
+            let mut value_hint: Option<ValueHint> = None;
 
             #[cfg(feature = "typing")]
             let mut valid_type : Option<Option<ValidType>> = None;
@@ -987,6 +1853,20 @@ mod argument {
                                 .collect::<Vec<String>>(),
                         );
                     }
+                    Field::AllowEmptyValues => {
+                        if allow_empty_values.is_some() {
+                            return Err(de::Error::duplicate_field("allow_empty_values"));
+                        }
+
+                        allow_empty_values = Some(map.next_value()?);
+                    }
+                    Field::ValueHint => {
+                        if value_hint.is_some() {
+                            return Err(de::Error::duplicate_field("value_hint"));
+                        }
+
+                        value_hint = Some(map.next_value()?);
+                    }
                 }
             }
 
@@ -1027,6 +1907,14 @@ mod argument {
                 }
             }
 
+            if let Some(allow_empty_values) = allow_empty_values {
+                argument = argument.allow_empty_values(allow_empty_values);
+            }
+
+            if let Some(value_hint) = value_hint {
+                argument = argument.value_hint(value_hint);
+            }
+
             Ok(argument)
         }
     }
@@ -1044,7 +1932,7 @@ mod tests {
             crate::typing::Type
         };
 
-        use crate::{Argument, ArgumentList};
+        use crate::{Argument, ArgumentList, ValueHint};
         use crate::serde::test_utils::ArgTokens;
         use crate::validator::validate_type;
 
@@ -1092,6 +1980,63 @@ mod tests {
             }
         }
 
+        #[test]
+        fn argument_value_hint_test() {
+            let arg = Argument::with_name("input").value_hint(ValueHint::FilePath);
+
+            serde_test::assert_tokens(
+                &arg,
+                ArgTokens::new("input")
+                    .value_count(1)
+                    .value_hint("file_path")
+                    .to_tokens()
+                    .as_slice(),
+            );
+
+            let arg = Argument::with_name("port").value_hint(ValueHint::Other("PORT".to_owned()));
+
+            serde_test::assert_tokens(
+                &arg,
+                ArgTokens::new("port")
+                    .value_count(1)
+                    .value_hint("PORT")
+                    .to_tokens()
+                    .as_slice(),
+            );
+
+            let arg = serde_json::from_str::<Argument>(
+                r#"{ "name": "target", "value_hint": "dir_path" }"#
+            ).unwrap();
+
+            assert_eq!(arg.get_value_hint(), Some(&ValueHint::DirPath));
+
+            let error = serde_json::from_str::<Argument>(
+                r#"{ "name": "target", "value_hint": " " }"#
+            ).unwrap_err();
+
+            assert!(error.to_string().contains("`value_hint` cannot be empty"));
+        }
+
+        #[test]
+        fn argument_allow_empty_values_test() {
+            let arg = Argument::with_name("prefix").allow_empty_values(false);
+
+            serde_test::assert_tokens(
+                &arg,
+                ArgTokens::new("prefix")
+                    .value_count(1)
+                    .allow_empty_values(false)
+                    .to_tokens()
+                    .as_slice()
+            );
+
+            let arg = serde_json::from_str::<Argument>(
+                r#"{ "name": "prefix", "allow_empty_values": false }"#
+            ).unwrap();
+
+            assert!(!arg.is_empty_values_allowed());
+        }
+
         #[test]
         fn argument_missing_fields_test1() {
             let arg = Argument::with_name("numbers");
@@ -1182,6 +2127,16 @@ mod tests {
             assert_eq!(arg.get_values_count().min(), Some(2));
             assert_eq!(arg.get_values_count().max(), Some(10));
         }
+
+        #[test]
+        fn argument_list_duplicated_from_json_test() {
+            let error = serde_json::from_str::<ArgumentList>(
+                r#"[{ "name": "value" }, { "name": "value" }]"#,
+            )
+            .unwrap_err();
+
+            assert!(error.to_string().contains("duplicated argument `value`"));
+        }
     }
 
     #[cfg(test)]
@@ -1217,6 +2172,120 @@ mod tests {
                 .as_slice());
         }
 
+        #[test]
+        fn option_occurrences_test() {
+            let opt = CommandOption::new("header")
+                .min_occurrences(1)
+                .max_occurrences(3);
+
+            serde_test::assert_tokens(&opt,
+            OptionTokens::new("header")
+                .multiple(true)
+                .min_occurrences(1)
+                .max_occurrences(3)
+                .to_tokens()
+                .as_slice());
+
+            let option = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "header", "max_occurrences": 2 }"#
+            ).unwrap();
+
+            assert_eq!(option.get_min_occurrences(), None);
+            assert_eq!(option.get_max_occurrences(), Some(2));
+            assert!(option.allow_multiple());
+
+            let error = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "header", "min_occurrences": 3, "max_occurrences": 2 }"#
+            ).unwrap_err();
+
+            assert!(error.to_string().contains("`min_occurrences` is greater than `max_occurrences`"));
+        }
+
+        #[test]
+        fn option_counted_test() {
+            let opt = CommandOption::new("verbose").alias("v").counted(true);
+
+            let json = serde_json::to_string(&opt).unwrap();
+            assert!(json.contains(r#""counted":true"#));
+
+            let option = serde_json::from_str::<CommandOption>(&json).unwrap();
+            assert!(option.is_counted());
+            assert!(option.allow_multiple());
+
+            // Only the counted options serialize the field
+            let json = serde_json::to_string(&CommandOption::new("verbose")).unwrap();
+            assert!(!json.contains("counted"));
+
+            let error = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "level", "counted": true, "args": [{ "name": "level" }] }"#
+            ).unwrap_err();
+
+            assert!(error.to_string().contains("`level` cannot be counted because takes arguments"));
+        }
+
+        #[test]
+        fn option_conflicts_with_test() {
+            let opt = CommandOption::new("json")
+                .conflicts_with("yaml")
+                .conflicts_with("toml");
+
+            serde_test::assert_tokens(&opt,
+            OptionTokens::new("json")
+                .conflicts_with("yaml")
+                .conflicts_with("toml")
+                .to_tokens()
+                .as_slice());
+
+            let option = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "json", "conflicts_with": ["yaml"] }"#
+            ).unwrap();
+
+            assert_eq!(option.get_conflicts(), &["yaml".to_owned()]);
+        }
+
+        #[test]
+        fn option_requires_test() {
+            let opt = CommandOption::new("output")
+                .requires("format")
+                .conflicts_with("stdout");
+
+            serde_test::assert_tokens(&opt,
+            OptionTokens::new("output")
+                .conflicts_with("stdout")
+                .requires("format")
+                .to_tokens()
+                .as_slice());
+
+            let option = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "output", "requires": ["format", "encoding"] }"#
+            ).unwrap();
+
+            assert_eq!(option.get_requires(), &["format".to_owned(), "encoding".to_owned()]);
+        }
+
+        #[test]
+        fn option_default_value_if_test() {
+            let opt = CommandOption::new("format")
+                .default_value_if("output", Some("report.json"), "json")
+                .default_value_if("verbose", None, "text");
+
+            serde_test::assert_tokens(&opt,
+            OptionTokens::new("format")
+                .default_value_if("output", Some("report.json"), "json")
+                .default_value_if("verbose", None, "text")
+                .to_tokens()
+                .as_slice());
+
+            let option = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "format", "default_value_ifs": [["output", "report.json", "json"], ["verbose", null, "text"]] }"#
+            ).unwrap();
+
+            assert_eq!(option.get_default_value_ifs(), &[
+                ("output".to_owned(), Some("report.json".to_owned()), "json".to_owned()),
+                ("verbose".to_owned(), None, "text".to_owned()),
+            ]);
+        }
+
         #[test]
         fn option_missing_fields_test() {
             let option = CommandOption::new("color")
@@ -1270,6 +2339,47 @@ mod tests {
             );
         }
 
+        #[test]
+        fn option_hidden_alias_test() {
+            let opt = CommandOption::new("color")
+                .alias("c")
+                .hidden_alias("colour");
+
+            serde_test::assert_tokens(&opt,
+            OptionTokens::new("color")
+                .alias("c")
+                .hidden_alias("colour")
+                .to_tokens()
+                .as_slice());
+
+            let error = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "color", "aliases": ["c"], "hidden_aliases": ["c"] }"#,
+            )
+            .unwrap_err();
+
+            assert!(error.to_string().contains("duplicated alias `c`"));
+        }
+
+        #[test]
+        fn option_duplicated_alias_from_json_test() {
+            let error = serde_json::from_str::<CommandOption>(
+                r#"{ "name": "color", "aliases" : ["c", "c"] }"#,
+            )
+            .unwrap_err();
+
+            assert!(error.to_string().contains("duplicated alias `c`"));
+        }
+
+        #[test]
+        fn option_list_duplicated_from_json_test() {
+            let error = serde_json::from_str::<OptionList>(
+                r#"[{ "name": "color" }, { "name": "size" }, { "name": "color" }]"#,
+            )
+            .unwrap_err();
+
+            assert!(error.to_string().contains("duplicated option `color`"));
+        }
+
         #[test]
         fn option_list_test() {
             let mut option_list = OptionList::new();
@@ -1297,10 +2407,216 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod option_args_tests {
+        use serde::Deserialize;
+        use crate::{Argument, Command, CommandOption, ParseResult};
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Replace {
+            from: String,
+            to: String,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Range {
+            start: i64,
+            end: i64,
+            step: Option<u32>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Scale {
+            factor: f64,
+            unit: Option<String>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Sum {
+            values: Vec<u32>,
+        }
+
+        fn parse(args: &[&str]) -> ParseResult {
+            Command::new("MyApp")
+                .option(CommandOption::new("replace")
+                    .alias("r")
+                    .arg(Argument::with_name("from"))
+                    .arg(Argument::with_name("to")))
+                .option(CommandOption::new("range")
+                    .arg(Argument::with_name("start"))
+                    .arg(Argument::with_name("end"))
+                    .arg(Argument::with_name("step").default(1)))
+                .option(CommandOption::new("scale")
+                    .arg(Argument::with_name("factor"))
+                    .arg(Argument::zero_or_one("unit")))
+                .option(CommandOption::new("sum")
+                    .arg(Argument::one_or_more("values")))
+                .parse_from(args.to_vec())
+                .unwrap()
+        }
+
+        #[test]
+        fn option_as_two_args_test() {
+            let result = parse(&["-r", "hello", "bye"]);
+            assert_eq!(
+                result.option_as::<Replace>("replace").unwrap(),
+                Replace { from: "hello".to_owned(), to: "bye".to_owned() }
+            );
+            assert_eq!(
+                result.option_as::<Replace>("r").unwrap(),
+                Replace { from: "hello".to_owned(), to: "bye".to_owned() }
+            );
+        }
+
+        #[test]
+        fn option_as_three_args_test() {
+            let result = parse(&["--range", "5", "10", "2"]);
+            assert_eq!(
+                result.option_as::<Range>("range").unwrap(),
+                Range { start: 5, end: 10, step: Some(2) }
+            );
+
+            // The `step` takes its default value
+            let result = parse(&["--range", "0", "10"]);
+            assert_eq!(
+                result.option_as::<Range>("range").unwrap(),
+                Range { start: 0, end: 10, step: Some(1) }
+            );
+        }
+
+        #[test]
+        fn option_as_optional_args_test() {
+            let result = parse(&["--scale", "1.5", "cm"]);
+            assert_eq!(
+                result.option_as::<Scale>("scale").unwrap(),
+                Scale { factor: 1.5, unit: Some("cm".to_owned()) }
+            );
+
+            let result = parse(&["--scale", "2"]);
+            assert_eq!(
+                result.option_as::<Scale>("scale").unwrap(),
+                Scale { factor: 2.0, unit: None }
+            );
+
+            let result = parse(&["--sum", "1", "2", "3"]);
+            assert_eq!(result.option_as::<Sum>("sum").unwrap(), Sum { values: vec![1, 2, 3] });
+        }
+
+        #[test]
+        fn option_as_error_test() {
+            #[derive(Debug, Deserialize)]
+            #[allow(dead_code)]
+            struct Missing {
+                from: String,
+                other: String,
+            }
+
+            let result = parse(&["--replace", "a", "b", "--range", "one", "2"]);
+
+            let error = result.option_as::<Missing>("replace").unwrap_err();
+            assert!(error.to_string().contains("missing field `other`"), "{}", error);
+
+            let error = result.option_as::<Range>("range").unwrap_err();
+            assert!(error.to_string().contains("invalid value for `start`: `one`"), "{}", error);
+
+            let error = result.option_as::<Replace>("other").unwrap_err();
+            assert!(error.to_string().contains("cannot find option named 'other'"), "{}", error);
+
+            // Not passed options have no values
+            let error = parse(&[]).option_as::<Replace>("replace").unwrap_err();
+            assert!(error.to_string().contains("expected 1 value for `from` but was 0"), "{}", error);
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Db {
+            host: String,
+            port: u16,
+            user: Option<String>,
+            #[serde(default)]
+            tls: bool,
+            range: Option<Range>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Server {
+            tls: Tls,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Tls {
+            cert: String,
+            protocols: Vec<String>,
+        }
+
+        fn parse_nested(args: &[&str]) -> ParseResult {
+            Command::new("MyApp")
+                .option(CommandOption::new("db.host").arg(Argument::new()))
+                .option(CommandOption::new("db.port").arg(Argument::new().default(5432)))
+                .option(CommandOption::new("db.user").arg(Argument::new()))
+                .option(CommandOption::new("db.tls"))
+                .option(CommandOption::new("db.range")
+                    .arg(Argument::with_name("start"))
+                    .arg(Argument::with_name("end"))
+                    .arg(Argument::zero_or_one("step")))
+                .option(CommandOption::new("server.tls.cert").arg(Argument::new()))
+                .option(CommandOption::new("server.tls.protocols").arg(Argument::one_or_more("protocols")))
+                .parse_from(args.to_vec())
+                .unwrap()
+        }
+
+        #[test]
+        fn option_as_nested_test() {
+            let result = parse_nested(&["--db.host", "localhost"]);
+            assert_eq!(
+                result.option_as::<Db>("db").unwrap(),
+                Db { host: "localhost".to_owned(), port: 5432, user: None, tls: false, range: None }
+            );
+
+            let result = parse_nested(&["--db.host", "remote", "--db.user", "admin", "--db.tls", "--db.range", "1", "9"]);
+            assert_eq!(
+                result.option_as::<Db>("db").unwrap(),
+                Db {
+                    host: "remote".to_owned(),
+                    port: 5432,
+                    user: Some("admin".to_owned()),
+                    tls: true,
+                    range: Some(Range { start: 1, end: 9, step: None })
+                }
+            );
+
+            // Each segment of the name is a nested struct
+            let result = parse_nested(&["--server.tls.cert", "a.pem", "--server.tls.protocols", "h2", "http/1.1"]);
+            assert_eq!(
+                result.option_as::<Server>("server").unwrap(),
+                Server { tls: Tls { cert: "a.pem".to_owned(), protocols: vec!["h2".to_owned(), "http/1.1".to_owned()] } }
+            );
+            assert_eq!(
+                result.option_as::<Tls>("server.tls").unwrap(),
+                Tls { cert: "a.pem".to_owned(), protocols: vec!["h2".to_owned(), "http/1.1".to_owned()] }
+            );
+        }
+
+        #[test]
+        fn option_as_nested_error_test() {
+            let result = parse_nested(&["--db.host", "localhost", "--db.port", "port"]);
+            let error = result.option_as::<Db>("db").unwrap_err();
+            assert!(error.to_string().contains("cannot deserialize option `db`"), "{}", error);
+            assert!(error.to_string().contains("`port`"), "{}", error);
+
+            let error = parse_nested(&["--server.tls.cert", "a.pem"]).option_as::<Server>("server").unwrap_err();
+            assert!(error.to_string().contains("missing field `protocols`"), "{}", error);
+
+            let error = parse_nested(&[]).option_as::<Db>("data").unwrap_err();
+            assert!(error.to_string().contains("cannot find option named 'data'"), "{}", error);
+        }
+    }
+
     #[cfg(test)]
     mod command_tests {
-        use crate::{ArgCount, Argument, Command, CommandOption};
+        use crate::{ArgCount, Argument, Command, CommandOption, Setting};
         use crate::serde::test_utils::{ArgTokens, CommandTokens, OptionTokens};
+        use std::cell::RefCell;
+        use std::rc::Rc;
 
         #[test]
         fn command_test() {
@@ -1341,6 +2657,71 @@ mod tests {
             );
         }
 
+        #[test]
+        fn command_aliases_test() {
+            let command = Command::new("install")
+                .alias("i")
+                .hidden_alias("add");
+
+            serde_test::assert_tokens(
+                &command,
+                CommandTokens::new("install")
+                    .alias("i")
+                    .hidden_alias("add")
+                    .to_tokens()
+                    .as_slice()
+            );
+
+            let command = serde_json::from_str::<Command>(
+                r#"{ "name": "install", "aliases": ["i"], "hidden_aliases": ["add"] }"#
+            ).unwrap();
+
+            assert_eq!(command.get_aliases().cloned().collect::<Vec<String>>(), vec!["i".to_owned()]);
+            assert_eq!(command.get_hidden_aliases().cloned().collect::<Vec<String>>(), vec!["add".to_owned()]);
+
+            let error = serde_json::from_str::<Command>(
+                r#"{ "name": "install", "aliases": ["i", "i"] }"#
+            ).unwrap_err();
+
+            assert!(error.to_string().contains("duplicated alias `i`"));
+        }
+
+        #[test]
+        fn command_summary_test() {
+            let command = Command::new("log")
+                .description("Shows the logs. Uses a pager if the output is a terminal.")
+                .summary("Shows the logs");
+
+            let json = serde_json::to_string(&command).unwrap();
+            let command = serde_json::from_str::<Command>(&json).unwrap();
+            assert_eq!(command.get_summary(), Some("Shows the logs"));
+
+            // The summary taken from the description is not serialized
+            let command = Command::new("log").description("Shows the logs. Uses a pager.");
+            let json = serde_json::to_string(&command).unwrap();
+            assert!(!json.contains("summary"));
+
+            let command = serde_json::from_str::<Command>(&json).unwrap();
+            assert_eq!(command.get_summary(), Some("Shows the logs."));
+        }
+
+        #[test]
+        fn command_before_and_after_help_test() {
+            let command = Command::new("MyApp")
+                .before_help("NOTE: experimental")
+                .after_help("LICENSE: MIT");
+
+            let json = serde_json::to_string(&command).unwrap();
+            let command = serde_json::from_str::<Command>(&json).unwrap();
+            assert_eq!(command.get_before_help(), Some("NOTE: experimental"));
+            assert_eq!(command.get_after_help(), Some("LICENSE: MIT"));
+
+            // Only serialized if set
+            let json = serde_json::to_string(&Command::new("MyApp")).unwrap();
+            assert!(!json.contains("before_help"));
+            assert!(!json.contains("after_help"));
+        }
+
         #[test]
         fn command_missing_fields_test() {
             let command = Command::new("echo").arg(Argument::with_name("value"));
@@ -1354,6 +2735,21 @@ mod tests {
             );
         }
 
+        #[test]
+        fn command_skip_fn_fields_test() {
+            let command = Command::new("echo")
+                .usage_with(|| "echo [VALUES]...".to_string())
+                .help_with(|| "echo 1.0".to_string());
+
+            let json = serde_json::to_string(&command).unwrap();
+            assert!(!json.contains("usage"));
+            assert!(!json.contains("help"));
+
+            let command = serde_json::from_str::<Command>(&json).unwrap();
+            assert_eq!(command.get_usage(), None);
+            assert_eq!(command.get_help(), None);
+        }
+
         #[test]
         fn command_from_json_test() {
             let command = serde_json::from_str::<Command>(
@@ -1415,6 +2811,235 @@ mod tests {
             assert_eq!(arg.get_name(), "values");
             assert_eq!(arg.get_values_count(), ArgCount::more_than(1));
         }
+
+        #[test]
+        fn command_set_handler_from_json_test() {
+            let mut command = serde_json::from_str::<Command>(
+                r#"
+                {
+                    "name": "kv",
+                    "subcommands" : [
+                        {
+                            "name" : "data",
+                            "subcommands" : [
+                                { "name" : "set", "args" : [{ "name" : "key" }, { "name" : "value" }] }
+                            ]
+                        }
+                    ]
+                }
+                "#,
+            )
+            .unwrap();
+
+            let values = Rc::new(RefCell::new(Vec::new()));
+            let set_values = values.clone();
+            command
+                .find_subcommand_mut("data.set")
+                .unwrap()
+                .set_handler(move |_, args| {
+                    set_values.borrow_mut().extend(args.get_raw_args().into_vec());
+                    Ok(())
+                });
+
+            crate::CommandLine::new(command).run_from(vec!["data", "set", "a", "1"]).unwrap();
+            assert_eq!(values.take(), vec!["a".to_owned(), "1".to_owned()]);
+        }
+
+        #[test]
+        fn command_settings_test() {
+            let command = Command::new("exec")
+                .setting(Setting::TreatEooAsValue)
+                .setting(Setting::AllowEmptyInvocation);
+
+            serde_test::assert_tokens(
+                &command,
+                CommandTokens::new("exec")
+                    .setting("allow_empty_invocation")
+                    .setting("treat_eoo_as_value")
+                    .to_tokens()
+                    .as_slice()
+            );
+        }
+
+        #[test]
+        fn command_settings_from_json_test() {
+            let command = serde_json::from_str::<Command>(
+                r#"{ "name": "exec", "settings": ["treat_eoo_as_value"] }"#
+            ).unwrap();
+
+            assert!(command.has_setting(Setting::TreatEooAsValue));
+            assert!(!command.has_setting(Setting::AllowEmptyInvocation));
+
+            assert!(serde_json::from_str::<Command>(
+                r#"{ "name": "exec", "settings": ["unknown"] }"#
+            ).is_err());
+        }
+
+        #[test]
+        fn command_merge_from_json_test() {
+            let host = Command::new("MyApp")
+                .subcommand(Command::new("install").description("Installs a package"));
+
+            let plugin = serde_json::from_str::<Command>(
+                r#"
+                {
+                    "name": "plugin",
+                    "subcommands" : [
+                        {
+                            "name" : "install",
+                            "description" : "Installs a plugin",
+                            "options" : [{ "name" : "force" }]
+                        },
+                        {
+                            "name" : "publish",
+                            "args" : [{ "name" : "path" }]
+                        }
+                    ]
+                }
+                "#,
+            )
+            .unwrap();
+
+            let command = host.merge(plugin).unwrap();
+
+            let install = command.find_subcommand("install").unwrap();
+            assert_eq!(install.get_description(), Some("Installs a package"));
+            assert!(install.get_options().contains("force"));
+
+            let publish = command.find_subcommand("publish").unwrap();
+            assert_eq!(publish.get_arg().unwrap().get_name(), "path");
+        }
+    }
+
+    #[cfg(all(feature = "json", feature = "toml", feature = "yaml"))]
+    mod format_tests {
+        use crate::{from_json_str, from_toml_str, from_yaml_str, ErrorKind};
+
+        const JSON: &str = r#"{
+            "name": "MyApp",
+            "description": "An app",
+            "options": [
+                {
+                    "name": "level",
+                    "aliases": ["l"],
+                    "args": [{ "name": "level", "valid_values": [1, 2, 3], "default_values": [2] }]
+                },
+                {
+                    "name": "ratio",
+                    "args": [{ "name": "ratio", "default_values": [0.5] }]
+                },
+                {
+                    "name": "verbose",
+                    "args": [{ "name": "enable", "valid_values": [true, false], "default_values": [false] }]
+                }
+            ],
+            "subcommands": [
+                {
+                    "name": "get",
+                    "description": "Gets a value",
+                    "args": [{ "name": "keys", "min_values": 1, "valid_values": ["a", "b", 10] }]
+                }
+            ]
+        }"#;
+
+        const TOML: &str = r#"
+            name = "MyApp"
+            description = "An app"
+
+            [[options]]
+            name = "level"
+            aliases = ["l"]
+            args = [{ name = "level", valid_values = [1, 2, 3], default_values = [2] }]
+
+            [[options]]
+            name = "ratio"
+            args = [{ name = "ratio", default_values = [0.5] }]
+
+            [[options]]
+            name = "verbose"
+            args = [{ name = "enable", valid_values = [true, false], default_values = [false] }]
+
+            [[subcommands]]
+            name = "get"
+            description = "Gets a value"
+            args = [{ name = "keys", min_values = 1, valid_values = ["a", "b", "10"] }]
+        "#;
+
+        const YAML: &str = r#"
+            name: MyApp
+            description: An app
+            options:
+              - name: level
+                aliases: [l]
+                args:
+                  - name: level
+                    valid_values: [1, 2, 3]
+                    default_values: [2]
+              - name: ratio
+                args:
+                  - name: ratio
+                    default_values: [0.5]
+              - name: verbose
+                args:
+                  - name: enable
+                    valid_values: [true, false]
+                    default_values: [false]
+            subcommands:
+              - name: get
+                description: Gets a value
+                args:
+                  - name: keys
+                    min_values: 1
+                    valid_values: [a, b, 10]
+        "#;
+
+        #[test]
+        fn same_command_from_formats_test() {
+            let from_json = from_json_str(JSON).unwrap();
+            let from_toml = from_toml_str(TOML).unwrap();
+            let from_yaml = from_yaml_str(YAML).unwrap();
+
+            let json = from_json.to_json_string_pretty().unwrap();
+            assert_eq!(json, from_toml.to_json_string_pretty().unwrap());
+            assert_eq!(json, from_yaml.to_json_string_pretty().unwrap());
+
+            // The pretty JSON is a definition too
+            assert_eq!(json, from_json_str(&json).unwrap().to_json_string_pretty().unwrap());
+        }
+
+        #[test]
+        fn any_to_string_formats_test() {
+            for command in [from_json_str(JSON), from_toml_str(TOML), from_yaml_str(YAML)] {
+                let command = command.unwrap();
+
+                let level = command.get_options().get_arg("level").unwrap();
+                assert_eq!(level.get_valid_values(), &["1", "2", "3"]);
+                assert_eq!(level.get_default_values(), &["2"]);
+
+                let ratio = command.get_options().get_arg("ratio").unwrap();
+                assert_eq!(ratio.get_default_values(), &["0.5"]);
+
+                let enable = command.get_options().get_arg("verbose").unwrap();
+                assert_eq!(enable.get_valid_values(), &["true", "false"]);
+                assert_eq!(enable.get_default_values(), &["false"]);
+
+                let keys = command.find_subcommand("get").unwrap().get_arg().unwrap();
+                assert_eq!(keys.get_valid_values(), &["a", "b", "10"]);
+            }
+        }
+
+        #[test]
+        fn deserialize_error_test() {
+            let error = from_json_str(r#"{ "name": "MyApp", "hidden": "yes" }"#).unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::Deserialize(_)));
+
+            let error = from_toml_str("name = ").unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::Deserialize(_)));
+
+            let error = from_yaml_str("name: MyApp\noptions: 10").unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::Deserialize(_)));
+            assert!(error.to_string().starts_with("invalid command definition: "));
+        }
     }
 }
 
@@ -1433,6 +3058,8 @@ mod test_utils {
         validation_error: Option<&'static str>,
         valid_values: Vec<&'static str>,
         default_values: Vec<&'static str>,
+        allow_empty_values: bool,
+        value_hint: Option<&'static str>,
 
         #[cfg(feature = "typing")]
         valid_type: Option<ValidType>,
@@ -1448,6 +3075,8 @@ mod test_utils {
                 validation_error: None,
                 valid_values: vec![],
                 default_values: vec![],
+                allow_empty_values: true,
+                value_hint: None,
 
                 #[cfg(feature = "typing")]
                 valid_type: None,
@@ -1496,11 +3125,21 @@ mod test_utils {
             self
         }
 
+        pub fn allow_empty_values(mut self, allow: bool) -> Self {
+            self.allow_empty_values = allow;
+            self
+        }
+
+        pub fn value_hint(mut self, hint: &'static str) -> Self {
+            self.value_hint = Some(hint);
+            self
+        }
+
         pub fn to_tokens(&self) -> Vec<Token> {
             let mut tokens = Vec::new();
             tokens.push(Token::Struct {
                 name: "Argument",
-                len: 8,
+                len: 8 + !self.allow_empty_values as usize + self.value_hint.is_some() as usize,
             });
 
             // Argument name
@@ -1575,6 +3214,18 @@ mod test_utils {
             }
             tokens.push(Token::SeqEnd);
 
+            // Argument allow empty values
+            if !self.allow_empty_values {
+                tokens.push(Token::Str("allow_empty_values"));
+                tokens.push(Token::Bool(false));
+            }
+
+            // Argument value hint
+            if let Some(hint) = self.value_hint {
+                tokens.push(Token::Str("value_hint"));
+                tokens.push(Token::Str(hint));
+            }
+
             // End
             tokens.push(Token::StructEnd);
             tokens
@@ -1585,12 +3236,18 @@ mod test_utils {
     pub struct OptionTokens {
         name: &'static str,
         aliases: Vec<&'static str>,
+        hidden_aliases: Vec<&'static str>,
         description: Option<&'static str>,
         args: Vec<ArgTokens>,
         required: bool,
         hidden: bool,
         multiple: bool,
+        min_occurrences: Option<usize>,
+        max_occurrences: Option<usize>,
         requires_assign: bool,
+        conflicts_with: Vec<&'static str>,
+        requires: Vec<&'static str>,
+        default_value_ifs: Vec<(&'static str, Option<&'static str>, &'static str)>,
     }
 
     impl OptionTokens {
@@ -1598,12 +3255,18 @@ mod test_utils {
             OptionTokens {
                 name,
                 aliases: vec![],
+                hidden_aliases: vec![],
                 description: None,
                 args: vec![],
                 required: false,
                 hidden: false,
                 multiple: false,
+                min_occurrences: None,
+                max_occurrences: None,
                 requires_assign: false,
+                conflicts_with: vec![],
+                requires: vec![],
+                default_value_ifs: vec![],
             }
         }
 
@@ -1612,6 +3275,11 @@ mod test_utils {
             self
         }
 
+        pub fn hidden_alias(mut self, alias: &'static str) -> Self {
+            self.hidden_aliases.push(alias);
+            self
+        }
+
         pub fn description(mut self, description: &'static str) -> Self {
             self.description = Some(description);
             self
@@ -1637,16 +3305,47 @@ mod test_utils {
             self
         }
 
+        pub fn min_occurrences(mut self, min: usize) -> Self {
+            self.min_occurrences = Some(min);
+            self
+        }
+
+        pub fn max_occurrences(mut self, max: usize) -> Self {
+            self.max_occurrences = Some(max);
+            self
+        }
+
         pub fn requires_assign(mut self, requires_assign: bool) -> Self {
             self.requires_assign = requires_assign;
             self
         }
 
+        pub fn conflicts_with(mut self, name: &'static str) -> Self {
+            self.conflicts_with.push(name);
+            self
+        }
+
+        pub fn requires(mut self, name: &'static str) -> Self {
+            self.requires.push(name);
+            self
+        }
+
+        pub fn default_value_if(mut self, name: &'static str, value: Option<&'static str>, default: &'static str) -> Self {
+            self.default_value_ifs.push((name, value, default));
+            self
+        }
+
         pub fn to_tokens(&self) -> Vec<Token> {
             let mut tokens = Vec::new();
             tokens.push(Token::Struct {
                 name: "CommandOption",
-                len: 8,
+                len: 8
+                    + !self.hidden_aliases.is_empty() as usize
+                    + !self.conflicts_with.is_empty() as usize
+                    + !self.requires.is_empty() as usize
+                    + !self.default_value_ifs.is_empty() as usize
+                    + self.min_occurrences.is_some() as usize
+                    + self.max_occurrences.is_some() as usize,
             });
 
             // Option name
@@ -1668,6 +3367,12 @@ mod test_utils {
                 tokens.push(Token::SeqEnd);
             }
 
+            // Option hidden aliases
+            if !self.hidden_aliases.is_empty() {
+                tokens.push(Token::Str("hidden_aliases"));
+                tokens.extend(str_seq(&self.hidden_aliases));
+            }
+
             // Option description
             tokens.push(Token::Str("description"));
             if let Some(description) = self.description {
@@ -1699,10 +3404,67 @@ mod test_utils {
             tokens.push(Token::Str("multiple"));
             tokens.push(Token::Bool(self.multiple));
 
+            // Option occurrences
+            if let Some(min) = self.min_occurrences {
+                tokens.push(Token::Str("min_occurrences"));
+                tokens.push(Token::U64(min as u64));
+            }
+
+            if let Some(max) = self.max_occurrences {
+                tokens.push(Token::Str("max_occurrences"));
+                tokens.push(Token::U64(max as u64));
+            }
+
             // Option assign required
             tokens.push(Token::Str("requires_assign"));
             tokens.push(Token::Bool(self.requires_assign));
 
+            // Option conflicts
+            if !self.conflicts_with.is_empty() {
+                tokens.push(Token::Str("conflicts_with"));
+                tokens.push(Token::Seq {
+                    len: Some(self.conflicts_with.len()),
+                });
+                for name in &self.conflicts_with {
+                    tokens.push(Token::Str(name));
+                }
+                tokens.push(Token::SeqEnd);
+            }
+
+            // Option requires
+            if !self.requires.is_empty() {
+                tokens.push(Token::Str("requires"));
+                tokens.push(Token::Seq {
+                    len: Some(self.requires.len()),
+                });
+                for name in &self.requires {
+                    tokens.push(Token::Str(name));
+                }
+                tokens.push(Token::SeqEnd);
+            }
+
+            // Option conditional default values
+            if !self.default_value_ifs.is_empty() {
+                tokens.push(Token::Str("default_value_ifs"));
+                tokens.push(Token::Seq {
+                    len: Some(self.default_value_ifs.len()),
+                });
+                for (name, value, default) in &self.default_value_ifs {
+                    tokens.push(Token::Tuple { len: 3 });
+                    tokens.push(Token::Str(name));
+                    match value {
+                        Some(value) => {
+                            tokens.push(Token::Some);
+                            tokens.push(Token::Str(value));
+                        }
+                        None => tokens.push(Token::None),
+                    }
+                    tokens.push(Token::Str(default));
+                    tokens.push(Token::TupleEnd);
+                }
+                tokens.push(Token::SeqEnd);
+            }
+
             // End
             tokens.push(Token::StructEnd);
             tokens
@@ -1712,6 +3474,8 @@ mod test_utils {
     #[derive(Debug, Clone)]
     pub struct CommandTokens {
         name: &'static str,
+        aliases: Vec<&'static str>,
+        hidden_aliases: Vec<&'static str>,
         description: Option<&'static str>,
         usage: Option<&'static str>,
         help: Option<&'static str>,
@@ -1719,12 +3483,15 @@ mod test_utils {
         options: Vec<OptionTokens>,
         args: Vec<ArgTokens>,
         hidden: bool,
+        settings: Vec<&'static str>,
     }
 
     impl CommandTokens {
         pub fn new(name: &'static str) -> Self {
             CommandTokens {
                 name,
+                aliases: vec![],
+                hidden_aliases: vec![],
                 description: None,
                 usage: None,
                 help: None,
@@ -1732,9 +3499,20 @@ mod test_utils {
                 options: vec![],
                 args: vec![],
                 hidden: false,
+                settings: vec![],
             }
         }
 
+        pub fn alias(mut self, alias: &'static str) -> Self {
+            self.aliases.push(alias);
+            self
+        }
+
+        pub fn hidden_alias(mut self, alias: &'static str) -> Self {
+            self.hidden_aliases.push(alias);
+            self
+        }
+
         pub fn description(mut self, description: &'static str) -> Self {
             self.description = Some(description);
             self
@@ -1770,17 +3548,33 @@ mod test_utils {
             self
         }
 
+        pub fn setting(mut self, setting: &'static str) -> Self {
+            self.settings.push(setting);
+            self
+        }
+
         pub fn to_tokens(&self) -> Vec<Token> {
             let mut tokens = Vec::new();
             tokens.push(Token::Struct {
                 name: "Command",
-                len: 8,
+                len: 9 + !self.aliases.is_empty() as usize + !self.hidden_aliases.is_empty() as usize,
             });
 
             // Command name
             tokens.push(Token::Str("name"));
             tokens.push(Token::String(self.name));
 
+            // Command aliases
+            if !self.aliases.is_empty() {
+                tokens.push(Token::Str("aliases"));
+                tokens.extend(str_seq(&self.aliases));
+            }
+
+            if !self.hidden_aliases.is_empty() {
+                tokens.push(Token::Str("hidden_aliases"));
+                tokens.extend(str_seq(&self.hidden_aliases));
+            }
+
             // Command description
             tokens.push(Token::Str("description"));
             if let Some(description) = self.description {
@@ -1841,9 +3635,25 @@ mod test_utils {
             tokens.push(Token::Str("hidden"));
             tokens.push(Token::Bool(self.hidden));
 
+            // Command settings
+            tokens.push(Token::Str("settings"));
+            tokens.push(Token::Seq { len: Some(self.settings.len()) });
+            for setting in &self.settings {
+                tokens.push(Token::Str(setting));
+            }
+            tokens.push(Token::SeqEnd);
+
             // End
             tokens.push(Token::StructEnd);
             tokens
         }
     }
-}
\ No newline at end of file
+
+    // The tokens of a sequence of strings
+    fn str_seq(values: &[&'static str]) -> Vec<Token> {
+        let mut tokens = vec![Token::Seq { len: Some(values.len()) }];
+        tokens.extend(values.iter().map(|s| Token::Str(s)));
+        tokens.push(Token::SeqEnd);
+        tokens
+    }
+}
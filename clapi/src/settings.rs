@@ -0,0 +1,178 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// A setting that changes how a `Command` is parsed or executed.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Setting {
+    /// The command can be executed without a subcommand when it have no handler,
+    /// takes no arguments and have subcommands.
+    ///
+    /// Not inherited by the subcommands.
+    AllowEmptyInvocation,
+    /// The end of options (`--`) is passed as a normal argument when the command is executed,
+    /// the values of an option only stop at a known option.
+    ///
+    /// Not inherited by the subcommands.
+    TreatEooAsValue,
+}
+
+impl Setting {
+    // All the settings, the position of each one is its bit in `CommandSettings`.
+    const ALL: [Setting; 2] = [Setting::AllowEmptyInvocation, Setting::TreatEooAsValue];
+
+    /// Returns the name of this setting.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Setting::AllowEmptyInvocation => "allow_empty_invocation",
+            Setting::TreatEooAsValue => "treat_eoo_as_value",
+        }
+    }
+
+    /// Returns `true` if this setting is applied to the subcommands of the command that declares it.
+    pub fn is_inherited(&self) -> bool {
+        match self {
+            Setting::AllowEmptyInvocation => false,
+            Setting::TreatEooAsValue => false,
+        }
+    }
+
+    #[inline]
+    fn bit(&self) -> u32 {
+        1 << (*self as u32)
+    }
+}
+
+impl Display for Setting {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Setting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Setting::ALL
+            .iter()
+            .find(|setting| setting.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("invalid setting: `{}`", s))
+    }
+}
+
+/// A set of `Setting`s of a `Command`.
+///
+/// # Example
+/// ```
+/// use clapi::{CommandSettings, Setting};
+///
+/// let mut settings = CommandSettings::new();
+/// settings.insert(Setting::AllowEmptyInvocation);
+/// settings.insert(Setting::TreatEooAsValue);
+/// assert!(settings.contains(Setting::TreatEooAsValue));
+///
+/// settings.remove(Setting::TreatEooAsValue);
+/// assert_eq!(settings.iter().collect::<Vec<Setting>>(), vec![Setting::AllowEmptyInvocation]);
+/// ```
+#[derive(Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CommandSettings {
+    bits: u32,
+}
+
+impl CommandSettings {
+    /// Constructs an empty `CommandSettings`.
+    #[inline]
+    pub fn new() -> Self {
+        CommandSettings { bits: 0 }
+    }
+
+    /// Returns `true` if the given setting is set.
+    #[inline]
+    pub fn contains(&self, setting: Setting) -> bool {
+        self.bits & setting.bit() != 0
+    }
+
+    /// Sets the given setting.
+    #[inline]
+    pub fn insert(&mut self, setting: Setting) {
+        self.bits |= setting.bit();
+    }
+
+    /// Unsets the given setting.
+    #[inline]
+    pub fn remove(&mut self, setting: Setting) {
+        self.bits &= !setting.bit();
+    }
+
+    /// Returns `true` if no setting is set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns an iterator over the settings that are set.
+    pub fn iter(&self) -> impl Iterator<Item = Setting> + '_ {
+        Setting::ALL.iter().copied().filter(move |s| self.contains(*s))
+    }
+
+    // Returns the settings that are inherited by the subcommands.
+    pub(crate) fn inherited(&self) -> CommandSettings {
+        self.iter().filter(|s| s.is_inherited()).collect()
+    }
+
+    // Sets all the settings of `other`.
+    pub(crate) fn extend(&mut self, other: CommandSettings) {
+        self.bits |= other.bits;
+    }
+}
+
+impl FromIterator<Setting> for CommandSettings {
+    fn from_iter<T: IntoIterator<Item = Setting>>(iter: T) -> Self {
+        let mut settings = CommandSettings::new();
+        for setting in iter {
+            settings.insert(setting);
+        }
+        settings
+    }
+}
+
+impl Debug for CommandSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_settings_test() {
+        let mut settings = CommandSettings::new();
+        assert!(settings.is_empty());
+
+        settings.insert(Setting::TreatEooAsValue);
+        settings.insert(Setting::AllowEmptyInvocation);
+        assert!(settings.contains(Setting::TreatEooAsValue));
+        assert!(settings.contains(Setting::AllowEmptyInvocation));
+        assert_eq!(
+            settings.iter().collect::<Vec<Setting>>(),
+            vec![Setting::AllowEmptyInvocation, Setting::TreatEooAsValue]
+        );
+
+        settings.remove(Setting::AllowEmptyInvocation);
+        assert!(!settings.contains(Setting::AllowEmptyInvocation));
+        assert!(!settings.is_empty());
+        assert!(settings.inherited().is_empty());
+    }
+
+    #[test]
+    fn setting_from_str_test() {
+        for setting in Setting::ALL.iter() {
+            assert_eq!(setting.as_str().parse::<Setting>(), Ok(*setting));
+        }
+
+        assert!("hidden".parse::<Setting>().is_err());
+    }
+}
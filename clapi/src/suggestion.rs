@@ -7,6 +7,8 @@ pub struct Suggestion {
     pub value: String,
     /// The similarity between the suggested value and the invalid one.
     pub similarity: f32,
+    /// The number of edits between the suggested value and the invalid one.
+    pub distance: usize,
 }
 
 /// Configuration for the suggestions.
@@ -18,8 +20,12 @@ pub struct SuggestionSource {
     pub ignore_case: bool,
     /// Min similarity to consider for a suggestion message.
     pub min_similarity: f32,
+    /// Max number of edits between a value and a suggestion, `None` for no limit.
+    pub max_distance: Option<usize>,
     /// Provides the message for the suggestions.
     pub message: fn(Vec<Suggestion>) -> Option<String>,
+    /// Provides command names to suggest besides the subcommands, like commands discovered at runtime.
+    pub commands: Option<fn() -> Vec<String>>,
 }
 
 impl Default for SuggestionSource {
@@ -37,19 +43,83 @@ impl SuggestionSource {
             max_count: NonZeroUsize::new(1).unwrap(),
             ignore_case: true,
             min_similarity: 0.0,
-            message: default_suggestion_message
+            max_distance: None,
+            message: default_suggestion_message,
+            commands: None,
         }
     }
 
-    /// Returns a suggestion message for the `value` from the `source` values
+    /// Constructs a new `SuggestionSource` that only suggests values within `threshold` edits.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::suggestion::SuggestionSource;
+    ///
+    /// let source = SuggestionSource::with_threshold(1);
+    /// let values = vec!["push".to_owned(), "pull".to_owned()];
+    /// assert_eq!(source.suggestions_for("puhs", &values).len(), 0);
+    /// assert_eq!(source.suggestions_for("pul", &values)[0].value, "pull");
+    /// ```
+    #[inline]
+    pub fn with_threshold(threshold: usize) -> Self {
+        SuggestionSource::new().max_distance(threshold)
+    }
+
+    /// Sets the max number of suggestions to return.
+    ///
+    /// # Panics
+    /// If `max_count` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::suggestion::SuggestionSource;
+    ///
+    /// let source = SuggestionSource::new().max_count(2);
+    /// let values = vec!["push".to_owned(), "pull".to_owned(), "fetch".to_owned()];
+    /// let suggestions = source.suggestions_for("pus", &values);
+    /// assert_eq!(suggestions.len(), 2);
+    /// assert_eq!(suggestions[0].value, "push");
+    /// assert_eq!(suggestions[1].value, "pull");
+    /// ```
+    pub fn max_count(mut self, max_count: usize) -> Self {
+        self.max_count = NonZeroUsize::new(max_count).expect("`max_count` cannot be 0");
+        self
+    }
+
+    /// Sets the max number of edits between a value and a suggestion.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::suggestion::SuggestionSource;
+    ///
+    /// let source = SuggestionSource::new().max_distance(2);
+    /// let values = vec!["status".to_owned(), "commit".to_owned()];
+    /// assert_eq!(source.suggestions_for("staus", &values)[0].value, "status");
+    /// assert!(source.suggestions_for("push", &values).is_empty());
+    /// ```
+    pub fn max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// Sets a function that provides command names to suggest besides the subcommands.
+    pub fn commands(mut self, commands: fn() -> Vec<String>) -> Self {
+        self.commands = Some(commands);
+        self
+    }
+
+    /// Returns the suggestions for the `value` from the `source` values, the closest first.
     pub fn suggestions_for(&self, value: &str, source: &[String]) -> Vec<Suggestion> {
-        suggestions_for(
-            self.max_count,
+        let mut result = find_suggestions(
             self.ignore_case,
             self.min_similarity,
+            self.max_distance,
             value,
-            source
-        )
+            source,
+        );
+
+        result.truncate(self.max_count.get());
+        result
     }
 
     /// Returns a suggestion message for the given suggestions.
@@ -95,30 +165,61 @@ pub fn suggestions_for(
     min_similarity: f32,
     value: &str,
     source: &[String],
+) -> Vec<Suggestion> {
+    let mut result = find_suggestions(ignore_case, min_similarity, None, value, source);
+    result.truncate(max_count.get());
+    result
+}
+
+// Returns all the values similar to `value`, sorted by distance and then alphabetically
+fn find_suggestions(
+    ignore_case: bool,
+    min_similarity: f32,
+    max_distance: Option<usize>,
+    value: &str,
+    source: &[String],
 ) -> Vec<Suggestion> {
     debug_assert!(min_similarity >= 0_f32 && min_similarity <= 1_f32);
     let mut result = Vec::new();
 
     for s in source {
         let cost = compute_levenshtein_distance(value, s, ignore_case);
-        let similarity = 1_f32 - (cost as f32 / std::cmp::max(value.len(), s.len()) as f32);
+        if max_distance.is_some_and(|max| cost > max) {
+            continue;
+        }
+
+        // The distance is in chars, so the similarity is relative to the length in chars
+        let max_len = std::cmp::max(value.chars().count(), s.chars().count());
+        let similarity = 1_f32 - (cost as f32 / max_len as f32);
 
         if similarity >= min_similarity {
             result.push(Suggestion {
                 value: s.clone(),
                 similarity,
+                distance: cost,
             });
         }
-
-        if result.len() == max_count.get() {
-            break;
-        }
     }
 
-    result.sort_by(|x, y| x.similarity.partial_cmp(&y.similarity).unwrap());
+    result.sort_by(|x, y| x.distance.cmp(&y.distance).then_with(|| x.value.cmp(&y.value)));
     result
 }
 
+/// Returns the number of single char edits needed to turn `a` into `b`,
+/// also known as the `Levenshtein distance`.
+///
+/// # Example
+/// ```
+/// use clapi::suggestion::edit_distance;
+///
+/// assert_eq!(edit_distance("kitten", "sitting"), 3);
+/// assert_eq!(edit_distance("Status", "status"), 1);
+/// ```
+#[inline]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    compute_levenshtein_distance(a, b, false)
+}
+
 /// Compute the `Levenshtein distance` between 2 `str`
 ///
 /// # See
@@ -156,7 +257,7 @@ pub fn compute_levenshtein_distance(a: &str, b: &str, ignore_case: bool) -> usiz
     #[inline(always)]
     fn equals(a: char, b: char, ignore_case: bool) -> bool {
         if ignore_case {
-            a.eq_ignore_ascii_case(&b)
+            a == b || a.to_lowercase().eq(b.to_lowercase())
         } else {
             a == b
         }
@@ -214,4 +315,87 @@ mod tests {
             4
         );
     }
+
+    #[test]
+    fn compute_levenshtein_distance_non_ascii_test() {
+        assert_eq!(compute_levenshtein_distance("número", "numero", false), 1);
+        assert_eq!(compute_levenshtein_distance("número", "núm", false), 3);
+        assert_eq!(compute_levenshtein_distance("año😀", "ano🎉", false), 2);
+        assert_eq!(compute_levenshtein_distance_ignore_case("NÚMERO", "número"), 0);
+        assert_eq!(compute_levenshtein_distance_ignore_case("ÑANDÚ", "ñandu"), 1);
+        assert_eq!(compute_levenshtein_distance("Ñ", "ñ", false), 1);
+    }
+
+    #[test]
+    fn suggestions_for_non_ascii_test() {
+        let max_count = NonZeroUsize::new(3).unwrap();
+        let source = vec!["número".to_owned(), "nombre".to_owned(), "año".to_owned()];
+
+        let suggestions = suggestions_for(max_count, false, 0.8, "númro", &source);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].value, "número");
+        // 1 edit in 6 chars
+        assert_eq!(suggestions[0].similarity, 1_f32 - 1_f32 / 6_f32);
+
+        let suggestions = suggestions_for(max_count, true, 0.5, "AÑO", &source);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].value, "año");
+        assert_eq!(suggestions[0].similarity, 1_f32);
+    }
+
+    #[test]
+    fn suggestions_for_most_similar_first_test() {
+        let max_count = NonZeroUsize::new(2).unwrap();
+        let source = vec!["add".to_owned(), "stash".to_owned(), "status".to_owned()];
+
+        let suggestions = suggestions_for(max_count, true, 0.0, "staus", &source);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].value, "status");
+        assert_eq!(suggestions[1].value, "stash");
+    }
+
+    #[test]
+    fn suggestions_max_distance_test() {
+        let source = vec!["red".to_owned(), "green".to_owned(), "blue".to_owned()];
+
+        let suggestions = SuggestionSource::new().suggestions_for("gren", &source);
+        assert_eq!(suggestions[0].value, "green");
+
+        let suggestions = SuggestionSource::new().suggestions_for("yellow", &source);
+        assert_eq!(suggestions.len(), 1);
+
+        let suggestions = SuggestionSource::new()
+            .max_distance(1)
+            .suggestions_for("yellow", &source);
+        assert!(suggestions.is_empty());
+
+        let suggestions = SuggestionSource::new()
+            .max_distance(1)
+            .suggestions_for("gren", &source);
+        assert_eq!(suggestions[0].value, "green");
+    }
+
+    #[test]
+    fn suggestions_ties_sorted_alphabetically_test() {
+        let source = vec!["pull".to_owned(), "push".to_owned(), "put".to_owned()];
+        let suggestions = SuggestionSource::new().max_count(3).suggestions_for("pux", &source);
+
+        let values = suggestions.iter().map(|s| s.value.as_str()).collect::<Vec<_>>();
+        assert_eq!(values, vec!["put", "pull", "push"]);
+        assert_eq!(suggestions[0].distance, 1);
+        assert_eq!(suggestions[1].distance, 2);
+        assert_eq!(suggestions[2].distance, 2);
+    }
+
+    #[test]
+    fn with_threshold_test() {
+        let source = vec!["commit".to_owned(), "checkout".to_owned(), "clone".to_owned()];
+        let suggestions = SuggestionSource::with_threshold(2)
+            .max_count(2)
+            .suggestions_for("comit", &source);
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].value, "commit");
+        assert_eq!(edit_distance("comit", "commit"), 1);
+    }
 }
@@ -0,0 +1,119 @@
+use crate::help::HelpStyle;
+use crate::{Command, Context};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Environment variable that regenerates the snapshots when set to `1`.
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+// Max width of the help messages of the snapshots
+const SNAPSHOT_MAX_WIDTH: usize = 100;
+
+/// Extension methods for test a `Command`.
+pub trait CommandExt {
+    /// Compares the default help message of this command and all its subcommands
+    /// against the golden files in the given directory.
+    ///
+    /// Each help message is stored in a file named after the path of the command,
+    /// like `MyApp.txt` and `MyApp.remote.add.txt`. Set the environment variable
+    /// `UPDATE_SNAPSHOTS=1` to write the current help messages to the files.
+    ///
+    /// The help messages are written with a max width of 100, so the golden files
+    /// don't depend on the terminal or the `COLUMNS` of who writes or checks them.
+    ///
+    /// # Panics
+    /// If any help message don't match its golden file, or the file don't exists.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use clapi::{Command, CommandOption};
+    /// use clapi::testing::CommandExt;
+    ///
+    /// Command::new("MyApp")
+    ///     .option(CommandOption::new("verbose"))
+    ///     .subcommand(Command::new("test"))
+    ///     .assert_help_snapshot("tests/snapshots");
+    /// ```
+    fn assert_help_snapshot<P: AsRef<Path>>(&self, dir: P);
+}
+
+impl CommandExt for Command {
+    fn assert_help_snapshot<P: AsRef<Path>>(&self, dir: P) {
+        // The same context used by `CommandLine::use_default_help`, with a fixed width
+        let context = Context::builder(self.clone())
+            .help_option(crate::context::default_help_option())
+            .help_command(crate::context::default_help_command())
+            .help_style(HelpStyle::new().max_width(SNAPSHOT_MAX_WIDTH))
+            .build();
+
+        assert_help_snapshot(&context, dir)
+    }
+}
+
+/// Compares the help message of the root command of the `Context` and all its subcommands
+/// against the golden files in the given directory.
+///
+/// See [`CommandExt::assert_help_snapshot`], the help messages are written with the `HelpStyle`
+/// of the `Context`, which should set a `max_width` so the golden files don't depend on the terminal.
+///
+/// # Panics
+/// If any help message don't match its golden file, or the file don't exists.
+pub fn assert_help_snapshot<P: AsRef<Path>>(context: &Context, dir: P) {
+    let dir = dir.as_ref();
+    let update = std::env::var(UPDATE_SNAPSHOTS).is_ok_and(|s| s == "1");
+    let mut snapshots = Vec::new();
+    collect_help(context, context.root(), context.root().get_name().to_owned(), &mut snapshots);
+
+    if update {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|e| panic!("failed to create `{}`: {}", dir.display(), e));
+    }
+
+    let mut errors = String::new();
+
+    for (file_name, help) in snapshots {
+        let path = dir.join(file_name);
+
+        if update {
+            std::fs::write(&path, &help)
+                .unwrap_or_else(|e| panic!("failed to write `{}`: {}", path.display(), e));
+            continue;
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if expected.replace("\r\n", "\n") == help => {}
+            Ok(expected) => {
+                writeln!(
+                    errors,
+                    "help snapshot mismatch `{}`\n--- expected\n{}\n--- actual\n{}",
+                    path.display(),
+                    expected,
+                    help
+                )
+                .unwrap();
+            }
+            Err(_) => {
+                writeln!(errors, "help snapshot not found `{}`", path.display()).unwrap();
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        panic!(
+            "{}\nset `{}=1` to update the snapshots",
+            errors, UPDATE_SNAPSHOTS
+        );
+    }
+}
+
+// Renders the help of the command and its subcommands, paired with the snapshot file name
+fn collect_help(context: &Context, command: &Command, path: String, snapshots: &mut Vec<(String, String)>) {
+    let mut help = String::new();
+    context.help().get_help(&mut help, context, command, true);
+    snapshots.push((format!("{}.txt", path), help));
+
+    for subcommand in command.get_subcommands() {
+        let path = format!("{}.{}", path, subcommand.get_name());
+        collect_help(context, subcommand, path, snapshots);
+    }
+}
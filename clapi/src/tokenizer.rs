@@ -1,7 +1,12 @@
-use crate::context::Context;
+use crate::context::{Context, OptionLookup};
+use crate::command::Command;
+use crate::option::CommandOption;
 use crate::error::{Error, ErrorKind, Result};
+use crate::settings::Setting;
 use crate::token::{Token, END_OF_OPTIONS};
 use std::borrow::Borrow;
+use std::ffi::OsStr;
+use std::iter::Peekable;
 
 /// A converts a collection of `String`s to `Token`s.
 #[derive(Debug)]
@@ -13,10 +18,7 @@ impl Tokenizer {
         S: Borrow<str>,
         I: IntoIterator<Item = S>,
     {
-        let mut iterator = args
-            .into_iter()
-            .filter(|s| !s.borrow().is_empty())
-            .peekable();
+        let mut iterator = args.into_iter().peekable();
 
         // Quick path
         if iterator.peek().is_none() {
@@ -25,6 +27,8 @@ impl Tokenizer {
 
         let mut tokens = Vec::new();
         let mut current_command = context.root();
+        let mut option_lookup = context.option_lookup();
+        let mut path = vec![current_command];
         let mut has_end_of_options = false;
 
         // Finds the executing command
@@ -35,11 +39,22 @@ impl Tokenizer {
             let s = iterator.next().unwrap().borrow().to_string();
             tokens.push(Token::Cmd(s))
         } else {
+            // The global options before the subcommands, these are moved after the subcommands
+            // so are parsed the same as if were declared after them
+            let mut global_tokens = Vec::new();
+
             while let Some(arg) = iterator.peek() {
                 if let Some(child) = current_command.find_subcommand(arg.borrow()) {
+                    option_lookup = option_lookup
+                        .get_child(child.get_name())
+                        .expect("the option lookup is built from the same command tree");
                     current_command = child;
+                    path.push(child);
                     tokens.push(Token::Cmd(child.get_name().to_string()));
                     iterator.next();
+                } else if is_global_option(context, option_lookup, &path, arg.borrow()) {
+                    let value = iterator.next().unwrap().borrow().to_string();
+                    tokenize_option(context, option_lookup, &path, &value, &mut iterator, false, &mut global_tokens)?;
                 } else {
                     // If the current don't take args, have subcommands and is not an option
                     // the next should be an unknown subcommand
@@ -54,72 +69,39 @@ impl Tokenizer {
                     break;
                 }
             }
+
+            tokens.extend(global_tokens);
         }
 
+        // If `--` is a value, the values of the options only stop at known options
+        let eoo_as_value = current_command.has_setting(Setting::TreatEooAsValue);
+
         // Check for options
         while let Some(arg) = iterator.peek() {
             let value: &str = arg.borrow();
 
             // End of the options
-            if value == END_OF_OPTIONS {
+            if value == END_OF_OPTIONS && !eoo_as_value {
                 tokens.push(Token::EOO);
                 has_end_of_options = true;
                 iterator.next();
                 break;
             }
 
-            if is_prefixed_option(context, value) {
-                let OptionAndArgs {
-                    prefixed_option,
-                    args,
-                    assign_op,
-                } = try_split_option_and_args(context, value)?;
-
-                // Moves to the next value
-                iterator.next();
-
-                // Adds the option
-                tokens.push(Token::Opt(prefixed_option.clone()));
-
-                // Adds the assign operator if any
-                if let Some(c) = assign_op {
-                    tokens.push(Token::AssignOp(c));
-                }
-
-                if let Some(args) = args {
-                    tokens.extend(args.into_iter().map(Token::Arg));
-                } else if let Some(opt) = current_command
-                    .get_options()
-                    .get(context.trim_prefix(&prefixed_option))
-                {
-                    for arg in opt.get_args() {
-                        let max_arg_count = arg.get_values_count().max_or_default();
-                        let mut count = 0;
-                        while count < max_arg_count {
-                            if let Some(value) = iterator.peek() {
-                                let s: &str = value.borrow();
-                                // If the token is prefixed as an option: exit
-                                if is_prefixed_option(context, s) || s == END_OF_OPTIONS {
-                                    break;
-                                } else {
-                                    // Adds the next argument
-                                    tokens.push(Token::Arg(s.to_string()));
-                                    iterator.next();
-                                    count += 1;
-                                }
-                            } else {
-                                break;
-                            }
-                        }
-                    }
+            if is_option(context, option_lookup, value) {
+                let value = iterator.next().unwrap().borrow().to_string();
+                if is_option_bundle(context, option_lookup, &value) {
+                    tokenize_bundle(context, option_lookup, &path, &value, &mut iterator, eoo_as_value, &mut tokens)?;
+                } else {
+                    tokenize_option(context, option_lookup, &path, &value, &mut iterator, eoo_as_value, &mut tokens)?;
                 }
             } else {
                 break;
             }
         }
 
-        if has_end_of_options {
-            // The rest if considered arguments
+        if has_end_of_options || eoo_as_value {
+            // The rest if considered arguments, `--` is a normal argument if `eoo_as_value`
             tokens.extend(iterator.map(|s| Token::Arg(s.borrow().to_string())));
         } else {
             for value in iterator {
@@ -137,13 +119,168 @@ impl Tokenizer {
     }
 }
 
+// Splits the arguments into the arguments of each command using the command separator
+// of the context, any separator after `--` is a value. Empty commands are skipped.
+// The arguments may be `OsString` so the invalid UTF-8 values are reported when parsed.
+pub(crate) fn split_commands<S, I>(context: &Context, args: I) -> Vec<Vec<S>>
+where
+    S: AsRef<OsStr>,
+    I: IntoIterator<Item = S>,
+{
+    let mut commands = Vec::new();
+    let mut current = Vec::new();
+    let mut has_end_of_options = false;
+
+    for arg in args {
+        let value = arg.as_ref();
+
+        if !has_end_of_options && context.command_separator().is_some_and(|s| value == s) {
+            if !current.is_empty() {
+                commands.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if value == END_OF_OPTIONS {
+            has_end_of_options = true;
+        }
+
+        current.push(arg);
+    }
+
+    if !current.is_empty() || commands.is_empty() {
+        commands.push(current);
+    }
+
+    commands
+}
+
+// Adds the tokens of the given prefixed option and the values it takes from the iterator
+fn tokenize_option<S, I>(
+    context: &Context,
+    option_lookup: &OptionLookup,
+    path: &[&Command],
+    value: &str,
+    iterator: &mut Peekable<I>,
+    eoo_as_value: bool,
+    tokens: &mut Vec<Token>,
+) -> Result<()>
+where
+    S: Borrow<str>,
+    I: Iterator<Item = S>,
+{
+    let OptionAndArgs {
+        prefixed_option,
+        args,
+        assign_op,
+    } = try_split_option_and_args(context, value)?;
+
+    // Adds the option
+    tokens.push(Token::Opt(prefixed_option.clone()));
+
+    // Adds the assign operator if any
+    if let Some(c) = assign_op {
+        tokens.push(Token::AssignOp(c));
+    }
+
+    let option = find_option(context, option_lookup, path, &prefixed_option);
+
+    if let Some(args) = args {
+        let arg = option.and_then(|opt| opt.get_arg());
+
+        // The empty value of `--option=` is accepted or rejected by the argument,
+        // and the values are split by the parser if the argument have a value delimiter
+        if (args.is_empty() && arg.is_some())
+            || arg.is_some_and(|arg| arg.get_value_delimiter().is_some())
+        {
+            tokens.push(Token::Arg(args));
+        } else {
+            tokens.extend(split_assigned_args(context, value, &args)?.into_iter().map(Token::Arg));
+        }
+    } else if let Some(opt) = option {
+        for arg in opt.get_args() {
+            let max_arg_count = arg.get_values_count().max_or_default();
+            let mut count = 0;
+            while count < max_arg_count {
+                if let Some(value) = iterator.peek() {
+                    let s: &str = value.borrow();
+                    // If the token is prefixed as an option: exit,
+                    // only the known options are checked if the argument takes hyphen values
+                    let is_option = if eoo_as_value {
+                        is_known_option(context, option_lookup, s)
+                    } else if arg.is_hyphen_values_allowed() {
+                        is_known_option(context, option_lookup, s) || s == END_OF_OPTIONS
+                    } else {
+                        is_option(context, option_lookup, s) || s == END_OF_OPTIONS
+                    };
+
+                    if is_option {
+                        break;
+                    } else {
+                        // Adds the next argument
+                        tokens.push(Token::Arg(s.to_string()));
+                        iterator.next();
+                        count += 1;
+                    }
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Adds the tokens of each option in a bundle of aliases like `-abc`,
+// if an option takes arguments the rest of the bundle is its value like `-ofile.txt`
+fn tokenize_bundle<S, I>(
+    context: &Context,
+    option_lookup: &OptionLookup,
+    path: &[&Command],
+    value: &str,
+    iterator: &mut Peekable<I>,
+    eoo_as_value: bool,
+    tokens: &mut Vec<Token>,
+) -> Result<()>
+where
+    S: Borrow<str>,
+    I: Iterator<Item = S>,
+{
+    let bundle = context.trim_prefix(value);
+    let prefix = &value[..value.len() - bundle.len()];
+
+    for (index, c) in bundle.char_indices() {
+        let prefixed_option = format!("{}{}", prefix, c);
+        let rest = &bundle[index + c.len_utf8()..];
+
+        if !is_known_option(context, option_lookup, &prefixed_option) {
+            return Err(Error::from(ErrorKind::UnexpectedOption(prefixed_option)));
+        }
+
+        // The last option of the bundle takes its values from the next arguments
+        if rest.is_empty() {
+            return tokenize_option(context, option_lookup, path, &prefixed_option, iterator, eoo_as_value, tokens);
+        }
+
+        tokens.push(Token::Opt(prefixed_option.clone()));
+
+        if find_option(context, option_lookup, path, &prefixed_option).is_some_and(|opt| opt.take_args()) {
+            tokens.push(Token::Arg(rest.to_owned()));
+            break;
+        }
+    }
+
+    Ok(())
+}
+
 struct OptionAndArgs {
     prefixed_option: String,
-    args: Option<Vec<String>>,
+    args: Option<String>,
     assign_op: Option<char>,
 }
 
-// Given an option returns the option and its args (if any)
+// Given an option returns the option and its unsplit args (if any)
 fn try_split_option_and_args(context: &Context, value: &str) -> Result<OptionAndArgs> {
     // Check if the value contains an assign operator like: --times=1
     if let Some(assign_op) = context
@@ -154,19 +291,15 @@ fn try_split_option_and_args(context: &Context, value: &str) -> Result<OptionAnd
         let option_and_args = value
             .split(assign_op)
             .map(|s| s.to_string())
-            .filter(|s| !s.is_empty())
             .collect::<Vec<String>>();
 
-        // We expect 2 parts: `option` = `arg1`
+        // We expect 2 parts: `option` = `arg1`, the `arg1` may be empty like: `--option=`
         return if option_and_args.len() != 2 {
             Err(Error::from(ErrorKind::InvalidExpression))
         } else {
             // We use the unprefixed option to do checks
             let unprefixed_option = context.trim_prefix(&option_and_args[0]);
 
-            // --values=" hello world","good day","bye, amigo"
-            let args = split_option_args(&option_and_args[1], context);
-
             // Error when: `=1,2,3`
             if unprefixed_option.is_empty() {
                 return Err(Error::new(
@@ -175,22 +308,9 @@ fn try_split_option_and_args(context: &Context, value: &str) -> Result<OptionAnd
                 ));
             }
 
-            // Error when: `--option=`
-            if args.is_empty() {
-                return Err(Error::new(
-                    ErrorKind::InvalidExpression,
-                    format!("no arguments specified: `{}`", value),
-                ));
-            }
-
-            // Error when: `--option=1,,,3`
-            if args.iter().any(|s| s.is_empty()) {
-                return Err(Error::new(ErrorKind::InvalidExpression, value));
-            }
-
             Ok(OptionAndArgs {
                 prefixed_option: option_and_args[0].clone(),
-                args: Some(args),
+                args: Some(option_and_args[1].clone()),
                 assign_op: Some(assign_op),
             })
         };
@@ -210,11 +330,32 @@ fn try_split_option_and_args(context: &Context, value: &str) -> Result<OptionAnd
     }
 }
 
-fn split_option_args(args: &str, context: &Context) -> Vec<String> {
+// Splits the args assigned to an option using the context delimiter, like: `--numbers=1,2,3`
+fn split_assigned_args(context: &Context, value: &str, args: &str) -> Result<Vec<String>> {
+    // --values=" hello world","good day","bye, amigo"
+    let args = split_option_args(args, context.delimiter());
+
+    // Error when: `--option=`
+    if args.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidExpression,
+            format!("no arguments specified: `{}`", value),
+        ));
+    }
+
+    // Error when: `--option=1,,,3`
+    if args.iter().any(|s| s.is_empty()) {
+        return Err(Error::new(ErrorKind::InvalidExpression, value));
+    }
+
+    Ok(args)
+}
+
+// Splits the given args by the delimiter, the delimiter can be escaped using quotes
+pub(crate) fn split_option_args(args: &str, delimiter: char) -> Vec<String> {
     const QUOTE_ESCAPE: char = '\\';
 
     let mut result = Vec::new();
-    let delimiter = context.delimiter();
     let mut chars = args.chars().peekable();
     let mut temp = String::new();
     let mut in_quote = false;
@@ -222,10 +363,6 @@ fn split_option_args(args: &str, context: &Context) -> Vec<String> {
     while let Some(c) = chars.next() {
         match c {
             '"' => {
-                if in_quote {
-                    result.push(temp.drain(..).collect());
-                }
-
                 in_quote = !in_quote;
             }
             QUOTE_ESCAPE if chars.peek() == Some(&'"') => {
@@ -235,7 +372,7 @@ fn split_option_args(args: &str, context: &Context) -> Vec<String> {
                 if in_quote {
                     temp.push(c);
                 } else {
-                    result.push(temp.drain(..).collect());
+                    result.push(std::mem::take(&mut temp));
                 }
             }
             _ => {
@@ -251,11 +388,89 @@ fn split_option_args(args: &str, context: &Context) -> Vec<String> {
 }
 
 // Returns `true` if the specified value starts with an option prefix.
+//
+// A lone prefix like `-` is not an option but a value,
+// commonly used as a placeholder for `stdin` or `stdout`.
 fn is_prefixed_option(context: &Context, value: &str) -> bool {
     context
         .name_prefixes()
         .chain(context.alias_prefixes())
         .any(|prefix| value.starts_with(prefix))
+        && !context.trim_prefix(value).is_empty()
+}
+
+// Checks if the value is an option, negative numbers like `-5` or `-3.14` are values
+// unless there is an option with that name or alias
+fn is_option(context: &Context, option_lookup: &OptionLookup, value: &str) -> bool {
+    is_prefixed_option(context, value)
+        && (!is_negative_number(value) || is_known_option(context, option_lookup, value))
+}
+
+fn is_negative_number(value: &str) -> bool {
+    value.strip_prefix('-').is_some_and(|n| {
+        n.starts_with(|c: char| c.is_ascii_digit() || c == '.') && n.parse::<f64>().is_ok()
+    })
+}
+
+// Checks if the value is a bundle of aliases like `-abc`, which is not an option by itself
+fn is_option_bundle(context: &Context, option_lookup: &OptionLookup, value: &str) -> bool {
+    context.is_option_bundling_allowed()
+        && context.alias_prefixes().any(|prefix| value.starts_with(prefix.as_str()))
+        && !context.name_prefixes().any(|prefix| value.starts_with(prefix.as_str()))
+        && !context.assign_operators().any(|op| value.contains(*op))
+        && context.trim_prefix(value).chars().count() > 1
+        && !is_known_option(context, option_lookup, value)
+}
+
+// Returns the option of the executing command or the global option of its parents
+// with the given prefixed name or alias, like: `--color`
+fn find_option<'a>(
+    context: &'a Context,
+    option_lookup: &OptionLookup,
+    path: &[&'a Command],
+    prefixed_option: &str,
+) -> Option<&'a CommandOption> {
+    let name = context.trim_prefix(prefixed_option);
+    option_lookup
+        .get(name)
+        .and_then(|id| id.resolve(path))
+        .or_else(|| context.find_global_option(option_lookup, name))
+}
+
+// Checks if the value is a global option of the executing command, its parents or the context, like: `--color=red`
+fn is_global_option(context: &Context, option_lookup: &OptionLookup, path: &[&Command], value: &str) -> bool {
+    if !is_prefixed_option(context, value) {
+        return false;
+    }
+
+    let option = match try_split_option_and_args(context, value) {
+        Ok(option) => option,
+        Err(_) => return false,
+    };
+
+    let name = context.trim_prefix(&option.prefixed_option);
+    match option_lookup.get(name).and_then(|id| id.resolve(path)) {
+        Some(option) => option.is_global(),
+        None => context.find_global_option(option_lookup, name).is_some(),
+    }
+}
+
+// Checks if the value is an option of the executing command, its parents or the context, like: `--color=red`
+fn is_known_option(context: &Context, option_lookup: &OptionLookup, value: &str) -> bool {
+    if !is_prefixed_option(context, value) {
+        return false;
+    }
+
+    let name = context
+        .trim_prefix(value)
+        .split(|c: char| context.assign_operators().any(|op| *op == c))
+        .next()
+        .unwrap();
+
+    crate::context::is_help_option(context, name)
+        || context.version_option().is_some_and(|o| o.get_name() == name || o.has_alias(name))
+        || option_lookup.get(name).is_some()
+        || context.find_global_option(option_lookup, name).is_some()
 }
 
 #[cfg(test)]
@@ -355,15 +570,96 @@ mod tests {
                     .arg(Argument::zero_or_one("N")),
             );
 
+        // The empty value is checked by the argument
+        let tokens = tokenize(command.clone(), "--numbers=").unwrap();
+        assert_eq!(tokens[2], Token::Arg(String::new()));
+
         // Err
-        assert!(tokenize(command.clone(), "-").is_err());
-        assert!(tokenize(command.clone(), "--numbers=").is_err());
+        assert!(tokenize(command.clone(), "--values=").is_err());
         assert!(tokenize(command.clone(), "--numbers=,").is_err());
         assert!(tokenize(command.clone(), "--numbers=1,,,2").is_err());
         assert!(tokenize(command.clone(), "--numbers=1,2,3,").is_err());
         assert!(tokenize(command.clone(), "--numbers=,1,2,3").is_err());
     }
 
+    #[test]
+    fn tokenize_lone_prefix_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_one("file"))
+            .option(CommandOption::new("input").alias("i").arg(Argument::with_name("input")));
+
+        let tokens1 = tokenize(command.clone(), "-").unwrap();
+        assert_eq!(tokens1, vec![Token::Arg("-".to_owned())]);
+
+        let tokens2 = tokenize(command.clone(), "--input - -").unwrap();
+        assert_eq!(tokens2.len(), 3);
+        assert_eq!(tokens2[0], Token::Opt("--input".to_owned()));
+        assert_eq!(tokens2[1], Token::Arg("-".to_owned()));
+        assert_eq!(tokens2[2], Token::Arg("-".to_owned()));
+
+        let tokens3 = tokenize(command.clone(), "-i - -- -").unwrap();
+        assert_eq!(tokens3.len(), 4);
+        assert_eq!(tokens3[0], Token::Opt("-i".to_owned()));
+        assert_eq!(tokens3[1], Token::Arg("-".to_owned()));
+        assert_eq!(tokens3[2], Token::EOO);
+        assert_eq!(tokens3[3], Token::Arg("-".to_owned()));
+    }
+
+    #[test]
+    fn tokenize_eoo_as_value_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_more("args"))
+            .option(CommandOption::new("env").arg(Argument::one_or_more("values")))
+            .option(CommandOption::new("verbose").alias("v"))
+            .treat_eoo_as_value(true);
+
+        let tokens1 = tokenize(command.clone(), "-- -x").unwrap();
+        assert_eq!(tokens1, vec![Token::Arg("--".to_owned()), Token::Arg("-x".to_owned())]);
+
+        let tokens2 = tokenize(command.clone(), "--env a -- -x -v b").unwrap();
+        assert_eq!(tokens2.len(), 6);
+        assert_eq!(tokens2[0], Token::Opt("--env".to_owned()));
+        assert_eq!(tokens2[1], Token::Arg("a".to_owned()));
+        assert_eq!(tokens2[2], Token::Arg("--".to_owned()));
+        assert_eq!(tokens2[3], Token::Arg("-x".to_owned()));
+        assert_eq!(tokens2[4], Token::Opt("-v".to_owned()));
+        assert_eq!(tokens2[5], Token::Arg("b".to_owned()));
+
+        // Only the command with the flag is affected
+        let root = Command::new("MyApp")
+            .arg(Argument::zero_or_more("args"))
+            .subcommand(Command::new("exec").arg(Argument::zero_or_more("args")).treat_eoo_as_value(true));
+
+        let tokens3 = tokenize(root.clone(), "exec -- a").unwrap();
+        assert_eq!(tokens3[1], Token::Arg("--".to_owned()));
+
+        let tokens4 = tokenize(root.clone(), "-- a").unwrap();
+        assert_eq!(tokens4, vec![Token::EOO, Token::Arg("a".to_owned())]);
+    }
+
+    #[test]
+    fn split_commands_test() {
+        let context = ContextBuilder::new(Command::new("MyApp"))
+            .command_separator(";")
+            .build();
+
+        let commands = split_commands(&context, split_into_args("a -x ; b 1;2 ; ; c -- ; d ;"));
+        assert_eq!(
+            commands,
+            vec![
+                vec!["a".to_owned(), "-x".to_owned()],
+                vec!["b".to_owned(), "1;2".to_owned()],
+                vec!["c".to_owned(), "--".to_owned(), ";".to_owned(), "d".to_owned(), ";".to_owned()],
+            ]
+        );
+
+        assert_eq!(split_commands(&context, Vec::<String>::new()), vec![Vec::<String>::new()]);
+
+        // Without separator is a single command
+        let context = Context::new(Command::new("MyApp"));
+        assert_eq!(split_commands(&context, vec!["a", ";", "b"]).len(), 1);
+    }
+
     #[test]
     fn split_with_spaces_test() {
         let command = Command::new("MyApp")
@@ -383,4 +679,122 @@ mod tests {
         assert_eq!(tokens[3], Token::Arg("good night".to_owned()));
         assert_eq!(tokens[4], Token::Arg("right, bye".to_owned()));
     }
+
+    #[test]
+    fn tokenize_non_ascii_options_test() {
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_more("valores"))
+            .option(CommandOption::new("número").alias("ñ").arg(Argument::with_name("número")))
+            .option(CommandOption::new("etiquetas").alias("é").arg(Argument::one_or_more("etiquetas")))
+            .option(CommandOption::new("año").alias("ä"));
+
+        let context = ContextBuilder::new(command).allow_option_bundling(true).build();
+        let tokenize = |value: &str| Tokenizer.tokenize(&context, split_into_args(value));
+
+        assert_eq!(tokenize("--número=5").unwrap(), vec![
+            Token::Opt("--número".to_owned()),
+            Token::AssignOp('='),
+            Token::Arg("5".to_owned()),
+        ]);
+
+        assert_eq!(tokenize("-ñ=😀 --año").unwrap(), vec![
+            Token::Opt("-ñ".to_owned()),
+            Token::AssignOp('='),
+            Token::Arg("😀".to_owned()),
+            Token::Opt("--año".to_owned()),
+        ]);
+
+        assert_eq!(tokenize("--etiquetas=😀,🎉,ñ").unwrap(), vec![
+            Token::Opt("--etiquetas".to_owned()),
+            Token::AssignOp('='),
+            Token::Arg("😀".to_owned()),
+            Token::Arg("🎉".to_owned()),
+            Token::Arg("ñ".to_owned()),
+        ]);
+
+        // Bundles of multi-byte aliases, the rest of the bundle is the value
+        assert_eq!(tokenize("-äñ😀 ü").unwrap(), vec![
+            Token::Opt("-ä".to_owned()),
+            Token::Opt("-ñ".to_owned()),
+            Token::Arg("😀".to_owned()),
+            Token::Arg("ü".to_owned()),
+        ]);
+
+        assert_eq!(
+            tokenize("-äü").unwrap_err().kind(),
+            &ErrorKind::UnexpectedOption("-ü".to_owned())
+        );
+
+        assert_eq!(tokenize("=😀").unwrap(), vec![Token::Arg("=😀".to_owned())]);
+    }
+
+    #[test]
+    fn tokenize_non_ascii_prefixes_test() {
+        let command = Command::new("MyApp")
+            .option(CommandOption::new("número").alias("ñ").arg(Argument::one_or_more("número")));
+
+        let context = ContextBuilder::new(command)
+            .name_prefix("—")
+            .alias_prefix("¬")
+            .assign_operator('→')
+            .delimiter('·')
+            .build();
+
+        let tokenize = |value: &str| Tokenizer.tokenize(&context, split_into_args(value));
+
+        assert_eq!(tokenize("—número→1·2").unwrap(), vec![
+            Token::Opt("—número".to_owned()),
+            Token::AssignOp('→'),
+            Token::Arg("1".to_owned()),
+            Token::Arg("2".to_owned()),
+        ]);
+
+        assert_eq!(tokenize("¬ñ 😀").unwrap(), vec![
+            Token::Opt("¬ñ".to_owned()),
+            Token::Arg("😀".to_owned()),
+        ]);
+
+        // A lone prefix is a value
+        assert_eq!(tokenize("—").unwrap(), vec![Token::Arg("—".to_owned())]);
+        assert_eq!(tokenize("—→1").unwrap_err().kind(), &ErrorKind::InvalidExpression);
+    }
+
+    // Generates arguments from a small unicode alphabet to check the tokenizer and parser don't panic
+    #[test]
+    fn tokenize_unicode_cases_test() {
+        const ALPHABET: &[&str] = &[
+            "-", "--", "=", ",", ":", "\"", "\\", " ", "1", "a", "ñ", "é", "ü", "ä", "😀", "🎉", "·", "—",
+            "número", "etiquetas", "año",
+        ];
+
+        let command = Command::new("MyApp")
+            .arg(Argument::zero_or_more("valores"))
+            .option(CommandOption::new("número").alias("ñ").arg(Argument::with_name("número")))
+            .option(CommandOption::new("etiquetas")
+                .alias("é")
+                .arg(Argument::one_or_more("etiquetas").value_delimiter('·')))
+            .option(CommandOption::new("año").alias("ä"))
+            .subcommand(Command::new("ünico").arg(Argument::zero_or_one("valor")));
+
+        let context = ContextBuilder::new(command.clone()).allow_option_bundling(true).build();
+
+        // xorshift, so the cases are the same in each run
+        let mut seed = 0x2545_F491_4F6C_DD1D_u64;
+        let mut next = move |max: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % max as u64) as usize
+        };
+
+        for _ in 0..2000 {
+            let args = (0..next(5))
+                .map(|_| (0..next(6) + 1).map(|_| ALPHABET[next(ALPHABET.len())]).collect::<String>())
+                .collect::<Vec<String>>();
+
+            let _ = Tokenizer.tokenize(&context, args.iter().map(|s| s.as_str()));
+            let _ = crate::Parser::new(&context).parse(args.iter().map(|s| s.as_str()));
+            let _ = command.clone().parse_from(args.iter().map(|s| s.as_str()));
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use std::any::TypeId;
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 
 /// Represents a type.
@@ -39,6 +40,12 @@ impl Type {
     }
 }
 
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.type_name)
+    }
+}
+
 impl Eq for Type {}
 
 impl PartialEq for Type {
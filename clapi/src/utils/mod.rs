@@ -1,7 +1,48 @@
 #![allow(dead_code)]
-#![allow(unused_must_use)]
+use crate::error::{Error, ErrorKind, Result};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::io::Write;
 
 pub use debug_utils::*;
+
+/// Writes the given value followed by a newline and flush the writer.
+///
+/// A closed writer (broken pipe) is not an error, as in `myapp --help | head -1`,
+/// any other IO error is returned as `ErrorKind::Other`.
+pub fn write_line<W: Write + ?Sized, D: Display>(writer: &mut W, value: D) -> Result<()> {
+    match writeln!(writer, "{}", value).and_then(|_| writer.flush()) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        Err(error) => Err(Error::new(ErrorKind::Other, error)),
+    }
+}
+
+/// Asserts the given name is not empty, don't contains whitespaces and don't starts with `-`.
+#[inline]
+pub(crate) fn assert_valid_name(source: &str, name: &str) {
+    assert!(!name.is_empty(), "{} cannot be empty", source);
+
+    if name.chars().any(char::is_whitespace) {
+        panic!("{} cannot contains whitespaces: `{}`", source, name);
+    }
+
+    if name.starts_with('-') {
+        panic!("{} cannot start with `-`: `{}`", source, name);
+    }
+}
+
+/// Compares two items by its `display_order` and then by its name,
+/// the items without a `display_order` are placed last and compare as equal.
+pub(crate) fn cmp_display_order(a: (Option<usize>, &str), b: (Option<usize>, &str)) -> Ordering {
+    match (a.0, b.0) {
+        (Some(x), Some(y)) => x.cmp(&y).then_with(|| a.1.cmp(b.1)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 mod debug_utils {
     use std::fmt::{Debug, Formatter};
 
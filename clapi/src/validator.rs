@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::marker::PhantomData;
 use std::str::FromStr;
@@ -6,11 +7,45 @@ use std::str::FromStr;
 use crate::typing::Type;
 
 /// Exposes a method for check if an `str` value is a valid argument value.
+///
+/// A panic in `validate` or `normalize` is caught by the `Argument` and returned as
+/// an `ErrorKind::ValidatorPanic` error. The validator is called as `AssertUnwindSafe`,
+/// so a validator with interior mutability should not be left in an invalid state by a panic.
 pub trait Validator {
     /// Checks if the given string slice is valid.
     /// Returns `Ok()` if is valid otherwise `Err(error)`.
     fn validate(&self, value: &str) -> Result<(), String>;
 
+    /// Returns the canonical form of a valid value, or `None` to keep the value as is.
+    ///
+    /// This is called after a successful `validate` and before checking the valid values
+    /// of the `Argument`, so the normalized value is the one compared and stored.
+    ///
+    /// # Example
+    /// ```
+    /// use clapi::Argument;
+    /// use clapi::validator::Validator;
+    ///
+    /// struct UrlValidator;
+    /// impl Validator for UrlValidator {
+    ///     fn validate(&self, value: &str) -> Result<(), String> {
+    ///         if value.starts_with("https://") { Ok(()) } else { Err(format!("invalid url: {}", value)) }
+    ///     }
+    ///
+    ///     fn normalize(&self, value: &str) -> Option<String> {
+    ///         value.strip_suffix('/').map(|s| s.to_owned())
+    ///     }
+    /// }
+    ///
+    /// let mut arg = Argument::with_name("url").validator(UrlValidator);
+    /// arg.set_values(vec!["https://example.com/"]).unwrap();
+    /// assert_eq!(arg.get_values(), &["https://example.com"]);
+    /// ```
+    fn normalize(&self, value: &str) -> Option<String> {
+        let _ = value;
+        None
+    }
+
     /// Returns the `Type` that is valid for this `Validator`, by default returns `None`.
     ///
     /// When `None` is returned differents types may be valid for the validator,
@@ -38,12 +73,25 @@ impl<T: 'static> Validator for TypeValidator<T>
         T: FromStr,
 {
     fn validate(&self, value: &str) -> Result<(), String> {
+        // `bool` values are case insensitive: `TRUE` or `False`
+        if is_bool::<T>() && (value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")) {
+            return Ok(());
+        }
+
         match T::from_str(value) {
             Ok(_) => Ok(()),
             Err(_) => Err(format!("`{}`", value)),
         }
     }
 
+    fn normalize(&self, value: &str) -> Option<String> {
+        if is_bool::<T>() && value.chars().any(|c| c.is_ascii_uppercase()) {
+            Some(value.to_ascii_lowercase())
+        } else {
+            None
+        }
+    }
+
     #[cfg(feature = "typing")]
     fn valid_type(&self) -> Option<Type> {
         Some(Type::of::<T>())
@@ -86,6 +134,170 @@ impl<T: 'static> Validator for RangeValidator<T>
     }
 }
 
+/// A `Validator` where a `str` is valid if matches a pattern of named parts,
+/// like `{owner}/{repo}` or `{host}:{port}`.
+///
+/// Each part is written as `{name}` and matches at least 1 char, the text between the parts
+/// must appear as is and each part ends at the first occurrence of the text that follows it.
+///
+/// # Example
+/// ```
+/// use clapi::validator::{CapturePattern, Validator};
+///
+/// let pattern = CapturePattern::new("{host}:{port}");
+/// let captures = pattern.captures("localhost:8080").unwrap();
+/// assert_eq!(captures["host"], "localhost");
+/// assert_eq!(captures["port"], "8080");
+/// assert!(pattern.validate("localhost").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturePattern {
+    pattern: String,
+    parts: Vec<PatternPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternPart {
+    Text(String),
+    Capture(String),
+}
+
+impl CapturePattern {
+    /// Constructs a new `CapturePattern` from the given pattern.
+    ///
+    /// # Panics
+    /// - If the pattern have no parts or a part is empty or is not closed.
+    /// - If a part name is declared twice or contains whitespaces.
+    /// - If 2 parts are not separated by text, like `{a}{b}`.
+    pub fn new(pattern: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut chars = pattern.chars();
+        let mut text = String::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => panic!("unclosed part in pattern `{}`", pattern),
+                            Some(c) => name.push(c),
+                        }
+                    }
+
+                    assert!(!name.is_empty(), "empty part in pattern `{}`", pattern);
+                    assert!(
+                        !name.contains(char::is_whitespace),
+                        "part `{}` cannot contains whitespaces in pattern `{}`", name, pattern
+                    );
+                    assert!(
+                        !parts.contains(&PatternPart::Capture(name.clone())),
+                        "duplicated part `{}` in pattern `{}`", name, pattern
+                    );
+
+                    if !text.is_empty() {
+                        parts.push(PatternPart::Text(std::mem::take(&mut text)));
+                    }
+
+                    assert!(
+                        !matches!(parts.last(), Some(PatternPart::Capture(_))),
+                        "parts must be separated by text in pattern `{}`", pattern
+                    );
+
+                    parts.push(PatternPart::Capture(name));
+                }
+                '}' => panic!("unexpected `}}` in pattern `{}`", pattern),
+                _ => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(PatternPart::Text(text));
+        }
+
+        assert!(
+            parts.iter().any(|p| matches!(p, PatternPart::Capture(_))),
+            "pattern `{}` have no parts", pattern
+        );
+
+        CapturePattern {
+            pattern: pattern.to_owned(),
+            parts,
+        }
+    }
+
+    /// Returns the pattern as a `str`.
+    pub fn as_str(&self) -> &str {
+        self.pattern.as_str()
+    }
+
+    /// Returns an iterator over the names of the parts of this pattern.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.parts.iter().filter_map(|p| match p {
+            PatternPart::Capture(name) => Some(name.as_str()),
+            PatternPart::Text(_) => None,
+        })
+    }
+
+    /// Returns the parts of the value by name, or `None` if the value don't match the pattern.
+    pub fn captures(&self, value: &str) -> Option<HashMap<String, String>> {
+        let mut captures = HashMap::new();
+        let mut rest = value;
+        let mut parts = self.parts.iter().peekable();
+
+        while let Some(part) = parts.next() {
+            match part {
+                PatternPart::Text(text) => rest = rest.strip_prefix(text.as_str())?,
+                PatternPart::Capture(name) => {
+                    // Parts always are followed by text or are the last part
+                    let len = match parts.peek() {
+                        Some(PatternPart::Text(text)) => {
+                            // Skips the first char, a part cannot be empty
+                            let first_len = rest.chars().next()?.len_utf8();
+                            rest[first_len..].find(text.as_str())? + first_len
+                        }
+                        _ => rest.len(),
+                    };
+
+                    if len == 0 {
+                        return None;
+                    }
+
+                    captures.insert(name.clone(), rest[..len].to_owned());
+                    rest = &rest[len..];
+                }
+            }
+        }
+
+        if rest.is_empty() {
+            Some(captures)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for CapturePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
+
+impl Validator for CapturePattern {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match self.captures(value) {
+            Some(_) => Ok(()),
+            None => Err(format!("expected `{}` but was `{}`", self.pattern, value)),
+        }
+    }
+}
+
+#[inline]
+fn is_bool<T: 'static>() -> bool {
+    std::any::TypeId::of::<T>() == std::any::TypeId::of::<bool>()
+}
+
 // This allow to use a closure as a `Validator`
 impl<F> Validator for F
     where
@@ -112,4 +324,82 @@ pub fn validate_range<T: 'static>(min: T, max: T) -> RangeValidator<T>
         T: FromStr + PartialOrd + Display,
 {
     RangeValidator::new(min, max)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_pattern_test() {
+        let pattern = CapturePattern::new("{owner}/{repo}");
+        assert_eq!(pattern.names().collect::<Vec<_>>(), vec!["owner", "repo"]);
+
+        let captures = pattern.captures("rust-lang/rust").unwrap();
+        assert_eq!(captures.len(), 2);
+        assert_eq!(captures["owner"], "rust-lang");
+        assert_eq!(captures["repo"], "rust");
+
+        // The last part takes the rest of the value
+        assert_eq!(pattern.captures("a/b/c").unwrap()["repo"], "b/c");
+
+        assert!(pattern.captures("rust").is_none());
+        assert!(pattern.captures("/rust").is_none());
+        assert!(pattern.captures("rust-lang/").is_none());
+    }
+
+    #[test]
+    fn capture_pattern_with_text_test() {
+        let pattern = CapturePattern::new("v{major}.{minor}.{patch}");
+        let captures = pattern.captures("v1.20.3").unwrap();
+        assert_eq!(captures["major"], "1");
+        assert_eq!(captures["minor"], "20");
+        assert_eq!(captures["patch"], "3");
+
+        assert!(pattern.captures("1.20.3").is_none());
+        assert!(pattern.captures("v1.20").is_none());
+
+        let pattern = CapturePattern::new("{year}-{month}-{day}");
+        assert_eq!(pattern.captures("2024-01-02").unwrap()["month"], "01");
+        assert!(pattern.captures("2024-01").is_none());
+
+        let pattern = CapturePattern::new("[{value}]");
+        assert_eq!(pattern.captures("[ñ]").unwrap()["value"], "ñ");
+        assert!(pattern.captures("[ñ").is_none());
+        assert!(pattern.captures("[]").is_none());
+    }
+
+    #[test]
+    fn capture_pattern_validate_test() {
+        let pattern = CapturePattern::new("{host}:{port}");
+        assert!(pattern.validate("localhost:80").is_ok());
+        assert_eq!(
+            pattern.validate("localhost"),
+            Err("expected `{host}:{port}` but was `localhost`".to_owned())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "parts must be separated by text in pattern `{a}{b}`")]
+    fn capture_pattern_adjacent_parts_test() {
+        CapturePattern::new("{a}{b}");
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicated part `a` in pattern `{a}-{a}`")]
+    fn capture_pattern_duplicated_part_test() {
+        CapturePattern::new("{a}-{a}");
+    }
+
+    #[test]
+    #[should_panic(expected = "unclosed part in pattern `{a`")]
+    fn capture_pattern_unclosed_part_test() {
+        CapturePattern::new("{a");
+    }
+
+    #[test]
+    #[should_panic(expected = "pattern `abc` have no parts")]
+    fn capture_pattern_no_parts_test() {
+        CapturePattern::new("abc");
+    }
+}
@@ -0,0 +1,40 @@
+Command::new("MyApp")
+    .description("A sample \"app\"")
+    .summary("A sample")
+    .version("1.0")
+    .example("MyApp --times 2 hello", "Prints \"hello\" twice")
+    .option(CommandOption::new("times")
+        .alias("t")
+        .alias("n")
+        .description("Number of times")
+        .required(true)
+        .arg(Argument::with_name("count")
+            .validator(validate_type::<u64>())
+            .validation_error("expected a number")
+            .defaults(["1"])))
+    .option(CommandOption::new("color")
+        .hidden_alias("colour")
+        .global(true)
+        .requires_assign(true)
+        .display_order(0)
+        .arg(Argument::with_name("color")
+            .valid_values(["red", "green", "blue"])))
+    .arg(Argument::with_name("values")
+        .values_count(ArgCount::new(Some(0), None))
+        .validator(validate_type::<std::string::String>())
+        .display_order(0))
+    .subcommand(Command::new("test")
+        .alias("t")
+        .hidden_alias("tests")
+        .usage("test [OPTIONS]")
+        .help("Runs the tests")
+        .hidden(true)
+        .option(CommandOption::new("verbose")
+            .hidden(true)
+            .multiple(true))
+        .option(CommandOption::new("quiet")
+            .alias("q")
+            .counted(true))
+        .arg(Argument::with_name("files")
+            .values_count(ArgCount::new(Some(1), Some(3)))
+            .value_hint(ValueHint::FilePath)))
@@ -30,13 +30,14 @@ impl DeclareVar {
 
         match &self.ty {
             VarType::Type(ty) if ty.is_bool() => {
+                let context = self.error_context(&format!("--{}", option_name), ty);
                 quote! {
                     match #source.get(#option_name){
                         None => false,
                         Some(option) => {
                             let arg = option.get_arg().unwrap();
                             if arg.is_set() {
-                                arg.convert::<bool>()?
+                                arg.convert::<bool>().map_err(|e| e.context(#context))?
                             } else {
                                 true
                             }
@@ -45,26 +46,36 @@ impl DeclareVar {
                 }
             }
             VarType::Type(ty) => {
+                let context = self.error_context(&format!("--{}", option_name), ty);
                 quote! {
                     #source.get(#option_name)
                         .unwrap()
                         .get_arg()
                         .unwrap()
-                        .convert::<#ty>()?
+                        .convert::<#ty>()
+                        .map_err(|e| e.context(#context))?
                 }
             }
             VarType::Vec(ty) => {
+                let context = self.error_context(&format!("--{}", option_name), ty);
                 quote! {
                     #source.get(#option_name)
                         .unwrap()
                         .get_args()
-                        .get_raw_args_as_type::<#ty>()?
+                        .get_raw_args_as_type::<#ty>()
+                        .map_err(|e| e.context(#context))?
                 }
             }
             VarType::Option(ty) => {
+                let context = self.error_context(&format!("--{}", option_name), ty);
                 quote! {
                     match #source.get(#option_name) {
-                        Some(option) => Some(option.get_arg().unwrap().convert::<#ty>()?),
+                        Some(option) => Some(
+                            option.get_arg()
+                                .unwrap()
+                                .convert::<#ty>()
+                                .map_err(|e| e.context(#context))?
+                        ),
                         None => None,
                     }
                 }
@@ -96,10 +107,14 @@ impl DeclareVar {
                     "multiple arguments defined, expected `Vec<{0}>` but was `{0}`",
                     type_name
                 );
+                let context = self.error_context(&arg_name, ty);
                 quote! {
                     {
                         if #source.len() == 1 {
-                            #source.get_raw_args_as_type::<#ty>()?.pop().unwrap()
+                            #source.get_raw_args_as_type::<#ty>()
+                                .map_err(|e| e.context(#context))?
+                                .pop()
+                                .unwrap()
                         } else {
                             panic!(#msg)
                         }
@@ -107,14 +122,17 @@ impl DeclareVar {
                 }
             }
             VarType::Vec(ty) => {
+                let context = self.error_context(&arg_name, ty);
                 quote! {
-                    #source.get_raw_args_as_type::<#ty>()?
+                    #source.get_raw_args_as_type::<#ty>()
+                        .map_err(|e| e.context(#context))?
                 }
             }
             VarType::Option(ty) => {
+                let context = self.error_context(&arg_name, ty);
                 quote! {
                     match #source.get(#arg_name) {
-                        Some(arg) => Some(arg.convert::<#ty>()?),
+                        Some(arg) => Some(arg.convert::<#ty>().map_err(|e| e.context(#context))?),
                         None => None,
                     }
                 }
@@ -134,15 +152,30 @@ impl DeclareVar {
             }
         }
     }
+
+    // Returns an expression with the context of the conversion errors, like:
+    // "in command 'sum': cannot convert value of '--times' to i32"
+    fn error_context(&self, name: &str, ty: &Type) -> TokenStream {
+        let command_name = &self.input.command_name;
+        let msg = format!(
+            "in command '{{}}': cannot convert value of '{}' to {}",
+            name,
+            ty.to_token_stream().to_string().replace(' ', "")
+        );
+
+        quote! { format!(#msg, #command_name) }
+    }
 }
 
 /// Represents the declaration of a variable in `clapi::app!` macro like:
-/// `(source, mut? name : i64)`
+/// `(source, command_name, mut? name : i64)`
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct VarInput {
     source: Ident,
     comma: syn::Token![,],
+    command_name: Ident,
+    comma2: syn::Token![,],
     mutability: Option<syn::Token![mut]>,
     name: Ident,
     colon: syn::Token![:],
@@ -154,6 +187,8 @@ impl Parse for VarInput {
         Ok(VarInput {
             source: input.parse()?,
             comma: input.parse()?,
+            command_name: input.parse()?,
+            comma2: input.parse()?,
             mutability: input.parse()?,
             name: input.parse()?,
             colon: input.parse()?,
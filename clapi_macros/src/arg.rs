@@ -1,11 +1,12 @@
 #![allow(clippy::len_zero, clippy::redundant_closure)]
 use crate::command::{is_option_bool_flag, FnArgData};
 use crate::macro_attribute::{display_lit, MacroAttribute, Value};
-use crate::utils::pat_type_to_string;
+use crate::utils::{pat_type_to_string, RenameRule};
 use crate::var::ArgumentType;
 use crate::{consts, LitExtensions, TypeExt};
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::*;
+use syn::spanned::Spanned;
 use syn::Lit;
 
 /// Tokens for an `arg` attribute.
@@ -27,6 +28,8 @@ pub struct ArgAttrData {
     default_values: Vec<Lit>,
     valid_values: Vec<Lit>,
     validation_error: Option<String>,
+    allow_empty_values: bool,
+    trim_values: bool,
     attribute: Option<MacroAttribute>,
 }
 
@@ -40,6 +43,7 @@ impl ArgAttrData {
             attribute,
             name_value,
             is_option,
+            ..
         } = arg_data.clone();
 
         let mut arg = ArgAttrData {
@@ -52,6 +56,8 @@ impl ArgAttrData {
             default_values: vec![],
             attribute,
             validation_error: None,
+            allow_empty_values: true,
+            trim_values: false,
         };
 
         // If is an option, we delegates reading the attribute to it
@@ -94,6 +100,20 @@ impl ArgAttrData {
 
                             arg.set_validation_error(error);
                         }
+                        consts::EMPTY => {
+                            let allow_empty = value
+                                .to_bool_literal()
+                                .expect("arg `empty` must be a bool literal");
+
+                            arg.set_allow_empty_values(allow_empty);
+                        }
+                        consts::TRIM => {
+                            let trim = value
+                                .to_bool_literal()
+                                .expect("arg `trim` must be a bool literal");
+
+                            arg.set_trim_values(trim);
+                        }
                         consts::DEFAULT => match value {
                             Value::Literal(lit) => arg.set_default_values(vec![lit]),
                             Value::Array(array) => arg.set_default_values(array),
@@ -102,6 +122,13 @@ impl ArgAttrData {
                             Value::Literal(lit) => arg.set_valid_values(vec![lit]),
                             Value::Array(array) => arg.set_valid_values(array),
                         },
+                        consts::ALLOW_UNUSED => {
+                            // Just type checking
+                            // This is used by `command.rs#is_allow_unused`
+                            value
+                                .to_bool_literal()
+                                .expect("arg `allow_unused` must be a bool literal");
+                        }
                         _ => panic!("invalid `arg` key `{}`", key),
                     }
                 }
@@ -115,6 +142,15 @@ impl ArgAttrData {
         self.name.as_str()
     }
 
+    pub fn arg_name(&self) -> &str {
+        self.fn_arg.0.arg_name.as_str()
+    }
+
+    // The span of the function argument
+    pub fn span(&self) -> Span {
+        self.fn_arg.0.pat_type.pat.span()
+    }
+
     pub fn arg_type(&self) -> &ArgumentType {
         &self.fn_arg.1
     }
@@ -133,6 +169,14 @@ impl ArgAttrData {
         self.name = name;
     }
 
+    // Renames this argument if the name was not set explicitly
+    pub fn rename(&mut self, rule: RenameRule) {
+        let arg_data = &self.fn_arg.0;
+        if arg_data.name.is_none() && self.name == arg_data.arg_name {
+            self.name = rule.apply(&arg_data.arg_name);
+        }
+    }
+
     pub fn set_min(&mut self, min: usize) {
         self.min = Some(min);
     }
@@ -159,10 +203,22 @@ impl ArgAttrData {
         self.default_values = default_values;
     }
 
+    pub fn clear_default_values(&mut self) {
+        self.default_values.clear();
+    }
+
     pub fn set_validation_error(&mut self, error: String) {
         self.validation_error = Some(error);
     }
 
+    pub fn set_allow_empty_values(&mut self, allow: bool) {
+        self.allow_empty_values = allow;
+    }
+
+    pub fn set_trim_values(&mut self, trim: bool) {
+        self.trim_values = trim;
+    }
+
     pub fn set_valid_values(&mut self, valid_values: Vec<Lit>) {
         assert!(valid_values.len() > 0, "valid values is empty");
         if let Err(diff) = check_same_type(valid_values.as_slice()) {
@@ -190,7 +246,10 @@ impl ArgAttrData {
 
         // Assertions
         self.assert_min_max(min, max);
-        self.assert_default_values_range(min, max);
+
+        if let Err(error) = self.check_default_values_count(min, max) {
+            return error.to_compile_error();
+        }
 
         // Argument count
         let min = quote_option!(min);
@@ -237,6 +296,20 @@ impl ArgAttrData {
             })
             .unwrap_or_else(|| quote! {});
 
+        // Argument allow empty values, by default `true`
+        let allow_empty_values = if self.allow_empty_values {
+            quote! {}
+        } else {
+            quote! { .allow_empty_values(false) }
+        };
+
+        // Argument trim values, by default `false`
+        let trim_values = if self.trim_values {
+            quote! { .trim(true) }
+        } else {
+            quote! {}
+        };
+
         // Argument name
         let name = quote_expr!(self.name.as_str().trim_start_matches("r#"));
 
@@ -246,10 +319,62 @@ impl ArgAttrData {
             #description
             #valid_values
             #validation_error
+            #allow_empty_values
+            #trim_values
             #default_values
         }
     }
 
+    /// Expands this argument as one argument per value name, each one taking exactly 1 value.
+    pub fn expand_value_names(&self, value_names: &[String]) -> Vec<TokenStream> {
+        let (arg, arg_type) = &self.fn_arg;
+
+        if !matches!(
+            arg_type,
+            ArgumentType::Vec(_) | ArgumentType::Slice(_) | ArgumentType::Array(_)
+        ) {
+            panic!(
+                "`value_names` can only be used with multi-value types but was `{}`",
+                pat_type_to_string(&arg.pat_type)
+            );
+        }
+
+        // Only 1 argument of an option can have default values
+        assert!(
+            !self.has_default_values(),
+            "`value_names` cannot be used with default values for `{}`",
+            pat_type_to_string(&arg.pat_type)
+        );
+
+        match self.get_value_count() {
+            (Some(min), Some(max)) if min == max => {
+                assert!(
+                    min == value_names.len(),
+                    "invalid `value_names` for `{}`, expected {} names but was {}",
+                    pat_type_to_string(&arg.pat_type),
+                    min,
+                    value_names.len()
+                );
+            }
+            _ => panic!(
+                "`value_names` requires an exact number of values for `{}`, `min` and `max` must be equals",
+                pat_type_to_string(&arg.pat_type)
+            ),
+        }
+
+        value_names
+            .iter()
+            .map(|name| {
+                let mut value_arg = self.clone();
+                value_arg.name = name.clone();
+                value_arg.min = Some(1);
+                value_arg.max = Some(1);
+
+                value_arg.expand()
+            })
+            .collect()
+    }
+
     fn get_value_count(&self) -> (Option<usize>, Option<usize>) {
         let (arg, arg_type) = &self.fn_arg;
 
@@ -271,34 +396,53 @@ impl ArgAttrData {
         }
     }
 
-    fn assert_default_values_range(&self, min: Option<usize>, max: Option<usize>) {
+    // Checks the number of `default` values against `min` and `max`,
+    // the error is spanned to the default values
+    fn check_default_values_count(
+        &self,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> syn::Result<()> {
         if self.default_values.is_empty() {
-            return;
+            return Ok(());
         }
 
         let len = self.default_values.len();
-
-        match (min, max) {
+        let expected = match (min, max) {
             (Some(min), Some(max)) if !(min..=max).contains(&len) => {
-                panic!(
-                    "invalid default values count, expected from {} to {} values but was {}",
-                    min, max, len
-                );
+                if min == max {
+                    format!("expected {} values", min)
+                } else {
+                    format!("expected from {} to {} values", min, max)
+                }
             }
             (Some(min), None) if !(min..).contains(&len) => {
-                panic!(
-                    "invalid default values count, expected {} or more values but was {}",
-                    min, len
-                );
+                format!("expected {} or more values", min)
             }
             (None, Some(max)) if !(..=max).contains(&len) => {
-                panic!(
-                    "invalid default values count, expected {} or less values but was {}",
-                    max, len
-                );
+                format!("expected {} or less values", max)
             }
-            _ => {}
-        }
+            _ => return Ok(()),
+        };
+
+        let defaults = self
+            .default_values
+            .iter()
+            .map(display_lit_to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let default_values = &self.default_values;
+        Err(syn::Error::new_spanned(
+            quote! { #(#default_values)* },
+            format!(
+                "invalid default values count for `{}`, {} but was {}: [{}]",
+                self.name.trim_start_matches("r#"),
+                expected,
+                len,
+                defaults
+            ),
+        ))
     }
 }
 
@@ -353,13 +497,13 @@ fn check_same_type(values: &[Lit]) -> Result<(), &Lit> {
     }
 }
 
-fn assert_same_type_as_fn_arg((arg, ty): &(FnArgData, ArgumentType), values: &[Lit]) {
-    fn display_lit_to_string(lit: &Lit) -> String {
-        let mut buf = String::new();
-        display_lit(&mut buf, lit).expect("error in `display_lit`");
-        buf
-    }
+fn display_lit_to_string(lit: &Lit) -> String {
+    let mut buf = String::new();
+    display_lit(&mut buf, lit).expect("error in `display_lit`");
+    buf
+}
 
+fn assert_same_type_as_fn_arg((arg, ty): &(FnArgData, ArgumentType), values: &[Lit]) {
     let arg_type = ty.get_type();
     let lit = &values[0];
     let lit_str = if values.len() > 1 {
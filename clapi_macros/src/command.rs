@@ -3,13 +3,17 @@
 use crate::arg::ArgAttrData;
 use crate::macro_attribute::{MacroAttribute, NameValueAttribute};
 use crate::option::OptionAttrData;
-use crate::utils::NamePath;
-use crate::var::{ArgLocalVar, ArgumentType};
+use crate::utils::{NamePath, RenameRule};
+use crate::var::{ArgLocalVar, SkipStrategy};
 use crate::TypeExt;
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
 use quote::*;
 use std::path::PathBuf;
-use syn::{AttrStyle, Attribute, AttributeArgs, Item, ItemFn, PatType, ReturnType, Stmt, Type};
+use syn::ext::IdentExt;
+use syn::{
+    AttrStyle, Attribute, AttributeArgs, GenericArgument, Ident, Item, ItemFn, Pat, PatType,
+    ReturnType, Stmt, Type,
+};
 
 /// Tokens for either `command` or `subcommand` attribute.
 ///
@@ -36,10 +40,19 @@ pub struct CommandAttrData {
     is_child: bool,
     version: Option<String>,
     description: Option<String>,
+    summary: Option<String>,
     usage: Option<StringSource>,
     help: Option<StringSource>,
+    before_help: Option<String>,
+    after_help: Option<String>,
+    examples: Vec<(String, String)>,
+    rename_all: Option<RenameRule>,
+    env_prefix: Option<String>,
+    deny_unused: Option<bool>,
+    bound_args: Vec<FnArgData>,
     item_fn: Option<ItemFn>,
     children: Vec<CommandAttrData>,
+    external_children: Vec<syn::Path>,
     is_hidden: Option<bool>,
     options: Vec<OptionAttrData>,
     args: Vec<ArgAttrData>,
@@ -49,13 +62,27 @@ pub struct CommandAttrData {
 }
 
 impl CommandAttrData {
-    pub fn from_fn(args: AttributeArgs, func: ItemFn) -> Self {
+    pub fn from_fn(mut args: AttributeArgs, func: ItemFn) -> Self {
         let name = func.sig.ident.to_string();
+        let external_children = imp::take_subcommands_paths(&mut args);
         let attr_data =
             NameValueAttribute::from_attribute_args(name.as_str(), args, AttrStyle::Outer)
                 .expect("failed to parse `command` attribute");
 
-        CommandAttrData::new_from_fn(attr_data, func, false, true, true)
+        let mut command = CommandAttrData::new_from_fn(attr_data, func, false, true, true);
+        command.external_children = external_children;
+        command
+    }
+
+    // A free function `subcommand`, only used in stable where the subcommands
+    // are declared with the `subcommands(...)` of the parent
+    pub fn from_subcommand_fn(mut args: AttributeArgs, func: ItemFn) -> Self {
+        let external_children = imp::take_subcommands_paths(&mut args);
+        let attr_data = imp::subcommand_args_name_values(args);
+
+        let mut command = CommandAttrData::new_from_fn(attr_data, func, true, true, false);
+        command.external_children = external_children;
+        command
     }
 
     pub fn from_path(args: AttributeArgs, func: ItemFn, path: PathBuf) -> Self {
@@ -70,10 +97,19 @@ impl CommandAttrData {
             name: None,
             version: None,
             description: None,
+            summary: None,
             usage: None,
             help: None,
+            before_help: None,
+            after_help: None,
+            examples: vec![],
+            rename_all: None,
+            env_prefix: None,
+            deny_unused: None,
+            bound_args: vec![],
             item_fn: None,
             children: vec![],
+            external_children: vec![],
             options: vec![],
             vars: vec![],
             args: vec![],
@@ -124,6 +160,28 @@ impl CommandAttrData {
         self.version = Some(version);
     }
 
+    pub fn add_example(&mut self, command_line: String, description: String) {
+        assert!(
+            !command_line.trim().is_empty(),
+            "`example` command-line cannot be empty"
+        );
+        self.examples.push((command_line, description));
+    }
+
+    pub fn set_rename_all(&mut self, rule: RenameRule) {
+        assert!(
+            self.rename_all.is_none(),
+            "command `rename_all` is already defined"
+        );
+        self.rename_all = Some(rule);
+    }
+
+    pub fn set_env_prefix(&mut self, prefix: String) {
+        assert!(!self.is_child, "only the root command can define an `env_prefix`");
+        assert!(!prefix.trim().is_empty(), "`env_prefix` cannot be empty");
+        self.env_prefix = Some(prefix);
+    }
+
     pub fn set_description(&mut self, description: String) {
         assert!(
             self.description.is_none(),
@@ -132,6 +190,25 @@ impl CommandAttrData {
         self.description = Some(description);
     }
 
+    pub fn set_deny_unused(&mut self, deny_unused: bool) {
+        self.deny_unused = Some(deny_unused);
+    }
+
+    pub fn set_summary(&mut self, summary: String) {
+        assert!(self.summary.is_none(), "command `summary` is already defined");
+        self.summary = Some(summary);
+    }
+
+    pub fn set_before_help(&mut self, before_help: String) {
+        assert!(self.before_help.is_none(), "command `before_help` is already defined");
+        self.before_help = Some(before_help);
+    }
+
+    pub fn set_after_help(&mut self, after_help: String) {
+        assert!(self.after_help.is_none(), "command `after_help` is already defined");
+        self.after_help = Some(after_help);
+    }
+
     pub fn set_usage(&mut self, usage: StringSource) {
         assert!(self.usage.is_none(), "command `usage` is already defined");
         self.usage = Some(usage);
@@ -170,26 +247,13 @@ impl CommandAttrData {
         self.is_hidden = Some(is_hidden);
     }
 
+    // Duplicated names are checked in `expand` after apply the `rename_all`
     pub fn set_option(&mut self, option: OptionAttrData) {
-        if self.options.contains(&option) {
-            panic!(
-                "duplicated option: `{}` in `{}`",
-                option.name(),
-                self.fn_name.name()
-            )
-        }
-
         self.options.push(option);
     }
 
+    // Duplicated names are checked in `expand` after apply the `rename_all`
     pub fn set_args(&mut self, args: ArgAttrData) {
-        if self.args.contains(&args) {
-            panic!(
-                "duplicated arg: `{}` in `{}`",
-                args.name(),
-                self.fn_name.name()
-            )
-        }
         self.args.push(args);
     }
 
@@ -237,6 +301,13 @@ impl CommandAttrData {
             self.fn_name
         );
 
+        // Apply only to root
+        if !self.is_child {
+            self.apply_rename_all(None);
+            self.apply_deny_unused(false);
+        }
+
+        let duplicated_errors = self.get_duplicated_names_errors();
         self.assert_global_options();
 
         // Apply only to root
@@ -257,7 +328,10 @@ impl CommandAttrData {
             .options
             .iter()
             .filter(|x| !x.is_from_global())
-            .map(|x| quote! { .option(#x)})
+            .map(|x| {
+                let negation = x.expand_negation().map(|n| quote! { .option(#n) });
+                quote! { .option(#x) #negation }
+            })
             .collect::<Vec<TokenStream>>();
 
         // Command children
@@ -267,6 +341,15 @@ impl CommandAttrData {
             .map(|x| quote! { .subcommand(#x)})
             .collect::<Vec<TokenStream>>();
 
+        // Command children declared with `subcommands(...)`, built by the function
+        // generated by its `#[subcommand]`
+        let external_children = self.external_children.iter().map(|path| {
+            let mut builder = path.clone();
+            let last = builder.segments.last_mut().unwrap();
+            last.ident = subcommand_builder_ident(&last.ident);
+            quote! { .subcommand(#builder()) }
+        });
+
         // Command function variables
         let vars = self
             .vars
@@ -280,18 +363,37 @@ impl CommandAttrData {
             .as_ref()
             .map(|s| quote! { .description(#s) });
 
+        // Command summary
+        let summary = self.summary.as_ref().map(|s| quote! { .summary(#s) });
+
         // Command hidden
         let hidden = self.is_hidden.as_ref().map(|s| quote! { .hidden(#s) });
 
-        // Command usage
-        let usage = self.usage.as_ref().map(|s| quote! { .usage(#s) });
+        // Command usage, functions are called lazily
+        let usage = self.usage.as_ref().map(|s| match s {
+            StringSource::String(_) => quote! { .usage(#s) },
+            StringSource::Fn(path) => quote! { .usage_with(|| #path().into()) },
+        });
+
+        // Command help, functions are called lazily
+        let help = self.help.as_ref().map(|s| match s {
+            StringSource::String(_) => quote! { .help(#s) },
+            StringSource::Fn(path) => quote! { .help_with(|| #path().into()) },
+        });
 
-        // Command help
-        let help = self.help.as_ref().map(|s| quote! { .help(#s) });
+        // Command text before and after the help
+        let before_help = self.before_help.as_ref().map(|s| quote! { .before_help(#s) });
+        let after_help = self.after_help.as_ref().map(|s| quote! { .after_help(#s) });
 
         // Command version
         let version = self.version.as_ref().map(|s| quote! { .version(#s) });
 
+        // Command examples
+        let examples = self
+            .examples
+            .iter()
+            .map(|(command_line, description)| quote! { .example(#command_line, #description) });
+
         // Instantiate `Command` or `RootCommand`
         let mut command = match &self.name {
             Some(name) => {
@@ -332,20 +434,39 @@ impl CommandAttrData {
             quote! {}
         };
 
+        // Options and arguments never used in the function body, only checked with `deny_unused`,
+        // and the options, arguments and subcommands with duplicated names
+        let mut errors = self.get_unused_args_errors();
+        errors.extend(duplicated_errors);
+
         // Build the command
         command = quote! {
             #command
                 #description
+                #summary
                 #usage
                 #hidden
                 #help
+                #before_help
+                #after_help
                 #version
+                #(#examples)*
                 #(#args)*
                 #(#options)*
                 #(#children)*
+                #(#external_children)*
                 #handler
         };
 
+        if !errors.is_empty() {
+            command = quote! {
+                {
+                    #(#errors)*
+                    #command
+                }
+            };
+        }
+
         if self.is_child {
             command
         } else {
@@ -388,6 +509,11 @@ impl CommandAttrData {
                 }
             };
 
+            let env_prefix = self
+                .env_prefix
+                .as_ref()
+                .map(|s| quote! { .env_prefix(#s) });
+
             // Emit the tokens to create the function with the `Command`
             quote! {
                 #(#attrs)*
@@ -397,6 +523,7 @@ impl CommandAttrData {
                     let command = #command ;
                     clapi::CommandLine::new(command)
                         #use_help
+                        #env_prefix
                         .use_default_suggestions()
                         .run()
                         #error_handling
@@ -405,6 +532,105 @@ impl CommandAttrData {
         }
     }
 
+    fn apply_rename_all(&mut self, inherited: Option<RenameRule>) {
+        // The subcommands use the rule of the closest command that defines one
+        let rule = self.rename_all.or(inherited);
+
+        if let Some(rule) = rule {
+            self.options.iter_mut().for_each(|o| o.rename(rule));
+            self.args.iter_mut().for_each(|a| a.rename(rule));
+            self.vars.iter_mut().for_each(|v| v.rename(rule));
+        }
+
+        for child in self.children.iter_mut() {
+            child.apply_rename_all(rule);
+        }
+    }
+
+    fn apply_deny_unused(&mut self, inherited: bool) {
+        // The subcommands use the value of the closest command that defines one
+        let deny_unused = self.deny_unused.unwrap_or(inherited);
+        self.deny_unused = Some(deny_unused);
+
+        for child in self.children.iter_mut() {
+            child.apply_deny_unused(deny_unused);
+        }
+    }
+
+    fn get_unused_args_errors(&self) -> Vec<TokenStream> {
+        self.get_unused_args()
+            .into_iter()
+            .map(|(ident, fn_arg)| {
+                let kind = if fn_arg.is_option { "option" } else { "argument" };
+                let error = syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "unused {} `{}` in `{}`, use it or declare it with `allow_unused`",
+                        kind,
+                        ident.unraw(),
+                        self.fn_name.name()
+                    ),
+                );
+
+                error.to_compile_error()
+            })
+            .collect()
+    }
+
+    // Marks the unused arguments of the function of this command as used,
+    // so only its `deny_unused` error is reported and not the compiler warnings
+    fn mark_unused_args(&self, item_fn: &mut ItemFn) {
+        let stmts = self
+            .get_unused_args()
+            .into_iter()
+            .map(|(ident, _)| syn::parse_quote! { let _ = &#ident; })
+            .collect::<Vec<Stmt>>();
+
+        item_fn.block.stmts.splice(0..0, stmts);
+    }
+
+    // Returns the subcommand declared with the given function
+    fn find_child(&self, fn_ident: &Ident) -> Option<&CommandAttrData> {
+        self.children
+            .iter()
+            .find(|child| child.item_fn.as_ref().is_some_and(|f| f.sig.ident == *fn_ident))
+    }
+
+    // Returns the arguments of the function never used in its body, only checked with `deny_unused`
+    fn get_unused_args(&self) -> Vec<(&Ident, &FnArgData)> {
+        if !self.deny_unused.unwrap_or(false) {
+            return vec![];
+        }
+
+        // Inner functions cannot use the arguments, so are ignored
+        let body = self
+            .item_fn
+            .as_ref()
+            .unwrap()
+            .block
+            .stmts
+            .iter()
+            .filter(|stmt| !matches!(stmt, Stmt::Item(Item::Fn(_))))
+            .map(|stmt| stmt.to_token_stream())
+            .collect::<TokenStream>();
+
+        self.bound_args
+            .iter()
+            .filter_map(|fn_arg| {
+                let ident = match fn_arg.pat_type.pat.as_ref() {
+                    Pat::Ident(pat_ident) => &pat_ident.ident,
+                    _ => return None,
+                };
+
+                if ident.to_string().starts_with('_') || contains_ident(body.clone(), ident) {
+                    return None;
+                }
+
+                Some((ident, fn_arg))
+            })
+            .collect()
+    }
+
     fn infer_global_options(&self) {
         /*
         FIXME: This is a hack to infer the options which requires to make a copy
@@ -448,6 +674,82 @@ impl CommandAttrData {
         }
     }
 
+    // Checks the names and aliases of the options, the names of the args and the names
+    // of the subcommands are unique, using the names after apply the `rename_all`,
+    // the errors point to the second declaration
+    fn get_duplicated_names_errors(&self) -> Vec<TokenStream> {
+        let command_name = self.fn_name.name();
+        let mut errors = Vec::new();
+
+        // (name or alias, function argument)
+        let mut option_names: Vec<(&str, &str)> = Vec::new();
+        for option in self.options.iter().filter(|o| !o.is_from_global()) {
+            for name in std::iter::once(option.name()).chain(option.alias()) {
+                if let Some((_, other)) = option_names
+                    .iter()
+                    .find(|(n, arg_name)| *n == name && *arg_name != option.arg_name)
+                {
+                    let error = syn::Error::new(
+                        option.span(),
+                        format!(
+                            "function arguments `{}` and `{}` are both declared as option `{}` in `{}`",
+                            other, option.arg_name, name, command_name
+                        ),
+                    );
+
+                    errors.push(error.to_compile_error());
+                }
+
+                option_names.push((name, &option.arg_name));
+            }
+        }
+
+        for (index, arg) in self.args.iter().enumerate() {
+            if let Some(other) = self.args[..index].iter().find(|a| a.name() == arg.name()) {
+                let error = syn::Error::new(
+                    arg.span(),
+                    format!(
+                        "function arguments `{}` and `{}` are both declared as arg `{}` in `{}`",
+                        other.arg_name(),
+                        arg.arg_name(),
+                        arg.name(),
+                        command_name
+                    ),
+                );
+
+                errors.push(error.to_compile_error());
+            }
+        }
+
+        // The children are in reverse declaration order, so the first declaration is after
+        for (index, child) in self.children.iter().enumerate() {
+            if let Some(other) = self.children[index + 1..]
+                .iter()
+                .find(|c| c.command_name() == child.command_name())
+            {
+                let error = syn::Error::new_spanned(
+                    &child.item_fn.as_ref().unwrap().sig.ident,
+                    format!(
+                        "subcommands `{}` and `{}` are both named `{}` in `{}`",
+                        other.fn_name.name(),
+                        child.fn_name.name(),
+                        child.command_name(),
+                        command_name
+                    ),
+                );
+
+                errors.push(error.to_compile_error());
+            }
+        }
+
+        errors
+    }
+
+    // The name used in the command-line, the subcommands use the function name by default
+    fn command_name(&self) -> &str {
+        self.name.as_deref().unwrap_or_else(|| self.fn_name.name())
+    }
+
     fn assert_global_options(&self) {
         if self.is_child {
             return;
@@ -501,6 +803,11 @@ impl CommandAttrData {
                             .unwrap();
 
                         var.name = Some(opt.name().to_string());
+
+                        // The value of a counted global option is its number of occurrences
+                        if opt.is_counted() {
+                            var.set_counted();
+                        }
                     }
                 } else {
                     update_children_options(opt, child);
@@ -524,6 +831,57 @@ impl CommandAttrData {
         }
     }
 
+    // Expands a free function `subcommand` and the function that builds its `Command`,
+    // the inner subcommands are moved to the builder function to be visible for the handlers
+    pub fn expand_subcommand_builder(mut self) -> TokenStream {
+        assert!(self.is_child, "`{}` is not a subcommand", self.fn_name);
+
+        // A free subcommand is the root of its own subcommands
+        self.apply_rename_all(None);
+        self.apply_deny_unused(false);
+        self.infer_global_options();
+        self.update_children_global_options();
+
+        let mut item_fn = self.item_fn.clone().unwrap();
+        let mut inner_subcommands = Vec::new();
+
+        for stmt in item_fn.block.stmts.iter_mut() {
+            if let Stmt::Item(Item::Fn(inner_fn)) = stmt {
+                let is_subcommand = inner_fn.attrs.iter().any(|att| {
+                    crate::consts::is_subcommand(&crate::utils::path_to_string(&att.path))
+                });
+
+                if is_subcommand {
+                    let mut inner = drop_command_attributes(inner_fn.clone());
+                    crate::utils::insert_allow_dead_code_attribute(&mut inner);
+                    if let Some(child) = self.find_child(&inner.sig.ident) {
+                        child.mark_unused_args(&mut inner);
+                    }
+                    inner_subcommands.push(inner.clone());
+                    *inner_fn = inner;
+                }
+            }
+        }
+
+        crate::utils::insert_allow_dead_code_attribute(&mut item_fn);
+        self.mark_unused_args(&mut item_fn);
+
+        let vis = &item_fn.vis;
+        let builder = subcommand_builder_ident(&item_fn.sig.ident);
+        let command = self.expand();
+
+        quote! {
+            #item_fn
+
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            #vis fn #builder() -> clapi::Command {
+                #(#inner_subcommands)*
+                #command
+            }
+        }
+    }
+
     fn get_body(&self, vars: &[TokenStream]) -> TokenStream {
         let ret = &self.item_fn.as_ref().unwrap().sig.output;
         let error_handling = match ret {
@@ -534,21 +892,11 @@ impl CommandAttrData {
 
         if self.is_child {
             let fn_name = self.fn_name.to_string().parse::<TokenStream>().unwrap();
-            let inputs = self.vars.iter().map(|var| {
-                let var_name = var.var_name().parse::<TokenStream>().unwrap();
-                let is_ref = match var.arg_type() {
-                    ArgumentType::Slice(ty) => {
-                        if ty.mutability {
-                            quote! { &mut }
-                        } else {
-                            quote! { & }
-                        }
-                    }
-                    _ => quote! {},
-                };
-
-                quote! { #is_ref #var_name }
-            });
+            // Slices are already borrowed from a local `Vec` declared in `vars`
+            let inputs = self
+                .vars
+                .iter()
+                .map(|var| var.var_name().parse::<TokenStream>().unwrap());
 
             quote! {
                 #(#vars)*
@@ -587,17 +935,25 @@ impl CommandAttrData {
             })
         }
 
-        fn statement_to_tokens(stmt: Stmt) -> TokenStream {
+        let statement_to_tokens = |stmt: Stmt| -> TokenStream {
             if let Stmt::Item(Item::Fn(ref item_fn)) = stmt {
-                if contains_subcommand_attribute(item_fn) && !contains_expressions(item_fn) {
+                if contains_subcommand_attribute(item_fn) {
+                    // Subcommands without expressions have no handler so are never called
                     let mut item_fn = item_fn.clone();
-                    crate::utils::insert_allow_dead_code_attribute(&mut item_fn);
+                    if !contains_expressions(&item_fn) {
+                        crate::utils::insert_allow_dead_code_attribute(&mut item_fn);
+                    }
+
+                    if let Some(child) = self.find_child(&item_fn.sig.ident) {
+                        child.mark_unused_args(&mut item_fn);
+                    }
+
                     return drop_command_attributes(item_fn).to_token_stream();
                 }
             }
 
             stmt.to_token_stream()
-        }
+        };
 
         // functions, struct, impl, const, statics, ...
         self.item_fn
@@ -643,6 +999,8 @@ pub struct FnArgData {
     pub name_value: Option<NameValueAttribute>,
     // If the function argument correspond to a command option.
     pub is_option: bool,
+    // How the value is obtained if is not a command-line input, declared with `skip`
+    pub skip: Option<SkipStrategy>,
 }
 
 // Represents the source of the string data used.
@@ -712,6 +1070,30 @@ pub fn contains_expressions(item_fn: &ItemFn) -> bool {
         .not()
 }
 
+// Returns `true` if the identifier is used in the tokens,
+// including the inline arguments of format strings like `"{name}"`.
+fn contains_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    let name = ident.unraw().to_string();
+
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(other) => other.unraw() == name,
+        TokenTree::Group(group) => contains_ident(group.stream(), ident),
+        TokenTree::Literal(literal) => {
+            let literal = literal.to_string();
+            literal.ends_with('"')
+                && (literal.contains(&format!("{{{}}}", name))
+                    || literal.contains(&format!("{{{}:", name)))
+        }
+        TokenTree::Punct(_) => false,
+    })
+}
+
+/// Returns the name of the function generated by a free function `subcommand`
+/// which builds its `Command`, like: `__clapi_subcommand_name`.
+pub fn subcommand_builder_ident(ident: &Ident) -> Ident {
+    format_ident!("__clapi_subcommand_{}", ident.unraw(), span = ident.span())
+}
+
 /// Remove all the `clapi` macro attributes like `command`, `subcommand`, `option` and `arg`
 /// from a `ItemFn`.
 pub fn drop_command_attributes(mut item_fn: ItemFn) -> ItemFn {
@@ -737,6 +1119,8 @@ pub fn drop_command_attributes(mut item_fn: ItemFn) -> ItemFn {
 /// #[command]
 /// fn main(enable: bool){}
 /// ```
+///
+/// An `Option<bool>` is also a flag, but is `None` if absent.
 pub fn is_option_bool_flag(fn_arg: &FnArgData) -> bool {
     // Only `option`s can be bool flags
     if !fn_arg.is_option {
@@ -744,7 +1128,8 @@ pub fn is_option_bool_flag(fn_arg: &FnArgData) -> bool {
     }
 
     // Of course, only bool can be an option bool flag
-    if !fn_arg.pat_type.ty.is_bool() {
+    let is_tri_state = is_optional_bool(&fn_arg.pat_type.ty);
+    if !fn_arg.pat_type.ty.is_bool() && !is_tri_state {
         return false;
     }
 
@@ -783,28 +1168,88 @@ pub fn is_option_bool_flag(fn_arg: &FnArgData) -> bool {
             })
             .unwrap_or(false);
 
-        // Is an option bool flag if: is boolean type and: min = 0, max = 1, default = false
-        min == 0 && max == 1 && default == false
+        // Is an option bool flag if: is boolean type and: min = 0, max = 1, default = false,
+        // an `Option<bool>` can have any default which is used if the flag is absent
+        min == 0 && max == 1 && (default == false || is_tri_state)
     } else {
         true
     }
 }
 
+/// Returns `true` if the function argument is a counted option, like:
+///
+/// ```text
+/// #[command]
+/// #[option(verbose, alias="v", counted)]
+/// fn main(verbose: u8){}
+/// ```
+pub fn is_option_counted(fn_arg: &FnArgData) -> bool {
+    fn_arg.is_option
+        && fn_arg
+            .name_value
+            .as_ref()
+            .and_then(|attribute| attribute.get(crate::consts::COUNTED))
+            .is_some_and(|v| v.to_bool_literal().expect("`counted` must be a bool literal"))
+}
+
+/// Returns `true` if the function argument is declared with `allow_unused`,
+/// which is not checked by the command `deny_unused`.
+pub fn is_allow_unused(fn_arg: &FnArgData) -> bool {
+    fn_arg
+        .name_value
+        .as_ref()
+        .and_then(|attribute| attribute.get(crate::consts::ALLOW_UNUSED))
+        .is_some_and(|v| v.to_bool_literal().expect("`allow_unused` must be a bool literal"))
+}
+
+/// Returns the default value of an `Option<bool>` flag, used when the flag is absent.
+pub fn get_tri_state_flag_default(fn_arg: &FnArgData) -> Option<bool> {
+    if !is_optional_bool(&fn_arg.pat_type.ty) || !is_option_bool_flag(fn_arg) {
+        return None;
+    }
+
+    fn_arg
+        .name_value
+        .as_ref()
+        .and_then(|attribute| attribute.get(crate::consts::DEFAULT))
+        .map(|v| {
+            v.to_bool_literal()
+                .expect("`default` must be a bool literal")
+        })
+}
+
+// Checks if the type is `Option<bool>`
+pub fn is_optional_bool(ty: &Type) -> bool {
+    if !ty.is_option() {
+        return false;
+    }
+
+    match ty.generic_arguments().as_slice() {
+        [GenericArgument::Type(generic)] => generic.is_bool(),
+        _ => false,
+    }
+}
+
 mod imp {
     use crate::arg::ArgAttrData;
     use crate::command::{
-        drop_command_attributes, is_option_bool_flag, CommandAttrData, FnArgData, StringSource,
+        drop_command_attributes, get_tri_state_flag_default, is_allow_unused, is_option_bool_flag,
+        is_option_counted, CommandAttrData, FnArgData, StringSource,
     };
-    use crate::macro_attribute::{MacroAttribute, NameValueAttribute};
+    use crate::macro_attribute::{MacroAttribute, MetaItem, NameValue, NameValueAttribute, Value};
     use crate::option::OptionAttrData;
     use crate::query::QueryItem;
-    use crate::utils::{path_to_string, NamePath};
-    use crate::var::{ArgLocalVar, VarSource};
+    use crate::utils::{path_to_string, NamePath, RenameRule};
+    use crate::var::{ArgLocalVar, SkipStrategy, VarSource};
     use crate::{consts, AttrQuery};
     use quote::ToTokens;
     use std::path::{Path, PathBuf};
     use std::sync::atomic::{AtomicBool, Ordering};
-    use syn::{AttrStyle, Attribute, AttributeArgs, File, FnArg, Item, ItemFn, PatType, Stmt};
+    use proc_macro2::Span;
+    use syn::{
+        AttrStyle, Attribute, AttributeArgs, File, FnArg, Item, ItemFn, Lit, LitBool, Meta,
+        NestedMeta, PatType, Stmt, Type,
+    };
 
     // Constructs a new `CommandAttrData` from a `ItemFn`
     pub fn command_from_fn_with_name(
@@ -834,6 +1279,13 @@ mod imp {
 
                     command.set_description(description);
                 }
+                crate::consts::SUMMARY => {
+                    let summary = value
+                        .to_string_literal()
+                        .expect("`summary` must be a string literal");
+
+                    command.set_summary(summary);
+                }
                 crate::consts::HIDDEN => {
                     let hidden = value
                         .to_bool_literal()
@@ -862,6 +1314,20 @@ mod imp {
 
                     command.set_help(StringSource::String(help));
                 }
+                crate::consts::BEFORE_HELP => {
+                    let before_help = value
+                        .to_string_literal()
+                        .expect("`before_help` must be a string literal");
+
+                    command.set_before_help(before_help);
+                }
+                crate::consts::AFTER_HELP => {
+                    let after_help = value
+                        .to_string_literal()
+                        .expect("`after_help` must be a string literal");
+
+                    command.set_after_help(after_help);
+                }
                 crate::consts::WITH_USAGE => {
                     let expr = value
                         .to_string_literal()
@@ -877,6 +1343,39 @@ mod imp {
                         });
                     command.set_usage(s);
                 }
+                crate::consts::EXAMPLE => {
+                    let values = value
+                        .to_string_array()
+                        .filter(|values| values.len() % 2 == 0)
+                        .expect("`example` must be a pair of string literals: `example=(\"command-line\", \"description\")`");
+
+                    for pair in values.chunks(2) {
+                        command.add_example(pair[0].clone(), pair[1].clone());
+                    }
+                }
+                crate::consts::RENAME_ALL => {
+                    let rule = value
+                        .to_string_literal()
+                        .expect("`rename_all` must be a string literal")
+                        .parse::<RenameRule>()
+                        .unwrap_or_else(|e| panic!("{}", e));
+
+                    command.set_rename_all(rule);
+                }
+                crate::consts::ENV_PREFIX => {
+                    let prefix = value
+                        .to_string_literal()
+                        .expect("`env_prefix` must be a string literal");
+
+                    command.set_env_prefix(prefix);
+                }
+                crate::consts::DENY_UNUSED => {
+                    let deny_unused = value
+                        .to_bool_literal()
+                        .expect("`deny_unused` must be a bool literal");
+
+                    command.set_deny_unused(deny_unused);
+                }
                 crate::consts::WITH_HELP => {
                     let expr = value
                         .to_string_literal()
@@ -900,21 +1399,44 @@ mod imp {
         }
 
         let fn_args = get_fn_args(&item_fn);
-        let arg_count = fn_args.iter().filter(|f| !f.is_option).count();
+        let arg_count = fn_args.iter().filter(|f| !f.is_option && f.skip.is_none()).count();
+
+        // Options and arguments checked by `deny_unused`
+        command.bound_args = fn_args
+            .iter()
+            .filter(|f| f.skip.is_none() && !is_allow_unused(f))
+            .cloned()
+            .collect();
 
         // Pass function arguments in order
         for fn_arg in &fn_args {
-            if fn_arg.is_option {
-                let source = if is_option_bool_flag(fn_arg) {
+            if let Some(strategy) = fn_arg.skip {
+                let source = VarSource::Skip(strategy, fn_arg.pat_type.ty.clone());
+                command.set_var(ArgLocalVar::new(fn_arg.pat_type.clone(), source, None));
+            } else if fn_arg.is_option {
+                let source = if is_option_counted(fn_arg) {
+                    VarSource::OptCount
+                } else if is_option_bool_flag(fn_arg) {
                     VarSource::OptBool
                 } else {
                     VarSource::Opts(fn_arg.arg_name.clone())
                 };
-                command.set_var(ArgLocalVar::new(
-                    fn_arg.pat_type.clone(),
-                    source,
-                    fn_arg.name.clone(),
-                ));
+                let mut var =
+                    ArgLocalVar::new(fn_arg.pat_type.clone(), source, fn_arg.name.clone());
+
+                if let Some(default) = get_tri_state_flag_default(fn_arg) {
+                    var.set_flag_default(default);
+                }
+
+                if let Some(value_names) = fn_arg
+                    .name_value
+                    .as_ref()
+                    .and_then(|attr| attr.get(consts::VALUE_NAMES))
+                {
+                    var.set_value_names(value_names.to_string_array().unwrap_or_default());
+                }
+
+                command.set_var(var);
             } else {
                 command.set_var(ArgLocalVar::new(
                     fn_arg.pat_type.clone(),
@@ -926,13 +1448,13 @@ mod imp {
 
         // Add args
         if arg_count > 0 {
-            for fn_arg in fn_args.iter().filter(|f| !f.is_option) {
+            for fn_arg in fn_args.iter().filter(|f| !f.is_option && f.skip.is_none()) {
                 command.set_args(ArgAttrData::from_arg_data(fn_arg.clone()));
             }
         }
 
         // Add options
-        for fn_arg in fn_args.into_iter().filter(|n| n.is_option) {
+        for fn_arg in fn_args.into_iter().filter(|n| n.is_option && n.skip.is_none()) {
             let option = OptionAttrData::from_arg_data(fn_arg);
             command.set_option(option);
         }
@@ -1085,9 +1607,7 @@ mod imp {
                         inner_fn.attrs.iter().position(|att| {
                             crate::consts::is_subcommand(path_to_string(&att.path).as_str())
                         }) {
-                        MacroAttribute::new(inner_fn.attrs.swap_remove(index))
-                            .into_name_values()
-                            .unwrap()
+                        subcommand_name_values(inner_fn.attrs.swap_remove(index))
                     } else {
                         unreachable!()
                     };
@@ -1187,6 +1707,10 @@ mod imp {
                 .map(|x| x.to_string_literal())
                 .flatten();
 
+            let skip = name_value
+                .as_ref()
+                .and_then(|name_value| get_skip_strategy(&arg_name, &pat_type, name_value));
+
             ret.push(FnArgData {
                 arg_name,
                 name,
@@ -1194,6 +1718,7 @@ mod imp {
                 attribute,
                 name_value,
                 is_option,
+                skip,
             });
         }
 
@@ -1214,6 +1739,44 @@ mod imp {
         })
     }
 
+    // Gets how the value of the function argument is obtained if declared with `skip`:
+    // `skip` or `skip="inject"` uses `clapi::inject`, and `skip="default"` uses `Default::default()`.
+    fn get_skip_strategy(
+        arg_name: &str,
+        pat_type: &PatType,
+        name_value: &NameValueAttribute,
+    ) -> Option<SkipStrategy> {
+        let value = name_value.get(consts::SKIP)?;
+        let strategy = if value.is_bool() {
+            if !value.to_bool_literal().unwrap() {
+                return None;
+            }
+
+            SkipStrategy::Inject
+        } else {
+            match value.to_string_literal().as_deref() {
+                Some("inject") => SkipStrategy::Inject,
+                Some("default") => SkipStrategy::Default,
+                _ => panic!(
+                    "invalid `skip` value for `{}`, expected `skip`, `skip=\"inject\"` or `skip=\"default\"` but was `{}`",
+                    arg_name, value
+                ),
+            }
+        };
+
+        assert!(
+            name_value.len() == 1,
+            "`skip` cannot be used with other keys in `{}`",
+            arg_name
+        );
+
+        if let Type::Reference(_) = pat_type.ty.as_ref() {
+            panic!("skipped argument `{}` cannot be a reference", arg_name);
+        }
+
+        Some(strategy)
+    }
+
     // Takes a `MacroAttribute` and returns its path, self and this name values
     fn split_attr_path_and_name_values(
         attribute: MacroAttribute,
@@ -1229,13 +1792,81 @@ mod imp {
         let name_values = if attribute.len() == 1 {
             NameValueAttribute::empty(attribute.path().to_owned(), AttrStyle::Outer)
         } else {
-            let meta_items = attribute[1..].to_vec();
-            NameValueAttribute::new(attribute.path(), meta_items, AttrStyle::Outer).unwrap()
+            NameValueAttribute::new(attribute.path(), flags_to_name_values(&attribute[1..]), AttrStyle::Outer).unwrap()
         };
 
         (name, attribute, name_values)
     }
 
+    // Converts the `#[subcommand]` attribute to name-values
+    fn subcommand_name_values(attribute: Attribute) -> NameValueAttribute {
+        let attribute = MacroAttribute::new(attribute);
+        NameValueAttribute::new(attribute.path(), flags_to_name_values(attribute.args()), AttrStyle::Outer).unwrap()
+    }
+
+    // Converts the arguments of a free function `#[subcommand(...)]` to name-values
+    pub fn subcommand_args_name_values(args: AttributeArgs) -> NameValueAttribute {
+        let attribute = MacroAttribute::from_attribute_args(consts::SUBCOMMAND, args, AttrStyle::Outer);
+        NameValueAttribute::new(attribute.path(), flags_to_name_values(attribute.args()), AttrStyle::Outer)
+            .expect("failed to parse `subcommand` attribute")
+    }
+
+    // Removes the `subcommands(path::to::fn, ...)` from the attribute arguments and returns its paths
+    pub fn take_subcommands_paths(args: &mut AttributeArgs) -> Vec<syn::Path> {
+        let mut paths: Vec<syn::Path> = Vec::new();
+
+        args.retain(|arg| match arg {
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident(consts::SUBCOMMANDS) => {
+                for nested in &list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::Path(path)) => {
+                            if paths.contains(path) {
+                                panic!("duplicated subcommand: `{}`", path_to_string(path));
+                            }
+
+                            paths.push(path.clone());
+                        }
+                        _ => panic!(
+                            "`subcommands` expects function paths like: `subcommands(path::to::function)`, but was `{}`",
+                            nested.to_token_stream()
+                        ),
+                    }
+                }
+
+                false
+            }
+            _ => true,
+        });
+
+        paths
+    }
+
+    // A single flag like `skip`, `hidden` or `counted` is the same as `skip=true`, `hidden=true` or `counted=true`
+    fn flags_to_name_values(meta_items: &[MetaItem]) -> Vec<MetaItem> {
+        meta_items
+            .iter()
+            .cloned()
+            .map(|meta_item| match meta_item {
+                MetaItem::Path(path)
+                    if path == consts::SKIP
+                        || path == consts::HIDDEN
+                        || path == consts::COUNTED
+                        || path == consts::DENY_UNUSED
+                        || path == consts::ALLOW_UNUSED =>
+                {
+                    MetaItem::NameValue(NameValue {
+                        name: path,
+                        value: Value::Literal(Lit::Bool(LitBool {
+                            value: true,
+                            span: Span::call_site(),
+                        })),
+                    })
+                }
+                meta_item => meta_item,
+            })
+            .collect()
+    }
+
     // Implementation of `CommandAttrData::from_path`
     pub fn command_from_path(
         args: AttributeArgs,
@@ -1450,11 +2081,7 @@ mod imp {
     fn get_subcommands_item_fn(root_path: &Path) -> Vec<QueryItem<(ItemFn, NameValueAttribute)>> {
         fn if_subcommand_to_name_value(attribute: &Attribute) -> Option<NameValueAttribute> {
             if consts::is_subcommand(&path_to_string(&attribute.path)) {
-                Some(
-                    MacroAttribute::new(attribute.clone())
-                        .into_name_values()
-                        .unwrap(),
-                )
+                Some(subcommand_name_values(attribute.clone()))
             } else {
                 None
             }
@@ -1489,3 +2116,236 @@ mod imp {
         panic!("`{}` is not a top free function", item_fn.sig.ident);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand(args: AttributeArgs, item_fn: ItemFn) -> String {
+        CommandAttrData::from_fn(args, item_fn).expand().to_string()
+    }
+
+    fn attribute_args(tokens: TokenStream) -> AttributeArgs {
+        use syn::parse::Parser;
+        use syn::punctuated::Punctuated;
+
+        Punctuated::<syn::NestedMeta, syn::Token![,]>::parse_terminated
+            .parse2(tokens)
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn hidden_expand_test() {
+        let tokens = expand(
+            vec![],
+            parse_quote! {
+                #[option(debug_internal, hidden=true)]
+                #[option(trace, hidden)]
+                #[option(verbose, hidden=false)]
+                fn app(debug_internal: bool, trace: bool, verbose: bool) {
+                    #[subcommand(hidden)]
+                    fn secret() {}
+
+                    #[subcommand(hidden=true)]
+                    fn other() {}
+                }
+            },
+        );
+
+        assert_eq!(tokens.matches(". hidden (true)").count(), 4, "{}", tokens);
+        assert_eq!(tokens.matches(". hidden (false)").count(), 1, "{}", tokens);
+    }
+
+    #[test]
+    fn before_and_after_help_expand_test() {
+        let tokens = expand(
+            attribute_args(quote! { before_help="NOTE: experimental", after_help="LICENSE: MIT" }),
+            parse_quote! {
+                fn app() {
+                    #[subcommand]
+                    fn test() {}
+                }
+            },
+        );
+
+        assert_eq!(tokens.matches(". before_help (\"NOTE: experimental\")").count(), 1, "{}", tokens);
+        assert_eq!(tokens.matches(". after_help (\"LICENSE: MIT\")").count(), 1, "{}", tokens);
+    }
+
+    #[test]
+    fn counted_expand_test() {
+        let tokens = expand(
+            vec![],
+            parse_quote! {
+                #[option(verbose, alias="v", counted, global=true)]
+                fn app(verbose: u8) {
+                    #[subcommand]
+                    #[option(verbose, from_global=true)]
+                    fn build(verbose: u8) {
+                        println!("{}", verbose);
+                    }
+
+                    println!("{}", verbose);
+                }
+            },
+        );
+
+        assert_eq!(tokens.matches(". counted (true)").count(), 1, "{}", tokens);
+        assert_eq!(tokens.matches("opts . get_count (\"verbose\")").count(), 2, "{}", tokens);
+        assert!(!tokens.contains("CommandOption :: new (\"verbose\") . alias (\"v\") . arg"), "{}", tokens);
+    }
+
+    #[test]
+    fn deny_unused_used_args_expand_test() {
+        let tokens = expand(
+            attribute_args(quote! { deny_unused = true }),
+            parse_quote! {
+                #[arg(values)]
+                #[option(times, default=1)]
+                fn app(times: u32, _ignored: bool, verbose: bool, values: Vec<i64>) {
+                    #[subcommand]
+                    fn echo(message: String) {
+                        println!("{message}");
+                    }
+
+                    if verbose {
+                        println!("{:?}", values.iter().map(|n| n * times as i64));
+                    }
+                }
+            },
+        );
+
+        assert!(!tokens.contains("compile_error"), "{}", tokens);
+    }
+
+    #[test]
+    fn deny_unused_expand_test() {
+        let tokens = expand(
+            attribute_args(quote! { deny_unused = true }),
+            parse_quote! {
+                #[arg(values)]
+                fn app(times: u32, values: Vec<i64>) {
+                    #[subcommand]
+                    fn echo(message: String, upper: bool) {
+                        println!("{}", message);
+
+                        fn inner(upper: bool) -> bool { upper }
+                    }
+
+                    #[subcommand(deny_unused = false)]
+                    fn other(force: bool) {
+                        println!("other");
+                    }
+
+                    println!("{:?}", values);
+                }
+            },
+        );
+
+        assert!(tokens.contains("unused option `times` in `app`"), "{}", tokens);
+        assert!(tokens.contains("unused option `upper` in `echo`"), "{}", tokens);
+        assert!(!tokens.contains("`values`"), "{}", tokens);
+        assert!(!tokens.contains("`message`"), "{}", tokens);
+        assert!(!tokens.contains("`force`"), "{}", tokens);
+
+        // The unused arguments are marked as used, so the compiler don't warn about them
+        assert!(tokens.contains("fn echo (message : String , upper : bool) { let _ = & upper ;"), "{}", tokens);
+        assert!(!tokens.contains("let _ = & force ;"), "{}", tokens);
+
+        let tokens = expand(
+            attribute_args(quote! { deny_unused = true }),
+            parse_quote! {
+                #[arg(values)]
+                fn app(values: Vec<i64>) {
+                    println!("done");
+                }
+            },
+        );
+
+        assert!(tokens.contains("unused argument `values` in `app`"), "{}", tokens);
+
+        // Not checked by default
+        let tokens = expand(vec![], parse_quote! { fn app(times: u32) { println!("done"); } });
+        assert!(!tokens.contains("compile_error"), "{}", tokens);
+    }
+
+    #[test]
+    fn deny_unused_allow_unused_expand_test() {
+        let tokens = expand(
+            attribute_args(quote! { deny_unused = true }),
+            parse_quote! {
+                #[option(times, allow_unused)]
+                #[option(color, allow_unused = true, default="auto")]
+                #[arg(values, allow_unused)]
+                #[option(verbose, allow_unused = false)]
+                fn app(times: u32, color: String, verbose: bool, values: Vec<i64>) {
+                    println!("done");
+                }
+            },
+        );
+
+        assert_eq!(tokens.matches("compile_error").count(), 1, "{}", tokens);
+        assert!(tokens.contains("unused option `verbose` in `app`"), "{}", tokens);
+    }
+
+    #[test]
+    fn subcommands_paths_expand_test() {
+        let tokens = expand(
+            attribute_args(quote! { subcommands(files::list, show_version), description="App" }),
+            parse_quote! {
+                fn app() {
+                    #[subcommand]
+                    fn inner() {}
+                }
+            },
+        );
+
+        assert!(tokens.contains(". description (\"App\")"), "{}", tokens);
+        assert!(tokens.contains(". subcommand (files :: __clapi_subcommand_list ())"), "{}", tokens);
+        assert!(tokens.contains(". subcommand (__clapi_subcommand_show_version ())"), "{}", tokens);
+        assert!(tokens.contains("clapi :: Command :: new (\"inner\")"), "{}", tokens);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicated subcommand: `files::list`")]
+    fn duplicated_subcommands_paths_test() {
+        expand(
+            attribute_args(quote! { subcommands(files::list, files::list) }),
+            parse_quote! { fn app() {} },
+        );
+    }
+
+    #[test]
+    fn subcommand_builder_expand_test() {
+        let tokens = CommandAttrData::from_subcommand_fn(
+            attribute_args(quote! { description="List the files", summary="List", hidden, subcommands(sort) }),
+            parse_quote! {
+                #[option(all)]
+                pub fn list(all: bool) {
+                    #[subcommand]
+                    fn recent() {
+                        println!("recent");
+                    }
+
+                    println!("{}", all);
+                }
+            },
+        )
+        .expand_subcommand_builder()
+        .to_string();
+
+        assert!(tokens.contains("pub fn list (all : bool)"), "{}", tokens);
+        assert!(tokens.contains("pub fn __clapi_subcommand_list () -> clapi :: Command"), "{}", tokens);
+        assert!(tokens.contains("clapi :: Command :: new (\"list\")"), "{}", tokens);
+        assert!(tokens.contains(". hidden (true)"), "{}", tokens);
+        assert!(tokens.contains(". summary (\"List\")"), "{}", tokens);
+        assert!(tokens.contains(". subcommand (__clapi_subcommand_sort ())"), "{}", tokens);
+
+        // The inner subcommand is declared in both, the function and the builder
+        assert_eq!(tokens.matches("fn recent ()").count(), 2, "{}", tokens);
+        assert!(!tokens.contains("# [subcommand]"), "{}", tokens);
+    }
+}
@@ -2,16 +2,25 @@
 
 pub const COMMAND: &str = "command";
 pub const SUBCOMMAND: &str = "subcommand";
+pub const SUBCOMMANDS: &str = "subcommands";
 pub const OPTION: &str = "option";
 pub const ARG: &str = "arg";
 pub const USAGE: &str = "usage";
 pub const WITH_USAGE: &str = "with_usage";
 pub const HELP: &str = "help";
 pub const WITH_HELP: &str = "with_help";
+pub const BEFORE_HELP: &str = "before_help";
+pub const AFTER_HELP: &str = "after_help";
 pub const NAME: &str = "name";
 pub const ALIAS: &str = "alias";
 pub const VERSION: &str = "version";
 pub const DESCRIPTION: &str = "description";
+pub const SUMMARY: &str = "summary";
+pub const EXAMPLE: &str = "example";
+pub const RENAME_ALL: &str = "rename_all";
+pub const ENV_PREFIX: &str = "env_prefix";
+pub const ENV: &str = "env";
+pub const SKIP: &str = "skip";
 pub const PARENT: &str = "parent";
 pub const MIN: &str = "min";
 pub const MAX: &str = "max";
@@ -21,11 +30,19 @@ pub const HIDDEN: &str = "hidden";
 pub const GLOBAL: &str = "global";
 pub const FROM_GLOBAL: &str = "from_global";
 pub const MULTIPLE: &str = "multiple";
+pub const COUNTED: &str = "counted";
 pub const REQUIRES_ASSIGN: &str = "requires_assign";
 pub const FLAG: &str = "flag";
+pub const VALUE_NAMES: &str = "value_names";
 pub const ERROR: &str = "error";
+pub const EMPTY: &str = "empty";
+pub const TRIM: &str = "trim";
 pub const COMMAND_HELP: &str = "command_help";
 pub const COMMAND_USAGE: &str = "command_usage";
+pub const COMMAND_ATTR: &str = "command_attr";
+pub const POSITIONAL: &str = "positional";
+pub const DENY_UNUSED: &str = "deny_unused";
+pub const ALLOW_UNUSED: &str = "allow_unused";
 
 pub fn is_clapi_attribute(path: &str) -> bool {
     is_command(path) || is_subcommand(path) || is_option(path) || is_arg(path)
@@ -0,0 +1,411 @@
+use crate::arg::ArgAttrData;
+use crate::command::{get_tri_state_flag_default, is_option_bool_flag, FnArgData};
+use crate::consts;
+use crate::macro_attribute::{MacroAttribute, MetaItem, NameValue, NameValueAttribute, Value};
+use crate::option::OptionAttrData;
+use crate::utils::RenameRule;
+use crate::var::{ArgLocalVar, VarSource};
+use proc_macro2::{Span, TokenStream};
+use quote::*;
+use syn::{
+    AttrStyle, Attribute, Data, DeriveInput, Fields, FnArg, Ident, Lit, LitBool, PatType, Type,
+};
+
+/// Tokens for a struct marked with `#[derive(FromCommand)]`.
+///
+/// ```text
+/// #[derive(FromCommand)]
+/// #[command_attr(description="Sum the values")]
+/// struct Sum {
+///     #[command_attr(alias="v", description="Show the steps")]
+///     verbose: bool,
+///     #[command_attr(positional)]
+///     values: Vec<i64>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct FromCommandData {
+    ident: Ident,
+    name: Option<String>,
+    description: Option<String>,
+    summary: Option<String>,
+    version: Option<String>,
+    usage: Option<String>,
+    help: Option<String>,
+    before_help: Option<String>,
+    after_help: Option<String>,
+    rename_all: Option<RenameRule>,
+    options: Vec<OptionAttrData>,
+    args: Vec<ArgAttrData>,
+    vars: Vec<ArgLocalVar>,
+}
+
+impl FromCommandData {
+    pub fn from_derive_input(input: DeriveInput) -> Self {
+        let ident = input.ident;
+
+        assert!(
+            input.generics.params.is_empty(),
+            "`FromCommand` cannot be derived for generic structs: `{}`",
+            ident
+        );
+
+        let fields = match input.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => fields.named,
+                _ => panic!(
+                    "`FromCommand` can only be derived for structs with named fields: `{}`",
+                    ident
+                ),
+            },
+            _ => panic!("`FromCommand` can only be derived for structs: `{}`", ident),
+        };
+
+        let mut data = FromCommandData {
+            ident,
+            name: None,
+            description: None,
+            summary: None,
+            version: None,
+            usage: None,
+            help: None,
+            before_help: None,
+            after_help: None,
+            rename_all: None,
+            options: vec![],
+            args: vec![],
+            vars: vec![],
+        };
+
+        if let Some(name_value) = get_command_attr(&input.attrs) {
+            data.set_name_values(name_value);
+        }
+
+        for field in fields {
+            let field_ident = field.ident.unwrap();
+            let ty = field.ty;
+
+            if let Type::Reference(_) = &ty {
+                panic!("field `{}` cannot be a reference", field_ident);
+            }
+
+            let fn_arg: FnArg = syn::parse_quote! { #field_ident: #ty };
+            let pat_type = match fn_arg {
+                FnArg::Typed(pat_type) => pat_type,
+                _ => unreachable!(),
+            };
+
+            data.add_field(field_ident.to_string(), pat_type, &field.attrs);
+        }
+
+        data
+    }
+
+    fn set_name_values(&mut self, name_value: NameValueAttribute) {
+        for (key, value) in &name_value {
+            match key.as_str() {
+                consts::NAME => {
+                    let name = value
+                        .to_string_literal()
+                        .expect("`name` must be a string literal");
+
+                    self.name = Some(name);
+                }
+                consts::DESCRIPTION => {
+                    let description = value
+                        .to_string_literal()
+                        .expect("`description` must be a string literal");
+
+                    self.description = Some(description);
+                }
+                consts::SUMMARY => {
+                    let summary = value
+                        .to_string_literal()
+                        .expect("`summary` must be a string literal");
+
+                    self.summary = Some(summary);
+                }
+                consts::VERSION => {
+                    assert!(
+                        value.is_integer() || value.is_float() || value.is_string(),
+                        "`version` must be an integer, float or string literal"
+                    );
+                    self.version = Some(value.parse_literal::<String>().unwrap());
+                }
+                consts::USAGE => {
+                    let usage = value
+                        .to_string_literal()
+                        .expect("`usage` must be a string literal");
+
+                    self.usage = Some(usage);
+                }
+                consts::HELP => {
+                    let help = value
+                        .to_string_literal()
+                        .expect("`help` must be a string literal");
+
+                    self.help = Some(help);
+                }
+                consts::BEFORE_HELP => {
+                    let before_help = value
+                        .to_string_literal()
+                        .expect("`before_help` must be a string literal");
+
+                    self.before_help = Some(before_help);
+                }
+                consts::AFTER_HELP => {
+                    let after_help = value
+                        .to_string_literal()
+                        .expect("`after_help` must be a string literal");
+
+                    self.after_help = Some(after_help);
+                }
+                consts::RENAME_ALL => {
+                    let rule = value
+                        .to_string_literal()
+                        .expect("`rename_all` must be a string literal")
+                        .parse::<RenameRule>()
+                        .unwrap_or_else(|e| panic!("{}", e));
+
+                    self.rename_all = Some(rule);
+                }
+                _ => panic!("invalid `{}` key: `{}`", consts::COMMAND_ATTR, key),
+            }
+        }
+    }
+
+    fn add_field(&mut self, field_name: String, pat_type: PatType, attrs: &[Attribute]) {
+        let (attribute, mut name_value) = match attrs
+            .iter()
+            .find(|att| att.path.is_ident(consts::COMMAND_ATTR))
+        {
+            Some(att) => {
+                let attribute = MacroAttribute::new(att.clone());
+                let name_value = to_name_values(&attribute);
+                (Some(attribute), Some(name_value))
+            }
+            None => (None, None),
+        };
+
+        // `positional` only marks the field as an argument, the rest of the keys are from `arg`
+        let is_option = match name_value.as_ref().and_then(|n| n.get(consts::POSITIONAL)) {
+            Some(value) => !value
+                .to_bool_literal()
+                .expect("`positional` must be a bool literal"),
+            None => true,
+        };
+
+        if let Some(attr) = name_value.take() {
+            let path = if is_option {
+                consts::OPTION
+            } else {
+                consts::ARG
+            };
+            let args = attr
+                .into_iter()
+                .filter(|(name, _)| name != consts::POSITIONAL)
+                .map(|(name, value)| NameValue { name, value })
+                .collect();
+
+            name_value = Some(NameValueAttribute::from_args(
+                path.to_owned(),
+                AttrStyle::Outer,
+                args,
+            ));
+        }
+
+        let name = name_value
+            .as_ref()
+            .and_then(|n| n.get(consts::NAME))
+            .and_then(|v| v.to_string_literal());
+
+        let fn_arg = FnArgData {
+            arg_name: field_name.clone(),
+            name: name.clone(),
+            pat_type: pat_type.clone(),
+            attribute,
+            name_value,
+            is_option,
+            skip: None,
+        };
+
+        if is_option {
+            let source = if is_option_bool_flag(&fn_arg) {
+                VarSource::OptBool
+            } else {
+                VarSource::Opts(field_name)
+            };
+
+            let mut var = ArgLocalVar::new(pat_type, source, name);
+
+            if let Some(default) = get_tri_state_flag_default(&fn_arg) {
+                var.set_flag_default(default);
+            }
+
+            if let Some(value_names) = fn_arg
+                .name_value
+                .as_ref()
+                .and_then(|attr| attr.get(consts::VALUE_NAMES))
+            {
+                var.set_value_names(value_names.to_string_array().unwrap_or_default());
+            }
+
+            self.vars.push(var);
+            self.options.push(OptionAttrData::from_arg_data(fn_arg));
+        } else {
+            self.vars.push(ArgLocalVar::new(
+                pat_type,
+                VarSource::Args(field_name),
+                name,
+            ));
+            self.args.push(ArgAttrData::from_arg_data(fn_arg));
+        }
+    }
+
+    fn assert_unique_names(&self) {
+        // (name or alias, field)
+        let mut option_names: Vec<(&str, &str)> = Vec::new();
+        for option in &self.options {
+            for name in std::iter::once(option.name()).chain(option.alias()) {
+                if let Some((_, other)) = option_names.iter().find(|(n, _)| *n == name) {
+                    panic!(
+                        "fields `{}` and `{}` are both declared as option `{}` in `{}`",
+                        other, option.arg_name, name, self.ident
+                    );
+                }
+
+                option_names.push((name, &option.arg_name));
+            }
+        }
+
+        for (index, arg) in self.args.iter().enumerate() {
+            if let Some(other) = self.args[..index].iter().find(|a| a.name() == arg.name()) {
+                panic!(
+                    "fields `{}` and `{}` are both declared as arg `{}` in `{}`",
+                    other.arg_name(),
+                    arg.arg_name(),
+                    arg.name(),
+                    self.ident
+                );
+            }
+        }
+    }
+
+    pub fn expand(mut self) -> TokenStream {
+        if let Some(rule) = self.rename_all {
+            self.options.iter_mut().for_each(|o| o.rename(rule));
+            self.args.iter_mut().for_each(|a| a.rename(rule));
+            self.vars.iter_mut().for_each(|v| v.rename(rule));
+        }
+
+        self.assert_unique_names();
+
+        let ident = &self.ident;
+
+        let command = match &self.name {
+            Some(name) => quote! { clapi::Command::new(#name) },
+            None => quote! { clapi::Command::root() },
+        };
+
+        let description = self
+            .description
+            .as_ref()
+            .map(|s| quote! { .description(#s) });
+        let summary = self.summary.as_ref().map(|s| quote! { .summary(#s) });
+        let version = self.version.as_ref().map(|s| quote! { .version(#s) });
+        let usage = self.usage.as_ref().map(|s| quote! { .usage(#s) });
+        let help = self.help.as_ref().map(|s| quote! { .help(#s) });
+        let before_help = self.before_help.as_ref().map(|s| quote! { .before_help(#s) });
+        let after_help = self.after_help.as_ref().map(|s| quote! { .after_help(#s) });
+        let options = self.options.iter().map(|x| {
+            let negation = x.expand_negation().map(|n| quote! { .option(#n) });
+            quote! { .option(#x) #negation }
+        });
+        let args = self.args.iter().map(|x| quote! { .arg(#x) });
+
+        // Each field is initialized in its own block to avoid shadowing `opts` or `args`
+        let fields = self.vars.iter().map(|var| {
+            let field = var.var_name().parse::<TokenStream>().unwrap();
+            quote! { #field: { #var #field } }
+        });
+
+        quote! {
+            impl clapi::FromArgs for #ident {
+                fn command() -> clapi::Command {
+                    #command
+                        #description
+                        #summary
+                        #version
+                        #usage
+                        #help
+                        #before_help
+                        #after_help
+                        #(#args)*
+                        #(#options)*
+                }
+
+                #[allow(unused_variables)]
+                fn from_parse_result(result: &clapi::ParseResult) -> clapi::Result<Self> {
+                    let opts = result.options();
+                    let args = result.args();
+
+                    Ok(#ident {
+                        #(#fields,)*
+                    })
+                }
+            }
+
+            impl #ident {
+                /// Returns the `Command` used to parse this type.
+                pub fn command() -> clapi::Command {
+                    <Self as clapi::FromArgs>::command()
+                }
+
+                /// Parses this type from `std::env::args`.
+                pub fn from_args() -> clapi::Result<Self> {
+                    clapi::try_parse::<Self>()
+                }
+
+                /// Parses this type from the given arguments.
+                pub fn parse_from<I, S>(args: I) -> clapi::Result<Self>
+                where
+                    I: IntoIterator<Item = S>,
+                    S: std::borrow::Borrow<str>,
+                {
+                    clapi::parse_from::<Self, I, S>(args)
+                }
+            }
+        }
+    }
+}
+
+// Returns the name-values of the struct `#[command_attr(...)]`, if any
+fn get_command_attr(attrs: &[Attribute]) -> Option<NameValueAttribute> {
+    attrs
+        .iter()
+        .find(|att| att.path.is_ident(consts::COMMAND_ATTR))
+        .map(|att| to_name_values(&MacroAttribute::new(att.clone())))
+}
+
+// A single `hidden` or `positional` is the same as `hidden=true` or `positional=true`
+fn to_name_values(attribute: &MacroAttribute) -> NameValueAttribute {
+    let meta_items = attribute
+        .iter()
+        .cloned()
+        .map(|meta_item| match meta_item {
+            MetaItem::Path(path) if path == consts::HIDDEN || path == consts::POSITIONAL => {
+                MetaItem::NameValue(NameValue {
+                    name: path,
+                    value: Value::Literal(Lit::Bool(LitBool {
+                        value: true,
+                        span: Span::call_site(),
+                    })),
+                })
+            }
+            meta_item => meta_item,
+        })
+        .collect();
+
+    NameValueAttribute::new(attribute.path(), meta_items, AttrStyle::Outer)
+        .unwrap_or_else(|e| panic!("invalid `{}`: {:?}", consts::COMMAND_ATTR, e))
+}
@@ -6,7 +6,7 @@ extern crate proc_macro;
 use crate::command::CommandAttrData;
 use proc_macro::TokenStream;
 use quote::ToTokens;
-use syn::{AttributeArgs, ItemFn};
+use syn::{AttributeArgs, DeriveInput, ItemFn};
 
 mod ext;
 pub(crate) use ext::*;
@@ -16,6 +16,7 @@ mod utils;
 mod arg;
 mod command;
 mod consts;
+mod derive;
 mod macro_attribute;
 mod option;
 mod query;
@@ -30,7 +31,16 @@ mod var;
 /// - `description`: Description of the command.
 /// - `usage`: Information of the usage of the command.
 /// - `help`: Help information about the command.
+/// - `before_help`: Text shown in the help message before the usage.
+/// - `after_help`: Text shown at the end of the help message, like a license note.
 /// - `version`: Version of the command-line app.
+/// - `example`: A pair of `(command-line, description)` shown in the help message, can be repeated.
+/// - `rename_all`: Rename policy of the options and arguments names derived from the function arguments,
+///   also applies to the subcommands: `"kebab-case"`, `"snake_case"`, `"lowercase"` or `"SCREAMING_SNAKE_CASE"`.
+/// - `env_prefix`: Prefix of the environment variables the options take their value from: `PREFIX_OPTION_NAME`.
+/// - `deny_unused`: Fails to compile if an option or argument is never used in the function body,
+///   also applies to the subcommands, by default false.
+/// - `subcommands`: Paths of free functions marked as `subcommand`, like: `subcommands(files::list, files::sort)`.
 ///
 /// # Example:
 /// ```ignore
@@ -44,6 +54,7 @@ mod var;
 #[cfg(not(nightly))]
 #[proc_macro_attribute]
 pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr: TokenStream = macro_attribute::flatten_tuple_values(attr.into()).into();
     let args = syn::parse_macro_input!(attr as AttributeArgs);
     let func = syn::parse_macro_input!(item as ItemFn);
 
@@ -59,7 +70,15 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - `description`: Description of the command.
 /// - `usage`: Information of the usage of the command.
 /// - `help`: Help information about the command.
+/// - `before_help`: Text shown in the help message before the usage.
+/// - `after_help`: Text shown at the end of the help message, like a license note.
 /// - `version`: Version of the command-line app.
+/// - `example`: A pair of `(command-line, description)` shown in the help message, can be repeated.
+/// - `rename_all`: Rename policy of the options and arguments names derived from the function arguments,
+///   also applies to the subcommands: `"kebab-case"`, `"snake_case"`, `"lowercase"` or `"SCREAMING_SNAKE_CASE"`.
+/// - `env_prefix`: Prefix of the environment variables the options take their value from: `PREFIX_OPTION_NAME`.
+/// - `deny_unused`: Fails to compile if an option or argument is never used in the function body,
+///   also applies to the subcommands, by default false.
 ///
 /// # Example:
 /// ```ignore
@@ -73,6 +92,7 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
 #[cfg(nightly)]
 #[proc_macro_attribute]
 pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr: TokenStream = macro_attribute::flatten_tuple_values(attr.into()).into();
     let args = syn::parse_macro_input!(attr as AttributeArgs);
     let func = syn::parse_macro_input!(item as ItemFn);
     let path = call_site::path();
@@ -83,7 +103,10 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Marks a function as a `subcommand`.
 ///
 /// ## Stable
-/// Only inner functions of a `command` or `subcommand` can be declared as a subcommand.
+/// Inner functions of a `command` or `subcommand` can be declared as a subcommand,
+/// free functions must be listed in the `subcommands(...)` of its parent.
+/// The `rename_all` of the parent don't apply to free functions,
+/// and the global options are used with `#[option(name, from_global)]`.
 ///
 /// ## Nightly
 /// When compiling for `nightly` rust any free function or inner can be marked as a `subcommand`.
@@ -93,7 +116,16 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - `description`: Description of the command.
 /// - `usage`: Information of the usage of the command.
 /// - `help`: Help information about the command.
-/// - `version`: Version of the command-line app.
+/// - `before_help`: Text shown in the help message before the usage.
+/// - `after_help`: Text shown at the end of the help message, like a license note.
+/// - `version`: Version of the subcommand, by default the version of the parent command.
+/// - `hidden`: If the subcommand is hidden for the help but still can be used, `hidden` is the same as `hidden=true`.
+/// - `example`: A pair of `(command-line, description)` shown in the help message, can be repeated.
+/// - `rename_all`: Rename policy of the options and arguments names derived from the function arguments,
+///   also applies to the subcommands: `"kebab-case"`, `"snake_case"`, `"lowercase"` or `"SCREAMING_SNAKE_CASE"`.
+/// - `subcommands`: Paths of free functions marked as `subcommand` (stable only).
+/// - `deny_unused`: Fails to compile if an option or argument is never used in the function body,
+///   by default the value of the parent command.
 ///
 /// # Example:
 /// ```ignore
@@ -107,15 +139,40 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// In stable, a free function is declared in the `subcommands(...)` of the parent:
+/// ```ignore
+/// use clapi::macros::*;
+///
+/// mod tasks {
+///     use clapi::macros::*;
+///
+///     #[subcommand(description="Runs the tests")]
+///     pub fn test(){
+///         println!("This is a test");
+///     }
+/// }
+///
+/// #[command(subcommands(tasks::test))]
+/// fn main(){}
+/// ```
+///
+/// If the function is not marked as `subcommand` fails to compile with:
+/// ``cannot find function `__clapi_subcommand_test` in module `tasks` ``.
 #[proc_macro_attribute]
-#[allow(unreachable_code, unused_mut)]
-pub fn subcommand(_: TokenStream, item: TokenStream) -> TokenStream {
+#[allow(unreachable_code, unused_mut, unused_variables)]
+pub fn subcommand(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut item_fn = syn::parse_macro_input!(item as ItemFn);
 
     #[cfg(not(nightly))]
     {
-        // SAFETY: The `subcommand` attribute is removed by the root `command` when is an inner function.
-        panic!("invalid function: `{}`\nfree function `subcommand`s are only supported in nightly builds", item_fn.sig.ident);
+        // The `subcommand` attribute is removed by the root `command` when is an inner function,
+        // so this is a free function declared in the `subcommands(...)` of its parent
+        let attr: TokenStream = macro_attribute::flatten_tuple_values(attr.into()).into();
+        let args = syn::parse_macro_input!(attr as AttributeArgs);
+        return CommandAttrData::from_subcommand_fn(args, item_fn)
+            .expand_subcommand_builder()
+            .into();
     }
 
     if !command::contains_expressions(&item_fn) {
@@ -150,13 +207,22 @@ pub fn subcommand(_: TokenStream, item: TokenStream) -> TokenStream {
 /// - `max`: Max number of values the option takes.
 /// - `default`: Default value(s) of the option.
 /// - `values`: Valid values of the option.
-/// - `hidden`: If the option is hidden for the help.
+/// - `hidden`: If the option is hidden for the help but still can be used, `hidden` is the same as `hidden=true`.
 /// - `multiple`: If the option allow multiple declarations.
+/// - `counted`: If the option takes no values and can be repeated, the value is the number of times
+///   was passed, like `-v -v -v`. The function argument must be an integer type.
 /// - `flag`: If the option is a bool flag, by default is `true`
 /// - `error`: Error show when the value is invalid.
+/// - `empty`: If the option accepts empty values like `--name=""`, by default true.
+/// - `trim`: If the whitespaces around the values are removed before validate them, by default false.
+/// - `value_names`: Names of each value of the option, the option must take an exact number of values.
 /// - `require_assign`: If the option requires to use `=` to assign the value, by default false,
 /// - `global`: If the option is global, by default false.
 /// - `from_global`: If the option is declared as global in a parent, by default false.
+/// - `env`: Environment variable the option takes its value from, or `false` to ignore the command `env_prefix`.
+/// - `skip`: The function argument is not an option, its value is `clapi::inject::<T>()`
+///   provided with `clapi::provide`, or `Default::default()` with `skip="default"`.
+/// - `allow_unused`: The option is not checked by the command `deny_unused`.
 ///
 /// Function arguments can be declared as the following types:
 /// - Any type that implement `FromStr`.
@@ -164,6 +230,10 @@ pub fn subcommand(_: TokenStream, item: TokenStream) -> TokenStream {
 /// - `&[T]` slices where `T` implements `FromStr`.
 /// - `Option<T>` where `T` implements `FromStr`.
 ///
+/// A `bool` option is a flag, `true` if present and `false` if absent.
+/// An `Option<bool>` option is `None` if absent, or `Some(default)` if a `default` is provided.
+/// An `Option<bool>` option also declares a `--no-<name>` option which sets it to `Some(false)`.
+///
 /// # Example:
 /// ```ignore
 /// use clapi::macros::*;
@@ -199,6 +269,11 @@ pub fn option(_: TokenStream, _: TokenStream) -> TokenStream {
 /// - `default`: Default value(s) of the argument.
 /// - `values`: Valid values of the argument.
 /// - `error`: Error show when the value is invalid.
+/// - `empty`: If the argument accepts empty values like `""`, by default true.
+/// - `trim`: If the whitespaces around the values are removed before validate them, by default false.
+/// - `skip`: The function argument is not an argument, its value is `clapi::inject::<T>()`
+///   provided with `clapi::provide`, or `Default::default()` with `skip="default"`.
+/// - `allow_unused`: The argument is not checked by the command `deny_unused`.
 ///
 /// Function arguments can be declared as the following types:
 /// - Any type that implement `FromStr`.
@@ -246,6 +321,58 @@ pub fn command_usage(_: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
+/// Implements `clapi::FromArgs` for a struct whose fields are the command options,
+/// which is parsed with `clapi::parse::<T>()`, also derives the `parse_from` and `from_args` constructors.
+///
+/// Each field is a command `option` named after the field,
+/// use `#[command_attr(positional)]` to declare the field as an argument instead.
+///
+/// # Struct options:
+/// - `name`: Name of the command, by default is the `executable` name.
+/// - `description`: Description of the command.
+/// - `usage`: Information of the usage of the command.
+/// - `help`: Help information about the command.
+/// - `before_help`: Text shown in the help message before the usage.
+/// - `after_help`: Text shown at the end of the help message, like a license note.
+/// - `version`: Version of the command-line app.
+/// - `rename_all`: Rename policy of the options and arguments names derived from the fields.
+///
+/// # Field options:
+/// Any key of the `option` attribute, or of the `arg` attribute if is `positional`.
+///
+/// Fields are declared with the same types of the function arguments of a `command`,
+/// a `bool` field is a flag, an `Option<T>` field is optional and a `Vec<T>` field takes many values.
+///
+/// # Example:
+/// ```ignore
+/// use clapi::macros::*;
+///
+/// #[derive(FromCommand)]
+/// #[command_attr(description="Repeats a message")]
+/// struct Repeat {
+///     #[command_attr(alias="t", default=1)]
+///     times: u32,
+///     #[command_attr(alias="u", description="Display the message in uppercase")]
+///     upper_case: bool,
+///     #[command_attr(positional)]
+///     message: Vec<String>,
+/// }
+///
+/// fn main() {
+///     let repeat = clapi::parse::<Repeat>();
+///     for _ in 0..repeat.times {
+///         println!("{}", repeat.message.join(" "));
+///     }
+/// }
+/// ```
+#[proc_macro_derive(FromCommand, attributes(command_attr))]
+pub fn from_command(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as DeriveInput);
+    derive::FromCommandData::from_derive_input(input)
+        .expand()
+        .into()
+}
+
 #[cfg(nightly)]
 mod call_site {
     use proc_macro::Span;
@@ -3,6 +3,8 @@ use std::fmt::{Display, Write, Formatter};
 use std::iter::Peekable;
 use std::ops::Index;
 use std::slice::SliceIndex;
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use quote::quote;
 use syn::{
     AttrStyle, Attribute, AttributeArgs, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path,
 };
@@ -322,13 +324,55 @@ fn get_attribute_args(attr: &Attribute) -> syn::Result<AttributeArgs> {
     let mut token_tree = attr.tokens.clone().into_iter();
     if let Some(proc_macro2::TokenTree::Group(group)) = token_tree.next() {
         use syn::parse_macro_input::ParseMacroInput;
-        let tokens = group.stream();
+        let tokens = flatten_tuple_values(group.stream());
         syn::parse::Parser::parse2(AttributeArgs::parse, tokens)
     } else {
         Ok(AttributeArgs::new())
     }
 }
 
+/// Converts the tuple values of the given attribute arguments into arrays,
+/// the values of repeated keys with tuple values are joined into a single array.
+///
+/// `key=("a", "b"), key=("c", "d")` is converted into `key="a", "b", "c", "d"`.
+pub fn flatten_tuple_values(tokens: TokenStream) -> TokenStream {
+    // Splits the arguments by commas
+    let mut args: Vec<Vec<TokenTree>> = vec![Vec::new()];
+    for token in tokens {
+        match token {
+            TokenTree::Punct(ref p) if p.as_char() == ',' => args.push(Vec::new()),
+            _ => args.last_mut().unwrap().push(token),
+        }
+    }
+
+    // The values of each tuple key and the position of its first occurrence
+    let mut tuples: Vec<(Ident, usize, Vec<TokenStream>)> = Vec::new();
+    let mut result: Vec<TokenStream> = Vec::new();
+
+    for arg in args.into_iter().filter(|arg| !arg.is_empty()) {
+        match arg.as_slice() {
+            [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Group(group)]
+                if eq.as_char() == '=' && group.delimiter() == Delimiter::Parenthesis =>
+            {
+                match tuples.iter_mut().find(|(k, _, _)| k == key) {
+                    Some((_, _, values)) => values.push(group.stream()),
+                    None => {
+                        tuples.push((key.clone(), result.len(), vec![group.stream()]));
+                        result.push(TokenStream::new());
+                    }
+                }
+            }
+            _ => result.push(arg.into_iter().collect()),
+        }
+    }
+
+    for (key, index, values) in tuples {
+        result[index] = quote! { #key = #(#values),* };
+    }
+
+    quote! { #(#result),* }
+}
+
 fn join_path_to_string(path: &Path) -> String {
     path.segments
         .iter()
@@ -488,4 +532,15 @@ mod tests {
             )]"
         )
     }
+
+    #[test]
+    fn flatten_tuple_values_test() {
+        let tokens = quote! { name="app", pair=("a", "b"), version=1.0, pair=("c", "d"), nested(x=1) };
+        let result = flatten_tuple_values(tokens);
+
+        assert_eq!(
+            result.to_string(),
+            quote! { name="app", pair="a", "b", "c", "d", version=1.0, nested(x=1) }.to_string()
+        );
+    }
 }
@@ -279,6 +279,19 @@ impl Value {
         None
     }
 
+    pub fn to_string_array(&self) -> Option<Vec<String>> {
+        match self {
+            Value::Literal(_) => self.to_string_literal().map(|s| vec![s]),
+            Value::Array(array) => array
+                .iter()
+                .map(|lit| match lit {
+                    Lit::Str(x) => Some(x.value()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
     pub fn to_char_literal(&self) -> Option<char> {
         if let Value::Literal(lit) = self {
             return match lit {
@@ -1,10 +1,14 @@
 use crate::arg::ArgAttrData;
-use crate::command::{is_option_bool_flag, FnArgData};
+use crate::command::{
+    get_tri_state_flag_default, is_option_bool_flag, is_optional_bool, FnArgData,
+};
 use crate::consts;
 use crate::macro_attribute::{MacroAttribute, Value};
-use proc_macro2::TokenStream;
+use crate::utils::RenameRule;
+use proc_macro2::{Span, TokenStream};
 use quote::*;
 use std::cell::Cell;
+use syn::spanned::Spanned;
 use syn::Lit;
 
 /// Tokens for an `option` attribute.
@@ -16,6 +20,7 @@ use syn::Lit;
 ///     description="Average",
 ///     hidden = false,
 ///     multiple = false,
+///     counted = false,
 ///     global = false,
 ///     flag=false,
 ///     min=1,
@@ -37,8 +42,15 @@ pub struct OptionAttrData {
     is_global: Option<bool>,
     pub(crate) from_global: Cell<Option<bool>>,
     allow_multiple: Option<bool>,
+    is_counted: bool,
     requires_assign: Option<bool>,
+    value_names: Vec<String>,
     is_flag: bool,
+    is_negatable: bool,
+    has_explicit_name: bool,
+    env: Option<String>,
+    use_env: Option<bool>,
+    span: Span,
 }
 
 impl OptionAttrData {
@@ -58,15 +70,23 @@ impl OptionAttrData {
             arg: None,
             is_hidden: None,
             allow_multiple: None,
+            is_counted: false,
             requires_assign: None,
             is_global: None,
             from_global: Cell::new(None),
+            value_names: vec![],
             is_flag: false,
+            is_negatable: false,
+            has_explicit_name: false,
+            env: None,
+            use_env: None,
+            span: Span::call_site(),
         }
     }
 
     pub fn from_arg_data(arg_data: FnArgData) -> Self {
         let mut option = OptionAttrData::new(arg_data.arg_name.clone());
+        option.span = arg_data.pat_type.pat.span();
         let mut arg = ArgAttrData::from_arg_data(arg_data.clone());
 
         if let Some(att) = &arg_data.name_value {
@@ -78,6 +98,7 @@ impl OptionAttrData {
                             .expect("option `name` must be a string literal");
 
                         option.set_name(name);
+                        option.has_explicit_name = true;
                     }
                     consts::ARG => {
                         let arg_name = value
@@ -128,6 +149,13 @@ impl OptionAttrData {
 
                         option.set_multiple(allow_multiple);
                     }
+                    consts::COUNTED => {
+                        let counted = value
+                            .to_bool_literal()
+                            .expect("option `counted` must be a bool literal");
+
+                        option.set_counted(counted);
+                    }
                     consts::REQUIRES_ASSIGN => {
                         let requires_assign = value
                             .to_bool_literal()
@@ -142,6 +170,20 @@ impl OptionAttrData {
 
                         arg.set_validation_error(error);
                     }
+                    consts::EMPTY => {
+                        let allow_empty = value
+                            .to_bool_literal()
+                            .expect("option `empty` must be a bool literal");
+
+                        arg.set_allow_empty_values(allow_empty);
+                    }
+                    consts::TRIM => {
+                        let trim = value
+                            .to_bool_literal()
+                            .expect("option `trim` must be a bool literal");
+
+                        arg.set_trim_values(trim);
+                    }
                     consts::DEFAULT => match value {
                         Value::Literal(lit) => arg.set_default_values(vec![lit.clone()]),
                         Value::Array(array) => arg.set_default_values(array.clone() as Vec<Lit>),
@@ -150,6 +192,20 @@ impl OptionAttrData {
                         Value::Literal(lit) => arg.set_valid_values(vec![lit.clone()]),
                         Value::Array(array) => arg.set_valid_values(array.clone() as Vec<Lit>),
                     },
+                    consts::VALUE_NAMES => {
+                        let value_names = value
+                            .to_string_array()
+                            .expect("option `value_names` must be an array of string literals");
+
+                        option.set_value_names(value_names);
+                    }
+                    consts::ALLOW_UNUSED => {
+                        // Just type checking
+                        // This is used by `command.rs#is_allow_unused`
+                        value
+                            .to_bool_literal()
+                            .expect("option `allow_unused` must be a bool literal");
+                    }
                     consts::FLAG => {
                         // Just type checking
                         // This is used by `command.rs#is_option_bool_flag`
@@ -164,6 +220,17 @@ impl OptionAttrData {
 
                         option.set_global(global);
                     }
+                    consts::ENV => {
+                        if value.is_bool() {
+                            option.set_use_env(value.to_bool_literal().unwrap());
+                        } else {
+                            let env = value
+                                .to_string_literal()
+                                .expect("option `env` must be a string or bool literal");
+
+                            option.set_env(env);
+                        }
+                    }
                     consts::FROM_GLOBAL => {
                         let from_global = value
                             .to_bool_literal()
@@ -185,15 +252,27 @@ impl OptionAttrData {
             // --flag=false     (false)
             // --flag           (true)
             // [no option]      (false)
+            //
+            // For `Option<bool>` the absent option is `None` or the `default` if any,
+            // which is handled by `ArgLocalVar`, otherwise `--flag` would take the default value.
+            // An `Option<bool>` flag also declares a `--no-flag` option which sets it to `false`.
 
             option.is_flag = true;
+            option.is_negatable = is_optional_bool(&arg_data.pat_type.ty);
             arg.set_min(0);
             arg.set_max(1); //#[option]
+
+            if get_tri_state_flag_default(&arg_data).is_some() {
+                arg.clear_default_values();
+            }
         }
 
-        // Sets the attribute and the args
+        // Sets the attribute and the args, a counted option takes no args
         option.attribute = arg_data.attribute;
-        option.set_args(arg);
+        if !option.is_counted {
+            option.set_args(arg);
+        }
+
         option
     }
 
@@ -201,6 +280,15 @@ impl OptionAttrData {
         self.name.as_str()
     }
 
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    // The span of the function argument
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
     pub fn is_global(&self) -> bool {
         self.is_global.unwrap_or(false)
     }
@@ -209,6 +297,10 @@ impl OptionAttrData {
         self.from_global.get().unwrap_or(false)
     }
 
+    pub fn is_counted(&self) -> bool {
+        self.is_counted
+    }
+
     pub fn set_name(&mut self, name: String) {
         assert!(!name.trim().is_empty(), "option `name` cannot be empty");
         assert!(
@@ -219,6 +311,17 @@ impl OptionAttrData {
         self.name = name;
     }
 
+    // Renames this option and its argument if the names were not set explicitly
+    pub fn rename(&mut self, rule: RenameRule) {
+        if !self.has_explicit_name {
+            self.name = rule.apply(&self.arg_name);
+        }
+
+        if let Some(arg) = &mut self.arg {
+            arg.rename(rule);
+        }
+    }
+
     pub fn set_alias(&mut self, alias: String) {
         self.alias = Some(alias);
     }
@@ -239,6 +342,10 @@ impl OptionAttrData {
         self.allow_multiple = Some(allow_multiple);
     }
 
+    pub fn set_counted(&mut self, counted: bool) {
+        self.is_counted = counted;
+    }
+
     pub fn set_requires_assign(&mut self, requires_assign: bool) {
         self.requires_assign = Some(requires_assign);
     }
@@ -247,6 +354,33 @@ impl OptionAttrData {
         self.is_global = Some(global);
     }
 
+    pub fn set_value_names(&mut self, value_names: Vec<String>) {
+        assert!(!value_names.is_empty(), "option `value_names` is empty");
+        for (index, name) in value_names.iter().enumerate() {
+            assert!(!name.trim().is_empty(), "option `value_names` cannot be empty");
+            assert!(
+                name.trim().chars().all(|c| !c.is_whitespace()),
+                "option `value_names` cannot contains whitespaces"
+            );
+            assert!(
+                !value_names[..index].contains(name),
+                "duplicated option value name: `{}`",
+                name
+            );
+        }
+
+        self.value_names = value_names;
+    }
+
+    pub fn set_env(&mut self, env: String) {
+        assert!(!env.trim().is_empty(), "option `env` cannot be empty");
+        self.env = Some(env);
+    }
+
+    pub fn set_use_env(&mut self, use_env: bool) {
+        self.use_env = Some(use_env);
+    }
+
     pub fn set_from_global(&self, from_global: bool) {
         self.from_global.set(Some(from_global));
     }
@@ -280,8 +414,15 @@ impl OptionAttrData {
             _ => quote! {},
         };
 
-        // Option argument
-        let arg = self.arg.as_ref().map(|arg| quote! { .arg(#arg) });
+        // Option argument, or one argument per value name
+        let arg = self.arg.as_ref().map(|arg| {
+            if self.value_names.is_empty() {
+                quote! { .arg(#arg) }
+            } else {
+                let args = arg.expand_value_names(&self.value_names);
+                quote! { #(.arg(#args))* }
+            }
+        });
 
         // Option is hidden
         let is_hidden = self
@@ -295,6 +436,13 @@ impl OptionAttrData {
             .as_ref()
             .map(|value| quote! { .multiple(#value) });
 
+        // Option is counted
+        let is_counted = if self.is_counted {
+            quote! { .counted(true) }
+        } else {
+            quote! {}
+        };
+
         // Option requires assign
         let requires_assign = self
             .requires_assign
@@ -306,6 +454,14 @@ impl OptionAttrData {
             .as_ref()
             .map(|value| quote! { .global(#value) });
 
+        // Option environment variable
+        let env = self.env.as_ref().map(|s| quote! { .env(#s) });
+
+        let use_env = self
+            .use_env
+            .as_ref()
+            .map(|value| quote! { .use_env(#value) });
+
         let name = quote_expr!(self.name.as_str().trim_start_matches("r#"));
 
         quote! {
@@ -315,11 +471,44 @@ impl OptionAttrData {
             #required
             #is_hidden
             #allow_multiple
+            #is_counted
             #requires_assign
             #is_global
+            #env
+            #use_env
             #arg
         }
     }
+
+    /// Returns the `--no-<name>` option of an `Option<bool>` flag, which sets the flag to `false`.
+    pub fn expand_negation(&self) -> Option<TokenStream> {
+        if !self.is_negatable || self.from_global.get() == Some(true) {
+            return None;
+        }
+
+        let name = self.name.as_str().trim_start_matches("r#");
+        let negation_name = quote_expr!(format!("no-{}", name));
+        let description = quote_expr!(format!("Sets `{}` to false", name));
+        let option_name = quote_expr!(name);
+
+        let is_hidden = self
+            .is_hidden
+            .as_ref()
+            .map(|value| quote! { .hidden(#value) });
+
+        let is_global = self
+            .is_global
+            .as_ref()
+            .map(|value| quote! { .global(#value) });
+
+        Some(quote! {
+            clapi::CommandOption::new(#negation_name)
+            .description(#description)
+            .conflicts_with(#option_name)
+            #is_hidden
+            #is_global
+        })
+    }
 }
 
 impl ToTokens for OptionAttrData {
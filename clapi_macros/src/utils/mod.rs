@@ -1,6 +1,9 @@
 mod name_path;
 pub use name_path::NamePath;
 
+mod rename_rule;
+pub use rename_rule::RenameRule;
+
 use syn::{ItemFn, Attribute};
 use syn::parse_quote::ParseQuote;
 use quote::ToTokens;
@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+/// A policy to rename the options and arguments derived from the function arguments.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RenameRule {
+    /// `dry_run` to `dry-run`.
+    Kebab,
+    /// `dry_run` to `dry_run`.
+    Snake,
+    /// `dry_run` to `dryrun`.
+    Lower,
+    /// `dry_run` to `DRY_RUN`.
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    /// Applies this rule to the given function argument name.
+    pub fn apply(&self, name: &str) -> String {
+        let words = name
+            .trim_start_matches("r#")
+            .split('_')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_lowercase());
+
+        match self {
+            RenameRule::Kebab => words.collect::<Vec<String>>().join("-"),
+            RenameRule::Snake => words.collect::<Vec<String>>().join("_"),
+            RenameRule::Lower => words.collect::<String>(),
+            RenameRule::ScreamingSnake => words.collect::<Vec<String>>().join("_").to_uppercase(),
+        }
+    }
+}
+
+impl FromStr for RenameRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "kebab-case" => Ok(RenameRule::Kebab),
+            "snake_case" => Ok(RenameRule::Snake),
+            "lowercase" => Ok(RenameRule::Lower),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            _ => Err(format!(
+                "invalid `rename_all` value: `{}`, expected \"kebab-case\", \"snake_case\", \"lowercase\" or \"SCREAMING_SNAKE_CASE\"",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_rule_test() {
+        assert_eq!(RenameRule::Kebab.apply("dry_run"), "dry-run");
+        assert_eq!(RenameRule::Snake.apply("dry_run"), "dry_run");
+        assert_eq!(RenameRule::Lower.apply("dry_run"), "dryrun");
+        assert_eq!(RenameRule::ScreamingSnake.apply("dry_run"), "DRY_RUN");
+        assert_eq!(RenameRule::Kebab.apply("r#type"), "type");
+        assert_eq!(RenameRule::Kebab.apply("max_line_count"), "max-line-count");
+    }
+
+    #[test]
+    fn rename_rule_from_str_test() {
+        assert_eq!("kebab-case".parse(), Ok(RenameRule::Kebab));
+        assert_eq!("SCREAMING_SNAKE_CASE".parse(), Ok(RenameRule::ScreamingSnake));
+        assert!("camelCase".parse::<RenameRule>().is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::RenameRule;
 use crate::TypeExt;
 use proc_macro2::TokenStream;
 use quote::*;
@@ -12,6 +13,8 @@ pub struct ArgLocalVar {
     is_mut: bool,
     source: VarSource,
     ty: ArgumentType,
+    value_names: Vec<String>,
+    flag_default: Option<bool>,
 }
 
 impl ArgLocalVar {
@@ -27,6 +30,19 @@ impl ArgLocalVar {
         &self.ty
     }
 
+    pub fn set_value_names(&mut self, value_names: Vec<String>) {
+        self.value_names = value_names;
+    }
+
+    pub fn set_flag_default(&mut self, default: bool) {
+        self.flag_default = Some(default);
+    }
+
+    // Takes the value from the number of times the option was passed
+    pub fn set_counted(&mut self) {
+        self.source = VarSource::OptCount;
+    }
+
     pub fn expand(&self) -> TokenStream {
         let var_name = self.var_name.as_str().parse::<TokenStream>().unwrap();
         let normalized_var_name = self
@@ -47,6 +63,34 @@ impl ArgLocalVar {
             VarSource::Opts(arg_name) => {
                 self.get_opts_source(self.name.as_deref().unwrap_or(arg_name))
             }
+            VarSource::Skip(strategy, ty) => match strategy {
+                SkipStrategy::Inject => quote! { clapi::inject::<#ty>()? },
+                SkipStrategy::Default => quote! { <#ty as std::default::Default>::default() },
+            },
+            VarSource::OptCount => {
+                // Handles a counted option, the value is the number of times was passed
+                // like `-v -v -v` and 0 if absent.
+                let option_name = quote_expr!(normalized_var_name);
+                let ty = match &self.ty {
+                    ArgumentType::Type(ty) => ty,
+                    _ => panic!(
+                        "counted option `{}` must be an integer type",
+                        normalized_var_name
+                    ),
+                };
+
+                quote! {
+                    {
+                        let count = opts.get_count(#option_name);
+                        <#ty as std::convert::TryFrom<usize>>::try_from(count).map_err(|_| {
+                            clapi::Error::new(
+                                clapi::ErrorKind::InvalidArgumentCount,
+                                format!("'{}' was passed too many times: {}", #option_name, count)
+                            )
+                        })?
+                    }
+                }
+            }
             VarSource::OptBool => {
                 // Handles an option `bool` flag, which returns `true`
                 // if the option exists or if the passed value is `true` otherwise `false`.
@@ -60,15 +104,36 @@ impl ArgLocalVar {
                 // - `true` : If passing `--enable=true`
                 // - `false`: If passing `--enable=false`
                 // - `false`: If passing nothing
+                //
+                // If the parameter is `Option<bool>` the values are wrapped in `Some`
+                // and is `None` if passing nothing, or `Some(default)` if there is a `default`,
+                // and `Some(false)` if passing `--no-enable`.
                 let option_name = quote_expr!(normalized_var_name);
+                let (absent, present, value) = if self.ty.is_option() {
+                    let absent = match self.flag_default {
+                        Some(default) => quote! { Some(#default) },
+                        None => quote! { None },
+                    };
+
+                    // `--no-enable` is declared with the option and conflicts with it
+                    let negation_name = quote_expr!(format!("no-{}", normalized_var_name));
+                    let absent = quote! {
+                        if opts.contains(#negation_name) { Some(false) } else { #absent }
+                    };
+
+                    (absent, quote! { Some(true) }, quote! { Some(v) })
+                } else {
+                    (quote! { false }, quote! { true }, quote! { v })
+                };
+
                 quote! {
                     match opts.get(#option_name) {
-                        None => false,
+                        None => #absent,
                         Some(option) => {
                             let arg = option.get_arg().unwrap();
                             match arg.convert::<bool>() {
-                                Ok(v) => v,
-                                Err(e) if e.kind() == &clapi::ErrorKind::InvalidArgumentCount => true,
+                                Ok(v) => #value,
+                                Err(e) if e.kind() == &clapi::ErrorKind::InvalidArgumentCount => #present,
                                 Err(e) => return Err(e)
                             }
                         },
@@ -78,22 +143,18 @@ impl ArgLocalVar {
         };
 
         match self.ty {
-            ArgumentType::Slice(_) => {
-                let concat = format!("tmp_{}", normalized_var_name);
+            ArgumentType::Slice(ref slice) => {
+                // The slice borrows from a named `Vec` that lives until the end of the handler
+                let concat = format!("tmp_{}", self.var_name.trim_start_matches("r#"));
                 let temp = syn::Ident::new(&concat, var_name.span());
-                let as_slice = match &self.ty {
-                    ArgumentType::Slice(slice) => {
-                        if slice.mutability {
-                            quote! { .as_mut_slice() }
-                        } else {
-                            quote! { .as_slice() }
-                        }
-                    }
-                    _ => unreachable!(),
+                let (temp_mut, as_slice) = if slice.mutability {
+                    (quote! { mut }, quote! { .as_mut_slice() })
+                } else {
+                    (quote! {}, quote! { .as_slice() })
                 };
 
                 quote! {
-                    let #is_mut #temp = #source ;
+                    let #temp_mut #temp = #source ;
                     let #is_mut #var_name = #temp #as_slice ;
                 }
             }
@@ -105,8 +166,25 @@ impl ArgLocalVar {
         }
     }
 
+    // Renames the option or argument of this variable if the name was not set explicitly
+    pub fn rename(&mut self, rule: RenameRule) {
+        if self.name.is_none() {
+            self.name = Some(rule.apply(&self.var_name));
+        }
+    }
+
     fn get_opts_source(&self, arg_name: &str) -> TokenStream {
-        let option_name = quote_expr!(self.var_name.as_str().trim_start_matches("r#"));
+        let option_name = quote_expr!(self
+            .name
+            .as_deref()
+            .unwrap_or(&self.var_name)
+            .trim_start_matches("r#"));
+
+        // Each value of the option is declared as a separated argument,
+        // so we zip them back together
+        if !self.value_names.is_empty() {
+            return self.get_value_names_source(option_name);
+        }
         let arg_name = quote_expr!(arg_name.trim_start_matches("r#"));
 
         match &self.ty {
@@ -155,6 +233,25 @@ impl ArgLocalVar {
         }
     }
 
+    fn get_value_names_source(&self, option_name: TokenStream) -> TokenStream {
+        let ty = self.ty.get_type();
+        let values = self.value_names.iter().map(|name| {
+            quote! { option_args.get(#name).unwrap().convert::<#ty>()? }
+        });
+
+        let collection = match &self.ty {
+            ArgumentType::Array(_) => quote! { [#(#values),*] },
+            _ => quote! { vec![#(#values),*] },
+        };
+
+        quote! {
+            {
+                let option_args = opts.get(#option_name).unwrap().get_args();
+                #collection
+            }
+        }
+    }
+
     fn get_args_source(&self, arg_name: &str) -> TokenStream {
         let normalized_name = arg_name.trim_start_matches("r#");
 
@@ -221,6 +318,19 @@ pub enum VarSource {
     Opts(String),
     /// The value from an option flag
     OptBool,
+    /// The number of times an option was passed
+    OptCount,
+    /// A value that is not from the command-line, of the given type.
+    Skip(SkipStrategy, Box<Type>),
+}
+
+/// How the value of a function argument marked with `skip` is obtained.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SkipStrategy {
+    /// From `clapi::inject::<T>()`.
+    Inject,
+    /// From `Default::default()`.
+    Default,
 }
 
 #[derive(Debug, Clone)]
@@ -294,11 +404,11 @@ impl Display for ArgumentType {
 }
 
 fn new_arg_local_var(pat_type: PatType, source: VarSource, name: Option<String>) -> ArgLocalVar {
-    let var_name = pat_type.pat.to_token_stream().to_string();
     let ty = get_argument_type(&pat_type);
-    let is_mut = match pat_type.pat.as_ref() {
-        Pat::Ident(ident) => ident.mutability.is_some(),
-        _ => false,
+    let (var_name, is_mut) = match pat_type.pat.as_ref() {
+        // The name without `mut`, which is declared separately
+        Pat::Ident(ident) => (ident.ident.to_string(), ident.mutability.is_some()),
+        pat => (pat.to_token_stream().to_string(), false),
     };
 
     ArgLocalVar {
@@ -307,6 +417,8 @@ fn new_arg_local_var(pat_type: PatType, source: VarSource, name: Option<String>)
         is_mut,
         source,
         ty,
+        value_names: vec![],
+        flag_default: None,
     }
 }
 
@@ -7,6 +7,7 @@ use std::num::NonZeroUsize;
     alias="t",
     description="number of times to repeat",
     default=1,
+    env="ECHO_TIMES",
     error="expected number greater than 0"
 )]
 fn main(times: NonZeroUsize, values: Vec<String>) {
@@ -0,0 +1,54 @@
+use clapi::generators::{settings_commands, SettingDef, SettingType, SettingsBackend};
+use clapi::{CommandLine, Error, ErrorKind};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Stores the settings in a json file
+struct JsonSettings {
+    path: PathBuf,
+    values: HashMap<String, String>,
+}
+
+impl JsonSettings {
+    fn open(path: PathBuf) -> Self {
+        let values = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+
+        JsonSettings { path, values }
+    }
+}
+
+impl SettingsBackend for JsonSettings {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> clapi::Result<()> {
+        self.values.insert(key.to_owned(), value.to_owned());
+        let json = serde_json::to_string_pretty(&self.values)
+            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+
+        std::fs::write(&self.path, json).map_err(|e| Error::new(ErrorKind::Other, e))
+    }
+}
+
+fn main() -> clapi::Result<()> {
+    let settings = JsonSettings::open(std::env::temp_dir().join("clapi_settings.json"));
+    let command = settings_commands(
+        vec![
+            SettingDef::new("editor", SettingType::String).description("the text editor"),
+            SettingDef::new("tab-size", SettingType::Integer).description("number of spaces of a tab"),
+            SettingDef::new("autosave", SettingType::Bool).description("save the files on close"),
+            SettingDef::new("zoom", SettingType::Float).description("zoom of the editor"),
+        ],
+        settings,
+    );
+
+    CommandLine::new(command)
+        .use_default_help()
+        .use_default_suggestions()
+        .run()
+        .map_err(|e| e.exit())
+}
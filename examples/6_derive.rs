@@ -0,0 +1,17 @@
+use clapi::macros::*;
+
+#[derive(FromCommand)]
+#[command_attr(description = "outputs the given values on the console", version = 1.0)]
+struct Echo {
+    #[command_attr(alias = "t", default = 1, description = "number of times to repeat")]
+    times: usize,
+    #[command_attr(positional)]
+    values: Vec<String>,
+}
+
+fn main() {
+    let echo = clapi::parse::<Echo>();
+    for _ in 0..echo.times {
+        println!("{}", echo.values.join(" "));
+    }
+}
@@ -0,0 +1,60 @@
+use clapi::{Command, CommandLine, Error, ErrorKind};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Loads the command tree declared in `cli.json`, the handlers are set using the path of the commands
+fn load_command() -> Command {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("cli.json");
+    let json = std::fs::read_to_string(&path).expect("unable to read `cli.json`");
+    serde_json::from_str::<Command>(&json).expect("invalid `cli.json`")
+}
+
+// The values of the store are saved in a json file
+fn store_path() -> PathBuf {
+    std::env::temp_dir().join("clapi_kv_store.json")
+}
+
+fn read_store() -> HashMap<String, String> {
+    std::fs::read_to_string(store_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn main() -> clapi::Result<()> {
+    let mut command = load_command();
+
+    command
+        .find_subcommand_mut("data.get")
+        .unwrap()
+        .set_handler(|_, args| {
+            let key = args.get("key").unwrap().convert::<String>()?;
+            match read_store().get(&key) {
+                Some(value) => println!("{}", value),
+                None => println!("`{}` is not set", key),
+            }
+            Ok(())
+        });
+
+    command
+        .find_subcommand_mut("data.set")
+        .unwrap()
+        .set_handler(|opts, args| {
+            let key = args.get("key").unwrap().convert::<String>()?;
+            let value = args.get("value").unwrap().convert::<String>()?;
+            if opts.contains("verbose") {
+                println!("{} = {}", key, value);
+            }
+
+            let mut store = read_store();
+            store.insert(key, value);
+            let json = serde_json::to_string_pretty(&store).unwrap();
+            std::fs::write(store_path(), json).map_err(|e| Error::new(ErrorKind::Other, e))
+        });
+
+    CommandLine::new(command)
+        .use_default_help()
+        .use_default_suggestions()
+        .run()
+        .map_err(|e| e.exit())
+}
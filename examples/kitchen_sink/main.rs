@@ -0,0 +1,234 @@
+use clapi::validator::validate_type;
+use clapi::{Argument, Command, CommandLine, CommandOption};
+
+// A fake package manager that wires together most of the features of the library,
+// `tests/kitchen_sink.rs` runs it through a table of invocations.
+fn main() -> clapi::Result<()> {
+    CommandLine::new(app())
+        .use_default_help()
+        .use_default_suggestions()
+        .run()
+        .map_err(|e| e.exit())
+}
+
+fn app() -> Command {
+    Command::new("kitchen_sink")
+        .version("1.0.0")
+        .description("A package manager that does nothing")
+        .option(
+            CommandOption::new("verbose")
+                .description("Shows more information")
+                .global(true),
+        )
+        .option(
+            CommandOption::new("color")
+                .description("When to use colors")
+                .global(true)
+                .arg(
+                    Argument::with_name("when")
+                        .valid_values(["auto", "always", "never"])
+                        .default("auto"),
+                ),
+        )
+        .subcommand(add())
+        .subcommand(remove())
+        .subcommand(sum())
+        .subcommand(run())
+        .subcommand(exec())
+        .subcommand(cat())
+        .subcommand(publish())
+        .subcommand(config())
+}
+
+fn add() -> Command {
+    Command::new("add")
+        .description("Adds a package")
+        .arg(Argument::with_name("name").description("name of the package"))
+        .option(
+            CommandOption::new("tag")
+                .description("Version of the package")
+                .arg(
+                    Argument::with_name("version")
+                        .validator(|s: &str| {
+                            if s == "latest" || s.split('.').all(|n| n.parse::<u32>().is_ok()) {
+                                Ok(())
+                            } else {
+                                Err(format!("invalid version `{}`", s))
+                            }
+                        })
+                        .default("latest"),
+                ),
+        )
+        .option(CommandOption::new("dev").description("Adds the package as a dev dependency"))
+        .handler(|opts, args| {
+            let name = args.convert::<String>("name")?;
+            let version = opts.convert::<String>("tag")?;
+            let dev = if opts.contains("dev") { " (dev)" } else { "" };
+
+            if opts.contains("verbose") {
+                println!("color: {}", opts.convert::<String>("color")?);
+            }
+
+            println!("added {}@{}{}", name, version, dev);
+            Ok(())
+        })
+}
+
+fn remove() -> Command {
+    Command::new("remove")
+        .description("Removes the packages")
+        .arg(Argument::one_or_more("names"))
+        .handler(|_, args| {
+            for name in args.convert_all::<String>("names")? {
+                println!("removed {}", name);
+            }
+            Ok(())
+        })
+}
+
+fn sum() -> Command {
+    Command::new("sum")
+        .description("Sums the numbers")
+        .arg(Argument::one_or_more("numbers").validator(validate_type::<i64>()))
+        .option(
+            CommandOption::new("times")
+                .alias("t")
+                .description("Number of times to sum the numbers")
+                .arg(
+                    Argument::with_name("times")
+                        .validator(validate_type::<u8>())
+                        .validation_error("expected a number from 0 to 255")
+                        .default(1),
+                ),
+        )
+        .handler(|opts, args| {
+            let times = opts.convert::<u8>("times")?;
+            let total = args.convert_all::<i64>("numbers")?.iter().sum::<i64>();
+            println!("{}", total * i64::from(times));
+            Ok(())
+        })
+}
+
+fn run() -> Command {
+    Command::new("run")
+        .description("Runs a script with the given arguments")
+        .arg(Argument::zero_or_more("args"))
+        .option(CommandOption::new("watch").alias("w").description("Runs on each change"))
+        .option(
+            CommandOption::new("env")
+                .description("Environment variables of the script")
+                .arg(Argument::one_or_more("vars")),
+        )
+        .handler(|opts, args| {
+            if opts.contains("watch") {
+                println!("watching");
+            }
+
+            if let Some(vars) = opts.get_arg("env") {
+                println!("env: {}", vars.get_values().join(" "));
+            }
+
+            println!("args: {:?}", args.get("args").unwrap().get_values());
+            Ok(())
+        })
+}
+
+fn exec() -> Command {
+    Command::new("exec")
+        .description("Executes a command, `--` is passed as is")
+        .arg(Argument::zero_or_more("args"))
+        .option(CommandOption::new("shell").arg(Argument::one_or_more("shell")))
+        .treat_eoo_as_value(true)
+        .handler(|opts, args| {
+            if let Some(shell) = opts.get_arg("shell") {
+                println!("shell: {:?}", shell.get_values());
+            }
+
+            println!("args: {:?}", args.get("args").unwrap().get_values());
+            Ok(())
+        })
+}
+
+fn cat() -> Command {
+    Command::new("cat")
+        .description("Prints a file, `-` is the standard input")
+        .arg(Argument::zero_or_one("file"))
+        .option(CommandOption::new("input").alias("i").arg(Argument::with_name("input")))
+        .handler(|opts, args| {
+            if let Some(input) = opts.get_arg("input") {
+                println!("input: {}", input.get_values()[0]);
+            }
+
+            match args.get("file").unwrap().get_values().first() {
+                Some(file) => println!("file: {}", file),
+                None => println!("file: none"),
+            }
+            Ok(())
+        })
+}
+
+fn publish() -> Command {
+    Command::new("publish")
+        .description("Publishes the package")
+        .option(
+            CommandOption::new("token")
+                .description("Token of the registry")
+                .required(true)
+                .arg(Argument::with_name("token").allow_empty_values(false)),
+        )
+        .option(
+            CommandOption::new("dry-run")
+                .description("Checks the package without publishing it")
+                .conflicts_with("force"),
+        )
+        .option(CommandOption::new("force").description("Publishes even if the version exists"))
+        .help(
+            "Publishes the package to the registry.
+
+EXAMPLES:
+   kitchen_sink publish --token abc123
+   kitchen_sink publish --token abc123 --dry-run",
+        )
+        .handler(|opts, _| {
+            if opts.contains("dry-run") {
+                println!("checked the package");
+            } else {
+                println!("published the package");
+            }
+            Ok(())
+        })
+}
+
+fn config() -> Command {
+    let key = || {
+        Argument::with_name("key")
+            .description("the name of the setting")
+            .valid_values(["editor", "theme"])
+    };
+
+    Command::new("config")
+        .description("Manages the configuration")
+        .subcommand(
+            Command::new("get")
+                .description("Prints a setting")
+                .arg(key())
+                .handler(|_, args| {
+                    let key = args.convert::<String>("key")?;
+                    let value = if key == "editor" { "vim" } else { "dark" };
+                    println!("{} = {}", key, value);
+                    Ok(())
+                }),
+        )
+        .subcommand(
+            Command::new("set")
+                .description("Sets a setting")
+                .arg(key())
+                .arg(Argument::with_name("value"))
+                .handler(|_, args| {
+                    let key = args.convert::<String>("key")?;
+                    let value = args.convert::<String>("value")?;
+                    println!("{} = {}", key, value);
+                    Ok(())
+                }),
+        )
+}
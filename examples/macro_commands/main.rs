@@ -0,0 +1,72 @@
+use clapi::macros::*;
+
+#[derive(Clone)]
+struct Database(String);
+
+#[derive(Default)]
+struct Counter(u32);
+
+// Commands declared with the macros, `tests/macro_commands.rs` runs them through
+// a table of invocations to check the generated code at run time.
+#[command(version = 1.0)]
+fn app() {
+    // `db` is provided with `clapi::provide` and `counter` uses `Default::default()`
+    #[subcommand]
+    #[option(db, skip)]
+    #[option(counter, skip = "default")]
+    #[arg(key)]
+    fn get(db: Database, mut counter: Counter, key: String) {
+        counter.0 += 1;
+        println!("{}/{} {}", db.0, key, counter.0);
+    }
+
+    // `color` is `None` if absent, `--no-color` gives `Some(false)`
+    #[subcommand]
+    #[option(color)]
+    fn paint(color: Option<bool>) {
+        println!("{:?}", color);
+    }
+
+    // Each value of `pair` is an argument named after its `value_names`
+    #[subcommand(description = "Replaces a value")]
+    #[option(pair, min = 2, max = 2, value_names = "FROM", "TO", description = "Values to replace")]
+    fn replace(pair: [String; 2]) {
+        println!("{} -> {}", pair[0], pair[1]);
+    }
+
+    // Answers `--version` with its own version instead of the version of `app`
+    #[subcommand(version = "2.0.1")]
+    fn release() {
+        println!("released");
+    }
+
+    // The option names follow the `rename_all` of each subcommand
+    #[subcommand(rename_all = "kebab-case")]
+    #[option(dry_run)]
+    fn kebab(dry_run: bool) {
+        println!("{}", dry_run);
+    }
+
+    #[subcommand(rename_all = "snake_case")]
+    #[option(dry_run)]
+    fn snake(dry_run: bool) {
+        println!("{}", dry_run);
+    }
+
+    #[subcommand(rename_all = "lowercase")]
+    #[option(dry_run)]
+    fn lower(dry_run: bool) {
+        println!("{}", dry_run);
+    }
+
+    #[subcommand(rename_all = "SCREAMING_SNAKE_CASE")]
+    #[option(dry_run)]
+    fn screaming(dry_run: bool) {
+        println!("{}", dry_run);
+    }
+}
+
+fn main() {
+    clapi::provide(Database("localhost".to_owned()));
+    app();
+}
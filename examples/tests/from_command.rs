@@ -0,0 +1,82 @@
+use clapi::macros::*;
+use clapi::ErrorKind;
+
+#[derive(Debug, FromCommand)]
+#[command_attr(description = "Sums the values")]
+struct Sum {
+    #[command_attr(alias = "t", default = 1, description = "Times to repeat")]
+    times: u32,
+    #[command_attr(alias = "v")]
+    verbose: bool,
+    #[command_attr(trim = true)]
+    label: Option<String>,
+    #[command_attr(positional, min = 1)]
+    values: Vec<i64>,
+}
+
+#[derive(Debug, FromCommand)]
+#[command_attr(rename_all = "kebab-case", summary = "Renamed fields")]
+struct Renamed {
+    dry_run: bool,
+    #[command_attr(name = "out")]
+    output_dir: Option<String>,
+}
+
+#[derive(Debug, FromCommand)]
+struct Paint {
+    color: Option<bool>,
+}
+
+#[test]
+fn from_command_test() {
+    let sum = Sum::parse_from(vec!["--verbose", "-t", "3", "1", "2", "3"]).unwrap();
+    assert_eq!(sum.times, 3);
+    assert!(sum.verbose);
+    assert_eq!(sum.label, None);
+    assert_eq!(sum.values, vec![1, 2, 3]);
+
+    let sum = Sum::parse_from(vec!["--label", " total  ", "10"]).unwrap();
+    assert_eq!(sum.times, 1);
+    assert!(!sum.verbose);
+    assert_eq!(sum.label.as_deref(), Some("total"));
+    assert_eq!(sum.values, vec![10]);
+}
+
+#[test]
+fn from_command_error_test() {
+    let error = Sum::parse_from(vec!["-t", "x", "1"]).unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::Other);
+
+    let error = Sum::parse_from(Vec::<&str>::new()).unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::InvalidArgumentCount);
+}
+
+#[test]
+fn parse_from_test() {
+    let sum = clapi::parse_from::<Sum, _, _>(vec!["-t", "2", "4"]).unwrap();
+    assert_eq!(sum.times, 2);
+    assert_eq!(sum.values, vec![4]);
+
+    let error = clapi::parse_from::<Sum, _, _>(vec!["--help"]).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::DisplayHelp(_)));
+}
+
+#[test]
+fn from_command_rename_all_test() {
+    let command = Renamed::command();
+    assert_eq!(command.get_summary(), Some("Renamed fields"));
+    assert!(command.get_options().contains("dry-run"));
+    assert!(command.get_options().contains("out"));
+
+    let renamed = Renamed::parse_from(vec!["--dry-run", "--out", "target"]).unwrap();
+    assert!(renamed.dry_run);
+    assert_eq!(renamed.output_dir.as_deref(), Some("target"));
+}
+
+#[test]
+fn from_command_tri_state_test() {
+    assert_eq!(Paint::parse_from(Vec::<&str>::new()).unwrap().color, None);
+    assert_eq!(Paint::parse_from(vec!["--color"]).unwrap().color, Some(true));
+    assert_eq!(Paint::parse_from(vec!["--no-color"]).unwrap().color, Some(false));
+    assert!(Paint::parse_from(vec!["--color", "--no-color"]).is_err());
+}
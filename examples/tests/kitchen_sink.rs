@@ -0,0 +1,117 @@
+use std::process::Command;
+
+// An invocation of the `kitchen_sink` binary and its expected output,
+// `stdout` must be equal and `stderr` must be contained in the output, or be empty if `""`.
+struct Case {
+    args: &'static [&'static str],
+    code: i32,
+    stdout: &'static str,
+    stderr: &'static str,
+}
+
+const fn ok(args: &'static [&'static str], stdout: &'static str) -> Case {
+    Case { args, code: 0, stdout, stderr: "" }
+}
+
+// Usage errors are written to `stderr` and exit with status 2
+const fn usage(args: &'static [&'static str], stderr: &'static str) -> Case {
+    Case { args, code: 2, stdout: "", stderr }
+}
+
+// Validation errors are written to `stderr` and exit with status 1
+const fn invalid(args: &'static [&'static str], stderr: &'static str) -> Case {
+    Case { args, code: 1, stdout: "", stderr }
+}
+
+const CASES: &[Case] = &[
+    // Defaults, flags and global options
+    ok(&["add", "serde"], "added serde@latest\n"),
+    ok(&["add", "--tag", "1.0.2", "--dev", "serde"], "added serde@1.0.2 (dev)\n"),
+    ok(&["--verbose", "add", "serde"], "color: auto\nadded serde@latest\n"),
+    ok(&["add", "--color", "never", "--verbose", "serde"], "color: never\nadded serde@latest\n"),
+    // Validators and valid values
+    invalid(&["add", "--tag", "x", "serde"], "in option '--tag': invalid version `x`"),
+    invalid(&["add", "--color", "blue", "serde"], "expected auto, always, never but was blue"),
+    usage(&["add"], "'name' requires 1 value but none was passed"),
+    // Options after the arguments are taken as values
+    usage(&["add", "serde", "--tag", "1.0.2"], "'name' requires 1 value but was 3"),
+    ok(&["remove", "a", "b", "c"], "removed a\nremoved b\nremoved c\n"),
+    usage(&["remove"], "'names' requires 1 or more values but none was passed"),
+    ok(&["sum", "1", "2", "3"], "6\n"),
+    ok(&["sum", "-t", "3", "1", "2", "3"], "18\n"),
+    ok(&["sum", "-t=2", "1"], "2\n"),
+    ok(&["sum", "-t", "2", "-5", "10", "-2"], "6\n"),
+    invalid(&["sum", "--times", "300", "1"], "in option '--times': expected a number from 0 to 255"),
+    invalid(&["sum", "1", "x"], "invalid value for argument 'numbers'"),
+    // `--` handling
+    ok(&["sum", "--", "-1", "-2"], "-3\n"),
+    ok(&["run", "--watch", "--", "--times", "3", "x"], "watching\nargs: [\"--times\", \"3\", \"x\"]\n"),
+    ok(&["run", "--", "--", "-a"], "args: [\"--\", \"-a\"]\n"),
+    ok(&["run", "--env", "A=1", "B=2", "--", "app"], "env: A=1 B=2\nargs: [\"app\"]\n"),
+    ok(&["run"], "args: []\n"),
+    invalid(&["run", "a", "--", "b"], "there is no option that expect arguments but `a` was found"),
+    ok(&["exec", "--", "a", "--", "b"], "args: [\"--\", \"a\", \"--\", \"b\"]\n"),
+    ok(&["exec", "--shell", "bash", "--", "-c", "ls"], "shell: [\"bash\", \"--\", \"-c\", \"ls\"]\nargs: []\n"),
+    ok(&["cat", "-"], "file: -\n"),
+    ok(&["cat", "-i", "-", "--", "-"], "input: -\nfile: -\n"),
+    ok(&["cat"], "file: none\n"),
+    // Required, conflicting and non-empty options
+    ok(&["publish", "--token", "abc"], "published the package\n"),
+    ok(&["publish", "--token", "abc", "--dry-run"], "checked the package\n"),
+    usage(&["publish"], "'token' is required"),
+    invalid(&["publish", "--token="], "value for 'token' cannot be empty"),
+    invalid(&["publish", "--token", ""], "value for 'token' cannot be empty"),
+    usage(&["publish", "--token", "abc", "--dry-run", "--force"], "'--dry-run' cannot be used with '--force'"),
+    // Nested subcommands
+    ok(&["config", "get", "editor"], "editor = vim\n"),
+    ok(&["config", "set", "theme", "light"], "theme = light\n"),
+    invalid(&["config", "get", "font"], "expected editor, theme but was font"),
+    usage(&["config"], "'config' requires a subcommand"),
+    usage(&[], "'kitchen_sink' requires a subcommand"),
+    // Help, version and suggestions
+    ok(&["--version"], "kitchen_sink 1.0.0\n"),
+    ok(
+        &["help", "publish"],
+        "Publishes the package to the registry.
+
+EXAMPLES:
+   kitchen_sink publish --token abc123
+   kitchen_sink publish --token abc123 --dry-run
+",
+    ),
+    usage(&["ad"], "Did you mean `add`?"),
+    usage(&["add", "--colr", "never", "serde"], "unexpected option: '--colr'"),
+];
+
+#[test]
+fn kitchen_sink_test() {
+    let mut failures = Vec::new();
+
+    for case in CASES {
+        let output = Command::new(env!("CARGO_BIN_EXE_kitchen_sink"))
+            .args(case.args)
+            // The help is wrapped to `COLUMNS`, so is fixed to not depend on the terminal
+            .env("COLUMNS", "100")
+            .output()
+            .expect("failed to run `kitchen_sink`");
+
+        let code = output.status.code();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let stderr_matches = if case.stderr.is_empty() {
+            stderr.is_empty()
+        } else {
+            stderr.contains(case.stderr)
+        };
+
+        if code != Some(case.code) || stdout != case.stdout || !stderr_matches {
+            failures.push(format!(
+                "kitchen_sink {:?}\n  status: {:?} (expected {})\n  stdout: {:?}\n  stderr: {:?}",
+                case.args, code, case.code, stdout, stderr
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{} of {} invocations failed:\n{}", failures.len(), CASES.len(), failures.join("\n"));
+}
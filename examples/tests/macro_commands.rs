@@ -0,0 +1,98 @@
+use std::process::Command;
+
+// An invocation of the `macro_commands` binary and its expected output,
+// `stdout` must be equal and `stderr` must be contained in the output, or be empty if `""`.
+struct Case {
+    args: &'static [&'static str],
+    code: i32,
+    stdout: &'static str,
+    stderr: &'static str,
+}
+
+const fn ok(args: &'static [&'static str], stdout: &'static str) -> Case {
+    Case { args, code: 0, stdout, stderr: "" }
+}
+
+// Usage errors are written to `stderr` and exit with status 2
+const fn usage(args: &'static [&'static str], stderr: &'static str) -> Case {
+    Case { args, code: 2, stdout: "", stderr }
+}
+
+const CASES: &[Case] = &[
+    // Skipped parameters are not options and take its value when the handler runs
+    ok(&["get", "users"], "localhost/users 1\n"),
+    usage(&["get", "--db", "remote", "users"], "unexpected option: '--db'"),
+    usage(&["get", "--counter", "2", "users"], "unexpected option: '--counter'"),
+    // `Option<bool>` flags are tri-state
+    ok(&["paint"], "None\n"),
+    ok(&["paint", "--color"], "Some(true)\n"),
+    ok(&["paint", "--color=true"], "Some(true)\n"),
+    ok(&["paint", "--color=false"], "Some(false)\n"),
+    ok(&["paint", "--no-color"], "Some(false)\n"),
+    usage(&["paint", "--color", "--no-color"], "--no-color"),
+    // Subcommands answer `--version` with its own version or the version of the root
+    ok(&["--version"], "macro_commands 1.0\n"),
+    ok(&["release", "--version"], "release 2.0.1\n"),
+    ok(&["paint", "--version"], "paint 1.0\n"),
+    ok(&["release"], "released\n"),
+    // Each `rename_all` case style accepts the renamed option
+    ok(&["kebab", "--dry-run"], "true\n"),
+    ok(&["snake", "--dry_run"], "true\n"),
+    ok(&["lower", "--dryrun"], "true\n"),
+    ok(&["screaming", "--DRY_RUN"], "true\n"),
+    ok(&["kebab"], "false\n"),
+    usage(&["kebab", "--dry_run"], "unexpected option: '--dry_run'"),
+    usage(&["snake", "--dry-run"], "unexpected option: '--dry-run'"),
+    usage(&["screaming", "--dry_run"], "unexpected option: '--dry_run'"),
+    // The `value_names` are shown in the usage and the options
+    ok(&["replace", "--pair", "a", "b"], "a -> b\n"),
+    ok(
+        &["replace", "--help"],
+        concat!(
+            "replace 1.0\n",
+            "   Replaces a value\n",
+            "\n",
+            "USAGE:\n",
+            "   replace [OPTIONS] --pair <FROM> <TO>\n",
+            "\n",
+            "OPTIONS:\n",
+            "   --pair <FROM> <TO>     Values to replace\n",
+            "   -v, --version          Shows the version of the command\n",
+            "\n",
+            "Use 'macro_commands help <subcommand>' for more information about a command.\n",
+        ),
+    ),
+];
+
+#[test]
+fn macro_commands_test() {
+    let mut failures = Vec::new();
+
+    for case in CASES {
+        let output = Command::new(env!("CARGO_BIN_EXE_macro_commands"))
+            .args(case.args)
+            // The help is wrapped to `COLUMNS`, so is fixed to not depend on the terminal
+            .env("COLUMNS", "100")
+            .output()
+            .expect("failed to run `macro_commands`");
+
+        let code = output.status.code();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let stderr_matches = if case.stderr.is_empty() {
+            stderr.is_empty()
+        } else {
+            stderr.contains(case.stderr)
+        };
+
+        if code != Some(case.code) || stdout != case.stdout || !stderr_matches {
+            failures.push(format!(
+                "macro_commands {:?}\n  status: {:?} (expected {})\n  stdout: {:?}\n  stderr: {:?}",
+                case.args, code, case.code, stdout, stderr
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{} of {} invocations failed:\n{}", failures.len(), CASES.len(), failures.join("\n"));
+}
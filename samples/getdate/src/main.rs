@@ -13,6 +13,7 @@ use clapi::macros::*;
 #[option(
     format,
     alias = "f",
+    env = "GETDATE_FORMAT",
     description = "The format to print the date and time in"
 )]
 #[option(
@@ -0,0 +1,8 @@
+use clapi::macros::*;
+
+#[command]
+#[arg(source, name="file")]
+#[arg(target, name="file")]
+fn copy(source: String, target: String){}
+
+fn main(){}
@@ -0,0 +1,8 @@
+use clapi::macros::*;
+
+#[command(rename_all="kebab-case")]
+#[option(out_dir)]
+#[option(output, alias="out-dir")]
+fn copy(out_dir: String, output: String){}
+
+fn main(){}
@@ -0,0 +1,12 @@
+use clapi::macros::*;
+
+#[command]
+fn app(){
+    #[subcommand]
+    fn list(){}
+
+    #[subcommand(name="list")]
+    fn show(){}
+}
+
+fn main(){}
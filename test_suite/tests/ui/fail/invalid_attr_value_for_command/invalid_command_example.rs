@@ -0,0 +1,6 @@
+use clapi::macros::*;
+
+#[command(example=("app --help"))]
+fn app(){}
+
+fn main(){}
@@ -0,0 +1,6 @@
+use clapi::macros::*;
+
+#[command(rename_all="camelCase")]
+fn app(){}
+
+fn main(){}
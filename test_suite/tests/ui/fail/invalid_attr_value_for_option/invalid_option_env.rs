@@ -0,0 +1,7 @@
+use clapi::macros::*;
+
+#[command]
+#[option(token, env=10)]
+fn app(token: String){}
+
+fn main(){}
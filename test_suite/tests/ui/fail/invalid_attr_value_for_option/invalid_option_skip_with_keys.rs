@@ -0,0 +1,7 @@
+use clapi::macros::*;
+
+#[command]
+#[option(x, skip, description="a value")]
+fn app(x: i64){}
+
+fn main(){}
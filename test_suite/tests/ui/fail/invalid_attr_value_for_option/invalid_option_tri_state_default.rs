@@ -0,0 +1,7 @@
+use clapi::macros::*;
+
+#[command]
+#[option(color, default="red")]
+fn test(color: Option<bool>){}
+
+fn main(){}
@@ -0,0 +1,5 @@
+use clapi::macros::*;
+
+#[command]
+#[arg(values, min=1, max=2, default=1,2,3)]
+fn main(values: Vec<i64>){}
@@ -0,0 +1,5 @@
+use clapi::macros::*;
+
+#[command]
+#[option(range, min=2, max=2, default=1)]
+fn main(range: Vec<i64>){}
@@ -0,0 +1,7 @@
+use clapi::macros::*;
+
+#[command]
+#[option(replace, min=2, max=2, value_names="FROM", "TO", default="a", "b")]
+fn test(replace: Vec<String>){}
+
+fn main(){}
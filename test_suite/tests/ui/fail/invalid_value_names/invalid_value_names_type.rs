@@ -0,0 +1,7 @@
+use clapi::macros::*;
+
+#[command]
+#[option(replace, value_names="FROM")]
+fn test(replace: String){}
+
+fn main(){}
@@ -0,0 +1,8 @@
+use clapi::macros::*;
+
+mod files {
+    pub fn list(){}
+}
+
+#[command(subcommands(files::list))]
+fn main(){}
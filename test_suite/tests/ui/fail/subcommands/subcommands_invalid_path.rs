@@ -0,0 +1,6 @@
+use clapi::macros::*;
+
+#[command(subcommands("list"))]
+fn app(){}
+
+fn main(){}
@@ -0,0 +1,8 @@
+use clapi::macros::*;
+
+#[command(deny_unused = true)]
+#[option(times, default=1)]
+#[option(color, allow_unused)]
+fn main(times: u32, color: String, verbose: bool) {
+    println!("{}", verbose);
+}
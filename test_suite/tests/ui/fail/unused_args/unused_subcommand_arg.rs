@@ -0,0 +1,10 @@
+use clapi::macros::*;
+
+#[command(deny_unused = true)]
+fn main() {
+    #[subcommand]
+    #[arg(values)]
+    fn sum(values: Vec<i64>) {
+        println!("sum");
+    }
+}
@@ -17,6 +17,7 @@ fn main(){
             (requires_assign => true)
             (@arg =>
                 (count => 1)
+                (allow_empty => false)
                 (type => u64)
             )
         )
@@ -31,7 +32,8 @@ fn main(){
             (error => "expected integer")
         )
         (@subcommand "author" =>
-            (description => "Shows the authors")
+            (description => "Shows the authors. Each author is shown in its own line.")
+            (summary => "Shows the authors")
             (hidden => false)
             (@arg "count" =>
                 (description => "Number of authors to show")
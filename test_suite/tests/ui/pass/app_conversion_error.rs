@@ -0,0 +1,31 @@
+fn main() {
+    let mut app = clapi::app! { MyApp =>
+        (@subcommand sum =>
+            (@option times =>
+                (@arg times => (type => i64))
+            )
+            (@option scale =>
+                (@arg scale => (type => i64) (count => 0..))
+            )
+            (@arg values => (count => 1..) (type => i64))
+            (handler (times: i32, scale: Vec<i8>, ...values: Vec<i32>) => {
+                let _ = (times, scale, values);
+            })
+        )
+    };
+
+    let error = app.run_from(vec!["sum", "--times", "3000000000", "1"]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected error: in command 'sum': cannot convert value of '--times' to i32: \
+        failed to parse `\"3000000000\"` to `i32`"
+    );
+
+    let error = app.run_from(vec!["sum", "--times", "1", "--scale", "300", "--", "1"]).unwrap_err();
+    assert!(error.to_string().contains("in command 'sum': cannot convert value of '--scale' to i8"));
+
+    let error = app.run_from(vec!["sum", "--times", "1", "--scale", "1", "--", "3000000000"]).unwrap_err();
+    assert!(error.to_string().contains("in command 'sum': cannot convert value of 'values' to i32"));
+
+    assert!(app.run_from(vec!["sum", "--times", "2", "--scale", "3", "--", "1", "2"]).is_ok());
+}
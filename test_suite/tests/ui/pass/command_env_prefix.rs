@@ -0,0 +1,9 @@
+use clapi::macros::*;
+
+#[command(rename_all="kebab-case", env_prefix="MYAPP")]
+#[option(dry_run)]
+#[option(log_level, env="LOG_LEVEL", default="info")]
+#[option(secret, env=false, default="none")]
+fn app(dry_run: bool, log_level: String, secret: String){}
+
+fn main(){}
@@ -0,0 +1,12 @@
+use clapi::macros::*;
+
+#[command(
+    example=("app sum 1 2 3 --precision=2", "Sum with two decimals"),
+    example=("app --help", "Shows the help")
+)]
+fn app(){
+    #[subcommand(example=("app sum 1 2", "Sum two numbers"))]
+    fn sum(){}
+}
+
+fn main(){}
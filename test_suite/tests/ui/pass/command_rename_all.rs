@@ -0,0 +1,19 @@
+use clapi::macros::*;
+
+#[command(rename_all="kebab-case")]
+#[option(dry_run)]
+#[option(max_count, default=1)]
+#[option(log_level, name="LOG", default="info")]
+#[option(out_dir, global=true, default=".")]
+fn app(dry_run: bool, max_count: u32, log_level: String, out_dir: String){
+    #[subcommand]
+    #[option(out_dir, from_global=true)]
+    #[option(page_size, default=10)]
+    fn list(out_dir: String, page_size: u32){}
+
+    #[subcommand(rename_all="SCREAMING_SNAKE_CASE")]
+    #[option(page_size, default=10)]
+    fn show(page_size: u32){}
+}
+
+fn main(){}
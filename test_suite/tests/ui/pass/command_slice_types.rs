@@ -0,0 +1,13 @@
+use clapi::macros::*;
+use std::path::PathBuf;
+
+#[command]
+#[option(numbers)]
+#[option(paths)]
+#[arg(values)]
+fn test(numbers: &[i64], paths: &[PathBuf], values: &mut [String]) {
+    values.sort();
+    println!("{:?} {:?} {:?}", numbers, paths, values);
+}
+
+fn main(){}
@@ -0,0 +1,32 @@
+use clapi::macros::*;
+
+mod files {
+    use clapi::macros::*;
+
+    #[subcommand(description="List the files", subcommands(sort))]
+    #[option(all, alias="a")]
+    pub fn list(all: bool){
+        println!("{}", all);
+    }
+
+    #[subcommand(hidden)]
+    #[arg(values, min=1)]
+    pub fn sort(values: Vec<String>){
+        println!("{:?}", values);
+    }
+}
+
+#[subcommand(rename_all="kebab-case")]
+fn show_version(short_version: bool){
+    println!("{}", short_version);
+}
+
+#[command(subcommands(files::list, show_version))]
+fn main(){
+    #[subcommand]
+    fn inner(){
+        println!("inner");
+    }
+
+    println!("main");
+}
@@ -0,0 +1,7 @@
+use clapi::macros::*;
+
+#[command]
+#[option(enable, default=true)]
+fn test(color: Option<bool>, enable: Option<bool>){}
+
+fn main(){}
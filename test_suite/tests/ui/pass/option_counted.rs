@@ -0,0 +1,16 @@
+use clapi::macros::*;
+
+#[command]
+#[option(verbose, alias="v", counted, global=true)]
+#[option(quiet, alias="q", counted=true)]
+fn app(verbose: u8, quiet: usize) {
+    println!("{} {}", verbose, quiet);
+
+    #[subcommand]
+    #[option(verbose, from_global=true)]
+    fn build(verbose: u32) {
+        println!("{}", verbose);
+    }
+}
+
+fn main(){}
@@ -0,0 +1,20 @@
+use clapi::macros::*;
+
+#[command]
+#[option(debug_internal, hidden=true)]
+#[option(trace, hidden)]
+#[option(verbose)]
+fn app(debug_internal: bool, trace: bool, verbose: bool) {
+    println!("{} {} {}", debug_internal, trace, verbose);
+
+    #[subcommand(hidden)]
+    #[option(force, hidden)]
+    fn secret(force: bool) {
+        println!("{}", force);
+    }
+
+    #[subcommand(hidden=true)]
+    fn other() {}
+}
+
+fn main(){}
@@ -0,0 +1,27 @@
+use clapi::macros::*;
+
+#[derive(Clone)]
+struct Database(String);
+
+#[derive(Default)]
+struct Counter(u32);
+
+#[command]
+#[option(db, skip)]
+#[option(counter, skip="default")]
+#[arg(values)]
+fn app(db: Database, mut counter: Counter, values: Vec<String>) {
+    counter.0 += 1;
+    println!("{} {} {:?}", db.0, counter.0, values);
+
+    #[subcommand]
+    #[arg(db, skip)]
+    #[arg(key)]
+    fn get(db: Database, key: String) {
+        println!("{} {}", db.0, key);
+    }
+}
+
+fn main(){
+    clapi::provide(Database("localhost".to_owned()));
+}
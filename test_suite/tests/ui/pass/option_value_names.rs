@@ -0,0 +1,8 @@
+use clapi::macros::*;
+
+#[command]
+#[option(replace, min=2, max=2, value_names="FROM", "TO")]
+#[option(pair, min=2, max=2, value_names="KEY", "VALUE")]
+fn test(replace: Vec<String>, pair: [String; 2]){}
+
+fn main(){}
@@ -0,0 +1,27 @@
+use clapi::macros::*;
+use std::path::PathBuf;
+
+#[command]
+fn test() {
+    #[subcommand]
+    #[option(numbers)]
+    fn sum(numbers: &[i64]) {
+        println!("{}", numbers.iter().sum::<i64>());
+    }
+
+    #[subcommand]
+    #[arg(values)]
+    fn sort(mut values: &mut [String]) {
+        values.sort();
+        values = &mut values[..];
+        println!("{:?}", values);
+    }
+
+    #[subcommand]
+    #[arg(files)]
+    fn paths(files: &[PathBuf]) {
+        println!("{:?}", files);
+    }
+}
+
+fn main(){}
@@ -0,0 +1,12 @@
+use clapi::macros::*;
+
+#[command(version=1.0)]
+fn app(){
+    #[subcommand(version="2.0.1")]
+    fn sub(){}
+
+    #[subcommand]
+    fn other(){}
+}
+
+fn main(){}